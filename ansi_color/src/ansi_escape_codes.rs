@@ -80,11 +80,89 @@ pub mod sgr_code_impl {
     }
 }
 
+/// CSI (control sequence introducer) sequences, other than [SgrCode], eg: to show or hide
+/// the cursor, or switch to the alternate screen buffer.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CsiSequence {
+    /// DECSET: enable a DEC private mode, eg: `\x1b[?25h` to show the cursor.
+    DecSet(DecPrivateMode),
+    /// DECRST: disable a DEC private mode, eg: `\x1b[?25l` to hide the cursor.
+    DecReset(DecPrivateMode),
+}
+
+/// The DEC private modes that can be enabled w/ [CsiSequence::DecSet] and disabled w/
+/// [CsiSequence::DecReset]. Use [DecPrivateMode::Other] for modes that aren't listed here.
+/// More info:
+/// - <https://vt100.net/docs/vt510-rm/DECSET.html>
+/// - <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h2-Functions-using-CSI-_-ordered-by-the-final-character_s_>
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DecPrivateMode {
+    /// DECCKM (1): the cursor keys send application sequences.
+    ApplicationCursorKeys,
+    /// DECAWM (7): wrap to the next line when a character is written to the last column.
+    AutoWrap,
+    /// DECTCEM (25): the cursor is visible.
+    ShowCursor,
+    /// 1000: report mouse button presses and releases.
+    MouseTracking,
+    /// 1004: report when the terminal gains or loses focus.
+    FocusEvents,
+    /// 1006: report mouse events in the SGR format.
+    MouseSgrFormat,
+    /// 1049: save the cursor, and switch to the (cleared) alternate screen buffer.
+    AlternateScreenBuffer,
+    /// 2004: wrap pasted text in `\x1b[200~` and `\x1b[201~`.
+    BracketedPaste,
+    /// Any other mode, by its number.
+    Other(u16),
+}
+
+impl DecPrivateMode {
+    #[rustfmt::skip]
+    pub fn as_u16(&self) -> u16 {
+        match self {
+            DecPrivateMode::ApplicationCursorKeys => 1,
+            DecPrivateMode::AutoWrap              => 7,
+            DecPrivateMode::ShowCursor            => 25,
+            DecPrivateMode::MouseTracking         => 1000,
+            DecPrivateMode::FocusEvents           => 1004,
+            DecPrivateMode::MouseSgrFormat        => 1006,
+            DecPrivateMode::AlternateScreenBuffer => 1049,
+            DecPrivateMode::BracketedPaste        => 2004,
+            DecPrivateMode::Other(mode)           => *mode,
+        }
+    }
+}
+
+pub mod csi_sequence_impl {
+    use std::fmt::{Display, Formatter, Result};
+
+    use super::{sgr_code_impl::CSI, CsiSequence};
+
+    impl Display for CsiSequence {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            write!(f, "{}", make_csi_sequence(*self))
+        }
+    }
+
+    pub const DEC_PRIVATE_MODE_PREFIX: &str = "?";
+    pub const DEC_SET: &str = "h";
+    pub const DEC_RESET: &str = "l";
+
+    #[rustfmt::skip]
+    fn make_csi_sequence(csi_sequence: CsiSequence) -> String {
+        match csi_sequence {
+            CsiSequence::DecSet(mode)   => format!("{CSI}{DEC_PRIVATE_MODE_PREFIX}{}{DEC_SET}", mode.as_u16()),
+            CsiSequence::DecReset(mode) => format!("{CSI}{DEC_PRIVATE_MODE_PREFIX}{}{DEC_RESET}", mode.as_u16()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
 
-    use super::SgrCode;
+    use super::{CsiSequence, DecPrivateMode, SgrCode};
 
     #[test]
     fn bold() {
@@ -175,4 +253,40 @@ mod tests {
         let sgr_code = SgrCode::BackgroundRGB(175, 215, 135);
         assert_eq!(sgr_code.to_string(), "\x1b[48;2;175;215;135m");
     }
+
+    #[test]
+    fn dec_set_show_cursor() {
+        let csi_sequence = CsiSequence::DecSet(DecPrivateMode::ShowCursor);
+        assert_eq!(csi_sequence.to_string(), "\x1b[?25h");
+    }
+
+    #[test]
+    fn dec_reset_show_cursor() {
+        let csi_sequence = CsiSequence::DecReset(DecPrivateMode::ShowCursor);
+        assert_eq!(csi_sequence.to_string(), "\x1b[?25l");
+    }
+
+    #[test]
+    fn dec_set_alternate_screen_buffer() {
+        let csi_sequence = CsiSequence::DecSet(DecPrivateMode::AlternateScreenBuffer);
+        assert_eq!(csi_sequence.to_string(), "\x1b[?1049h");
+    }
+
+    #[test]
+    fn dec_reset_alternate_screen_buffer() {
+        let csi_sequence = CsiSequence::DecReset(DecPrivateMode::AlternateScreenBuffer);
+        assert_eq!(csi_sequence.to_string(), "\x1b[?1049l");
+    }
+
+    #[test]
+    fn dec_set_bracketed_paste() {
+        let csi_sequence = CsiSequence::DecSet(DecPrivateMode::BracketedPaste);
+        assert_eq!(csi_sequence.to_string(), "\x1b[?2004h");
+    }
+
+    #[test]
+    fn dec_set_other() {
+        let csi_sequence = CsiSequence::DecSet(DecPrivateMode::Other(1002));
+        assert_eq!(csi_sequence.to_string(), "\x1b[?1002h");
+    }
 }
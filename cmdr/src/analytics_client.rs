@@ -72,6 +72,8 @@ pub mod config_folder {
     pub enum ConfigPaths {
         R3BLTopLevelFolderName,
         ProxyMachineIdFile,
+        GitiCommandHistoryFile,
+        EdiRecoveryFolder,
     }
 
     impl Display for ConfigPaths {
@@ -79,6 +81,8 @@ pub mod config_folder {
             let path = match self {
                 ConfigPaths::R3BLTopLevelFolderName => "r3bl-cmdr",
                 ConfigPaths::ProxyMachineIdFile => "id",
+                ConfigPaths::GitiCommandHistoryFile => "giti_history.json",
+                ConfigPaths::EdiRecoveryFolder => "edi_recovery",
             };
             write!(f, "{}", path)
         }
@@ -89,6 +93,17 @@ pub mod config_folder {
         path.join(ConfigPaths::ProxyMachineIdFile.to_string())
     }
 
+    /// This is where the history of the git commands that giti runs is stored.
+    pub fn get_giti_command_history_file_path(path: PathBuf) -> PathBuf {
+        path.join(ConfigPaths::GitiCommandHistoryFile.to_string())
+    }
+
+    /// This is where edi writes the unsaved content of its buffers, so that it can be
+    /// recovered after a crash.
+    pub fn get_edi_recovery_folder_path(path: PathBuf) -> PathBuf {
+        path.join(ConfigPaths::EdiRecoveryFolder.to_string())
+    }
+
     /// This is where the config folder is.
     pub fn try_get_config_folder_path() -> Option<PathBuf> {
         let home_config_folder_path = config_dir()?;
@@ -158,6 +173,25 @@ pub mod file_io {
         file.write_all(contents.as_bytes()).into_diagnostic()?;
         Ok(())
     }
+
+    /// Write the `contents` to a temporary file next to `path`, and then rename it to
+    /// `path`. If this is interrupted (eg: by a crash), then `path` is left as it was,
+    /// instead of being partially written.
+    pub fn try_write_file_contents_atomically(
+        path: &PathBuf,
+        contents: &str,
+    ) -> CommonResult<()> {
+        let mut temp_file_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_file_name.push(".tmp");
+        let temp_path = path.with_file_name(temp_file_name);
+        {
+            let mut file = File::create(&temp_path).into_diagnostic()?;
+            file.write_all(contents.as_bytes()).into_diagnostic()?;
+            file.sync_all().into_diagnostic()?;
+        }
+        fs::rename(&temp_path, path).into_diagnostic()?;
+        Ok(())
+    }
 }
 
 pub mod proxy_machine_id {
@@ -64,16 +64,23 @@ async fn main() -> CommonResult<()> {
                     "".to_string(),
                     AnalyticsAction::EdiFileNew,
                 );
-                launcher::run_app(None).await?;
+                launcher::run_app(None, cli_arg.zen_width, !cli_arg.no_open_link).await?;
             }
             1 => {
                 report_analytics::start_task_to_generate_event(
                     "".to_string(),
                     AnalyticsAction::EdiFileOpenSingle,
                 );
-                launcher::run_app(Some(cli_arg.file_paths[0].clone())).await?;
+                launcher::run_app(
+                    Some(cli_arg.file_paths[0].clone()),
+                    cli_arg.zen_width,
+                    !cli_arg.no_open_link,
+                )
+                .await?;
             }
             _ => {
+                let zen_width = cli_arg.zen_width;
+                let is_open_link_enabled = !cli_arg.no_open_link;
                 if let Some(file_path) =
                     edi_ui_templates::handle_multiple_files_not_supported_yet(cli_arg)
                 {
@@ -81,7 +88,8 @@ async fn main() -> CommonResult<()> {
                         "".to_string(),
                         AnalyticsAction::EdiFileOpenMultiple,
                     );
-                    launcher::run_app(Some(file_path)).await?;
+                    launcher::run_app(Some(file_path), zen_width, is_open_link_enabled)
+                        .await?;
                 }
             }
         }
@@ -177,6 +185,7 @@ pub mod edi_ui_templates {
 
 mod clap_config {
     use clap::{Args, Parser};
+    use r3bl_cmdr::edi::DEFAULT_ZEN_MODE_TEXT_COLUMN_WIDTH;
 
     /// More info: <https://docs.rs/clap/latest/clap/_derive/_tutorial/chapter_2/index.html>
     #[derive(Debug, Parser)]
@@ -197,6 +206,21 @@ mod clap_config {
         #[arg(name = "file paths")]
         pub file_paths: Vec<String>,
 
+        #[arg(
+            long,
+            short = 'z',
+            default_value_t = DEFAULT_ZEN_MODE_TEXT_COLUMN_WIDTH,
+            value_parser = clap::value_parser!(u16).range(1..),
+            help = "Width of the centered text column in zen mode (toggle it using Alt+Z)."
+        )]
+        pub zen_width: u16,
+
+        #[arg(
+            long,
+            help = "Disable opening the link under the caret in the default browser (using Alt+O)."
+        )]
+        pub no_open_link: bool,
+
         #[command(flatten)]
         pub global_options: GlobalOption,
     }
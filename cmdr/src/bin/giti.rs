@@ -21,12 +21,15 @@
 use clap::Parser;
 use r3bl_ansi_color::{AnsiStyledText, Style};
 use r3bl_cmdr::{color_constants::DefaultColors::{FrozenBlue, GuardsRed, MoonlightBlue},
-                giti::{get_giti_command_subcommand_names,
+                giti::{command_history,
+                       get_giti_command_subcommand_names,
                        giti_ui_templates,
                        single_select_instruction_header,
                        try_checkout_branch,
                        try_delete_branch,
                        try_make_new_branch,
+                       try_resolve_conflicts,
+                       try_show_command_history,
                        BranchSubcommand,
                        CLIArg,
                        CLICommand,
@@ -56,6 +59,10 @@ async fn main() -> CommonResult<()> {
             report_analytics::disable();
         }
 
+        if cli_arg.global_options.no_history {
+            command_history::disable();
+        }
+
         upgrade_check::start_task_to_check_for_updates();
         report_analytics::start_task_to_generate_event(
             "".to_string(),
@@ -132,6 +139,8 @@ pub fn try_run_command(
             },
             _ => user_typed_giti_branch(),
         },
+        CLICommand::Resolve {} => try_resolve_conflicts(),
+        CLICommand::History {} => try_show_command_history(),
         CLICommand::Commit {} => unimplemented!(),
         CLICommand::Remote {} => unimplemented!(),
     }
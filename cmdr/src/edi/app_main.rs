@@ -29,6 +29,7 @@ use r3bl_core::{call_if_true,
                 throws,
                 throws_with_return,
                 tui_styled_text,
+                tui_styled_texts,
                 tui_stylesheet,
                 ANSIBasicColor,
                 Ansi256GradientIndex,
@@ -40,16 +41,17 @@ use r3bl_core::{call_if_true,
                 CommonResult,
                 GradientGenerationPolicy,
                 Position,
+                RgbValue,
                 Size,
                 TextColorizationPolicy,
                 TuiColor,
-                TuiStyledTexts,
                 TuiStylesheet,
                 UnicodeString};
 use r3bl_macro::tui_style;
 use r3bl_tui::{box_end,
                box_props,
                box_start,
+               get_current_line_style,
                render_component_in_current_box,
                render_component_in_given_box,
                render_ops,
@@ -57,8 +59,11 @@ use r3bl_tui::{box_end,
                surface,
                App,
                BoxedSafeApp,
+               CheckboxToggleMode,
+               ClipboardExportMode,
                ComponentRegistry,
                ComponentRegistryMap,
+               CurrentLineHighlightMode,
                DialogBuffer,
                DialogChoice,
                DialogComponent,
@@ -67,32 +72,46 @@ use r3bl_tui::{box_end,
                EditMode,
                EditorComponent,
                EditorEngineConfig,
+               EventConsumption,
                EventPropagation,
                FlexBox,
                FlexBoxId,
                GlobalData,
                HasEditorBuffers,
                HasFocus,
+               HorizontalOverflowMarkersMode,
+               IndentStyle,
+               InlineDiagnosticsMode,
                InputEvent,
                Key,
                KeyPress,
                LayoutDirection,
                LayoutManagement,
                LineMode,
+               LineWrapMode,
+               LinkTarget,
+               ListRenumberingMode,
+               MinimapMode,
                ModifierKeysMask,
+               MultiCaretMode,
+               OverwriteMode,
                PerformPositioningAndSizing,
                RenderOp,
                RenderPipeline,
+               SpecialKey,
+               StatusBar,
+               StatusBarAlignment,
                Surface,
                SurfaceProps,
                SurfaceRender,
                SyntaxHighlightMode,
                TerminalWindowMainThreadSignal,
+               TypewriterScrollingMode,
                ZOrder,
                DEBUG_TUI_MOD};
 use tokio::sync::mpsc::Sender;
 
-use crate::edi::{file_utils, State};
+use crate::edi::{file_utils, recovery, State};
 
 /// Signals that can be sent to the app.
 #[derive(Default, Clone, Debug)]
@@ -100,6 +119,8 @@ use crate::edi::{file_utils, State};
 pub enum AppSignal {
     AskForFilenameToSaveFile,
     SaveFile,
+    /// Write (or remove) the crash recovery file, see [crate::edi::recovery].
+    WriteRecoveryFile,
     #[default]
     Noop,
 }
@@ -126,6 +147,7 @@ pub enum Id {
     StyleDialogTitle = 12,
     StyleDialogEditor = 13,
     StyleDialogResultsPanel = 14,
+    StyleZenModeMargin = 15,
 }
 
 mod id_impl {
@@ -140,6 +162,80 @@ mod id_impl {
     }
 }
 
+/// Hotkeys that are handled by the app before the input event is routed to the focused
+/// component.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GlobalHotkey {
+    SaveFile,
+    ToggleZenMode,
+    OpenFeedbackLink,
+    OpenLinkUnderCaret,
+    ToggleOutline,
+    CycleIndentStyle,
+}
+
+mod global_hotkey_impl {
+    use super::*;
+
+    impl GlobalHotkey {
+        pub fn try_from_input_event(input_event: &InputEvent) -> Option<Self> {
+            if input_event.matches_keypress(KeyPress::WithModifiers {
+                key: Key::Character('s'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            }) {
+                Some(GlobalHotkey::SaveFile)
+            } else if input_event.matches_keypress(KeyPress::WithModifiers {
+                key: Key::Character('z'),
+                mask: ModifierKeysMask::new().with_alt(),
+            }) {
+                Some(GlobalHotkey::ToggleZenMode)
+            } else if input_event.matches_keypress(KeyPress::WithModifiers {
+                key: Key::Character('k'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            }) {
+                Some(GlobalHotkey::OpenFeedbackLink)
+            } else if input_event.matches_keypress(KeyPress::WithModifiers {
+                key: Key::Character('o'),
+                mask: ModifierKeysMask::new().with_alt(),
+            }) {
+                Some(GlobalHotkey::OpenLinkUnderCaret)
+            } else if input_event.matches_keypress(KeyPress::WithModifiers {
+                key: Key::Character('t'),
+                mask: ModifierKeysMask::new().with_alt(),
+            }) {
+                Some(GlobalHotkey::ToggleOutline)
+            } else if input_event.matches_keypress(KeyPress::WithModifiers {
+                key: Key::Character('i'),
+                mask: ModifierKeysMask::new().with_alt(),
+            }) {
+                Some(GlobalHotkey::CycleIndentStyle)
+            } else {
+                None
+            }
+        }
+
+        /// Hotkeys that have been disabled (eg: using a command line option) are passed
+        /// on to the focused component.
+        pub fn is_enabled(&self, state: &State) -> bool {
+            match self {
+                GlobalHotkey::OpenLinkUnderCaret => state.is_open_link_enabled,
+                _ => true,
+            }
+        }
+
+        pub fn get_description(&self) -> &'static str {
+            match self {
+                GlobalHotkey::SaveFile => "Save file",
+                GlobalHotkey::ToggleZenMode => "Toggle zen mode",
+                GlobalHotkey::OpenFeedbackLink => "Open feedback link",
+                GlobalHotkey::OpenLinkUnderCaret => "Open link under caret",
+                GlobalHotkey::ToggleOutline => "Toggle outline",
+                GlobalHotkey::CycleIndentStyle => "Change indentation",
+            }
+        }
+    }
+}
+
 /// The main app struct.
 pub struct AppMain;
 
@@ -189,45 +285,91 @@ mod app_main_impl_app_trait {
             component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
             has_focus: &mut HasFocus,
         ) -> CommonResult<EventPropagation> {
-            // Handle Ctrl + s.
-            if input_event.matches_keypress(KeyPress::WithModifiers {
-                key: Key::Character('s'),
-                mask: ModifierKeysMask::new().with_ctrl(),
-            }) {
-                send_signal!(
-                    global_data.main_thread_channel_sender,
-                    TerminalWindowMainThreadSignal::ApplyAction(AppSignal::SaveFile)
-                );
-
-                return Ok(EventPropagation::Consumed);
+            // Status messages are only shown until the next key is pressed.
+            if let InputEvent::Keyboard(_) = input_event {
+                global_data.state.maybe_status_message = None;
             }
 
-            // Handle Ctrl + k.
-            if input_event.matches_keypress(KeyPress::WithModifiers {
-                key: Key::Character('k'),
-                mask: ModifierKeysMask::new().with_ctrl(),
-            }) {
-                let link_url =
-                    "https://github.com/r3bl-org/r3bl-open-core/issues/new/choose";
-                let result_open = open::that(link_url);
-                match result_open {
-                    Ok(_) => {
-                        call_if_true!(DEBUG_TUI_MOD, {
-                            tracing::debug!(
-                                "\n📣 Opened feedback link: {}",
-                                format!("{link_url:?}").green()
+            match GlobalHotkey::try_from_input_event(&input_event)
+                .filter(|hotkey| hotkey.is_enabled(&global_data.state))
+            {
+                Some(GlobalHotkey::SaveFile) => {
+                    send_signal!(
+                        global_data.main_thread_channel_sender,
+                        TerminalWindowMainThreadSignal::ApplyAction(AppSignal::SaveFile)
+                    );
+                    return Ok(EventPropagation::Consumed);
+                }
+
+                Some(GlobalHotkey::ToggleZenMode) => {
+                    global_data
+                        .state
+                        .toggle_zen_mode(FlexBoxId::from(Id::ComponentEditor));
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+
+                Some(GlobalHotkey::OpenFeedbackLink) => {
+                    let link_url =
+                        "https://github.com/r3bl-org/r3bl-open-core/issues/new/choose";
+                    let result_open = open::that(link_url);
+                    match result_open {
+                        Ok(_) => {
+                            call_if_true!(DEBUG_TUI_MOD, {
+                                tracing::debug!(
+                                    "\n📣 Opened feedback link: {}",
+                                    format!("{link_url:?}").green()
+                                );
+                            });
+                        }
+                        Err(err) => {
+                            tracing::error!(
+                                "\n📣 Error opening feedback link: {}",
+                                format!("{err:?}").red()
                             );
-                        });
-                    }
-                    Err(err) => {
-                        tracing::error!(
-                            "\n📣 Error opening feedback link: {}",
-                            format!("{err:?}").red()
-                        );
+                        }
                     }
+                    return Ok(EventPropagation::Consumed);
+                }
+
+                Some(GlobalHotkey::OpenLinkUnderCaret) => {
+                    let state = &mut global_data.state;
+                    let maybe_link_target = state
+                        .editor_buffers
+                        .get(&FlexBoxId::from(Id::ComponentEditor))
+                        .and_then(r3bl_tui::get_link_at_caret);
+                    state.maybe_status_message =
+                        Some(open_link::open_link_target(maybe_link_target));
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+
+                Some(GlobalHotkey::ToggleOutline) => {
+                    global_data
+                        .state
+                        .toggle_outline_panel(FlexBoxId::from(Id::ComponentEditor));
+                    return Ok(EventPropagation::ConsumedRender);
                 }
 
-                return Ok(EventPropagation::Consumed);
+                Some(GlobalHotkey::CycleIndentStyle) => {
+                    let state = &mut global_data.state;
+                    let indent_style =
+                        state.cycle_indent_style(FlexBoxId::from(Id::ComponentEditor));
+                    state.maybe_status_message =
+                        Some(format!("Indentation: {indent_style}"));
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+
+                None => {}
+            }
+
+            // While the outline panel is focused, it gets all the keyboard input.
+            if global_data.state.outline_panel.is_focused {
+                if let InputEvent::Keyboard(_) = input_event {
+                    outline_panel::handle_input_event(
+                        input_event,
+                        &mut global_data.state,
+                    );
+                    return Ok(EventPropagation::ConsumedRender);
+                }
             }
 
             // If modal not activated, route the input event to the focused component.
@@ -239,6 +381,34 @@ mod app_main_impl_app_trait {
             )
         }
 
+        fn app_would_consume_input_event(
+            &self,
+            input_event: InputEvent,
+            global_data: &GlobalData<State, AppSignal>,
+            component_registry_map: &ComponentRegistryMap<State, AppSignal>,
+            has_focus: &HasFocus,
+        ) -> EventConsumption {
+            // The global hotkeys take precedence over the focused component.
+            if let Some(hotkey) = GlobalHotkey::try_from_input_event(&input_event)
+                .filter(|hotkey| hotkey.is_enabled(&global_data.state))
+            {
+                return EventConsumption::Consumed(Some(hotkey.get_description().into()));
+            }
+
+            if global_data.state.outline_panel.is_focused {
+                if let InputEvent::Keyboard(_) = input_event {
+                    return EventConsumption::Consumed(None);
+                }
+            }
+
+            ComponentRegistry::would_focused_component_consume_event(
+                global_data,
+                input_event,
+                component_registry_map,
+                has_focus,
+            )
+        }
+
         fn app_handle_signal(
             &mut self,
             action: &AppSignal,
@@ -265,6 +435,7 @@ mod app_main_impl_app_trait {
                             // Found file path in the editor buffer.
                             Some(file_path) => {
                                 file_utils::save_content_to_file(file_path, content);
+                                state.mark_as_saved();
                             }
                             // Could not find file path in the editor buffer. This is a
                             // new buffer. Need to ask user via dialog box.
@@ -308,6 +479,10 @@ mod app_main_impl_app_trait {
 
                     return Ok(EventPropagation::ConsumedRender);
                 }
+                AppSignal::WriteRecoveryFile => {
+                    global_data.state.write_recovery_file();
+                    return Ok(EventPropagation::Consumed);
+                }
                 AppSignal::Noop => {}
             }
 
@@ -322,16 +497,50 @@ mod app_main_impl_app_trait {
         ) -> CommonResult<RenderPipeline> {
             throws_with_return!({
                 let window_size = global_data.window_size;
+                let zen_mode = global_data.state.zen_mode.clone();
+                let maybe_status_message = global_data.state.maybe_status_message.clone();
+                let indent_style = global_data
+                    .state
+                    .get_indent_style(FlexBoxId::from(Id::ComponentEditor));
+
+                // The outline panel isn't shown in zen mode.
+                let is_outline_visible =
+                    global_data.state.outline_panel.is_visible && !zen_mode.is_active;
+                let (outline_col_index, outline_col_count) = global_data
+                    .state
+                    .outline_panel
+                    .get_panel_bounds(window_size.col_count);
+
+                // In zen mode, the editor is placed in a centered text column that takes
+                // up the entire height of the window, since there is no status bar.
+                let (surface_pos, surface_size) = if zen_mode.is_active {
+                    let (col_index, col_count) =
+                        zen_mode.get_text_column_bounds(window_size.col_count);
+                    (
+                        position!(col_index: col_index, row_index: 0),
+                        size!(col_count: col_count, row_count: window_size.row_count),
+                    )
+                } else {
+                    let col_count = if is_outline_visible {
+                        outline_col_index
+                    } else {
+                        window_size.col_count
+                    };
+                    (
+                        position!(col_index: 0, row_index: 0),
+                        size!(
+                            col_count: col_count,
+                            row_count: window_size.row_count - 1), // Bottom row for for status bar.
+                    )
+                };
 
                 // Create a surface and then run the SurfaceRenderer (ContainerSurfaceRender) on it.
                 let mut surface = {
                     let mut it = surface!(stylesheet: stylesheet::create_stylesheet()?);
 
                     it.surface_start(SurfaceProps {
-                        pos: position!(col_index: 0, row_index: 0),
-                        size: size!(
-                            col_count: window_size.col_count,
-                            row_count: window_size.row_count - 1), // Bottom row for for status bar.
+                        pos: surface_pos,
+                        size: surface_size,
                     })?;
 
                     perform_layout::ContainerSurfaceRender { _app: self }
@@ -347,8 +556,37 @@ mod app_main_impl_app_trait {
                     it
                 };
 
-                // Render status bar.
-                status_bar::render_status_bar(&mut surface.render_pipeline, window_size);
+                if zen_mode.is_active {
+                    // Render the margins on either side of the text column.
+                    zen_mode_margins::render_margins(
+                        &mut surface.render_pipeline,
+                        window_size,
+                        surface_pos.col_index,
+                        surface_size.col_count,
+                    );
+                } else {
+                    if is_outline_visible {
+                        let editor_id = FlexBoxId::from(Id::ComponentEditor);
+                        global_data.state.update_outline(editor_id);
+                        outline_panel::render_outline_panel(
+                            &mut surface.render_pipeline,
+                            &global_data.state,
+                            position!(col_index: outline_col_index, row_index: 0),
+                            size!(
+                                col_count: outline_col_count,
+                                row_count: surface_size.row_count
+                            ),
+                        );
+                    }
+
+                    // Render status bar.
+                    status_bar::render_status_bar(
+                        &mut surface.render_pipeline,
+                        window_size,
+                        maybe_status_message.as_deref(),
+                        indent_style,
+                    );
+                }
 
                 // Return RenderOps pipeline (which will actually be painted elsewhere).
                 surface.render_pipeline
@@ -427,6 +665,18 @@ mod modal_dialog_ask_for_filename_to_save_file {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            clipboard_export: ClipboardExportMode::PlainText,
+            inline_diagnostics: InlineDiagnosticsMode::Disable,
+            minimap: MinimapMode::Disable,
+            list_renumbering: ListRenumberingMode::Disable,
+            typewriter_scrolling: TypewriterScrollingMode::Disable,
+            overwrite_mode: OverwriteMode::Disable,
+            current_line_highlight: CurrentLineHighlightMode::Disable,
+            multi_caret: MultiCaretMode::Disable,
+            horizontal_overflow_markers: HorizontalOverflowMarkersMode::Disable,
+            checkbox_toggle: CheckboxToggleMode::Disable,
+            line_wrap: LineWrapMode::None,
+            indent_style: IndentStyle::default(),
         };
 
         let boxed_dialog_component = {
@@ -615,9 +865,22 @@ mod populate_component_registry {
                     main_thread_channel_sender,
                     TerminalWindowMainThreadSignal::Render(Some(my_id))
                 );
+                recovery::schedule_recovery_write(main_thread_channel_sender);
             }
 
-            let config_options = EditorEngineConfig::default();
+            let config_options = EditorEngineConfig {
+                list_renumbering: ListRenumberingMode::Enable,
+                overwrite_mode: OverwriteMode::Enable,
+                current_line_highlight: CurrentLineHighlightMode::Enable(
+                    get_current_line_style(),
+                ),
+                multi_caret: MultiCaretMode::Enable,
+                horizontal_overflow_markers: HorizontalOverflowMarkersMode::Enable(
+                    tui_style! { attrib: [dim] },
+                ),
+                checkbox_toggle: CheckboxToggleMode::Enable,
+                ..Default::default()
+            };
             EditorComponent::new_boxed(id, config_options, on_buffer_change)
         };
 
@@ -665,65 +928,285 @@ mod stylesheet {
                 id: Id::StyleDialogResultsPanel.into()
                 // attrib: [bold]
                 color_fg: TuiColor::Basic(ANSIBasicColor::Blue)
+              },
+              tui_style! {
+                id: Id::StyleZenModeMargin.into()
+                color_bg: TuiColor::Rgb(RgbValue { red: 20, green: 20, blue: 30 })
               }
             }
         })
     }
 }
 
-mod status_bar {
+mod zen_mode_margins {
     use super::*;
 
-    /// Shows helpful messages at the bottom row of the screen.
-    pub fn render_status_bar(pipeline: &mut RenderPipeline, size: Size) {
-        let separator_style = tui_style!(
+    /// Paint the margins to the left and right of the zen mode text column using the
+    /// [Id::StyleZenModeMargin] style from the stylesheet.
+    pub fn render_margins(
+        pipeline: &mut RenderPipeline,
+        window_size: Size,
+        text_column_col_index: ChUnit,
+        text_column_col_count: ChUnit,
+    ) {
+        let maybe_style = get_tui_style! {
+            @from_result: stylesheet::create_stylesheet(),
+            Id::StyleZenModeMargin.into()
+        };
+        let style = maybe_style.unwrap_or_default();
+
+        let left_margin_col_count = text_column_col_index;
+        let right_margin_col_index = text_column_col_index + text_column_col_count;
+        let right_margin_col_count = window_size.col_count - right_margin_col_index;
+
+        let mut render_ops = render_ops!();
+        for row_index in 0..ch!(@to_usize window_size.row_count) {
+            for (col_index, col_count) in [
+                (ch!(0), left_margin_col_count),
+                (right_margin_col_index, right_margin_col_count),
+            ] {
+                if col_count == ch!(0) {
+                    continue;
+                }
+                render_ops.push(RenderOp::MoveCursorPositionAbs(
+                    position!(col_index: col_index, row_index: ch!(row_index)),
+                ));
+                let spaces = " ".repeat(ch!(@to_usize col_count));
+                render_tui_styled_texts_into(
+                    &tui_styled_texts! {
+                        tui_styled_text! { @style: style, @text: spaces },
+                    },
+                    &mut render_ops,
+                );
+            }
+        }
+        pipeline.push(ZOrder::Normal, render_ops);
+    }
+}
+
+mod outline_panel {
+    use super::*;
+
+    pub fn handle_input_event(input_event: InputEvent, state: &mut State) {
+        let InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        }) = input_event
+        else {
+            return;
+        };
+        let editor_id = FlexBoxId::from(Id::ComponentEditor);
+        match special_key {
+            SpecialKey::Up => state.outline_panel.move_selection(-1),
+            SpecialKey::Down => state.outline_panel.move_selection(1),
+            SpecialKey::PageUp => state.outline_panel.move_selection(-10),
+            SpecialKey::PageDown => state.outline_panel.move_selection(10),
+            SpecialKey::Enter => state.go_to_selected_outline_entry(editor_id),
+            SpecialKey::Esc => state.outline_panel.is_focused = false,
+            _ => {}
+        }
+    }
+
+    /// Paint the headings of the document in the given box. The first column is used for
+    /// the border. When there are more headings than rows, the list is scrolled so that
+    /// the selected heading (or the active one, when the panel isn't focused) is visible.
+    pub fn render_outline_panel(
+        pipeline: &mut RenderPipeline,
+        state: &State,
+        panel_pos: Position,
+        panel_size: Size,
+    ) {
+        let panel = &state.outline_panel;
+        let maybe_active_index =
+            state.get_active_outline_index(FlexBoxId::from(Id::ComponentEditor));
+        let text_width = ch!(@to_usize panel_size.col_count).saturating_sub(2);
+        let row_count = ch!(@to_usize panel_size.row_count);
+
+        let border_style = tui_style!(
             attrib: [dim]
             color_fg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
         );
-
-        let app_text = &UnicodeString::from("edi 🦜 ✶early access✶");
-
-        let mut color_wheel = ColorWheel::new(vec![
-            ColorWheelConfig::Rgb(
-                Vec::from(["#3eff03", "#00e5ff"].map(String::from)),
-                ColorWheelSpeed::Fast,
-                15,
-            ),
-            ColorWheelConfig::Ansi256(
-                Ansi256GradientIndex::MediumGreenToMediumBlue,
-                ColorWheelSpeed::Fast,
-            ),
-        ]);
-
-        let app_text_styled_texts = color_wheel.colorize_into_styled_texts(
-            app_text,
-            GradientGenerationPolicy::ReuseExistingGradientAndResetIndex,
-            TextColorizationPolicy::ColorEachCharacter(None),
+        let active_style = tui_style!(
+            attrib: [bold]
+            color_fg: TuiColor::Basic(ANSIBasicColor::Cyan)
         );
+        let selected_style = tui_style!(attrib: [reverse]);
 
-        let styled_texts: TuiStyledTexts = {
-            let mut it = Default::default();
-            it += app_text_styled_texts;
-            it += tui_styled_text! { @style: separator_style , @text: " │ "};
-            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Save: Ctrl+S "};
-            it += tui_styled_text! { @style: tui_style!() , @text: "💾"};
-            it += tui_styled_text! { @style: separator_style , @text: " │ "};
-            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Feedback: Ctrl+K "};
-            it += tui_styled_text! { @style: tui_style!() , @text: "💭"};
-            it += tui_styled_text! { @style: separator_style , @text: " │ "};
-            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Exit: Ctrl+Q "};
-            it += tui_styled_text! { @style: tui_style!() , @text: "🖖"};
-            it
+        let index_to_show = if panel.is_focused {
+            Some(panel.selected_index)
+        } else {
+            maybe_active_index
         };
-
-        let display_width = styled_texts.display_width();
-        let col_center: ChUnit = (size.col_count - display_width) / 2;
-        let row_bottom: ChUnit = size.row_count - 1;
-        let center: Position = position!(col_index: col_center, row_index: row_bottom);
+        let first_index = index_to_show
+            .unwrap_or(0)
+            .saturating_sub(row_count.saturating_sub(1));
 
         let mut render_ops = render_ops!();
-        render_ops.push(RenderOp::MoveCursorPositionAbs(center));
-        render_tui_styled_texts_into(&styled_texts, &mut render_ops);
+        for row_offset in 0..row_count {
+            let index = first_index + row_offset;
+            render_ops.push(RenderOp::MoveCursorPositionAbs(
+                panel_pos + position!(col_index: 0, row_index: row_offset),
+            ));
+
+            let text = match panel.outline.get_entry_display_text(index, text_width) {
+                Some(text) => text,
+                None if index == 0 && row_offset == 0 => "No headings".to_string(),
+                None => String::new(),
+            };
+            let padding = " ".repeat(
+                text_width
+                    .saturating_sub(UnicodeString::from(&text).display_width.into()),
+            );
+            let style = if panel.is_focused && index == panel.selected_index {
+                selected_style
+            } else if Some(index) == maybe_active_index {
+                active_style
+            } else {
+                tui_style!()
+            };
+
+            render_tui_styled_texts_into(
+                &tui_styled_texts! {
+                    tui_styled_text! { @style: border_style, @text: "│ " },
+                    tui_styled_text! { @style: style, @text: format!("{text}{padding}") },
+                },
+                &mut render_ops,
+            );
+        }
         pipeline.push(ZOrder::Normal, render_ops);
     }
 }
+
+mod open_link {
+    use super::*;
+
+    /// Open the `maybe_link_target` using the OS opener (eg: the default browser for
+    /// URLs, or the default app / file manager for files), and return a message that can
+    /// be shown to the user.
+    pub fn open_link_target(maybe_link_target: Option<LinkTarget>) -> String {
+        let Some(link_target) = maybe_link_target else {
+            return "No link under caret".to_string();
+        };
+
+        let link = match link_target {
+            LinkTarget::Url(url) => url,
+            LinkTarget::File(path) => {
+                if !path.exists() {
+                    return format!("File not found: {}", path.display());
+                }
+                path.display().to_string()
+            }
+        };
+
+        match open::that(&link) {
+            Ok(_) => {
+                call_if_true!(DEBUG_TUI_MOD, {
+                    tracing::debug!(
+                        "\n📣 Opened link under caret: {}",
+                        format!("{link:?}").green()
+                    );
+                });
+                format!("Opened {link}")
+            }
+            Err(err) => {
+                tracing::error!(
+                    "\n📣 Error opening link under caret: {}",
+                    format!("{err:?}").red()
+                );
+                format!("Could not open {link}")
+            }
+        }
+    }
+}
+
+mod status_bar {
+    use super::*;
+
+    /// Shows helpful messages at the bottom row of the screen, and the indentation that is
+    /// used by the editor. When the window is too narrow, the lowest priority hints are
+    /// dropped first.
+    pub fn render_status_bar(
+        pipeline: &mut RenderPipeline,
+        size: Size,
+        maybe_status_message: Option<&str>,
+        indent_style: IndentStyle,
+    ) {
+        let separator_style = tui_style!(
+            attrib: [dim]
+            color_fg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
+        );
+
+        let mut status_bar = StatusBar::default().with_separator(tui_styled_texts! {
+            tui_styled_text! { @style: separator_style , @text: " │ "}
+        });
+
+        if let Some(status_message) = maybe_status_message {
+            let status_message = status_message.to_string();
+            status_bar = status_bar.add_segment(StatusBarAlignment::Center, 5, move || {
+                tui_styled_texts! {
+                    tui_styled_text! { @style: tui_style!(attrib: [bold]) , @text: &status_message},
+                }
+            });
+        }
+
+        let status_bar = status_bar
+            .add_segment(StatusBarAlignment::Center, 4, || {
+                let app_text = &UnicodeString::from("edi 🦜 ✶early access✶");
+
+                let mut color_wheel = ColorWheel::new(vec![
+                    ColorWheelConfig::Rgb(
+                        Vec::from(["#3eff03", "#00e5ff"].map(String::from)),
+                        ColorWheelSpeed::Fast,
+                        15,
+                    ),
+                    ColorWheelConfig::Ansi256(
+                        Ansi256GradientIndex::MediumGreenToMediumBlue,
+                        ColorWheelSpeed::Fast,
+                    ),
+                ]);
+
+                color_wheel.colorize_into_styled_texts(
+                    app_text,
+                    GradientGenerationPolicy::ReuseExistingGradientAndResetIndex,
+                    TextColorizationPolicy::ColorEachCharacter(None),
+                )
+            })
+            .add_segment(StatusBarAlignment::Center, 3, move || {
+                let indent_style = indent_style.to_string();
+                tui_styled_texts! {
+                    tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Indent (Alt+I): "},
+                    tui_styled_text! { @style: tui_style!() , @text: &indent_style},
+                }
+            })
+            .add_segment(StatusBarAlignment::Center, 3, || {
+                tui_styled_texts! {
+                    tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Save: Ctrl+S "},
+                    tui_styled_text! { @style: tui_style!() , @text: "💾"},
+                }
+            })
+            .add_segment(StatusBarAlignment::Center, 1, || {
+                tui_styled_texts! {
+                    tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Feedback: Ctrl+K "},
+                    tui_styled_text! { @style: tui_style!() , @text: "💭"},
+                }
+            })
+            .add_segment(StatusBarAlignment::Center, 2, || {
+                tui_styled_texts! {
+                    tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Exit: Ctrl+Q "},
+                    tui_styled_text! { @style: tui_style!() , @text: "🖖"},
+                }
+            })
+            .add_segment(StatusBarAlignment::Center, 0, || {
+                tui_styled_texts! {
+                    tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Zen: Alt+Z "},
+                    tui_styled_text! { @style: tui_style!() , @text: "🧘"},
+                }
+            });
+
+        let row_bottom: ChUnit = size.row_count - 1;
+        status_bar.render_into(
+            pipeline,
+            position!(col_index: 0, row_index: row_bottom),
+            size.col_count,
+        );
+    }
+}
@@ -15,15 +15,27 @@
  *   limitations under the License.
  */
 
-use r3bl_core::{throws, CommonResult};
+use r3bl_core::{ch, throws, CommonResult};
 use r3bl_tui::{keypress, InputEvent, ModifierKeysMask, TerminalWindow};
 
-use crate::edi::{constructor, AppMain};
+use crate::edi::{constructor, recovery, AppMain, RestoreChoice};
 
-pub async fn run_app(maybe_file_path: Option<String>) -> CommonResult<()> {
+pub async fn run_app(
+    maybe_file_path: Option<String>,
+    zen_mode_text_column_width: u16,
+    is_open_link_enabled: bool,
+) -> CommonResult<()> {
     throws!({
         // Create a new state from the file path.
-        let state = constructor::new(&maybe_file_path);
+        let mut state = constructor::new(&maybe_file_path);
+        state.zen_mode.text_column_width = ch!(zen_mode_text_column_width);
+        state.is_open_link_enabled = is_open_link_enabled;
+
+        // Recover the unsaved changes from the last time edi crashed (if any).
+        state.recovery.maybe_folder_path = recovery::get_recovery_folder_path();
+        if let RestoreChoice::Quit = state.offer_to_restore_recovery_file() {
+            return Ok(());
+        }
 
         // Create a new app.
         let app = AppMain::new_boxed();
@@ -34,6 +46,10 @@ pub async fn run_app(maybe_file_path: Option<String>) -> CommonResult<()> {
         )];
 
         // Create a window.
-        _ = TerminalWindow::main_event_loop(app, exit_keys, state).await?;
+        let (mut global_data, _, _) =
+            TerminalWindow::main_event_loop(app, exit_keys, state).await?;
+
+        // This is a normal exit, so the recovery file is no longer needed.
+        global_data.state.remove_recovery_file();
     })
 }
@@ -18,9 +18,11 @@
 // Include.
 pub mod app_main;
 pub mod launcher;
+pub mod recovery;
 pub mod state;
 
 // Reexport.
 pub use app_main::*;
 pub use launcher::*;
+pub use recovery::*;
 pub use state::*;
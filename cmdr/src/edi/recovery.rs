@@ -0,0 +1,496 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Crash recovery for edi. While the editor buffer has unsaved changes, its content (and
+//! metadata, like the caret) is written to a recovery file in
+//! [config_folder::get_edi_recovery_folder_path], at most once every
+//! [RECOVERY_WRITE_DELAY]. The recovery file is removed when the buffer is saved, or
+//! when edi exits normally. So if a recovery file is found when edi starts, then edi
+//! crashed (or was killed) the last time that file was edited, and the user is asked
+//! whether to restore it, compare it w/ the file, or discard it.
+
+use std::{fs,
+          path::{Path, PathBuf},
+          sync::atomic::{AtomicBool, Ordering},
+          time::Duration};
+
+use miette::IntoDiagnostic as _;
+use r3bl_ansi_color::{AnsiStyledText, Style};
+use r3bl_tui::{EditorBuffer,
+               EditorBufferSnapshot,
+               FlexBoxId,
+               TerminalWindowMainThreadSignal};
+use r3bl_tuify::{select_from_list, SelectionMode, StyleSheet};
+use tokio::sync::mpsc::Sender;
+
+use crate::{color_constants::DefaultColors::{GuardsRed, LizardGreen, SlateGray},
+            config_folder,
+            edi::{AppSignal, Id, State},
+            file_io};
+
+/// How long to wait after the buffer changes, before writing the recovery file. Changes
+/// made while waiting are written at the same time.
+pub const RECOVERY_WRITE_DELAY: Duration = Duration::from_secs(5);
+
+/// Buffers that haven't been saved to a file yet share this recovery file.
+const UNTITLED_RECOVERY_FILE_NAME: &str = "untitled";
+
+static IS_RECOVERY_WRITE_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Recovery {
+    /// Where recovery files are written. Recovery is turned off if this is [None].
+    pub maybe_folder_path: Option<PathBuf>,
+    /// The content of the editor buffer as it was last read from, or saved to, the file.
+    /// The buffer is dirty when its content is different from this.
+    pub saved_content: String,
+    /// The recovery file that is currently in use, so that it can be removed.
+    pub maybe_recovery_file_path: Option<PathBuf>,
+}
+
+/// What the user wants to do w/ the content of a recovery file that is found when edi
+/// starts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RestoreChoice {
+    Restore,
+    Discard,
+    /// Don't open the file, and leave the recovery file alone.
+    Quit,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LineDiff {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+pub fn get_recovery_folder_path() -> Option<PathBuf> {
+    config_folder::try_get_config_folder_path()
+        .map(config_folder::get_edi_recovery_folder_path)
+}
+
+/// The same file maps to the same absolute path, no matter which folder edi is started
+/// from. Files that don't exist yet can't be canonicalized, so they are resolved against
+/// the current folder instead.
+pub fn get_absolute_file_path(file_path: &str) -> String {
+    let path = Path::new(file_path);
+    let absolute_path = match fs::canonicalize(path) {
+        Ok(it) => it,
+        Err(_) => match std::env::current_dir() {
+            Ok(current_dir) => current_dir.join(path),
+            Err(_) => path.to_path_buf(),
+        },
+    };
+    absolute_path.to_string_lossy().to_string()
+}
+
+/// Each file has its own recovery file, which is named after its absolute path, w/ `%`
+/// escaped as `%%`, and the path separators (and drive colons) replaced w/ `%`.
+pub fn get_recovery_file_path(
+    folder_path: &Path,
+    maybe_file_path: &Option<String>,
+) -> PathBuf {
+    let file_name = match maybe_file_path {
+        Some(file_path) => get_absolute_file_path(file_path)
+            .replace('%', "%%")
+            .replace(['/', '\\', ':'], "%"),
+        None => UNTITLED_RECOVERY_FILE_NAME.to_string(),
+    };
+    folder_path.join(format!("{file_name}.json"))
+}
+
+/// Returns the snapshot in the recovery file for `maybe_file_path`, if there is one, and
+/// it really is for that file (and not another one whose path maps to the same recovery
+/// file name).
+pub fn try_read_recovery_file(
+    folder_path: &Path,
+    maybe_file_path: &Option<String>,
+) -> Option<EditorBufferSnapshot> {
+    let recovery_file_path = get_recovery_file_path(folder_path, maybe_file_path);
+    let contents = file_io::try_read_file_contents(&recovery_file_path).ok()?;
+    let snapshot = match EditorBufferSnapshot::try_from_json(&contents) {
+        Ok(it) => it,
+        Err(error) => {
+            tracing::error!("Could not parse the edi recovery file.\n{error:?}");
+            return None;
+        }
+    };
+    let maybe_absolute_file_path = maybe_file_path.as_deref().map(get_absolute_file_path);
+    (snapshot.maybe_file_path == maybe_absolute_file_path && snapshot.is_dirty)
+        .then_some(snapshot)
+}
+
+/// Send [AppSignal::WriteRecoveryFile] after [RECOVERY_WRITE_DELAY], unless it has
+/// already been scheduled.
+pub fn schedule_recovery_write(
+    main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AppSignal>>,
+) {
+    if IS_RECOVERY_WRITE_SCHEDULED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tokio::spawn(async move {
+        tokio::time::sleep(RECOVERY_WRITE_DELAY).await;
+        IS_RECOVERY_WRITE_SCHEDULED.store(false, Ordering::SeqCst);
+        let _ = main_thread_channel_sender
+            .send(TerminalWindowMainThreadSignal::ApplyAction(
+                AppSignal::WriteRecoveryFile,
+            ))
+            .await;
+    });
+}
+
+impl State {
+    pub fn get_editor_buffer(&self) -> Option<&EditorBuffer> {
+        self.editor_buffers
+            .get(&FlexBoxId::from(Id::ComponentEditor))
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        match self.get_editor_buffer() {
+            Some(buffer) => {
+                buffer.get_as_string_with_newlines() != self.recovery.saved_content
+            }
+            None => false,
+        }
+    }
+
+    /// Write the recovery file if the buffer is dirty, otherwise remove it.
+    pub fn write_recovery_file(&mut self) {
+        let Some(folder_path) = self.recovery.maybe_folder_path.clone() else {
+            return;
+        };
+        if !self.is_dirty() {
+            self.remove_recovery_file();
+            return;
+        }
+        let Some(buffer) = self.get_editor_buffer() else {
+            return;
+        };
+
+        let maybe_file_path = buffer.editor_content.maybe_file_path.clone();
+        let mut snapshot = buffer.get_snapshot(true);
+        snapshot.maybe_file_path = maybe_file_path.as_deref().map(get_absolute_file_path);
+        let recovery_file_path = get_recovery_file_path(&folder_path, &maybe_file_path);
+
+        // The file path changes when a new buffer is given a file name.
+        if self.recovery.maybe_recovery_file_path.as_ref() != Some(&recovery_file_path) {
+            self.remove_recovery_file();
+        }
+
+        let result = fs::create_dir_all(&folder_path)
+            .into_diagnostic()
+            .and_then(|_| snapshot.to_json())
+            .and_then(|json| {
+                file_io::try_write_file_contents_atomically(&recovery_file_path, &json)
+            });
+        match result {
+            Ok(_) => self.recovery.maybe_recovery_file_path = Some(recovery_file_path),
+            Err(error) => {
+                tracing::error!("Could not write the edi recovery file.\n{error:?}")
+            }
+        }
+    }
+
+    pub fn remove_recovery_file(&mut self) {
+        if let Some(recovery_file_path) = self.recovery.maybe_recovery_file_path.take() {
+            let _ = fs::remove_file(recovery_file_path);
+        }
+    }
+
+    /// Call this when the content of the buffer has been saved to its file.
+    pub fn mark_as_saved(&mut self) {
+        if let Some(buffer) = self.get_editor_buffer() {
+            self.recovery.saved_content = buffer.get_as_string_with_newlines();
+        }
+        self.remove_recovery_file();
+    }
+
+    /// If there is a recovery file for the editor buffer, then ask the user what to do
+    /// w/ it, and do it.
+    pub fn offer_to_restore_recovery_file(&mut self) -> RestoreChoice {
+        let Some(folder_path) = self.recovery.maybe_folder_path.clone() else {
+            return RestoreChoice::Discard;
+        };
+        let Some(buffer) = self.get_editor_buffer() else {
+            return RestoreChoice::Discard;
+        };
+        let maybe_file_path = buffer.editor_content.maybe_file_path.clone();
+        let Some(snapshot) = try_read_recovery_file(&folder_path, &maybe_file_path)
+        else {
+            return RestoreChoice::Discard;
+        };
+        self.recovery.maybe_recovery_file_path =
+            Some(get_recovery_file_path(&folder_path, &maybe_file_path));
+
+        let saved_lines: Vec<String> = self
+            .recovery
+            .saved_content
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
+        let choice = ask_user_what_to_do_w_recovery_file(&saved_lines, &snapshot);
+
+        match choice {
+            RestoreChoice::Restore => {
+                if let Some(buffer) = self
+                    .editor_buffers
+                    .get_mut(&FlexBoxId::from(Id::ComponentEditor))
+                {
+                    buffer.restore_snapshot(&snapshot);
+                }
+            }
+            RestoreChoice::Discard => self.remove_recovery_file(),
+            RestoreChoice::Quit => {}
+        }
+
+        choice
+    }
+}
+
+fn ask_user_what_to_do_w_recovery_file(
+    saved_lines: &[String],
+    snapshot: &EditorBufferSnapshot,
+) -> RestoreChoice {
+    const RESTORE: &str = "Restore the unsaved changes";
+    const COMPARE: &str = "Compare the unsaved changes w/ the file";
+    const DISCARD: &str = "Discard the unsaved changes";
+
+    let file_name = snapshot
+        .maybe_file_path
+        .as_deref()
+        .unwrap_or("the new file");
+    loop {
+        let maybe_user_choices = select_from_list(
+            format!(
+                "edi did not exit normally while editing {file_name}. What do you want to do w/ the unsaved changes? (Esc to quit)"
+            ),
+            vec![RESTORE.to_string(), COMPARE.to_string(), DISCARD.to_string()],
+            5,
+            0,
+            SelectionMode::Single,
+            StyleSheet::default(),
+        );
+        let maybe_user_choice = maybe_user_choices
+            .as_ref()
+            .and_then(|user_choices| user_choices.first())
+            .map(String::as_str);
+
+        match maybe_user_choice {
+            Some(RESTORE) => return RestoreChoice::Restore,
+            Some(DISCARD) => return RestoreChoice::Discard,
+            Some(COMPARE) => {
+                print_line_diff(&get_line_diff(saved_lines, &snapshot.lines))
+            }
+            _ => return RestoreChoice::Quit,
+        }
+    }
+}
+
+fn print_line_diff(line_diffs: &[LineDiff]) {
+    for line_diff in line_diffs {
+        let (text, color) = match line_diff {
+            LineDiff::Same(line) => (format!("  {line}"), SlateGray),
+            LineDiff::Removed(line) => (format!("- {line}"), GuardsRed),
+            LineDiff::Added(line) => (format!("+ {line}"), LizardGreen),
+        };
+        AnsiStyledText {
+            text: &text,
+            style: &[Style::Foreground(color.as_ansi_color())],
+        }
+        .println();
+    }
+}
+
+/// The most cells that the longest common subsequence table in [get_line_diff] may have,
+/// which keeps comparing two big files that have little in common from taking a lot of
+/// time & memory.
+pub const MAX_LINE_DIFF_TABLE_SIZE: usize = 1_000_000;
+
+/// Compare the lines of the file (`old_lines`) w/ the lines in the recovery file
+/// (`new_lines`), using the longest common subsequence of lines.
+///
+/// The lines that both start or end w/ are not part of the comparison. If what is left
+/// is bigger than [MAX_LINE_DIFF_TABLE_SIZE], then it is shown as removed, followed by
+/// added, w/out looking for the lines that are in common.
+pub fn get_line_diff(old_lines: &[String], new_lines: &[String]) -> Vec<LineDiff> {
+    let prefix_len = old_lines
+        .iter()
+        .zip(new_lines)
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let suffix_len = old_lines[prefix_len..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix_len..].iter().rev())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let old_middle = &old_lines[prefix_len..old_lines.len() - suffix_len];
+    let new_middle = &new_lines[prefix_len..new_lines.len() - suffix_len];
+
+    let mut acc: Vec<LineDiff> = old_lines[..prefix_len]
+        .iter()
+        .cloned()
+        .map(LineDiff::Same)
+        .collect();
+    let table_size = (old_middle.len() + 1).saturating_mul(new_middle.len() + 1);
+    if table_size > MAX_LINE_DIFF_TABLE_SIZE {
+        acc.extend(old_middle.iter().cloned().map(LineDiff::Removed));
+        acc.extend(new_middle.iter().cloned().map(LineDiff::Added));
+    } else {
+        acc.extend(get_lcs_line_diff(old_middle, new_middle));
+    }
+    acc.extend(
+        old_lines[old_lines.len() - suffix_len..]
+            .iter()
+            .cloned()
+            .map(LineDiff::Same),
+    );
+    acc
+}
+
+fn get_lcs_line_diff(old_lines: &[String], new_lines: &[String]) -> Vec<LineDiff> {
+    // lcs_len[i][j] is the length of the longest common subsequence of old_lines[i..]
+    // and new_lines[j..].
+    let mut lcs_len = vec![vec![0_usize; new_lines.len() + 1]; old_lines.len() + 1];
+    for i in (0..old_lines.len()).rev() {
+        for j in (0..new_lines.len()).rev() {
+            lcs_len[i][j] = if old_lines[i] == new_lines[j] {
+                lcs_len[i + 1][j + 1] + 1
+            } else {
+                std::cmp::max(lcs_len[i + 1][j], lcs_len[i][j + 1])
+            };
+        }
+    }
+
+    let mut acc = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old_lines.len() && j < new_lines.len() {
+        if old_lines[i] == new_lines[j] {
+            acc.push(LineDiff::Same(old_lines[i].clone()));
+            i += 1;
+            j += 1;
+        } else if lcs_len[i + 1][j] >= lcs_len[i][j + 1] {
+            acc.push(LineDiff::Removed(old_lines[i].clone()));
+            i += 1;
+        } else {
+            acc.push(LineDiff::Added(new_lines[j].clone()));
+            j += 1;
+        }
+    }
+    acc.extend(old_lines[i..].iter().cloned().map(LineDiff::Removed));
+    acc.extend(new_lines[j..].iter().cloned().map(LineDiff::Added));
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::friendly_random_id;
+
+    use super::*;
+
+    fn make_temp_folder_path() -> PathBuf {
+        PathBuf::from(format!(
+            "/tmp/{}_edi_recovery",
+            friendly_random_id::generate_friendly_random_id()
+        ))
+    }
+
+    fn to_lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_get_recovery_file_path() {
+        let folder_path = PathBuf::from("/tmp/recovery");
+        assert_eq!(
+            get_recovery_file_path(&folder_path, &Some("/home/foo/100%.md".to_string())),
+            PathBuf::from("/tmp/recovery/%home%foo%100%%.md.json")
+        );
+        assert_eq!(
+            get_recovery_file_path(&folder_path, &None),
+            PathBuf::from("/tmp/recovery/untitled.json")
+        );
+    }
+
+    #[test]
+    fn test_write_and_read_recovery_file() {
+        let folder_path = make_temp_folder_path();
+        let file_path = Some(format!("{}.md", folder_path.display()));
+
+        let mut state = super::super::constructor::new(&file_path);
+        state.recovery.maybe_folder_path = Some(folder_path.clone());
+
+        // Not dirty, so nothing is written.
+        state.write_recovery_file();
+        assert!(try_read_recovery_file(&folder_path, &file_path).is_none());
+
+        state
+            .editor_buffers
+            .get_mut(&FlexBoxId::from(Id::ComponentEditor))
+            .unwrap()
+            .set_lines(to_lines(&["unsaved"]));
+        assert!(state.is_dirty());
+        state.write_recovery_file();
+        let snapshot = try_read_recovery_file(&folder_path, &file_path).unwrap();
+        assert_eq!(snapshot.lines, to_lines(&["unsaved"]));
+
+        // Another file doesn't match this recovery file.
+        assert!(try_read_recovery_file(&folder_path, &None).is_none());
+
+        // Saving removes the recovery file.
+        state.mark_as_saved();
+        assert!(!state.is_dirty());
+        assert!(try_read_recovery_file(&folder_path, &file_path).is_none());
+
+        fs::remove_dir_all(folder_path).unwrap();
+    }
+
+    #[test]
+    fn test_get_line_diff() {
+        let old_lines = to_lines(&["a", "b", "c"]);
+        let new_lines = to_lines(&["a", "x", "c", "d"]);
+        assert_eq!(
+            get_line_diff(&old_lines, &new_lines),
+            vec![
+                LineDiff::Same("a".to_string()),
+                LineDiff::Removed("b".to_string()),
+                LineDiff::Added("x".to_string()),
+                LineDiff::Same("c".to_string()),
+                LineDiff::Added("d".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_line_diff_of_big_files_w_nothing_in_common() {
+        let old_lines: Vec<String> = (0..2_000).map(|it| format!("old {it}")).collect();
+        let new_lines: Vec<String> = (0..2_000).map(|it| format!("new {it}")).collect();
+        let mut old_with_ends = to_lines(&["start"]);
+        old_with_ends.extend(old_lines.iter().cloned());
+        old_with_ends.push("end".to_string());
+        let mut new_with_ends = to_lines(&["start"]);
+        new_with_ends.extend(new_lines.iter().cloned());
+        new_with_ends.push("end".to_string());
+
+        let mut expected = vec![LineDiff::Same("start".to_string())];
+        expected.extend(old_lines.into_iter().map(LineDiff::Removed));
+        expected.extend(new_lines.into_iter().map(LineDiff::Added));
+        expected.push(LineDiff::Same("end".to_string()));
+        assert_eq!(get_line_diff(&old_with_ends, &new_with_ends), expected);
+    }
+}
@@ -21,26 +21,243 @@ use std::{collections::HashMap,
           path::Path};
 
 use crossterm::style::Stylize;
-use r3bl_core::call_if_true;
-use r3bl_tui::{DialogBuffer,
+use r3bl_core::{call_if_true, ch, ChUnit, Position};
+use r3bl_tui::{move_caret_to_outline_entry,
+               CaretKind,
+               DialogBuffer,
+               DocumentOutline,
                EditorBuffer,
                FlexBoxId,
                HasDialogBuffers,
                HasEditorBuffers,
+               IndentStyle,
+               ScrollOffset,
                DEBUG_TUI_MOD,
                DEFAULT_SYN_HI_FILE_EXT};
 
-use crate::{edi::Id, report_analytics, AnalyticsAction};
+use crate::{edi::{Id, Recovery},
+            report_analytics,
+            AnalyticsAction};
 
 #[derive(Clone, PartialEq)]
 pub struct State {
     pub editor_buffers: HashMap<FlexBoxId, EditorBuffer>,
     pub dialog_buffers: HashMap<FlexBoxId, DialogBuffer>,
+    pub zen_mode: ZenMode,
+    pub outline_panel: OutlinePanel,
+    /// Whether the link under the caret can be opened (using Alt+O).
+    pub is_open_link_enabled: bool,
+    /// Feedback (eg: after trying to open a link) that is shown in the status bar, until
+    /// the next key is pressed.
+    pub maybe_status_message: Option<String>,
+    pub recovery: Recovery,
+}
+
+pub const DEFAULT_ZEN_MODE_TEXT_COLUMN_WIDTH: u16 = 80;
+
+/// The indent styles that the user can cycle through (using Alt+I), to override the one
+/// that was detected when the file was opened.
+pub const INDENT_STYLES_TO_CYCLE_THROUGH: [IndentStyle; 4] = [
+    IndentStyle::Spaces(2),
+    IndentStyle::Spaces(4),
+    IndentStyle::Spaces(8),
+    IndentStyle::Tabs,
+];
+
+/// Zen mode is a distraction free mode, where the status bar is hidden, and the editor is
+/// placed in a text column that is centered in the window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZenMode {
+    pub is_active: bool,
+    /// Width of the centered text column. If the window is narrower than this, then the
+    /// text column takes up the entire width of the window. A width of `0` is treated as
+    /// `1`, so that there is always a column for the caret.
+    pub text_column_width: ChUnit,
+    /// The editor's scroll offset and (scroll adjusted) caret when zen mode was entered,
+    /// so that the scroll position can be restored when zen mode is exited.
+    pub maybe_saved_scroll: Option<(ScrollOffset, Position)>,
+}
+
+impl Default for ZenMode {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            text_column_width: ch!(DEFAULT_ZEN_MODE_TEXT_COLUMN_WIDTH),
+            maybe_saved_scroll: None,
+        }
+    }
+}
+
+impl ZenMode {
+    /// Returns the column index and width of the centered text column for the given
+    /// window width.
+    pub fn get_text_column_bounds(&self, window_width: ChUnit) -> (ChUnit, ChUnit) {
+        let text_column_width = std::cmp::max(self.text_column_width, ch!(1));
+        let width = std::cmp::min(text_column_width, window_width);
+        let col_index = (window_width - width) / 2;
+        (col_index, width)
+    }
+}
+
+pub const DEFAULT_OUTLINE_PANEL_WIDTH: u16 = 32;
+
+/// The outline panel lists the headings of the document, and is painted to the right of
+/// the editor. While it is focused, it handles the keyboard input (Up, Down, Enter to go
+/// to the selected heading, and Esc to go back to the editor). The heading that the caret
+/// is in (the active heading) is highlighted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OutlinePanel {
+    pub is_visible: bool,
+    pub is_focused: bool,
+    pub outline: DocumentOutline,
+    pub selected_index: usize,
+}
+
+impl OutlinePanel {
+    /// Returns the column index and width of the panel for the given window width. The
+    /// panel never takes up more than a third of the window.
+    pub fn get_panel_bounds(&self, window_width: ChUnit) -> (ChUnit, ChUnit) {
+        let width = std::cmp::min(ch!(DEFAULT_OUTLINE_PANEL_WIDTH), window_width / 3);
+        (window_width - width, width)
+    }
+
+    /// Move the selection up (negative `delta`) or down, w/out going past either end.
+    pub fn move_selection(&mut self, delta: isize) {
+        let Some(last_index) = self.outline.entries.len().checked_sub(1) else {
+            return;
+        };
+        self.selected_index = self
+            .selected_index
+            .saturating_add_signed(delta)
+            .min(last_index);
+    }
+}
+
+impl State {
+    /// The indent style of the editor, which is detected when the file is opened. If it
+    /// can't be detected, this is the default one (which is in the editor's config).
+    pub fn get_indent_style(&self, editor_id: FlexBoxId) -> IndentStyle {
+        self.editor_buffers
+            .get(&editor_id)
+            .and_then(|buffer| buffer.get_maybe_indent_style())
+            .unwrap_or_default()
+    }
+
+    /// Switch the editor to the next one of [INDENT_STYLES_TO_CYCLE_THROUGH], and return
+    /// it.
+    pub fn cycle_indent_style(&mut self, editor_id: FlexBoxId) -> IndentStyle {
+        let current = self.get_indent_style(editor_id);
+        let next = match INDENT_STYLES_TO_CYCLE_THROUGH
+            .iter()
+            .position(|it| *it == current)
+        {
+            Some(index) => {
+                INDENT_STYLES_TO_CYCLE_THROUGH
+                    [(index + 1) % INDENT_STYLES_TO_CYCLE_THROUGH.len()]
+            }
+            None => INDENT_STYLES_TO_CYCLE_THROUGH[0],
+        };
+        if let Some(buffer) = self.editor_buffers.get_mut(&editor_id) {
+            buffer.set_indent_style(next);
+        }
+        next
+    }
+
+    /// Parse the headings of the editor's content again, if it has changed. This is cheap
+    /// to call when nothing has changed.
+    pub fn update_outline(&mut self, editor_id: FlexBoxId) {
+        let Some(buffer) = self.editor_buffers.get(&editor_id) else {
+            return;
+        };
+        let panel = &mut self.outline_panel;
+        if panel.outline.update(buffer.get_lines()) {
+            panel.move_selection(0);
+        }
+    }
+
+    /// Returns the index of the heading that the editor's caret is in.
+    pub fn get_active_outline_index(&self, editor_id: FlexBoxId) -> Option<usize> {
+        let buffer = self.editor_buffers.get(&editor_id)?;
+        let caret_row = buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        self.outline_panel
+            .outline
+            .get_active_entry_index(ch!(@to_usize caret_row))
+    }
+
+    /// Show and focus the outline panel (selecting the active heading), or hide it if it
+    /// is already focused.
+    pub fn toggle_outline_panel(&mut self, editor_id: FlexBoxId) {
+        if self.outline_panel.is_focused {
+            self.outline_panel.is_visible = false;
+            self.outline_panel.is_focused = false;
+            return;
+        }
+
+        self.update_outline(editor_id);
+        let maybe_active_index = self.get_active_outline_index(editor_id);
+        let panel = &mut self.outline_panel;
+        panel.is_visible = true;
+        panel.is_focused = true;
+        panel.selected_index = maybe_active_index.unwrap_or(0);
+    }
+
+    /// Move the editor's caret to the selected heading, and give the focus back to the
+    /// editor.
+    pub fn go_to_selected_outline_entry(&mut self, editor_id: FlexBoxId) {
+        let panel = &mut self.outline_panel;
+        panel.is_focused = false;
+        let (Some(entry), Some(buffer)) = (
+            panel.outline.entries.get(panel.selected_index),
+            self.editor_buffers.get_mut(&editor_id),
+        ) else {
+            return;
+        };
+        move_caret_to_outline_entry(buffer, entry);
+    }
+
+    /// Enter or exit zen mode. The scroll position of the editor is saved when entering
+    /// zen mode, and it is restored when exiting (as long as the caret is still below and
+    /// to the right of the saved scroll offset). In all cases the editor re-validates its
+    /// scroll when the size of its viewport changes, so the caret remains visible.
+    pub fn toggle_zen_mode(&mut self, editor_id: FlexBoxId) {
+        let maybe_editor_buffer = self.editor_buffers.get_mut(&editor_id);
+
+        if !self.zen_mode.is_active {
+            self.zen_mode.is_active = true;
+            self.zen_mode.maybe_saved_scroll = maybe_editor_buffer.map(|buffer| {
+                (
+                    buffer.get_scroll_offset(),
+                    buffer.get_caret(CaretKind::ScrollAdjusted),
+                )
+            });
+            return;
+        }
+
+        self.zen_mode.is_active = false;
+        let maybe_saved_scroll = self.zen_mode.maybe_saved_scroll.take();
+        let (Some(buffer), Some((saved_scroll_offset, _))) =
+            (maybe_editor_buffer, maybe_saved_scroll)
+        else {
+            return;
+        };
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        if caret_adj.row_index < saved_scroll_offset.row_index
+            || caret_adj.col_index < saved_scroll_offset.col_index
+        {
+            return;
+        }
+
+        let (_, caret, scroll_offset, _) = buffer.get_mut();
+        *scroll_offset = saved_scroll_offset;
+        caret.row_index = caret_adj.row_index - saved_scroll_offset.row_index;
+        caret.col_index = caret_adj.col_index - saved_scroll_offset.col_index;
+    }
 }
 
 #[cfg(test)]
 mod state_tests {
-    use r3bl_core::friendly_random_id;
+    use r3bl_core::{friendly_random_id, position};
     use r3bl_tui::FlexBoxId;
 
     use super::*;
@@ -155,6 +372,143 @@ mod state_tests {
         // Delete the file.
         std::fs::remove_file(filename).unwrap();
     }
+
+    #[test]
+    fn test_zen_mode_text_column_bounds() {
+        let zen_mode = ZenMode {
+            text_column_width: ch!(80),
+            ..Default::default()
+        };
+        assert_eq!(
+            zen_mode.get_text_column_bounds(ch!(100)),
+            (ch!(10), ch!(80))
+        );
+        assert_eq!(zen_mode.get_text_column_bounds(ch!(81)), (ch!(0), ch!(80)));
+        assert_eq!(zen_mode.get_text_column_bounds(ch!(60)), (ch!(0), ch!(60)));
+    }
+
+    #[test]
+    fn test_zen_mode_text_column_width_zero_is_clamped() {
+        let zen_mode = ZenMode {
+            text_column_width: ch!(0),
+            ..Default::default()
+        };
+        assert_eq!(zen_mode.get_text_column_bounds(ch!(11)), (ch!(5), ch!(1)));
+    }
+
+    #[test]
+    fn test_toggle_zen_mode_restores_scroll() {
+        let id = FlexBoxId::from(Id::ComponentEditor);
+        let mut state = State::default();
+
+        // Pretend that the editor was scrolled before entering zen mode.
+        {
+            let buffer = state.editor_buffers.get_mut(&id).unwrap();
+            let (_, caret, scroll_offset, _) = buffer.get_mut();
+            *scroll_offset = position!(col_index: 0, row_index: 10);
+            *caret = position!(col_index: 2, row_index: 3);
+        }
+
+        state.toggle_zen_mode(id);
+        assert!(state.zen_mode.is_active);
+
+        // The editor scrolls differently in the zen mode layout.
+        {
+            let buffer = state.editor_buffers.get_mut(&id).unwrap();
+            let (_, caret, scroll_offset, _) = buffer.get_mut();
+            *scroll_offset = position!(col_index: 0, row_index: 5);
+            *caret = position!(col_index: 2, row_index: 8);
+        }
+
+        state.toggle_zen_mode(id);
+        assert!(!state.zen_mode.is_active);
+        assert_eq!(state.zen_mode.maybe_saved_scroll, None);
+
+        let buffer = state.editor_buffers.get(&id).unwrap();
+        assert_eq!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 10)
+        );
+        assert_eq!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 13)
+        );
+    }
+
+    #[test]
+    fn test_outline_panel() {
+        let id = FlexBoxId::from(Id::ComponentEditor);
+        let mut state = State::default();
+        {
+            let buffer = state.editor_buffers.get_mut(&id).unwrap();
+            buffer.set_lines(
+                ["# Title", "text", "## One", "text", "## Two", "text"]
+                    .iter()
+                    .map(|it| it.to_string())
+                    .collect(),
+            );
+            let (_, caret, _, _) = buffer.get_mut();
+            *caret = position!(col_index: 1, row_index: 3);
+        }
+
+        // Opening the panel selects the active heading.
+        state.toggle_outline_panel(id);
+        assert!(state.outline_panel.is_visible && state.outline_panel.is_focused);
+        assert_eq!(state.outline_panel.outline.entries.len(), 3);
+        assert_eq!(state.outline_panel.selected_index, 1);
+
+        state.outline_panel.move_selection(5);
+        assert_eq!(state.outline_panel.selected_index, 2);
+        state.outline_panel.move_selection(-5);
+        assert_eq!(state.outline_panel.selected_index, 0);
+        state.outline_panel.move_selection(2);
+
+        // The panel stays visible after going to a heading.
+        state.go_to_selected_outline_entry(id);
+        assert!(state.outline_panel.is_visible && !state.outline_panel.is_focused);
+        let buffer = state.editor_buffers.get(&id).unwrap();
+        assert_eq!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 4)
+        );
+        assert_eq!(state.get_active_outline_index(id), Some(2));
+
+        // Focus the panel, and then hide it.
+        state.toggle_outline_panel(id);
+        state.toggle_outline_panel(id);
+        assert!(!state.outline_panel.is_visible && !state.outline_panel.is_focused);
+    }
+
+    #[test]
+    fn test_indent_style_is_detected_and_cycled() {
+        let filename = format!(
+            "/tmp/{}_file.md",
+            friendly_random_id::generate_friendly_random_id()
+        );
+        std::fs::write(filename.clone(), "- a\n  - b\n    - c\n").unwrap();
+        let mut state = constructor::new(&Some(filename.clone()));
+        std::fs::remove_file(filename).unwrap();
+
+        let id = FlexBoxId::from(Id::ComponentEditor);
+        assert_eq!(state.get_indent_style(id), IndentStyle::Spaces(2));
+        assert_eq!(state.cycle_indent_style(id), IndentStyle::Spaces(4));
+        assert_eq!(state.cycle_indent_style(id), IndentStyle::Spaces(8));
+        assert_eq!(state.cycle_indent_style(id), IndentStyle::Tabs);
+        assert_eq!(state.cycle_indent_style(id), IndentStyle::Spaces(2));
+
+        // Nothing to detect in an empty buffer, so the default is used.
+        assert_eq!(
+            State::default().get_indent_style(id),
+            IndentStyle::default()
+        );
+    }
+
+    #[test]
+    fn test_outline_panel_bounds() {
+        let panel = OutlinePanel::default();
+        assert_eq!(panel.get_panel_bounds(ch!(120)), (ch!(88), ch!(32)));
+        assert_eq!(panel.get_panel_bounds(ch!(60)), (ch!(40), ch!(20)));
+    }
 }
 
 pub mod constructor {
@@ -165,16 +519,30 @@ pub mod constructor {
             Self {
                 editor_buffers: create_hash_map_of_editor_buffers(&None),
                 dialog_buffers: Default::default(),
+                zen_mode: Default::default(),
+                outline_panel: Default::default(),
+                is_open_link_enabled: true,
+                maybe_status_message: None,
+                recovery: Default::default(),
             }
         }
     }
 
     pub fn new(maybe_file_path: &Option<String>) -> State {
         match maybe_file_path {
-            Some(_) => State {
-                editor_buffers: create_hash_map_of_editor_buffers(maybe_file_path),
-                dialog_buffers: Default::default(),
-            },
+            Some(_) => {
+                let mut state = State {
+                    editor_buffers: create_hash_map_of_editor_buffers(maybe_file_path),
+                    dialog_buffers: Default::default(),
+                    zen_mode: Default::default(),
+                    outline_panel: Default::default(),
+                    is_open_link_enabled: true,
+                    maybe_status_message: None,
+                    recovery: Default::default(),
+                };
+                state.mark_as_saved();
+                state
+            }
             None => State::default(),
         }
     }
@@ -188,6 +556,8 @@ pub mod constructor {
                 maybe_file_path,
             );
             editor_buffer.set_lines(file_utils::get_content(maybe_file_path));
+            // Edits match the indentation of the file, unless it is ambiguous.
+            editor_buffer.detect_indent_style();
             editor_buffer
         };
 
@@ -290,6 +660,10 @@ mod impl_editor_support {
         fn contains_editor_buffer(&self, id: FlexBoxId) -> bool {
             self.editor_buffers.contains_key(&id)
         }
+
+        fn get_editor_buffer(&self, id: FlexBoxId) -> Option<&EditorBuffer> {
+            self.editor_buffers.get(&id)
+        }
     }
 }
 
@@ -319,9 +693,17 @@ mod impl_debug_format {
             "\nState [\n\
             - dialog_buffers:\n{:?}\n\
             - editor_buffers:\n{:?}\n\
+            - zen_mode: {:?}\n\
+            - is_open_link_enabled: {:?}\n\
+            - maybe_status_message: {:?}\n\
+            - recovery: {:?}\n\
             ]",
             this.dialog_buffers,
             this.editor_buffers,
+            this.zen_mode,
+            this.is_open_link_enabled,
+            this.maybe_status_message,
+            this.recovery,
         }
     }
 }
@@ -22,9 +22,12 @@ use branch_checkout_formatting::{add_spaces_to_end_of_string,
                                  get_formatted_modified_files};
 use r3bl_ansi_color::{AnsiStyledText, Style};
 use r3bl_core::{ch, get_terminal_width, CommonResult, UnicodeString};
-use r3bl_tuify::{select_from_list_with_multi_line_header, SelectionMode, StyleSheet};
+use r3bl_tuify::{SelectFromListBuilder, SelectionMode, StyleSheet};
 
-use super::{get_branches, try_get_current_branch};
+use super::{get_branches,
+            try_get_current_branch,
+            CommitLogPreviews,
+            BRANCH_PREVIEW_COMMIT_COUNT};
 use crate::{color_constants::DefaultColors::{FrozenBlue,
                                              GuardsRed,
                                              LizardGreen,
@@ -33,6 +36,7 @@ use crate::{color_constants::DefaultColors::{FrozenBlue,
                                              Orange,
                                              SlateGray},
             giti::{clap_config::BranchSubcommand,
+                   command_history,
                    report_unknown_error_and_propagate,
                    single_select_instruction_header,
                    ui_strings::UIStrings::{AlreadyOnCurrentBranch,
@@ -176,7 +180,8 @@ pub fn try_checkout_branch(
             // Below code will execute if user doesn't have any modified uncommitted files.
             let checkout_branch_command: &mut Command =
                 &mut create_git_command_to_checkout_branch(&branch_name);
-            let branch_checkout_result_output = checkout_branch_command.output();
+            let branch_checkout_result_output =
+                command_history::run_and_record(checkout_branch_command);
 
             match branch_checkout_result_output {
                 Ok(branch_checkout_output) => {
@@ -242,16 +247,30 @@ pub fn try_checkout_branch(
             let current_branch = try_get_current_branch()?;
 
             if let Ok(branches) = get_branches() {
-                // Ask user to select a branch to check out to.
-                let maybe_selected_branch = select_from_list_with_multi_line_header(
-                    instructions_and_branches,
-                    branches,
-                    Some(20),
-                    None,
-                    SelectionMode::Single,
-                    StyleSheet::default(),
+                // The recent commits on each branch are fetched in the background, so
+                // that moving the caret doesn't wait for `git log` to run.
+                let mut commit_log_previews = CommitLogPreviews::prefetch(
+                    branches
+                        .iter()
+                        .map(|branch| branch.trim_start_matches("(current) ").to_string())
+                        .collect(),
+                    &current_branch,
+                    BRANCH_PREVIEW_COMMIT_COUNT,
                 );
 
+                // Ask user to select a branch to check out to. The recent commits on the
+                // focused branch are shown in a preview pane below the branches.
+                let maybe_selected_branch = SelectFromListBuilder::new()
+                    .multi_line_header(instructions_and_branches)
+                    .items(branches)
+                    .max_height(20)
+                    .selection_mode(SelectionMode::Single)
+                    .style(StyleSheet::default())
+                    .preview(BRANCH_PREVIEW_COMMIT_COUNT, |branch: &str| {
+                        commit_log_previews.take(branch.trim_start_matches("(current) "))
+                    })
+                    .build_and_run();
+
                 // If user selected a branch, then check out to it.
                 if let Some(selected_branch) = maybe_selected_branch {
                     let selected_branch = &selected_branch[0];
@@ -259,7 +278,8 @@ pub fn try_checkout_branch(
                         selected_branch.trim_start_matches("(current) ");
                     let checkout_branch_command: &mut Command =
                         &mut create_git_command_to_checkout_branch(selected_branch);
-                    let branch_checkout_result_output = checkout_branch_command.output();
+                    let branch_checkout_result_output =
+                        command_history::run_and_record(checkout_branch_command);
 
                     match branch_checkout_result_output {
                         Ok(branch_checkout_output) => {
@@ -28,6 +28,7 @@ use crate::{color_constants::DefaultColors::{FrozenBlue,
                                              MoonlightBlue,
                                              SlateGray},
             giti::{clap_config::BranchSubcommand,
+                   command_history,
                    giti_ui_templates::report_unknown_error_and_propagate,
                    multi_select_instruction_header,
                    single_select_instruction_header,
@@ -138,7 +139,7 @@ pub fn try_delete_branch() -> CommonResult<CommandSuccessfulResponse> {
                             &mut try_delete_branch_inner::create_git_command_to_delete_branches(
                                 &branches,
                             );
-                        let result_output = command.output();
+                        let result_output = command_history::run_and_record(command);
 
                         match result_output {
                             Ok(output) => {
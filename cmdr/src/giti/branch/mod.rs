@@ -20,9 +20,11 @@ pub mod checkout;
 pub mod delete;
 pub mod giti_ui_templates;
 pub mod new;
+pub mod preview;
 
 // Re-export.
 pub use checkout::*;
 pub use delete::*;
 pub use giti_ui_templates::*;
 pub use new::*;
+pub use preview::*;
@@ -28,6 +28,7 @@ use crate::{color_constants::DefaultColors::{FrozenBlue,
                                              SlateGray},
             giti::{self,
                    clap_config::BranchSubcommand,
+                   command_history,
                    report_unknown_error_and_propagate,
                    CommandSuccessfulResponse,
                    UIStrings::{BranchAlreadyExists,
@@ -67,8 +68,9 @@ pub fn try_make_new_branch(
             // create a new branch and check it out.
             let git_command_to_create_and_switch_to_branch: &mut Command =
                 &mut create_git_command_to_create_and_switch_to_branch(&branch_name);
-            let result_create_new_branch =
-                git_command_to_create_and_switch_to_branch.output();
+            let result_create_new_branch = command_history::run_and_record(
+                git_command_to_create_and_switch_to_branch,
+            );
             match result_create_new_branch {
                 Ok(new_branch_output) => {
                     if new_branch_output.status.success() {
@@ -106,8 +108,9 @@ pub fn try_make_new_branch(
                         &mut create_git_command_to_create_and_switch_to_branch(
                             &branch_name,
                         );
-                    let result_create_new_branch =
-                        git_command_to_create_and_switch_to_branch.output();
+                    let result_create_new_branch = command_history::run_and_record(
+                        git_command_to_create_and_switch_to_branch,
+                    );
                     match result_create_new_branch {
                         Ok(new_branch_output) => {
                             if new_branch_output.status.success() {
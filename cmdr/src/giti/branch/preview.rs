@@ -0,0 +1,199 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::{HashMap, HashSet, VecDeque},
+          process::Command,
+          sync::{Arc, Condvar, Mutex},
+          thread};
+
+use crate::giti::ui_strings::UIStrings::{FailedToGetCommitLog, NoUniqueCommitsOnBranch};
+
+/// The number of commits that are shown in the preview pane when selecting a branch.
+pub const BRANCH_PREVIEW_COMMIT_COUNT: usize = 5;
+
+/// The number of background threads that run `git log` for the branches, so that a repo
+/// w/ lots of branches doesn't start a `git` process for each of them all at once.
+const PREFETCH_THREAD_COUNT: usize = 4;
+
+/// The commit log previews for the branches, which are fetched in background threads as
+/// soon as this is created. This keeps `git log` off the input path, so moving the caret
+/// to a branch doesn't block the keypress handler while `git` runs.
+///
+/// The branches are fetched in the order that they're passed in, which is the order they
+/// are shown in the list. By the time the caret reaches a branch, its preview is most
+/// likely ready. If it isn't, then [CommitLogPreviews::take] waits for it.
+pub struct CommitLogPreviews {
+    fetched: Arc<FetchedPreviews>,
+    queued_branch_names: HashSet<String>,
+    current_branch: String,
+    max_count: usize,
+}
+
+#[derive(Default)]
+struct FetchedPreviews {
+    map: Mutex<HashMap<String, Vec<String>>>,
+    on_insert: Condvar,
+}
+
+impl CommitLogPreviews {
+    /// Start fetching the previews for `branch_names` in background threads. See
+    /// [get_commit_log_preview_for_branch] for what each preview contains.
+    pub fn prefetch(
+        branch_names: Vec<String>,
+        current_branch: &str,
+        max_count: usize,
+    ) -> Self {
+        let fetched = Arc::new(FetchedPreviews::default());
+        let queued_branch_names = branch_names.iter().cloned().collect();
+        let queue = Arc::new(Mutex::new(VecDeque::from(branch_names)));
+
+        for _ in 0..PREFETCH_THREAD_COUNT {
+            let fetched = fetched.clone();
+            let queue = queue.clone();
+            let current_branch = current_branch.to_string();
+            thread::spawn(move || loop {
+                let Some(branch_name) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let lines = get_commit_log_preview_for_branch(
+                    &branch_name,
+                    &current_branch,
+                    max_count,
+                );
+                fetched.map.lock().unwrap().insert(branch_name, lines);
+                fetched.on_insert.notify_all();
+            });
+        }
+
+        Self {
+            fetched,
+            queued_branch_names,
+            current_branch: current_branch.to_string(),
+            max_count,
+        }
+    }
+
+    /// Take the preview for `branch_name`, waiting for its background thread if it hasn't
+    /// been fetched yet. It is meant to be called once for each branch, since the caller
+    /// (the preview pane) caches it. If the branch wasn't passed to
+    /// [CommitLogPreviews::prefetch] (or was already taken), then it is fetched right away.
+    pub fn take(&mut self, branch_name: &str) -> Vec<String> {
+        if !self.queued_branch_names.remove(branch_name) {
+            return get_commit_log_preview_for_branch(
+                branch_name,
+                &self.current_branch,
+                self.max_count,
+            );
+        }
+
+        let map = self.fetched.map.lock().unwrap();
+        let mut map = self
+            .fetched
+            .on_insert
+            .wait_while(map, |map| !map.contains_key(branch_name))
+            .unwrap();
+        map.remove(branch_name).unwrap_or_default()
+    }
+}
+
+/// Get the recent commits on `branch_name` to show in the preview pane when selecting a
+/// branch. Each commit is a single line containing the short hash and the subject. These
+/// lines are not clipped here, since the preview pane clips them to the width of the
+/// terminal.
+///
+/// - If `branch_name` is the current branch, then its most recent commits are returned.
+/// - Otherwise, only the commits on `branch_name` that aren't on the current branch are
+///   returned. If there are none, then a message saying so is returned instead.
+pub fn get_commit_log_preview_for_branch(
+    branch_name: &str,
+    current_branch: &str,
+    max_count: usize,
+) -> Vec<String> {
+    let mut command =
+        create_git_command_to_get_commit_log(branch_name, current_branch, max_count);
+
+    match command.output() {
+        Ok(output) if output.status.success() => {
+            let commits: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.to_string())
+                .collect();
+            if commits.is_empty() {
+                vec![NoUniqueCommitsOnBranch {
+                    branch_name: branch_name.to_string(),
+                    current_branch: current_branch.to_string(),
+                }
+                .to_string()]
+            } else {
+                commits
+            }
+        }
+        _ => vec![FailedToGetCommitLog {
+            branch_name: branch_name.to_string(),
+        }
+        .to_string()],
+    }
+}
+
+fn create_git_command_to_get_commit_log(
+    branch_name: &str,
+    current_branch: &str,
+    max_count: usize,
+) -> Command {
+    // When HEAD is detached, there is no current branch name.
+    let current_branch = if current_branch.is_empty() {
+        "HEAD"
+    } else {
+        current_branch
+    };
+
+    let revision_range = if branch_name == current_branch {
+        branch_name.to_string()
+    } else {
+        format!("{current_branch}..{branch_name}")
+    };
+
+    let mut command = Command::new("git");
+    command.args([
+        "log",
+        "--no-color",
+        "--format=%h %s",
+        &format!("--max-count={max_count}"),
+        &revision_range,
+        "--",
+    ]);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_prefetched_preview_matches_the_one_fetched_right_away() {
+        let expected = get_commit_log_preview_for_branch("HEAD", "HEAD", 2);
+
+        let mut previews =
+            CommitLogPreviews::prefetch(vec!["HEAD".to_string()], "HEAD", 2);
+        assert_eq2!(previews.take("HEAD"), expected);
+
+        // Once taken (or if never queued), the preview is fetched right away.
+        assert_eq2!(previews.take("HEAD"), expected);
+    }
+}
@@ -68,6 +68,13 @@ pub struct GlobalOption {
         help = "Disable anonymous data collection for analytics to improve the product; this data does not include IP addresses, or any other private user data, like user, branch, or repo names"
     )]
     pub no_analytics: bool,
+
+    #[arg(
+        global = true,
+        long,
+        help = "Don't save the git commands that giti runs to its history, which you can see w/ `giti history`"
+    )]
+    pub no_history: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -89,6 +96,16 @@ pub enum CLICommand {
         maybe_branch_name: Option<String>,
     },
 
+    #[clap(
+        about = "🩹 Resolve merge conflicts by picking ours, theirs, or editing each conflicted file\n💡 Eg: `giti resolve`"
+    )]
+    Resolve {},
+
+    #[clap(
+        about = "📜 Show the git commands that giti ran for you, so you can run them again yourself\n💡 Eg: `giti history`"
+    )]
+    History {},
+
     #[clap(about = "TODO Commit help")]
     Commit {},
 
@@ -0,0 +1,403 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashMap, fs, path::PathBuf, process::Command};
+
+use r3bl_ansi_color::{AnsiStyledText, Style};
+use r3bl_core::CommonResult;
+use r3bl_tuify::{select_from_list_with_multi_line_header, SelectionMode, StyleSheet};
+
+use crate::{color_constants::DefaultColors::{FrozenBlue,
+                                             GuardsRed,
+                                             LizardGreen,
+                                             MoonlightBlue,
+                                             Orange,
+                                             SlateGray},
+            giti::{command_history,
+                   report_unknown_error_and_propagate,
+                   single_select_instruction_header,
+                   ui_strings::UIStrings::{AbortResolvingConflicts,
+                                           AbortedResolvingConflicts,
+                                           FailedToResolveConflictInFile,
+                                           FailedToStageResolvedFiles,
+                                           KeepOurs,
+                                           KeepTheirs,
+                                           NoConflictedFiles,
+                                           OpenInEdi,
+                                           ResolveConflictInBinaryFile,
+                                           ResolveConflictInFile,
+                                           ResolvedAndStagedFiles,
+                                           StillHasConflictMarkers},
+                   CommandSuccessfulResponse}};
+
+/// Walk the user through each file that has a merge conflict, and let them pick how to
+/// resolve it: keep "ours", keep "theirs", or open it in `edi` to resolve it manually.
+/// Binary files can't be edited, so only "ours" or "theirs" is offered for them.
+///
+/// Nothing is staged until every file has been visited. If the user aborts at any point,
+/// the contents of every conflicted file are restored, so the working tree and the index
+/// are left exactly as they were before this command ran.
+pub fn try_resolve_conflicts() -> CommonResult<CommandSuccessfulResponse> {
+    let response = CommandSuccessfulResponse::default();
+
+    let conflicted_files = get_conflicted_files()?;
+
+    if conflicted_files.is_empty() {
+        AnsiStyledText {
+            text: &NoConflictedFiles.to_string(),
+            style: &[Style::Foreground(SlateGray.as_ansi_color())],
+        }
+        .println();
+        return Ok(response);
+    }
+
+    // Save the original contents, so that they can be restored if the user aborts.
+    let original_file_contents: HashMap<String, Vec<u8>> = conflicted_files
+        .iter()
+        .filter_map(|file| Some((file.clone(), fs::read(file).ok()?)))
+        .collect();
+
+    let mut resolved_files: Vec<String> = vec![];
+
+    for file in &conflicted_files {
+        let is_binary = original_file_contents
+            .get(file)
+            .map(|content| is_binary(content))
+            .unwrap_or(false);
+
+        match ask_user_how_to_resolve(file, is_binary) {
+            Resolution::KeepOurs => {
+                if try_checkout_side(file, "--ours")? {
+                    resolved_files.push(file.clone());
+                }
+            }
+            Resolution::KeepTheirs => {
+                if try_checkout_side(file, "--theirs")? {
+                    resolved_files.push(file.clone());
+                }
+            }
+            Resolution::OpenInEdi => {
+                if try_open_in_edi(file)? {
+                    resolved_files.push(file.clone());
+                }
+            }
+            Resolution::Abort => {
+                restore_original_file_contents(&original_file_contents);
+                AnsiStyledText {
+                    text: &AbortedResolvingConflicts.to_string(),
+                    style: &[Style::Foreground(SlateGray.as_ansi_color())],
+                }
+                .println();
+                return Ok(response);
+            }
+        }
+    }
+
+    if !resolved_files.is_empty() {
+        try_stage_resolved_files(&resolved_files)?;
+    }
+
+    Ok(response)
+}
+
+/// The choices that the user can make for each conflicted file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    KeepOurs,
+    KeepTheirs,
+    OpenInEdi,
+    Abort,
+}
+
+fn ask_user_how_to_resolve(file: &str, is_binary: bool) -> Resolution {
+    let header_text = if is_binary {
+        ResolveConflictInBinaryFile {
+            file_name: file.to_string(),
+        }
+        .to_string()
+    } else {
+        ResolveConflictInFile {
+            file_name: file.to_string(),
+        }
+        .to_string()
+    };
+
+    let default_header_style = [
+        Style::Foreground(FrozenBlue.as_ansi_color()),
+        Style::Background(MoonlightBlue.as_ansi_color()),
+    ];
+
+    let instructions_and_header = {
+        let mut instructions_and_header = single_select_instruction_header();
+        let header = AnsiStyledText {
+            text: &header_text,
+            style: &default_header_style,
+        };
+        instructions_and_header.push(vec![header]);
+        instructions_and_header
+    };
+
+    let options = get_resolution_options(is_binary);
+
+    let maybe_selected = select_from_list_with_multi_line_header(
+        instructions_and_header,
+        options.iter().map(|(it, _)| it.clone()).collect(),
+        Some(20),
+        None,
+        SelectionMode::Single,
+        StyleSheet::default(),
+    );
+
+    // Esc or Ctrl+C is the same as choosing to abort.
+    match maybe_selected {
+        Some(selected) => options
+            .into_iter()
+            .find(|(it, _)| Some(it) == selected.first())
+            .map(|(_, resolution)| resolution)
+            .unwrap_or(Resolution::Abort),
+        None => Resolution::Abort,
+    }
+}
+
+/// Binary files can't be edited in `edi`, so that option is not offered for them.
+pub fn get_resolution_options(is_binary: bool) -> Vec<(String, Resolution)> {
+    let mut options = vec![
+        (KeepOurs.to_string(), Resolution::KeepOurs),
+        (KeepTheirs.to_string(), Resolution::KeepTheirs),
+    ];
+    if !is_binary {
+        options.push((OpenInEdi.to_string(), Resolution::OpenInEdi));
+    }
+    options.push((AbortResolvingConflicts.to_string(), Resolution::Abort));
+    options
+}
+
+/// Git's own heuristic: a file is binary if it has a NUL byte in its first 8000 bytes.
+pub fn is_binary(content: &[u8]) -> bool {
+    content.iter().take(8000).any(|byte| *byte == 0)
+}
+
+/// Check for the lines that git inserts to mark the start, middle, and end of a conflict.
+pub fn has_conflict_markers(content: &str) -> bool {
+    let mut has_start = false;
+    let mut has_middle = false;
+    let mut has_end = false;
+    for line in content.lines() {
+        if line.starts_with("<<<<<<< ") || line == "<<<<<<<" {
+            has_start = true;
+        } else if line == "=======" {
+            has_middle = true;
+        } else if line.starts_with(">>>>>>> ") || line == ">>>>>>>" {
+            has_end = true;
+        }
+    }
+    has_start && has_middle && has_end
+}
+
+/// The paths that `git diff --name-only` prints are relative to the root of the repo, not
+/// to the current dir. They are joined w/ the root of the repo, so that the files can be
+/// read, checked out and opened from any sub dir of the repo.
+fn get_conflicted_files() -> CommonResult<Vec<String>> {
+    let repo_root = get_repo_root()?;
+    let mut command = create_git_command_to_get_conflicted_files();
+    match command.output() {
+        Ok(output) => Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| repo_root.join(line).to_string_lossy().to_string())
+            .collect()),
+        // Can't even execute output(), something unknown has gone wrong. Propagate the
+        // error.
+        Err(error) => report_unknown_error_and_propagate(&mut command, error),
+    }
+}
+
+fn get_repo_root() -> CommonResult<PathBuf> {
+    let mut command = create_git_command_to_get_repo_root();
+    match command.output() {
+        Ok(output) => Ok(PathBuf::from(
+            String::from_utf8_lossy(&output.stdout).trim_end(),
+        )),
+        // Can't even execute output(), something unknown has gone wrong. Propagate the
+        // error.
+        Err(error) => report_unknown_error_and_propagate(&mut command, error),
+    }
+}
+
+/// Returns `true` if the file was resolved by checking out the given side.
+fn try_checkout_side(file: &str, side: &str) -> CommonResult<bool> {
+    let mut command = create_git_command_to_checkout_side(file, side);
+    match command_history::run_and_record(&mut command) {
+        Ok(output) if output.status.success() => Ok(true),
+        Ok(output) => {
+            display_error_message(
+                FailedToResolveConflictInFile {
+                    file_name: file.to_string(),
+                    error_message: String::from_utf8_lossy(&output.stderr).to_string(),
+                }
+                .to_string(),
+            );
+            Ok(false)
+        }
+        Err(error) => report_unknown_error_and_propagate(&mut command, error),
+    }
+}
+
+/// Returns `true` if the user saved the file without any conflict markers left in it.
+fn try_open_in_edi(file: &str) -> CommonResult<bool> {
+    let mut command = Command::new("edi");
+    command.arg(file);
+    match command.status() {
+        Ok(_) => {
+            let content = fs::read_to_string(file).unwrap_or_default();
+            if has_conflict_markers(&content) {
+                AnsiStyledText {
+                    text: &StillHasConflictMarkers {
+                        file_name: file.to_string(),
+                    }
+                    .to_string(),
+                    style: &[Style::Foreground(Orange.as_ansi_color())],
+                }
+                .println();
+                Ok(false)
+            } else {
+                Ok(true)
+            }
+        }
+        Err(error) => report_unknown_error_and_propagate(&mut command, error),
+    }
+}
+
+fn try_stage_resolved_files(files: &[String]) -> CommonResult<()> {
+    let mut command = create_git_command_to_stage_files(files);
+    match command_history::run_and_record(&mut command) {
+        Ok(output) if output.status.success() => {
+            AnsiStyledText {
+                text: &ResolvedAndStagedFiles {
+                    files: files.join(", "),
+                }
+                .to_string(),
+                style: &[Style::Foreground(LizardGreen.as_ansi_color())],
+            }
+            .println();
+            Ok(())
+        }
+        Ok(output) => {
+            display_error_message(
+                FailedToStageResolvedFiles {
+                    error_message: String::from_utf8_lossy(&output.stderr).to_string(),
+                }
+                .to_string(),
+            );
+            Ok(())
+        }
+        Err(error) => report_unknown_error_and_propagate(&mut command, error),
+    }
+}
+
+fn restore_original_file_contents(original_file_contents: &HashMap<String, Vec<u8>>) {
+    for (file, content) in original_file_contents {
+        if let Err(error) = fs::write(file, content) {
+            tracing::error!("Failed to restore {file}: {error:?}");
+        }
+    }
+}
+
+fn display_error_message(message: String) {
+    AnsiStyledText {
+        text: &message,
+        style: &[Style::Foreground(GuardsRed.as_ansi_color())],
+    }
+    .println();
+}
+
+fn create_git_command_to_get_repo_root() -> Command {
+    let mut command = Command::new("git");
+    command.args(["rev-parse", "--show-toplevel"]);
+    command
+}
+
+fn create_git_command_to_get_conflicted_files() -> Command {
+    let mut command = Command::new("git");
+    command.args(["diff", "--name-only", "--diff-filter=U"]);
+    command
+}
+
+fn create_git_command_to_checkout_side(file: &str, side: &str) -> Command {
+    let mut command = Command::new("git");
+    command.args(["checkout", side, "--", file]);
+    command
+}
+
+fn create_git_command_to_stage_files(files: &[String]) -> Command {
+    let mut command = Command::new("git");
+    command.args(["add", "--"]);
+    command.args(files);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_conflict_markers() {
+        let content = "line 1\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n";
+        assert!(has_conflict_markers(content));
+
+        let content = "line 1\nresolved\n";
+        assert!(!has_conflict_markers(content));
+
+        // A markdown heading underline is not a conflict.
+        let content = "Heading\n=======\nsome text\n";
+        assert!(!has_conflict_markers(content));
+    }
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b"plain text\n"));
+        assert!(is_binary(&[0x89, 0x50, 0x4e, 0x47, 0x00, 0x01]));
+    }
+
+    #[test]
+    fn test_binary_files_cant_be_opened_in_edi() {
+        let resolutions = |is_binary| {
+            get_resolution_options(is_binary)
+                .into_iter()
+                .map(|(_, it)| it)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            resolutions(false),
+            vec![
+                Resolution::KeepOurs,
+                Resolution::KeepTheirs,
+                Resolution::OpenInEdi,
+                Resolution::Abort
+            ]
+        );
+        assert_eq!(
+            resolutions(true),
+            vec![
+                Resolution::KeepOurs,
+                Resolution::KeepTheirs,
+                Resolution::Abort
+            ]
+        );
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_ansi_color::{AnsiStyledText, Style};
+use r3bl_core::CommonResult;
+use r3bl_tuify::{SelectFromListBuilder, SelectionMode, StyleSheet};
+
+use super::{command_history, CommandHistoryEntry};
+use crate::{color_constants::DefaultColors::{FrozenBlue, MoonlightBlue, SlateGray},
+            giti::{single_select_instruction_header,
+                   ui_strings::UIStrings::{CommandHistoryExitCode,
+                                           CommandHistoryNotRun,
+                                           NoCommandsInHistory,
+                                           RunThisCommandToDoItAgain,
+                                           SelectCommandFromHistory},
+                   CommandSuccessfulResponse}};
+
+/// The number of lines of a command's output that are shown in the preview pane.
+pub const COMMAND_HISTORY_PREVIEW_LINE_COUNT: usize = 5;
+
+/// Show the git commands that giti has run, most recent first, w/ the output of the
+/// focused one in a preview pane. The selected command is printed as plain text, so that
+/// the user can copy it and run it again.
+pub fn try_show_command_history() -> CommonResult<CommandSuccessfulResponse> {
+    let entries: Vec<CommandHistoryEntry> =
+        command_history::load().into_iter().rev().collect();
+
+    if entries.is_empty() {
+        AnsiStyledText {
+            text: &NoCommandsInHistory.to_string(),
+            style: &[Style::Foreground(SlateGray.as_ansi_color())],
+        }
+        .println();
+        return Ok(CommandSuccessfulResponse::default());
+    }
+
+    let default_header_style = [
+        Style::Foreground(FrozenBlue.as_ansi_color()),
+        Style::Background(MoonlightBlue.as_ansi_color()),
+    ];
+    let select_command_header_text = &SelectCommandFromHistory.to_string();
+    let instructions_and_commands = {
+        let mut instructions_and_commands = single_select_instruction_header();
+        let header = AnsiStyledText {
+            text: select_command_header_text,
+            style: &default_header_style,
+        };
+        instructions_and_commands.push(vec![header]);
+        instructions_and_commands
+    };
+
+    let items: Vec<String> = entries.iter().map(format_entry).collect();
+
+    let maybe_selected = SelectFromListBuilder::new()
+        .multi_line_header(instructions_and_commands)
+        .items(items.clone())
+        .max_height(20)
+        .selection_mode(SelectionMode::Single)
+        .style(StyleSheet::default())
+        .preview(COMMAND_HISTORY_PREVIEW_LINE_COUNT, |item: &str| match items
+            .iter()
+            .position(|it| it == item)
+        {
+            Some(index) => get_output_preview(&entries[index]),
+            None => vec![],
+        })
+        .build_and_run();
+
+    if let Some(selected) = maybe_selected {
+        if let Some(index) = items.iter().position(|it| *it == selected[0]) {
+            AnsiStyledText {
+                text: &RunThisCommandToDoItAgain.to_string(),
+                style: &[Style::Foreground(SlateGray.as_ansi_color())],
+            }
+            .println();
+            // Plain text, so that it can be copied from the terminal as is.
+            println!("{}", entries[index].command);
+        }
+    }
+
+    Ok(CommandSuccessfulResponse::default())
+}
+
+/// Failed commands are marked w/ ❌ and their exit code, so that they stand out from the
+/// ones that succeeded.
+fn format_entry(entry: &CommandHistoryEntry) -> String {
+    let CommandHistoryEntry {
+        timestamp, command, ..
+    } = entry;
+    match (entry.is_success(), entry.maybe_exit_code) {
+        (true, _) => format!("✅ {timestamp}  {command}"),
+        (false, Some(exit_code)) => format!(
+            "❌ {timestamp}  {command}  {}",
+            CommandHistoryExitCode { exit_code }
+        ),
+        (false, None) => format!("❌ {timestamp}  {command}  {CommandHistoryNotRun}"),
+    }
+}
+
+/// The output is redacted again, since the history may have been saved by an older
+/// version of giti that didn't redact it.
+fn get_output_preview(entry: &CommandHistoryEntry) -> Vec<String> {
+    command_history::redact_output(&entry.output)
+        .lines()
+        .take(COMMAND_HISTORY_PREVIEW_LINE_COUNT)
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_entry() {
+        let mut entry = CommandHistoryEntry {
+            timestamp: "2024-10-20 13:45:01".to_string(),
+            command: "git checkout main".to_string(),
+            maybe_exit_code: Some(0),
+            output: "".to_string(),
+        };
+        assert_eq!(
+            format_entry(&entry),
+            "✅ 2024-10-20 13:45:01  git checkout main"
+        );
+
+        entry.maybe_exit_code = Some(1);
+        assert_eq!(
+            format_entry(&entry),
+            "❌ 2024-10-20 13:45:01  git checkout main  (exit code 1)"
+        );
+
+        entry.maybe_exit_code = None;
+        assert_eq!(
+            format_entry(&entry),
+            "❌ 2024-10-20 13:45:01  git checkout main  (could not run)"
+        );
+    }
+}
@@ -19,10 +19,14 @@
 pub mod branch;
 pub mod clap_config;
 pub mod common_types;
+pub mod conflict;
+pub mod history;
 pub mod ui_strings;
 
 // Re-export.
 pub use branch::*;
 pub use clap_config::*;
 pub use common_types::*;
+pub use conflict::*;
+pub use history::*;
 pub use ui_strings::*;
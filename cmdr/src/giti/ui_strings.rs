@@ -85,6 +85,45 @@ pub enum UIStrings {
     },
     EnterBranchNameYouWantToCreate,
     NoNewBranchWasCreated,
+    NoUniqueCommitsOnBranch {
+        branch_name: String,
+        current_branch: String,
+    },
+    FailedToGetCommitLog {
+        branch_name: String,
+    },
+    NoConflictedFiles,
+    ResolveConflictInFile {
+        file_name: String,
+    },
+    ResolveConflictInBinaryFile {
+        file_name: String,
+    },
+    KeepOurs,
+    KeepTheirs,
+    OpenInEdi,
+    AbortResolvingConflicts,
+    AbortedResolvingConflicts,
+    StillHasConflictMarkers {
+        file_name: String,
+    },
+    FailedToResolveConflictInFile {
+        file_name: String,
+        error_message: String,
+    },
+    ResolvedAndStagedFiles {
+        files: String,
+    },
+    FailedToStageResolvedFiles {
+        error_message: String,
+    },
+    NoCommandsInHistory,
+    SelectCommandFromHistory,
+    CommandHistoryExitCode {
+        exit_code: i32,
+    },
+    CommandHistoryNotRun,
+    RunThisCommandToDoItAgain,
 }
 
 impl Display for UIStrings {
@@ -213,6 +252,65 @@ impl Display for UIStrings {
                 UIStrings::NoNewBranchWasCreated => {
                     String::from(" No new branch was created")
                 }
+                UIStrings::NoUniqueCommitsOnBranch {
+                    branch_name,
+                    current_branch,
+                } => {
+                    format!(
+                        "No commits on '{branch_name}' that aren't already on '{current_branch}'"
+                    )
+                }
+                UIStrings::FailedToGetCommitLog { branch_name } => {
+                    format!("Failed to get the commit log for branch '{branch_name}'")
+                }
+                UIStrings::NoConflictedFiles => {
+                    String::from(" There are no files with merge conflicts 🎉")
+                }
+                UIStrings::ResolveConflictInFile { file_name } => {
+                    format!(" Resolve the conflict in '{file_name}'")
+                }
+                UIStrings::ResolveConflictInBinaryFile { file_name } => {
+                    format!(" Resolve the conflict in binary file '{file_name}'")
+                }
+                UIStrings::KeepOurs => String::from("Keep ours (the current branch)"),
+                UIStrings::KeepTheirs => String::from("Keep theirs (the incoming branch)"),
+                UIStrings::OpenInEdi => String::from("Open in edi to resolve manually"),
+                UIStrings::AbortResolvingConflicts => {
+                    String::from("Abort, and leave all files as they were")
+                }
+                UIStrings::AbortedResolvingConflicts => String::from(
+                    " Aborted, all conflicted files have been left as they were",
+                ),
+                UIStrings::StillHasConflictMarkers { file_name } => {
+                    format!(" '{file_name}' still has conflict markers, so it was not staged")
+                }
+                UIStrings::FailedToResolveConflictInFile {
+                    file_name,
+                    error_message,
+                } => {
+                    format!(
+                        " Failed to resolve the conflict in '{file_name}'!\n\n{error_message}"
+                    )
+                }
+                UIStrings::ResolvedAndStagedFiles { files } => {
+                    format!(" Resolved and staged ✅ {files}")
+                }
+                UIStrings::FailedToStageResolvedFiles { error_message } => {
+                    format!(" Failed to stage the resolved files!\n\n{error_message}")
+                }
+                UIStrings::NoCommandsInHistory => {
+                    String::from(" giti hasn't run any git commands for you yet")
+                }
+                UIStrings::SelectCommandFromHistory => String::from(
+                    " Select a git command that giti ran, to see how to run it again",
+                ),
+                UIStrings::CommandHistoryExitCode { exit_code } => {
+                    format!("(exit code {exit_code})")
+                }
+                UIStrings::CommandHistoryNotRun => String::from("(could not run)"),
+                UIStrings::RunThisCommandToDoItAgain => {
+                    String::from(" Run this command to do it again:")
+                }
             }
         }
 
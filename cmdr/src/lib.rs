@@ -145,6 +145,8 @@
 //! - To delete one or more branches in your repo run `giti branch delete`.
 //! - To checkout a branch run `giti branch checkout`.
 //! - To create a new branch run `giti branch new`.
+//! - To resolve merge conflicts one file at a time run `giti resolve`.
+//! - To see (and copy) the git commands that `giti` ran for you run `giti history`.
 //!
 //! To run from source:
 //! - Clone the `r3bl-open-core` repo.
@@ -155,6 +157,8 @@
 //! - To delete one or more branches in your repo run `giti branch delete`.
 //! - To checkout a branch run `giti branch checkout`.
 //! - To create a new branch run `giti branch new`.
+//! - To resolve merge conflicts one file at a time run `giti resolve`.
+//! - To see (and copy) the git commands that `giti` ran for you run `giti history`.
 //! - If you want to generate log output for `giti`, run `giti -l`. For example,
 //!   `giti -l branch delete`. To view this log output run `nu run log`.
 //!
@@ -173,6 +177,8 @@
 //! - Run `edi` from anywhere on your system.
 //! - Try `edi --help` to see the available commands.
 //! - To open an existing file, run `edi <file_name>`. For example, `edi README.md`.
+//! - To toggle zen mode (which hides the status bar, and centers the text) press
+//!   `Alt+Z`. To change the width of the text column, run `edi --zen-width <width>`.
 //!
 //! To run from source:
 //! - Clone the `r3bl-open-core` repo.
@@ -181,6 +187,8 @@
 //! - Run `edi` from anywhere on your system.
 //! - Try `edi --help` to see the available commands.
 //! - To open an existing file, run `edi <file_name>`. For example, `edi README.md`.
+//! - To toggle zen mode (which hides the status bar, and centers the text) press
+//!   `Alt+Z`. To change the width of the text column, run `edi --zen-width <width>`.
 //! - If you want to generate log output for `edi`, run `edi -l`. For example,
 //!   `edi -l README.md`. To view this log output run `nu run log`.
 //!
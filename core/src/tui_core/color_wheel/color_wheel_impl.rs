@@ -493,11 +493,13 @@ impl ColorWheel {
                     if let Some((bg_red, bg_green, bg_blue)) = maybe_bg_color {
                         let (fg_red, fg_green, fg_blue) =
                             ColorUtils::calc_fg_color((bg_red, bg_green, bg_blue));
+                        // The background keeps the color from the wheel, which has
+                        // already been narrowed down to what the terminal supports.
                         acc += tui_styled_text!(
                             @style: inner::gen_style_fg_bg_color_for(
                                 maybe_style,
                                 Some(TuiColor::Rgb(RgbValue::from_u8(fg_red, fg_green, fg_blue))),
-                                Some(TuiColor::Rgb(RgbValue::from_u8(bg_red, bg_green, bg_blue))),
+                                Some(next_bg_color),
                             ),
                             @text: next_character,
                         );
@@ -602,7 +604,7 @@ mod tests_color_wheel_rgb {
     use serial_test::serial;
 
     use super::*;
-    use crate::assert_eq2;
+    use crate::{assert_eq2, ColorizeTarget, LolcatBuilder};
 
     mod test_helpers {
         use super::*;
@@ -944,4 +946,51 @@ mod tests_color_wheel_rgb {
 
         global_color_support::clear_override()
     }
+
+    #[serial]
+    #[test]
+    fn test_colorize_background_w_lolcat_is_narrowed() {
+        let is_black_or_white = |color: Option<TuiColor>| {
+            color == Some(TuiColor::Rgb(RgbValue::from_u8(0, 0, 0)))
+                || color == Some(TuiColor::Rgb(RgbValue::from_u8(255, 255, 255)))
+        };
+        let colorize = || {
+            let mut color_wheel = ColorWheel::new(vec![
+                ColorWheelConfig::Lolcat(
+                    LolcatBuilder::new().set_target(ColorizeTarget::Background),
+                ),
+                ColorWheelConfig::Ansi256(
+                    Ansi256GradientIndex::MediumGreenToMediumBlue,
+                    ColorWheelSpeed::Fast,
+                ),
+            ]);
+            color_wheel.colorize_into_styled_texts(
+                &UnicodeString::from("HELLO"),
+                GradientGenerationPolicy::ReuseExistingGradientAndResetIndex,
+                TextColorizationPolicy::ColorEachCharacter(None),
+            )
+        };
+
+        // Truecolor: the gradient from the lolcat is in the background.
+        global_color_support::set_override(ColorSupport::Truecolor);
+        let styled_texts = colorize();
+        assert_eq2!(styled_texts.len(), 5);
+        for styled_text in styled_texts.inner.iter() {
+            let style = styled_text.get_style();
+            assert!(matches!(style.color_bg, Some(TuiColor::Rgb(_))));
+            assert!(is_black_or_white(style.color_fg));
+        }
+
+        // ANSI 256: the background is still colorized, using the ANSI 256 gradient.
+        global_color_support::set_override(ColorSupport::Ansi256);
+        let styled_texts = colorize();
+        assert_eq2!(styled_texts.len(), 5);
+        for styled_text in styled_texts.inner.iter() {
+            let style = styled_text.get_style();
+            assert!(matches!(style.color_bg, Some(TuiColor::Ansi(_))));
+            assert!(is_black_or_white(style.color_fg));
+        }
+
+        global_color_support::clear_override()
+    }
 }
@@ -18,7 +18,7 @@
 use r3bl_ansi_color::{global_color_support, ColorSupport};
 use serde::{Deserialize, Serialize};
 
-use super::{Lolcat, LolcatBuilder};
+use super::{ColorizeTarget, Lolcat, LolcatBuilder};
 use crate::{Ansi256GradientIndex, TuiColor};
 
 /// For RGB colors:
@@ -42,7 +42,7 @@ impl ColorWheelConfig {
     pub fn config_contains_bg_lolcat(configs: &[ColorWheelConfig]) -> bool {
         for config in configs {
             if let ColorWheelConfig::Lolcat(LolcatBuilder {
-                background_mode: true,
+                target: ColorizeTarget::Background,
                 ..
             }) = config
             {
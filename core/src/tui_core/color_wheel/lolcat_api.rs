@@ -86,6 +86,8 @@ pub fn lolcat_each_char_in_unicode_string(
 ///   same generated colors over and over again.
 /// - If you want to change where the color wheel "begins", you have to change the speed, seed, and
 ///   delta of this [Lolcat] instance.
+/// - The frequency and spread (like in the original `lolcat`) change what the gradient
+///   looks like, ie: how quickly the colors change from one grapheme to the next.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub struct LolcatBuilder {
     /// Rate at which the color changes when
@@ -95,11 +97,47 @@ pub struct LolcatBuilder {
     pub seed: f64,
     /// Delta that should be applied to the seed for it to change colors.
     pub seed_delta: f64,
-    /// - `true` means the background is colorized, and the foreground is computed for contrast. The
-    ///   primary effect here is that the background of the generated colors is what is being
-    ///   lolcat'd.
-    /// - `false` means that only the foreground color is cycled, background is left alone.
-    pub background_mode: bool,
+    /// How quickly the colors cycle through the rainbow, for each step of the seed. Higher
+    /// values make the bands of color narrower. Defaults to `0.1`.
+    pub frequency: f64,
+    /// How many steps of the seed it takes to spread one color out over. Higher values
+    /// make the bands of color wider (it must not be `0`). Defaults to `3.0`.
+    pub spread: f64,
+    /// Whether the gradient is applied to the foreground or the background color.
+    pub target: ColorizeTarget,
+    /// Which way the gradient flows when multiple lines are colorized w/
+    /// [colorize_lines](Lolcat::colorize_lines).
+    pub gradient_direction: GradientDirection,
+}
+
+/// Which color channel of the generated [crate::TuiStyle]s the gradient is applied to.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ColorizeTarget {
+    /// Only the foreground color is cycled, the background is left alone.
+    #[default]
+    Foreground,
+    /// The background color is cycled, and the foreground is black or white (based on the
+    /// luminance of the background) for contrast. This is useful for banners and headers.
+    /// When it is used in a [crate::ColorWheel], the background colors are still narrowed
+    /// down to what the terminal supports (truecolor, ANSI 256, or grayscale).
+    Background,
+}
+
+/// Which way the gradient flows across multiple lines, when they are colorized w/
+/// [colorize_lines](Lolcat::colorize_lines). Each step (between graphemes, or lines) is
+/// the [ColorChangeSpeed].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// The color changes w/ each grapheme in a line, and every line starts w/ the same
+    /// color, so the colors are in columns.
+    #[default]
+    Horizontal,
+    /// Each line has a single color, which changes w/ each line, so the colors are in
+    /// rows.
+    Vertical,
+    /// The color changes w/ each grapheme in a line, and each line starts one step
+    /// further along than the line above it, so the colors run diagonally.
+    Diagonal,
 }
 
 impl Default for LolcatBuilder {
@@ -108,7 +146,10 @@ impl Default for LolcatBuilder {
             color_change_speed: ColorChangeSpeed::Slow,
             seed: 1.0,
             seed_delta: 1.0,
-            background_mode: false, /* color only the foreground */
+            frequency: 0.1,
+            spread: 3.0,
+            target: ColorizeTarget::Foreground,
+            gradient_direction: GradientDirection::Horizontal,
         }
     }
 }
@@ -116,11 +157,21 @@ impl Default for LolcatBuilder {
 impl LolcatBuilder {
     pub fn new() -> Self { Self::default() }
 
-    pub fn set_background_mode(mut self, background_mode: bool) -> Self {
-        self.background_mode = background_mode;
+    pub fn set_target(mut self, target: ColorizeTarget) -> Self {
+        self.target = target;
         self
     }
 
+    /// Same as [set_target](LolcatBuilder::set_target) w/ [ColorizeTarget::Background]
+    /// (when `true`), or [ColorizeTarget::Foreground] (when `false`).
+    pub fn set_background_mode(self, background_mode: bool) -> Self {
+        self.set_target(if background_mode {
+            ColorizeTarget::Background
+        } else {
+            ColorizeTarget::Foreground
+        })
+    }
+
     pub fn set_color_change_speed(
         mut self,
         color_change_speed: ColorChangeSpeed,
@@ -139,15 +190,37 @@ impl LolcatBuilder {
         self
     }
 
+    pub fn set_frequency(mut self, frequency: f64) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn set_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn set_gradient_direction(
+        mut self,
+        gradient_direction: GradientDirection,
+    ) -> Self {
+        self.gradient_direction = gradient_direction;
+        self
+    }
+
     pub fn build(self) -> Lolcat {
         let mut new_lolcat = Lolcat {
             seed_delta: self.seed_delta,
             color_wheel_control: Default::default(),
+            gradient_direction: self.gradient_direction,
         };
 
         new_lolcat.color_wheel_control.color_change_speed = self.color_change_speed;
         new_lolcat.color_wheel_control.seed = self.seed;
-        new_lolcat.color_wheel_control.background_mode = self.background_mode;
+        new_lolcat.color_wheel_control.frequency = self.frequency;
+        new_lolcat.color_wheel_control.spread = self.spread;
+        new_lolcat.color_wheel_control.background_mode =
+            self.target == ColorizeTarget::Background;
 
         new_lolcat
     }
@@ -155,6 +228,11 @@ impl LolcatBuilder {
     pub fn apply(&self, lolcat: &mut Lolcat) {
         lolcat.color_wheel_control.color_change_speed = self.color_change_speed;
         lolcat.color_wheel_control.seed = self.seed;
+        lolcat.color_wheel_control.frequency = self.frequency;
+        lolcat.color_wheel_control.spread = self.spread;
+        lolcat.color_wheel_control.background_mode =
+            self.target == ColorizeTarget::Background;
         lolcat.seed_delta = self.seed_delta;
+        lolcat.gradient_direction = self.gradient_direction;
     }
 }
@@ -19,7 +19,7 @@ use std::fmt::{Debug, Formatter, Result};
 
 use serde::{Deserialize, Serialize};
 
-use super::LolcatBuilder;
+use super::{GradientDirection, LolcatBuilder};
 use crate::{tui_styled_text,
             ColorUtils,
             ColorWheelControl,
@@ -35,6 +35,7 @@ use crate::{tui_styled_text,
 pub struct Lolcat {
     pub color_wheel_control: ColorWheelControl,
     pub seed_delta: f64,
+    pub gradient_direction: GradientDirection,
 }
 
 impl Default for Lolcat {
@@ -65,36 +66,7 @@ impl Lolcat {
         let mut acc = TuiStyledTexts::default();
 
         for segment in &input.vec_segment {
-            let new_color = ColorUtils::get_color_tuple(&self.color_wheel_control);
-            let derived_from_new_color = ColorUtils::calc_fg_color(new_color);
-
-            let style = if self.color_wheel_control.background_mode {
-                TuiStyle {
-                    color_fg: TuiColor::Rgb(RgbValue::from_u8(
-                        derived_from_new_color.0,
-                        derived_from_new_color.1,
-                        derived_from_new_color.2,
-                    ))
-                    .into(),
-                    color_bg: TuiColor::Rgb(RgbValue::from_u8(
-                        new_color.0,
-                        new_color.1,
-                        new_color.2,
-                    ))
-                    .into(),
-                    ..Default::default()
-                }
-            } else {
-                TuiStyle {
-                    color_fg: TuiColor::Rgb(RgbValue::from_u8(
-                        new_color.0,
-                        new_color.1,
-                        new_color.2,
-                    ))
-                    .into(),
-                    ..Default::default()
-                }
-            };
+            let style = self.get_current_style();
 
             acc += tui_styled_text!(
                 @style: style,
@@ -108,5 +80,179 @@ impl Lolcat {
         acc
     }
 
+    /// Colorize each of the `lines`, w/ the gradient flowing in the
+    /// [GradientDirection] that this was built w/. Each line starts from the current seed,
+    /// plus one step for each line above it (except for
+    /// [GradientDirection::Horizontal]). Afterwards the seed is where the last line ends.
+    ///
+    /// Like [colorize_to_styled_texts](Lolcat::colorize_to_styled_texts), this always
+    /// colorizes to truecolor.
+    pub fn colorize_lines(&mut self, lines: &[UnicodeString]) -> Vec<TuiStyledTexts> {
+        let start_seed = self.color_wheel_control.seed;
+        let step = f64::from(self.color_wheel_control.color_change_speed);
+
+        lines
+            .iter()
+            .enumerate()
+            .map(|(line_index, line)| match self.gradient_direction {
+                GradientDirection::Horizontal => {
+                    self.color_wheel_control.seed = start_seed;
+                    self.colorize_to_styled_texts(line)
+                }
+                GradientDirection::Vertical => {
+                    self.color_wheel_control.seed = start_seed + step * line_index as f64;
+                    let mut acc = TuiStyledTexts::default();
+                    if !line.string.is_empty() {
+                        acc += tui_styled_text!(
+                            @style: self.get_current_style(),
+                            @text: line.string.clone(),
+                        );
+                    }
+                    self.color_wheel_control.seed += step;
+                    acc
+                }
+                GradientDirection::Diagonal => {
+                    self.color_wheel_control.seed = start_seed + step * line_index as f64;
+                    self.colorize_to_styled_texts(line)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the style for the current seed.
+    fn get_current_style(&self) -> TuiStyle {
+        let new_color = ColorUtils::get_color_tuple(&self.color_wheel_control);
+        let derived_from_new_color = ColorUtils::calc_fg_color(new_color);
+
+        if self.color_wheel_control.background_mode {
+            TuiStyle {
+                color_fg: TuiColor::Rgb(RgbValue::from_u8(
+                    derived_from_new_color.0,
+                    derived_from_new_color.1,
+                    derived_from_new_color.2,
+                ))
+                .into(),
+                color_bg: TuiColor::Rgb(RgbValue::from_u8(
+                    new_color.0,
+                    new_color.1,
+                    new_color.2,
+                ))
+                .into(),
+                ..Default::default()
+            }
+        } else {
+            TuiStyle {
+                color_fg: TuiColor::Rgb(RgbValue::from_u8(
+                    new_color.0,
+                    new_color.1,
+                    new_color.2,
+                ))
+                .into(),
+                ..Default::default()
+            }
+        }
+    }
+
     pub fn next_color(&mut self) { self.color_wheel_control.seed += self.seed_delta; }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_eq2, ColorChangeSpeed, ColorizeTarget};
+
+    fn get_fg_colors(styled_texts: &TuiStyledTexts) -> Vec<Option<TuiColor>> {
+        styled_texts
+            .inner
+            .iter()
+            .map(|it| it.get_style().color_fg)
+            .collect()
+    }
+
+    fn colorize_lines(direction: GradientDirection) -> Vec<TuiStyledTexts> {
+        let mut lolcat = LolcatBuilder::new()
+            .set_color_change_speed(ColorChangeSpeed::Rapid)
+            .set_gradient_direction(direction)
+            .build();
+        let lines = ["abc", "abc", "abc"].map(UnicodeString::from);
+        lolcat.colorize_lines(&lines)
+    }
+
+    #[test]
+    fn test_colorize_lines_vertical() {
+        let lines = colorize_lines(GradientDirection::Vertical);
+
+        // Each line has a single color.
+        for line in &lines {
+            assert_eq2!(line.len(), 1);
+            assert_eq2!(line.inner[0].get_text().string, "abc");
+        }
+
+        // Which is different on each line.
+        let colors: Vec<_> = lines.iter().flat_map(get_fg_colors).collect();
+        assert_ne!(colors[0], colors[1]);
+        assert_ne!(colors[1], colors[2]);
+    }
+
+    #[test]
+    fn test_colorize_lines_horizontal_and_diagonal() {
+        let lines = colorize_lines(GradientDirection::Horizontal);
+        assert_eq2!(get_fg_colors(&lines[0]), get_fg_colors(&lines[1]));
+        assert_ne!(get_fg_colors(&lines[0])[0], get_fg_colors(&lines[0])[1]);
+
+        // Each line is shifted by one step, so the 2nd color of a line is the 1st color of
+        // the next one.
+        let lines = colorize_lines(GradientDirection::Diagonal);
+        assert_eq2!(get_fg_colors(&lines[0])[1], get_fg_colors(&lines[1])[0]);
+        assert_eq2!(get_fg_colors(&lines[1])[2], get_fg_colors(&lines[2])[1]);
+        assert_ne!(get_fg_colors(&lines[0]), get_fg_colors(&lines[1]));
+    }
+
+    #[test]
+    fn test_colorize_target_background() {
+        let mut lolcat = LolcatBuilder::new()
+            .set_target(ColorizeTarget::Background)
+            .build();
+        let styled_texts = lolcat.colorize_to_styled_texts(&"abc".into());
+        for styled_text in styled_texts.inner.iter() {
+            let style = styled_text.get_style();
+            let Some(TuiColor::Rgb(bg)) = style.color_bg else {
+                panic!("The background should be colorized");
+            };
+            // The foreground is black or white, for contrast.
+            let (red, green, blue) =
+                ColorUtils::calc_fg_color((bg.red, bg.green, bg.blue));
+            assert_eq2!(
+                style.color_fg,
+                Some(TuiColor::Rgb(RgbValue::from_u8(red, green, blue)))
+            );
+        }
+
+        // The default target is the foreground.
+        let mut lolcat = LolcatBuilder::new().build();
+        let styled_texts = lolcat.colorize_to_styled_texts(&"abc".into());
+        assert_eq2!(styled_texts.inner[0].get_style().color_bg, None);
+    }
+
+    #[test]
+    fn test_frequency_and_spread() {
+        fn colorize(builder: LolcatBuilder) -> Vec<Option<TuiColor>> {
+            let mut lolcat = builder.build();
+            get_fg_colors(&lolcat.colorize_to_styled_texts(&"Hello, world!".into()))
+        }
+
+        // The defaults match the ones used by the color wheel.
+        let default_colors = colorize(LolcatBuilder::new());
+        assert_eq2!(
+            default_colors,
+            colorize(LolcatBuilder::new().set_frequency(0.1).set_spread(3.0))
+        );
+
+        let low_frequency = colorize(LolcatBuilder::new().set_frequency(0.1));
+        let high_frequency = colorize(LolcatBuilder::new().set_frequency(2.0));
+        assert_ne!(low_frequency, high_frequency);
+
+        let wide_spread = colorize(LolcatBuilder::new().set_spread(8.0));
+        assert_ne!(default_colors, wide_spread);
+    }
+}
@@ -19,12 +19,14 @@
 pub mod ch_unit;
 pub mod percent;
 pub mod position;
+pub mod scroll_ratio;
 pub mod size;
 
 // Re-export.
 pub use ch_unit::*;
 pub use percent::*;
 pub use position::*;
+pub use scroll_ratio::*;
 pub use size::*;
 
 // Tests.
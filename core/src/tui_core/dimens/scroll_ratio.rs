@@ -0,0 +1,71 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use crate::{ch, ChUnit, ChUnitPrimitiveType};
+
+/// Returns the scroll offset (the index of the first visible row) that shows the position
+/// at `ratio` of the content, where `0.0` is the start and `1.0` is the end.
+///
+/// - The scroll offset is clamped so that the last page is never scrolled past the end of
+///   the content, so `1.0` shows the last `viewport_length` rows.
+/// - `ratio` values outside of `[0.0, 1.0]` are clamped, and `NaN` is treated as `0.0`.
+/// - If all the content fits in the viewport, then the scroll offset is always `0`.
+pub fn get_scroll_offset_for_ratio(
+    ratio: f64,
+    content_length: ChUnit,
+    viewport_length: ChUnit,
+) -> ChUnit {
+    let max_scroll_offset = content_length - viewport_length;
+    let ratio = if ratio.is_nan() {
+        0.0
+    } else {
+        ratio.clamp(0.0, 1.0)
+    };
+    let scroll_offset = (ratio * f64::from(max_scroll_offset.value)).round();
+    ch!(scroll_offset as ChUnitPrimitiveType)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_eq2;
+
+    #[test]
+    fn test_get_scroll_offset_for_ratio() {
+        // 100 rows of content, 10 rows visible, so the max scroll offset is 90.
+        assert_eq2!(get_scroll_offset_for_ratio(0.0, ch!(100), ch!(10)), ch!(0));
+        assert_eq2!(get_scroll_offset_for_ratio(0.5, ch!(100), ch!(10)), ch!(45));
+        assert_eq2!(get_scroll_offset_for_ratio(1.0, ch!(100), ch!(10)), ch!(90));
+
+        // Out of range values are clamped.
+        assert_eq2!(get_scroll_offset_for_ratio(-1.0, ch!(100), ch!(10)), ch!(0));
+        assert_eq2!(get_scroll_offset_for_ratio(2.5, ch!(100), ch!(10)), ch!(90));
+        assert_eq2!(
+            get_scroll_offset_for_ratio(f64::NAN, ch!(100), ch!(10)),
+            ch!(0)
+        );
+        assert_eq2!(
+            get_scroll_offset_for_ratio(f64::INFINITY, ch!(100), ch!(10)),
+            ch!(90)
+        );
+
+        // Content that fits in the viewport can't be scrolled.
+        assert_eq2!(get_scroll_offset_for_ratio(0.5, ch!(5), ch!(10)), ch!(0));
+        assert_eq2!(get_scroll_offset_for_ratio(1.0, ch!(10), ch!(10)), ch!(0));
+        assert_eq2!(get_scroll_offset_for_ratio(1.0, ch!(0), ch!(0)), ch!(0));
+    }
+}
@@ -169,6 +169,7 @@ pub mod convert;
 pub mod grapheme_cluster_segment;
 pub mod range;
 pub mod result_types;
+pub mod truncation_indicator;
 pub mod unicode_string;
 
 // Re-export.
@@ -176,6 +177,7 @@ pub use convert::*;
 pub use grapheme_cluster_segment::*;
 pub use range::*;
 pub use result_types::*;
+pub use truncation_indicator::*;
 pub use unicode_string::*;
 
 // Tests.
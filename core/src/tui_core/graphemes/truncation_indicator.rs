@@ -0,0 +1,172 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Text that is too wide for the space that it is painted in is truncated, and a
+//! [TruncationIndicator] is painted at the end to show that something was cut off. All
+//! the components that truncate text use [UnicodeString::truncate_end_with_indicator],
+//! so that the indicator is measured (it may contain wide graphemes) and placed the same
+//! way everywhere.
+
+use crate::{ChUnit, TuiStyle, UnicodeString};
+
+/// The text (and optional style) that is painted at the end of truncated text. The style
+/// type `S` is generic, since the components that use this have their own style types.
+/// If there is no style, then the indicator is painted in the style of the text that was
+/// truncated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TruncationIndicator<S = TuiStyle> {
+    pub text: &'static str,
+    pub maybe_style: Option<S>,
+}
+
+/// A single (1 column wide) ellipsis character.
+pub const ELLIPSIS: &str = "…";
+
+/// Three periods, which is 3 columns wide.
+pub const THREE_DOTS: &str = "...";
+
+impl<S> Default for TruncationIndicator<S> {
+    fn default() -> Self { Self::ellipsis() }
+}
+
+impl<S> TruncationIndicator<S> {
+    pub const fn new(text: &'static str) -> Self {
+        Self {
+            text,
+            maybe_style: None,
+        }
+    }
+
+    /// See [ELLIPSIS].
+    pub const fn ellipsis() -> Self { Self::new(ELLIPSIS) }
+
+    /// See [THREE_DOTS].
+    pub const fn three_dots() -> Self { Self::new(THREE_DOTS) }
+
+    pub fn with_style(mut self, style: S) -> Self {
+        self.maybe_style = Some(style);
+        self
+    }
+
+    pub fn get_display_width(&self) -> ChUnit {
+        UnicodeString::from(self.text).display_width
+    }
+
+    /// Truncate the `text` to fit in `max_display_col_count` columns, and append the
+    /// indicator if it was truncated. The indicator isn't styled, so this is meant for
+    /// text that is painted in a single style.
+    pub fn truncate_end(&self, text: &str, max_display_col_count: ChUnit) -> String {
+        let text = UnicodeString::from(text);
+        match text.truncate_end_with_indicator(max_display_col_count, self) {
+            (truncated_text, true) => format!("{truncated_text}{}", self.text),
+            (truncated_text, false) => truncated_text.to_string(),
+        }
+    }
+}
+
+impl UnicodeString {
+    /// Returns the part of the string that fits in `max_display_col_count` columns, while
+    /// leaving room for the `truncation_indicator` at the end, and whether the indicator
+    /// must be painted after it.
+    /// - If the whole string fits, then it is returned as is (w/out the indicator).
+    /// - If the indicator itself doesn't fit, then the string is clipped w/out it, so the
+    ///   result never exceeds the given width.
+    ///
+    /// Grapheme clusters are never split, so the result may be narrower than the given
+    /// width.
+    ///
+    /// ```rust
+    /// use r3bl_core::{ch, TruncationIndicator, UnicodeString};
+    ///
+    /// let indicator: TruncationIndicator = TruncationIndicator::ellipsis();
+    /// let text = UnicodeString::from("abcdef");
+    /// assert_eq!(text.truncate_end_with_indicator(ch!(4), &indicator), ("abc", true));
+    /// assert_eq!(text.truncate_end_with_indicator(ch!(6), &indicator), ("abcdef", false));
+    /// ```
+    pub fn truncate_end_with_indicator<S>(
+        &self,
+        max_display_col_count: ChUnit,
+        truncation_indicator: &TruncationIndicator<S>,
+    ) -> (&str, bool) {
+        if self.display_width <= max_display_col_count {
+            return (&self.string, false);
+        }
+
+        let indicator_display_width = truncation_indicator.get_display_width();
+        if indicator_display_width > max_display_col_count {
+            return (self.truncate_end_to_fit_width(max_display_col_count), false);
+        }
+
+        (
+            self.truncate_end_to_fit_width(
+                max_display_col_count - indicator_display_width,
+            ),
+            true,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_eq2, ch};
+
+    #[test]
+    fn test_truncate_end_with_indicator() {
+        let ellipsis: TruncationIndicator = TruncationIndicator::ellipsis();
+        let three_dots: TruncationIndicator = TruncationIndicator::three_dots();
+        let text = UnicodeString::from("ab😃cd");
+
+        // Fits, so there's no indicator.
+        assert_eq2!(
+            text.truncate_end_with_indicator(ch!(6), &ellipsis),
+            ("ab😃cd", false)
+        );
+
+        // The wide grapheme is never split.
+        assert_eq2!(
+            text.truncate_end_with_indicator(ch!(5), &ellipsis),
+            ("ab😃", true)
+        );
+        assert_eq2!(
+            text.truncate_end_with_indicator(ch!(4), &ellipsis),
+            ("ab", true)
+        );
+        assert_eq2!(
+            text.truncate_end_with_indicator(ch!(5), &three_dots),
+            ("ab", true)
+        );
+
+        // The indicator doesn't fit.
+        assert_eq2!(
+            text.truncate_end_with_indicator(ch!(2), &three_dots),
+            ("ab", false)
+        );
+        assert_eq2!(
+            text.truncate_end_with_indicator(ch!(0), &ellipsis),
+            ("", false)
+        );
+    }
+
+    #[test]
+    fn test_wide_indicator() {
+        let indicator: TruncationIndicator = TruncationIndicator::new("⏩");
+        assert_eq2!(indicator.get_display_width(), ch!(2));
+        assert_eq2!(indicator.truncate_end("abcdef", ch!(5)), "abc⏩");
+        assert_eq2!(indicator.truncate_end("abcdef", ch!(1)), "a");
+    }
+}
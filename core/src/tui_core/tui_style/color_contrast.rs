@@ -0,0 +1,292 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Helpers to keep text legible when the fg and bg colors are too close to each other.
+//! The contrast between them is measured using the
+//! [WCAG](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) contrast ratio (which ranges
+//! from `1.0` to `21.0`), and the fg color can be lightened or darkened (which preserves
+//! its hue) to meet a minimum contrast ratio.
+//!
+//! The colors are measured as they are actually painted, which depends on the color
+//! support of the terminal (eg: an RGB color is downgraded to ANSI 256 or grayscale). This
+//! is opt-in, see [TuiStyle::with_min_contrast_ratio].
+
+use r3bl_ansi_color::{color_utils::srgb_to_linear,
+                      global_color_support,
+                      Ansi256Color,
+                      ColorSupport,
+                      TransformColor};
+
+use crate::{AnsiValue, RgbValue, TuiColor, TuiStyle};
+
+/// The minimum contrast ratio for normal text in WCAG level AA.
+pub const DEFAULT_MIN_CONTRAST_RATIO: f64 = 4.5;
+
+/// The number of steps that are tried when mixing the fg color towards white or black.
+const ADJUST_STEP_COUNT: u8 = 20;
+
+/// Returns a value between `0.0` (black) and `1.0` (white).
+pub fn get_relative_luminance(color: RgbValue) -> f64 {
+    let red = srgb_to_linear(f64::from(color.red) / 255.0);
+    let green = srgb_to_linear(f64::from(color.green) / 255.0);
+    let blue = srgb_to_linear(f64::from(color.blue) / 255.0);
+    0.2126 * red + 0.7152 * green + 0.0722 * blue
+}
+
+/// Returns a value between `1.0` (no contrast) and `21.0` (black on white). The order of
+/// the arguments does not matter.
+pub fn get_contrast_ratio(lhs: RgbValue, rhs: RgbValue) -> f64 {
+    let lhs_luminance = get_relative_luminance(lhs);
+    let rhs_luminance = get_relative_luminance(rhs);
+    let (lighter, darker) = if lhs_luminance > rhs_luminance {
+        (lhs_luminance, rhs_luminance)
+    } else {
+        (rhs_luminance, lhs_luminance)
+    };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Returns the RGB value that is painted for the `color`, once it has been downgraded
+/// for the `color_support` (the same way as the crossterm color converter does it).
+/// Returns [None] for [TuiColor::Reset], since it depends on the terminal's theme.
+pub fn get_painted_rgb_value(
+    color: TuiColor,
+    color_support: ColorSupport,
+) -> Option<RgbValue> {
+    let rgb_value = match color {
+        TuiColor::Reset => return None,
+        TuiColor::Basic(_) => RgbValue::try_from_tui_color(color).ok()?,
+        TuiColor::Ansi(ansi_value) => RgbValue::from(ansi_value),
+        TuiColor::Rgb(rgb_value) => match color_support {
+            ColorSupport::Ansi256 => RgbValue::from(AnsiValue::from(rgb_value)),
+            _ => rgb_value,
+        },
+    };
+
+    match color_support {
+        ColorSupport::Truecolor | ColorSupport::Ansi256 => Some(rgb_value),
+        ColorSupport::Grayscale | ColorSupport::NoColor => {
+            let ansi_grayscale_color: Ansi256Color = r3bl_ansi_color::Color::Rgb(
+                rgb_value.red,
+                rgb_value.green,
+                rgb_value.blue,
+            )
+            .as_grayscale();
+            Some(RgbValue::from(AnsiValue::new(ansi_grayscale_color.index)))
+        }
+    }
+}
+
+/// Returns the `fg_color` as is if its contrast w/ the `bg_color` (as painted for the
+/// `color_support`) is at least `min_contrast_ratio`. Otherwise, the `fg_color` is mixed
+/// towards white or black (trying the direction that increases the contrast first) just
+/// enough to meet it. If it can't be met, then white or black is returned, whichever has
+/// more contrast.
+pub fn adjust_fg_color_for_contrast(
+    fg_color: TuiColor,
+    bg_color: TuiColor,
+    min_contrast_ratio: f64,
+    color_support: ColorSupport,
+) -> TuiColor {
+    let (Some(fg_rgb_value), Some(bg_rgb_value)) = (
+        get_painted_rgb_value(fg_color, ColorSupport::Truecolor),
+        get_painted_rgb_value(bg_color, color_support),
+    ) else {
+        return fg_color;
+    };
+
+    let get_painted_contrast_ratio = |color: TuiColor| -> f64 {
+        get_painted_rgb_value(color, color_support)
+            .map(|it| get_contrast_ratio(it, bg_rgb_value))
+            .unwrap_or_default()
+    };
+
+    if get_painted_contrast_ratio(fg_color) >= min_contrast_ratio {
+        return fg_color;
+    }
+
+    let white = RgbValue::from_u8(255, 255, 255);
+    let black = RgbValue::from_u8(0, 0, 0);
+    let targets =
+        if get_relative_luminance(fg_rgb_value) >= get_relative_luminance(bg_rgb_value) {
+            [white, black]
+        } else {
+            [black, white]
+        };
+
+    for target in targets {
+        for step in 1..=ADJUST_STEP_COUNT {
+            let amount = f64::from(step) / f64::from(ADJUST_STEP_COUNT);
+            let candidate = TuiColor::Rgb(mix_rgb_values(fg_rgb_value, target, amount));
+            if get_painted_contrast_ratio(candidate) >= min_contrast_ratio {
+                return candidate;
+            }
+        }
+    }
+
+    if get_painted_contrast_ratio(TuiColor::Rgb(white))
+        >= get_painted_contrast_ratio(TuiColor::Rgb(black))
+    {
+        TuiColor::Rgb(white)
+    } else {
+        TuiColor::Rgb(black)
+    }
+}
+
+/// Linear interpolation from `from` (`amount` is `0.0`) to `to` (`amount` is `1.0`).
+fn mix_rgb_values(from: RgbValue, to: RgbValue, amount: f64) -> RgbValue {
+    let mix = |from: u8, to: u8| -> u8 {
+        (f64::from(from) + (f64::from(to) - f64::from(from)) * amount).round() as u8
+    };
+    RgbValue::from_u8(
+        mix(from.red, to.red),
+        mix(from.green, to.green),
+        mix(from.blue, to.blue),
+    )
+}
+
+impl TuiStyle {
+    /// Adjust the fg color (if needed), so that its contrast w/ the bg color is at least
+    /// `min_contrast_ratio` (eg: [DEFAULT_MIN_CONTRAST_RATIO]). Nothing is changed if
+    /// either color isn't set. See [adjust_fg_color_for_contrast].
+    pub fn with_min_contrast_ratio(mut self, min_contrast_ratio: f64) -> Self {
+        if let (Some(color_fg), Some(color_bg)) = (self.color_fg, self.color_bg) {
+            self.color_fg = Some(adjust_fg_color_for_contrast(
+                color_fg,
+                color_bg,
+                min_contrast_ratio,
+                global_color_support::detect(),
+            ));
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_eq2, ANSIBasicColor};
+
+    #[test]
+    fn test_get_contrast_ratio() {
+        let white = RgbValue::from_u8(255, 255, 255);
+        let black = RgbValue::from_u8(0, 0, 0);
+        assert_eq2!(get_relative_luminance(white), 1.0);
+        assert_eq2!(get_relative_luminance(black), 0.0);
+        assert_eq2!(get_contrast_ratio(white, black), 21.0);
+        assert_eq2!(get_contrast_ratio(black, white), 21.0);
+        assert_eq2!(get_contrast_ratio(white, white), 1.0);
+    }
+
+    #[test]
+    fn test_adjust_fg_color_keeps_legible_colors() {
+        let fg_color = TuiColor::Basic(ANSIBasicColor::White);
+        let bg_color = TuiColor::Rgb(RgbValue::from_u8(20, 20, 20));
+        assert_eq2!(
+            adjust_fg_color_for_contrast(
+                fg_color,
+                bg_color,
+                DEFAULT_MIN_CONTRAST_RATIO,
+                ColorSupport::Truecolor
+            ),
+            fg_color
+        );
+
+        // Nothing can be measured against the terminal's default colors.
+        assert_eq2!(
+            adjust_fg_color_for_contrast(
+                TuiColor::Reset,
+                bg_color,
+                DEFAULT_MIN_CONTRAST_RATIO,
+                ColorSupport::Truecolor
+            ),
+            TuiColor::Reset
+        );
+    }
+
+    #[test]
+    fn test_adjust_fg_color_preserves_hue() {
+        // Dark blue on black is lightened.
+        let bg_color = TuiColor::Rgb(RgbValue::from_u8(0, 0, 0));
+        let fg_color = TuiColor::Rgb(RgbValue::from_u8(20, 20, 120));
+        let adjusted_color = adjust_fg_color_for_contrast(
+            fg_color,
+            bg_color,
+            DEFAULT_MIN_CONTRAST_RATIO,
+            ColorSupport::Truecolor,
+        );
+        let TuiColor::Rgb(adjusted_rgb_value) = adjusted_color else {
+            panic!("Expected an RGB color");
+        };
+        assert!(adjusted_rgb_value.blue > adjusted_rgb_value.red);
+        assert!(adjusted_rgb_value.red == adjusted_rgb_value.green);
+        assert!(
+            get_contrast_ratio(adjusted_rgb_value, RgbValue::from_u8(0, 0, 0))
+                >= DEFAULT_MIN_CONTRAST_RATIO
+        );
+
+        // Light yellow on white is darkened.
+        let bg_color = TuiColor::Basic(ANSIBasicColor::White);
+        let fg_color = TuiColor::Rgb(RgbValue::from_u8(255, 255, 200));
+        let TuiColor::Rgb(adjusted_rgb_value) = adjust_fg_color_for_contrast(
+            fg_color,
+            bg_color,
+            DEFAULT_MIN_CONTRAST_RATIO,
+            ColorSupport::Truecolor,
+        ) else {
+            panic!("Expected an RGB color");
+        };
+        assert!(adjusted_rgb_value.red > adjusted_rgb_value.blue);
+        assert!(adjusted_rgb_value.red < 255);
+    }
+
+    #[test]
+    fn test_adjust_fg_color_for_downgraded_colors() {
+        let bg_color = TuiColor::Rgb(RgbValue::from_u8(40, 40, 40));
+        let fg_color = TuiColor::Rgb(RgbValue::from_u8(70, 60, 60));
+        for color_support in [
+            ColorSupport::Truecolor,
+            ColorSupport::Ansi256,
+            ColorSupport::Grayscale,
+        ] {
+            let adjusted_color = adjust_fg_color_for_contrast(
+                fg_color,
+                bg_color,
+                DEFAULT_MIN_CONTRAST_RATIO,
+                color_support,
+            );
+            let contrast_ratio = get_contrast_ratio(
+                get_painted_rgb_value(adjusted_color, color_support).unwrap(),
+                get_painted_rgb_value(bg_color, color_support).unwrap(),
+            );
+            assert!(contrast_ratio >= DEFAULT_MIN_CONTRAST_RATIO);
+        }
+    }
+
+    #[test]
+    fn test_tui_style_with_min_contrast_ratio() {
+        // No bg color, so nothing is changed.
+        let style = TuiStyle {
+            color_fg: Some(TuiColor::Rgb(RgbValue::from_u8(10, 10, 10))),
+            ..Default::default()
+        };
+        assert_eq2!(
+            style.with_min_contrast_ratio(DEFAULT_MIN_CONTRAST_RATIO),
+            style
+        );
+    }
+}
@@ -0,0 +1,122 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Force the tier that colors are degraded to (truecolor → ANSI 256 → grayscale),
+//! regardless of what is detected from the environment (eg: `COLORTERM`, `TERM`,
+//! `NO_COLOR`), eg: for screenshots and deterministic tests. This is respected everywhere
+//! that colors are emitted, ie: [crate::ColorWheel] (including lolcat), [crate::TuiStyle]s
+//! that are painted by `r3bl_tui`, [r3bl_ansi_color::AnsiStyledText], and `r3bl_tuify`.
+//!
+//! The override is global, so tests that use it should be annotated w/ `#[serial]`
+//! (from the [serial_test](https://crates.io/crates/serial_test) crate).
+
+use r3bl_ansi_color::{global_color_support, ColorSupport};
+
+/// All the colors that are emitted after this is called are degraded to `color_support`.
+pub fn set_color_support_override(color_support: ColorSupport) {
+    global_color_support::set_override(color_support);
+}
+
+/// Go back to detecting the color support from the environment.
+pub fn clear_color_support_override() { global_color_support::clear_override(); }
+
+/// Returns the color support that colors are degraded to, which is the override (if it is
+/// set), or what is detected from the environment.
+pub fn get_color_support() -> ColorSupport { global_color_support::detect() }
+
+/// Run `f` w/ the given color support override, and then restore the previous override
+/// (or clear it, if there wasn't one).
+pub fn with_color_support_override<R>(
+    color_support: ColorSupport,
+    f: impl FnOnce() -> R,
+) -> R {
+    let maybe_prev_override = global_color_support::try_get_override().ok();
+    set_color_support_override(color_support);
+    let it = f();
+    match maybe_prev_override {
+        Some(prev_override) => set_color_support_override(prev_override),
+        None => clear_color_support_override(),
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+
+    use super::*;
+    use crate::{assert_eq2,
+                ColorWheel,
+                ColorWheelConfig,
+                GradientGenerationPolicy,
+                LolcatBuilder,
+                RgbValue,
+                TextColorizationPolicy,
+                TuiColor,
+                TuiStyle,
+                UnicodeString};
+
+    fn has_truecolor_escape_sequences(output: &str) -> bool {
+        output.contains("[38;2;") || output.contains("[48;2;")
+    }
+
+    #[serial]
+    #[test]
+    fn test_grayscale_override_has_no_truecolor_output() {
+        let colorize = || {
+            let mut color_wheel = ColorWheel::new(vec![ColorWheelConfig::Lolcat(
+                LolcatBuilder::new().set_background_mode(true),
+            )]);
+            let lolcat = color_wheel.colorize_into_string(
+                &UnicodeString::from("Hello, world!"),
+                GradientGenerationPolicy::ReuseExistingGradientAndResetIndex,
+                TextColorizationPolicy::ColorEachCharacter(None),
+                None,
+            );
+            let styled_text = ColorWheel::lolcat_into_string(
+                "Hello, world!",
+                Some(TuiStyle {
+                    color_bg: Some(TuiColor::Rgb(RgbValue::from_u8(10, 20, 30))),
+                    ..Default::default()
+                }),
+            );
+            format!("{lolcat}{styled_text}")
+        };
+
+        let output = with_color_support_override(ColorSupport::Truecolor, colorize);
+        assert!(has_truecolor_escape_sequences(&output));
+
+        let output = with_color_support_override(ColorSupport::Grayscale, colorize);
+        assert!(!has_truecolor_escape_sequences(&output));
+        assert!(output.contains("[38;5;"));
+        assert!(output.contains("[48;5;"));
+    }
+
+    #[serial]
+    #[test]
+    fn test_with_color_support_override_restores_the_previous_one() {
+        set_color_support_override(ColorSupport::Ansi256);
+        with_color_support_override(ColorSupport::Grayscale, || {
+            assert_eq2!(get_color_support(), ColorSupport::Grayscale);
+        });
+        assert_eq2!(get_color_support(), ColorSupport::Ansi256);
+
+        clear_color_support_override();
+        with_color_support_override(ColorSupport::Grayscale, || {});
+        assert!(global_color_support::try_get_override().is_err());
+    }
+}
@@ -16,12 +16,16 @@
  */
 
 // Attach sources.
+pub mod color_contrast;
+pub mod color_support_override;
 pub mod hex_color_parser;
 pub mod tui_color;
 pub mod tui_style_impl;
 pub mod tui_stylesheet;
 
 // Re-export.
+pub use color_contrast::*;
+pub use color_support_override::*;
 pub use hex_color_parser::*;
 pub use tui_color::*;
 pub use tui_style_impl::*;
@@ -20,14 +20,16 @@ use std::{io::{stderr, Write},
           time::Duration};
 
 use r3bl_core::StdMutex;
-use r3bl_terminal_async::{Spinner,
+use r3bl_terminal_async::{MultiSpinner,
+                          Spinner,
                           SpinnerColor,
                           SpinnerStyle,
                           SpinnerTemplate,
                           TerminalAsync,
                           ARTIFICIAL_UI_DELAY,
                           DELAY_MS,
-                          DELAY_UNIT};
+                          DELAY_UNIT,
+                          MULTI_SPINNER_MAX_VISIBLE_COUNT};
 use tokio::{time::Instant, try_join};
 
 #[tokio::main]
@@ -54,6 +56,48 @@ pub async fn main() -> miette::Result<()> {
     })
     .await?;
 
+    println!("-------------> Example with many spinners <-------------");
+    example_with_many_spinners().await?;
+
+    Ok(())
+}
+
+async fn example_with_many_spinners() -> miette::Result<()> {
+    let terminal_async = TerminalAsync::try_new("$ ").await?;
+    let terminal_async = terminal_async.expect("terminal is not fully interactive");
+
+    let maybe_multi_spinner = MultiSpinner::try_start(
+        DELAY_UNIT,
+        MULTI_SPINNER_MAX_VISIBLE_COUNT,
+        Arc::new(StdMutex::new(stderr())),
+        terminal_async.clone_shared_writer(),
+    )
+    .await?;
+    let Some(mut multi_spinner) = maybe_multi_spinner else {
+        return Ok(());
+    };
+
+    // Add more spinners than can be shown at once.
+    let task_count = MULTI_SPINNER_MAX_VISIBLE_COUNT + 2;
+    for index in 0..task_count {
+        multi_spinner.add(
+            format!("task {index}"),
+            format!("Downloading file {index}"),
+            SpinnerStyle::default(),
+        )?;
+    }
+
+    // Finish them one at a time, the remaining ones move up to take their place.
+    for index in 0..task_count {
+        tokio::time::sleep(ARTIFICIAL_UI_DELAY / 4).await;
+        multi_spinner.finish(
+            &format!("task {index}"),
+            &format!("Downloaded file {index}"),
+        )?;
+    }
+
+    multi_spinner.stop().await?;
+
     Ok(())
 }
 
@@ -273,6 +273,8 @@
 //! - In-memory History.
 //! - Left, Right: Move cursor left/right.
 //! - Up, Down: Scroll through input history.
+//! - Ctrl-R: Search the input history, w/ the part of the entry that matched highlighted.
+//!   Press it again for the next match, Esc to cancel, or any other key to use the entry.
 //! - Ctrl-W: Erase the input from the cursor to the previous whitespace.
 //! - Ctrl-U: Erase the input before the cursor.
 //! - Ctrl-L: Clear the screen.
@@ -280,6 +282,9 @@
 //! - Home: Jump to the start of the line.
 //!     - When the "emacs" feature (on by default) is enabled, Ctrl-A has the same effect.
 //! - End: Jump to the end of the line.
+//! - Tab: Complete the text under the cursor, and press it again to cycle through the
+//!   candidates. This is only enabled if [`Readline::set_completion_provider()`] is called
+//!   (eg: w/ a [`FilePathCompletionProvider`] to complete file paths).
 //!     - When the "emacs" feature (on by default) is enabled, Ctrl-E has the same effect.
 //! - Ctrl-C, Ctrl-D: Send an `Eof` event.
 //! - Ctrl-C: Send an `Interrupt` event.
@@ -361,6 +366,12 @@
 //! ensure that they exit as a response to user cancellation. Take a look at the
 //! `examples/terminal_async.rs` file to get an understanding of how to use this API.
 //!
+//! When many concurrent tasks each need a spinner, use [`MultiSpinner::try_start()`]
+//! instead, so they don't fight over the same line. It shows a stacked list of named
+//! spinners (each w/ its own message and style), and summarizes the ones that don't fit in
+//! [`MultiSpinner::max_visible_count`] as `+N more`. Use [`MultiSpinner::add()`] and
+//! [`MultiSpinner::finish()`] from any task to add and remove spinners.
+//!
 //! The third change is that [`TerminalAsync::try_new()`] now accepts prompts that can
 //! have ANSI escape sequences in them. Here's an example of this.
 //!
@@ -473,6 +484,7 @@ use r3bl_core::{StdMutex, SendRawTerminal, SafeRawTerminal};
 // Type aliases.
 pub type SafeLineState = Arc<StdMutex<LineState>>;
 pub type SafeHistory = Arc<StdMutex<History>>;
+pub type SafeCompletionProvider = Arc<dyn CompletionProvider>;
 
 pub type SafeBool = Arc<StdMutex<bool>>;
 
@@ -16,9 +16,11 @@
  */
 
 // Attach sources.
+pub mod multi_spinner;
 pub mod spinner;
 pub mod terminal_async;
 
 // Re-export.
+pub use multi_spinner::*;
 pub use spinner::*;
 pub use terminal_async::*;
@@ -0,0 +1,286 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{sync::Arc, time::Duration};
+
+use crossterm::terminal;
+use r3bl_ansi_color::{is_fully_uninteractive_terminal,
+                      is_stdout_piped,
+                      StdoutIsPipedResult,
+                      TTYResult};
+use r3bl_core::{LineStateControlSignal, SharedWriter};
+use tokio::time::interval;
+
+use crate::{spinner_render, SafeBool, SafeRawTerminal, SpinnerStyle, StdMutex};
+
+/// The default for [MultiSpinner::max_visible_count].
+pub const MULTI_SPINNER_MAX_VISIBLE_COUNT: usize = 5;
+
+/// A named spinner that is shown by a [MultiSpinner].
+#[derive(Debug, Clone)]
+pub struct MultiSpinnerEntry {
+    pub name: String,
+    pub message: String,
+    pub style: SpinnerStyle,
+    /// Used to determine the output of the next tick (for this entry).
+    pub count: usize,
+}
+
+pub type SafeMultiSpinnerEntries = Arc<StdMutex<Vec<MultiSpinnerEntry>>>;
+
+/// Shows many spinners at once, as a stacked list, eg: when concurrent tasks each want
+/// to show their progress. Each spinner has a name, its own message, and its own
+/// [SpinnerStyle]. At most [MultiSpinner::max_visible_count] spinners are shown, and
+/// the rest are summarized in a last line, eg: `+3 more`.
+///
+/// Just like [crate::Spinner], the output from all the [SharedWriter]s is paused while
+/// this is active (and resumed in [MultiSpinner::stop]), and <kbd>Ctrl+C</kbd> and
+/// <kbd>Ctrl+D</kbd> are directed to it. Spinners can be added and finished from any
+/// task, since `&self` is all that is needed, so this can be shared using an [Arc].
+pub struct MultiSpinner {
+    pub tick_delay: Duration,
+    pub max_visible_count: usize,
+    pub safe_output_terminal: SafeRawTerminal,
+    pub shared_writer: SharedWriter,
+    pub shutdown_sender: tokio::sync::broadcast::Sender<()>,
+    safe_entries: SafeMultiSpinnerEntries,
+    safe_is_shutdown: SafeBool,
+}
+
+impl MultiSpinner {
+    /// Create a new instance of [MultiSpinner], w/out any spinners in it. Use
+    /// [MultiSpinner::add] to add them.
+    ///
+    /// # Returns
+    /// 1. If the terminal is not fully interactive then it will return [None], and won't
+    ///    start the task. See [crate::Spinner::try_start] for details.
+    /// 2. Otherwise, it will start the task and return a [MultiSpinner] instance.
+    pub async fn try_start(
+        tick_delay: Duration,
+        max_visible_count: usize,
+        safe_output_terminal: SafeRawTerminal,
+        shared_writer: SharedWriter,
+    ) -> miette::Result<Option<MultiSpinner>> {
+        if let StdoutIsPipedResult::StdoutIsPiped = is_stdout_piped() {
+            return Ok(None);
+        }
+        if let TTYResult::IsNotInteractive = is_fully_uninteractive_terminal() {
+            return Ok(None);
+        }
+
+        // Shutdown broadcast channel.
+        let (shutdown_sender, _) = tokio::sync::broadcast::channel::<()>(1);
+
+        let mut multi_spinner = MultiSpinner {
+            tick_delay,
+            max_visible_count,
+            safe_output_terminal,
+            shared_writer,
+            shutdown_sender,
+            safe_entries: Arc::new(StdMutex::new(vec![])),
+            safe_is_shutdown: Arc::new(StdMutex::new(false)),
+        };
+
+        // Start task.
+        multi_spinner.try_start_task().await?;
+
+        Ok(Some(multi_spinner))
+    }
+
+    /// This is meant for the tasks that added spinners to check if they should shutdown,
+    /// due to:
+    /// 1. The user pressing `Ctrl-C` or `Ctrl-D`.
+    /// 2. Or the [MultiSpinner::stop] got called.
+    pub fn is_shutdown(&self) -> bool { *self.safe_is_shutdown.lock().unwrap() }
+
+    /// Add a spinner to the bottom of the list. Returns an error if there is already a
+    /// spinner w/ the same `name`.
+    pub fn add(
+        &self,
+        name: impl Into<String>,
+        message: impl Into<String>,
+        style: SpinnerStyle,
+    ) -> miette::Result<()> {
+        let name = name.into();
+        let mut entries = self.safe_entries.lock().unwrap();
+        if entries.iter().any(|entry| entry.name == name) {
+            return Err(miette::miette!("Spinner {name:?} already exists"));
+        }
+        entries.push(MultiSpinnerEntry {
+            name,
+            message: message.into(),
+            style,
+            count: 0,
+        });
+        Ok(())
+    }
+
+    /// Change the message of the spinner w/ the given `name`.
+    pub fn set_message(
+        &self,
+        name: &str,
+        message: impl Into<String>,
+    ) -> miette::Result<()> {
+        let mut entries = self.safe_entries.lock().unwrap();
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| miette::miette!("Spinner {name:?} does not exist"))?;
+        entry.message = message.into();
+        Ok(())
+    }
+
+    /// Remove the spinner w/ the given `name`, and print its `final_message` above the
+    /// remaining spinners, which are painted again right away (in the same flush) so
+    /// they don't flicker.
+    pub fn finish(&self, name: &str, final_message: &str) -> miette::Result<()> {
+        // The entries are always locked before the terminal, by every task.
+        let mut entries = self.safe_entries.lock().unwrap();
+        let index = entries
+            .iter()
+            .position(|entry| entry.name == name)
+            .ok_or_else(|| miette::miette!("Spinner {name:?} does not exist"))?;
+        let entry = entries.remove(index);
+
+        let display_width = get_terminal_display_width();
+        let final_output =
+            spinner_render::render_final_tick(&entry.style, final_message, display_width);
+        let lines = spinner_render::render_multi_spinner_tick(
+            &mut entries,
+            self.max_visible_count,
+            display_width,
+        );
+        spinner_render::print_multi_spinner_final_tick(
+            &final_output,
+            &lines,
+            &mut *self.safe_output_terminal.lock().unwrap(),
+        )
+    }
+
+    /// Returns the names of the spinners that haven't been finished yet.
+    pub fn get_names(&self) -> Vec<String> {
+        let entries = self.safe_entries.lock().unwrap();
+        entries.iter().map(|entry| entry.name.clone()).collect()
+    }
+
+    async fn try_start_task(&mut self) -> miette::Result<()> {
+        // Tell readline that spinner is active & register the spinner shutdown sender.
+        _ = self
+            .shared_writer
+            .line_state_control_channel_sender
+            .send(LineStateControlSignal::SpinnerActive(
+                self.shutdown_sender.clone(),
+            ))
+            .await;
+
+        // Pause the terminal.
+        let _ = self
+            .shared_writer
+            .line_state_control_channel_sender
+            .send(LineStateControlSignal::Pause)
+            .await;
+
+        let tick_delay = self.tick_delay;
+        let max_visible_count = self.max_visible_count;
+        let safe_entries = self.safe_entries.clone();
+        let safe_output_terminal = self.safe_output_terminal.clone();
+
+        let mut shutdown_receiver = self.shutdown_sender.subscribe();
+
+        let self_safe_is_shutdown = self.safe_is_shutdown.clone();
+
+        tokio::spawn(async move {
+            let mut interval = interval(tick_delay);
+
+            loop {
+                tokio::select! {
+                    // Poll interval.
+                    // This branch is cancel safe because tick is cancel safe.
+                    _ = interval.tick() => {
+                        let mut entries = safe_entries.lock().unwrap();
+                        let lines = spinner_render::render_multi_spinner_tick(
+                            &mut entries,
+                            max_visible_count,
+                            get_terminal_display_width()
+                        );
+                        let _ = spinner_render::print_multi_spinner_tick(
+                            &lines,
+                            &mut (*safe_output_terminal.lock().unwrap())
+                        );
+                        // Increment count to affect the output in the next iteration of
+                        // this loop.
+                        for entry in entries.iter_mut() {
+                            entry.count += 1;
+                        }
+                    },
+
+                    // Poll shutdown channel.
+                    // This branch is cancel safe because recv is cancel safe.
+                    _ = shutdown_receiver.recv() => {
+                        *self_safe_is_shutdown.lock().unwrap() = true;
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Stop all the spinners (w/out printing a final message for the ones that haven't
+    /// been finished), and resume the terminal.
+    pub async fn stop(&mut self) -> miette::Result<()> {
+        // Tell readline that spinner is inactive.
+        _ = self
+            .shared_writer
+            .line_state_control_channel_sender
+            .send(LineStateControlSignal::SpinnerInactive)
+            .await;
+
+        // Shutdown the task (if it hasn't already been shutdown).
+        if !*self.safe_is_shutdown.lock().unwrap() {
+            // Produces an error if the spinner is already shutdown.
+            _ = self.shutdown_sender.send(());
+        }
+
+        // Clear the spinners that are still painted.
+        {
+            let mut entries = self.safe_entries.lock().unwrap();
+            entries.clear();
+            spinner_render::print_multi_spinner_tick(
+                &[],
+                &mut *self.safe_output_terminal.lock().unwrap(),
+            )?;
+        }
+
+        // Resume the terminal.
+        let _ = self
+            .shared_writer
+            .line_state_control_channel_sender
+            .send(LineStateControlSignal::Resume)
+            .await;
+
+        Ok(())
+    }
+}
+
+fn get_terminal_display_width() -> usize {
+    match terminal::size() {
+        Ok((columns, _rows)) => columns as usize,
+        Err(_) => 0,
+    }
+}
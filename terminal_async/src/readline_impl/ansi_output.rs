@@ -0,0 +1,207 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Output from [r3bl_core::SharedWriter]s may contain ANSI escape codes (eg: colored log
+//! lines). When the terminal doesn't support color, or the output is redirected, these
+//! codes just show up as garbage. [AnsiOutputMode] controls whether the SGR (color and
+//! text attribute) codes are stripped before the output is printed. Other escape
+//! sequences (eg: cursor moves) are passed through as is.
+
+use r3bl_ansi_color::{global_color_support, ColorSupport};
+
+const ESC: u8 = 0x1b;
+
+/// A sequence that is still incomplete after this many bytes is not a valid escape
+/// sequence, so it is printed as is, instead of holding on to it forever.
+const MAX_PENDING_SEQUENCE_LEN: usize = 64;
+
+/// Use [crate::Readline::set_ansi_output_mode] to change this.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnsiOutputMode {
+    /// Print the output as is.
+    #[default]
+    Preserve,
+    /// Strip the SGR codes when [global_color_support::detect] returns
+    /// [ColorSupport::NoColor], eg: if `NO_COLOR` is set, or the output is redirected.
+    /// This takes [global_color_support::set_override] into account.
+    Auto,
+    /// Always strip the SGR codes.
+    Strip,
+}
+
+impl AnsiOutputMode {
+    pub fn should_strip(&self) -> bool {
+        match self {
+            AnsiOutputMode::Preserve => false,
+            AnsiOutputMode::Auto => {
+                global_color_support::detect() == ColorSupport::NoColor
+            }
+            AnsiOutputMode::Strip => true,
+        }
+    }
+}
+
+/// Removes SGR codes (`ESC [ ... m`) from the output, even if they are split across
+/// writes. An escape sequence that is incomplete at the end of the data is held back
+/// until the rest of it arrives.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SgrStripper {
+    pending: Vec<u8>,
+}
+
+enum Sequence {
+    /// A complete sequence of the given length, and whether it is an SGR code.
+    Complete {
+        len: usize,
+        is_sgr: bool,
+    },
+    Incomplete,
+}
+
+impl SgrStripper {
+    pub fn strip(&mut self, data: &[u8]) -> Vec<u8> {
+        let mut input = std::mem::take(&mut self.pending);
+        input.extend_from_slice(data);
+
+        let mut acc = Vec::with_capacity(input.len());
+        let mut index = 0;
+        while index < input.len() {
+            if input[index] != ESC {
+                acc.push(input[index]);
+                index += 1;
+                continue;
+            }
+
+            match parse_sequence(&input[index..]) {
+                Sequence::Complete { len, is_sgr } => {
+                    if !is_sgr {
+                        acc.extend_from_slice(&input[index..index + len]);
+                    }
+                    index += len;
+                }
+                Sequence::Incomplete
+                    if input.len() - index < MAX_PENDING_SEQUENCE_LEN =>
+                {
+                    self.pending = input[index..].to_vec();
+                    break;
+                }
+                Sequence::Incomplete => {
+                    acc.extend_from_slice(&input[index..]);
+                    break;
+                }
+            }
+        }
+
+        acc
+    }
+}
+
+/// Parse the escape sequence at the start of `bytes` (which starts w/ [ESC]). Only CSI
+/// sequences (`ESC [`, parameter bytes, intermediate bytes, final byte) are parsed, any
+/// other escape is treated as a 1 byte sequence.
+fn parse_sequence(bytes: &[u8]) -> Sequence {
+    match bytes.get(1) {
+        None => return Sequence::Incomplete,
+        Some(b'[') => {}
+        Some(_) => {
+            return Sequence::Complete {
+                len: 1,
+                is_sgr: false,
+            }
+        }
+    }
+
+    for (index, byte) in bytes.iter().enumerate().skip(2) {
+        match byte {
+            // Parameter and intermediate bytes.
+            0x20..=0x3f => continue,
+            // Final byte.
+            0x40..=0x7e => {
+                return Sequence::Complete {
+                    len: index + 1,
+                    is_sgr: *byte == b'm',
+                }
+            }
+            // Not a valid CSI sequence, so leave it (and the rest) alone.
+            _ => {
+                return Sequence::Complete {
+                    len: index,
+                    is_sgr: false,
+                }
+            }
+        }
+    }
+
+    Sequence::Incomplete
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn strip(stripper: &mut SgrStripper, data: &str) -> String {
+        String::from_utf8(stripper.strip(data.as_bytes())).unwrap()
+    }
+
+    #[test]
+    fn test_strip_sgr_codes() {
+        let mut stripper = SgrStripper::default();
+        assert_eq!(
+            strip(
+                &mut stripper,
+                "\x1b[1;31mred\x1b[0m plain \x1b[38;2;1;2;3mrgb\x1b[m\n"
+            ),
+            "red plain rgb\n"
+        );
+    }
+
+    #[test]
+    fn test_preserve_other_sequences() {
+        let mut stripper = SgrStripper::default();
+        assert_eq!(
+            strip(&mut stripper, "\x1b[2K\x1b[1Aup\x1b[32m!\n"),
+            "\x1b[2K\x1b[1Aup!\n"
+        );
+        // A lone escape is left alone.
+        assert_eq!(strip(&mut stripper, "\x1bc\n"), "\x1bc\n");
+    }
+
+    #[test]
+    fn test_sequence_split_across_writes() {
+        let mut stripper = SgrStripper::default();
+        assert_eq!(strip(&mut stripper, "a\x1b"), "a");
+        assert_eq!(strip(&mut stripper, "[1;3"), "");
+        assert_eq!(strip(&mut stripper, "2mb\x1b[1"), "b");
+        assert_eq!(strip(&mut stripper, "Ac\n"), "\x1b[1Ac\n");
+    }
+
+    #[test]
+    fn test_invalid_incomplete_sequence_is_not_held_forever() {
+        let mut stripper = SgrStripper::default();
+        let data = format!("\x1b[{}", "1;".repeat(MAX_PENDING_SEQUENCE_LEN));
+        assert_eq!(strip(&mut stripper, &data), data);
+        assert_eq!(strip(&mut stripper, "ok\n"), "ok\n");
+    }
+
+    #[test]
+    fn test_should_strip() {
+        assert!(!AnsiOutputMode::Preserve.should_strip());
+        assert!(AnsiOutputMode::Strip.should_strip());
+    }
+}
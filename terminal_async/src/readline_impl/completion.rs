@@ -0,0 +1,280 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{ops::Range,
+          path::{PathBuf, MAIN_SEPARATOR},
+          time::Duration};
+
+/// How long to wait for a [CompletionProvider] before giving up, eg: on a slow (network)
+/// filesystem.
+pub const COMPLETION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The candidates that can replace the text in [replace_range](Completions::replace_range)
+/// of the line. When there is more than one, pressing <kbd>Tab</kbd> repeatedly cycles
+/// through them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completions {
+    /// Byte range in the line.
+    pub replace_range: Range<usize>,
+    pub candidates: Vec<String>,
+}
+
+/// The [Completions] that are being cycled through, by pressing <kbd>Tab</kbd>
+/// repeatedly. This ends when the line is changed in any other way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionCycle {
+    /// The line before any of the candidates was applied.
+    pub original_line: String,
+    pub completions: Completions,
+    pub current_index: usize,
+    /// The line after the current candidate was applied.
+    pub completed_line: String,
+}
+
+/// Provides the completions for the text under the cursor, when the user presses
+/// <kbd>Tab</kbd>. Use [crate::Readline::set_completion_provider] to enable it.
+///
+/// This is run on a blocking thread (using [tokio::task::spawn_blocking]) w/ a timeout of
+/// [COMPLETION_TIMEOUT], so it is ok for it to perform slow (blocking) IO.
+pub trait CompletionProvider: Send + Sync {
+    /// Returns [None] if there is nothing to complete. `cursor_byte_index` is the byte
+    /// index of the cursor in the `line`.
+    fn get_completions(
+        &self,
+        line: &str,
+        cursor_byte_index: usize,
+    ) -> Option<Completions>;
+}
+
+/// Completes the file path under the cursor against the filesystem. Relative paths are
+/// resolved against the current working directory of the process, and a leading `~` is
+/// expanded to the user's home folder (but is kept as is in the line).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePathCompletionProvider {
+    /// Hidden files (whose name starts w/ `.`) are always shown if the text being
+    /// completed starts w/ `.`.
+    pub show_hidden_files: bool,
+    /// This defaults to the convention of the platform (case insensitive on Windows and
+    /// macOS).
+    pub is_case_sensitive: bool,
+    pub maybe_home_folder: Option<PathBuf>,
+}
+
+impl Default for FilePathCompletionProvider {
+    fn default() -> Self {
+        Self {
+            show_hidden_files: false,
+            is_case_sensitive: !cfg!(any(windows, target_os = "macos")),
+            maybe_home_folder: std::env::var_os(if cfg!(windows) {
+                "USERPROFILE"
+            } else {
+                "HOME"
+            })
+            .map(PathBuf::from),
+        }
+    }
+}
+
+fn is_path_separator(ch: char) -> bool { ch == '/' || ch == MAIN_SEPARATOR }
+
+impl FilePathCompletionProvider {
+    /// Expand a leading `~` in the folder that is read (not the text in the line).
+    fn expand_home_folder(&self, folder: &str) -> Option<PathBuf> {
+        match (folder.strip_prefix('~'), &self.maybe_home_folder) {
+            (Some(""), Some(home_folder)) => Some(home_folder.clone()),
+            (Some(rest), Some(home_folder)) if rest.starts_with(is_path_separator) => {
+                Some(home_folder.join(&rest[1..]))
+            }
+            // Eg: `~user/` is not supported.
+            (Some(_), _) => None,
+            (None, _) if folder.is_empty() => Some(PathBuf::from(".")),
+            (None, _) => Some(PathBuf::from(folder)),
+        }
+    }
+
+    fn is_match(&self, file_name: &str, prefix: &str) -> bool {
+        if file_name.starts_with('.')
+            && !self.show_hidden_files
+            && !prefix.starts_with('.')
+        {
+            return false;
+        }
+        if self.is_case_sensitive {
+            file_name.starts_with(prefix)
+        } else {
+            file_name.to_lowercase().starts_with(&prefix.to_lowercase())
+        }
+    }
+}
+
+impl CompletionProvider for FilePathCompletionProvider {
+    fn get_completions(
+        &self,
+        line: &str,
+        cursor_byte_index: usize,
+    ) -> Option<Completions> {
+        // The path under the cursor starts after the last whitespace.
+        let before_cursor = line.get(..cursor_byte_index)?;
+        let path_start_byte_index = before_cursor
+            .char_indices()
+            .rev()
+            .find(|(_, ch)| ch.is_whitespace())
+            .map(|(index, ch)| index + ch.len_utf8())
+            .unwrap_or(0);
+        let path = &before_cursor[path_start_byte_index..];
+
+        // Eg: `~` on its own is completed to `~/`.
+        let (folder, file_name_prefix) = match path.rfind(is_path_separator) {
+            Some(index) => path.split_at(index + 1),
+            None if path == "~" => (path, ""),
+            None => ("", path),
+        };
+        let folder_to_read = self.expand_home_folder(folder)?;
+        let folder = if folder == "~" {
+            format!("~{MAIN_SEPARATOR}")
+        } else {
+            folder.to_string()
+        };
+
+        let mut candidates: Vec<String> = std::fs::read_dir(folder_to_read)
+            .ok()?
+            .filter_map(|it| it.ok())
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                if !self.is_match(&file_name, file_name_prefix) {
+                    return None;
+                }
+                // This follows symlinks, so a link to a folder is completed as a folder.
+                let is_folder = entry.path().is_dir();
+                Some(if is_folder {
+                    format!("{folder}{file_name}{MAIN_SEPARATOR}")
+                } else {
+                    format!("{folder}{file_name}")
+                })
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+        candidates.sort();
+
+        Some(Completions {
+            replace_range: path_start_byte_index..cursor_byte_index,
+            candidates,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn make_test_folder(name: &str) -> PathBuf {
+        let folder = std::env::temp_dir()
+            .join(format!("r3bl_terminal_async_{name}_{}", std::process::id()));
+        fs::create_dir_all(folder.join("src_folder")).unwrap();
+        fs::write(folder.join("Cargo.toml"), "").unwrap();
+        fs::write(folder.join("cargo_notes.md"), "").unwrap();
+        fs::write(folder.join(".hidden"), "").unwrap();
+        folder
+    }
+
+    #[test]
+    fn test_file_path_completions() {
+        let folder = make_test_folder("completions");
+        let folder_str = format!("{}{MAIN_SEPARATOR}", folder.display());
+        let provider = FilePathCompletionProvider {
+            show_hidden_files: false,
+            is_case_sensitive: true,
+            maybe_home_folder: Some(folder.clone()),
+        };
+
+        // Only the path under the cursor is completed, and folders get a separator.
+        let line = format!("open {folder_str}s rest");
+        let cursor_byte_index = line.len() - " rest".len();
+        let completions = provider.get_completions(&line, cursor_byte_index).unwrap();
+        assert_eq2!(completions.replace_range, 5..cursor_byte_index);
+        assert_eq2!(
+            completions.candidates,
+            vec![format!("{folder_str}src_folder{MAIN_SEPARATOR}")]
+        );
+
+        // Case sensitivity.
+        let line = format!("{folder_str}c");
+        let completions = provider.get_completions(&line, line.len()).unwrap();
+        assert_eq2!(
+            completions.candidates,
+            vec![format!("{folder_str}cargo_notes.md")]
+        );
+
+        let provider = FilePathCompletionProvider {
+            is_case_sensitive: false,
+            ..provider
+        };
+        let completions = provider.get_completions(&line, line.len()).unwrap();
+        assert_eq2!(
+            completions.candidates,
+            vec![
+                format!("{folder_str}Cargo.toml"),
+                format!("{folder_str}cargo_notes.md")
+            ]
+        );
+
+        // Hidden files.
+        let line = folder_str.clone();
+        let completions = provider.get_completions(&line, line.len()).unwrap();
+        assert_eq2!(completions.candidates.len(), 3);
+        let line = format!("{folder_str}.");
+        let completions = provider.get_completions(&line, line.len()).unwrap();
+        assert_eq2!(completions.candidates, vec![format!("{folder_str}.hidden")]);
+
+        // Nothing matches.
+        let line = format!("{folder_str}zzz");
+        assert_eq2!(provider.get_completions(&line, line.len()), None);
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_file_path_completions_expand_home_folder() {
+        let folder = make_test_folder("home");
+        let provider = FilePathCompletionProvider {
+            show_hidden_files: true,
+            is_case_sensitive: true,
+            maybe_home_folder: Some(folder.clone()),
+        };
+
+        // The `~` is kept in the line.
+        let line = format!("~{MAIN_SEPARATOR}src");
+        let completions = provider.get_completions(&line, line.len()).unwrap();
+        assert_eq2!(
+            completions.candidates,
+            vec![format!("~{MAIN_SEPARATOR}src_folder{MAIN_SEPARATOR}")]
+        );
+
+        let completions = provider.get_completions("~", 1).unwrap();
+        assert_eq2!(completions.candidates.len(), 4);
+        assert!(completions.candidates[0].starts_with(&format!("~{MAIN_SEPARATOR}")));
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+}
@@ -0,0 +1,301 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Incremental search of the [crate::History]. Press <kbd>Ctrl+R</kbd> to start it, and
+//! then type to search. The newest entry that matches is shown instead of the line, and
+//! the part of it that matched is highlighted, so it is clear why it matched.
+//! - <kbd>Ctrl+R</kbd> again shows the next (older) entry that matches.
+//! - <kbd>Backspace</kbd> removes the last grapheme cluster from the query.
+//! - <kbd>Esc</kbd> or <kbd>Ctrl+G</kbd> ends the search, and restores the line (and
+//!   cursor) from before it started.
+//! - Any other key (eg: <kbd>Enter</kbd>, or the arrow keys) ends the search, and puts
+//!   the entry in the line, and then does what it normally does.
+//!
+//! Use [crate::Readline::set_history_search_mode] to choose how entries are matched,
+//! and [crate::Readline::set_history_search_highlight] to choose how the matches are
+//! highlighted.
+
+use r3bl_ansi_color::{AnsiStyledText, Style};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::History;
+
+/// How the query is matched against the entries of the [History]. Matching ignores
+/// case, unless the query has an uppercase character in it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistorySearchMode {
+    /// The entry must start w/ the query.
+    Prefix,
+    /// The entry must contain the characters of the query in the same order, but not
+    /// necessarily next to each other. Eg: `gco` matches `git checkout`.
+    #[default]
+    Fuzzy,
+}
+
+/// How the matching part of the entry is painted while searching. The highlight is
+/// never painted when [crate::AnsiOutputMode::should_strip] is true.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistorySearchHighlight {
+    Disable,
+    Enable(Vec<Style>),
+}
+
+impl Default for HistorySearchHighlight {
+    fn default() -> Self {
+        HistorySearchHighlight::Enable(vec![Style::Bold, Style::Underline])
+    }
+}
+
+/// An entry of the [History] that matches the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryMatch {
+    /// Index of the entry in [History::entries].
+    pub entry_index: usize,
+    pub entry: String,
+    /// The indices of the grapheme clusters of the `entry` that matched the query.
+    pub grapheme_indices: Vec<usize>,
+}
+
+/// Is [Some] in [crate::LineState] while the user is searching the history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistorySearch {
+    pub query: String,
+    /// The line from before the search started, which is restored if it is cancelled.
+    pub original_line: String,
+    pub original_line_cursor_grapheme: usize,
+    pub maybe_match: Option<HistoryMatch>,
+}
+
+impl HistorySearch {
+    pub fn new(original_line: String, original_line_cursor_grapheme: usize) -> Self {
+        Self {
+            query: String::new(),
+            original_line,
+            original_line_cursor_grapheme,
+            maybe_match: None,
+        }
+    }
+
+    /// Returns the text that is painted before the entry, in place of the prompt.
+    pub fn get_prompt(&self) -> String {
+        match (&self.maybe_match, self.query.is_empty()) {
+            (None, false) => format!("(failed history search)'{}': ", self.query),
+            _ => format!("(history search)'{}': ", self.query),
+        }
+    }
+
+    /// Returns the entry that matched (w/ the matching grapheme clusters highlighted w/
+    /// the given `styles`), or an empty string if nothing matched.
+    pub fn get_highlighted_entry(&self, styles: &[Style]) -> String {
+        match &self.maybe_match {
+            Some(history_match) => highlight_graphemes(
+                &history_match.entry,
+                &history_match.grapheme_indices,
+                styles,
+            ),
+            None => String::new(),
+        }
+    }
+}
+
+impl History {
+    /// Find the newest entry (starting at `start_index`) that matches the `query`. An
+    /// empty query doesn't match anything.
+    pub fn find_match(
+        &self,
+        query: &str,
+        mode: HistorySearchMode,
+        start_index: usize,
+    ) -> Option<HistoryMatch> {
+        if query.is_empty() {
+            return None;
+        }
+        self.entries.iter().enumerate().skip(start_index).find_map(
+            |(entry_index, entry)| {
+                let grapheme_indices = get_matching_grapheme_indices(entry, query, mode)?;
+                Some(HistoryMatch {
+                    entry_index,
+                    entry: entry.clone(),
+                    grapheme_indices,
+                })
+            },
+        )
+    }
+}
+
+/// Returns the indices of the grapheme clusters of the `entry` that match the `query`,
+/// or [None] if it doesn't match. Fuzzy matching picks the earliest grapheme cluster for
+/// each one in the query.
+pub fn get_matching_grapheme_indices(
+    entry: &str,
+    query: &str,
+    mode: HistorySearchMode,
+) -> Option<Vec<usize>> {
+    let is_case_sensitive = query.chars().any(char::is_uppercase);
+    let normalize = |it: &str| match is_case_sensitive {
+        true => it.to_string(),
+        false => it.to_lowercase(),
+    };
+    let entry_graphemes = entry.graphemes(true).map(normalize).collect::<Vec<_>>();
+    let query_graphemes = query.graphemes(true).map(normalize).collect::<Vec<_>>();
+
+    match mode {
+        HistorySearchMode::Prefix => entry_graphemes
+            .starts_with(&query_graphemes)
+            .then(|| (0..query_graphemes.len()).collect()),
+        HistorySearchMode::Fuzzy => {
+            let mut it = Vec::with_capacity(query_graphemes.len());
+            let mut entry_iter = entry_graphemes.iter().enumerate();
+            for query_grapheme in query_graphemes.iter() {
+                let (index, _) = entry_iter
+                    .find(|(_, entry_grapheme)| *entry_grapheme == query_grapheme)?;
+                it.push(index);
+            }
+            Some(it)
+        }
+    }
+}
+
+/// Paint the grapheme clusters of `text` at the given (sorted) indices w/ the `styles`.
+/// Runs of adjacent grapheme clusters are painted together.
+pub fn highlight_graphemes(
+    text: &str,
+    grapheme_indices: &[usize],
+    styles: &[Style],
+) -> String {
+    if styles.is_empty() || grapheme_indices.is_empty() {
+        return text.to_string();
+    }
+
+    let mut it = String::with_capacity(text.len());
+    let mut run = String::new();
+    for (index, grapheme) in text.graphemes(true).enumerate() {
+        if grapheme_indices.binary_search(&index).is_ok() {
+            run.push_str(grapheme);
+            continue;
+        }
+        if !run.is_empty() {
+            it.push_str(
+                &AnsiStyledText {
+                    text: &run,
+                    style: styles,
+                }
+                .to_string(),
+            );
+            run.clear();
+        }
+        it.push_str(grapheme);
+    }
+    if !run.is_empty() {
+        it.push_str(
+            &AnsiStyledText {
+                text: &run,
+                style: styles,
+            }
+            .to_string(),
+        );
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_match() {
+        let mode = HistorySearchMode::Prefix;
+        assert_eq!(
+            get_matching_grapheme_indices("git status", "git", mode),
+            Some(vec![0, 1, 2])
+        );
+        assert_eq!(
+            get_matching_grapheme_indices("git status", "status", mode),
+            None
+        );
+        // Smart case.
+        assert_eq!(
+            get_matching_grapheme_indices("Git status", "git", mode),
+            Some(vec![0, 1, 2])
+        );
+        assert_eq!(
+            get_matching_grapheme_indices("git status", "Git", mode),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_match_w_grapheme_clusters() {
+        let mode = HistorySearchMode::Fuzzy;
+        assert_eq!(
+            get_matching_grapheme_indices("git checkout", "gco", mode),
+            Some(vec![0, 4, 9])
+        );
+        assert_eq!(
+            get_matching_grapheme_indices("git checkout", "ogc", mode),
+            None
+        );
+
+        // The emoji (w/ a skin tone modifier) is a single grapheme cluster.
+        assert_eq!(
+            get_matching_grapheme_indices("echo 👍🏽 ok", "👍🏽k", mode),
+            Some(vec![5, 8])
+        );
+    }
+
+    #[test]
+    fn test_highlight_graphemes() {
+        let styles = [Style::Bold];
+        let bold = |it: &str| {
+            AnsiStyledText {
+                text: it,
+                style: &styles,
+            }
+            .to_string()
+        };
+
+        assert_eq!(
+            highlight_graphemes("git checkout", &[0, 1, 4], &styles),
+            format!("{}t {}heckout", bold("gi"), bold("c"))
+        );
+        assert_eq!(
+            highlight_graphemes("a👍🏽b", &[1], &styles),
+            format!("a{}b", bold("👍🏽"))
+        );
+        assert_eq!(highlight_graphemes("abc", &[0], &[]), "abc");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_find_match() {
+        let (mut history, _) = History::new();
+        history.update(Some("cargo test".into()));
+        history.update(Some("git commit".into()));
+        history.update(Some("cargo build".into()));
+
+        let mode = HistorySearchMode::Fuzzy;
+        let it = history.find_match("cat", mode, 0).unwrap();
+        assert_eq!((it.entry_index, it.entry.as_str()), (2, "cargo test"));
+
+        let it = history.find_match("cargo", mode, 0).unwrap();
+        assert_eq!(it.entry_index, 0);
+        let it = history.find_match("cargo", mode, 1).unwrap();
+        assert_eq!(it.entry_index, 2);
+        assert_eq!(history.find_match("cargo", mode, 3), None);
+        assert_eq!(history.find_match("", mode, 0), None);
+    }
+}
@@ -25,7 +25,16 @@ use crossterm::{cursor,
 use r3bl_core::{ok, MemoizedLenMap, StringLength};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{ReadlineError, ReadlineEvent, SafeHistory};
+use crate::{AnsiOutputMode,
+            CompletionCycle,
+            Completions,
+            HistorySearch,
+            HistorySearchHighlight,
+            HistorySearchMode,
+            ReadlineError,
+            ReadlineEvent,
+            SafeHistory,
+            SgrStripper};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum LineStateLiveness {
@@ -72,6 +81,23 @@ pub struct LineState {
 
     /// Use to memoize the length of strings.
     pub memoized_len_map: MemoizedLenMap,
+
+    /// Is [Some] while the user is cycling through the candidates of a completion.
+    pub maybe_completion_cycle: Option<CompletionCycle>,
+
+    /// Whether the SGR codes are stripped from the output that is printed.
+    pub ansi_output_mode: AnsiOutputMode,
+
+    /// Holds on to SGR codes that are split across calls to
+    /// [LineState::print_data_and_flush].
+    pub sgr_stripper: SgrStripper,
+
+    /// Is [Some] while the user is searching the history. See [crate::history_search].
+    pub maybe_history_search: Option<HistorySearch>,
+
+    pub history_search_mode: HistorySearchMode,
+
+    pub history_search_highlight: HistorySearchHighlight,
 }
 
 macro_rules! early_return_if_paused {
@@ -106,6 +132,12 @@ impl LineState {
             last_line_length: 0,
             is_paused: LineStateLiveness::NotPaused,
             memoized_len_map,
+            maybe_completion_cycle: None,
+            ansi_output_mode: AnsiOutputMode::default(),
+            sgr_stripper: SgrStripper::default(),
+            maybe_history_search: None,
+            history_search_mode: HistorySearchMode::default(),
+            history_search_highlight: HistorySearchHighlight::default(),
         }
     }
 
@@ -221,21 +253,49 @@ impl LineState {
         ok!()
     }
 
-    /// Render line (prompt + line) and flush.
+    /// Render line (prompt + line) and flush. While searching the history, the search
+    /// query and the entry that matched are rendered instead.
     pub fn render_and_flush(&mut self, term: &mut dyn Write) -> io::Result<()> {
         early_return_if_paused!(self @Unit);
 
-        let output = format!("{}{}", self.prompt, self.line);
+        let (output, prompt, line) = match self.maybe_history_search.clone() {
+            Some(history_search) => {
+                let styles = match (
+                    &self.history_search_highlight,
+                    self.ansi_output_mode.should_strip(),
+                ) {
+                    (HistorySearchHighlight::Enable(styles), false) => styles.as_slice(),
+                    _ => &[],
+                };
+                let prompt = history_search.get_prompt();
+                let output =
+                    format!("{}{}", prompt, history_search.get_highlighted_entry(styles));
+                let entry = history_search
+                    .maybe_match
+                    .map(|it| it.entry)
+                    .unwrap_or_default();
+                (output, prompt, entry)
+            }
+            None => (
+                format!("{}{}", self.prompt, self.line),
+                self.prompt.clone(),
+                self.line.clone(),
+            ),
+        };
         write!(term, "{}", output)?;
 
         let prompt_len =
-            StringLength::StripAnsi.calculate(&self.prompt, &mut self.memoized_len_map);
+            StringLength::StripAnsi.calculate(&prompt, &mut self.memoized_len_map);
 
-        let line_len =
-            StringLength::Unicode.calculate(&self.line, &mut self.memoized_len_map);
+        let line_len = StringLength::Unicode.calculate(&line, &mut self.memoized_len_map);
 
         let total_line_len = prompt_len + line_len;
 
+        // The cursor is at the end of the entry while searching the history.
+        if self.maybe_history_search.is_some() {
+            self.current_column = total_line_len;
+        }
+
         self.move_to_beginning(term, total_line_len)?;
         self.move_from_beginning(term, self.current_column)?;
 
@@ -259,6 +319,15 @@ impl LineState {
         data: &[u8],
         term: &mut dyn Write,
     ) -> Result<(), ReadlineError> {
+        let stripped_data;
+        let data = match self.ansi_output_mode.should_strip() {
+            true => {
+                stripped_data = self.sgr_stripper.strip(data);
+                stripped_data.as_slice()
+            }
+            false => data,
+        };
+
         self.clear(term)?;
 
         // If last written data was not newline, restore the cursor
@@ -344,12 +413,236 @@ impl LineState {
         ok!()
     }
 
+    /// Returns the line and the byte index of the cursor in it.
+    pub fn get_line_and_cursor_byte_index(&self) -> (String, usize) {
+        let (pos, str) = self.current_grapheme().unwrap_or((0, ""));
+        (self.line.clone(), pos + str.len())
+    }
+
+    /// Start cycling through the candidates of the `completions`, by applying the first
+    /// one. If there is only one candidate, then it is simply applied, so that the next
+    /// <kbd>Tab</kbd> completes further (eg: inside a folder).
+    pub fn start_completion_and_render(
+        &mut self,
+        completions: Completions,
+        term: &mut dyn Write,
+    ) -> io::Result<()> {
+        early_return_if_paused!(self @Unit);
+
+        // Ignore invalid completions.
+        if completions.candidates.is_empty()
+            || self.line.get(completions.replace_range.clone()).is_none()
+        {
+            return ok!();
+        }
+
+        let mut completion_cycle = CompletionCycle {
+            original_line: self.line.clone(),
+            completions,
+            current_index: 0,
+            completed_line: String::new(),
+        };
+        self.apply_completion_candidate_and_render(&mut completion_cycle, term)?;
+
+        if completion_cycle.completions.candidates.len() > 1 {
+            self.maybe_completion_cycle = Some(completion_cycle);
+        }
+
+        ok!()
+    }
+
+    /// Apply the next candidate of the current completion cycle. Returns `false` if there
+    /// isn't one (or the line has changed since), in which case the completions need to
+    /// be fetched again.
+    pub fn cycle_completion_and_render(
+        &mut self,
+        term: &mut dyn Write,
+    ) -> io::Result<bool> {
+        if self.is_paused.is_paused() {
+            return Ok(false);
+        }
+
+        let Some(mut completion_cycle) = self.maybe_completion_cycle.take() else {
+            return Ok(false);
+        };
+        if completion_cycle.completed_line != self.line {
+            return Ok(false);
+        }
+
+        completion_cycle.current_index = (completion_cycle.current_index + 1)
+            % completion_cycle.completions.candidates.len();
+        self.apply_completion_candidate_and_render(&mut completion_cycle, term)?;
+        self.maybe_completion_cycle = Some(completion_cycle);
+
+        Ok(true)
+    }
+
+    /// Replace the range of the original line w/ the current candidate, and move the
+    /// cursor to the end of it.
+    fn apply_completion_candidate_and_render(
+        &mut self,
+        completion_cycle: &mut CompletionCycle,
+        term: &mut dyn Write,
+    ) -> io::Result<()> {
+        let replace_range = completion_cycle.completions.replace_range.clone();
+        let candidate =
+            &completion_cycle.completions.candidates[completion_cycle.current_index];
+        let cursor_byte_index = replace_range.start + candidate.len();
+
+        self.clear(term)?;
+        self.line = completion_cycle.original_line.clone();
+        self.line.replace_range(replace_range, candidate);
+        self.line_cursor_grapheme =
+            self.line[..cursor_byte_index].graphemes(true).count();
+        self.move_cursor(0)?;
+        self.render_and_flush(term)?;
+
+        completion_cycle.completed_line = self.line.clone();
+
+        ok!()
+    }
+
+    /// Start searching the history if <kbd>Ctrl+R</kbd> is pressed, or apply the key to
+    /// the search that is active. See [crate::history_search] for the keys. Returns
+    /// `true` if the key was used up by the search, and `false` if it should be handled
+    /// as usual (in which case the search has ended, if it was active).
+    fn apply_history_search_key_and_render(
+        &mut self,
+        key_event: KeyEvent,
+        term: &mut dyn Write,
+        safe_history: &SafeHistory,
+    ) -> io::Result<bool> {
+        if self.is_paused.is_paused() {
+            return Ok(false);
+        }
+
+        let is_control = key_event.modifiers == KeyModifiers::CONTROL;
+        let Some(mut history_search) = self.maybe_history_search.take() else {
+            if is_control && key_event.code == KeyCode::Char('r') {
+                self.clear(term)?;
+                self.maybe_history_search = Some(HistorySearch::new(
+                    self.line.clone(),
+                    self.line_cursor_grapheme,
+                ));
+                self.render_and_flush(term)?;
+                return Ok(true);
+            }
+            return Ok(false);
+        };
+
+        let mode = self.history_search_mode;
+        match (key_event.code, is_control) {
+            // Show the next (older) match, if there is one.
+            (KeyCode::Char('r'), true) => {
+                let start_index = history_search
+                    .maybe_match
+                    .as_ref()
+                    .map(|it| it.entry_index + 1)
+                    .unwrap_or_default();
+                let maybe_next_match = safe_history.lock().unwrap().find_match(
+                    &history_search.query,
+                    mode,
+                    start_index,
+                );
+                if maybe_next_match.is_some() {
+                    history_search.maybe_match = maybe_next_match;
+                }
+            }
+            // Cancel.
+            (KeyCode::Esc, _) | (KeyCode::Char('g'), true) => {
+                self.end_history_search_and_render(history_search, false, term)?;
+                return Ok(true);
+            }
+            // Cancel, and then let Ctrl+C or Ctrl+D do what they normally do.
+            (KeyCode::Char('c' | 'd'), true) => {
+                self.end_history_search_and_render(history_search, false, term)?;
+                return Ok(false);
+            }
+            // Edit the query, and search again from the newest entry.
+            (KeyCode::Char(character), false) => {
+                history_search.query.push(character);
+                history_search.maybe_match = safe_history.lock().unwrap().find_match(
+                    &history_search.query,
+                    mode,
+                    0,
+                );
+            }
+            (KeyCode::Backspace, _) => {
+                if let Some((index, _)) =
+                    history_search.query.grapheme_indices(true).next_back()
+                {
+                    history_search.query.truncate(index);
+                }
+                history_search.maybe_match = safe_history.lock().unwrap().find_match(
+                    &history_search.query,
+                    mode,
+                    0,
+                );
+            }
+            // Accept the match, and then let the key do what it normally does.
+            _ => {
+                self.end_history_search_and_render(history_search, true, term)?;
+                return Ok(false);
+            }
+        }
+
+        self.clear(term)?;
+        self.maybe_history_search = Some(history_search);
+        self.render_and_flush(term)?;
+
+        Ok(true)
+    }
+
+    /// Put the entry that matched in the line (if `is_accepted` and there is one), or
+    /// restore the line from before the search started. The line is rendered w/o the
+    /// search query and highlight.
+    fn end_history_search_and_render(
+        &mut self,
+        history_search: HistorySearch,
+        is_accepted: bool,
+        term: &mut dyn Write,
+    ) -> io::Result<()> {
+        // This uses the column of the cursor in the search, so it clears all of it.
+        self.clear(term)?;
+        self.maybe_history_search = None;
+
+        match (is_accepted, history_search.maybe_match) {
+            (true, Some(history_match)) => {
+                self.line = history_match.entry;
+                self.line_cursor_grapheme = self.line.graphemes(true).count();
+            }
+            _ => {
+                self.line = history_search.original_line;
+                self.line_cursor_grapheme = history_search.original_line_cursor_grapheme;
+            }
+        }
+        self.move_cursor(0)?;
+        self.render_and_flush(term)?;
+
+        ok!()
+    }
+
     pub fn apply_event_and_render(
         &mut self,
         event: Event,
         term: &mut dyn Write,
         safe_history: SafeHistory,
     ) -> Result<Option<ReadlineEvent>, ReadlineError> {
+        // Any other key press ends the completion cycle.
+        if let Event::Key(
+            key_event @ KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            },
+        ) = event
+        {
+            self.maybe_completion_cycle = None;
+
+            if self.apply_history_search_key_and_render(key_event, term, &safe_history)? {
+                return Ok(None);
+            }
+        }
+
         match event {
             // Control Keys
             Event::Key(KeyEvent {
@@ -636,6 +929,7 @@ impl LineState {
 mod tests {
     use std::sync::Arc;
 
+    use r3bl_ansi_color::{AnsiStyledText, Style};
     use r3bl_test_fixtures::StdoutMock;
 
     use super::*;
@@ -715,4 +1009,142 @@ mod tests {
 
         assert_eq!(line.line, "");
     }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_cycle_completions() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+        line.line = "cat sr rest".into();
+        line.line_cursor_grapheme = 6;
+
+        let stdout_mock = StdoutMock::default();
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+        let term = &mut *safe_output_terminal.lock().unwrap();
+
+        assert_eq!(
+            line.get_line_and_cursor_byte_index(),
+            ("cat sr rest".into(), 6)
+        );
+        assert!(!line.cycle_completion_and_render(term).unwrap());
+
+        let completions = Completions {
+            replace_range: 4..6,
+            candidates: vec!["src/".into(), "sr.txt".into()],
+        };
+        line.start_completion_and_render(completions, term).unwrap();
+        assert_eq!(line.line, "cat src/ rest");
+        assert_eq!(line.line_cursor_grapheme, 8);
+
+        // Cycle through the candidates, and wrap around.
+        assert!(line.cycle_completion_and_render(term).unwrap());
+        assert_eq!(line.line, "cat sr.txt rest");
+        assert_eq!(line.line_cursor_grapheme, 10);
+        assert!(line.cycle_completion_and_render(term).unwrap());
+        assert_eq!(line.line, "cat src/ rest");
+
+        // Any other key ends the cycle.
+        let (history, _) = History::new();
+        let safe_history = Arc::new(StdMutex::new(history));
+        let event = Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        line.apply_event_and_render(event, term, safe_history)
+            .unwrap();
+        assert_eq!(line.line, "cat src/a rest");
+        assert!(!line.cycle_completion_and_render(term).unwrap());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_history_search() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+        line.line = "ls".into();
+        line.move_cursor(2).unwrap();
+        line.history_search_highlight = HistorySearchHighlight::Enable(vec![Style::Bold]);
+
+        let (mut history, _) = History::new();
+        history.update(Some("cargo test".into()));
+        history.update(Some("git commit".into()));
+        history.update(Some("cargo build".into()));
+        let safe_history = Arc::new(StdMutex::new(history));
+
+        let key = |code, modifiers| Event::Key(KeyEvent::new(code, modifiers));
+        let ctrl_r = key(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        let apply = |line: &mut LineState, event: Event| {
+            let term = &mut StdoutMock::default();
+            let it = line
+                .apply_event_and_render(event, term, safe_history.clone())
+                .unwrap();
+            (it, term.get_copy_of_buffer_as_string())
+        };
+        let get_entry = |line: &LineState| {
+            let history_search = line.maybe_history_search.as_ref().unwrap();
+            history_search
+                .maybe_match
+                .as_ref()
+                .map(|it| it.entry.clone())
+        };
+        let bold = |it: &str| {
+            AnsiStyledText {
+                text: it,
+                style: &[Style::Bold],
+            }
+            .to_string()
+        };
+
+        // Fuzzy search w/ the non-contiguous matches highlighted.
+        apply(&mut line, ctrl_r.clone());
+        apply(&mut line, key(KeyCode::Char('c'), KeyModifiers::NONE));
+        let (_, output) = apply(&mut line, key(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert_eq!(get_entry(&line), Some("git commit".into()));
+        assert!(output.contains(&format!(
+            "(history search)'ct': git {}ommi{}",
+            bold("c"),
+            bold("t")
+        )));
+        assert_eq!(line.current_column, 32);
+
+        // The next match, and then the query doesn't match anything.
+        apply(&mut line, ctrl_r.clone());
+        assert_eq!(get_entry(&line), Some("cargo test".into()));
+        let (_, output) = apply(&mut line, key(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert_eq!(get_entry(&line), None);
+        assert!(output.contains("(failed history search)'ctx': "));
+
+        // Cancelling restores the line and cursor, w/o the highlight.
+        apply(&mut line, key(KeyCode::Backspace, KeyModifiers::NONE));
+        assert_eq!(get_entry(&line), Some("git commit".into()));
+        let (_, output) = apply(&mut line, key(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(line.maybe_history_search.is_none());
+        assert_eq!(line.line, "ls");
+        assert_eq!(line.current_column, 5);
+        assert!(output.contains("fools"));
+        assert!(!output.contains("\x1b[1m"));
+
+        // Enter accepts the match, and submits it.
+        apply(&mut line, ctrl_r.clone());
+        apply(&mut line, key(KeyCode::Char('b'), KeyModifiers::NONE));
+        let (it, _) = apply(&mut line, key(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(it, Some(ReadlineEvent::Line(it)) if it == "cargo build"));
+        assert!(line.maybe_history_search.is_none());
+    }
+
+    #[test]
+    fn test_print_data_and_flush_strips_sgr_codes() {
+        let data = b"\x1b[31mred\x1b[1";
+        let mut line = LineState::new("> ".into(), (100, 100));
+
+        let term = &mut StdoutMock::default();
+        line.print_data_and_flush(data, term).unwrap();
+        assert!(term.get_copy_of_buffer_as_string().contains("\x1b[31mred"));
+
+        let term = &mut StdoutMock::default();
+        line.ansi_output_mode = AnsiOutputMode::Strip;
+        line.print_data_and_flush(data, term).unwrap();
+        // The incomplete code is held back until the rest of it is printed.
+        line.print_data_and_flush(b"m!\n", term).unwrap();
+        let output = term.get_copy_of_buffer_as_string();
+        assert!(output.contains("red"));
+        assert!(output.contains("!\n"));
+        assert!(!output.contains("\x1b[31m"));
+        assert!(!output.contains("\x1b[1m"));
+    }
 }
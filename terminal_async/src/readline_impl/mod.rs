@@ -16,11 +16,17 @@
  */
 
 // Attach.
+pub mod ansi_output;
+pub mod completion;
 pub mod history;
+pub mod history_search;
 pub mod line_state;
 pub mod readline;
 
 // Re-export.
+pub use ansi_output::*;
+pub use completion::*;
 pub use history::*;
+pub use history_search::*;
 pub use line_state::*;
 pub use readline::*;
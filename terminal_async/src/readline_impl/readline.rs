@@ -29,15 +29,20 @@ use r3bl_core::{output_device_as_mut,
 use thiserror::Error;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
-use crate::{History,
+use crate::{AnsiOutputMode,
+            History,
+            HistorySearchHighlight,
+            HistorySearchMode,
             LineState,
             LineStateLiveness,
             PauseBuffer,
+            SafeCompletionProvider,
             SafeHistory,
             SafeLineState,
             SafePauseBuffer,
             StdMutex,
-            CHANNEL_CAPACITY};
+            CHANNEL_CAPACITY,
+            COMPLETION_TIMEOUT};
 
 const CTRL_C: crossterm::event::Event =
     crossterm::event::Event::Key(crossterm::event::KeyEvent::new(
@@ -45,6 +50,12 @@ const CTRL_C: crossterm::event::Event =
         crossterm::event::KeyModifiers::CONTROL,
     ));
 
+const TAB: crossterm::event::Event =
+    crossterm::event::Event::Key(crossterm::event::KeyEvent::new(
+        crossterm::event::KeyCode::Tab,
+        crossterm::event::KeyModifiers::NONE,
+    ));
+
 const CTRL_D: crossterm::event::Event =
     crossterm::event::Event::Key(crossterm::event::KeyEvent::new(
         crossterm::event::KeyCode::Char('d'),
@@ -186,6 +197,10 @@ pub struct Readline {
     /// - Is [None] if no [crate::Spinner] is active. Also works with the
     ///   [LineStateControlSignal::Resume] signal.
     pub safe_spinner_is_active: Arc<StdMutex<Option<tokio::sync::broadcast::Sender<()>>>>,
+
+    /// Is [Some] if <kbd>Tab</kbd> completion is enabled. See
+    /// [Readline::set_completion_provider].
+    pub maybe_completion_provider: Option<SafeCompletionProvider>,
 }
 
 /// Error returned from [`readline()`][Readline::readline]. Such errors generally require
@@ -489,6 +504,7 @@ impl Readline {
             safe_history,
             safe_is_paused_buffer,
             safe_spinner_is_active,
+            maybe_completion_provider: None,
         };
 
         // Print the prompt.
@@ -537,6 +553,43 @@ impl Readline {
         history.entries.truncate(max_size);
     }
 
+    /// Enable (or disable w/ [None]) completion of the text under the cursor when the
+    /// user presses <kbd>Tab</kbd>. Pressing it repeatedly cycles through the candidates.
+    /// Eg: use [crate::FilePathCompletionProvider] to complete file paths.
+    pub fn set_completion_provider(
+        &mut self,
+        maybe_completion_provider: Option<SafeCompletionProvider>,
+    ) {
+        self.maybe_completion_provider = maybe_completion_provider;
+    }
+
+    /// Set whether the SGR (color and text attribute) codes are stripped from the output
+    /// of the [SharedWriter]s, eg: use [AnsiOutputMode::Auto] to strip them when the
+    /// terminal doesn't support color. The default is [AnsiOutputMode::Preserve].
+    pub fn set_ansi_output_mode(&mut self, ansi_output_mode: AnsiOutputMode) {
+        let mut line_state = self.safe_line_state.lock().unwrap();
+        line_state.ansi_output_mode = ansi_output_mode;
+    }
+
+    /// Set how the query is matched against the history entries, when the user searches
+    /// the history w/ <kbd>Ctrl+R</kbd>. The default is [HistorySearchMode::Fuzzy]. See
+    /// [crate::history_search] for details.
+    pub fn set_history_search_mode(&mut self, history_search_mode: HistorySearchMode) {
+        let mut line_state = self.safe_line_state.lock().unwrap();
+        line_state.history_search_mode = history_search_mode;
+    }
+
+    /// Set how the part of the history entry that matches the query is highlighted, when
+    /// the user searches the history w/ <kbd>Ctrl+R</kbd>. The default is bold and
+    /// underlined. Use [HistorySearchHighlight::Disable] to not highlight it.
+    pub fn set_history_search_highlight(
+        &mut self,
+        history_search_highlight: HistorySearchHighlight,
+    ) {
+        let mut line_state = self.safe_line_state.lock().unwrap();
+        line_state.history_search_highlight = history_search_highlight;
+    }
+
     /// Set whether the input line should remain on the screen after events.
     ///
     /// If `enter` is true, then when the user presses "Enter", the prompt and the text
@@ -572,6 +625,21 @@ impl Readline {
                 // - So if this future is dropped, then the item in the
                 //   pinned_input_stream isn't used and the state isn't modified.
                 result_crossterm_event = self.input_device.next() => {
+                    // Tab completion (if enabled) does not block the runtime on slow IO.
+                    if let (Ok(event), Some(completion_provider)) =
+                        (&result_crossterm_event, &self.maybe_completion_provider)
+                    {
+                        if *event == TAB {
+                            readline_internal::complete_line_and_render(
+                                completion_provider.clone(),
+                                self.safe_line_state.clone(),
+                                self.output_device.clone(),
+                            )
+                            .await?;
+                            continue;
+                        }
+                    }
+
                     match readline_internal::apply_event_to_line_state_and_render(
                         result_crossterm_event,
                         self.safe_line_state.clone(),
@@ -658,6 +726,51 @@ pub mod readline_internal {
 
         ControlFlowExtended::Continue
     }
+
+    /// Apply the next candidate if the user is cycling through completions. Otherwise
+    /// get the completions for the text under the cursor (on a blocking thread, w/ a
+    /// timeout) and apply the first one. Nothing happens if there are no completions.
+    pub async fn complete_line_and_render(
+        completion_provider: SafeCompletionProvider,
+        self_line_state: SafeLineState,
+        output_device: OutputDevice,
+    ) -> Result<(), ReadlineError> {
+        let (line, cursor_byte_index) = {
+            let mut line_state = self_line_state.lock().unwrap();
+            if line_state.is_paused.is_paused()
+                || line_state.maybe_history_search.is_some()
+            {
+                return Ok(());
+            }
+            let term = output_device_as_mut!(output_device);
+            if line_state.cycle_completion_and_render(term)? {
+                return Ok(());
+            }
+            line_state.get_line_and_cursor_byte_index()
+        };
+
+        let original_line_and_cursor = (line.clone(), cursor_byte_index);
+        let result_maybe_completions = tokio::time::timeout(
+            COMPLETION_TIMEOUT,
+            tokio::task::spawn_blocking(move || {
+                completion_provider.get_completions(&line, cursor_byte_index)
+            }),
+        )
+        .await;
+        let Ok(Ok(Some(completions))) = result_maybe_completions else {
+            return Ok(());
+        };
+
+        // Ignore the completions if the line was changed in the meantime.
+        let mut line_state = self_line_state.lock().unwrap();
+        if line_state.get_line_and_cursor_byte_index() != original_line_and_cursor {
+            return Ok(());
+        }
+        let term = output_device_as_mut!(output_device);
+        line_state.start_completion_and_render(completions, term)?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -20,11 +20,11 @@ use crossterm::{cursor::{MoveToColumn, MoveUp},
                 terminal::{Clear, ClearType},
                 QueueableCommand};
 use miette::IntoDiagnostic as _;
-use r3bl_core::ch;
+use r3bl_core::{ch, ChUnit, TruncationIndicator};
 use r3bl_tui::convert_from_tui_color_to_crossterm_color;
-use r3bl_tuify::clip_string_to_width_with_ellipsis;
 
 use crate::{spinner_render::style::style,
+            MultiSpinnerEntry,
             SendRawTerminal,
             SpinnerColor,
             SpinnerStyle,
@@ -32,6 +32,13 @@ use crate::{spinner_render::style::style,
             BLOCK_DOTS,
             BRAILLE_DOTS};
 
+/// Painted at the end of messages that are too wide to fit.
+const TRUNCATION_INDICATOR: TruncationIndicator = TruncationIndicator::three_dots();
+
+fn clip_string_to_width_with_ellipsis(text: String, display_width: ChUnit) -> String {
+    TRUNCATION_INDICATOR.truncate_end(&text, display_width)
+}
+
 pub fn render_tick(
     style: &mut SpinnerStyle,
     message: &str,
@@ -165,6 +172,95 @@ pub fn print_final_tick(
     Ok(())
 }
 
+/// Render one line for each of the (first `max_visible_count`) `entries`, each w/ its
+/// own style and animation. If there are more entries than that, a last line summarizes
+/// how many are hidden, eg: `+3 more`.
+pub fn render_multi_spinner_tick(
+    entries: &mut [MultiSpinnerEntry],
+    max_visible_count: usize,
+    display_width: usize,
+) -> Vec<String> {
+    let visible_count = std::cmp::min(entries.len(), max_visible_count);
+    let hidden_count = entries.len() - visible_count;
+
+    let mut acc = entries
+        .iter_mut()
+        .take(visible_count)
+        .map(|entry| {
+            render_tick(&mut entry.style, &entry.message, entry.count, display_width)
+        })
+        .collect::<Vec<_>>();
+
+    if hidden_count > 0 {
+        acc.push(clip_string_to_width_with_ellipsis(
+            format!("+{hidden_count} more"),
+            ch!(display_width),
+        ));
+    }
+
+    acc
+}
+
+/// Paint the `lines` starting at the current line, and clear anything that was painted
+/// below them before (eg: when a spinner is removed). The cursor is moved back to the
+/// first line, so that the next tick paints over the same lines. Everything is flushed at
+/// once, to avoid flicker.
+pub fn print_multi_spinner_tick(
+    lines: &[String],
+    writer: &mut SendRawTerminal,
+) -> miette::Result<()> {
+    queue_multi_spinner_lines(lines, writer)?;
+    writer.flush().into_diagnostic()?;
+    Ok(())
+}
+
+/// Paint the `final_output` of a spinner that has finished in place of the first line,
+/// and then paint the remaining `lines` below it.
+pub fn print_multi_spinner_final_tick(
+    final_output: &str,
+    lines: &[String],
+    writer: &mut SendRawTerminal,
+) -> miette::Result<()> {
+    writer
+        .queue(MoveToColumn(0))
+        .into_diagnostic()?
+        .queue(Clear(ClearType::CurrentLine))
+        .into_diagnostic()?
+        .queue(Print(format!("{}\n", final_output)))
+        .into_diagnostic()?;
+    queue_multi_spinner_lines(lines, writer)?;
+    writer.flush().into_diagnostic()?;
+    Ok(())
+}
+
+fn queue_multi_spinner_lines(
+    lines: &[String],
+    writer: &mut SendRawTerminal,
+) -> miette::Result<()> {
+    for line in lines {
+        writer
+            .queue(MoveToColumn(0))
+            .into_diagnostic()?
+            .queue(Clear(ClearType::CurrentLine))
+            .into_diagnostic()?
+            .queue(Print(format!("{}\n", line)))
+            .into_diagnostic()?;
+    }
+
+    writer
+        .queue(MoveToColumn(0))
+        .into_diagnostic()?
+        .queue(Clear(ClearType::FromCursorDown))
+        .into_diagnostic()?;
+
+    // MoveUp(0) moves up by 1 line, so it can't be used here.
+    if !lines.is_empty() {
+        writer.queue(MoveUp(lines.len() as u16)).into_diagnostic()?;
+    }
+
+    Ok(())
+}
+
 fn apply_color(output: &str, color: &mut SpinnerColor) -> String {
     let mut return_it = output.to_string();
     if let SpinnerColor::ColorWheel(ref mut color_wheel) = color {
@@ -177,3 +273,64 @@ fn apply_color(output: &str, color: &mut SpinnerColor) -> String {
     }
     return_it
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use r3bl_test_fixtures::StdoutMock;
+
+    use super::*;
+    use crate::StdMutex;
+
+    fn make_entries(count: usize) -> Vec<MultiSpinnerEntry> {
+        (0..count)
+            .map(|it| MultiSpinnerEntry {
+                name: format!("task {it}"),
+                message: format!("message {it}"),
+                style: SpinnerStyle {
+                    template: SpinnerTemplate::Braille,
+                    color: SpinnerColor::None,
+                },
+                count: it,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_multi_spinner_tick() {
+        let mut entries = make_entries(2);
+        assert_eq!(
+            render_multi_spinner_tick(&mut entries, 3, 80),
+            vec!["⠁ message 0", "⠃ message 1"]
+        );
+
+        // Overflow is summarized.
+        let mut entries = make_entries(5);
+        assert_eq!(
+            render_multi_spinner_tick(&mut entries, 3, 80),
+            vec!["⠁ message 0", "⠃ message 1", "⡇ message 2", "+2 more"]
+        );
+    }
+
+    #[test]
+    fn test_print_multi_spinner_final_tick() {
+        let stdout_mock = StdoutMock::default();
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let lines = vec!["⠃ message 1".to_string()];
+        print_multi_spinner_final_tick(
+            "done 0",
+            &lines,
+            &mut *safe_output_terminal.lock().unwrap(),
+        )
+        .unwrap();
+
+        // spell-checker:disable
+        assert_eq!(
+            stdout_mock.get_copy_of_buffer_as_string(),
+            "\u{1b}[1G\u{1b}[2Kdone 0\n\u{1b}[1G\u{1b}[2K⠃ message 1\n\u{1b}[1G\u{1b}[J\u{1b}[1A"
+        );
+        // spell-checker:enable
+    }
+}
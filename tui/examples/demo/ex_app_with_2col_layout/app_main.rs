@@ -129,7 +129,7 @@ mod app_main_impl_app_trait {
         ) -> CommonResult<EventPropagation> {
             // Try to handle left and right arrow key input events & return if handled.
             if let Continuation::Return =
-                handle_focus::handle_focus_switch(input_event, has_focus)
+                handle_focus::handle_focus_switch(&input_event, has_focus)
             {
                 return Ok(EventPropagation::ConsumedRender);
             }
@@ -298,7 +298,7 @@ mod handle_focus {
     use super::*;
 
     pub fn handle_focus_switch(
-        input_event: InputEvent,
+        input_event: &InputEvent,
         has_focus: &mut HasFocus,
     ) -> Continuation<String> {
         let mut event_consumed = false;
@@ -310,7 +310,7 @@ mod handle_focus {
         }
 
         // Handle Left, Right to switch focus between columns.
-        if let InputEvent::Keyboard(keypress) = input_event {
+        if let InputEvent::Keyboard(keypress) = *input_event {
             match keypress {
                 KeyPress::Plain {
                     key: Key::SpecialKey(SpecialKey::Left),
@@ -47,8 +47,11 @@ use r3bl_tui::{box_end,
                surface,
                App,
                BoxedSafeApp,
+               CheckboxToggleMode,
+               ClipboardExportMode,
                ComponentRegistry,
                ComponentRegistryMap,
+               CurrentLineHighlightMode,
                DialogBuffer,
                DialogChoice,
                DialogComponent,
@@ -64,13 +67,21 @@ use r3bl_tui::{box_end,
                GlobalData,
                HasEditorBuffers,
                HasFocus,
+               HorizontalOverflowMarkersMode,
+               IndentStyle,
+               InlineDiagnosticsMode,
                InputEvent,
                Key,
                KeyPress,
                LayoutDirection,
                LayoutManagement,
                LineMode,
+               LineWrapMode,
+               ListRenumberingMode,
+               MinimapMode,
                ModifierKeysMask,
+               MultiCaretMode,
+               OverwriteMode,
                PerformPositioningAndSizing,
                RenderOp,
                RenderPipeline,
@@ -79,6 +90,7 @@ use r3bl_tui::{box_end,
                SurfaceRender,
                SyntaxHighlightMode,
                TerminalWindowMainThreadSignal,
+               TypewriterScrollingMode,
                ZOrder,
                DEBUG_TUI_MOD};
 use tokio::sync::mpsc::Sender;
@@ -165,7 +177,7 @@ mod app_main_impl_app_trait {
             // Check to see if the modal dialog should be activated.
             if let modal_dialogs::ModalActivateResult::Yes =
                 modal_dialogs::should_activate(
-                    input_event,
+                    &input_event,
                     component_registry_map,
                     has_focus,
                     state,
@@ -309,7 +321,7 @@ mod modal_dialogs {
     }
 
     pub fn should_activate(
-        input_event: InputEvent,
+        input_event: &InputEvent,
         component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
         has_focus: &mut HasFocus,
         state: &mut State,
@@ -614,6 +626,18 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            clipboard_export: ClipboardExportMode::PlainText,
+            inline_diagnostics: InlineDiagnosticsMode::Disable,
+            minimap: MinimapMode::Disable,
+            list_renumbering: ListRenumberingMode::Disable,
+            typewriter_scrolling: TypewriterScrollingMode::Disable,
+            overwrite_mode: OverwriteMode::Disable,
+            current_line_highlight: CurrentLineHighlightMode::Disable,
+            multi_caret: MultiCaretMode::Disable,
+            horizontal_overflow_markers: HorizontalOverflowMarkersMode::Disable,
+            checkbox_toggle: CheckboxToggleMode::Disable,
+            line_wrap: LineWrapMode::None,
+            indent_style: IndentStyle::default(),
         };
 
         let boxed_dialog_component = {
@@ -697,6 +721,18 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            clipboard_export: ClipboardExportMode::PlainText,
+            inline_diagnostics: InlineDiagnosticsMode::Disable,
+            minimap: MinimapMode::Disable,
+            list_renumbering: ListRenumberingMode::Disable,
+            typewriter_scrolling: TypewriterScrollingMode::Disable,
+            overwrite_mode: OverwriteMode::Disable,
+            current_line_highlight: CurrentLineHighlightMode::Disable,
+            multi_caret: MultiCaretMode::Disable,
+            horizontal_overflow_markers: HorizontalOverflowMarkersMode::Disable,
+            checkbox_toggle: CheckboxToggleMode::Disable,
+            line_wrap: LineWrapMode::None,
+            indent_style: IndentStyle::default(),
         };
 
         let boxed_dialog_component = {
@@ -144,6 +144,10 @@ mod impl_editor_support {
         fn contains_editor_buffer(&self, id: FlexBoxId) -> bool {
             self.editor_buffers.contains_key(&id)
         }
+
+        fn get_editor_buffer(&self, id: FlexBoxId) -> Option<&EditorBuffer> {
+            self.editor_buffers.get(&id)
+        }
     }
 }
 
@@ -127,6 +127,10 @@ mod state_impl {
         fn contains_editor_buffer(&self, id: FlexBoxId) -> bool {
             self.editor_buffers.contains_key(&id)
         }
+
+        fn get_editor_buffer(&self, id: FlexBoxId) -> Option<&EditorBuffer> {
+            self.editor_buffers.get(&id)
+        }
     }
 }
 
@@ -935,8 +935,11 @@
 #![warn(clippy::all)]
 #![warn(clippy::unwrap_in_result)]
 #![warn(rust_2018_idioms)]
+// Benchmarks use the `test` crate, which needs nightly.
+#![cfg_attr(test, feature(test))]
 
 // Attach.
+pub mod md;
 pub mod tui;
 
 // Re-export.
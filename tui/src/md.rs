@@ -0,0 +1,483 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! The public API of the Markdown parser, for apps that want to parse Markdown and use
+//! the AST (abstract syntax tree) w/out the rest of the TUI framework. The parser itself
+//! lives in [crate::md_parser], and this module just re-exports the parts of it that are
+//! meant to be used from outside of this crate.
+//!
+//! ```rust
+//! use r3bl_tui::md::{parse, MdBlock, MdLineFragment};
+//!
+//! let (_remainder, document) = parse("# Hello\nSome *bold* text\n").unwrap();
+//! assert!(matches!(document[0], MdBlock::Heading(_)));
+//! assert!(matches!(
+//!     &document[1],
+//!     MdBlock::Text(fragments) if fragments[1] == MdLineFragment::Bold("bold")
+//! ));
+//! ```
+//!
+//! The AST borrows its text from the input, so it can't outlive it.
+//!
+//! # Serde
+//!
+//! With the `md-serde` feature, all the types in the AST implement `Serialize` &
+//! `Deserialize`, eg: to save the parsed document as JSON. Since the AST borrows its
+//! text, deserializing it also borrows from the (JSON) input, which fails if a string in
+//! it has to be unescaped (eg: it has a `\n` or a `"`). To load any JSON, deserialize it
+//! into the [owned] mirror of the AST instead, which is serialized the same way, and
+//! borrow the AST from it w/ [owned::as_borrowed()].
+//!
+//! ```rust
+//! # #[cfg(feature = "md-serde")]
+//! # {
+//! use r3bl_tui::md::{owned, parse};
+//!
+//! let input = "Say \"hi\"\n```\nlet a;\n```\n";
+//! let (_, document) = parse(input).unwrap();
+//! let json = serde_json::to_string(&document).unwrap();
+//!
+//! let loaded: owned::MdDocument = serde_json::from_str(&json).unwrap();
+//! assert_eq!(owned::as_borrowed(&loaded), document);
+//! # }
+//! ```
+
+pub use crate::{convert_md_document_to_html as to_html,
+                parse_markdown as parse,
+                BulletKind,
+                CodeBlockLine,
+                CodeBlockLineContent,
+                CodeBlockLines,
+                ColumnAlignment,
+                HeadingData,
+                HeadingLevel,
+                HyperlinkData,
+                Lines,
+                List,
+                MdBlock,
+                MdDocument,
+                MdLineFragment,
+                MdLineFragments};
+
+/// An owned mirror of the AST, for deserializing JSON that the borrowed AST can't borrow
+/// its text from. Each type has the same shape (and is serialized the same way) as the
+/// borrowed type w/ the same name, but owns its text.
+#[cfg(feature = "md-serde")]
+pub mod owned {
+    use serde::{Deserialize, Serialize};
+
+    use super::{BulletKind, ColumnAlignment, HeadingLevel, List};
+
+    pub type MdDocument = List<MdBlock>;
+    pub type MdLineFragments = List<MdLineFragment>;
+    pub type Lines = List<MdLineFragments>;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, size_of::SizeOf)]
+    pub struct HeadingData {
+        pub heading_level: HeadingLevel,
+        pub text: String,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, size_of::SizeOf)]
+    pub enum MdBlock {
+        Heading(HeadingData),
+        SmartList((Lines, BulletKind, usize)),
+        Text(MdLineFragments),
+        CodeBlock(List<CodeBlockLine>),
+        Title(String),
+        Date(String),
+        Tags(List<String>),
+        Authors(List<String>),
+        FrontMatter(String),
+        BlockQuote {
+            #[size_of(skip_bounds)]
+            blocks: List<MdBlock>,
+            raw_lines: Vec<String>,
+        },
+        FootnoteDef {
+            label: String,
+            content: MdLineFragments,
+        },
+        Table {
+            headers: Vec<MdLineFragments>,
+            alignments: Vec<ColumnAlignment>,
+            rows: Vec<Vec<MdLineFragments>>,
+            raw_lines: Vec<String>,
+        },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    pub enum MdLineFragment {
+        UnorderedListBullet {
+            indent: usize,
+            is_first_line: bool,
+        },
+        OrderedListBullet {
+            indent: usize,
+            number: usize,
+            is_first_line: bool,
+        },
+        Plain(String),
+        Bold(String),
+        Italic(String),
+        InlineCode(String),
+        Strikethrough(MdLineFragments),
+        Highlight(MdLineFragments),
+        Link(HyperlinkData),
+        Image(HyperlinkData),
+        Checkbox(bool),
+        FootnoteRef(String),
+    }
+
+    /// See [super::MdLineFragment] for why this isn't derived.
+    impl size_of::SizeOf for MdLineFragment {
+        fn size_of_children(&self, context: &mut size_of::Context) {
+            match self {
+                MdLineFragment::Plain(text)
+                | MdLineFragment::Bold(text)
+                | MdLineFragment::Italic(text)
+                | MdLineFragment::InlineCode(text)
+                | MdLineFragment::FootnoteRef(text) => text.size_of_children(context),
+                MdLineFragment::Strikethrough(fragments)
+                | MdLineFragment::Highlight(fragments) => {
+                    fragments.size_of_children(context)
+                }
+                MdLineFragment::Link(it) | MdLineFragment::Image(it) => {
+                    it.size_of_children(context)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, size_of::SizeOf)]
+    pub struct HyperlinkData {
+        pub text: String,
+        pub url: String,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, size_of::SizeOf)]
+    pub struct CodeBlockLine {
+        pub language: Option<String>,
+        pub content: CodeBlockLineContent,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, size_of::SizeOf)]
+    pub enum CodeBlockLineContent {
+        Text(String),
+        StartTag,
+        EndTag,
+    }
+
+    /// Copies the text of the `document` into an owned [MdDocument].
+    pub fn to_owned(document: &super::MdDocument<'_>) -> MdDocument {
+        map_list(document, |block| block.into())
+    }
+
+    /// Returns the AST that borrows its text from the owned `document`.
+    pub fn as_borrowed(document: &MdDocument) -> super::MdDocument<'_> {
+        map_list(document, |block| block.into())
+    }
+
+    fn map_list<'a, T, U>(list: &'a List<T>, f: impl FnMut(&'a T) -> U) -> List<U>
+    where
+        T: size_of::SizeOf,
+        U: size_of::SizeOf,
+    {
+        List::from(list.iter().map(f).collect::<Vec<_>>())
+    }
+
+    fn to_strings(strings: &[&str]) -> Vec<String> {
+        strings.iter().map(|it| it.to_string()).collect()
+    }
+
+    fn map_lines(lines: &super::Lines<'_>) -> Lines {
+        map_list(lines, |fragments| map_list(fragments, |it| it.into()))
+    }
+
+    fn map_lines_back(lines: &Lines) -> super::Lines<'_> {
+        map_list(lines, |fragments| map_list(fragments, |it| it.into()))
+    }
+
+    impl From<&super::MdBlock<'_>> for MdBlock {
+        fn from(block: &super::MdBlock<'_>) -> Self {
+            use super::MdBlock as B;
+            match block {
+                B::Heading(it) => MdBlock::Heading(HeadingData {
+                    heading_level: it.heading_level,
+                    text: it.text.to_string(),
+                }),
+                B::SmartList((lines, bullet_kind, indent)) => {
+                    MdBlock::SmartList((map_lines(lines), *bullet_kind, *indent))
+                }
+                B::Text(fragments) => MdBlock::Text(map_list(fragments, |it| it.into())),
+                B::CodeBlock(lines) => {
+                    MdBlock::CodeBlock(map_list(lines, |it| it.into()))
+                }
+                B::Title(it) => MdBlock::Title(it.to_string()),
+                B::Date(it) => MdBlock::Date(it.to_string()),
+                B::Tags(it) => MdBlock::Tags(to_strings(it).into()),
+                B::Authors(it) => MdBlock::Authors(to_strings(it).into()),
+                B::FrontMatter(it) => MdBlock::FrontMatter(it.to_string()),
+                B::BlockQuote { blocks, raw_lines } => MdBlock::BlockQuote {
+                    blocks: map_list(blocks, |it| it.into()),
+                    raw_lines: to_strings(raw_lines),
+                },
+                B::FootnoteDef { label, content } => MdBlock::FootnoteDef {
+                    label: label.to_string(),
+                    content: map_list(content, |it| it.into()),
+                },
+                B::Table {
+                    headers,
+                    alignments,
+                    rows,
+                    raw_lines,
+                } => MdBlock::Table {
+                    headers: headers
+                        .iter()
+                        .map(|it| map_list(it, |it| it.into()))
+                        .collect(),
+                    alignments: alignments.clone(),
+                    rows: rows
+                        .iter()
+                        .map(|row| {
+                            row.iter().map(|it| map_list(it, |it| it.into())).collect()
+                        })
+                        .collect(),
+                    raw_lines: to_strings(raw_lines),
+                },
+            }
+        }
+    }
+
+    impl<'a> From<&'a MdBlock> for super::MdBlock<'a> {
+        fn from(block: &'a MdBlock) -> Self {
+            use super::MdBlock as B;
+            match block {
+                MdBlock::Heading(it) => B::Heading(super::HeadingData {
+                    heading_level: it.heading_level,
+                    text: &it.text,
+                }),
+                MdBlock::SmartList((lines, bullet_kind, indent)) => {
+                    B::SmartList((map_lines_back(lines), *bullet_kind, *indent))
+                }
+                MdBlock::Text(fragments) => B::Text(map_list(fragments, |it| it.into())),
+                MdBlock::CodeBlock(lines) => {
+                    B::CodeBlock(map_list(lines, |it| it.into()))
+                }
+                MdBlock::Title(it) => B::Title(it),
+                MdBlock::Date(it) => B::Date(it),
+                MdBlock::Tags(it) => B::Tags(map_list(it, |it| it.as_str())),
+                MdBlock::Authors(it) => B::Authors(map_list(it, |it| it.as_str())),
+                MdBlock::FrontMatter(it) => B::FrontMatter(it),
+                MdBlock::BlockQuote { blocks, raw_lines } => B::BlockQuote {
+                    blocks: map_list(blocks, |it| it.into()),
+                    raw_lines: raw_lines.iter().map(|it| it.as_str()).collect(),
+                },
+                MdBlock::FootnoteDef { label, content } => B::FootnoteDef {
+                    label,
+                    content: map_list(content, |it| it.into()),
+                },
+                MdBlock::Table {
+                    headers,
+                    alignments,
+                    rows,
+                    raw_lines,
+                } => B::Table {
+                    headers: headers
+                        .iter()
+                        .map(|it| map_list(it, |it| it.into()))
+                        .collect(),
+                    alignments: alignments.clone(),
+                    rows: rows
+                        .iter()
+                        .map(|row| {
+                            row.iter().map(|it| map_list(it, |it| it.into())).collect()
+                        })
+                        .collect(),
+                    raw_lines: raw_lines.iter().map(|it| it.as_str()).collect(),
+                },
+            }
+        }
+    }
+
+    impl From<&super::MdLineFragment<'_>> for MdLineFragment {
+        fn from(fragment: &super::MdLineFragment<'_>) -> Self {
+            use super::MdLineFragment as F;
+            match fragment {
+                F::UnorderedListBullet {
+                    indent,
+                    is_first_line,
+                } => MdLineFragment::UnorderedListBullet {
+                    indent: *indent,
+                    is_first_line: *is_first_line,
+                },
+                F::OrderedListBullet {
+                    indent,
+                    number,
+                    is_first_line,
+                } => MdLineFragment::OrderedListBullet {
+                    indent: *indent,
+                    number: *number,
+                    is_first_line: *is_first_line,
+                },
+                F::Plain(it) => MdLineFragment::Plain(it.to_string()),
+                F::Bold(it) => MdLineFragment::Bold(it.to_string()),
+                F::Italic(it) => MdLineFragment::Italic(it.to_string()),
+                F::InlineCode(it) => MdLineFragment::InlineCode(it.to_string()),
+                F::Strikethrough(it) => {
+                    MdLineFragment::Strikethrough(map_list(it, |it| it.into()))
+                }
+                F::Highlight(it) => {
+                    MdLineFragment::Highlight(map_list(it, |it| it.into()))
+                }
+                F::Link(it) => MdLineFragment::Link(it.into()),
+                F::Image(it) => MdLineFragment::Image(it.into()),
+                F::Checkbox(it) => MdLineFragment::Checkbox(*it),
+                F::FootnoteRef(it) => MdLineFragment::FootnoteRef(it.to_string()),
+            }
+        }
+    }
+
+    impl<'a> From<&'a MdLineFragment> for super::MdLineFragment<'a> {
+        fn from(fragment: &'a MdLineFragment) -> Self {
+            use super::MdLineFragment as F;
+            match fragment {
+                MdLineFragment::UnorderedListBullet {
+                    indent,
+                    is_first_line,
+                } => F::UnorderedListBullet {
+                    indent: *indent,
+                    is_first_line: *is_first_line,
+                },
+                MdLineFragment::OrderedListBullet {
+                    indent,
+                    number,
+                    is_first_line,
+                } => F::OrderedListBullet {
+                    indent: *indent,
+                    number: *number,
+                    is_first_line: *is_first_line,
+                },
+                MdLineFragment::Plain(it) => F::Plain(it),
+                MdLineFragment::Bold(it) => F::Bold(it),
+                MdLineFragment::Italic(it) => F::Italic(it),
+                MdLineFragment::InlineCode(it) => F::InlineCode(it),
+                MdLineFragment::Strikethrough(it) => {
+                    F::Strikethrough(map_list(it, |it| it.into()))
+                }
+                MdLineFragment::Highlight(it) => {
+                    F::Highlight(map_list(it, |it| it.into()))
+                }
+                MdLineFragment::Link(it) => F::Link(it.into()),
+                MdLineFragment::Image(it) => F::Image(it.into()),
+                MdLineFragment::Checkbox(it) => F::Checkbox(*it),
+                MdLineFragment::FootnoteRef(it) => F::FootnoteRef(it),
+            }
+        }
+    }
+
+    impl From<&super::HyperlinkData<'_>> for HyperlinkData {
+        fn from(it: &super::HyperlinkData<'_>) -> Self {
+            HyperlinkData {
+                text: it.text.to_string(),
+                url: it.url.to_string(),
+            }
+        }
+    }
+
+    impl<'a> From<&'a HyperlinkData> for super::HyperlinkData<'a> {
+        fn from(it: &'a HyperlinkData) -> Self {
+            super::HyperlinkData::new(&it.text, &it.url)
+        }
+    }
+
+    impl From<&super::CodeBlockLine<'_>> for CodeBlockLine {
+        fn from(line: &super::CodeBlockLine<'_>) -> Self {
+            CodeBlockLine {
+                language: line.language.map(|it| it.to_string()),
+                content: match line.content {
+                    super::CodeBlockLineContent::Text(it) => {
+                        CodeBlockLineContent::Text(it.to_string())
+                    }
+                    super::CodeBlockLineContent::StartTag => {
+                        CodeBlockLineContent::StartTag
+                    }
+                    super::CodeBlockLineContent::EndTag => CodeBlockLineContent::EndTag,
+                },
+            }
+        }
+    }
+
+    impl<'a> From<&'a CodeBlockLine> for super::CodeBlockLine<'a> {
+        fn from(line: &'a CodeBlockLine) -> Self {
+            super::CodeBlockLine {
+                language: line.language.as_deref(),
+                content: match &line.content {
+                    CodeBlockLineContent::Text(it) => {
+                        super::CodeBlockLineContent::Text(it)
+                    }
+                    CodeBlockLineContent::StartTag => {
+                        super::CodeBlockLineContent::StartTag
+                    }
+                    CodeBlockLineContent::EndTag => super::CodeBlockLineContent::EndTag,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "md-serde"))]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_serde_round_trip() {
+        let input =
+            "# Title\n> - [link](url)\n| a |\n|:-:|\n| `b` |\n```rust\nlet a;\n```\n";
+        let (_, document) = parse(input).unwrap();
+
+        let json = serde_json::to_string(&document).unwrap();
+        let deserialized: MdDocument<'_> = serde_json::from_str(&json).unwrap();
+        assert_eq2!(deserialized, document);
+    }
+
+    #[test]
+    fn test_serde_round_trip_w_escaped_text() {
+        let input = concat!(
+            "# Say \"hi\"\n",
+            "A \\ backslash, `\"quoted\"` code, and ~~*\"struck\"*~~\n",
+            "```rust\n",
+            "let a = \"a\\tb\";\n",
+            "let b = '\\\\';\n",
+            "```\n",
+        );
+        let (_, document) = parse(input).unwrap();
+
+        let json = serde_json::to_string(&document).unwrap();
+
+        // The strings in the JSON are escaped, so the borrowed AST can't be loaded from it.
+        assert!(serde_json::from_str::<MdDocument<'_>>(&json).is_err());
+
+        let loaded: owned::MdDocument = serde_json::from_str(&json).unwrap();
+        assert_eq2!(loaded, owned::to_owned(&document));
+        assert_eq2!(owned::as_borrowed(&loaded), document);
+        assert_eq2!(serde_json::to_string(&loaded).unwrap(), json);
+    }
+}
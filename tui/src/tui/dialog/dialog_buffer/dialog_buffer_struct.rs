@@ -20,7 +20,7 @@ use std::fmt::{Debug, Formatter, Result};
 use r3bl_core::{ch, ChUnit};
 use serde::{Deserialize, Serialize};
 
-use crate::{format_option, EditorBuffer, DEFAULT_SYN_HI_FILE_EXT};
+use crate::{format_option, AutocompleteStatus, EditorBuffer, DEFAULT_SYN_HI_FILE_EXT};
 
 /// Please do not construct this struct directly and use [new_empty](DialogBuffer::new_empty)
 /// instead.
@@ -32,6 +32,10 @@ pub struct DialogBuffer {
     pub editor_buffer: EditorBuffer,
     pub title: String,
     pub maybe_results: Option<Vec<String>>,
+    /// Shown in the results panel (in autocomplete mode) while there are no results, eg:
+    /// when the autocomplete provider is being retried. See
+    /// [fetch_autocomplete_results_with_retry](crate::fetch_autocomplete_results_with_retry).
+    pub autocomplete_status: AutocompleteStatus,
 }
 
 impl DialogBuffer {
@@ -53,6 +57,7 @@ impl DialogBuffer {
             ),
             title: Default::default(),
             maybe_results: None,
+            autocomplete_status: AutocompleteStatus::Idle,
         }
     }
 }
@@ -64,10 +69,12 @@ impl Debug for DialogBuffer {
           "\nDialogBuffer [      \n\
           ├ title: {}            \n\
           ├ maybe_results: {:?}  \n\
+          ├ autocomplete_status: {:?}  \n\
           └ editor_buffer: {}  \n\
           ]",
           self.title,
           maybe_results,
+          self.autocomplete_status,
           self.editor_buffer.get_as_string_with_comma_instead_of_newlines()
         }
     }
@@ -27,6 +27,7 @@ use crate::{Component,
             DialogEngineArgs,
             DialogEngineConfigOptions,
             EditorEngineConfig,
+            EventConsumption,
             EventPropagation,
             FlexBox,
             FlexBoxId,
@@ -222,6 +223,18 @@ where
             }
         }
     }
+
+    fn would_consume_event(
+        &self,
+        _global_data: &GlobalData<S, AS>,
+        input_event: InputEvent,
+        _has_focus: &HasFocus,
+    ) -> EventConsumption {
+        match DialogEngineApi::would_apply_event(&self.data.dialog_engine, input_event) {
+            Some(description) => EventConsumption::Consumed(Some(description)),
+            None => EventConsumption::Propagate,
+        }
+    }
 }
 
 impl<S, AS> DialogComponent<S, AS>
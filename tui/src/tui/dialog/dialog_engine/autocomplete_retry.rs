@@ -0,0 +1,459 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Retry failed calls to an async autocomplete provider w/ exponential backoff, for a
+//! dialog in [DialogEngineMode::ModalAutocomplete](crate::DialogEngineMode) mode.
+//!
+//! The dialog doesn't call the provider itself. The app does that in its
+//! [OnDialogEditorChangeFn](crate::OnDialogEditorChangeFn) (which runs on every
+//! keystroke), by spawning a task that calls [fetch_autocomplete_results_with_retry]:
+//! 1. Call [AutocompleteRequestTracker::start_request] before spawning the task. This
+//!    cancels any pending retries for older keystrokes, since their results are stale.
+//! 2. In the task, pass the provider to [fetch_autocomplete_results_with_retry]. The
+//!    backoff is awaited (w/ [tokio::time::sleep]), so it never blocks the UI, and it is
+//!    cut short as soon as a newer request is started.
+//! 3. Dispatch an action (w/ the main thread channel sender) for every
+//!    [AutocompleteStatus] change, and for the [AutocompleteFetchResult], which sets
+//!    [DialogBuffer::autocomplete_status](crate::DialogBuffer::autocomplete_status) and
+//!    [DialogBuffer::maybe_results](crate::DialogBuffer::maybe_results). The results
+//!    panel shows a retrying indicator, or the error, while there are no results.
+//!
+//! The provider can return [AutocompleteError::permanent] for errors that won't go away
+//! by trying again (eg: a malformed query), so that they are not retried.
+
+use std::{future::Future,
+          sync::{atomic::{AtomicU64, Ordering},
+                 Arc},
+          time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+/// Default delay before the first retry. It is doubled for each retry after that.
+pub const DEFAULT_AUTOCOMPLETE_RETRY_INITIAL_BACKOFF: Duration =
+    Duration::from_millis(250);
+
+/// Default upper limit of the delay between retries.
+pub const DEFAULT_AUTOCOMPLETE_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Default number of times the provider is called (including the first call).
+pub const DEFAULT_AUTOCOMPLETE_RETRY_MAX_ATTEMPTS: u8 = 3;
+
+/// Whether [fetch_autocomplete_results_with_retry] retries failed provider calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AutocompleteRetryMode {
+    /// The provider is called once, and an error is shown right away if it fails.
+    #[default]
+    Disable,
+    Enable(AutocompleteRetryConfig),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutocompleteRetryConfig {
+    pub max_attempts: u8,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for AutocompleteRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_AUTOCOMPLETE_RETRY_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_AUTOCOMPLETE_RETRY_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_AUTOCOMPLETE_RETRY_MAX_BACKOFF,
+        }
+    }
+}
+
+impl AutocompleteRetryConfig {
+    pub fn with_max_attempts(mut self, max_attempts: u8) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// The delay before retrying after the given (1 based) attempt has failed. It
+    /// doubles after each attempt, up to the max backoff.
+    pub fn get_backoff(&self, failed_attempt: u8) -> Duration {
+        let exponent = u32::from(failed_attempt.saturating_sub(1)).min(16);
+        self.initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff)
+    }
+}
+
+impl AutocompleteRetryMode {
+    /// The provider is always called at least once.
+    pub fn get_max_attempts(&self) -> u8 {
+        match self {
+            AutocompleteRetryMode::Disable => 1,
+            AutocompleteRetryMode::Enable(config) => config.max_attempts.max(1),
+        }
+    }
+}
+
+/// An error returned by an autocomplete provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutocompleteError {
+    pub message: String,
+    /// If this is false, then the provider is not called again.
+    pub is_retryable: bool,
+}
+
+impl AutocompleteError {
+    /// A transient error (eg: a network timeout), which may succeed if retried.
+    pub fn retryable(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            is_retryable: true,
+        }
+    }
+
+    /// An error that won't go away by retrying.
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            is_retryable: false,
+        }
+    }
+}
+
+/// What the results panel shows while it doesn't have any results.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutocompleteStatus {
+    /// Nothing to show, or the results are ready.
+    #[default]
+    Idle,
+    /// The provider failed, and it is about to be called again.
+    Retrying { attempt: u8, max_attempts: u8 },
+    /// The provider failed, and it won't be called again for this query.
+    Failed(String),
+}
+
+/// The outcome of [fetch_autocomplete_results_with_retry].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutocompleteFetchResult {
+    Results(Vec<String>),
+    /// All the attempts failed, or the error was not retryable.
+    Failed(AutocompleteError),
+    /// A newer request was started, so the results (or error) of this one are stale,
+    /// and must be dropped.
+    Cancelled,
+}
+
+/// Identifies a request that was started w/ [AutocompleteRequestTracker::start_request].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AutocompleteRequestId(u64);
+
+/// Keeps track of the latest autocomplete request, so that pending retries for older
+/// requests can be cancelled. It is cheap to clone, and all the clones share the same
+/// state, so one can be moved into each spawned task.
+#[derive(Debug, Clone, Default)]
+pub struct AutocompleteRequestTracker {
+    latest_request_id: Arc<AtomicU64>,
+    /// Wakes up the tasks that are waiting in [AutocompleteRequestTracker::cancelled].
+    new_request_notify: Arc<Notify>,
+}
+
+impl AutocompleteRequestTracker {
+    /// Start a new request, which cancels all the older ones.
+    pub fn start_request(&self) -> AutocompleteRequestId {
+        let request_id = AutocompleteRequestId(
+            self.latest_request_id.fetch_add(1, Ordering::SeqCst) + 1,
+        );
+        self.new_request_notify.notify_waiters();
+        request_id
+    }
+
+    pub fn is_latest(&self, request_id: AutocompleteRequestId) -> bool {
+        self.latest_request_id.load(Ordering::SeqCst) == request_id.0
+    }
+
+    /// Completes as soon as a request newer than `request_id` is started (or right away
+    /// if there already is one).
+    pub async fn cancelled(&self, request_id: AutocompleteRequestId) {
+        loop {
+            // Register for the notification before checking, so that a request that is
+            // started in between isn't missed.
+            let notified = self.new_request_notify.notified();
+            if !self.is_latest(request_id) {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Call the `provider` until it succeeds, it returns an error that isn't retryable, or
+/// the max attempts of the `retry_mode` are used up. `on_status_change` is called before
+/// each retry, and when it gives up, so that the dialog can show it. This returns
+/// [AutocompleteFetchResult::Cancelled] as soon as a newer request is started w/ the
+/// `tracker`, w/out waiting for the backoff to run out if it is waiting to retry.
+pub async fn fetch_autocomplete_results_with_retry<F, Fut>(
+    retry_mode: AutocompleteRetryMode,
+    tracker: &AutocompleteRequestTracker,
+    request_id: AutocompleteRequestId,
+    mut provider: F,
+    mut on_status_change: impl FnMut(AutocompleteStatus),
+) -> AutocompleteFetchResult
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Vec<String>, AutocompleteError>>,
+{
+    let max_attempts = retry_mode.get_max_attempts();
+    let mut attempt = 1;
+    loop {
+        if !tracker.is_latest(request_id) {
+            return AutocompleteFetchResult::Cancelled;
+        }
+
+        let result = provider().await;
+
+        // A newer keystroke arrived while the provider was running.
+        if !tracker.is_latest(request_id) {
+            return AutocompleteFetchResult::Cancelled;
+        }
+
+        match (result, retry_mode) {
+            (Ok(results), _) => return AutocompleteFetchResult::Results(results),
+            (Err(error), AutocompleteRetryMode::Enable(config))
+                if error.is_retryable && attempt < max_attempts =>
+            {
+                on_status_change(AutocompleteStatus::Retrying {
+                    attempt: attempt + 1,
+                    max_attempts,
+                });
+                tokio::select! {
+                    _ = tokio::time::sleep(config.get_backoff(attempt)) => {}
+                    _ = tracker.cancelled(request_id) => {
+                        return AutocompleteFetchResult::Cancelled;
+                    }
+                }
+                attempt += 1;
+            }
+            (Err(error), _) => {
+                on_status_change(AutocompleteStatus::Failed(error.message.clone()));
+                return AutocompleteFetchResult::Failed(error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    fn make_retry_mode(max_attempts: u8) -> AutocompleteRetryMode {
+        AutocompleteRetryMode::Enable(
+            AutocompleteRetryConfig::default()
+                .with_max_attempts(max_attempts)
+                .with_initial_backoff(MS)
+                .with_max_backoff(2 * MS),
+        )
+    }
+
+    #[test]
+    fn test_get_backoff() {
+        let config = AutocompleteRetryConfig::default()
+            .with_initial_backoff(100 * MS)
+            .with_max_backoff(350 * MS);
+        assert_eq!(config.get_backoff(1), 100 * MS);
+        assert_eq!(config.get_backoff(2), 200 * MS);
+        assert_eq!(config.get_backoff(3), 350 * MS);
+        assert_eq!(config.get_backoff(u8::MAX), 350 * MS);
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let tracker = AutocompleteRequestTracker::default();
+        let request_id = tracker.start_request();
+        let call_count = RefCell::new(0);
+        let mut statuses = vec![];
+
+        let result = fetch_autocomplete_results_with_retry(
+            make_retry_mode(3),
+            &tracker,
+            request_id,
+            || {
+                *call_count.borrow_mut() += 1;
+                let call_count = *call_count.borrow();
+                async move {
+                    match call_count {
+                        3 => Ok(vec!["foo".to_string()]),
+                        _ => Err(AutocompleteError::retryable("timeout")),
+                    }
+                }
+            },
+            |status| statuses.push(status),
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            AutocompleteFetchResult::Results(vec!["foo".to_string()])
+        );
+        assert_eq!(
+            statuses,
+            vec![
+                AutocompleteStatus::Retrying {
+                    attempt: 2,
+                    max_attempts: 3
+                },
+                AutocompleteStatus::Retrying {
+                    attempt: 3,
+                    max_attempts: 3
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_give_up_after_max_attempts() {
+        let tracker = AutocompleteRequestTracker::default();
+        let request_id = tracker.start_request();
+        let call_count = RefCell::new(0);
+        let mut statuses = vec![];
+
+        let result = fetch_autocomplete_results_with_retry(
+            make_retry_mode(2),
+            &tracker,
+            request_id,
+            || {
+                *call_count.borrow_mut() += 1;
+                async { Err(AutocompleteError::retryable("timeout")) }
+            },
+            |status| statuses.push(status),
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            AutocompleteFetchResult::Failed(AutocompleteError::retryable("timeout"))
+        );
+        assert_eq!(*call_count.borrow(), 2);
+        assert_eq!(
+            statuses.last(),
+            Some(&AutocompleteStatus::Failed("timeout".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_permanent_error_and_disabled_mode_are_not_retried() {
+        let tracker = AutocompleteRequestTracker::default();
+
+        for (retry_mode, error) in [
+            (
+                make_retry_mode(3),
+                AutocompleteError::permanent("bad query"),
+            ),
+            (
+                AutocompleteRetryMode::Disable,
+                AutocompleteError::retryable("timeout"),
+            ),
+        ] {
+            let request_id = tracker.start_request();
+            let call_count = RefCell::new(0);
+            let result = fetch_autocomplete_results_with_retry(
+                retry_mode,
+                &tracker,
+                request_id,
+                || {
+                    *call_count.borrow_mut() += 1;
+                    let error = error.clone();
+                    async move { Err(error) }
+                },
+                |_| {},
+            )
+            .await;
+
+            assert_eq!(result, AutocompleteFetchResult::Failed(error));
+            assert_eq!(*call_count.borrow(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_newer_request_cancels_pending_retries() {
+        let tracker = AutocompleteRequestTracker::default();
+        let request_id = tracker.start_request();
+        let call_count = RefCell::new(0);
+
+        let result = fetch_autocomplete_results_with_retry(
+            make_retry_mode(5),
+            &tracker,
+            request_id,
+            || {
+                *call_count.borrow_mut() += 1;
+                // A newer keystroke arrives while the first call is in flight.
+                tracker.start_request();
+                async { Err(AutocompleteError::retryable("timeout")) }
+            },
+            |_| {},
+        )
+        .await;
+
+        assert_eq!(result, AutocompleteFetchResult::Cancelled);
+        assert_eq!(*call_count.borrow(), 1);
+        assert!(!tracker.is_latest(request_id));
+    }
+
+    #[tokio::test]
+    async fn test_newer_request_cuts_the_backoff_short() {
+        let tracker = AutocompleteRequestTracker::default();
+        let request_id = tracker.start_request();
+        let retry_mode = AutocompleteRetryMode::Enable(
+            AutocompleteRetryConfig::default()
+                .with_initial_backoff(Duration::from_secs(60))
+                .with_max_backoff(Duration::from_secs(60)),
+        );
+
+        // A newer keystroke arrives while the first request is waiting to retry.
+        let newer_request = {
+            let tracker = tracker.clone();
+            async move {
+                tokio::time::sleep(10 * MS).await;
+                tracker.start_request();
+            }
+        };
+        let fetch = fetch_autocomplete_results_with_retry(
+            retry_mode,
+            &tracker,
+            request_id,
+            || async { Err(AutocompleteError::retryable("timeout")) },
+            |_| {},
+        );
+
+        let (result, _) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(fetch, newer_request)
+        })
+        .await
+        .unwrap();
+        assert_eq!(result, AutocompleteFetchResult::Cancelled);
+    }
+}
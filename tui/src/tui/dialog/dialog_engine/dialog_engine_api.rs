@@ -37,6 +37,7 @@ use r3bl_core::{ch,
 use crate::{render_ops,
             render_pipeline,
             render_tui_styled_texts_into,
+            AutocompleteStatus,
             BorderGlyphCharacter,
             DialogBuffer,
             DialogChoice,
@@ -204,7 +205,7 @@ impl DialogEngineApi {
     {
         // Was a dialog choice made?
         if let Some(choice) = internal_impl::try_handle_dialog_choice(
-            input_event,
+            &input_event,
             mut_state.get_mut_dialog_buffer(self_id),
             dialog_engine,
         ) {
@@ -214,7 +215,7 @@ impl DialogEngineApi {
 
         // Was up / down pressed to select autocomplete results & vert scroll the results panel?
         if let EventPropagation::ConsumedRender = internal_impl::try_handle_up_down(
-            input_event,
+            &input_event,
             mut_state.get_mut_dialog_buffer(self_id),
             dialog_engine,
         ) {
@@ -251,6 +252,34 @@ impl DialogEngineApi {
             }
         }
     }
+
+    /// Returns a description of what [apply_event](DialogEngineApi::apply_event) would do
+    /// w/ the given [InputEvent], or [None] if it would be a noop. This does not have any
+    /// side effects.
+    pub fn would_apply_event(
+        dialog_engine: &DialogEngine,
+        input_event: InputEvent,
+    ) -> Option<String> {
+        match DialogEvent::from(&input_event) {
+            DialogEvent::EnterPressed => return Some("Accept dialog".into()),
+            DialogEvent::EscPressed => return Some("Cancel dialog".into()),
+            DialogEvent::None => {}
+        }
+
+        if input_event.matches(&[
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Up),
+            }),
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Down),
+            }),
+        ]) {
+            return Some("Select result".into());
+        }
+
+        EditorEngineApi::would_apply_event(&dialog_engine.editor_engine, input_event)
+            .map(|editor_event| format!("{editor_event:?}"))
+    }
 }
 
 #[repr(u16)]
@@ -488,8 +517,8 @@ mod internal_impl {
         let mut it = render_ops!();
 
         if let Some(dialog_buffer) = state.get_mut_dialog_buffer(self_id) {
-            if let Some(results) = dialog_buffer.maybe_results.as_ref() {
-                if !results.is_empty() {
+            match dialog_buffer.maybe_results.as_ref() {
+                Some(results) if !results.is_empty() => {
                     paint_results(
                         &mut it,
                         origin_pos,
@@ -497,12 +526,66 @@ mod internal_impl {
                         results,
                         dialog_engine,
                     );
-                };
+                }
+                // No results, so show why (if the provider is being retried, or failed).
+                _ => paint_autocomplete_status(
+                    &mut it,
+                    origin_pos,
+                    bounds_size,
+                    &dialog_buffer.autocomplete_status,
+                    dialog_engine,
+                ),
             }
         };
 
         return Ok(it);
 
+        pub fn paint_autocomplete_status(
+            ops: &mut RenderOps,
+            origin_pos: &Position,
+            bounds_size: &Size,
+            autocomplete_status: &AutocompleteStatus,
+            dialog_engine: &DialogEngine,
+        ) {
+            let text = match autocomplete_status {
+                AutocompleteStatus::Idle => return,
+                AutocompleteStatus::Retrying {
+                    attempt,
+                    max_attempts,
+                } => format!("Retrying ({attempt}/{max_attempts})…"),
+                AutocompleteStatus::Failed(message) => format!("Error: {message}"),
+            };
+
+            let max_display_col_count = bounds_size.col_count - 2;
+            let text = UnicodeString::from(text);
+            let clipped_text = text.clip_to_width(ch!(0), max_display_col_count);
+
+            // The status is painted in the first row of the results panel.
+            let rel_insertion_pos = position!(
+                col_index: ch!(1),
+                row_index: ch!(DisplayConstants::SimpleModalRowCount as u16)
+            );
+
+            let style = match dialog_engine.dialog_options.maybe_style_results_panel {
+                Some(style) => TuiStyle { dim: true, ..style },
+                None => TuiStyle {
+                    dim: true,
+                    ..Default::default()
+                },
+            };
+
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionRelTo(
+                *origin_pos,
+                rel_insertion_pos,
+            ));
+            ops.push(RenderOp::ApplyColors(Some(style)));
+            ops.push(RenderOp::PaintTextWithAttributes(
+                clipped_text.to_string(),
+                Some(style),
+            ));
+        }
+
         pub fn paint_results(
             ops: &mut RenderOps,
             origin_pos: &Position,
@@ -785,7 +868,7 @@ mod internal_impl {
     }
 
     pub fn try_handle_dialog_choice(
-        input_event: InputEvent,
+        input_event: &InputEvent,
         maybe_dialog_buffer: Option<&mut DialogBuffer>,
         dialog_engine: &mut DialogEngine,
     ) -> Option<DialogChoice> {
@@ -823,7 +906,7 @@ mod internal_impl {
     }
 
     pub fn try_handle_up_down(
-        input_event: InputEvent,
+        input_event: &InputEvent,
         maybe_dialog_buffer: Option<&mut DialogBuffer>,
         dialog_engine: &mut DialogEngine,
     ) -> EventPropagation {
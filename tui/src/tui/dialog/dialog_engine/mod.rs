@@ -16,9 +16,11 @@
  */
 
 // Attach.
+pub mod autocomplete_retry;
 pub mod dialog_engine_api;
 pub mod dialog_engine_struct;
 
 // Re-export.
+pub use autocomplete_retry::*;
 pub use dialog_engine_api::*;
 pub use dialog_engine_struct::*;
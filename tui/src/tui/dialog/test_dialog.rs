@@ -46,6 +46,11 @@ pub mod mock_real_objects_for_dialog {
             maybe_saved_offscreen_buffer,
             main_thread_channel_sender,
             output_device,
+            maybe_render_profiler: None,
+            render_overlays: Default::default(),
+            input_event_repeat_count: 1,
+            hit_test_areas: Default::default(),
+            render_throttle_state: Default::default(),
         };
 
         (global_data, stdout_mock)
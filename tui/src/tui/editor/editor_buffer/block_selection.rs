@@ -0,0 +1,297 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Rectangular (aka block, or column) selection. Instead of flowing from one position
+//! to another across lines (see [SelectionKind::Stream]), the same range of display
+//! cols is selected in each row between the two corners of the block:
+//!
+//! ```text
+//! R ┌──────────┐
+//! 0 ▸ab░░░░ef  │  <- anchor is at col 2 of row 0
+//! 1 ▸gh░░░░    │  <- this line is too short, so it is padded w/ spaces
+//! 2 ▸mn░░░░st  │  <- head is at col 6 of row 2
+//!   └──────────┘
+//!   C0123456789
+//! ```
+//!
+//! The block is stored in [SelectionKind::Block] along w/ the ranges in the
+//! [SelectionMap](super::SelectionMap), so that rendering, copying, and deleting work
+//! the same way as they do for a stream selection. The head can be past the end of its
+//! line, so that the block keeps its width across short lines. The caret is placed at
+//! the head, clipped to its line.
+
+use std::cmp::{max, min};
+
+use r3bl_core::{ch, position, ChUnit, Position, SelectionRange, UnicodeString};
+
+use super::{CaretKind, EditorBuffer, SelectionKind};
+use crate::{CaretDirection, EditorEngine, EditorEngineInternalApi};
+
+/// Start a new (empty) block selection at the given scroll adjusted position, eg: where
+/// the mouse was clicked. The caret is moved there.
+pub fn start_block_selection_at(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    pos: Position,
+) -> Option<()> {
+    let pos = clip_to_content(buffer, pos)?;
+    set_block_selection(buffer, engine, pos, pos);
+    Some(())
+}
+
+/// Move the head of the block selection to the given scroll adjusted position, eg:
+/// where the mouse was dragged to. If there's no block selection, it starts at the
+/// caret.
+pub fn extend_block_selection_to(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    pos: Position,
+) -> Option<()> {
+    let head = clip_to_content(buffer, pos)?;
+    let anchor = get_block_anchor(buffer);
+    set_block_selection(buffer, engine, anchor, head);
+    Some(())
+}
+
+/// Move the head of the block selection by one row or col in the given direction. If
+/// there's no block selection, it starts at the caret.
+pub fn extend_block_selection(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    direction: CaretDirection,
+) -> Option<()> {
+    let head = match buffer.get_selection_map().kind {
+        SelectionKind::Block { head, .. } => head,
+        SelectionKind::Stream => buffer.get_caret(CaretKind::ScrollAdjusted),
+    };
+    let new_head = match direction {
+        CaretDirection::Up => position!(
+            col_index: head.col_index,
+            row_index: head.row_index - ch!(1)
+        ),
+        CaretDirection::Down => position!(
+            col_index: head.col_index,
+            row_index: head.row_index + ch!(1)
+        ),
+        CaretDirection::Left => position!(
+            col_index: head.col_index - ch!(1),
+            row_index: head.row_index
+        ),
+        CaretDirection::Right => position!(
+            col_index: head.col_index + ch!(1),
+            row_index: head.row_index
+        ),
+    };
+    extend_block_selection_to(buffer, engine, new_head)
+}
+
+/// Returns the `anchor` of the block selection, or the caret if there isn't one.
+fn get_block_anchor(buffer: &EditorBuffer) -> Position {
+    match buffer.get_selection_map().kind {
+        SelectionKind::Block { anchor, .. } => anchor,
+        SelectionKind::Stream => buffer.get_caret(CaretKind::ScrollAdjusted),
+    }
+}
+
+/// The row is clipped to the last line, and the col to the width of the widest line.
+/// Returns [None] if the buffer is empty.
+fn clip_to_content(buffer: &EditorBuffer, pos: Position) -> Option<Position> {
+    let lines = buffer.get_lines();
+    let last_row_index = lines.len().checked_sub(1)?;
+    let max_col_index = lines
+        .iter()
+        .map(|line| line.display_width)
+        .max()
+        .unwrap_or_default();
+    Some(position!(
+        col_index: min(pos.col_index, max_col_index),
+        row_index: min(pos.row_index, ch!(last_row_index))
+    ))
+}
+
+/// Replace the selection w/ the block between the `anchor` and the `head`, and move the
+/// caret to the `head`.
+fn set_block_selection(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    anchor: Position,
+    head: Position,
+) {
+    let caret = position!(
+        col_index: get_grapheme_start_col_index(
+            &buffer.get_lines()[ch!(@to_usize head.row_index)],
+            head.col_index,
+        ),
+        row_index: head.row_index
+    );
+    EditorEngineInternalApi::to_position(buffer, engine, caret);
+
+    let range = SelectionRange {
+        start_display_col_index: min(anchor.col_index, head.col_index),
+        end_display_col_index: max(anchor.col_index, head.col_index),
+    };
+    let (_, _, _, selection_map) = buffer.get_mut();
+    selection_map.clear();
+    let start_row_index = ch!(@to_usize min(anchor.row_index, head.row_index));
+    let end_row_index = ch!(@to_usize max(anchor.row_index, head.row_index));
+    for row_index in start_row_index..=end_row_index {
+        selection_map.map.insert(ch!(row_index), range);
+    }
+    selection_map.kind = SelectionKind::Block { anchor, head };
+}
+
+/// Returns the col of the start of the grapheme cluster at `col_index` (which may be
+/// wide), or the end of the `line` if it is past it.
+fn get_grapheme_start_col_index(line: &UnicodeString, col_index: ChUnit) -> ChUnit {
+    if col_index >= line.display_width {
+        return line.display_width;
+    }
+    line.iter()
+        .rev()
+        .map(|segment| segment.display_col_offset)
+        .find(|display_col_offset| *display_col_offset <= col_index)
+        .unwrap_or_default()
+}
+
+/// Returns the `selected_text` from a row of a block selection, padded w/ spaces to the
+/// width of the `range`, so that the cols of the block line up when it is copied.
+pub fn pad_block_selection_row(selected_text: &str, range: SelectionRange) -> String {
+    let width = range.end_display_col_index - range.start_display_col_index;
+    let pad_width = width - UnicodeString::from(selected_text).display_width;
+    format!("{selected_text}{}", " ".repeat(ch!(@to_usize pad_width)))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{test_fixtures::mock_real_objects_for_editor, DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_buffer(lines: &[&str]) -> EditorBuffer {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+        buffer
+    }
+
+    fn get_ranges(buffer: &EditorBuffer) -> Vec<(ChUnit, ChUnit, ChUnit)> {
+        let selection_map = buffer.get_selection_map();
+        selection_map
+            .get_ordered_indices()
+            .into_iter()
+            .map(|row_index| {
+                let range = selection_map.get(row_index).unwrap();
+                (
+                    row_index,
+                    range.start_display_col_index,
+                    range.end_display_col_index,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_extend_block_selection() {
+        let mut buffer = make_buffer(&["abcdef", "gh", "mnopqrst"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        start_block_selection_at(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 0),
+        );
+        for direction in [
+            CaretDirection::Down,
+            CaretDirection::Down,
+            CaretDirection::Right,
+            CaretDirection::Right,
+        ] {
+            extend_block_selection(&mut buffer, &mut engine, direction);
+        }
+
+        assert!(buffer.get_selection_map().is_block());
+        assert_eq2!(
+            get_ranges(&buffer),
+            vec![
+                (ch!(0), ch!(2), ch!(4)),
+                (ch!(1), ch!(2), ch!(4)),
+                (ch!(2), ch!(2), ch!(4)),
+            ]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 2)
+        );
+
+        // The head can be past the end of a short line, but the caret can't.
+        let mut buffer = make_buffer(&["abcdef", "gh", "mnopqrst"]);
+        start_block_selection_at(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 4, row_index: 0),
+        );
+        extend_block_selection(&mut buffer, &mut engine, CaretDirection::Down);
+        extend_block_selection(&mut buffer, &mut engine, CaretDirection::Left);
+        assert_eq2!(
+            get_ranges(&buffer),
+            vec![(ch!(0), ch!(3), ch!(4)), (ch!(1), ch!(3), ch!(4)),]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+
+        // The head is clipped to the content.
+        extend_block_selection_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 99, row_index: 99),
+        );
+        assert_eq2!(
+            buffer.get_selection_map().kind,
+            SelectionKind::Block {
+                anchor: position!(col_index: 4, row_index: 0),
+                head: position!(col_index: 8, row_index: 2),
+            }
+        );
+
+        // Clearing the selection goes back to a stream selection.
+        buffer.clear_selection();
+        assert_eq2!(buffer.get_selection_map().kind, SelectionKind::Stream);
+    }
+
+    #[test]
+    fn test_caret_is_not_in_the_middle_of_a_wide_grapheme() {
+        let line = UnicodeString::from("a😃b");
+        assert_eq2!(get_grapheme_start_col_index(&line, ch!(1)), ch!(1));
+        assert_eq2!(get_grapheme_start_col_index(&line, ch!(2)), ch!(1));
+        assert_eq2!(get_grapheme_start_col_index(&line, ch!(3)), ch!(3));
+        assert_eq2!(get_grapheme_start_col_index(&line, ch!(9)), ch!(4));
+    }
+
+    #[test]
+    fn test_pad_block_selection_row() {
+        let range = SelectionRange {
+            start_display_col_index: ch!(2),
+            end_display_col_index: ch!(6),
+        };
+        assert_eq2!(pad_block_selection_row("cdef", range), "cdef");
+        assert_eq2!(pad_block_selection_row("😃", range), "😃  ");
+        assert_eq2!(pad_block_selection_row("", range), "    ");
+    }
+}
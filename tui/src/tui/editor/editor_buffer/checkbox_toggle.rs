@@ -0,0 +1,208 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Toggle the checkbox of a Markdown task list item (eg: `- [ ] todo` ↔ `- [x] todo`).
+//! This is used by [EditorEngineApi::apply_event](crate::EditorEngineApi::apply_event)
+//! when [crate::CheckboxToggleMode::Enable] is set.
+//!
+//! Only the checkbox is replaced, so the rest of the line (and the caret) stays where it
+//! is, since `[ ]` and `[x]` have the same width. Items in nested lists (that are
+//! indented) and ordered lists can be toggled too. Lines that aren't task list items, and
+//! lines in code blocks, are left alone.
+
+use r3bl_core::UnicodeString;
+
+use super::EditorBuffer;
+use crate::{constants::{CHECKED,
+                        CODE_BLOCK_START_PARTIAL,
+                        ORDERED_LIST_PARTIAL_PREFIX,
+                        SPACE,
+                        UNCHECKED,
+                        UNORDERED_LIST},
+            DEFAULT_SYN_HI_FILE_EXT};
+
+/// The checkbox of a task list item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TaskCheckbox {
+    pub row_index: usize,
+    /// Display col index of the `[`.
+    pub col_index: usize,
+    pub is_checked: bool,
+}
+
+impl TaskCheckbox {
+    /// Returns true if the given display col index is on the checkbox (including its
+    /// brackets).
+    pub fn contains_col(&self, col_index: usize) -> bool {
+        (self.col_index..self.col_index + CHECKED.len()).contains(&col_index)
+    }
+
+    /// Returns the given `line` w/ this checkbox toggled.
+    pub fn get_toggled_line(&self, line: &str) -> String {
+        let replacement = if self.is_checked { UNCHECKED } else { CHECKED };
+        // The list prefix is ASCII, so the display col index is also the byte index.
+        let start = self.col_index;
+        let end = start + CHECKED.len();
+        format!("{}{}{}", &line[..start], replacement, &line[end..])
+    }
+}
+
+/// Returns the checkbox on the given row of a Markdown buffer, or [None] if that row
+/// isn't a task list item.
+pub fn get_task_checkbox(
+    buffer: &EditorBuffer,
+    row_index: usize,
+) -> Option<TaskCheckbox> {
+    if buffer.get_maybe_file_extension() != Some(DEFAULT_SYN_HI_FILE_EXT) {
+        return None;
+    }
+    get_task_checkbox_in_lines(buffer.get_lines(), row_index)
+}
+
+pub fn get_task_checkbox_in_lines(
+    lines: &[UnicodeString],
+    row_index: usize,
+) -> Option<TaskCheckbox> {
+    let line = lines.get(row_index)?;
+    if is_in_code_block(lines, row_index) {
+        return None;
+    }
+
+    let col_index = get_checkbox_col_index(&line.string)?;
+    let is_checked = line.string[col_index..].starts_with(CHECKED);
+
+    Some(TaskCheckbox {
+        row_index,
+        col_index,
+        is_checked,
+    })
+}
+
+/// Returns the index of the checkbox in a line like `  - [ ] todo` or `1. [x] done`,
+/// which is followed by a space or the end of the line. Everything before the checkbox
+/// is ASCII, so this is both a byte index and a display col index.
+fn get_checkbox_col_index(line: &str) -> Option<usize> {
+    let rem = line.trim_start_matches(SPACE);
+
+    let rem = if let Some(rem) = rem.strip_prefix(UNORDERED_LIST) {
+        rem.strip_prefix(SPACE)?
+    } else {
+        let digits = rem.chars().take_while(char::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        rem[digits..].strip_prefix(ORDERED_LIST_PARTIAL_PREFIX)?
+    };
+
+    let col_index = line.len() - rem.len();
+    let after_checkbox = rem
+        .strip_prefix(CHECKED)
+        .or_else(|| rem.strip_prefix(UNCHECKED))?;
+    match after_checkbox.is_empty() || after_checkbox.starts_with(SPACE) {
+        true => Some(col_index),
+        false => None,
+    }
+}
+
+/// Returns true if the given row is inside a fenced code block (including its fences).
+fn is_in_code_block(lines: &[UnicodeString], row_index: usize) -> bool {
+    let fence_count = lines[..=row_index]
+        .iter()
+        .filter(|line| {
+            line.string
+                .trim_start()
+                .starts_with(CODE_BLOCK_START_PARTIAL)
+        })
+        .count();
+    let is_fence = lines[row_index]
+        .string
+        .trim_start()
+        .starts_with(CODE_BLOCK_START_PARTIAL);
+    fence_count % 2 == 1 || is_fence
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn make_lines(lines: &[&str]) -> Vec<UnicodeString> {
+        lines.iter().map(|it| UnicodeString::from(*it)).collect()
+    }
+
+    #[test]
+    fn test_get_task_checkbox() {
+        let lines = make_lines(&[
+            "- [ ] todo",
+            "  - [x] nested done",
+            "10. [ ]",
+            "- [x]done",
+            "- not a task",
+            "[ ] not a list",
+            "```",
+            "- [ ] in a code block",
+            "```",
+            "- [ ] 😃 after",
+        ]);
+
+        let get = |row_index| get_task_checkbox_in_lines(&lines, row_index);
+
+        assert_eq2!(
+            get(0),
+            Some(TaskCheckbox {
+                row_index: 0,
+                col_index: 2,
+                is_checked: false
+            })
+        );
+        assert_eq2!(
+            get(1),
+            Some(TaskCheckbox {
+                row_index: 1,
+                col_index: 4,
+                is_checked: true
+            })
+        );
+        assert_eq2!(get(2).map(|it| it.col_index), Some(4));
+        for row_index in 3..=8 {
+            assert_eq2!(get(row_index), None);
+        }
+        assert_eq2!(get(9).map(|it| it.col_index), Some(2));
+        assert_eq2!(get(10), None);
+    }
+
+    #[test]
+    fn test_get_toggled_line() {
+        let lines = make_lines(&["  - [ ] todo [x]", "1. [x] 😃 done"]);
+
+        let checkbox = get_task_checkbox_in_lines(&lines, 0).unwrap();
+        assert!(checkbox.contains_col(4));
+        assert!(checkbox.contains_col(6));
+        assert!(!checkbox.contains_col(7));
+        assert_eq2!(
+            checkbox.get_toggled_line(&lines[0].string),
+            "  - [x] todo [x]"
+        );
+
+        let checkbox = get_task_checkbox_in_lines(&lines, 1).unwrap();
+        assert_eq2!(
+            checkbox.get_toggled_line(&lines[1].string),
+            "1. [ ] 😃 done"
+        );
+    }
+}
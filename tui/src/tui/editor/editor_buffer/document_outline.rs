@@ -0,0 +1,252 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! The outline (table of contents) of a Markdown document, which lists its headings. An
+//! app can paint it next to the editor, highlight the heading that the caret is in, and
+//! move the caret to a heading when it is picked.
+//!
+//! The headings are found by parsing the whole document (using [parse_markdown()]), so
+//! headings in code blocks are not included. [DocumentOutline::update] only parses the
+//! document again when its content changes, so it can be called on every render.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use r3bl_core::{ch, position, UnicodeString};
+
+use super::EditorBuffer;
+use crate::{parse_markdown, truncate_inline_diagnostic_text, MdBlock};
+
+/// Headings that are nested deeper than this (relative to the top level headings of the
+/// document) are indented as much as the ones at this depth, so that there's still room
+/// for their text.
+pub const OUTLINE_MAX_INDENT_DEPTH: usize = 3;
+
+pub const OUTLINE_INDENT: &str = "  ";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutlineEntry {
+    /// The row of the heading in the document.
+    pub row_index: usize,
+    /// `1` for `#`, `2` for `##`, etc.
+    pub level: usize,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DocumentOutline {
+    pub entries: Vec<OutlineEntry>,
+    /// Hash of the content that the entries were parsed from, if it has been parsed.
+    pub maybe_content_hash: Option<u64>,
+}
+
+impl DocumentOutline {
+    /// Parse the `lines` again if they have changed since the last time. If they can't
+    /// be parsed (eg: while a heading is being typed), the previous entries are kept.
+    /// Returns true if the entries changed.
+    pub fn update(&mut self, lines: &[UnicodeString]) -> bool {
+        let content_hash = {
+            let mut hasher = DefaultHasher::new();
+            for line in lines {
+                line.string.hash(&mut hasher);
+            }
+            hasher.finish()
+        };
+        if self.maybe_content_hash == Some(content_hash) {
+            return false;
+        }
+        self.maybe_content_hash = Some(content_hash);
+
+        match get_outline_entries(lines) {
+            Some(entries) if entries != self.entries => {
+                self.entries = entries;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the index of the heading of the section that the given row is in, or
+    /// [None] if the row is above the first heading.
+    pub fn get_active_entry_index(&self, row_index: usize) -> Option<usize> {
+        let count = self
+            .entries
+            .partition_point(|entry| entry.row_index <= row_index);
+        count.checked_sub(1)
+    }
+
+    /// Returns the text for the entry at the given index, indented by its nesting depth
+    /// (see [OUTLINE_MAX_INDENT_DEPTH]), and truncated w/ an ellipsis to fit in
+    /// `max_width` display cols.
+    pub fn get_entry_display_text(
+        &self,
+        index: usize,
+        max_width: usize,
+    ) -> Option<String> {
+        let entry = self.entries.get(index)?;
+        let min_level = self.entries.iter().map(|it| it.level).min()?;
+        let depth = std::cmp::min(entry.level - min_level, OUTLINE_MAX_INDENT_DEPTH);
+        let text = format!("{}{}", OUTLINE_INDENT.repeat(depth), entry.text);
+        Some(truncate_inline_diagnostic_text(&text, ch!(max_width)))
+    }
+}
+
+/// Returns the headings in the `lines`, or [None] if they can't be parsed as Markdown
+/// (or the parsed blocks don't match up w/ the lines).
+pub fn get_outline_entries(lines: &[UnicodeString]) -> Option<Vec<OutlineEntry>> {
+    let mut input = lines
+        .iter()
+        .map(|line| line.string.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    input.push('\n');
+
+    let (remainder, document) = parse_markdown(&input).ok()?;
+    if !remainder.is_empty() {
+        return None;
+    }
+
+    let mut acc = vec![];
+    let mut row_index = 0;
+    for md_block in document.iter() {
+        if let MdBlock::Heading(heading_data) = md_block {
+            acc.push(OutlineEntry {
+                row_index,
+                level: heading_data.heading_level.level,
+                text: heading_data.text.trim().to_string(),
+            });
+        }
+        row_index += md_block.get_line_count()?;
+    }
+
+    if row_index != lines.len() {
+        return None;
+    }
+
+    Some(acc)
+}
+
+/// Move the caret to the start of the heading of the given entry, and scroll it to the
+/// top of the viewport. The selection and extra carets are removed.
+pub fn move_caret_to_outline_entry(buffer: &mut EditorBuffer, entry: &OutlineEntry) {
+    if entry.row_index >= buffer.get_lines().len() {
+        return;
+    }
+    buffer.clear_selection();
+    buffer.clear_extra_carets();
+    let (_, caret, scroll_offset, _) = buffer.get_mut();
+    *scroll_offset = position!(col_index: 0, row_index: entry.row_index);
+    *caret = position!(col_index: 0, row_index: 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{CaretKind, DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_lines(lines: &[&str]) -> Vec<UnicodeString> {
+        lines.iter().map(|it| UnicodeString::from(*it)).collect()
+    }
+
+    const DOCUMENT: &[&str] = &[
+        "# Title",
+        "intro",
+        "```sh",
+        "# not a heading",
+        "```",
+        "## Install",
+        "- step 1",
+        "- step 2",
+        "###### Very deep and long heading",
+        "## Usage",
+    ];
+
+    #[test]
+    fn test_get_outline_entries() {
+        let entries = get_outline_entries(&make_lines(DOCUMENT)).unwrap();
+        assert_eq2!(
+            entries
+                .iter()
+                .map(|it| (it.row_index, it.level, it.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 1, "Title"),
+                (5, 2, "Install"),
+                (8, 6, "Very deep and long heading"),
+                (9, 2, "Usage"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_and_active_entry() {
+        let mut lines = make_lines(DOCUMENT);
+        let mut outline = DocumentOutline::default();
+        assert!(outline.update(&lines));
+        assert!(!outline.update(&lines));
+
+        assert_eq2!(outline.get_active_entry_index(0), Some(0));
+        assert_eq2!(outline.get_active_entry_index(4), Some(0));
+        assert_eq2!(outline.get_active_entry_index(7), Some(1));
+        assert_eq2!(outline.get_active_entry_index(100), Some(3));
+
+        // A heading is added, and the one above it is renamed.
+        lines[1] = UnicodeString::from("# Intro");
+        lines[9] = UnicodeString::from("## How to use");
+        assert!(outline.update(&lines));
+        assert_eq2!(outline.entries.len(), 5);
+        assert_eq2!(outline.entries[4].text, "How to use");
+        assert_eq2!(outline.get_active_entry_index(3), Some(1));
+
+        // No headings before the first row.
+        lines.insert(0, UnicodeString::from("text"));
+        outline.update(&lines);
+        assert_eq2!(outline.get_active_entry_index(0), None);
+    }
+
+    #[test]
+    fn test_get_entry_display_text() {
+        let mut outline = DocumentOutline::default();
+        outline.update(&make_lines(DOCUMENT));
+
+        assert_eq2!(outline.get_entry_display_text(0, 20).unwrap(), "Title");
+        assert_eq2!(outline.get_entry_display_text(1, 20).unwrap(), "  Install");
+        // The indent is capped, and the text is truncated.
+        assert_eq2!(
+            outline.get_entry_display_text(2, 20).unwrap(),
+            "      Very deep and…"
+        );
+        assert_eq2!(outline.get_entry_display_text(4, 20), None);
+    }
+
+    #[test]
+    fn test_move_caret_to_outline_entry() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(DOCUMENT.iter().map(|it| it.to_string()).collect());
+        let mut outline = DocumentOutline::default();
+        outline.update(buffer.get_lines());
+
+        move_caret_to_outline_entry(&mut buffer, &outline.entries[3]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 9)
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(9));
+    }
+}
@@ -21,7 +21,14 @@ use crossterm::style::Stylize;
 use r3bl_core::{call_if_true, ch, UnicodeString};
 
 use super::EditorBuffer;
-use crate::{EditorArgsMut, EditorEngineInternalApi, DEBUG_TUI_COPY_PASTE};
+use crate::{convert_md_document_to_html,
+            pad_block_selection_row,
+            parse_markdown,
+            ClipboardExportMode,
+            EditorArgsMut,
+            EditorEngineInternalApi,
+            DEBUG_TUI_COPY_PASTE,
+            DEFAULT_SYN_HI_FILE_EXT};
 
 pub type ClipboardResult<T> = Result<T, Box<dyn Error + Send + Sync + 'static>>;
 
@@ -33,17 +40,39 @@ pub trait ClipboardService {
         content: String,
     ) -> ClipboardResult<()>;
     fn try_to_get_content_from_clipboard(&mut self) -> ClipboardResult<String>;
+
+    /// Put both a plain text and an HTML rendition of the same content into the
+    /// clipboard, so that rich text editors can paste the formatted version.
+    ///
+    /// The default implementation only puts the `plain_text` into the clipboard. This is
+    /// what the [SystemClipboard](crate::SystemClipboard) does, since the clipboard
+    /// provider that it uses (and terminal based clipboards like OSC 52) can only hold a
+    /// single text payload. Implementations that can hold multiple formats should
+    /// override this.
+    fn try_to_put_rich_content_into_clipboard(
+        &mut self,
+        plain_text: String,
+        _html: String,
+    ) -> ClipboardResult<()> {
+        self.try_to_put_content_into_clipboard(plain_text)
+    }
 }
 
+/// HTML content larger than this (in bytes) is not placed on the clipboard, only the
+/// plain text is. This keeps large selections within the payload limits of common
+/// clipboard transports.
+pub const MAX_HTML_CLIPBOARD_EXPORT_SIZE: usize = 100_000;
+
 pub fn copy_to_clipboard(
     buffer: &EditorBuffer,
+    export_mode: &ClipboardExportMode,
     clipboard_service_provider: &mut impl ClipboardService,
 ) {
     let lines: &Vec<UnicodeString> = buffer.get_lines();
     let selection_map = buffer.get_selection_map();
 
     // Initialize an empty string to store the copied text.
-    let mut vec_str: Vec<&str> = vec![];
+    let mut vec_str: Vec<String> = vec![];
 
     // Sort the row indices so that the copied text is in the correct order.
     let row_indices = selection_map.get_ordered_indices();
@@ -53,13 +82,22 @@ pub fn copy_to_clipboard(
         if let Some(selection_range) = selection_map.map.get(&row_index) {
             if let Some(line) = lines.get(ch!(@to_usize row_index)) {
                 let selected_text = line.clip_to_range(*selection_range);
-                vec_str.push(selected_text);
+                // The rows of a block selection are padded, so that its cols line up.
+                vec_str.push(if selection_map.is_block() {
+                    pad_block_selection_row(selected_text, *selection_range)
+                } else {
+                    selected_text.to_string()
+                });
             }
         }
     }
 
-    let result =
-        clipboard_service_provider.try_to_put_content_into_clipboard(vec_str.join("\n"));
+    let plain_text = vec_str.join("\n");
+    let result = match get_html_for_export(buffer, export_mode, &plain_text) {
+        Some(html) => clipboard_service_provider
+            .try_to_put_rich_content_into_clipboard(plain_text, html),
+        None => clipboard_service_provider.try_to_put_content_into_clipboard(plain_text),
+    };
     if let Err(error) = result {
         call_if_true!(DEBUG_TUI_COPY_PASTE, {
             tracing::debug!(
@@ -70,6 +108,78 @@ pub fn copy_to_clipboard(
     }
 }
 
+/// Returns the HTML rendition of the `plain_text` if the `export_mode` asks for it, the
+/// buffer contains Markdown, and the result fits in [MAX_HTML_CLIPBOARD_EXPORT_SIZE].
+fn get_html_for_export(
+    buffer: &EditorBuffer,
+    export_mode: &ClipboardExportMode,
+    plain_text: &str,
+) -> Option<String> {
+    if *export_mode != ClipboardExportMode::PlainTextAndHtml {
+        return None;
+    }
+
+    if buffer.get_maybe_file_extension() != Some(DEFAULT_SYN_HI_FILE_EXT) {
+        return None;
+    }
+
+    // The parser expects each line to be terminated by a new line.
+    let input = format!("{plain_text}\n");
+    let (_, document) = parse_markdown(&input).ok()?;
+    let html = convert_md_document_to_html(&document);
+
+    if html.len() > MAX_HTML_CLIPBOARD_EXPORT_SIZE {
+        call_if_true!(DEBUG_TUI_COPY_PASTE, {
+            tracing::debug!(
+                "\n📋📋📋 HTML is too large to copy to clipboard, only copying plain text: {} bytes",
+                html.len()
+            );
+        });
+        return None;
+    }
+
+    Some(html)
+}
+
+/// Insert the `text` at the caret, line by line, so that each new line in it (`\n`,
+/// `\r\n`, or `\r`) splits the line at the caret, eg: for text that is pasted.
+pub fn insert_text_at_caret(args: EditorArgsMut<'_>, text: &str) {
+    // Terminals send a carriage return for each new line in a bracketed paste.
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+
+    // If the text does not contain a new line, then insert the text.
+    if !text.contains('\n') {
+        EditorEngineInternalApi::insert_str_at_caret(
+            EditorArgsMut {
+                editor_engine: args.editor_engine,
+                editor_buffer: args.editor_buffer,
+            },
+            text.as_str(),
+        );
+        return;
+    }
+
+    // If the text contains a new line, then insert the text line by line.
+    let lines = text.split('\n');
+    let line_count = lines.clone().count();
+    for (line_index, line) in lines.enumerate() {
+        EditorEngineInternalApi::insert_str_at_caret(
+            EditorArgsMut {
+                editor_engine: args.editor_engine,
+                editor_buffer: args.editor_buffer,
+            },
+            line,
+        );
+        // This is not the last line, so insert a new line.
+        if line_index < line_count - 1 {
+            EditorEngineInternalApi::insert_new_line_at_caret(EditorArgsMut {
+                editor_engine: args.editor_engine,
+                editor_buffer: args.editor_buffer,
+            });
+        }
+    }
+}
+
 pub fn paste_from_clipboard(
     args: EditorArgsMut<'_>,
     clipboard_service_provider: &mut impl ClipboardService,
@@ -77,39 +187,7 @@ pub fn paste_from_clipboard(
     let result = clipboard_service_provider.try_to_get_content_from_clipboard();
     match result {
         Ok(clipboard_text) => {
-            // If the clipboard text does not contain a new line, then insert the text.
-            if !clipboard_text.contains('\n') {
-                EditorEngineInternalApi::insert_str_at_caret(
-                    EditorArgsMut {
-                        editor_engine: args.editor_engine,
-                        editor_buffer: args.editor_buffer,
-                    },
-                    clipboard_text.as_str(),
-                );
-            }
-            // If the clipboard text contains a new line, then insert the text line by line.
-            else {
-                let lines = clipboard_text.split('\n');
-                let line_count = lines.clone().count();
-                for (line_index, line) in lines.enumerate() {
-                    EditorEngineInternalApi::insert_str_at_caret(
-                        EditorArgsMut {
-                            editor_engine: args.editor_engine,
-                            editor_buffer: args.editor_buffer,
-                        },
-                        line,
-                    );
-                    // This is not the last line, so insert a new line.
-                    if line_index < line_count - 1 {
-                        EditorEngineInternalApi::insert_new_line_at_caret(
-                            EditorArgsMut {
-                                editor_engine: args.editor_engine,
-                                editor_buffer: args.editor_buffer,
-                            },
-                        );
-                    }
-                }
-            }
+            insert_text_at_caret(args, &clipboard_text);
 
             call_if_true!(DEBUG_TUI_COPY_PASTE, {
                 tracing::debug!(
@@ -0,0 +1,157 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ChUnit, TruncationIndicator, TuiStyle};
+use serde::{Deserialize, Serialize};
+
+/// A diagnostic (eg: an error or warning from a linter or plugin) that applies to a
+/// single row in the [crate::EditorBuffer]. These are not part of the content (and are
+/// not saved in the undo history), so they have to be updated by whoever produces them
+/// when the content changes, using
+/// [EditorBuffer::set_diagnostics](crate::EditorBuffer::set_diagnostics).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Scroll adjusted row index.
+    pub row_index: ChUnit,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
+/// Ordered from the lowest to the highest severity.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum DiagnosticSeverity {
+    Hint,
+    Info,
+    Warning,
+    Error,
+}
+
+impl DiagnosticSeverity {
+    pub fn get_icon(&self) -> &'static str {
+        match self {
+            DiagnosticSeverity::Hint => "💡",
+            DiagnosticSeverity::Info => "ℹ",
+            DiagnosticSeverity::Warning => "⚠",
+            DiagnosticSeverity::Error => "✗",
+        }
+    }
+}
+
+/// The text that is painted after the end of a line, when
+/// [crate::InlineDiagnosticsMode::Enable] is set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlineDiagnostic {
+    pub severity: DiagnosticSeverity,
+    pub text: String,
+}
+
+/// Returns the inline text for the given row. When there are many diagnostics on the
+/// row, the (first) one w/ the highest severity is shown, along w/ a count of the
+/// others.
+pub fn get_inline_diagnostic(
+    diagnostics: &[Diagnostic],
+    row_index: ChUnit,
+) -> Option<InlineDiagnostic> {
+    let mut count = 0;
+    let mut maybe_most_severe: Option<&Diagnostic> = None;
+
+    for diagnostic in diagnostics.iter().filter(|it| it.row_index == row_index) {
+        count += 1;
+        match maybe_most_severe {
+            Some(most_severe) if most_severe.severity >= diagnostic.severity => {}
+            _ => maybe_most_severe = Some(diagnostic),
+        }
+    }
+
+    let most_severe = maybe_most_severe?;
+    // Only use the first line of the message, since this is painted on a single row.
+    let message = most_severe.message.lines().next().unwrap_or_default();
+    let text = match count {
+        1 => format!("{} {}", most_severe.severity.get_icon(), message),
+        _ => format!(
+            "{} {} (+{} more)",
+            most_severe.severity.get_icon(),
+            message,
+            count - 1
+        ),
+    };
+
+    Some(InlineDiagnostic {
+        severity: most_severe.severity,
+        text,
+    })
+}
+
+/// Truncate the text so that it fits in the given width. An ellipsis is added to the end
+/// if the text had to be truncated.
+pub fn truncate_inline_diagnostic_text(text: &str, available_width: ChUnit) -> String {
+    TruncationIndicator::<TuiStyle>::ellipsis().truncate_end(text, available_width)
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, ch};
+
+    use super::*;
+
+    fn diagnostic(row: usize, severity: DiagnosticSeverity, message: &str) -> Diagnostic {
+        Diagnostic {
+            row_index: ch!(row),
+            severity,
+            message: message.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_get_inline_diagnostic() {
+        let diagnostics = vec![
+            diagnostic(0, DiagnosticSeverity::Warning, "unused variable"),
+            diagnostic(1, DiagnosticSeverity::Info, "first\nsecond"),
+            diagnostic(0, DiagnosticSeverity::Error, "type mismatch"),
+            diagnostic(0, DiagnosticSeverity::Hint, "consider this"),
+        ];
+
+        assert_eq2!(
+            get_inline_diagnostic(&diagnostics, ch!(0)),
+            Some(InlineDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                text: "✗ type mismatch (+2 more)".to_string(),
+            })
+        );
+
+        assert_eq2!(
+            get_inline_diagnostic(&diagnostics, ch!(1)),
+            Some(InlineDiagnostic {
+                severity: DiagnosticSeverity::Info,
+                text: "ℹ first".to_string(),
+            })
+        );
+
+        assert_eq2!(get_inline_diagnostic(&diagnostics, ch!(2)), None);
+    }
+
+    #[test]
+    fn test_truncate_inline_diagnostic_text() {
+        assert_eq2!(truncate_inline_diagnostic_text("abcdef", ch!(10)), "abcdef");
+        assert_eq2!(truncate_inline_diagnostic_text("abcdef", ch!(6)), "abcdef");
+        assert_eq2!(truncate_inline_diagnostic_text("abcdef", ch!(4)), "abc…");
+        assert_eq2!(truncate_inline_diagnostic_text("abcdef", ch!(1)), "…");
+        assert_eq2!(truncate_inline_diagnostic_text("abcdef", ch!(0)), "");
+    }
+}
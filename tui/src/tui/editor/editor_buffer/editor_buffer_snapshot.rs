@@ -0,0 +1,143 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! A snapshot of the content of an [EditorBuffer], along w/ the metadata that is needed
+//! to put it back, that can be serialized (eg: to a file). Apps use this to recover
+//! unsaved work after a crash.
+
+use miette::IntoDiagnostic as _;
+use r3bl_core::{ch, position, CommonResult, Position, UnicodeString};
+use serde::{Deserialize, Serialize};
+
+use super::{cache, history, CaretKind, EditorBuffer};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EditorBufferSnapshot {
+    pub maybe_file_path: Option<String>,
+    pub maybe_file_extension: Option<String>,
+    pub lines: Vec<String>,
+    /// Scroll adjusted caret.
+    pub caret: Position,
+    /// Whether the content is different from what was last saved to the file.
+    pub is_dirty: bool,
+}
+
+impl EditorBufferSnapshot {
+    pub fn to_json(&self) -> CommonResult<String> {
+        serde_json::to_string(self).into_diagnostic()
+    }
+
+    pub fn try_from_json(json: &str) -> CommonResult<Self> {
+        serde_json::from_str(json).into_diagnostic()
+    }
+}
+
+impl EditorBuffer {
+    pub fn get_snapshot(&self, is_dirty: bool) -> EditorBufferSnapshot {
+        EditorBufferSnapshot {
+            maybe_file_path: self.editor_content.maybe_file_path.clone(),
+            maybe_file_extension: self.editor_content.maybe_file_extension.clone(),
+            lines: self
+                .get_lines()
+                .iter()
+                .map(|line| line.string.clone())
+                .collect(),
+            caret: self.get_caret(CaretKind::ScrollAdjusted),
+            is_dirty,
+        }
+    }
+
+    /// Replace the content w/ the one in the `snapshot`, and move the caret back to
+    /// where it was (clipped to the content). The scroll offset is reset, and the editor
+    /// scrolls the caret into view the next time it is rendered. The undo history is
+    /// cleared.
+    pub fn restore_snapshot(&mut self, snapshot: &EditorBufferSnapshot) {
+        let content = &mut self.editor_content;
+        content.lines = snapshot
+            .lines
+            .iter()
+            .map(|line| UnicodeString::from(line.as_str()))
+            .collect();
+        if content.lines.is_empty() {
+            content.lines.push(UnicodeString::default());
+        }
+        content.maybe_file_path = snapshot.maybe_file_path.clone();
+        content.maybe_file_extension = snapshot.maybe_file_extension.clone();
+        content.scroll_offset = Default::default();
+        content.selection_map.clear();
+        content.extra_carets.clear();
+
+        let max_row_index = content.lines.len() - 1;
+        let row_index =
+            std::cmp::min(ch!(@to_usize snapshot.caret.row_index), max_row_index);
+        let line_width = content.lines[row_index].display_width;
+        let col_index = std::cmp::min(snapshot.caret.col_index, line_width);
+        content.caret_display_position =
+            position!(col_index: col_index, row_index: row_index);
+
+        cache::clear(self);
+        history::clear(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut buffer = EditorBuffer::new_empty(
+            &Some("md".to_string()),
+            &Some("/tmp/foo.md".to_string()),
+        );
+        buffer.set_lines(vec!["abc".to_string(), "defgh".to_string()]);
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 4, row_index: 1);
+
+        let json = buffer.get_snapshot(true).to_json().unwrap();
+        let snapshot = EditorBufferSnapshot::try_from_json(&json).unwrap();
+        assert_eq2!(snapshot, buffer.get_snapshot(true));
+
+        let mut restored = EditorBuffer::new_empty(&None, &None);
+        restored.restore_snapshot(&snapshot);
+        assert_eq2!(restored.get_lines(), buffer.get_lines());
+        assert_eq2!(restored.get_maybe_file_extension(), Some("md"));
+        assert_eq2!(
+            restored.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_restore_snapshot_clips_caret() {
+        let snapshot = EditorBufferSnapshot {
+            maybe_file_path: None,
+            maybe_file_extension: None,
+            lines: vec!["abc".to_string()],
+            caret: position!(col_index: 10, row_index: 5),
+            is_dirty: true,
+        };
+        let mut buffer = EditorBuffer::new_empty(&None, &None);
+        buffer.restore_snapshot(&snapshot);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+    }
+}
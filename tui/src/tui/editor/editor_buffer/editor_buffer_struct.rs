@@ -30,7 +30,7 @@ use r3bl_core::{call_if_true,
 use serde::{Deserialize, Serialize};
 use size_of::SizeOf as _;
 
-use super::SelectionMap;
+use super::{Diagnostic, FindState, IndentStyle, SelectionMap};
 use crate::{EditorEngine,
             EditorEngineApi,
             HasFocus,
@@ -182,11 +182,34 @@ use crate::{EditorEngine,
 /// in the map represents a row of text in the buffer.
 /// - The row index is the key.
 /// - The value is the [r3bl_core::SelectionRange].
+///
+/// ## `diagnostics`
+///
+/// The [Diagnostic]s (eg: errors and warnings from a plugin) for the rows in the buffer.
+/// They are not part of [EditorContent], so they don't affect the undo history. To update
+/// them, use [set_diagnostics](EditorBuffer::set_diagnostics).
+///
+/// ## `maybe_indent_style`
+///
+/// The [IndentStyle] for this buffer, eg: when it is detected from the content of a file
+/// that was opened (using [detect_indent_style](EditorBuffer::detect_indent_style)), or
+/// chosen by the user. When it is [None], [crate::EditorEngineConfig::indent_style] is
+/// used.
+///
+/// ## `maybe_find_state`
+///
+/// The [FindState] while the user is finding (and maybe replacing) text in the buffer,
+/// and [None] otherwise. It holds the query, and all of its matches, so that they can be
+/// highlighted when the buffer is rendered. It is not part of [EditorContent], so it
+/// doesn't affect the undo history. See [get_find_state](EditorBuffer::get_find_state).
 #[derive(Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct EditorBuffer {
     pub editor_content: EditorContent,
     pub history: EditorBufferHistory,
     pub render_cache: HashMap<String, RenderOps>,
+    pub diagnostics: Vec<Diagnostic>,
+    pub maybe_indent_style: Option<IndentStyle>,
+    pub maybe_find_state: Option<FindState>,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, Default, size_of::SizeOf)]
@@ -197,12 +220,22 @@ pub struct EditorContent {
     pub maybe_file_extension: Option<String>,
     pub maybe_file_path: Option<String>,
     pub selection_map: SelectionMap,
+    /// Scroll adjusted positions of the carets that were added in
+    /// [crate::MultiCaretMode::Enable], in addition to the caret above. They are sorted,
+    /// and never contain the caret above.
+    pub extra_carets: Vec<Position>,
 }
 
+/// The oldest versions are dropped when the history grows past this many versions.
+pub const MAX_UNDO_REDO_SIZE: usize = 1000;
+
 #[derive(Clone, PartialEq, Serialize, Deserialize, size_of::SizeOf)]
 pub struct EditorBufferHistory {
     versions: Vec<EditorContent>,
     current_index: isize,
+    /// The character that was typed last, while the current version is still being
+    /// typed (see [history::push_typed_char]).
+    maybe_typed_char: Option<char>,
 }
 
 impl Default for EditorBufferHistory {
@@ -210,6 +243,7 @@ impl Default for EditorBufferHistory {
         Self {
             versions: vec![],
             current_index: -1,
+            maybe_typed_char: None,
         }
     }
 }
@@ -241,6 +275,7 @@ pub mod history {
 
         // Normal history insertion.
         editor_buffer.history.push_content(content_copy);
+        editor_buffer.history.maybe_typed_char = None;
 
         call_if_true!(DEBUG_TUI_COPY_PASTE, {
             tracing::debug!(
@@ -250,10 +285,49 @@ pub mod history {
         });
     }
 
-    pub fn undo(editor_buffer: &mut EditorBuffer) {
+    /// Like [push], but for a `character` that was just typed. Consecutive characters
+    /// are coalesced into a single version, so that a word that was typed is undone all
+    /// at once, instead of one character at a time. A new version is started by the first
+    /// character of the next word, or by anything else that is pushed (or undone) in
+    /// between, eg: a new line.
+    ///
+    /// Call [end_typing] when anything else happens to the buffer (eg: the caret is
+    /// moved), so that the characters typed after it aren't coalesced into this version.
+    pub fn push_typed_char(editor_buffer: &mut EditorBuffer, character: char) {
+        let is_start_of_next_word = match editor_buffer.history.maybe_typed_char {
+            Some(prev_character) => {
+                prev_character.is_whitespace() && !character.is_whitespace()
+            }
+            None => true,
+        };
+
+        match editor_buffer.history.get_current_index() {
+            Some(current_index) if !is_start_of_next_word => {
+                // Invalidate the content cache, since the content just changed.
+                cache::clear(editor_buffer);
+                let current_index = convert_isize_to_usize(current_index);
+                editor_buffer.history.versions[current_index] =
+                    editor_buffer.editor_content.clone();
+            }
+            _ => push(editor_buffer),
+        }
+
+        editor_buffer.history.maybe_typed_char = Some(character);
+    }
+
+    /// Stop coalescing typed characters into the current version, see [push_typed_char].
+    pub fn end_typing(editor_buffer: &mut EditorBuffer) {
+        editor_buffer.history.maybe_typed_char = None;
+    }
+
+    /// Returns [None] if there is nothing to undo.
+    pub fn undo(editor_buffer: &mut EditorBuffer) -> Option<()> {
         // Invalidate the content cache, since the content just changed.
         cache::clear(editor_buffer);
 
+        end_typing(editor_buffer);
+        let index_before = editor_buffer.history.current_index;
+
         let retain_caret_position = editor_buffer.editor_content.caret_display_position;
         if let Some(content) = editor_buffer.history.previous_content() {
             editor_buffer.editor_content = content;
@@ -263,12 +337,18 @@ pub mod history {
         call_if_true!(DEBUG_TUI_COPY_PASTE, {
             tracing::debug!("🍎🍎🍎 undo editor_buffer: {:?}", editor_buffer);
         });
+
+        (editor_buffer.history.current_index != index_before).then_some(())
     }
 
-    pub fn redo(editor_buffer: &mut EditorBuffer) {
+    /// Returns [None] if there is nothing to redo.
+    pub fn redo(editor_buffer: &mut EditorBuffer) -> Option<()> {
         // Invalidate the content cache, since the content just changed.
         cache::clear(editor_buffer);
 
+        end_typing(editor_buffer);
+        let index_before = editor_buffer.history.current_index;
+
         if let Some(content) = editor_buffer.history.next_content() {
             editor_buffer.editor_content = content;
         }
@@ -276,6 +356,8 @@ pub mod history {
         call_if_true!(DEBUG_TUI_COPY_PASTE, {
             tracing::debug!("🍎🍎🍎 redo editor_buffer: {:?}", editor_buffer);
         });
+
+        (editor_buffer.history.current_index != index_before).then_some(())
     }
 
     impl EditorBufferHistory {
@@ -324,6 +406,12 @@ pub mod history {
         fn push_content(&mut self, content: EditorContent) {
             self.versions.push(content);
             self.increment_index();
+
+            // Drop the oldest version, if there are too many.
+            if self.versions.len() > MAX_UNDO_REDO_SIZE {
+                self.versions.remove(0);
+                self.current_index -= 1;
+            }
         }
 
         fn previous_content(&mut self) -> Option<EditorContent> {
@@ -499,6 +587,25 @@ mod history_tests {
         assert_eq2!(editor_buffer.history.current_index, 0);
     }
 
+    #[test]
+    fn test_history_is_bounded() {
+        let mut editor_buffer = EditorBuffer::default();
+        for index in 0..MAX_UNDO_REDO_SIZE + 10 {
+            editor_buffer.editor_content.lines =
+                vec![UnicodeString::from(index.to_string())];
+            history::push(&mut editor_buffer);
+        }
+        assert_eq2!(editor_buffer.history.versions.len(), MAX_UNDO_REDO_SIZE);
+        assert_eq2!(
+            editor_buffer.history.current_index,
+            (MAX_UNDO_REDO_SIZE - 1) as isize
+        );
+
+        // The oldest versions were dropped.
+        while history::undo(&mut editor_buffer).is_some() {}
+        assert_eq2!(editor_buffer.editor_content.lines[0].string, "10");
+    }
+
     #[test]
     fn test_undo_and_multiple_redos() {
         let mut editor_buffer = EditorBuffer::default();
@@ -568,9 +675,27 @@ pub mod cache {
 
     pub fn clear(editor_buffer: &mut EditorBuffer) { editor_buffer.render_cache.clear(); }
 
-    /// Cache key is combination of scroll_offset and window_size.
-    fn generate_key(editor_buffer: &EditorBuffer, window_size: Size) -> String {
-        format!("{}{}", editor_buffer.get_scroll_offset(), window_size,)
+    /// Cache key is combination of scroll_offset, window_size, the position and size of
+    /// the box that the editor is rendered in (which can change even when the window size
+    /// does not, eg: when the app changes its layout), the [crate::LineWrapMode], and the
+    /// syntax language set on the engine (which can both be changed at runtime).
+    fn generate_key(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        window_size: Size,
+    ) -> String {
+        let (box_origin_pos, box_bounds_size) = editor_engine
+            .current_box
+            .get_style_adjusted_position_and_size();
+        format!(
+            "{}{}{}{}{:?}{:?}",
+            editor_buffer.get_scroll_offset(),
+            window_size,
+            box_origin_pos,
+            box_bounds_size,
+            editor_engine.config_options.line_wrap,
+            editor_engine.maybe_syntax_language,
+        )
     }
 
     /// Render the content of the editor buffer to the screen from the cache if the content
@@ -579,6 +704,9 @@ pub mod cache {
     /// The cache miss occurs if
     /// - Scroll Offset changes
     /// - Window size changes
+    /// - Position or size of the editor's box changes
+    /// - Line wrap mode changes
+    /// - Syntax language changes
     /// - Content of the editor changes
     pub fn render_content(
         editor_buffer: &mut EditorBuffer,
@@ -587,7 +715,7 @@ pub mod cache {
         has_focus: &mut HasFocus,
         render_ops: &mut RenderOps,
     ) {
-        let key = generate_key(editor_buffer, window_size);
+        let key = generate_key(editor_buffer, editor_engine, window_size);
         if let Some(cached_output) = editor_buffer.render_cache.get(&key) {
             // Cache hit
             *render_ops = cached_output.clone();
@@ -598,14 +726,14 @@ pub mod cache {
         // - Content has been modified.
         // - Scroll Offset or Window size has been modified.
         editor_buffer.render_cache.clear();
-        let render_args = RenderArgs {
+        let mut render_args = RenderArgs {
             editor_engine,
             editor_buffer,
             has_focus,
         };
 
         // Re-render content, generate & write to render_ops.
-        EditorEngineApi::render_content(&render_args, render_ops);
+        EditorEngineApi::render_content(&mut render_args, render_ops);
 
         // Snapshot the render_ops in the cache.
         editor_buffer.render_cache.insert(key, render_ops.clone());
@@ -754,9 +882,33 @@ pub mod access_and_mutate {
 
         pub fn clear_selection(&mut self) { self.editor_content.selection_map.clear(); }
 
+        pub fn has_extra_carets(&self) -> bool {
+            !self.editor_content.extra_carets.is_empty()
+        }
+
+        pub fn get_extra_carets(&self) -> &[Position] {
+            &self.editor_content.extra_carets
+        }
+
+        pub fn set_extra_carets(&mut self, extra_carets: Vec<Position>) {
+            self.editor_content.extra_carets = extra_carets;
+        }
+
+        pub fn clear_extra_carets(&mut self) { self.editor_content.extra_carets.clear(); }
+
         pub fn get_selection_map(&self) -> &SelectionMap {
             &self.editor_content.selection_map
         }
+
+        pub fn get_diagnostics(&self) -> &[Diagnostic] { &self.diagnostics }
+
+        pub fn set_diagnostics(&mut self, diagnostics: Vec<Diagnostic>) {
+            self.diagnostics = diagnostics;
+        }
+
+        pub fn get_find_state(&self) -> Option<&FindState> {
+            self.maybe_find_state.as_ref()
+        }
     }
 }
 
@@ -769,10 +921,16 @@ pub mod debug_format_helpers {
                 f,
                 "\nEditorBuffer [                                    \n \
                 ├ content: {0:?}                                     \n \
-                └ history: {1:?}                                     \n \
+                ├ history: {1:?}                                     \n \
+                ├ diagnostics: {2}                                   \n \
+                ├ indent_style: {3:?}                                \n \
+                └ find_state: {4:?}                                  \n \
                 ]",
                 /* 0 */ self.editor_content,
                 /* 1 */ self.history,
+                /* 2 */ self.diagnostics.len(),
+                /* 3 */ self.maybe_indent_style,
+                /* 4 */ self.maybe_find_state,
             }
         }
     }
@@ -784,6 +942,7 @@ pub mod debug_format_helpers {
                 "\n\tEditorContent [                                 \n \
                 \t├ lines: {0}, size: {1} b                          \n \
                 \t├ selection_map: {4}                               \n \
+                \t├ extra_carets: {7:?}                              \n \
                 \t└ ext: {2:?}, path:{6:?}, caret: {3:?}, scroll_offset: {5:?}   \n \
                 \t]",
                 /* 0 */ self.lines.len(),
@@ -793,6 +952,7 @@ pub mod debug_format_helpers {
                 /* 4 */ self.selection_map.to_formatted_string(),
                 /* 5 */ self.scroll_offset,
                 /* 6 */ self.maybe_file_path,
+                /* 7 */ self.extra_carets,
             }
         }
     }
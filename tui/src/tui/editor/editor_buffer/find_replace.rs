@@ -0,0 +1,489 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Incremental find, and replace. While finding, the [FindState] is kept in the
+//! [EditorBuffer] (outside of its content, so it doesn't affect the undo history), and
+//! key presses edit the query (or the replacement) instead of the content. Each change
+//! to the query finds all of its matches again, and the caret jumps to the first one
+//! that is at, or after, where the caret was when finding started.
+//!
+//! Matches are found by comparing grapheme clusters, not bytes or chars, so that a query
+//! never matches part of an emoji (or any other grapheme cluster that is made up of
+//! multiple chars), and the display cols of each match are correct for wide graphemes
+//! (eg: CJK). Matching is case sensitive, and matches never span lines, or overlap.
+
+use r3bl_core::{ch, position, ChUnit, Position, SelectionRange, UnicodeString};
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{CaretKind, EditorBuffer, RowIndex};
+use crate::{EditorEngine, EditorEngineInternalApi, FindAction};
+
+/// The state of finding (and replacing) in an [EditorBuffer], see
+/// [EditorBuffer::get_find_state].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FindState {
+    pub query: String,
+    /// This is [None] when only finding, and the replacement text when replacing.
+    pub maybe_replacement: Option<String>,
+    /// Which of the two fields key presses are typed into.
+    pub focus: FindField,
+    /// All the matches of the `query`, sorted by their position in the buffer.
+    pub matches: Vec<FindMatch>,
+    /// The index (into `matches`) of the match that the caret is on.
+    pub maybe_current_match_index: Option<usize>,
+    /// The scroll adjusted position of the caret when finding started. Matches are
+    /// searched for from here when the query changes.
+    pub origin: Position,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FindField {
+    #[default]
+    Query,
+    Replacement,
+}
+
+/// A match of the query in the row at `row_index`, its `range` is in display cols.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FindMatch {
+    pub row_index: RowIndex,
+    pub range: SelectionRange,
+}
+
+impl FindState {
+    pub fn get_current_match(&self) -> Option<&FindMatch> {
+        self.matches.get(self.maybe_current_match_index?)
+    }
+
+    /// Returns the text that describes the state, eg: to show in a find bar. The field
+    /// that has focus is followed by a `▏`.
+    pub fn get_status_text(&self) -> String {
+        let cursor = |field: FindField| if self.focus == field { "▏" } else { "" };
+        let mut it = format!(" Find: {}{}", self.query, cursor(FindField::Query));
+        if let Some(replacement) = &self.maybe_replacement {
+            it.push_str(&format!(
+                "  Replace: {}{}",
+                replacement,
+                cursor(FindField::Replacement)
+            ));
+        }
+        match (self.maybe_current_match_index, self.matches.len()) {
+            (_, 0) if self.query.is_empty() => {}
+            (_, 0) => it.push_str("  No results"),
+            (Some(index), count) => it.push_str(&format!("  {}/{}", index + 1, count)),
+            (None, count) => it.push_str(&format!("  {count}")),
+        }
+        it.push(' ');
+        it
+    }
+}
+
+/// Returns the matches of the `query` in the `lines`, comparing grapheme clusters.
+pub fn find_matches(lines: &[UnicodeString], query: &str) -> Vec<FindMatch> {
+    let query: Vec<&str> = query.graphemes(true).collect();
+    if query.is_empty() {
+        return vec![];
+    }
+
+    let mut acc = vec![];
+    for (row_index, line) in lines.iter().enumerate() {
+        let mut start_index = 0;
+        while start_index + query.len() <= line.len() {
+            let candidate = &line[start_index..start_index + query.len()];
+            let is_match = candidate
+                .iter()
+                .zip(query.iter())
+                .all(|(segment, query_grapheme)| segment.string == *query_grapheme);
+            if !is_match {
+                start_index += 1;
+                continue;
+            }
+
+            let first = &candidate[0];
+            let last = &candidate[candidate.len() - 1];
+            acc.push(FindMatch {
+                row_index: ch!(row_index),
+                range: SelectionRange::new(
+                    first.display_col_offset,
+                    last.display_col_offset + last.unicode_width,
+                ),
+            });
+            start_index += query.len();
+        }
+    }
+    acc
+}
+
+/// Apply the given [FindAction] to the `buffer`. Returns [None] if there was nothing to
+/// apply it to, eg: when not finding.
+pub fn apply_find_action(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    find_action: FindAction,
+) -> Option<()> {
+    match find_action {
+        FindAction::Start => start_find(buffer, FindField::Query),
+        FindAction::StartReplace => start_find(buffer, FindField::Replacement),
+        FindAction::InsertText(text) => {
+            let find_state = buffer.maybe_find_state.as_mut()?;
+            // Matches never span lines, so only the first line of the text is used.
+            let text = text.lines().next().unwrap_or_default();
+            get_focused_field(find_state).push_str(text);
+            if find_state.focus == FindField::Query {
+                let origin = find_state.origin;
+                update_matches(buffer, engine, origin);
+            }
+        }
+        FindAction::DeleteChar => {
+            let find_state = buffer.maybe_find_state.as_mut()?;
+            let field = get_focused_field(find_state);
+            let last_grapheme_len = field.graphemes(true).next_back()?.len();
+            field.truncate(field.len() - last_grapheme_len);
+            if find_state.focus == FindField::Query {
+                let origin = find_state.origin;
+                update_matches(buffer, engine, origin);
+            }
+        }
+        FindAction::Next => go_to_match(buffer, engine, 1)?,
+        FindAction::Previous => go_to_match(buffer, engine, -1)?,
+        FindAction::ToggleField => {
+            let find_state = buffer.maybe_find_state.as_mut()?;
+            find_state.maybe_replacement.as_ref()?;
+            find_state.focus = match find_state.focus {
+                FindField::Query => FindField::Replacement,
+                FindField::Replacement => FindField::Query,
+            };
+        }
+        FindAction::ReplaceCurrent => replace_current_match(buffer, engine)?,
+        FindAction::ReplaceAll => replace_all_matches(buffer, engine)?,
+        FindAction::Exit => {
+            buffer.maybe_find_state.take()?;
+        }
+    }
+    Some(())
+}
+
+/// Start finding, or just move the focus to the given field if already finding.
+fn start_find(buffer: &mut EditorBuffer, focus: FindField) {
+    let origin = buffer.get_caret(CaretKind::ScrollAdjusted);
+    let find_state = buffer.maybe_find_state.get_or_insert_with(|| FindState {
+        origin,
+        ..Default::default()
+    });
+    if focus == FindField::Replacement && find_state.maybe_replacement.is_none() {
+        find_state.maybe_replacement = Some(String::new());
+    }
+    find_state.focus = focus;
+}
+
+fn get_focused_field(find_state: &mut FindState) -> &mut String {
+    match (find_state.focus, &mut find_state.maybe_replacement) {
+        (FindField::Replacement, Some(replacement)) => replacement,
+        _ => &mut find_state.query,
+    }
+}
+
+/// Find all the matches of the query again after the content of the `buffer` was changed
+/// by something other than a [FindAction] (eg: undo), w/out moving the caret. The current
+/// match is the one that starts at the caret, if there is one.
+pub fn refresh_find_matches(buffer: &mut EditorBuffer) {
+    let caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+    let Some(query) = buffer.maybe_find_state.as_ref().map(|it| it.query.clone()) else {
+        return;
+    };
+    let matches = find_matches(buffer.get_lines(), &query);
+    let maybe_current_match_index = matches.iter().position(|it| {
+        it.row_index == caret.row_index
+            && it.range.start_display_col_index == caret.col_index
+    });
+
+    if let Some(find_state) = buffer.maybe_find_state.as_mut() {
+        find_state.matches = matches;
+        find_state.maybe_current_match_index = maybe_current_match_index;
+    }
+}
+
+/// Find all the matches of the query again, and move the caret to the first one that is
+/// at, or after, `from` (or the first one in the buffer, if there isn't one after it).
+fn update_matches(buffer: &mut EditorBuffer, engine: &mut EditorEngine, from: Position) {
+    let Some(query) = buffer.maybe_find_state.as_ref().map(|it| it.query.clone()) else {
+        return;
+    };
+    let matches = find_matches(buffer.get_lines(), &query);
+    let maybe_current_match_index = if matches.is_empty() {
+        None
+    } else {
+        matches
+            .iter()
+            .position(|it| {
+                (it.row_index, it.range.start_display_col_index)
+                    >= (from.row_index, from.col_index)
+            })
+            .or(Some(0))
+    };
+
+    if let Some(find_state) = buffer.maybe_find_state.as_mut() {
+        find_state.matches = matches;
+        find_state.maybe_current_match_index = maybe_current_match_index;
+    }
+    move_caret_to_current_match(buffer, engine);
+}
+
+/// Move to the match that is `offset` matches away from the current one, wrapping
+/// around at the start and end of the buffer.
+fn go_to_match(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    offset: isize,
+) -> Option<()> {
+    let find_state = buffer.maybe_find_state.as_mut()?;
+    let match_count = find_state.matches.len() as isize;
+    if match_count == 0 {
+        return None;
+    }
+    let new_index = match find_state.maybe_current_match_index {
+        Some(index) => (index as isize + offset).rem_euclid(match_count),
+        None => 0,
+    };
+    find_state.maybe_current_match_index = Some(new_index as usize);
+    move_caret_to_current_match(buffer, engine);
+    Some(())
+}
+
+fn move_caret_to_current_match(buffer: &mut EditorBuffer, engine: &mut EditorEngine) {
+    let Some(find_match) = buffer
+        .maybe_find_state
+        .as_ref()
+        .and_then(|it| it.get_current_match())
+        .copied()
+    else {
+        return;
+    };
+    EditorEngineInternalApi::to_position(
+        buffer,
+        engine,
+        position!(
+            col_index: find_match.range.start_display_col_index,
+            row_index: find_match.row_index
+        ),
+    );
+}
+
+/// Replace the current match, and move to the next one (after the replacement).
+fn replace_current_match(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+) -> Option<()> {
+    let find_state = buffer.maybe_find_state.as_ref()?;
+    let replacement = find_state.maybe_replacement.clone()?;
+    let find_match = *find_state.get_current_match()?;
+
+    replace_matches_in_line(buffer, find_match.row_index, &[find_match], &replacement);
+
+    let replacement_width = UnicodeString::from(replacement.as_str()).display_width;
+    update_matches(
+        buffer,
+        engine,
+        position!(
+            col_index: find_match.range.start_display_col_index + replacement_width,
+            row_index: find_match.row_index
+        ),
+    );
+    Some(())
+}
+
+fn replace_all_matches(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+) -> Option<()> {
+    let find_state = buffer.maybe_find_state.as_ref()?;
+    let replacement = find_state.maybe_replacement.clone()?;
+    let matches = find_state.matches.clone();
+    if matches.is_empty() {
+        return None;
+    }
+
+    for row_matches in matches.chunk_by(|lhs, rhs| lhs.row_index == rhs.row_index) {
+        replace_matches_in_line(
+            buffer,
+            row_matches[0].row_index,
+            row_matches,
+            &replacement,
+        );
+    }
+
+    let origin = buffer.get_caret(CaretKind::ScrollAdjusted);
+    update_matches(buffer, engine, origin);
+    Some(())
+}
+
+/// Replace the `row_matches` (which must all be in the line at `row_index`, and be
+/// sorted) w/ the `replacement`.
+fn replace_matches_in_line(
+    buffer: &mut EditorBuffer,
+    row_index: ChUnit,
+    row_matches: &[FindMatch],
+    replacement: &str,
+) {
+    let (lines, _, _, _) = buffer.get_mut();
+    let Some(line) = lines.get_mut(ch!(@to_usize row_index)) else {
+        return;
+    };
+
+    let mut new_line = line.string.clone();
+    // Replace from the end of the line, so that the byte offsets of the matches before
+    // it are still valid.
+    for find_match in row_matches.iter().rev() {
+        let mut byte_range = line
+            .iter()
+            .filter(|segment| {
+                segment.display_col_offset >= find_match.range.start_display_col_index
+                    && segment.display_col_offset < find_match.range.end_display_col_index
+            })
+            .map(|segment| segment.byte_offset..segment.byte_offset + segment.byte_size);
+        let Some(first) = byte_range.next() else {
+            continue;
+        };
+        let end = byte_range.next_back().map_or(first.end, |it| it.end);
+        new_line.replace_range(first.start..end, replacement);
+    }
+    *line = UnicodeString::from(new_line);
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{test_fixtures::mock_real_objects_for_editor, DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_lines(lines: &[&str]) -> Vec<UnicodeString> {
+        lines.iter().map(|it| UnicodeString::from(*it)).collect()
+    }
+
+    fn get_ranges(matches: &[FindMatch]) -> Vec<(usize, usize, usize)> {
+        matches
+            .iter()
+            .map(|it| {
+                (
+                    ch!(@to_usize it.row_index),
+                    ch!(@to_usize it.range.start_display_col_index),
+                    ch!(@to_usize it.range.end_display_col_index),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_matches() {
+        let lines = make_lines(&["abab", "xaba", "", "aaa"]);
+        assert_eq2!(
+            get_ranges(&find_matches(&lines, "ab")),
+            vec![(0, 0, 2), (0, 2, 4), (1, 1, 3)]
+        );
+        // Matches don't overlap.
+        assert_eq2!(get_ranges(&find_matches(&lines, "aa")), vec![(3, 0, 2)]);
+        assert_eq2!(find_matches(&lines, ""), vec![]);
+    }
+
+    #[test]
+    fn test_find_matches_w_wide_graphemes() {
+        // The display cols of the matches take the width of the graphemes into account.
+        let lines = make_lines(&["😃a😃a", "日本語の本"]);
+        assert_eq2!(
+            get_ranges(&find_matches(&lines, "😃a")),
+            vec![(0, 0, 3), (0, 3, 6)]
+        );
+        assert_eq2!(
+            get_ranges(&find_matches(&lines, "本")),
+            vec![(1, 2, 4), (1, 8, 10)]
+        );
+
+        // A query never matches part of a grapheme cluster.
+        let lines = make_lines(&["👨‍👩‍👧 👨"]);
+        assert_eq2!(get_ranges(&find_matches(&lines, "👨")), vec![(0, 3, 5)]);
+    }
+
+    #[test]
+    fn test_find_and_replace() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["one two".into(), "two one two".into()]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        apply_find_action(&mut buffer, &mut engine, FindAction::Start);
+        apply_find_action(
+            &mut buffer,
+            &mut engine,
+            FindAction::InsertText("tw".into()),
+        );
+        apply_find_action(&mut buffer, &mut engine, FindAction::InsertText("o".into()));
+        let find_state = buffer.get_find_state().unwrap();
+        assert_eq2!(find_state.matches.len(), 3);
+        assert_eq2!(find_state.maybe_current_match_index, Some(0));
+        assert_eq2!(find_state.get_status_text(), " Find: two▏  1/3 ");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        // Moving between matches wraps around.
+        apply_find_action(&mut buffer, &mut engine, FindAction::Previous);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 1)
+        );
+        apply_find_action(&mut buffer, &mut engine, FindAction::Next);
+        apply_find_action(&mut buffer, &mut engine, FindAction::Next);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Replace the current match, and move to the next one.
+        apply_find_action(&mut buffer, &mut engine, FindAction::StartReplace);
+        apply_find_action(&mut buffer, &mut engine, FindAction::InsertText("2".into()));
+        assert_eq2!(
+            buffer.get_find_state().unwrap().get_status_text(),
+            " Find: two  Replace: 2▏  2/3 "
+        );
+        apply_find_action(&mut buffer, &mut engine, FindAction::ReplaceCurrent);
+        assert_eq2!(buffer.get_lines(), &make_lines(&["one two", "2 one two"]));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 6, row_index: 1)
+        );
+
+        apply_find_action(&mut buffer, &mut engine, FindAction::ReplaceAll);
+        assert_eq2!(buffer.get_lines(), &make_lines(&["one 2", "2 one 2"]));
+        assert_eq2!(
+            buffer.get_find_state().unwrap().get_status_text(),
+            " Find: two  Replace: 2▏  No results "
+        );
+
+        // Deleting a char from the query finds its matches again.
+        apply_find_action(&mut buffer, &mut engine, FindAction::ToggleField);
+        apply_find_action(&mut buffer, &mut engine, FindAction::DeleteChar);
+        apply_find_action(&mut buffer, &mut engine, FindAction::DeleteChar);
+        assert_eq2!(buffer.get_find_state().unwrap().query, "t");
+        assert_eq2!(buffer.get_find_state().unwrap().matches, vec![]);
+
+        apply_find_action(&mut buffer, &mut engine, FindAction::Exit);
+        assert_eq2!(buffer.get_find_state(), None);
+    }
+}
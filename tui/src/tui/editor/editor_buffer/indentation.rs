@@ -0,0 +1,224 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashMap,
+          fmt::{Display, Formatter, Result}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{EditorBuffer, EditorEngineConfig};
+
+pub const DEFAULT_INDENT_WIDTH: usize = 4;
+
+/// Widths larger than this are not considered to be a level of indentation when
+/// detecting it, eg: when a line is aligned w/ something on the line above it.
+pub const MAX_INDENT_WIDTH: usize = 8;
+
+/// At least this many lines must be indented for the indentation to be detected.
+pub const MIN_INDENTED_LINES_FOR_DETECTION: usize = 2;
+
+/// How many times more lines have to be indented w/ one kind of whitespace (tabs or
+/// spaces) than the other, for it to be the predominant one.
+pub const INDENT_DETECTION_DOMINANCE_RATIO: usize = 2;
+
+/// What is inserted when the Tab key is pressed in the editor (see
+/// [crate::EditorEvent::InsertIndent]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IndentStyle {
+    Tabs,
+    /// The number of spaces in one level of indentation.
+    Spaces(usize),
+}
+
+impl Default for IndentStyle {
+    fn default() -> Self { IndentStyle::Spaces(DEFAULT_INDENT_WIDTH) }
+}
+
+impl Display for IndentStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            IndentStyle::Tabs => write!(f, "Tabs"),
+            IndentStyle::Spaces(width) => write!(f, "Spaces: {width}"),
+        }
+    }
+}
+
+impl IndentStyle {
+    /// The text for one level of indentation.
+    pub fn get_indent_string(&self) -> String {
+        match self {
+            IndentStyle::Tabs => "\t".to_string(),
+            IndentStyle::Spaces(width) => " ".repeat(*width),
+        }
+    }
+}
+
+/// Returns the predominant [IndentStyle] of the `lines`, or [None] if it can't be
+/// detected, eg: if there are too few indented lines, tabs and spaces are mixed w/out
+/// either being predominant, or the widths of the space indented lines are ambiguous.
+///
+/// The width of space indentation is the most common (positive) difference between the
+/// indentation of consecutive lines, so that nested blocks are counted once per level.
+/// Blank lines are skipped.
+pub fn detect_indent_style<'a>(
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Option<IndentStyle> {
+    let mut tab_indented_line_count = 0;
+    let mut space_indented_line_count = 0;
+    let mut width_deltas: HashMap<usize, usize> = HashMap::new();
+    let mut prev_space_width = 0;
+
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.starts_with('\t') {
+            tab_indented_line_count += 1;
+            continue;
+        }
+
+        let space_width = line.len() - line.trim_start_matches(' ').len();
+        if space_width > 0 {
+            space_indented_line_count += 1;
+        }
+        if space_width > prev_space_width
+            && space_width - prev_space_width <= MAX_INDENT_WIDTH
+        {
+            *width_deltas
+                .entry(space_width - prev_space_width)
+                .or_default() += 1;
+        }
+        prev_space_width = space_width;
+    }
+
+    if tab_indented_line_count + space_indented_line_count
+        < MIN_INDENTED_LINES_FOR_DETECTION
+    {
+        return None;
+    }
+
+    if tab_indented_line_count
+        >= space_indented_line_count * INDENT_DETECTION_DOMINANCE_RATIO
+    {
+        return Some(IndentStyle::Tabs);
+    }
+
+    if space_indented_line_count
+        < tab_indented_line_count * INDENT_DETECTION_DOMINANCE_RATIO
+    {
+        return None;
+    }
+
+    // The most common width wins, and a tie is ambiguous.
+    let max_count = width_deltas.values().copied().max()?;
+    let mut widths_w_max_count = width_deltas
+        .iter()
+        .filter(|(_, count)| **count == max_count)
+        .map(|(width, _)| *width);
+    match (widths_w_max_count.next(), widths_w_max_count.next()) {
+        (Some(width), None) => Some(IndentStyle::Spaces(width)),
+        _ => None,
+    }
+}
+
+impl EditorBuffer {
+    /// Detects the [IndentStyle] of the content (see [detect_indent_style]), and uses it
+    /// for this buffer instead of [crate::EditorEngineConfig::indent_style]. If it can't
+    /// be detected, the config is used. Returns the detected style.
+    pub fn detect_indent_style(&mut self) -> Option<IndentStyle> {
+        self.maybe_indent_style =
+            detect_indent_style(self.get_lines().iter().map(|line| line.string.as_str()));
+        self.maybe_indent_style
+    }
+
+    /// The [IndentStyle] that is used for this buffer (eg: because it was detected, or
+    /// chosen by the user), if it overrides [crate::EditorEngineConfig::indent_style].
+    pub fn get_maybe_indent_style(&self) -> Option<IndentStyle> {
+        self.maybe_indent_style
+    }
+
+    /// The [IndentStyle] that is used for this buffer, which is its own (if it has one),
+    /// or the one in the `config`.
+    pub fn get_indent_style(&self, config: &EditorEngineConfig) -> IndentStyle {
+        self.maybe_indent_style.unwrap_or(config.indent_style)
+    }
+
+    pub fn set_indent_style(&mut self, indent_style: IndentStyle) {
+        self.maybe_indent_style = Some(indent_style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn detect(content: &str) -> Option<IndentStyle> {
+        detect_indent_style(content.lines())
+    }
+
+    #[test]
+    fn test_detect_spaces() {
+        let content = "fn main() {\n  if true {\n    foo();\n\n    bar();\n  }\n}\n";
+        assert_eq2!(detect(content), Some(IndentStyle::Spaces(2)));
+
+        let content = "a:\n    b:\n        c\n    d:\n        e\nf:\n    g\n";
+        assert_eq2!(detect(content), Some(IndentStyle::Spaces(4)));
+    }
+
+    #[test]
+    fn test_detect_tabs() {
+        let content = "fn main() {\n\tif true {\n\t\tfoo();\n\t}\n}\n";
+        assert_eq2!(detect(content), Some(IndentStyle::Tabs));
+    }
+
+    #[test]
+    fn test_detect_falls_back_when_ambiguous() {
+        // Empty and single line files.
+        assert_eq2!(detect(""), None);
+        assert_eq2!(detect("    foo"), None);
+
+        // No indentation at all.
+        assert_eq2!(detect("foo\nbar\nbaz\n"), None);
+
+        // Just as many lines are indented w/ tabs as w/ spaces.
+        assert_eq2!(detect("a\n\tb\n  c\n\td\n  e\n"), None);
+
+        // Just as many levels are 2 spaces wide as 4 spaces wide.
+        assert_eq2!(detect("a\n  b\nc\n    d\n"), None);
+    }
+
+    #[test]
+    fn test_editor_buffer_indent_style() {
+        let mut buffer = EditorBuffer::new_empty(&Some("md".into()), &None);
+        assert_eq2!(buffer.get_maybe_indent_style(), None);
+
+        buffer.set_lines(vec!["a".into(), "\tb".into(), "\t\tc".into()]);
+        assert_eq2!(buffer.detect_indent_style(), Some(IndentStyle::Tabs));
+        assert_eq2!(buffer.get_maybe_indent_style(), Some(IndentStyle::Tabs));
+
+        buffer.set_indent_style(IndentStyle::Spaces(2));
+        assert_eq2!(
+            buffer.get_maybe_indent_style(),
+            Some(IndentStyle::Spaces(2))
+        );
+        assert_eq2!(IndentStyle::Spaces(2).get_indent_string(), "  ");
+        assert_eq2!(IndentStyle::Spaces(2).to_string(), "Spaces: 2");
+    }
+}
@@ -0,0 +1,269 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Find the link under the caret, so that an app can open it (eg: in the default browser
+//! using the OS opener). This only detects and resolves the link, it is up to the app to
+//! decide what to do with it.
+//!
+//! In Markdown files, the current line is parsed (using
+//! [crate::parse_inline_fragments_until_eol_or_eoi()]) to find `[text](url)` links and
+//! `![alt](url)` images. In all files, bare `http://` and `https://` URLs are detected as
+//! well. Since links can't span multiple lines in Markdown, only the line that the caret
+//! is on has to be checked.
+
+use std::path::{Path, PathBuf};
+
+use r3bl_core::{ch, UnicodeString};
+
+use super::{CaretKind, EditorBuffer};
+use crate::{parse_inline_fragments_until_eol_or_eoi,
+            CheckboxParsePolicy,
+            MdLineFragment,
+            DEFAULT_SYN_HI_FILE_EXT};
+
+/// What a link points to, once it has been resolved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// A URL w/ a scheme, eg: `https://r3bl.com` or `mailto:foo@bar.com`.
+    Url(String),
+    /// A path to a file or folder. Relative links are resolved against the folder of
+    /// the file that is being edited.
+    File(PathBuf),
+}
+
+/// Returns the [LinkTarget] of the link under the caret, or [None] if the caret isn't on
+/// a link. Links to a heading in the same document (eg: `#intro`) are ignored.
+pub fn get_link_at_caret(buffer: &EditorBuffer) -> Option<LinkTarget> {
+    let caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+    let line = buffer.get_lines().get(ch!(@to_usize caret.row_index))?;
+    let byte_index = get_byte_index_at_display_col(line, ch!(@to_usize caret.col_index))?;
+
+    let is_markdown = buffer.get_maybe_file_extension() == Some(DEFAULT_SYN_HI_FILE_EXT);
+    let maybe_url = if is_markdown {
+        get_md_link_url_at(&line.string, byte_index)
+    } else {
+        None
+    }
+    .or_else(|| get_bare_url_at(&line.string, byte_index))?;
+
+    get_link_target(maybe_url, buffer.editor_content.maybe_file_path.as_deref())
+}
+
+/// Resolve a `url` (as it is written in the document) into a [LinkTarget].
+pub fn get_link_target(url: &str, maybe_file_path: Option<&str>) -> Option<LinkTarget> {
+    let url = url.trim();
+    if url.is_empty() || url.starts_with('#') {
+        return None;
+    }
+
+    if url.contains("://") || url.starts_with("mailto:") {
+        return Some(LinkTarget::Url(url.to_string()));
+    }
+
+    // Drop the anchor (if any), since it isn't part of the path.
+    let path = Path::new(url.split('#').next().unwrap_or(url));
+    if path.is_absolute() {
+        return Some(LinkTarget::File(path.to_path_buf()));
+    }
+
+    let maybe_parent_dir = maybe_file_path.and_then(|it| Path::new(it).parent());
+    Some(LinkTarget::File(match maybe_parent_dir {
+        Some(parent_dir) => parent_dir.join(path),
+        None => path.to_path_buf(),
+    }))
+}
+
+/// Returns [None] if the caret is past the end of the line.
+fn get_byte_index_at_display_col(
+    line: &UnicodeString,
+    display_col: usize,
+) -> Option<usize> {
+    line.vec_segment
+        .iter()
+        .find(|segment| {
+            let start = ch!(@to_usize segment.display_col_offset);
+            display_col >= start
+                && display_col < start + ch!(@to_usize segment.unicode_width)
+        })
+        .map(|segment| segment.byte_offset)
+}
+
+/// Walk the fragments in the `line`, and return the url of the link (or image) that
+/// contains the `byte_index`.
+fn get_md_link_url_at(line: &str, byte_index: usize) -> Option<&str> {
+    let mut input = line;
+    while !input.is_empty() {
+        let start = line.len() - input.len();
+        let Ok((rem, fragment)) = parse_inline_fragments_until_eol_or_eoi(
+            input,
+            CheckboxParsePolicy::IgnoreCheckbox,
+        ) else {
+            return None;
+        };
+        let end = line.len() - rem.len();
+        // Guard against a parser that doesn't consume any input.
+        if end == start {
+            return None;
+        }
+
+        if (start..end).contains(&byte_index) {
+            return match fragment {
+                MdLineFragment::Link(link) | MdLineFragment::Image(link) => {
+                    Some(link.url)
+                }
+                _ => None,
+            };
+        }
+
+        input = rem;
+    }
+    None
+}
+
+/// Find the whitespace delimited word that contains the `byte_index`, and return it if
+/// it is a `http://` or `https://` URL. Punctuation around the URL (eg: `<url>` or a
+/// period at the end of a sentence) is not part of it.
+fn get_bare_url_at(line: &str, byte_index: usize) -> Option<&str> {
+    let start = line[..byte_index]
+        .rfind(char::is_whitespace)
+        .map(|it| it + 1)
+        .unwrap_or(0);
+    let end = line[byte_index..]
+        .find(char::is_whitespace)
+        .map(|it| it + byte_index)
+        .unwrap_or(line.len());
+
+    let word = line[start..end]
+        .trim_start_matches(['<', '(', '"', '\''])
+        .trim_end_matches(['>', ')', '"', '\'', '.', ',', ';', ':', '!', '?']);
+
+    if word.starts_with("http://") || word.starts_with("https://") {
+        Some(word)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position};
+
+    use super::*;
+
+    fn make_buffer(
+        line: &str,
+        col_index: usize,
+        maybe_file_path: Option<&str>,
+    ) -> EditorBuffer {
+        let mut buffer = EditorBuffer::new_empty(
+            &Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()),
+            &maybe_file_path.map(String::from),
+        );
+        buffer.set_lines(vec![line.to_string()]);
+        let (_, caret, _, _) = buffer.get_mut();
+        *caret = position!(col_index: col_index, row_index: 0);
+        buffer
+    }
+
+    #[test]
+    fn test_md_link_at_caret() {
+        let line = "see [r3bl](https://r3bl.com) for more";
+
+        // On the text of the link.
+        let buffer = make_buffer(line, 6, None);
+        assert_eq2!(
+            get_link_at_caret(&buffer),
+            Some(LinkTarget::Url("https://r3bl.com".into()))
+        );
+
+        // On the url of the link.
+        let buffer = make_buffer(line, 20, None);
+        assert_eq2!(
+            get_link_at_caret(&buffer),
+            Some(LinkTarget::Url("https://r3bl.com".into()))
+        );
+
+        // Not on the link.
+        let buffer = make_buffer(line, 1, None);
+        assert_eq2!(get_link_at_caret(&buffer), None);
+
+        // Past the end of the line.
+        let buffer = make_buffer(line, line.len(), None);
+        assert_eq2!(get_link_at_caret(&buffer), None);
+    }
+
+    #[test]
+    fn test_bare_url_at_caret() {
+        let line = "docs: <https://docs.rs/r3bl_tui>. 😃 https://r3bl.com/foo_bar.";
+
+        let buffer = make_buffer(line, 10, None);
+        assert_eq2!(
+            get_link_at_caret(&buffer),
+            Some(LinkTarget::Url("https://docs.rs/r3bl_tui".into()))
+        );
+
+        // Display cols are used (the emoji is 2 cols wide).
+        let buffer = make_buffer(line, 50, None);
+        assert_eq2!(
+            get_link_at_caret(&buffer),
+            Some(LinkTarget::Url("https://r3bl.com/foo_bar".into()))
+        );
+
+        let buffer = make_buffer(line, 2, None);
+        assert_eq2!(get_link_at_caret(&buffer), None);
+    }
+
+    #[test]
+    fn test_relative_link_at_caret() {
+        let buffer = make_buffer(
+            "[notes](../notes.md#todo)",
+            2,
+            Some("/home/foo/docs/README.md"),
+        );
+        assert_eq2!(
+            get_link_at_caret(&buffer),
+            Some(LinkTarget::File(PathBuf::from(
+                "/home/foo/docs/../notes.md"
+            )))
+        );
+
+        // Links to a heading in the same document are ignored.
+        let buffer = make_buffer("[intro](#intro)", 2, None);
+        assert_eq2!(get_link_at_caret(&buffer), None);
+    }
+
+    #[test]
+    fn test_get_link_target() {
+        assert_eq2!(
+            get_link_target("mailto:foo@bar.com", None),
+            Some(LinkTarget::Url("mailto:foo@bar.com".into()))
+        );
+        assert_eq2!(
+            get_link_target("/tmp/foo.md", Some("bar/baz.md")),
+            Some(LinkTarget::File(PathBuf::from("/tmp/foo.md")))
+        );
+        assert_eq2!(
+            get_link_target("foo.md", Some("bar/baz.md")),
+            Some(LinkTarget::File(PathBuf::from("bar/foo.md")))
+        );
+        assert_eq2!(
+            get_link_target("foo.md", None),
+            Some(LinkTarget::File(PathBuf::from("foo.md")))
+        );
+        assert_eq2!(get_link_target(" ", None), None);
+    }
+}
@@ -16,15 +16,35 @@
  */
 
 // Attach.
+pub mod block_selection;
+pub mod checkbox_toggle;
+pub mod document_outline;
 pub mod editor_buffer_clipboard_support;
+pub mod editor_buffer_diagnostics;
 pub mod editor_buffer_selection_support;
+pub mod editor_buffer_snapshot;
 pub mod editor_buffer_struct;
+pub mod find_replace;
+pub mod indentation;
+pub mod link_at_caret;
+pub mod ordered_list_renumbering;
+pub mod selection_expansion;
 pub mod selection_map;
 pub mod system_clipboard_service_provider;
 
 // Re-export.
+pub use block_selection::*;
+pub use checkbox_toggle::*;
+pub use document_outline::*;
 pub use editor_buffer_clipboard_support::*;
+pub use editor_buffer_diagnostics::*;
 pub use editor_buffer_selection_support::*;
+pub use editor_buffer_snapshot::*;
 pub use editor_buffer_struct::*;
+pub use find_replace::*;
+pub use indentation::*;
+pub use link_at_caret::*;
+pub use ordered_list_renumbering::*;
+pub use selection_expansion::*;
 pub use selection_map::*;
 pub use system_clipboard_service_provider::*;
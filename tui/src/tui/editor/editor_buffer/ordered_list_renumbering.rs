@@ -0,0 +1,302 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Keep the numbers of ordered lists in Markdown sequential, when items are inserted or
+//! deleted. This is used by
+//! [EditorEngineApi::apply_event](crate::EditorEngineApi::apply_event) when
+//! [crate::ListRenumberingMode::Enable] is set.
+//!
+//! The parsed [crate::MdDocument] is used to find the lists:
+//! - Each list is numbered starting from the number of its first item.
+//! - Nested lists are numbered independently of the list that they are nested in, which
+//!   continues after them.
+//! - Lists that use the same number for every item (eg: `1.`) are left alone, since this
+//!   is often done on purpose.
+
+use r3bl_core::{ch, UnicodeString};
+
+use crate::{parse_markdown, BulletKind, MdBlock};
+
+/// A line that has to be replaced in order to renumber a list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenumberedLine {
+    pub row_index: usize,
+    pub line: String,
+    /// Display col index at which the number starts.
+    pub number_col_index: usize,
+    /// Change in the display width of the line (if the number has more or fewer digits).
+    pub width_delta: isize,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ListItem {
+    row_index: usize,
+    indent: usize,
+    bullet_kind: BulletKind,
+}
+
+/// Returns the lines that have to be replaced so that all the ordered lists are numbered
+/// sequentially. Nothing is returned if the lines can't be parsed as Markdown.
+pub fn get_renumbered_lines(lines: &[UnicodeString]) -> Vec<RenumberedLine> {
+    let Some(blocks) = get_list_items(lines) else {
+        return vec![];
+    };
+
+    let mut acc = vec![];
+
+    // Stack of the lists that are currently open, from the outermost to the innermost.
+    let mut open_lists: Vec<Vec<ListItem>> = vec![];
+
+    for maybe_item in blocks {
+        let Some(item) = maybe_item else {
+            // Any other block ends all the open lists.
+            for list in open_lists.drain(..).rev() {
+                renumber_list(lines, &list, &mut acc);
+            }
+            continue;
+        };
+
+        // Close the lists that are nested deeper than this item.
+        while let Some(list) = open_lists.last() {
+            if list[0].indent <= item.indent {
+                break;
+            }
+            if let Some(list) = open_lists.pop() {
+                renumber_list(lines, &list, &mut acc);
+            }
+        }
+
+        match open_lists.last_mut() {
+            Some(list)
+                if list[0].indent == item.indent
+                    && is_same_kind(list[0].bullet_kind, item.bullet_kind) =>
+            {
+                list.push(item);
+            }
+            Some(list) if list[0].indent == item.indent => {
+                // An unordered item after an ordered one (or vice versa) starts a new list.
+                let list = std::mem::replace(list, vec![item]);
+                renumber_list(lines, &list, &mut acc);
+            }
+            _ => open_lists.push(vec![item]),
+        }
+    }
+
+    for list in open_lists.drain(..).rev() {
+        renumber_list(lines, &list, &mut acc);
+    }
+
+    acc.sort_by_key(|it| it.row_index);
+    acc
+}
+
+fn is_same_kind(lhs: BulletKind, rhs: BulletKind) -> bool {
+    matches!(
+        (lhs, rhs),
+        (BulletKind::Ordered(_), BulletKind::Ordered(_))
+            | (BulletKind::Unordered, BulletKind::Unordered)
+    )
+}
+
+/// Parse the lines as Markdown, and return the list item that each block starts w/ (or
+/// [None] for blocks that aren't list items). Returns [None] if the lines of the parsed
+/// blocks don't match up w/ the given lines.
+fn get_list_items(lines: &[UnicodeString]) -> Option<Vec<Option<ListItem>>> {
+    let mut input = lines
+        .iter()
+        .map(|line| line.string.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    input.push('\n');
+
+    let (remainder, document) = parse_markdown(&input).ok()?;
+    if !remainder.is_empty() {
+        return None;
+    }
+
+    let mut acc = vec![];
+    let mut row_index = 0;
+    for md_block in document.iter() {
+        acc.push(match md_block {
+            MdBlock::SmartList((_, bullet_kind, indent)) => Some(ListItem {
+                row_index,
+                indent: *indent,
+                bullet_kind: *bullet_kind,
+            }),
+            _ => None,
+        });
+        row_index += md_block.get_line_count()?;
+    }
+
+    if row_index != lines.len() {
+        return None;
+    }
+
+    Some(acc)
+}
+
+fn renumber_list(
+    lines: &[UnicodeString],
+    list: &[ListItem],
+    acc: &mut Vec<RenumberedLine>,
+) {
+    let numbers: Vec<usize> = list
+        .iter()
+        .filter_map(|item| match item.bullet_kind {
+            BulletKind::Ordered(number) => Some(number),
+            BulletKind::Unordered => None,
+        })
+        .collect();
+
+    let Some(first_number) = numbers.first() else {
+        return;
+    };
+
+    // Preserve lists that use the same number for every item.
+    if numbers.len() > 1 && numbers.iter().all(|it| it == first_number) {
+        return;
+    }
+
+    for (index, (item, number)) in list.iter().zip(numbers.iter()).enumerate() {
+        let expected_number = first_number + index;
+        if *number == expected_number {
+            continue;
+        }
+        if let Some(renumbered_line) =
+            renumber_line(&lines[item.row_index], item.row_index, expected_number)
+        {
+            acc.push(renumbered_line);
+        }
+    }
+}
+
+/// Replace the number at the start of the line (after the indent).
+fn renumber_line(
+    line: &UnicodeString,
+    row_index: usize,
+    number: usize,
+) -> Option<RenumberedLine> {
+    let text = line.string.as_str();
+    let number_start = text.len() - text.trim_start_matches(' ').len();
+    let number_len = text[number_start..]
+        .chars()
+        .take_while(|it| it.is_ascii_digit())
+        .count();
+    if number_len == 0 {
+        return None;
+    }
+
+    let new_number = number.to_string();
+    let new_line = format!(
+        "{}{}{}",
+        &text[..number_start],
+        new_number,
+        &text[number_start + number_len..]
+    );
+
+    Some(RenumberedLine {
+        row_index,
+        width_delta: new_number.len() as isize - number_len as isize,
+        number_col_index: ch!(@to_usize UnicodeString::from(&text[..number_start]).display_width),
+        line: new_line,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn renumber(lines: &[&str]) -> Vec<String> {
+        let lines: Vec<UnicodeString> =
+            lines.iter().map(|it| UnicodeString::from(*it)).collect();
+        let mut acc: Vec<String> = lines.iter().map(|it| it.string.clone()).collect();
+        for RenumberedLine {
+            row_index, line, ..
+        } in get_renumbered_lines(&lines)
+        {
+            acc[row_index] = line;
+        }
+        acc
+    }
+
+    #[test]
+    fn test_renumber_after_insert_and_delete() {
+        // Inserted item.
+        assert_eq2!(
+            renumber(&["1. a", "2. b", "2. new", "3. c"]),
+            vec!["1. a", "2. b", "3. new", "4. c"]
+        );
+        // Deleted item.
+        assert_eq2!(
+            renumber(&["1. a", "3. c", "4. d"]),
+            vec!["1. a", "2. c", "3. d"]
+        );
+        // Starts at the number of the first item.
+        assert_eq2!(renumber(&["5. a", "7. b"]), vec!["5. a", "6. b"]);
+        // Nothing to do.
+        let lines: Vec<UnicodeString> = ["1. a", "2. b"]
+            .iter()
+            .map(|it| UnicodeString::from(*it))
+            .collect();
+        assert_eq2!(get_renumbered_lines(&lines), vec![]);
+    }
+
+    #[test]
+    fn test_preserve_same_number_lists() {
+        assert_eq2!(
+            renumber(&["1. a", "1. b", "1. c"]),
+            vec!["1. a", "1. b", "1. c"]
+        );
+    }
+
+    #[test]
+    fn test_nested_lists_are_renumbered_independently() {
+        assert_eq2!(
+            renumber(&["1. a", "  1. x", "  3. y", "3. b", "  - z", "  - w", "4. c"]),
+            vec!["1. a", "  1. x", "  2. y", "2. b", "  - z", "  - w", "3. c"]
+        );
+    }
+
+    #[test]
+    fn test_other_blocks_end_the_list() {
+        assert_eq2!(
+            renumber(&["1. a", "3. b", "", "5. c", "9. d"]),
+            vec!["1. a", "2. b", "", "5. c", "6. d"]
+        );
+    }
+
+    #[test]
+    fn test_width_delta() {
+        let lines: Vec<UnicodeString> = (1..=10)
+            .map(|it| match it {
+                10 => UnicodeString::from("9. last"),
+                _ => UnicodeString::from(format!("{it}. item")),
+            })
+            .collect();
+        assert_eq2!(
+            get_renumbered_lines(&lines),
+            vec![RenumberedLine {
+                row_index: 9,
+                line: "10. last".to_string(),
+                number_col_index: 0,
+                width_delta: 1,
+            }]
+        );
+    }
+}
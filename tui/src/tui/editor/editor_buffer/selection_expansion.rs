@@ -0,0 +1,739 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Progressively grow the selection (starting from the caret) by word, line, or block,
+//! and shrink it back down again. Each expansion is recorded in the
+//! [SelectionMap::expansion_history], so that shrinking restores the exact selection that
+//! existed before the expansion. Just like select all, the caret does not move.
+//!
+//! Block expansion uses the parsed [crate::MdDocument] when the buffer contains Markdown,
+//! so that whole list items (along w/ their nested items), code blocks, and lists can be
+//! selected. For other files, paragraphs (lines separated by blank lines) are used.
+
+use r3bl_core::{ch, position, ChUnit, Position, SelectionRange, UnicodeString};
+use serde::{Deserialize, Serialize};
+
+use super::{EditorBuffer, SelectionMap};
+use crate::{get_front_matter_line_count,
+            parse_markdown,
+            MdBlock,
+            DEFAULT_SYN_HI_FILE_EXT};
+
+/// A contiguous selection from `start` (inclusive) to `end` (exclusive). Both positions
+/// are scroll adjusted display positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, size_of::SizeOf)]
+pub struct SelectionSpan {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Records a single expansion, so that it can be undone by [shrink_selection].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, size_of::SizeOf)]
+pub struct SelectionExpansion {
+    pub maybe_span_before: Option<SelectionSpan>,
+    pub span_after: SelectionSpan,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExpandSelectionBy {
+    Word,
+    Line,
+    Block,
+}
+
+/// Grow the selection. The first expansion starts from the caret. Returns the new span,
+/// or [None] if the selection can't grow any further (eg: at the end of the document).
+pub fn expand_selection(
+    buffer: &mut EditorBuffer,
+    by: ExpandSelectionBy,
+) -> Option<SelectionSpan> {
+    if buffer.is_empty() {
+        return None;
+    }
+
+    let caret = buffer.get_caret(super::CaretKind::ScrollAdjusted);
+    let maybe_span_before = get_selection_span(buffer.get_selection_map());
+    let lines = buffer.get_lines();
+
+    let span_after = match by {
+        ExpandSelectionBy::Word => expand_by_word(lines, caret, maybe_span_before),
+        ExpandSelectionBy::Line => expand_by_line(lines, caret, maybe_span_before),
+        ExpandSelectionBy::Block => {
+            let is_markdown =
+                buffer.get_maybe_file_extension() == Some(DEFAULT_SYN_HI_FILE_EXT);
+            expand_by_block(lines, caret, maybe_span_before, is_markdown)
+        }
+    }?;
+
+    if Some(span_after) == maybe_span_before {
+        return None;
+    }
+
+    let (_, _, _, selection_map) = buffer.get_mut();
+    let mut history = std::mem::take(&mut selection_map.expansion_history);
+    // The selection was changed by something other than an expansion, so the history
+    // no longer applies.
+    if history.last().map(|it| Some(it.span_after)) != Some(maybe_span_before) {
+        history.clear();
+    }
+    history.push(SelectionExpansion {
+        maybe_span_before,
+        span_after,
+    });
+    set_selection_span(buffer, Some(span_after));
+    buffer.get_mut().3.expansion_history = history;
+
+    Some(span_after)
+}
+
+/// Undo the last expansion, restoring the previous selection. Returns the restored
+/// selection, or [None] if there is nothing to shrink.
+pub fn shrink_selection(buffer: &mut EditorBuffer) -> Option<Option<SelectionSpan>> {
+    let maybe_current_span = get_selection_span(buffer.get_selection_map());
+    let (_, _, _, selection_map) = buffer.get_mut();
+    let mut history = std::mem::take(&mut selection_map.expansion_history);
+
+    let last = *history.last()?;
+    if Some(last.span_after) != maybe_current_span {
+        // The selection was changed by something other than an expansion.
+        return None;
+    }
+    history.pop();
+
+    set_selection_span(buffer, last.maybe_span_before);
+    buffer.get_mut().3.expansion_history = history;
+
+    Some(last.maybe_span_before)
+}
+
+/// Returns the selection in the [SelectionMap] as a contiguous span.
+pub fn get_selection_span(selection_map: &SelectionMap) -> Option<SelectionSpan> {
+    let row_indices = selection_map.get_ordered_indices();
+    let first_row_index = *row_indices.first()?;
+    let last_row_index = *row_indices.last()?;
+    Some(SelectionSpan {
+        start: position!(
+            col_index: selection_map.get(first_row_index)?.start_display_col_index,
+            row_index: first_row_index
+        ),
+        end: position!(
+            col_index: selection_map.get(last_row_index)?.end_display_col_index,
+            row_index: last_row_index
+        ),
+    })
+}
+
+/// Replace the selection w/ the given span. This clears the
+/// [SelectionMap::expansion_history].
+fn set_selection_span(buffer: &mut EditorBuffer, maybe_span: Option<SelectionSpan>) {
+    let line_widths: Vec<ChUnit> = buffer
+        .get_lines()
+        .iter()
+        .map(|line| line.display_width)
+        .collect();
+
+    let (_, _, _, selection_map) = buffer.get_mut();
+    selection_map.clear();
+
+    let Some(SelectionSpan { start, end }) = maybe_span else {
+        return;
+    };
+
+    for row_index in ch!(@to_usize start.row_index)..=ch!(@to_usize end.row_index) {
+        let line_width = line_widths.get(row_index).copied().unwrap_or_default();
+        let start_display_col_index = if ch!(row_index) == start.row_index {
+            start.col_index
+        } else {
+            ch!(0)
+        };
+        let end_display_col_index = if ch!(row_index) == end.row_index {
+            end.col_index
+        } else {
+            line_width
+        };
+        selection_map.map.insert(
+            ch!(row_index),
+            SelectionRange {
+                start_display_col_index,
+                end_display_col_index,
+            },
+        );
+    }
+}
+
+fn is_before_or_at(lhs: Position, rhs: Position) -> bool {
+    (lhs.row_index, lhs.col_index) <= (rhs.row_index, rhs.col_index)
+}
+
+fn contains(outer: SelectionSpan, inner: SelectionSpan) -> bool {
+    is_before_or_at(outer.start, inner.start) && is_before_or_at(inner.end, outer.end)
+}
+
+mod word {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum CharClass {
+        Word,
+        Space,
+        Punctuation,
+    }
+
+    pub fn classify(grapheme: &str) -> CharClass {
+        match grapheme.chars().next() {
+            Some(it) if it.is_alphanumeric() || it == '_' => CharClass::Word,
+            Some(it) if it.is_whitespace() => CharClass::Space,
+            _ => CharClass::Punctuation,
+        }
+    }
+
+    /// Returns the display col range of the run of graphemes (of the same class) that
+    /// the caret is on. If the caret is just after a word (eg: at the end of the line, or
+    /// on a space) then that word is used.
+    pub fn get_run_at(
+        line: &UnicodeString,
+        col_index: ChUnit,
+    ) -> Option<(ChUnit, ChUnit)> {
+        let segments = &line.vec_segment;
+        if segments.is_empty() {
+            return None;
+        }
+
+        let maybe_index_at_caret = segments.iter().position(|seg| {
+            seg.display_col_offset <= col_index
+                && col_index < seg.display_col_offset + seg.unicode_width
+        });
+
+        let index = match maybe_index_at_caret {
+            Some(index)
+                if classify(&segments[index].string) == CharClass::Space
+                    && index > 0
+                    && classify(&segments[index - 1].string) == CharClass::Word =>
+            {
+                index - 1
+            }
+            Some(index) => index,
+            None => segments.len() - 1,
+        };
+
+        let class = classify(&segments[index].string);
+        let mut start = index;
+        while start > 0 && classify(&segments[start - 1].string) == class {
+            start -= 1;
+        }
+        let mut end = index;
+        while end + 1 < segments.len() && classify(&segments[end + 1].string) == class {
+            end += 1;
+        }
+
+        Some((
+            segments[start].display_col_offset,
+            segments[end].display_col_offset + segments[end].unicode_width,
+        ))
+    }
+
+    /// Returns the position just after the next word (or run of punctuation) that is at
+    /// or after `from`, skipping over spaces and line breaks.
+    pub fn get_end_of_next_run(
+        lines: &[UnicodeString],
+        from: Position,
+    ) -> Option<Position> {
+        let mut row_index = ch!(@to_usize from.row_index);
+        let mut col_index = from.col_index;
+
+        loop {
+            let line = lines.get(row_index)?;
+            let maybe_next_non_space = line.vec_segment.iter().find(|seg| {
+                seg.display_col_offset >= col_index
+                    && classify(&seg.string) != CharClass::Space
+            });
+            if let Some(seg) = maybe_next_non_space {
+                let (_, end) = get_run_at(line, seg.display_col_offset)?;
+                return Some(position!(col_index: end, row_index: ch!(row_index)));
+            }
+            // Nothing left on this line, so continue w/ the next one.
+            row_index += 1;
+            col_index = ch!(0);
+        }
+    }
+}
+
+fn expand_by_word(
+    lines: &[UnicodeString],
+    caret: Position,
+    maybe_span: Option<SelectionSpan>,
+) -> Option<SelectionSpan> {
+    match maybe_span {
+        None => {
+            let line = lines.get(ch!(@to_usize caret.row_index))?;
+            let (start, end) = word::get_run_at(line, caret.col_index)?;
+            Some(SelectionSpan {
+                start: position!(col_index: start, row_index: caret.row_index),
+                end: position!(col_index: end, row_index: caret.row_index),
+            })
+        }
+        Some(span) => {
+            let end = word::get_end_of_next_run(lines, span.end)?;
+            Some(SelectionSpan { end, ..span })
+        }
+    }
+}
+
+fn get_full_lines_span(
+    lines: &[UnicodeString],
+    start_row_index: usize,
+    end_row_index: usize,
+) -> SelectionSpan {
+    let end_line_width = lines
+        .get(end_row_index)
+        .map(|line| line.display_width)
+        .unwrap_or_default();
+    SelectionSpan {
+        start: position!(col_index: 0, row_index: ch!(start_row_index)),
+        end: position!(col_index: end_line_width, row_index: ch!(end_row_index)),
+    }
+}
+
+fn get_row_range(caret: Position, maybe_span: Option<SelectionSpan>) -> (usize, usize) {
+    match maybe_span {
+        Some(span) => (
+            ch!(@to_usize span.start.row_index),
+            ch!(@to_usize span.end.row_index),
+        ),
+        None => (
+            ch!(@to_usize caret.row_index),
+            ch!(@to_usize caret.row_index),
+        ),
+    }
+}
+
+/// The first expansion selects the lines that the selection (or caret) is on. Then each
+/// expansion adds the line below, or the line above once the end of the document is
+/// reached.
+fn expand_by_line(
+    lines: &[UnicodeString],
+    caret: Position,
+    maybe_span: Option<SelectionSpan>,
+) -> Option<SelectionSpan> {
+    let (start_row_index, end_row_index) = get_row_range(caret, maybe_span);
+    let full_lines_span = get_full_lines_span(lines, start_row_index, end_row_index);
+
+    if maybe_span != Some(full_lines_span) {
+        return Some(full_lines_span);
+    }
+
+    if end_row_index + 1 < lines.len() {
+        Some(get_full_lines_span(
+            lines,
+            start_row_index,
+            end_row_index + 1,
+        ))
+    } else if start_row_index > 0 {
+        Some(get_full_lines_span(
+            lines,
+            start_row_index - 1,
+            end_row_index,
+        ))
+    } else {
+        None
+    }
+}
+
+mod block {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum BlockKind {
+        /// A list item (w/ its indent).
+        ListItem(usize),
+        CodeBlock,
+        Text,
+        Empty,
+        Other,
+    }
+
+    /// A block of lines (both row indices are inclusive).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct BlockRows {
+        pub kind: BlockKind,
+        pub start_row_index: usize,
+        pub end_row_index: usize,
+    }
+
+    /// Parse the lines as Markdown, and return the rows that each block spans. Returns
+    /// [None] if the lines of the parsed blocks don't match up w/ the given lines.
+    pub fn get_md_block_rows(lines: &[UnicodeString]) -> Option<Vec<BlockRows>> {
+        let mut input = lines
+            .iter()
+            .map(|line| line.string.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n");
+        input.push('\n');
+
+        let (remainder, document) = parse_markdown(&input).ok()?;
+        if !remainder.is_empty() {
+            return None;
+        }
+
+        let mut acc = vec![];
+        let mut row_index = 0;
+        for md_block in document.iter() {
+            let (kind, line_count) = match md_block {
+                MdBlock::SmartList((list_lines, _, indent)) => {
+                    (BlockKind::ListItem(*indent), list_lines.len())
+                }
+                MdBlock::CodeBlock(code_block_lines) => {
+                    (BlockKind::CodeBlock, code_block_lines.len())
+                }
+                MdBlock::Text(fragments) if fragments.is_empty() => (BlockKind::Empty, 1),
+                MdBlock::Text(_) => (BlockKind::Text, 1),
+                MdBlock::FrontMatter(content) => {
+                    (BlockKind::Other, get_front_matter_line_count(content))
+                }
+                MdBlock::Table { raw_lines, .. }
+                | MdBlock::BlockQuote { raw_lines, .. } => {
+                    (BlockKind::Other, raw_lines.len())
+                }
+                _ => (BlockKind::Other, 1),
+            };
+            if line_count == 0 {
+                return None;
+            }
+            acc.push(BlockRows {
+                kind,
+                start_row_index: row_index,
+                end_row_index: row_index + line_count - 1,
+            });
+            row_index += line_count;
+        }
+
+        if row_index != lines.len() {
+            return None;
+        }
+
+        Some(acc)
+    }
+
+    /// Treat each line as a block, which is either empty or text.
+    pub fn get_plain_text_block_rows(lines: &[UnicodeString]) -> Vec<BlockRows> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(row_index, line)| BlockRows {
+                kind: if line.string.trim().is_empty() {
+                    BlockKind::Empty
+                } else {
+                    BlockKind::Text
+                },
+                start_row_index: row_index,
+                end_row_index: row_index,
+            })
+            .collect()
+    }
+
+    fn is_same_group(lhs: BlockKind, rhs: BlockKind) -> bool {
+        matches!(
+            (lhs, rhs),
+            (BlockKind::ListItem(_), BlockKind::ListItem(_))
+                | (BlockKind::Text, BlockKind::Text)
+        )
+    }
+
+    /// Returns the ranges of blocks (as indices into `blocks`) that contain the given
+    /// rows, from the smallest to the largest:
+    /// 1. The blocks that the rows are in.
+    /// 2. If the first block is a list item, then it is extended to include its nested
+    ///    items.
+    /// 3. The surrounding group of blocks (eg: the whole list, or the paragraph).
+    pub fn get_candidates(
+        blocks: &[BlockRows],
+        start_row_index: usize,
+        end_row_index: usize,
+    ) -> Vec<(usize, usize)> {
+        let find = |row_index: usize| {
+            blocks.iter().position(|block| {
+                block.start_row_index <= row_index && row_index <= block.end_row_index
+            })
+        };
+        let (Some(first), Some(last)) = (find(start_row_index), find(end_row_index))
+        else {
+            return vec![];
+        };
+
+        let mut acc = vec![(first, last)];
+
+        if let BlockKind::ListItem(indent) = blocks[first].kind {
+            let mut end = last;
+            while let Some(BlockKind::ListItem(next_indent)) =
+                blocks.get(end + 1).map(|it| it.kind)
+            {
+                if next_indent <= indent {
+                    break;
+                }
+                end += 1;
+            }
+            acc.push((first, end));
+        }
+
+        let (mut start, mut end) = *acc.last().unwrap_or(&(first, last));
+        while start > 0 && is_same_group(blocks[start - 1].kind, blocks[start].kind) {
+            start -= 1;
+        }
+        while end + 1 < blocks.len()
+            && is_same_group(blocks[end].kind, blocks[end + 1].kind)
+        {
+            end += 1;
+        }
+        acc.push((start, end));
+
+        acc
+    }
+}
+
+/// Expand to the smallest block (from [block::get_candidates]) that contains the
+/// selection (or caret), and finally to the whole document.
+fn expand_by_block(
+    lines: &[UnicodeString],
+    caret: Position,
+    maybe_span: Option<SelectionSpan>,
+    is_markdown: bool,
+) -> Option<SelectionSpan> {
+    let blocks = match is_markdown {
+        true => block::get_md_block_rows(lines)
+            .unwrap_or_else(|| block::get_plain_text_block_rows(lines)),
+        false => block::get_plain_text_block_rows(lines),
+    };
+
+    let (start_row_index, end_row_index) = get_row_range(caret, maybe_span);
+
+    let mut candidates: Vec<SelectionSpan> =
+        block::get_candidates(&blocks, start_row_index, end_row_index)
+            .into_iter()
+            .map(|(first, last)| {
+                get_full_lines_span(
+                    lines,
+                    blocks[first].start_row_index,
+                    blocks[last].end_row_index,
+                )
+            })
+            .collect();
+    candidates.push(get_full_lines_span(lines, 0, lines.len() - 1));
+
+    candidates.into_iter().find(|candidate| match maybe_span {
+        Some(span) => *candidate != span && contains(*candidate, span),
+        None => true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn make_buffer(lines: &[&str]) -> EditorBuffer {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+        buffer
+    }
+
+    fn set_caret(buffer: &mut EditorBuffer, caret_position: Position) {
+        let (_, caret, _, _) = buffer.get_mut();
+        *caret = caret_position;
+    }
+
+    fn span(start: (usize, usize), end: (usize, usize)) -> SelectionSpan {
+        SelectionSpan {
+            start: position!(col_index: ch!(start.1), row_index: ch!(start.0)),
+            end: position!(col_index: ch!(end.1), row_index: ch!(end.0)),
+        }
+    }
+
+    fn selected_text(buffer: &EditorBuffer) -> Vec<String> {
+        let selection_map = buffer.get_selection_map();
+        selection_map
+            .get_ordered_indices()
+            .into_iter()
+            .map(|row_index| {
+                let range = selection_map.get(row_index).unwrap();
+                buffer.get_lines()[ch!(@to_usize row_index)]
+                    .clip_to_range(*range)
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_by_word_and_shrink() {
+        let mut buffer = make_buffer(&["let foo_bar = baz(1);", "qux"]);
+        set_caret(&mut buffer, position!(col_index: 6, row_index: 0));
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Word);
+        assert_eq2!(selected_text(&buffer), vec!["foo_bar"]);
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Word);
+        assert_eq2!(selected_text(&buffer), vec!["foo_bar ="]);
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Word);
+        assert_eq2!(selected_text(&buffer), vec!["foo_bar = baz"]);
+
+        // Shrinking restores the previous selections, in reverse order.
+        shrink_selection(&mut buffer);
+        assert_eq2!(selected_text(&buffer), vec!["foo_bar ="]);
+        shrink_selection(&mut buffer);
+        assert_eq2!(selected_text(&buffer), vec!["foo_bar"]);
+        assert_eq2!(shrink_selection(&mut buffer), Some(None));
+        assert!(buffer.get_selection_map().is_empty());
+        assert_eq2!(shrink_selection(&mut buffer), None);
+    }
+
+    #[test]
+    fn test_expand_by_word_across_lines_and_at_end_of_document() {
+        let mut buffer = make_buffer(&["abc", "  def"]);
+        set_caret(&mut buffer, position!(col_index: 3, row_index: 0));
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Word);
+        assert_eq2!(selected_text(&buffer), vec!["abc"]);
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Word);
+        assert_eq2!(selected_text(&buffer), vec!["abc", "  def"]);
+
+        // Can't grow past the end of the document.
+        assert_eq2!(expand_selection(&mut buffer, ExpandSelectionBy::Word), None);
+        assert_eq2!(
+            get_selection_span(buffer.get_selection_map()),
+            Some(span((0, 0), (1, 5)))
+        );
+    }
+
+    #[test]
+    fn test_expand_by_line() {
+        let mut buffer = make_buffer(&["one", "two", "three"]);
+        set_caret(&mut buffer, position!(col_index: 1, row_index: 1));
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Line);
+        assert_eq2!(selected_text(&buffer), vec!["two"]);
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Line);
+        assert_eq2!(selected_text(&buffer), vec!["two", "three"]);
+
+        // At the end of the document, the line above is added.
+        expand_selection(&mut buffer, ExpandSelectionBy::Line);
+        assert_eq2!(selected_text(&buffer), vec!["one", "two", "three"]);
+
+        assert_eq2!(expand_selection(&mut buffer, ExpandSelectionBy::Line), None);
+    }
+
+    #[test]
+    fn test_expand_by_block_md_list() {
+        let mut buffer = make_buffer(&[
+            "# Heading",
+            "- item 1",
+            "  - nested a",
+            "  - nested b",
+            "- item 2",
+            "",
+            "```rust",
+            "let a = 1;",
+            "```",
+        ]);
+
+        // Caret on "item 1".
+        set_caret(&mut buffer, position!(col_index: 3, row_index: 1));
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Block);
+        assert_eq2!(
+            get_selection_span(buffer.get_selection_map()),
+            Some(span((1, 0), (1, 8)))
+        );
+
+        // The list item w/ its nested items.
+        expand_selection(&mut buffer, ExpandSelectionBy::Block);
+        assert_eq2!(
+            get_selection_span(buffer.get_selection_map()),
+            Some(span((1, 0), (3, 12)))
+        );
+
+        // The whole list.
+        expand_selection(&mut buffer, ExpandSelectionBy::Block);
+        assert_eq2!(
+            get_selection_span(buffer.get_selection_map()),
+            Some(span((1, 0), (4, 8)))
+        );
+
+        // The whole document.
+        expand_selection(&mut buffer, ExpandSelectionBy::Block);
+        assert_eq2!(
+            get_selection_span(buffer.get_selection_map()),
+            Some(span((0, 0), (8, 3)))
+        );
+
+        assert_eq2!(
+            expand_selection(&mut buffer, ExpandSelectionBy::Block),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expand_by_block_md_code_block() {
+        let mut buffer = make_buffer(&["text", "", "```rust", "let a = 1;", "```"]);
+        set_caret(&mut buffer, position!(col_index: 2, row_index: 3));
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Block);
+        assert_eq2!(selected_text(&buffer), vec!["```rust", "let a = 1;", "```"]);
+    }
+
+    #[test]
+    fn test_expand_by_block_plain_text_paragraph() {
+        let mut buffer = EditorBuffer::new_empty(&Some("txt".to_owned()), &None);
+        buffer.set_lines(
+            ["a", "b", "", "c", "d", "e"]
+                .iter()
+                .map(|it| it.to_string())
+                .collect(),
+        );
+        set_caret(&mut buffer, position!(col_index: 0, row_index: 4));
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Block);
+        assert_eq2!(selected_text(&buffer), vec!["d"]);
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Block);
+        assert_eq2!(selected_text(&buffer), vec!["c", "d", "e"]);
+    }
+
+    #[test]
+    fn test_history_is_discarded_when_selection_changes() {
+        let mut buffer = make_buffer(&["abc def"]);
+        set_caret(&mut buffer, position!(col_index: 0, row_index: 0));
+
+        expand_selection(&mut buffer, ExpandSelectionBy::Word);
+        assert_eq2!(selected_text(&buffer), vec!["abc"]);
+
+        // Some other change to the selection.
+        buffer.get_mut().3.map.insert(
+            ch!(0),
+            SelectionRange {
+                start_display_col_index: ch!(0),
+                end_display_col_index: ch!(2),
+            },
+        );
+
+        assert_eq2!(shrink_selection(&mut buffer), None);
+        assert_eq2!(selected_text(&buffer), vec!["ab"]);
+    }
+}
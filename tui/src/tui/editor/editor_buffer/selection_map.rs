@@ -22,7 +22,7 @@ use crossterm::style::{StyledContent, Stylize};
 use r3bl_core::{ch, position, CaretMovementDirection, ChUnit, Position, SelectionRange};
 use serde::{Deserialize, Serialize};
 
-use crate::{DeleteSelectionWith, EditorBuffer};
+use crate::{DeleteSelectionWith, EditorBuffer, SelectionExpansion};
 
 /// Key is the row index, value is the selected range in that line (display col index
 /// range).
@@ -34,10 +34,30 @@ use crate::{DeleteSelectionWith, EditorBuffer};
 pub struct SelectionMap {
     pub map: HashMap<RowIndex, SelectionRange>,
     pub maybe_previous_direction: Option<CaretMovementDirection>,
+    /// Stack of selection expansions, so that they can be shrunk back. More info in
+    /// [crate::expand_selection].
+    pub expansion_history: Vec<SelectionExpansion>,
+    /// Whether this is a stream or a block selection. More info in
+    /// [crate::block_selection].
+    pub kind: SelectionKind,
 }
 
 pub type RowIndex = ChUnit;
 
+/// The shape of the selection in the [SelectionMap].
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, size_of::SizeOf,
+)]
+pub enum SelectionKind {
+    /// The selection flows from its start to its end, across lines, like text does.
+    #[default]
+    Stream,
+    /// The same range of display cols is selected in each of the rows between the
+    /// `anchor` (where the selection started) and the `head` (where it was extended to).
+    /// Both are scroll adjusted, and the `head` can be past the end of its line.
+    Block { anchor: Position, head: Position },
+}
+
 #[test]
 fn test_selection_map_direction_change() {
     use r3bl_core::{assert_eq2, CaretMovementDirection};
@@ -145,8 +165,12 @@ impl SelectionMap {
     pub fn clear(&mut self) {
         self.map.clear();
         self.maybe_previous_direction = None;
+        self.expansion_history.clear();
+        self.kind = SelectionKind::Stream;
     }
 
+    pub fn is_block(&self) -> bool { matches!(self.kind, SelectionKind::Block { .. }) }
+
     pub fn iter(&self) -> impl Iterator<Item = (&RowIndex, &SelectionRange)> {
         self.map.iter()
     }
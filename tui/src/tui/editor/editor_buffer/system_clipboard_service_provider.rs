@@ -15,13 +15,21 @@
  *   limitations under the License.
  */
 
+use std::sync::Mutex;
+
+#[cfg(feature = "system-clipboard")]
 use copypasta_ext::{copypasta::ClipboardProvider, x11_fork::ClipboardContext};
 use crossterm::style::Stylize;
-use r3bl_core::{call_if_true, throws};
+use r3bl_core::call_if_true;
 
 use super::{ClipboardResult, ClipboardService};
 use crate::DEBUG_TUI_COPY_PASTE;
 
+/// Uses the OS clipboard when the `system-clipboard` feature is enabled (it is by
+/// default). Content that is copied is also kept in the [InMemoryClipboard], which is
+/// used to paste from when the OS clipboard isn't available (eg: in a headless
+/// environment, or w/out the feature). This way copy, cut, and paste always work
+/// within the app.
 pub struct SystemClipboard;
 
 impl ClipboardService for SystemClipboard {
@@ -29,24 +37,78 @@ impl ClipboardService for SystemClipboard {
         &mut self,
         content: String,
     ) -> ClipboardResult<()> {
-        throws!({
-            let mut ctx = ClipboardContext::new()?;
-            ctx.set_contents(content.clone())?;
+        InMemoryClipboard.try_to_put_content_into_clipboard(content.clone())?;
 
+        #[cfg(feature = "system-clipboard")]
+        if let Err(error) = try_to_put_content_into_os_clipboard(&content) {
             call_if_true!(DEBUG_TUI_COPY_PASTE, {
                 tracing::debug!(
-                    "\n📋📋📋 Selected Text was copied to clipboard: \n{}",
-                    content.to_string().black().on_green(),
+                    "\n📋📋📋 OS clipboard isn't available, using the in memory one: {}",
+                    format!("{error}").white().on_dark_red(),
                 );
             });
-        })
+        }
+
+        call_if_true!(DEBUG_TUI_COPY_PASTE, {
+            tracing::debug!(
+                "\n📋📋📋 Selected Text was copied to clipboard: \n{}",
+                content.to_string().black().on_green(),
+            );
+        });
+
+        Ok(())
     }
 
     fn try_to_get_content_from_clipboard(&mut self) -> ClipboardResult<String> {
-        let mut ctx = ClipboardContext::new()?;
-        let content = ctx.get_contents()?;
+        #[cfg(feature = "system-clipboard")]
+        if let Ok(content) = try_to_get_content_from_os_clipboard() {
+            return Ok(content);
+        }
 
-        Ok(content)
+        InMemoryClipboard.try_to_get_content_from_clipboard()
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+fn try_to_put_content_into_os_clipboard(content: &str) -> ClipboardResult<()> {
+    let mut ctx = ClipboardContext::new()?;
+    ctx.set_contents(content.to_string())?;
+    Ok(())
+}
+
+#[cfg(feature = "system-clipboard")]
+fn try_to_get_content_from_os_clipboard() -> ClipboardResult<String> {
+    let mut ctx = ClipboardContext::new()?;
+    let content = ctx.get_contents()?;
+    Ok(content)
+}
+
+/// The content of the [InMemoryClipboard], which is shared by the whole process (like
+/// the OS clipboard is).
+static IN_MEMORY_CLIPBOARD_CONTENT: Mutex<String> = Mutex::new(String::new());
+
+/// A clipboard that only lives in the memory of this process. It is shared by all its
+/// instances, so content that is copied from one editor can be pasted into another.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InMemoryClipboard;
+
+impl ClipboardService for InMemoryClipboard {
+    fn try_to_put_content_into_clipboard(
+        &mut self,
+        content: String,
+    ) -> ClipboardResult<()> {
+        let mut it = IN_MEMORY_CLIPBOARD_CONTENT
+            .lock()
+            .map_err(|error| error.to_string())?;
+        *it = content;
+        Ok(())
+    }
+
+    fn try_to_get_content_from_clipboard(&mut self) -> ClipboardResult<String> {
+        let it = IN_MEMORY_CLIPBOARD_CONTENT
+            .lock()
+            .map_err(|error| error.to_string())?;
+        Ok(it.clone())
     }
 }
 
@@ -56,6 +118,7 @@ pub mod test_fixtures {
     #[derive(Debug, Default)]
     pub struct TestClipboard {
         pub content: String,
+        pub html_content: Option<String>,
     }
 
     impl ClipboardService for TestClipboard {
@@ -64,6 +127,17 @@ pub mod test_fixtures {
             content: String,
         ) -> ClipboardResult<()> {
             self.content = content;
+            self.html_content = None;
+            Ok(())
+        }
+
+        fn try_to_put_rich_content_into_clipboard(
+            &mut self,
+            plain_text: String,
+            html: String,
+        ) -> ClipboardResult<()> {
+            self.content = plain_text;
+            self.html_content = Some(html);
             Ok(())
         }
 
@@ -72,3 +146,42 @@ pub mod test_fixtures {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+    use serial_test::serial;
+
+    use super::*;
+
+    #[serial]
+    #[test]
+    fn test_in_memory_clipboard_is_shared() {
+        InMemoryClipboard
+            .try_to_put_content_into_clipboard("abc\ndef".into())
+            .unwrap();
+        assert_eq2!(
+            InMemoryClipboard
+                .try_to_get_content_from_clipboard()
+                .unwrap(),
+            "abc\ndef"
+        );
+    }
+
+    #[serial]
+    #[test]
+    fn test_in_memory_clipboard_only_keeps_plain_text_of_rich_content() {
+        InMemoryClipboard
+            .try_to_put_rich_content_into_clipboard(
+                "*a*".into(),
+                "<p><em>a</em></p>".into(),
+            )
+            .unwrap();
+        assert_eq2!(
+            InMemoryClipboard
+                .try_to_get_content_from_clipboard()
+                .unwrap(),
+            "*a*"
+        );
+    }
+}
@@ -27,6 +27,7 @@ use crate::{BoxedSafeComponent,
             EditorEngineApi,
             EditorEngineApplyEventResult,
             EditorEngineConfig,
+            EventConsumption,
             EventPropagation,
             FlexBox,
             FlexBoxId,
@@ -151,7 +152,11 @@ pub mod editor_component_impl_component_trait {
             _: &mut HasFocus,
         ) -> CommonResult<EventPropagation> {
             throws_with_return!({
-                let GlobalData { state, .. } = global_data;
+                let GlobalData {
+                    state,
+                    input_event_repeat_count,
+                    ..
+                } = global_data;
 
                 let EditorComponentData {
                     editor_engine,
@@ -169,13 +174,19 @@ pub mod editor_component_impl_component_trait {
 
                 // BOOKM: Editor component processes input event here
                 // Try to apply the `input_event` to `editor_engine` to decide whether to
-                // fire action.
-                let result = EditorEngineApi::apply_event(
-                    mut_editor_buffer,
-                    editor_engine,
-                    input_event,
-                    &mut SystemClipboard,
-                )?;
+                // fire action. A coalesced key repeat is applied once for each repeat.
+                let mut result = EditorEngineApplyEventResult::NotApplied;
+                for _ in 0..(*input_event_repeat_count).max(1) {
+                    result = EditorEngineApi::apply_event(
+                        mut_editor_buffer,
+                        editor_engine,
+                        input_event.clone(),
+                        &mut SystemClipboard,
+                    )?;
+                    if let EditorEngineApplyEventResult::NotApplied = result {
+                        break;
+                    }
+                }
 
                 match result {
                     EditorEngineApplyEventResult::Applied => {
@@ -194,6 +205,40 @@ pub mod editor_component_impl_component_trait {
                 }
             });
         }
+
+        fn would_consume_event(
+            &self,
+            global_data: &GlobalData<S, AS>,
+            input_event: InputEvent,
+            _has_focus: &HasFocus,
+        ) -> EventConsumption {
+            let editor_engine = &self.data.editor_engine;
+
+            // Mirror what [Component::handle_event] does w/ the buffer in the state. If
+            // the state doesn't provide it, it is assumed not to be searched.
+            let maybe_editor_event = match global_data
+                .state
+                .get_editor_buffer(self.data.id)
+            {
+                Some(editor_buffer) => EditorEngineApi::would_apply_event_to_buffer(
+                    editor_engine,
+                    editor_buffer,
+                    &input_event,
+                ),
+                None => EditorEngineApi::would_apply_event(editor_engine, input_event),
+            };
+
+            match maybe_editor_event {
+                Some(editor_event) => {
+                    EventConsumption::Consumed(Some(format!("{editor_event:?}")))
+                }
+                None => EventConsumption::Propagate,
+            }
+        }
+
+        /// The event is applied to the [EditorEngine] once for each repeat in
+        /// [Component::handle_event].
+        fn supports_input_event_repeat_count(&self) -> bool { true }
     }
 }
 
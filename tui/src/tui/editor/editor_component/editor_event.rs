@@ -18,34 +18,58 @@
 use std::fmt::Debug;
 
 use crossterm::style::Stylize;
-use r3bl_core::{call_if_true, Size};
+use r3bl_core::{call_if_true, ch, ChUnit, Position, Size};
 use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{editor_buffer::EditorBuffer,
+use crate::{apply_find_action,
+            editor_buffer::EditorBuffer,
             editor_buffer_clipboard_support::ClipboardService,
+            extend_block_selection,
+            extend_block_selection_to,
+            get_buffer_position,
+            get_task_checkbox,
             history,
+            multi_caret,
+            start_block_selection_at,
+            CaretKind,
             DeleteSelectionWith,
             EditorArgsMut,
             EditorEngine,
             EditorEngineInternalApi,
+            ExpandSelectionBy,
+            FindField,
+            FindState,
             InputEvent,
             Key,
             KeyPress,
             KeyState,
+            LineMode,
             ModifierKeysMask,
+            MouseInput,
+            MouseInputKind,
             SelectMode,
             SpecialKey,
+            TaskCheckbox,
+            TypingMode,
             DEBUG_TUI_COPY_PASTE};
 
+/// How many rows the viewport is scrolled by each turn of the mouse wheel.
+pub const MOUSE_WHEEL_SCROLL_ROW_COUNT: usize = 3;
+
 /// Events that can be applied to the [EditorEngine] to modify an [EditorBuffer].
 ///
 /// By providing a conversion from [InputEvent] to [EditorEvent] it becomes easier to write event
 /// handlers that consume [InputEvent] and then execute [EditorEvent] on an [EditorBuffer].
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EditorEvent {
     InsertChar(char),
     InsertString(String),
     InsertNewLine,
+    /// Insert one level of indentation at the caret, using the [crate::IndentStyle] of
+    /// the [EditorBuffer] (or [crate::EditorEngineConfig::indent_style]). This does
+    /// nothing in a [LineMode::SingleLine] editor, and `Tab` isn't mapped to it there.
+    InsertIndent,
     Delete,
     Backspace,
     Home,
@@ -53,16 +77,66 @@ pub enum EditorEvent {
     PageDown,
     PageUp,
     MoveCaret(CaretDirection),
+    /// Move the caret to the given row (clipped to the last row), scrolling it into view.
+    MoveCaretToRow(ChUnit),
+    /// Scroll the viewport up by the given number of rows (eg: when the mouse wheel is
+    /// turned), w/out moving the caret off of its row in the viewport.
+    ScrollUp(ChUnit),
+    /// Scroll the viewport down by the given number of rows, see [EditorEvent::ScrollUp].
+    ScrollDown(ChUnit),
     Resize(Size),
     Select(SelectionAction),
     Copy,
     Paste,
+    /// Insert text that was pasted into the terminal (see [InputEvent::Paste]) as a
+    /// single undo step. Unlike typing it, it is never overwritten in
+    /// [crate::TypingMode::Overwrite], and its new lines are inserted as is.
+    PasteText(String),
     Cut,
     Undo,
     Redo,
+    /// Switch between [crate::TypingMode::Insert] and [crate::TypingMode::Overwrite].
+    ToggleTypingMode,
+    /// Add a caret in the line above the first caret (see [crate::MultiCaretMode]).
+    AddCaretAbove,
+    /// Add a caret in the line below the last caret (see [crate::MultiCaretMode]).
+    AddCaretBelow,
+    /// Toggle the checkbox of the task list item on the caret's line (see
+    /// [crate::CheckboxToggleMode]).
+    ToggleCheckbox,
+    /// Toggle the checkbox at the given position, which is relative to the top left
+    /// corner of the viewport (eg: where the mouse was clicked). See
+    /// [crate::CheckboxToggleMode].
+    ToggleCheckboxAt(Position),
+    /// Find (and replace) text in the buffer, see [crate::find_replace].
+    Find(FindAction),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FindAction {
+    /// Start finding, or move the focus to the query if already finding.
+    Start,
+    /// Start replacing, or move the focus to the replacement if already replacing.
+    StartReplace,
+    /// Type the given text into the field that has focus.
+    InsertText(String),
+    /// Delete the last grapheme cluster of the field that has focus.
+    DeleteChar,
+    /// Move the caret to the next match, wrapping around at the end of the buffer.
+    Next,
+    /// Move the caret to the previous match, wrapping around at the start of the buffer.
+    Previous,
+    /// Move the focus between the query and the replacement.
+    ToggleField,
+    /// Replace the current match, and move the caret to the next one.
+    ReplaceCurrent,
+    /// Replace all the matches.
+    ReplaceAll,
+    /// Stop finding.
+    Exit,
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SelectionAction {
     OneCharLeft,
     OneCharRight,
@@ -74,9 +148,38 @@ pub enum SelectionAction {
     End,
     All,
     Esc,
+    /// Grow the selection (starting from the caret) to the next word boundary.
+    ExpandByWord,
+    /// Grow the selection to whole lines, then one line at a time.
+    ExpandByLine,
+    /// Grow the selection to the enclosing block, eg: list item, code block, paragraph.
+    ExpandByBlock,
+    /// Undo the last expansion.
+    Shrink,
+    /// Grow or shrink the block selection (see [crate::block_selection]) by one row or
+    /// col, starting from the caret.
+    Block(CaretDirection),
+    /// Start a new block selection at the given position, which is relative to the top
+    /// left corner of the viewport (eg: where the mouse was clicked).
+    BlockStartAt(Position),
+    /// Extend the block selection to the given position, which is relative to the top
+    /// left corner of the viewport (eg: where the mouse was dragged to).
+    BlockExtendTo(Position),
+}
+
+impl SelectionAction {
+    /// Returns true if this action selects a block, instead of a stream of text.
+    pub fn is_block(&self) -> bool {
+        matches!(
+            self,
+            SelectionAction::Block(_)
+                | SelectionAction::BlockStartAt(_)
+                | SelectionAction::BlockExtendTo(_)
+        )
+    }
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CaretDirection {
     Up,
     Down,
@@ -117,6 +220,37 @@ impl TryFrom<InputEvent> for EditorEvent {
                     },
             }) => Ok(EditorEvent::Redo),
 
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('z' | 'Z'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::Redo),
+
+            // Multiple caret events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Up),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::AddCaretAbove),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Down),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::AddCaretBelow),
+
             // Selection events.
             InputEvent::Keyboard(KeyPress::WithModifiers {
                 key: Key::SpecialKey(SpecialKey::Right),
@@ -158,6 +292,54 @@ impl TryFrom<InputEvent> for EditorEvent {
                     },
             }) => Ok(EditorEvent::Select(SelectionAction::OneLineUp)),
 
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Right,
+            ))),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Left),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Left,
+            ))),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Down),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Down,
+            ))),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Up),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Up,
+            ))),
+
             InputEvent::Keyboard(KeyPress::WithModifiers {
                 key: Key::SpecialKey(SpecialKey::PageUp),
                 mask:
@@ -212,6 +394,46 @@ impl TryFrom<InputEvent> for EditorEvent {
                 key: Key::SpecialKey(SpecialKey::Esc),
             }) => Ok(EditorEvent::Select(SelectionAction::Esc)),
 
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('w'),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::NotPressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::ExpandByWord)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('l'),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::NotPressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::ExpandByLine)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('b'),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::NotPressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::ExpandByBlock)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('s'),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::NotPressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Shrink)),
+
             //  Clipboard events.
             InputEvent::Keyboard(KeyPress::WithModifiers {
                 key: Key::Character('c'),
@@ -243,7 +465,42 @@ impl TryFrom<InputEvent> for EditorEvent {
                     },
             }) => Ok(EditorEvent::Paste),
 
+            // Find events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('f'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::Find(FindAction::Start)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('h'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::Find(FindAction::StartReplace)),
+
             // Other events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character(' '),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::ToggleCheckbox),
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Insert),
+            }) => Ok(EditorEvent::ToggleTypingMode),
+
             InputEvent::Keyboard(KeyPress::Plain {
                 key: Key::SpecialKey(SpecialKey::PageDown),
             }) => Ok(EditorEvent::PageDown),
@@ -262,6 +519,18 @@ impl TryFrom<InputEvent> for EditorEvent {
 
             InputEvent::Resize(size) => Ok(EditorEvent::Resize(size)),
 
+            InputEvent::Mouse(MouseInput {
+                kind: MouseInputKind::ScrollUp,
+                ..
+            }) => Ok(EditorEvent::ScrollUp(ch!(MOUSE_WHEEL_SCROLL_ROW_COUNT))),
+
+            InputEvent::Mouse(MouseInput {
+                kind: MouseInputKind::ScrollDown,
+                ..
+            }) => Ok(EditorEvent::ScrollDown(ch!(MOUSE_WHEEL_SCROLL_ROW_COUNT))),
+
+            InputEvent::Paste(text) => Ok(Self::PasteText(text)),
+
             InputEvent::Keyboard(KeyPress::Plain {
                 key: Key::Character(character),
             }) => Ok(Self::InsertChar(character)),
@@ -270,6 +539,10 @@ impl TryFrom<InputEvent> for EditorEvent {
                 key: Key::SpecialKey(SpecialKey::Enter),
             }) => Ok(Self::InsertNewLine),
 
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Tab),
+            }) => Ok(Self::InsertIndent),
+
             InputEvent::Keyboard(KeyPress::Plain {
                 key: Key::SpecialKey(SpecialKey::Delete),
             }) => Ok(Self::Delete),
@@ -300,6 +573,241 @@ impl TryFrom<InputEvent> for EditorEvent {
 }
 
 impl EditorEvent {
+    /// Returns true if this event can change the content (lines) of the buffer. Undo and
+    /// redo are not included, since they restore content from the history.
+    pub fn is_content_mutation(&self) -> bool {
+        matches!(
+            self,
+            EditorEvent::InsertChar(_)
+                | EditorEvent::InsertString(_)
+                | EditorEvent::InsertNewLine
+                | EditorEvent::InsertIndent
+                | EditorEvent::Delete
+                | EditorEvent::Backspace
+                | EditorEvent::Paste
+                | EditorEvent::PasteText(_)
+                | EditorEvent::Cut
+                | EditorEvent::ToggleCheckbox
+                | EditorEvent::ToggleCheckboxAt(_)
+                | EditorEvent::Find(FindAction::ReplaceCurrent | FindAction::ReplaceAll)
+        )
+    }
+
+    /// Converts the [InputEvent] into a [EditorEvent::Find] while finding, ie: while the
+    /// [EditorBuffer] has a [FindState]. Key presses edit the query (or the replacement)
+    /// instead of the content. Returns [None] for the events that don't have anything to
+    /// do w/ finding, so that they can be applied as usual (eg: moving the caret).
+    pub fn try_from_while_finding(
+        input_event: &InputEvent,
+        find_state: &FindState,
+    ) -> Option<EditorEvent> {
+        let is_replacing = find_state.maybe_replacement.is_some();
+        let find_action = match input_event {
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Esc),
+            }) => FindAction::Exit,
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Enter),
+            }) => match find_state.focus {
+                FindField::Query => FindAction::Next,
+                FindField::Replacement => FindAction::ReplaceCurrent,
+            },
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Enter),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => FindAction::Previous,
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Enter),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::NotPressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) if is_replacing => FindAction::ReplaceAll,
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Tab),
+            }) if is_replacing => FindAction::ToggleField,
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Backspace),
+            }) => FindAction::DeleteChar,
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character(character),
+            }) => FindAction::InsertText(character.to_string()),
+
+            InputEvent::Paste(text) => FindAction::InsertText(text.clone()),
+
+            _ => match EditorEvent::try_from(input_event.clone()) {
+                Ok(EditorEvent::Find(find_action)) => find_action,
+                _ => return None,
+            },
+        };
+        Some(EditorEvent::Find(find_action))
+    }
+
+    /// Returns true if this event can be applied in [crate::EditMode::ReadOnly], ie: it
+    /// doesn't change the content. Navigating, scrolling, selecting, copying, and finding
+    /// are allowed, while editing, undo, redo, and replacing are not.
+    pub fn is_allowed_in_read_only_mode(&self) -> bool {
+        !self.is_content_mutation()
+            && !matches!(
+                self,
+                EditorEvent::Undo
+                    | EditorEvent::Redo
+                    | EditorEvent::ToggleTypingMode
+                    | EditorEvent::Find(FindAction::StartReplace)
+            )
+    }
+
+    /// Returns the checkbox that [EditorEvent::ToggleCheckbox] or
+    /// [EditorEvent::ToggleCheckboxAt] would toggle, or [None] if there isn't one (or if
+    /// this is any other event).
+    pub fn get_checkbox_to_toggle(
+        editor_engine: &EditorEngine,
+        editor_buffer: &EditorBuffer,
+        editor_event: &EditorEvent,
+    ) -> Option<TaskCheckbox> {
+        match editor_event {
+            EditorEvent::ToggleCheckbox => {
+                let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+                get_task_checkbox(editor_buffer, ch!(@to_usize caret.row_index))
+            }
+            EditorEvent::ToggleCheckboxAt(viewport_pos) => {
+                let viewport_size = editor_engine.current_box.style_adjusted_bounds_size;
+                if viewport_pos.row_index >= viewport_size.row_count
+                    || viewport_pos.col_index >= viewport_size.col_count
+                {
+                    return None;
+                }
+                let pos =
+                    get_buffer_position(editor_buffer, editor_engine, *viewport_pos)?;
+                get_task_checkbox(editor_buffer, ch!(@to_usize pos.row_index))
+                    .filter(|it| it.contains_col(ch!(@to_usize pos.col_index)))
+            }
+            _ => None,
+        }
+    }
+
+    fn apply_selection_action(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        selection_action: SelectionAction,
+    ) {
+        match selection_action {
+            SelectionAction::OneCharRight => {
+                EditorEngineInternalApi::right(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Enabled,
+                );
+            }
+            SelectionAction::OneCharLeft => {
+                EditorEngineInternalApi::left(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Enabled,
+                );
+            }
+            SelectionAction::OneLineDown => {
+                EditorEngineInternalApi::down(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Enabled,
+                );
+            }
+            SelectionAction::OneLineUp => {
+                EditorEngineInternalApi::up(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Enabled,
+                );
+            }
+            SelectionAction::PageUp => {
+                EditorEngineInternalApi::page_up(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Enabled,
+                );
+            }
+            SelectionAction::PageDown => {
+                EditorEngineInternalApi::page_down(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Enabled,
+                );
+            }
+            SelectionAction::Home => {
+                EditorEngineInternalApi::home(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Enabled,
+                );
+            }
+            SelectionAction::End => {
+                EditorEngineInternalApi::end(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Enabled,
+                );
+            }
+            SelectionAction::All => {
+                EditorEngineInternalApi::select_all(editor_buffer, SelectMode::Enabled);
+            }
+            SelectionAction::Esc => {
+                EditorEngineInternalApi::clear_selection(editor_buffer);
+            }
+            SelectionAction::ExpandByWord => {
+                EditorEngineInternalApi::expand_selection(
+                    editor_buffer,
+                    ExpandSelectionBy::Word,
+                );
+            }
+            SelectionAction::ExpandByLine => {
+                EditorEngineInternalApi::expand_selection(
+                    editor_buffer,
+                    ExpandSelectionBy::Line,
+                );
+            }
+            SelectionAction::ExpandByBlock => {
+                EditorEngineInternalApi::expand_selection(
+                    editor_buffer,
+                    ExpandSelectionBy::Block,
+                );
+            }
+            SelectionAction::Shrink => {
+                EditorEngineInternalApi::shrink_selection(editor_buffer);
+            }
+            SelectionAction::Block(direction) => {
+                extend_block_selection(editor_buffer, editor_engine, direction);
+            }
+            SelectionAction::BlockStartAt(viewport_pos) => {
+                if let Some(pos) =
+                    get_buffer_position(editor_buffer, editor_engine, viewport_pos)
+                {
+                    start_block_selection_at(editor_buffer, editor_engine, pos);
+                }
+            }
+            SelectionAction::BlockExtendTo(viewport_pos) => {
+                if let Some(pos) =
+                    get_buffer_position(editor_buffer, editor_engine, viewport_pos)
+                {
+                    extend_block_selection_to(editor_buffer, editor_engine, pos);
+                }
+            }
+        }
+    }
+
     fn delete_text_if_selected(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
@@ -316,11 +824,72 @@ impl EditorEvent {
         );
     }
 
+    /// In [TypingMode::Overwrite], delete as many grapheme clusters to the right of the
+    /// caret as there are in the `chunk` that is about to be inserted. This stops at the
+    /// end of the line, so lines are never joined, and the rest of the `chunk` is simply
+    /// inserted.
+    fn delete_text_to_overwrite(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        chunk: &str,
+    ) {
+        if editor_engine.typing_mode != TypingMode::Overwrite {
+            return;
+        }
+
+        for _ in chunk.graphemes(true) {
+            if EditorEngineInternalApi::string_at_caret(editor_buffer, editor_engine)
+                .is_none()
+            {
+                break;
+            }
+            EditorEngineInternalApi::delete_at_caret(editor_buffer, editor_engine);
+        }
+    }
+
     pub fn apply_editor_event(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
         editor_event: EditorEvent,
         clipboard_service_provider: &mut impl ClipboardService,
+    ) {
+        if editor_buffer.has_extra_carets() {
+            if multi_caret::is_applied_at_each_caret(&editor_event) {
+                editor_buffer.clear_selection();
+                multi_caret::apply_at_each_caret(
+                    editor_engine,
+                    editor_buffer,
+                    |editor_engine, editor_buffer| {
+                        Self::apply_editor_event_at_caret(
+                            editor_engine,
+                            editor_buffer,
+                            editor_event.clone(),
+                            clipboard_service_provider,
+                        )
+                    },
+                );
+                return;
+            }
+
+            if !multi_caret::keeps_extra_carets(&editor_event) {
+                editor_buffer.clear_extra_carets();
+            }
+        }
+
+        Self::apply_editor_event_at_caret(
+            editor_engine,
+            editor_buffer,
+            editor_event,
+            clipboard_service_provider,
+        );
+    }
+
+    /// Apply the event at the primary caret only.
+    fn apply_editor_event_at_caret(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        editor_event: EditorEvent,
+        clipboard_service_provider: &mut impl ClipboardService,
     ) {
         match editor_event {
             EditorEvent::Undo => {
@@ -331,8 +900,32 @@ impl EditorEvent {
                 history::redo(editor_buffer);
             }
 
+            EditorEvent::ToggleTypingMode => {
+                editor_engine.typing_mode = match editor_engine.typing_mode {
+                    TypingMode::Insert => TypingMode::Overwrite,
+                    TypingMode::Overwrite => TypingMode::Insert,
+                };
+            }
+
+            EditorEvent::AddCaretAbove => {
+                multi_caret::add_caret(editor_buffer, editor_engine, CaretDirection::Up);
+            }
+
+            EditorEvent::AddCaretBelow => {
+                multi_caret::add_caret(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Down,
+                );
+            }
+
             EditorEvent::InsertChar(character) => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
+                Self::delete_text_to_overwrite(
+                    editor_engine,
+                    editor_buffer,
+                    &String::from(character),
+                );
                 EditorEngineInternalApi::insert_str_at_caret(
                     EditorArgsMut {
                         editor_buffer,
@@ -384,6 +977,49 @@ impl EditorEvent {
                 }
             }
 
+            EditorEvent::ToggleCheckbox | EditorEvent::ToggleCheckboxAt(_) => {
+                if let Some(checkbox) = Self::get_checkbox_to_toggle(
+                    editor_engine,
+                    editor_buffer,
+                    &editor_event,
+                ) {
+                    EditorEngineInternalApi::toggle_checkbox(
+                        editor_buffer,
+                        editor_engine,
+                        checkbox,
+                    );
+                }
+            }
+
+            EditorEvent::MoveCaretToRow(row_index) => {
+                EditorEngineInternalApi::to_row(
+                    editor_buffer,
+                    editor_engine,
+                    row_index,
+                    SelectMode::Disabled,
+                );
+            }
+
+            EditorEvent::ScrollUp(row_count) => {
+                EditorEngineInternalApi::scroll_up(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    row_count,
+                );
+            }
+
+            EditorEvent::ScrollDown(row_count) => {
+                EditorEngineInternalApi::scroll_down(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    row_count,
+                );
+            }
+
             EditorEvent::MoveCaret(direction) => {
                 match direction {
                     CaretDirection::Left => EditorEngineInternalApi::left(
@@ -409,8 +1045,28 @@ impl EditorEvent {
                 };
             }
 
+            EditorEvent::InsertIndent => {
+                // Single line editors (eg: in dialogs) don't have any use for indentation.
+                if editor_engine.config_options.multiline_mode == LineMode::SingleLine {
+                    return;
+                }
+                let indent = editor_buffer
+                    .get_indent_style(&editor_engine.config_options)
+                    .get_indent_string();
+                Self::delete_text_if_selected(editor_engine, editor_buffer);
+                Self::delete_text_to_overwrite(editor_engine, editor_buffer, &indent);
+                EditorEngineInternalApi::insert_str_at_caret(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &indent,
+                )
+            }
+
             EditorEvent::InsertString(chunk) => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
+                Self::delete_text_to_overwrite(editor_engine, editor_buffer, &chunk);
                 EditorEngineInternalApi::insert_str_at_caret(
                     EditorArgsMut {
                         editor_buffer,
@@ -460,77 +1116,24 @@ impl EditorEvent {
                 );
             }
 
-            EditorEvent::Select(selection_action) => match selection_action {
-                SelectionAction::OneCharRight => {
-                    EditorEngineInternalApi::right(
-                        editor_buffer,
-                        editor_engine,
-                        SelectMode::Enabled,
-                    );
-                }
-                SelectionAction::OneCharLeft => {
-                    EditorEngineInternalApi::left(
-                        editor_buffer,
-                        editor_engine,
-                        SelectMode::Enabled,
-                    );
+            EditorEvent::Select(selection_action) => {
+                // A stream selection doesn't continue from a block selection.
+                if editor_buffer.get_selection_map().is_block()
+                    && !selection_action.is_block()
+                {
+                    editor_buffer.clear_selection();
                 }
-                SelectionAction::OneLineDown => {
-                    EditorEngineInternalApi::down(
-                        editor_buffer,
-                        editor_engine,
-                        SelectMode::Enabled,
-                    );
-                }
-                SelectionAction::OneLineUp => {
-                    EditorEngineInternalApi::up(
-                        editor_buffer,
-                        editor_engine,
-                        SelectMode::Enabled,
-                    );
-                }
-                SelectionAction::PageUp => {
-                    EditorEngineInternalApi::page_up(
-                        editor_buffer,
-                        editor_engine,
-                        SelectMode::Enabled,
-                    );
-                }
-                SelectionAction::PageDown => {
-                    EditorEngineInternalApi::page_down(
-                        editor_buffer,
-                        editor_engine,
-                        SelectMode::Enabled,
-                    );
-                }
-                SelectionAction::Home => {
-                    EditorEngineInternalApi::home(
-                        editor_buffer,
-                        editor_engine,
-                        SelectMode::Enabled,
-                    );
-                }
-                SelectionAction::End => {
-                    EditorEngineInternalApi::end(
-                        editor_buffer,
-                        editor_engine,
-                        SelectMode::Enabled,
-                    );
-                }
-                SelectionAction::All => {
-                    EditorEngineInternalApi::select_all(
-                        editor_buffer,
-                        SelectMode::Enabled,
-                    );
-                }
-                SelectionAction::Esc => {
-                    EditorEngineInternalApi::clear_selection(editor_buffer);
-                }
-            },
+                Self::apply_selection_action(
+                    editor_engine,
+                    editor_buffer,
+                    selection_action,
+                );
+            }
 
             EditorEvent::Cut => {
                 EditorEngineInternalApi::copy_editor_selection_to_clipboard(
                     editor_buffer,
+                    editor_engine,
                     clipboard_service_provider,
                 );
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
@@ -539,6 +1142,7 @@ impl EditorEvent {
             EditorEvent::Copy => {
                 EditorEngineInternalApi::copy_editor_selection_to_clipboard(
                     editor_buffer,
+                    editor_engine,
                     clipboard_service_provider,
                 );
             }
@@ -553,6 +1157,22 @@ impl EditorEvent {
                     clipboard_service_provider,
                 )
             }
+
+            EditorEvent::PasteText(text) => {
+                Self::delete_text_if_selected(editor_engine, editor_buffer);
+                EditorEngineInternalApi::paste_text_into_editor(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &text,
+                )
+            }
+
+            EditorEvent::Find(find_action) => {
+                editor_buffer.clear_selection();
+                apply_find_action(editor_buffer, editor_engine, find_action);
+            }
         };
     }
 
@@ -15,6 +15,8 @@
  *   limitations under the License.
  */
 
+use std::cmp::{max, min};
+
 use crossterm::style::Stylize;
 use r3bl_core::{call_if_true,
                 ch,
@@ -29,46 +31,90 @@ use r3bl_core::{call_if_true,
                 SelectionRange,
                 Size,
                 TuiColor,
+                TuiStyle,
                 TuiStyledTexts,
                 UnicodeString,
-                UnicodeStringSegmentSliceResult};
+                UnicodeStringSegmentSliceResult,
+                SPACER};
 use r3bl_macro::tui_style;
 use syntect::easy::HighlightLines;
 
 use crate::{cache,
+            compute_gutters_layout,
+            compute_minimap_layout,
             convert_syntect_to_styled_text,
             editor_buffer_clipboard_support::ClipboardService,
+            fit_gutter_text,
+            get_caret_visual_position,
+            get_current_find_match_style,
+            get_find_bar_style,
+            get_find_match_style,
+            get_inline_diagnostic,
+            get_minimap_row_text,
             get_selection_style,
+            get_visual_position,
+            get_visual_rows,
             history,
+            is_markdown_language,
+            pad_block_selection_row,
+            refresh_find_matches,
             render_ops,
             render_pipeline,
             render_tui_styled_texts_into,
+            scroll_caret_into_view,
+            truncate_inline_diagnostic_text,
             try_get_syntax_ref,
-            try_parse_and_highlight,
+            try_get_syntax_ref_by_language,
+            Button,
             CaretKind,
+            CheckboxToggleMode,
+            CurrentLineHighlightMode,
+            CursorShape,
+            DiagnosticSeverity,
             EditMode,
+            EditorArgsMut,
             EditorBuffer,
             EditorEngine,
             EditorEngineInternalApi,
             EditorEvent,
+            EditorTransaction,
+            FindAction,
             FlexBox,
             HasFocus,
+            HorizontalOverflowMarkers,
+            HorizontalOverflowMarkersMode,
+            InlineDiagnostic,
+            InlineDiagnosticsMode,
             InputEvent,
-            Key,
-            KeyPress,
+            KeyState,
+            LineMode,
+            LineWrapMode,
             List,
+            ListRenumberingMode,
+            MinimapMode,
+            ModifierKeysMask,
+            MouseInput,
+            MouseInputKind,
+            MultiCaretMode,
+            OverwriteMode,
+            PartialFlexBox,
             RenderArgs,
             RenderOp,
             RenderOps,
             RenderPipeline,
-            SpecialKey,
+            SelectionAction,
             StyleUSSpan,
+            StyleUSSpanLine,
             SyntaxHighlightMode,
+            TypewriterScrollingMode,
+            TypingMode,
+            VisualRow,
             ZOrder,
             DEBUG_TUI_COPY_PASTE,
             DEBUG_TUI_MOD,
             DEBUG_TUI_SYN_HI,
-            DEFAULT_CURSOR_CHAR};
+            DEFAULT_CURSOR_CHAR,
+            MINIMAP_SEPARATOR};
 
 pub struct EditorEngineApi;
 
@@ -82,40 +128,9 @@ impl EditorEngineApi {
         input_event: InputEvent,
         clipboard_service_provider: &mut impl ClipboardService,
     ) -> CommonResult<EditorEngineApplyEventResult> {
-        let editor_config = &editor_engine.config_options;
-
-        if let EditMode::ReadOnly = editor_config.edit_mode {
-            if !input_event.matches_any_of_these_keypresses(&[
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Up),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Down),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Left),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Right),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Home),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::End),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::PageUp),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::PageDown),
-                },
-            ]) {
-                return Ok(EditorEngineApplyEventResult::NotApplied);
-            }
-        }
-
-        if let Ok(editor_event) = EditorEvent::try_from(input_event) {
+        if let Some(editor_event) =
+            Self::would_apply_event_to_buffer(editor_engine, editor_buffer, &input_event)
+        {
             if editor_buffer.history.is_empty() {
                 history::push(editor_buffer);
             }
@@ -127,9 +142,50 @@ impl EditorEngineApi {
                 clipboard_service_provider,
             );
 
+            // Renumber before the undo step is added, so that it is part of the same step.
+            if editor_engine.config_options.list_renumbering
+                == ListRenumberingMode::Enable
+                && editor_event.is_content_mutation()
+            {
+                EditorEngineInternalApi::renumber_ordered_lists(
+                    editor_buffer,
+                    editor_engine,
+                );
+            }
+
+            // The content changed in some other way than replacing (eg: undo), so the
+            // matches have to be found again.
+            if !matches!(editor_event, EditorEvent::Find(_))
+                && (editor_event.is_content_mutation()
+                    || matches!(editor_event, EditorEvent::Undo | EditorEvent::Redo))
+            {
+                refresh_find_matches(editor_buffer);
+            }
+
+            if editor_engine.config_options.typewriter_scrolling
+                == TypewriterScrollingMode::Enable
+            {
+                EditorEngineInternalApi::center_caret_vertically(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            // When the lines are wrapped, the caret's line can be in the viewport while
+            // the caret itself is below it.
+            scroll_caret_into_view(EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            });
+
+            // The undo step is added when the transaction is committed.
+            if editor_engine.maybe_transaction.is_some() {
+                return Ok(EditorEngineApplyEventResult::Applied);
+            }
+
             match editor_event {
-                EditorEvent::InsertChar(_) => {
-                    history::push(editor_buffer);
+                EditorEvent::InsertChar(character) => {
+                    history::push_typed_char(editor_buffer, character);
                 }
                 EditorEvent::InsertString(_) => {
                     history::push(editor_buffer);
@@ -137,6 +193,9 @@ impl EditorEngineApi {
                 EditorEvent::InsertNewLine => {
                     history::push(editor_buffer);
                 }
+                EditorEvent::InsertIndent => {
+                    history::push(editor_buffer);
+                }
                 EditorEvent::Delete => {
                     history::push(editor_buffer);
                 }
@@ -149,10 +208,21 @@ impl EditorEngineApi {
                 EditorEvent::Paste => {
                     history::push(editor_buffer);
                 }
+                EditorEvent::PasteText(_) => {
+                    history::push(editor_buffer);
+                }
                 EditorEvent::Cut => {
                     history::push(editor_buffer);
                 }
-                _ => {}
+                EditorEvent::ToggleCheckbox | EditorEvent::ToggleCheckboxAt(_) => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::Find(
+                    FindAction::ReplaceCurrent | FindAction::ReplaceAll,
+                ) => {
+                    history::push(editor_buffer);
+                }
+                _ => history::end_typing(editor_buffer),
             }
             Ok(EditorEngineApplyEventResult::Applied)
         } else {
@@ -160,6 +230,286 @@ impl EditorEngineApi {
         }
     }
 
+    /// Returns the [EditorEvent] that [apply_event](EditorEngineApi::apply_event) would
+    /// apply to the `editor_buffer` for the given [InputEvent], or [None] if it would
+    /// return [EditorEngineApplyEventResult::NotApplied]. Unlike
+    /// [would_apply_event](EditorEngineApi::would_apply_event), this takes the state of
+    /// the `editor_buffer` into account (eg: whether it is being searched). This does not
+    /// have any side effects.
+    pub fn would_apply_event_to_buffer(
+        editor_engine: &EditorEngine,
+        editor_buffer: &EditorBuffer,
+        input_event: &InputEvent,
+    ) -> Option<EditorEvent> {
+        // While finding, key presses edit the query instead of the content.
+        let editor_event =
+            Self::would_apply_find_event(editor_engine, editor_buffer, input_event)
+                .or_else(|| {
+                    Self::would_apply_event(editor_engine, input_event.clone())
+                })?;
+
+        // Nothing to toggle, so let the app know (eg: to give feedback).
+        if matches!(
+            editor_event,
+            EditorEvent::ToggleCheckbox | EditorEvent::ToggleCheckboxAt(_)
+        ) && EditorEvent::get_checkbox_to_toggle(
+            editor_engine,
+            editor_buffer,
+            &editor_event,
+        )
+        .is_none()
+        {
+            return None;
+        }
+
+        Some(editor_event)
+    }
+
+    /// Returns the [EditorEvent::Find] that [apply_event](EditorEngineApi::apply_event)
+    /// would apply for the given [InputEvent] while the `editor_buffer` is being searched
+    /// (see [crate::find_replace]), or [None] if it isn't, or if the [InputEvent] doesn't
+    /// have anything to do w/ finding. This does not have any side effects.
+    pub fn would_apply_find_event(
+        editor_engine: &EditorEngine,
+        editor_buffer: &EditorBuffer,
+        input_event: &InputEvent,
+    ) -> Option<EditorEvent> {
+        let find_state = editor_buffer.get_find_state()?;
+        let editor_event = EditorEvent::try_from_while_finding(input_event, find_state)?;
+
+        // Finding is allowed in read only mode, but replacing isn't.
+        if editor_engine.config_options.edit_mode == EditMode::ReadOnly
+            && !editor_event.is_allowed_in_read_only_mode()
+        {
+            return None;
+        }
+
+        Some(editor_event)
+    }
+
+    /// Returns the [EditorEvent] that [apply_event](EditorEngineApi::apply_event) would
+    /// apply for the given [InputEvent], taking the [EditMode] into account. This does not
+    /// have any side effects.
+    pub fn would_apply_event(
+        editor_engine: &EditorEngine,
+        input_event: InputEvent,
+    ) -> Option<EditorEvent> {
+        let editor_config = &editor_engine.config_options;
+
+        // Navigating w/ the minimap is allowed even in read only mode.
+        if let Some(editor_event) = editor_engine
+            .maybe_minimap_layout
+            .and_then(|layout| layout.try_convert_input_event(&input_event))
+        {
+            return Some(editor_event);
+        }
+
+        // Dragging the mouse while holding Alt selects a block (see
+        // [crate::block_selection]). This is allowed in read only mode.
+        if let InputEvent::Mouse(MouseInput {
+            pos,
+            kind:
+                kind @ (MouseInputKind::MouseDown(Button::Left)
+                | MouseInputKind::MouseDrag(Button::Left)),
+            maybe_modifier_keys:
+                Some(ModifierKeysMask {
+                    alt_key_state: KeyState::Pressed,
+                    ..
+                }),
+        }) = &input_event
+        {
+            let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+            if pos.col_index >= origin_pos.col_index
+                && pos.row_index >= origin_pos.row_index
+            {
+                let viewport_pos = position!(
+                    col_index: pos.col_index - origin_pos.col_index,
+                    row_index: pos.row_index - origin_pos.row_index
+                );
+                return Some(EditorEvent::Select(match kind {
+                    MouseInputKind::MouseDown(_) => {
+                        SelectionAction::BlockStartAt(viewport_pos)
+                    }
+                    _ => SelectionAction::BlockExtendTo(viewport_pos),
+                }));
+            }
+        }
+
+        // Clicking on a checkbox toggles it (this isn't allowed in read only mode).
+        if let (
+            CheckboxToggleMode::Enable,
+            EditMode::ReadWrite,
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDown(Button::Left),
+                ..
+            }),
+        ) = (
+            &editor_config.checkbox_toggle,
+            &editor_config.edit_mode,
+            &input_event,
+        ) {
+            let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+            if pos.col_index >= origin_pos.col_index
+                && pos.row_index >= origin_pos.row_index
+            {
+                return Some(EditorEvent::ToggleCheckboxAt(position!(
+                    col_index: pos.col_index - origin_pos.col_index,
+                    row_index: pos.row_index - origin_pos.row_index
+                )));
+            }
+        }
+
+        let editor_event = EditorEvent::try_from(input_event).ok()?;
+
+        // Read only editors can still be navigated, scrolled, selected, and copied from.
+        if editor_config.edit_mode == EditMode::ReadOnly
+            && !editor_event.is_allowed_in_read_only_mode()
+        {
+            return None;
+        }
+
+        if editor_event == EditorEvent::ToggleTypingMode
+            && editor_config.overwrite_mode == OverwriteMode::Disable
+        {
+            return None;
+        }
+
+        if matches!(
+            editor_event,
+            EditorEvent::AddCaretAbove | EditorEvent::AddCaretBelow
+        ) && editor_config.multi_caret == MultiCaretMode::Disable
+        {
+            return None;
+        }
+
+        if editor_event == EditorEvent::ToggleCheckbox
+            && editor_config.checkbox_toggle == CheckboxToggleMode::Disable
+        {
+            return None;
+        }
+
+        // Single line editors (eg: in dialogs) don't have any use for indentation.
+        if editor_event == EditorEvent::InsertIndent
+            && editor_config.multiline_mode == LineMode::SingleLine
+        {
+            return None;
+        }
+
+        Some(editor_event)
+    }
+
+    /// Scroll the viewport to the position at `ratio` (from `0.0` to `1.0`) of the
+    /// content, eg: `0.5` to jump to the middle of the document. Values outside of this
+    /// range are clamped, and the last page is never scrolled past the end of the content.
+    /// The caret stays on the same row of the viewport, and the selection is left as is.
+    pub fn scroll_to_ratio(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        ratio: f64,
+    ) {
+        EditorEngineInternalApi::scroll_to_ratio(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            ratio,
+        );
+    }
+
+    /// Begin an undo transaction, so that all the mutations that are made to the
+    /// [EditorBuffer] until [commit_transaction](EditorEngineApi::commit_transaction) is
+    /// called, are undone (and redone) as a single step. Use
+    /// [rollback_transaction](EditorEngineApi::rollback_transaction) to discard them
+    /// instead.
+    ///
+    /// Transactions can be nested, in which case they are flattened into the outermost
+    /// one, which is the only one that adds an undo step when it is committed.
+    pub fn begin_transaction(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+    ) {
+        match editor_engine.maybe_transaction {
+            Some(ref mut transaction) => transaction.depth += 1,
+            None => {
+                // Make sure that the content before the transaction can be undone to.
+                if editor_buffer.history.is_empty() {
+                    history::push(editor_buffer);
+                }
+                editor_engine.maybe_transaction = Some(EditorTransaction {
+                    depth: 1,
+                    content_before: editor_buffer.editor_content.clone(),
+                });
+            }
+        }
+    }
+
+    /// Commit the current transaction. When the outermost transaction is committed, a
+    /// single undo step is added for all its mutations (if the content changed), and the
+    /// caret is left where the last mutation put it (and scrolled into view). Returns
+    /// [None] if there is no transaction in progress.
+    pub fn commit_transaction(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+    ) -> Option<()> {
+        let transaction = editor_engine.maybe_transaction.as_mut()?;
+        transaction.depth -= 1;
+        if transaction.depth > 0 {
+            return Some(());
+        }
+
+        let transaction = editor_engine.maybe_transaction.take()?;
+        if transaction.content_before.lines != editor_buffer.editor_content.lines {
+            history::push(editor_buffer);
+        }
+
+        EditorEngineInternalApi::validate_scroll(EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        });
+
+        Some(())
+    }
+
+    /// Discard all the mutations made since the outermost transaction began, restoring
+    /// the exact content, caret, scroll offset, and selection from that time. This ends
+    /// the transaction, even if it is nested. Returns [None] if there is no transaction
+    /// in progress.
+    pub fn rollback_transaction(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+    ) -> Option<()> {
+        let transaction = editor_engine.maybe_transaction.take()?;
+        editor_buffer.editor_content = transaction.content_before;
+        cache::clear(editor_buffer);
+        Some(())
+    }
+
+    /// Undo the last undo step, eg: the last word that was typed (see
+    /// [history::push_typed_char]). Returns [None] if there is nothing to undo, or if the
+    /// editor is read only.
+    pub fn undo(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+    ) -> Option<()> {
+        if let EditMode::ReadOnly = editor_engine.config_options.edit_mode {
+            return None;
+        }
+        history::undo(editor_buffer)
+    }
+
+    /// Redo the last undo step that was undone. Returns [None] if there is nothing to
+    /// redo, or if the editor is read only.
+    pub fn redo(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+    ) -> Option<()> {
+        if let EditMode::ReadOnly = editor_engine.config_options.edit_mode {
+            return None;
+        }
+        history::redo(editor_buffer)
+    }
+
     pub fn render_engine(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
@@ -168,7 +518,64 @@ impl EditorEngineApi {
         window_size: Size,
     ) -> CommonResult<RenderPipeline> {
         throws_with_return!({
-            editor_engine.current_box = current_box.into();
+            let new_current_box: PartialFlexBox = current_box.into();
+
+            // When there are gutters, the text gets a narrower box, to their right.
+            let (new_current_box, maybe_gutters_layout) = match compute_gutters_layout(
+                &new_current_box,
+                &editor_engine.gutters,
+                editor_buffer,
+            ) {
+                Some((text_box, layout)) => (text_box, Some(layout)),
+                None => (new_current_box, None),
+            };
+            editor_engine.maybe_gutters_layout = maybe_gutters_layout;
+
+            // When the minimap is shown, the text gets a narrower box.
+            let (new_current_box, maybe_minimap_layout) =
+                match editor_engine.config_options.minimap {
+                    MinimapMode::Enable => {
+                        match compute_minimap_layout(
+                            &new_current_box,
+                            editor_buffer.len().into(),
+                        ) {
+                            Some((text_box, layout)) => (text_box, Some(layout)),
+                            None => (new_current_box, None),
+                        }
+                    }
+                    MinimapMode::Disable => (new_current_box, None),
+                };
+            editor_engine.maybe_minimap_layout = maybe_minimap_layout;
+
+            let is_viewport_resized =
+                editor_engine.current_box.style_adjusted_bounds_size
+                    != new_current_box.style_adjusted_bounds_size;
+            editor_engine.current_box = new_current_box;
+
+            // The viewport can change size w/out the window being resized (eg: when the
+            // app changes its layout), so make sure the caret is still visible.
+            if is_viewport_resized {
+                EditorEngineInternalApi::validate_scroll(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            if editor_engine.config_options.typewriter_scrolling
+                == TypewriterScrollingMode::Enable
+            {
+                EditorEngineInternalApi::center_caret_vertically(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            // When the lines are wrapped, the caret's line can be in the viewport while
+            // the caret itself is below it.
+            scroll_caret_into_view(EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            });
 
             if editor_buffer.is_empty() {
                 EditorEngineApi::render_empty_state(RenderArgs {
@@ -187,6 +594,38 @@ impl EditorEngineApi {
                     &mut render_ops,
                 );
 
+                EditorEngineApi::render_current_line_highlight(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_inline_diagnostics(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_gutters(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_minimap(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
                 EditorEngineApi::render_selection(
                     RenderArgs {
                         editor_buffer,
@@ -195,6 +634,30 @@ impl EditorEngineApi {
                     },
                     &mut render_ops,
                 );
+                EditorEngineApi::render_find_matches(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_overflow_markers(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_find_bar(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
                 EditorEngineApi::render_caret(
                     RenderArgs {
                         editor_buffer,
@@ -211,7 +674,7 @@ impl EditorEngineApi {
         })
     }
 
-    pub fn render_content(render_args: &RenderArgs<'_>, render_ops: &mut RenderOps) {
+    pub fn render_content(render_args: &mut RenderArgs<'_>, render_ops: &mut RenderOps) {
         let RenderArgs {
             editor_buffer,
             editor_engine,
@@ -267,7 +730,13 @@ impl EditorEngineApi {
             )
         });
 
-        match editor_buffer.is_file_extension_default() {
+        // The language that is set on the engine overrides the file extension.
+        let is_markdown = match &editor_engine.maybe_syntax_language {
+            Some(language) => is_markdown_language(language),
+            None => editor_buffer.is_file_extension_default(),
+        };
+
+        match is_markdown {
             // Render using custom MD parser.
             true => syn_hi_r3bl_path::render_content(
                 editor_buffer,
@@ -295,6 +764,12 @@ impl EditorEngineApi {
             ..
         } = render_args;
 
+        if editor_engine.config_options.line_wrap != LineWrapMode::None {
+            Self::render_wrapped_selection(editor_buffer, editor_engine, render_ops);
+            return;
+        }
+
+        let is_block = editor_buffer.get_selection_map().is_block();
         for (row_index, range_of_display_col_indices) in
             editor_buffer.get_selection_map().iter()
         {
@@ -305,29 +780,29 @@ impl EditorEngineApi {
 
             if let Some(line) = lines.get(ch!(@to_usize *row_index)) {
                 // Take the scroll_offset into account when "slicing" the selection.
-                let selection = match range_of_display_col_indices
+                let visible_range = match range_of_display_col_indices
                     .locate_scroll_offset_col(scroll_offset)
                 {
                     ScrollOffsetColLocationInRange::Underflow => {
-                        let it = line.clip_to_range(*range_of_display_col_indices);
-                        if it.is_empty() {
-                            continue;
-                        };
-                        it
-                    }
-                    ScrollOffsetColLocationInRange::Overflow => {
-                        let scroll_offset_clipped_selection_range = SelectionRange {
-                            start_display_col_index: scroll_offset.col_index,
-                            ..*range_of_display_col_indices
-                        };
-                        let it =
-                            line.clip_to_range(scroll_offset_clipped_selection_range);
-                        if it.is_empty() {
-                            continue;
-                        };
-                        it
+                        *range_of_display_col_indices
                     }
+                    ScrollOffsetColLocationInRange::Overflow => SelectionRange {
+                        start_display_col_index: scroll_offset.col_index,
+                        ..*range_of_display_col_indices
+                    },
                 };
+                let it = line.clip_to_range(visible_range);
+
+                // A block selection is painted as a rectangle, even past the end of
+                // short lines.
+                let selection = if is_block {
+                    pad_block_selection_row(it, visible_range)
+                } else {
+                    it.to_string()
+                };
+                if selection.is_empty() {
+                    continue;
+                }
 
                 call_if_true!(DEBUG_TUI_COPY_PASTE, {
                     tracing::debug!(
@@ -364,50 +839,600 @@ impl EditorEngineApi {
 
                 render_ops.push(RenderOp::ApplyColors(Some(get_selection_style())));
 
-                render_ops.push(RenderOp::PaintTextWithAttributes(
-                    selection.to_string(),
-                    None,
-                ));
+                render_ops.push(RenderOp::PaintTextWithAttributes(selection, None));
 
                 render_ops.push(RenderOp::ResetColor);
             }
         }
     }
 
-    fn render_caret(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+    /// When the lines are wrapped, the selection in each line is painted on each row of
+    /// the viewport that the line is painted on, where they overlap.
+    /// Highlight the matches of the query while finding (see [crate::find_replace]). The
+    /// current match is painted w/ a different style than the others.
+    fn render_find_matches(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
         let RenderArgs {
             editor_buffer,
             editor_engine,
-            has_focus,
+            ..
         } = render_args;
 
-        if has_focus.does_id_have_focus(editor_engine.current_box.id) {
-            let str_at_caret: String = if let Some(UnicodeStringSegmentSliceResult {
-                unicode_string_seg: str_seg,
-                ..
-            }) =
-                EditorEngineInternalApi::string_at_caret(editor_buffer, editor_engine)
-            {
-                str_seg.string
-            } else {
-                DEFAULT_CURSOR_CHAR.into()
-            };
+        let Some(find_state) = editor_buffer.get_find_state() else {
+            return;
+        };
+        let maybe_current_match = find_state.get_current_match();
+        let viewport_width = editor_engine.viewport_width();
 
-            render_ops.push(RenderOp::MoveCursorPositionRelTo(
-                editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
-            ));
-            render_ops.push(RenderOp::PaintTextWithAttributes(
-                str_at_caret,
-                tui_style! { attrib: [reverse] }.into(),
-            ));
-            render_ops.push(RenderOp::MoveCursorPositionRelTo(
-                editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
-            ));
-            render_ops.push(RenderOp::ResetColor);
-        }
-    }
+        for (row_index, visual_row) in get_visual_rows(editor_buffer, editor_engine)
+            .take(ch!(@to_usize editor_engine.viewport_height()))
+            .enumerate()
+        {
+            let row_matches = find_state
+                .matches
+                .iter()
+                .filter(|it| it.row_index == ch!(visual_row.line_index));
+            for find_match in row_matches {
+                let start_col_index = max(
+                    find_match.range.start_display_col_index,
+                    visual_row.start_col_index,
+                );
+                let end_col_index = min(
+                    find_match.range.end_display_col_index,
+                    visual_row.start_col_index + visual_row.get_col_count(viewport_width),
+                );
+                if start_col_index >= end_col_index {
+                    continue;
+                }
+
+                let line = &editor_buffer.get_lines()[visual_row.line_index];
+                let text = line
+                    .clip_to_range(SelectionRange::new(start_col_index, end_col_index));
+                let style = if maybe_current_match == Some(find_match) {
+                    get_current_find_match_style()
+                } else {
+                    get_find_match_style()
+                };
+
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    editor_engine.current_box.style_adjusted_origin_pos,
+                    position!(
+                        col_index: start_col_index - visual_row.start_col_index,
+                        row_index: row_index
+                    ),
+                ));
+                render_ops.push(RenderOp::ApplyColors(Some(style)));
+                render_ops
+                    .push(RenderOp::PaintTextWithAttributes(text.to_string(), None));
+                render_ops.push(RenderOp::ResetColor);
+            }
+        }
+    }
+
+    /// Paint the find bar (the query, the replacement, and which match the caret is on)
+    /// over the last row of the viewport while finding (see [crate::find_replace]).
+    fn render_find_bar(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        let Some(find_state) = editor_buffer.get_find_state() else {
+            return;
+        };
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+        if viewport_height == ch!(0) {
+            return;
+        }
+
+        let text = UnicodeString::from(find_state.get_status_text());
+        let text = text.truncate_end_to_fit_width(viewport_width);
+        let pad_width = viewport_width - UnicodeString::from(text).display_width;
+        let text = format!("{text}{}", " ".repeat(ch!(@to_usize pad_width)));
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            position!(col_index: 0, row_index: viewport_height - ch!(1)),
+        ));
+        render_ops.push(RenderOp::ApplyColors(Some(get_find_bar_style())));
+        render_ops.push(RenderOp::PaintTextWithAttributes(text, None));
+        render_ops.push(RenderOp::ResetColor);
+    }
+
+    fn render_wrapped_selection(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        render_ops: &mut RenderOps,
+    ) {
+        let selection_map = editor_buffer.get_selection_map();
+        let viewport_width = editor_engine.viewport_width();
+
+        for (row_index, visual_row) in get_visual_rows(editor_buffer, editor_engine)
+            .take(ch!(@to_usize editor_engine.viewport_height()))
+            .enumerate()
+        {
+            let Some(range) = selection_map.get(ch!(visual_row.line_index)) else {
+                continue;
+            };
+            let start_col_index =
+                max(range.start_display_col_index, visual_row.start_col_index);
+            let end_col_index = min(
+                range.end_display_col_index,
+                visual_row.start_col_index + visual_row.get_col_count(viewport_width),
+            );
+            if start_col_index >= end_col_index {
+                continue;
+            }
+
+            let line = &editor_buffer.get_lines()[visual_row.line_index];
+            let selection =
+                line.clip_to_range(SelectionRange::new(start_col_index, end_col_index));
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position!(
+                    col_index: start_col_index - visual_row.start_col_index,
+                    row_index: row_index
+                ),
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(get_selection_style())));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                selection.to_string(),
+                None,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    // BOOKM: Render inline diagnostics
+    /// Paint the diagnostics for each visible row after the end of its line. This is not
+    /// cached, since the diagnostics can change w/out the content changing.
+    /// The content in `render_ops` is a copy of what is in the render cache, so the
+    /// highlight is applied to it in place, which keeps the cache independent of the
+    /// caret. Each line of content starts w/ a move to its first column, which is used to
+    /// find the ops that paint the caret's line. The spans on it that don't have their own
+    /// background get the highlight's, and then the rest of the row is filled in.
+    fn render_current_line_highlight(
+        render_args: RenderArgs<'_>,
+        render_ops: &mut RenderOps,
+    ) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            has_focus,
+        } = render_args;
+
+        let CurrentLineHighlightMode::Enable(highlight_style) =
+            editor_engine.config_options.current_line_highlight
+        else {
+            return;
+        };
+        let Some(color_bg) = highlight_style.color_bg else {
+            return;
+        };
+        if !has_focus.does_id_have_focus(editor_engine.current_box.id) {
+            return;
+        }
+
+        let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+        let caret_adj_row_index =
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        // The rows of the viewport that the caret's line is painted on. There is more than
+        // one when the lines are wrapped.
+        let current_line_rows: Vec<(ChUnit, VisualRow)> =
+            get_visual_rows(editor_buffer, editor_engine)
+                .take(ch!(@to_usize editor_engine.viewport_height()))
+                .enumerate()
+                .filter(|(_, it)| it.line_index == ch!(@to_usize caret_adj_row_index))
+                .map(|(row_index, it)| (ch!(row_index), it))
+                .collect();
+        let line_start_render_ops: Vec<RenderOp> = current_line_rows
+            .iter()
+            .map(|(row_index, _)| {
+                RenderOp::MoveCursorPositionRelTo(
+                    origin_pos,
+                    position!(col_index: 0, row_index: *row_index),
+                )
+            })
+            .collect();
+        let bg_style = tui_style! { color_bg: color_bg };
+
+        let mut is_in_current_line = false;
+        for render_op in render_ops.list.iter_mut() {
+            match render_op {
+                RenderOp::MoveCursorPositionRelTo(..) => {
+                    is_in_current_line = line_start_render_ops.contains(render_op);
+                }
+                RenderOp::ApplyColors(Some(style))
+                | RenderOp::PaintTextWithAttributes(_, Some(style))
+                    if is_in_current_line && style.color_bg.is_none() =>
+                {
+                    style.color_bg = Some(color_bg);
+                }
+                RenderOp::ApplyColors(maybe_style @ None) if is_in_current_line => {
+                    *maybe_style = Some(bg_style);
+                }
+                _ => {}
+            }
+        }
+
+        // Fill the rest of each row, after the end of the line's text.
+        let max_display_col_count = editor_engine
+            .current_box
+            .style_adjusted_bounds_size
+            .col_count;
+        let line_display_width =
+            editor_buffer.get_line_display_width(caret_adj_row_index);
+        for (row_index, visual_row) in current_line_rows {
+            let text_end_col_index = min(
+                line_display_width - visual_row.start_col_index,
+                visual_row.get_col_count(max_display_col_count),
+            );
+            if text_end_col_index >= max_display_col_count {
+                continue;
+            }
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                origin_pos,
+                position!(col_index: text_end_col_index, row_index: row_index),
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(bg_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                SPACER.repeat(ch!(@to_usize max_display_col_count - text_end_col_index)),
+                None,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    fn render_inline_diagnostics(
+        render_args: RenderArgs<'_>,
+        render_ops: &mut RenderOps,
+    ) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        if editor_engine.config_options.inline_diagnostics
+            == InlineDiagnosticsMode::Disable
+            || editor_buffer.get_diagnostics().is_empty()
+        {
+            return;
+        }
+
+        let Size {
+            col_count: max_display_col_count,
+            row_count: max_display_row_count,
+        } = editor_engine.current_box.style_adjusted_bounds_size;
+        for (raw_row_index, visual_row) in get_visual_rows(editor_buffer, editor_engine)
+            .take(ch!(@to_usize max_display_row_count))
+            .enumerate()
+        {
+            // When the line is wrapped, this goes after the end of its last row.
+            if !visual_row.is_last_segment {
+                continue;
+            }
+            let line = &editor_buffer.get_lines()[visual_row.line_index];
+            let Some(InlineDiagnostic { severity, text }) = get_inline_diagnostic(
+                editor_buffer.get_diagnostics(),
+                ch!(visual_row.line_index),
+            ) else {
+                continue;
+            };
+
+            // Leave a gap of one column between the end of the line and the text.
+            let raw_col_index = if line.display_width > visual_row.start_col_index {
+                line.display_width - visual_row.start_col_index + 1
+            } else {
+                ch!(0)
+            };
+            if raw_col_index >= max_display_col_count {
+                continue;
+            }
+
+            let text = truncate_inline_diagnostic_text(
+                &text,
+                max_display_col_count - raw_col_index,
+            );
+            if text.is_empty() {
+                continue;
+            }
+
+            let color_fg = match severity {
+                DiagnosticSeverity::Error => TuiColor::Basic(ANSIBasicColor::Red),
+                DiagnosticSeverity::Warning => TuiColor::Basic(ANSIBasicColor::Yellow),
+                DiagnosticSeverity::Info => TuiColor::Basic(ANSIBasicColor::Blue),
+                DiagnosticSeverity::Hint => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            };
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position!(col_index: raw_col_index, row_index: ch!(raw_row_index)),
+            ));
+            render_ops.push(RenderOp::ApplyColors(
+                tui_style! {
+                    attrib: [dim]
+                    color_fg: color_fg
+                }
+                .into(),
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(text, None));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    // BOOKM: Render minimap
+    /// Paint the minimap (if it is shown) to the right of the text. The rows that overlap
+    /// w/ the viewport are highlighted. This is not cached, since it depends on the
+    /// scroll offset.
+    /// Paint each gutter for every line that is visible in the viewport. Rows below the
+    /// end of the buffer are left blank.
+    fn render_gutters(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        let Some(ref layout) = editor_engine.maybe_gutters_layout else {
+            return;
+        };
+
+        for (row_index, visual_row) in get_visual_rows(editor_buffer, editor_engine)
+            .take(ch!(@to_usize layout.size.row_count))
+            .enumerate()
+        {
+            let mut col_index = ch!(0);
+            for (registered_gutter, width) in
+                editor_engine.gutters.iter().zip(layout.widths.iter())
+            {
+                // When the line is wrapped, the rows after its first one are left blank.
+                let (text, maybe_style) = match visual_row.segment_index {
+                    0 => registered_gutter
+                        .gutter
+                        .get_line_content(editor_buffer, visual_row.line_index)
+                        .unwrap_or_default(),
+                    _ => Default::default(),
+                };
+
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    layout.origin_pos,
+                    position!(col_index: col_index, row_index: ch!(row_index)),
+                ));
+                render_ops.push(RenderOp::ApplyColors(maybe_style));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    fit_gutter_text(&text, *width),
+                    maybe_style,
+                ));
+                render_ops.push(RenderOp::ResetColor);
+
+                col_index += *width;
+            }
+        }
+    }
+
+    /// Paint the [HorizontalOverflowMarkers] (if they are enabled) over the edges of each
+    /// line in the viewport that has text hidden to its left or right.
+    fn render_overflow_markers(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        let HorizontalOverflowMarkersMode::Enable(style) =
+            editor_engine.config_options.horizontal_overflow_markers
+        else {
+            return;
+        };
+        // Nothing is hidden to the left or right of the lines, when they are wrapped.
+        if editor_engine.config_options.line_wrap != LineWrapMode::None {
+            return;
+        }
+
+        let markers = HorizontalOverflowMarkers::default().with_style(Some(style));
+        let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+
+        for (row_index, line) in editor_buffer
+            .get_lines()
+            .iter()
+            .skip(ch!(@to_usize scroll_offset.row_index))
+            .take(ch!(@to_usize editor_engine.viewport_height()))
+            .enumerate()
+        {
+            for overlay in
+                markers.get_edge_overlays(line, scroll_offset.col_index, viewport_width)
+            {
+                let style = *overlay.styled_text.get_style();
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin_pos,
+                    position!(col_index: overlay.col_index, row_index: ch!(row_index)),
+                ));
+                render_ops.push(RenderOp::ApplyColors(Some(style)));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    overlay.styled_text.get_text().string.clone(),
+                    Some(style),
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+        }
+    }
+
+    fn render_minimap(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        let Some(layout) = editor_engine.maybe_minimap_layout else {
+            return;
+        };
+
+        let lines = editor_buffer.get_lines();
+        let viewport_start = ch!(@to_usize editor_buffer.get_scroll_offset().row_index);
+        let viewport_end = match editor_engine.config_options.line_wrap {
+            LineWrapMode::None => {
+                viewport_start + ch!(@to_usize editor_engine.viewport_height())
+            }
+            // Fewer lines fit in the viewport, when they are wrapped.
+            LineWrapMode::Word | LineWrapMode::Char => {
+                get_visual_rows(editor_buffer, editor_engine)
+                    .take(ch!(@to_usize editor_engine.viewport_height()))
+                    .last()
+                    .map(|it| it.line_index + 1)
+                    .unwrap_or(viewport_start)
+            }
+        };
+        let cell_count = ch!(@to_usize layout.size.col_count) - 1;
+
+        for minimap_row_index in 0..ch!(@to_usize layout.size.row_count) {
+            let (start, end) = layout.get_line_range(minimap_row_index);
+            let is_in_viewport = start < viewport_end && end > viewport_start;
+            let row_text = get_minimap_row_text(lines, (start, end), cell_count);
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                layout.origin_pos,
+                position!(col_index: 0, row_index: ch!(minimap_row_index)),
+            ));
+            render_ops.push(RenderOp::ApplyColors(
+                tui_style! {
+                    attrib: [dim]
+                }
+                .into(),
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                MINIMAP_SEPARATOR.to_string(),
+                None,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+
+            let row_style = match is_in_viewport {
+                true => tui_style! {
+                    color_fg: TuiColor::Basic(ANSIBasicColor::White)
+                    color_bg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
+                },
+                false => tui_style! {
+                    attrib: [dim]
+                    color_fg: TuiColor::Basic(ANSIBasicColor::Grey)
+                },
+            };
+            render_ops.push(RenderOp::ApplyColors(row_style.into()));
+            render_ops.push(RenderOp::PaintTextWithAttributes(row_text, None));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    fn render_caret(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            has_focus,
+        } = render_args;
+
+        if has_focus.does_id_have_focus(editor_engine.current_box.id) {
+            let str_at_caret: String = if let Some(UnicodeStringSegmentSliceResult {
+                unicode_string_seg: str_seg,
+                ..
+            }) =
+                EditorEngineInternalApi::string_at_caret(editor_buffer, editor_engine)
+            {
+                str_seg.string
+            } else {
+                DEFAULT_CURSOR_CHAR.into()
+            };
+
+            // The caret is painted over the whole grapheme cluster (which may be wide), so
+            // that it is visible even in terminals that can't change the shape of their
+            // cursor. In read only mode, it is a thin bar (underline) since text can't be
+            // typed there, and the terminal's cursor isn't shown.
+            let (caret_style, maybe_cursor_shape) = match (
+                &editor_engine.config_options.edit_mode,
+                &editor_engine.typing_mode,
+            ) {
+                (EditMode::ReadOnly, _) => (tui_style! { attrib: [underline] }, None),
+                (EditMode::ReadWrite, TypingMode::Insert) => (
+                    tui_style! { attrib: [reverse] },
+                    Some(CursorShape::SteadyBar),
+                ),
+                (EditMode::ReadWrite, TypingMode::Overwrite) => (
+                    tui_style! { attrib: [reverse, bold, underline] },
+                    Some(CursorShape::SteadyBlock),
+                ),
+            };
+            // The extra carets are painted first, so that the terminal's cursor ends up at
+            // the primary caret.
+            Self::render_extra_carets(
+                editor_buffer,
+                editor_engine,
+                caret_style,
+                render_ops,
+            );
+            let caret_visual_pos =
+                get_caret_visual_position(editor_buffer, editor_engine);
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                caret_visual_pos,
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                str_at_caret,
+                caret_style.into(),
+            ));
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                caret_visual_pos,
+            ));
+            if let Some(cursor_shape) = maybe_cursor_shape {
+                render_ops.push(RenderOp::ShowCursor(cursor_shape));
+            }
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    /// The extra carets (see [crate::MultiCaretMode]) that are in the viewport are painted
+    /// dimmed, so that the primary caret stands out.
+    fn render_extra_carets(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        caret_style: TuiStyle,
+        render_ops: &mut RenderOps,
+    ) {
+        let extra_caret_style = TuiStyle {
+            dim: true,
+            ..caret_style
+        };
+
+        for extra_caret in editor_buffer.get_extra_carets() {
+            let Some(visual_pos) =
+                get_visual_position(editor_buffer, editor_engine, *extra_caret)
+            else {
+                continue;
+            };
+
+            let str_at_caret: String = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize extra_caret.row_index))
+                .and_then(|line| {
+                    line.get_string_at_display_col_index(extra_caret.col_index)
+                })
+                .map(|result| result.unicode_string_seg.string)
+                .unwrap_or_else(|| DEFAULT_CURSOR_CHAR.into());
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                visual_pos,
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                str_at_caret,
+                extra_caret_style.into(),
+            ));
+        }
+    }
 
     pub fn render_empty_state(render_args: RenderArgs<'_>) -> RenderPipeline {
         let RenderArgs {
@@ -462,6 +1487,7 @@ impl EditorEngineApi {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EditorEngineApplyEventResult {
     Applied,
     NotApplied,
@@ -473,14 +1499,15 @@ mod syn_hi_r3bl_path {
     /// Try convert [Vec] of [US] to [MdDocument]:
     /// - Step 1: Get the lines from the buffer using
     ///           [editor_buffer.get_lines()](EditorBuffer::get_lines()).
-    /// - Step 2: Convert the lines into a [List] of [StyleUSSpanLine] using
-    ///           [try_parse_and_highlight()]. If this fails then take the path of no
+    /// - Step 2: Convert the lines into a [List] of [StyleUSSpanLine] using the
+    ///           [crate::MdIncrementalHighlighter], which only parses the blocks that were
+    ///           changed since the last render. If this fails then take the path of no
     ///           syntax highlighting else take the path of syntax highlighting.
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
         max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
-        editor_engine: &&mut EditorEngine,
+        editor_engine: &mut EditorEngine,
         max_display_col_count: ChUnit,
     ) {
         // Try to parse the Vec<US> into an MDDocument & render it.
@@ -504,15 +1531,24 @@ mod syn_hi_r3bl_path {
         editor_buffer: &&EditorBuffer,
         max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
-        editor_engine: &&mut EditorEngine,
+        editor_engine: &mut EditorEngine,
         max_display_col_count: ChUnit,
     ) -> CommonResult<()> {
         throws!({
-            let lines = try_parse_and_highlight(
-                editor_buffer.get_lines(),
-                &editor_engine.current_box.get_computed_style(),
-                Some((&editor_engine.syntax_set, &editor_engine.theme)),
-            )?;
+            let lines = {
+                let EditorEngine {
+                    current_box,
+                    syntax_set,
+                    theme,
+                    md_incremental_highlighter,
+                    ..
+                } = &mut *editor_engine;
+                md_incremental_highlighter.try_parse_and_highlight(
+                    editor_buffer.get_lines(),
+                    &current_box.get_computed_style(),
+                    Some((&*syntax_set, &*theme)),
+                )?
+            };
 
             call_if_true!(DEBUG_TUI_SYN_HI, {
                 tracing::debug!(
@@ -524,21 +1560,23 @@ mod syn_hi_r3bl_path {
                 )
             });
 
-            for (row_index, line) in lines
-                .iter()
-                .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-                .enumerate()
+            for (row_index, visual_row) in
+                get_visual_rows(editor_buffer, editor_engine).enumerate()
             {
                 // Clip the content to max rows.
                 if ch!(row_index) > max_display_row_count {
                     break;
                 }
 
+                let Some(line) = lines.get(visual_row.line_index) else {
+                    break;
+                };
+
                 render_single_line(
                     line,
-                    editor_buffer,
-                    editor_engine,
+                    &editor_engine,
                     row_index,
+                    visual_row,
                     max_display_col_count,
                     render_ops,
                 );
@@ -548,9 +1586,9 @@ mod syn_hi_r3bl_path {
 
     fn render_single_line(
         line: &List<StyleUSSpan>,
-        editor_buffer: &&EditorBuffer,
         editor_engine: &&mut EditorEngine,
         row_index: usize,
+        visual_row: VisualRow,
         max_display_col_count: ChUnit,
         render_ops: &mut RenderOps,
     ) {
@@ -558,9 +1596,10 @@ mod syn_hi_r3bl_path {
             editor_engine.current_box.style_adjusted_origin_pos,
             position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
         ));
-        let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let styled_texts: TuiStyledTexts =
-            line.clip(scroll_offset_col, max_display_col_count);
+        let styled_texts: TuiStyledTexts = line.clip(
+            visual_row.start_col_index,
+            visual_row.get_col_count(max_display_col_count),
+        );
         render_tui_styled_texts_into(&styled_texts, render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
@@ -573,25 +1612,24 @@ mod syn_hi_syntect_path {
         editor_buffer: &&EditorBuffer,
         max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
-        editor_engine: &&mut EditorEngine,
+        editor_engine: &mut EditorEngine,
         max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-            .enumerate()
+        // Paint each row of the viewport (starting at the scroll_offset.row).
+        for (row_index, visual_row) in
+            get_visual_rows(editor_buffer, editor_engine).enumerate()
         {
             // Clip the content to max rows.
             if ch!(row_index) > max_display_row_count {
                 break;
             }
 
+            let line = &editor_buffer.get_lines()[visual_row.line_index];
+
             render_single_line(
                 render_ops,
                 row_index,
+                visual_row,
                 editor_engine,
                 editor_buffer,
                 line,
@@ -603,7 +1641,8 @@ mod syn_hi_syntect_path {
     fn render_single_line(
         render_ops: &mut RenderOps,
         row_index: usize,
-        editor_engine: &&mut EditorEngine,
+        visual_row: VisualRow,
+        editor_engine: &mut EditorEngine,
         editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
         max_display_col_count: ChUnit,
@@ -618,10 +1657,10 @@ mod syn_hi_syntect_path {
 
         match it {
             // If enabled, and we have a SyntaxReference then try and highlight the line.
-            Some(syntect_highlighted_line) => {
+            Some(style_us_span_line) => {
                 render_line_with_syntect(
-                    syntect_highlighted_line,
-                    editor_buffer,
+                    style_us_span_line,
+                    visual_row,
                     max_display_col_count,
                     render_ops,
                 );
@@ -630,28 +1669,25 @@ mod syn_hi_syntect_path {
             None => {
                 no_syn_hi_path::render_line_no_syntax_highlight(
                     line,
-                    editor_buffer,
+                    visual_row,
                     max_display_col_count,
                     render_ops,
-                    editor_engine,
+                    &editor_engine,
                 );
             }
         }
     }
 
     fn render_line_with_syntect(
-        syntect_highlighted_line: Vec<(syntect::highlighting::Style, &str)>,
-        editor_buffer: &&EditorBuffer,
+        list: StyleUSSpanLine,
+        visual_row: VisualRow,
         max_display_col_count: ChUnit,
         render_ops: &mut RenderOps,
     ) {
-        let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let list: List<StyleUSSpan> =
-            convert_syntect_to_styled_text::convert_highlighted_line_from_syntect_to_tui(
-                syntect_highlighted_line,
-            );
-        let styled_texts: TuiStyledTexts =
-            list.clip(scroll_offset_col, max_display_col_count);
+        let styled_texts: TuiStyledTexts = list.clip(
+            visual_row.start_col_index,
+            visual_row.get_col_count(max_display_col_count),
+        );
         render_tui_styled_texts_into(&styled_texts, render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
@@ -660,19 +1696,39 @@ mod syn_hi_syntect_path {
     /// create a new [HighlightLines] for each line, but if this struct is re-used then it
     /// will not be able to highlight the lines correctly in the editor component. This
     /// struct is mutated when it is used to highlight a line, so it must be re-created
-    /// for each line.
-    fn try_get_syntect_highlighted_line<'a>(
-        editor_engine: &'a &mut EditorEngine,
+    /// for each line. Since each line is highlighted on its own, the result is cached
+    /// (see [crate::SyntectLineCache]), so that a line is only highlighted again when it
+    /// changes.
+    fn try_get_syntect_highlighted_line(
+        editor_engine: &mut EditorEngine,
         editor_buffer: &&EditorBuffer,
-        line: &'a str,
-    ) -> Option<Vec<(syntect::highlighting::Style, &'a str)>> {
-        let file_ext = editor_buffer.get_maybe_file_extension()?;
-        let syntax_ref = try_get_syntax_ref(&editor_engine.syntax_set, file_ext)?;
-        let theme = &editor_engine.theme;
-        let mut highlighter = HighlightLines::new(syntax_ref, theme);
-        highlighter
-            .highlight_line(line, &editor_engine.syntax_set)
-            .ok()
+        line: &str,
+    ) -> Option<StyleUSSpanLine> {
+        let EditorEngine {
+            syntax_set,
+            theme,
+            maybe_syntax_language,
+            syntect_line_cache,
+            ..
+        } = editor_engine;
+        let (syntax_set, theme) = (&*syntax_set, &*theme);
+        let syntax_ref = match maybe_syntax_language {
+            Some(language) => try_get_syntax_ref_by_language(syntax_set, language),
+            None => {
+                let file_ext = editor_buffer.get_maybe_file_extension()?;
+                try_get_syntax_ref(syntax_set, file_ext)?
+            }
+        };
+        syntect_line_cache.get_or_highlight(&syntax_ref.name, line, || {
+            let mut highlighter = HighlightLines::new(syntax_ref, theme);
+            let syntect_highlighted_line =
+                highlighter.highlight_line(line, syntax_set).ok()?;
+            Some(
+                convert_syntect_to_styled_text::convert_highlighted_line_from_syntect_to_tui(
+                    syntect_highlighted_line,
+                ),
+            )
+        })
     }
 }
 
@@ -686,24 +1742,22 @@ mod no_syn_hi_path {
         editor_engine: &&mut EditorEngine,
         max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-            .enumerate()
+        // Paint each row of the viewport (starting at the scroll_offset.row).
+        for (row_index, visual_row) in
+            get_visual_rows(editor_buffer, editor_engine).enumerate()
         {
             // Clip the content to max rows.
             if ch!(row_index) > max_display_row_count {
                 break;
             }
 
+            let line = &editor_buffer.get_lines()[visual_row.line_index];
+
             render_single_line(
                 render_ops,
                 row_index,
+                visual_row,
                 editor_engine,
-                editor_buffer,
                 line,
                 max_display_col_count,
             );
@@ -713,8 +1767,8 @@ mod no_syn_hi_path {
     fn render_single_line(
         render_ops: &mut RenderOps,
         row_index: usize,
+        visual_row: VisualRow,
         editor_engine: &&mut EditorEngine,
-        editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
         max_display_col_count: ChUnit,
     ) {
@@ -725,7 +1779,7 @@ mod no_syn_hi_path {
 
         no_syn_hi_path::render_line_no_syntax_highlight(
             line,
-            editor_buffer,
+            visual_row,
             max_display_col_count,
             render_ops,
             editor_engine,
@@ -735,16 +1789,16 @@ mod no_syn_hi_path {
     /// This is used as a fallback by other render paths.
     pub fn render_line_no_syntax_highlight(
         line: &UnicodeString,
-        editor_buffer: &&EditorBuffer,
+        visual_row: VisualRow,
         max_display_col_count: ChUnit,
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
     ) {
-        let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
-
-        // Clip the content [scroll_offset.col .. max cols].
-        let truncated_line =
-            line.clip_to_width(scroll_offset_col_index, max_display_col_count);
+        // Clip the content [visual_row.start_col .. max cols].
+        let truncated_line = line.clip_to_width(
+            visual_row.start_col_index,
+            visual_row.get_col_count(max_display_col_count),
+        );
 
         render_ops.push(RenderOp::ApplyColors(
             editor_engine.current_box.get_computed_style(),
@@ -791,7 +1845,13 @@ mod test_cache {
             has_focus,
             render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Render the caret to screen. This should not change the content and result in a cache hit.
         EditorEngineApi::render_caret(
@@ -823,7 +1883,13 @@ mod test_cache {
             has_focus,
             render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Render the selection of text to screen. This should not change the content and result in a cache hit.
         EditorEngineApi::render_selection(
@@ -855,7 +1921,34 @@ mod test_cache {
             has_focus,
             render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
+
+        // Change in the position of the editor's box (w/out a change in window size)
+        // should invalidate the cache and result in a cache miss.
+        editor_engine.current_box.style_adjusted_origin_pos = position!(
+            col_index: ch!(10),
+            row_index: ch!(0)
+        );
+        cache::render_content(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            has_focus,
+            render_ops,
+        );
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Change in content should invalidate the cache and result in a cache miss.
         editor_buffer.set_lines(vec!["r3bl".to_string()]);
@@ -866,17 +1959,50 @@ mod test_cache {
             has_focus,
             render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
+
+        // Change in the syntax language should invalidate the cache and result in a
+        // cache miss, so that the content is highlighted again.
+        editor_engine.set_syntax_language("rust");
+        cache::render_content(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            has_focus,
+            render_ops,
+        );
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
     }
 
     fn test_cache_miss(
         editor_buffer: &mut EditorBuffer,
+        editor_engine: &EditorEngine,
         window_size: Size,
         render_ops: &mut RenderOps,
         cache: &mut HashMap<String, RenderOps>,
     ) {
         cache.clear(); // invalidating cache
-        let key = format!("{}{}", editor_buffer.get_scroll_offset(), window_size); // generating key
+        let key = format!(
+            "{}{}{}{}{:?}{:?}",
+            editor_buffer.get_scroll_offset(),
+            window_size,
+            editor_engine.current_box.style_adjusted_origin_pos,
+            editor_engine.current_box.style_adjusted_bounds_size,
+            editor_engine.config_options.line_wrap,
+            editor_engine.maybe_syntax_language,
+        ); // generating key
         cache.insert(key, render_ops.clone()); // enter the new entry into cache
         assert_eq2!(editor_buffer.render_cache, cache.clone());
     }
@@ -888,3 +2014,532 @@ mod test_cache {
         assert_eq2!(editor_buffer.render_cache, cache.clone());
     }
 }
+
+#[cfg(test)]
+mod test_syntect_line_cache {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::test_fixtures::mock_real_objects_for_editor;
+
+    #[test]
+    fn test_scrolling_through_a_file_only_highlights_each_line_once() {
+        let mut editor_buffer = EditorBuffer::new_empty(&Some("rs".to_string()), &None);
+        editor_buffer.set_lines(
+            (0..100)
+                .map(|it| format!("let x{it} = \"{it}\";"))
+                .collect(),
+        );
+        let mut editor_engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut has_focus = HasFocus::default();
+
+        let mut render_at = |editor_buffer: &mut EditorBuffer, row_index: usize| {
+            editor_buffer.editor_content.scroll_offset.row_index = ch!(row_index);
+            EditorEngineApi::render_content(
+                &mut RenderArgs {
+                    editor_engine: &mut editor_engine,
+                    editor_buffer,
+                    has_focus: &mut has_focus,
+                },
+                &mut render_ops!(),
+            );
+        };
+
+        // Scroll down through the whole file, and then back up.
+        for row_index in (0..=90).chain((0..=90).rev()) {
+            render_at(&mut editor_buffer, row_index);
+        }
+
+        let cache = &editor_engine.syntect_line_cache;
+        assert_eq2!(cache.miss_count, 100);
+        assert_eq2!(cache.len(), 100);
+        assert!(cache.hit_count > 10 * cache.miss_count);
+
+        // Only the line that was edited is highlighted again.
+        editor_buffer.set_lines(
+            (0..100)
+                .map(|it| match it {
+                    0 => "let edited = 0;".to_string(),
+                    _ => format!("let x{it} = \"{it}\";"),
+                })
+                .collect(),
+        );
+        EditorEngineApi::render_content(
+            &mut RenderArgs {
+                editor_engine: &mut editor_engine,
+                editor_buffer: &editor_buffer,
+                has_focus: &mut has_focus,
+            },
+            &mut render_ops!(),
+        );
+        assert_eq2!(editor_engine.syntect_line_cache.miss_count, 101);
+    }
+}
+
+#[cfg(test)]
+mod test_md_incremental_highlighter {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{test_fixtures::mock_real_objects_for_editor, DEFAULT_SYN_HI_FILE_EXT};
+
+    #[test]
+    fn test_editing_markdown_only_parses_changed_blocks() {
+        let mut editor_buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut lines = (0..100).map(|it| format!("Line {it}.")).collect::<Vec<_>>();
+        editor_buffer.set_lines(lines.clone());
+        let mut editor_engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut has_focus = HasFocus::default();
+
+        let mut render = |editor_buffer: &EditorBuffer| {
+            EditorEngineApi::render_content(
+                &mut RenderArgs {
+                    editor_engine: &mut editor_engine,
+                    editor_buffer,
+                    has_focus: &mut has_focus,
+                },
+                &mut render_ops!(),
+            );
+        };
+
+        render(&editor_buffer);
+        lines[50] = "Line *50*.".to_string();
+        editor_buffer.set_lines(lines);
+        render(&editor_buffer);
+
+        let highlighter = &editor_engine.md_incremental_highlighter;
+        assert_eq2!(highlighter.full_parse_count, 1);
+        assert_eq2!(highlighter.incremental_parse_count, 1);
+        assert!(highlighter.last_parsed_line_count < 10);
+    }
+}
+
+/// Run w/ `cargo bench -p r3bl_tui bench_syntect_line_cache`, to compare scrolling
+/// through a long highlighted file w/ and w/out the [crate::SyntectLineCache].
+#[cfg(test)]
+mod bench_syntect_line_cache {
+    extern crate test;
+    use test::Bencher;
+
+    use super::*;
+    use crate::test_fixtures::mock_real_objects_for_editor;
+
+    const LINE_COUNT: usize = 200;
+
+    /// Renders each page of the file, from the top to the bottom. When `use_cache` is
+    /// `false`, the cache is invalidated before each render, so every visible line is
+    /// highlighted again, just like before the cache.
+    fn scroll_through(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        use_cache: bool,
+    ) {
+        let viewport_height = ch!(@to_usize editor_engine.viewport_height());
+        for row_index in 0..=(LINE_COUNT - viewport_height) {
+            editor_buffer.editor_content.scroll_offset.row_index = ch!(row_index);
+            if !use_cache {
+                editor_engine.syntect_line_cache.invalidate();
+            }
+            EditorEngineApi::render_content(
+                &mut RenderArgs {
+                    editor_engine,
+                    editor_buffer,
+                    has_focus: &mut HasFocus::default(),
+                },
+                &mut render_ops!(),
+            );
+        }
+    }
+
+    fn bench_scroll_through(bencher: &mut Bencher, use_cache: bool) {
+        let mut editor_buffer = EditorBuffer::new_empty(&Some("rs".to_string()), &None);
+        editor_buffer.set_lines(
+            (0..LINE_COUNT)
+                .map(|it| format!("let x{it}: Vec<&str> = vec![\"{it}\"]; // {it}"))
+                .collect(),
+        );
+        let mut editor_engine = mock_real_objects_for_editor::make_editor_engine();
+        bencher
+            .iter(|| scroll_through(&mut editor_engine, &mut editor_buffer, use_cache));
+    }
+
+    #[bench]
+    fn bench_scroll_through_w_cache(bencher: &mut Bencher) {
+        bench_scroll_through(bencher, true);
+    }
+
+    #[bench]
+    fn bench_scroll_through_wo_cache(bencher: &mut Bencher) {
+        bench_scroll_through(bencher, false);
+    }
+}
+
+#[cfg(test)]
+mod test_inline_diagnostics {
+    use r3bl_core::{assert_eq2, Position};
+
+    use super::*;
+    use crate::Diagnostic;
+
+    fn get_painted_text_with_positions(
+        render_ops: &RenderOps,
+    ) -> Vec<(Position, String)> {
+        let mut acc = vec![];
+        let mut maybe_position = None;
+        for render_op in render_ops.iter() {
+            match render_op {
+                RenderOp::MoveCursorPositionRelTo(_, position) => {
+                    maybe_position = Some(*position)
+                }
+                RenderOp::PaintTextWithAttributes(text, _) => {
+                    if let Some(position) = maybe_position {
+                        acc.push((position, text.clone()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        acc
+    }
+
+    fn make_engine_and_buffer(
+        inline_diagnostics: InlineDiagnosticsMode,
+    ) -> (EditorEngine, EditorBuffer) {
+        let mut editor_engine = EditorEngine::default();
+        editor_engine.config_options.inline_diagnostics = inline_diagnostics;
+        editor_engine.current_box.style_adjusted_bounds_size = Size {
+            col_count: ch!(12),
+            row_count: ch!(2),
+        };
+
+        let mut editor_buffer = EditorBuffer::new_empty(&None, &None);
+        editor_buffer.set_lines(vec![
+            "abc".to_string(),
+            "abcdefgh".to_string(),
+            "xyz".to_string(),
+        ]);
+        editor_buffer.set_diagnostics(vec![
+            Diagnostic {
+                row_index: ch!(0),
+                severity: DiagnosticSeverity::Error,
+                message: "oops".to_string(),
+            },
+            Diagnostic {
+                row_index: ch!(1),
+                severity: DiagnosticSeverity::Warning,
+                message: "long message".to_string(),
+            },
+            // Not in the viewport.
+            Diagnostic {
+                row_index: ch!(2),
+                severity: DiagnosticSeverity::Info,
+                message: "hidden".to_string(),
+            },
+        ]);
+
+        (editor_engine, editor_buffer)
+    }
+
+    #[test]
+    fn test_render_inline_diagnostics() {
+        let (mut editor_engine, editor_buffer) =
+            make_engine_and_buffer(InlineDiagnosticsMode::Enable);
+        let has_focus = &mut HasFocus::default();
+        let render_ops = &mut render_ops!();
+
+        EditorEngineApi::render_inline_diagnostics(
+            RenderArgs {
+                editor_buffer: &editor_buffer,
+                editor_engine: &mut editor_engine,
+                has_focus,
+            },
+            render_ops,
+        );
+
+        assert_eq2!(
+            get_painted_text_with_positions(render_ops),
+            vec![
+                (position!(col_index: 4, row_index: 0), "✗ oops".to_string()),
+                // Truncated to fit the viewport.
+                (position!(col_index: 9, row_index: 1), "⚠ …".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_inline_diagnostics_disabled() {
+        let (mut editor_engine, editor_buffer) =
+            make_engine_and_buffer(InlineDiagnosticsMode::Disable);
+        let has_focus = &mut HasFocus::default();
+        let render_ops = &mut render_ops!();
+
+        EditorEngineApi::render_inline_diagnostics(
+            RenderArgs {
+                editor_buffer: &editor_buffer,
+                editor_engine: &mut editor_engine,
+                has_focus,
+            },
+            render_ops,
+        );
+
+        assert!(render_ops.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_current_line_highlight {
+    use r3bl_core::{assert_eq2, RgbValue, TuiStyle};
+
+    use super::*;
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                CaretDirection,
+                CurrentLineHighlightMode,
+                EditorEngineConfig,
+                FlexBoxId};
+
+    fn get_highlight_style() -> TuiStyle {
+        tui_style! {
+            color_bg: TuiColor::Rgb(RgbValue::from_hex("#2b2735"))
+        }
+    }
+
+    /// Returns the render ops for the content, w/ the caret on the second line.
+    fn render(has_focus: &mut HasFocus) -> RenderOps {
+        let mut editor_engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                syntax_highlight: SyntaxHighlightMode::Disable,
+                current_line_highlight: CurrentLineHighlightMode::Enable(
+                    get_highlight_style(),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        editor_engine.current_box.style_adjusted_bounds_size = Size {
+            col_count: ch!(12),
+            row_count: ch!(3),
+        };
+
+        let mut editor_buffer = EditorBuffer::new_empty(&None, &None);
+        editor_buffer.set_lines(vec!["abc".to_string(), "abcdefgh".to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut editor_engine,
+            &mut editor_buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+            &mut TestClipboard::default(),
+        );
+
+        let mut render_ops = render_ops!();
+        let mut render_args = RenderArgs {
+            editor_buffer: &editor_buffer,
+            editor_engine: &mut editor_engine,
+            has_focus,
+        };
+        EditorEngineApi::render_content(&mut render_args, &mut render_ops);
+        EditorEngineApi::render_current_line_highlight(render_args, &mut render_ops);
+        render_ops
+    }
+
+    /// Returns the background color of each [RenderOp::ApplyColors] in each row.
+    fn get_bg_colors_by_row(render_ops: &RenderOps) -> Vec<(ChUnit, Option<TuiColor>)> {
+        let mut acc = vec![];
+        let mut maybe_row_index = None;
+        for render_op in render_ops.iter() {
+            match render_op {
+                RenderOp::MoveCursorPositionRelTo(_, position) => {
+                    maybe_row_index = Some(position.row_index)
+                }
+                RenderOp::ApplyColors(maybe_style) => {
+                    if let Some(row_index) = maybe_row_index {
+                        acc.push((row_index, maybe_style.and_then(|it| it.color_bg)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        acc
+    }
+
+    #[test]
+    fn test_render_current_line_highlight() {
+        let has_focus = &mut HasFocus::default();
+        has_focus.set_id(FlexBoxId::default());
+        let render_ops = render(has_focus);
+        let color_bg = get_highlight_style().color_bg;
+
+        assert_eq2!(
+            get_bg_colors_by_row(&render_ops),
+            vec![
+                (ch!(0), None),
+                (ch!(1), color_bg),
+                // The rest of the row, after the end of the text.
+                (ch!(1), color_bg),
+            ]
+        );
+
+        // The rest of the row is filled w/ spaces, to the width of the viewport.
+        let last_ops = &render_ops.list[render_ops.len() - 4..];
+        assert_eq2!(
+            last_ops[0],
+            RenderOp::MoveCursorPositionRelTo(
+                position!(col_index: 0, row_index: 0),
+                position!(col_index: 8, row_index: 1)
+            )
+        );
+        assert_eq2!(
+            last_ops[2],
+            RenderOp::PaintTextWithAttributes("    ".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_render_current_line_highlight_without_focus() {
+        let has_focus = &mut HasFocus::default();
+        let render_ops = render(has_focus);
+
+        assert_eq2!(
+            get_bg_colors_by_row(&render_ops),
+            vec![(ch!(0), None), (ch!(1), None)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_render_extra_carets {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::FlexBoxId;
+
+    #[test]
+    fn test_render_extra_carets() {
+        let has_focus = &mut HasFocus::default();
+        has_focus.set_id(FlexBoxId::default());
+
+        let mut editor_engine = EditorEngine::default();
+        editor_engine.current_box.style_adjusted_bounds_size = Size {
+            col_count: ch!(12),
+            row_count: ch!(2),
+        };
+
+        let mut editor_buffer = EditorBuffer::new_empty(&None, &None);
+        editor_buffer.set_lines(vec![
+            "abc".to_string(),
+            "de".to_string(),
+            "f".to_string(),
+        ]);
+        // The caret in the last row is not in the viewport.
+        editor_buffer.set_extra_carets(vec![
+            position!(col_index: 2, row_index: 1),
+            position!(col_index: 0, row_index: 2),
+        ]);
+
+        let mut render_ops = render_ops!();
+        let render_args = RenderArgs {
+            editor_buffer: &editor_buffer,
+            editor_engine: &mut editor_engine,
+            has_focus,
+        };
+        EditorEngineApi::render_caret(render_args, &mut render_ops);
+
+        let origin = position!(col_index: 0, row_index: 0);
+        assert_eq2!(
+            render_ops.list[..4],
+            [
+                // The extra caret is painted dimmed, past the end of its line.
+                RenderOp::MoveCursorPositionRelTo(
+                    origin,
+                    position!(col_index: 2, row_index: 1)
+                ),
+                RenderOp::PaintTextWithAttributes(
+                    DEFAULT_CURSOR_CHAR.into(),
+                    Some(tui_style! { attrib: [reverse, dim] })
+                ),
+                // Followed by the primary caret.
+                RenderOp::MoveCursorPositionRelTo(
+                    origin,
+                    position!(col_index: 0, row_index: 0)
+                ),
+                RenderOp::PaintTextWithAttributes(
+                    "a".into(),
+                    Some(tui_style! { attrib: [reverse] })
+                ),
+            ]
+        );
+        assert_eq2!(render_ops.len(), 7);
+        // The terminal's cursor is shown at the primary caret.
+        assert_eq2!(
+            render_ops.list[5],
+            RenderOp::ShowCursor(CursorShape::SteadyBar)
+        );
+    }
+
+    #[test]
+    fn test_render_overwrite_mode_cursor_shape() {
+        let has_focus = &mut HasFocus::default();
+        has_focus.set_id(FlexBoxId::default());
+
+        let mut editor_engine = EditorEngine {
+            typing_mode: TypingMode::Overwrite,
+            ..Default::default()
+        };
+        editor_engine.current_box.style_adjusted_bounds_size = Size {
+            col_count: ch!(12),
+            row_count: ch!(2),
+        };
+
+        let mut editor_buffer = EditorBuffer::new_empty(&None, &None);
+        editor_buffer.set_lines(vec!["abc".to_string()]);
+
+        let mut render_ops = render_ops!();
+        let render_args = RenderArgs {
+            editor_buffer: &editor_buffer,
+            editor_engine: &mut editor_engine,
+            has_focus,
+        };
+        EditorEngineApi::render_caret(render_args, &mut render_ops);
+
+        assert!(render_ops
+            .list
+            .contains(&RenderOp::ShowCursor(CursorShape::SteadyBlock)));
+    }
+
+    #[test]
+    fn test_render_read_only_caret() {
+        let has_focus = &mut HasFocus::default();
+        has_focus.set_id(FlexBoxId::default());
+
+        let mut editor_engine = EditorEngine::default();
+        editor_engine.config_options.edit_mode = EditMode::ReadOnly;
+        editor_engine.current_box.style_adjusted_bounds_size = Size {
+            col_count: ch!(12),
+            row_count: ch!(2),
+        };
+
+        let mut editor_buffer = EditorBuffer::new_empty(&None, &None);
+        editor_buffer.set_lines(vec!["abc".to_string()]);
+
+        let mut render_ops = render_ops!();
+        let render_args = RenderArgs {
+            editor_buffer: &editor_buffer,
+            editor_engine: &mut editor_engine,
+            has_focus,
+        };
+        EditorEngineApi::render_caret(render_args, &mut render_ops);
+
+        // The caret is a thin bar, instead of a block.
+        assert_eq2!(
+            render_ops.list[1],
+            RenderOp::PaintTextWithAttributes(
+                "a".into(),
+                Some(tui_style! { attrib: [underline] })
+            )
+        );
+        // The terminal's cursor isn't shown.
+        assert!(!render_ops
+            .list
+            .iter()
+            .any(|it| matches!(it, RenderOp::ShowCursor(_))));
+    }
+}
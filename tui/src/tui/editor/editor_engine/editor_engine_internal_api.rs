@@ -18,8 +18,10 @@
 use std::{cmp::Ordering, collections::HashMap, mem::replace};
 
 use r3bl_core::{ch,
+                get_scroll_offset_for_ratio,
                 position,
                 ChUnit,
+                ChUnitPrimitiveType,
                 Position,
                 UnicodeString,
                 UnicodeStringSegmentSliceResult};
@@ -27,6 +29,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::{editor_buffer_clipboard_support,
             editor_buffer_clipboard_support::ClipboardService,
+            get_col_index_in_segment,
+            get_renumbered_lines,
+            get_segment_index,
+            get_wrap_segments,
+            selection_expansion,
             CaretDirection,
             CaretKind,
             EditorArgs,
@@ -34,8 +41,13 @@ use crate::{editor_buffer_clipboard_support,
             EditorBuffer,
             EditorBufferApi,
             EditorEngine,
+            ExpandSelectionBy,
             LineMode,
-            ScrollOffset};
+            LineWrapMode,
+            RenumberedLine,
+            ScrollOffset,
+            TaskCheckbox,
+            DEFAULT_SYN_HI_FILE_EXT};
 
 /// Functions that implement the editor engine.
 pub struct EditorEngineInternalApi;
@@ -46,7 +58,12 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
-        caret_mut::up(buffer, engine, select_mode)
+        match engine.config_options.line_wrap {
+            LineWrapMode::None => caret_mut::up(buffer, engine, select_mode),
+            LineWrapMode::Word | LineWrapMode::Char => {
+                caret_mut::to_visual_row(buffer, engine, CaretDirection::Up, select_mode)
+            }
+        }
     }
 
     pub fn left(
@@ -70,7 +87,15 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
-        caret_mut::down(buffer, engine, select_mode)
+        match engine.config_options.line_wrap {
+            LineWrapMode::None => caret_mut::down(buffer, engine, select_mode),
+            LineWrapMode::Word | LineWrapMode::Char => caret_mut::to_visual_row(
+                buffer,
+                engine,
+                CaretDirection::Down,
+                select_mode,
+            ),
+        }
     }
 
     pub fn page_up(
@@ -94,7 +119,34 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
-        caret_mut::to_start_of_line(buffer, engine, select_mode)
+        match engine.config_options.line_wrap {
+            LineWrapMode::None => {
+                caret_mut::to_start_of_line(buffer, engine, select_mode)
+            }
+            LineWrapMode::Word | LineWrapMode::Char => caret_mut::to_edge_of_visual_row(
+                buffer,
+                engine,
+                VisualRowEdge::Start,
+                select_mode,
+            ),
+        }
+    }
+
+    pub fn to_row(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        row_index: ChUnit,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::to_row(buffer, engine, row_index, select_mode)
+    }
+
+    pub fn to_position(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        position: Position,
+    ) -> Option<()> {
+        caret_mut::to_position(buffer, engine, position)
     }
 
     pub fn end(
@@ -102,7 +154,15 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
-        caret_mut::to_end_of_line(buffer, engine, select_mode)
+        match engine.config_options.line_wrap {
+            LineWrapMode::None => caret_mut::to_end_of_line(buffer, engine, select_mode),
+            LineWrapMode::Word | LineWrapMode::Char => caret_mut::to_edge_of_visual_row(
+                buffer,
+                engine,
+                VisualRowEdge::End,
+                select_mode,
+            ),
+        }
     }
 
     pub fn select_all(buffer: &mut EditorBuffer, select_mode: SelectMode) -> Option<()> {
@@ -113,10 +173,39 @@ impl EditorEngineInternalApi {
         caret_mut::clear_selection(buffer)
     }
 
+    pub fn expand_selection(
+        buffer: &mut EditorBuffer,
+        by: ExpandSelectionBy,
+    ) -> Option<()> {
+        selection_expansion::expand_selection(buffer, by)?;
+        None
+    }
+
+    pub fn shrink_selection(buffer: &mut EditorBuffer) -> Option<()> {
+        selection_expansion::shrink_selection(buffer)?;
+        None
+    }
+
     pub fn validate_scroll(args: EditorArgsMut<'_>) {
         scroll_editor_buffer::validate_scroll(args);
     }
 
+    pub fn center_caret_vertically(args: EditorArgsMut<'_>) {
+        scroll_editor_buffer::center_caret_vertically(args);
+    }
+
+    pub fn scroll_to_ratio(args: EditorArgsMut<'_>, ratio: f64) {
+        scroll_editor_buffer::scroll_to_ratio(args, ratio);
+    }
+
+    pub fn scroll_up(args: EditorArgsMut<'_>, row_count: ChUnit) {
+        scroll_editor_buffer::scroll_up(args, row_count);
+    }
+
+    pub fn scroll_down(args: EditorArgsMut<'_>, row_count: ChUnit) {
+        scroll_editor_buffer::scroll_down(args, row_count);
+    }
+
     pub fn string_at_caret(
         buffer: &EditorBuffer,
         engine: &EditorEngine,
@@ -167,9 +256,29 @@ impl EditorEngineInternalApi {
 
     pub fn copy_editor_selection_to_clipboard(
         buffer: &EditorBuffer,
+        engine: &EditorEngine,
         clipboard: &mut impl ClipboardService,
     ) {
-        editor_buffer_clipboard_support::copy_to_clipboard(buffer, clipboard)
+        editor_buffer_clipboard_support::copy_to_clipboard(
+            buffer,
+            &engine.config_options.clipboard_export,
+            clipboard,
+        )
+    }
+
+    pub fn renumber_ordered_lists(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::renumber_ordered_lists(buffer, engine)
+    }
+
+    pub fn toggle_checkbox(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        checkbox: TaskCheckbox,
+    ) -> Option<()> {
+        content_mut::toggle_checkbox(buffer, engine, checkbox)
     }
 
     pub fn paste_clipboard_content_into_editor(
@@ -178,6 +287,10 @@ impl EditorEngineInternalApi {
     ) {
         editor_buffer_clipboard_support::paste_from_clipboard(args, clipboard)
     }
+
+    pub fn paste_text_into_editor(args: EditorArgsMut<'_>, text: &str) {
+        editor_buffer_clipboard_support::insert_text_at_caret(args, text)
+    }
 }
 
 /// Helper macros just for this module.
@@ -292,6 +405,13 @@ mod caret_get {
     }
 }
 
+/// Which end of a row of the viewport to move the caret to, when the lines are wrapped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VisualRowEdge {
+    Start,
+    End,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum SelectMode {
     Enabled,
@@ -539,6 +659,94 @@ mod caret_mut {
         None
     }
 
+    /// Move the caret to the given row (clipped to the last row), scrolling it into view.
+    pub fn to_row(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        row_index: ChUnit,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+        multiline_disabled_check_early_return!(editor_engine, @None);
+
+        // This is only set if select_mode is enabled.
+        let maybe_previous_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        let mut desired_caret_adj_row = row_index;
+        scroll_editor_buffer::clip_caret_row_to_content_height(
+            editor_buffer,
+            &mut desired_caret_adj_row,
+        );
+        let current_caret_adj_row =
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+
+        match desired_caret_adj_row.cmp(&current_caret_adj_row) {
+            Ordering::Greater => scroll_editor_buffer::change_caret_row_by(
+                EditorArgsMut {
+                    editor_engine,
+                    editor_buffer,
+                },
+                desired_caret_adj_row - current_caret_adj_row,
+                CaretDirection::Down,
+            ),
+            Ordering::Less => scroll_editor_buffer::change_caret_row_by(
+                EditorArgsMut {
+                    editor_engine,
+                    editor_buffer,
+                },
+                current_caret_adj_row - desired_caret_adj_row,
+                CaretDirection::Up,
+            ),
+            Ordering::Equal => {}
+        }
+
+        // This is only set if select_mode is enabled.
+        let maybe_current_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        // This is only runs if select_mode is enabled.
+        select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+            editor_buffer,
+            maybe_previous_caret_display_position,
+            maybe_current_caret_display_position,
+        );
+
+        None
+    }
+
+    /// Move the caret to the given scroll adjusted position (its col is clipped to the
+    /// width of the line), scrolling it into view. The selection is left as is.
+    pub fn to_position(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        position: Position,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        to_row(
+            editor_buffer,
+            editor_engine,
+            position.row_index,
+            SelectMode::Disabled,
+        );
+
+        let caret_adj_row = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let line_display_width =
+            content_get::line_display_width_at_row_index(editor_buffer, caret_adj_row);
+        let viewport_width = editor_engine.horizontal_scroll_width();
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+        scroll_editor_buffer::set_caret_col(
+            caret,
+            scroll_offset,
+            viewport_width,
+            line_display_width,
+            std::cmp::min(position.col_index, line_display_width),
+        );
+
+        None
+    }
+
     pub fn page_down(
         editor_buffer: &mut EditorBuffer,
         editor_engine: &mut EditorEngine,
@@ -633,7 +841,7 @@ mod caret_mut {
                         editor_buffer,
                         editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index,
                     );
-                let viewport_width = editor_engine.viewport_width();
+                let viewport_width = editor_engine.horizontal_scroll_width();
                 validate_editor_buffer_change::apply_change(
                     editor_buffer,
                     editor_engine,
@@ -653,6 +861,193 @@ mod caret_mut {
         None
     }
 
+    /// Move the caret to the row of the viewport above or below it, when the lines are
+    /// wrapped (see [crate::line_wrap]). This may be on the same line of the buffer. It
+    /// keeps its distance from the start of the row, as far as the new row allows. At the
+    /// top (or bottom) of the buffer, this acts like [up] (or [down]).
+    pub fn to_visual_row(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        direction: CaretDirection,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+        multiline_disabled_check_early_return!(editor_engine, @None);
+
+        let viewport_width = editor_engine.viewport_width();
+        let line_wrap = editor_engine.config_options.line_wrap.clone();
+        let get_segments = |row_index: usize| {
+            editor_buffer.get_lines().get(row_index).map(|line| {
+                (
+                    line.clone(),
+                    get_wrap_segments(line, viewport_width, &line_wrap),
+                )
+            })
+        };
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret_adj.row_index);
+        let (_, segments) = get_segments(row_index)?;
+        let segment_index = get_segment_index(&segments, caret_adj.col_index);
+        let col_offset = caret_adj.col_index - segments[segment_index].start_col_index;
+
+        // The (row index, segment index) to move to.
+        let (desired_row_index, desired_segment_index) = match direction {
+            CaretDirection::Up if segment_index > 0 => (row_index, segment_index - 1),
+            CaretDirection::Up if row_index > 0 => {
+                let (_, segments) = get_segments(row_index - 1)?;
+                (row_index - 1, segments.len() - 1)
+            }
+            CaretDirection::Up => return up(editor_buffer, editor_engine, select_mode),
+            CaretDirection::Down if segment_index + 1 < segments.len() => {
+                (row_index, segment_index + 1)
+            }
+            CaretDirection::Down if row_index + 1 < editor_buffer.get_lines().len() => {
+                (row_index + 1, 0)
+            }
+            CaretDirection::Down => {
+                return down(editor_buffer, editor_engine, select_mode)
+            }
+            CaretDirection::Left | CaretDirection::Right => return None,
+        };
+
+        let (desired_line, desired_segments) = get_segments(desired_row_index)?;
+        let desired_col_index = get_col_index_in_segment(
+            &desired_line,
+            &desired_segments,
+            desired_segment_index,
+            col_offset,
+        );
+
+        // This is only set if select_mode is enabled.
+        let maybe_previous_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        match desired_row_index.cmp(&row_index) {
+            Ordering::Less => scroll_editor_buffer::change_caret_row_by(
+                EditorArgsMut {
+                    editor_engine,
+                    editor_buffer,
+                },
+                ch!(1),
+                CaretDirection::Up,
+            ),
+            Ordering::Greater => scroll_editor_buffer::change_caret_row_by(
+                EditorArgsMut {
+                    editor_engine,
+                    editor_buffer,
+                },
+                ch!(1),
+                CaretDirection::Down,
+            ),
+            Ordering::Equal => {}
+        }
+        set_caret_col_in_line(editor_buffer, editor_engine, desired_col_index);
+
+        // This is only set if select_mode is enabled.
+        let maybe_current_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        // This is only runs if select_mode is enabled.
+        match desired_row_index == row_index {
+            true => select_mode.handle_selection_single_line_caret_movement(
+                editor_buffer,
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position,
+            ),
+            false => select_mode
+                .update_selection_based_on_caret_movement_in_multiple_lines(
+                    editor_buffer,
+                    maybe_previous_caret_display_position,
+                    maybe_current_caret_display_position,
+                ),
+        };
+
+        None
+    }
+
+    /// Move the caret to the start (or end) of the row of the viewport that it is on, when
+    /// the lines are wrapped (see [crate::line_wrap]). The end of a row that isn't the last
+    /// one of its line is before its last grapheme cluster, since the col after it is on
+    /// the next row. Like [to_start_of_line] and [to_end_of_line], this selects w/
+    /// [SelectMode::Enabled] by calling [left] or [right] repeatedly.
+    pub fn to_edge_of_visual_row(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        edge: VisualRowEdge,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret_adj.row_index))?;
+        let segments = get_wrap_segments(
+            line,
+            editor_engine.viewport_width(),
+            &editor_engine.config_options.line_wrap,
+        );
+        let segment_index = get_segment_index(&segments, caret_adj.col_index);
+        let desired_col_index = match edge {
+            VisualRowEdge::Start => segments[segment_index].start_col_index,
+            VisualRowEdge::End => get_col_index_in_segment(
+                line,
+                &segments,
+                segment_index,
+                ch!(ChUnitPrimitiveType::MAX),
+            ),
+        };
+
+        match select_mode {
+            SelectMode::Enabled => loop {
+                let col_index =
+                    editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+                match col_index.cmp(&desired_col_index) {
+                    Ordering::Less => right(editor_buffer, editor_engine, select_mode),
+                    Ordering::Greater => left(editor_buffer, editor_engine, select_mode),
+                    Ordering::Equal => break,
+                };
+                // Stop if the caret can't move any further.
+                if editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index
+                    == col_index
+                {
+                    break;
+                }
+            },
+            SelectMode::Disabled => {
+                set_caret_col_in_line(editor_buffer, editor_engine, desired_col_index);
+            }
+        }
+
+        None
+    }
+
+    /// Move the caret to the given col in its line (clipped to the line's width). The
+    /// selection is left as is.
+    fn set_caret_col_in_line(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        col_index: ChUnit,
+    ) {
+        let line_display_width =
+            content_get::line_display_width_at_caret(editor_buffer, editor_engine);
+        let viewport_width = editor_engine.horizontal_scroll_width();
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| {
+                scroll_editor_buffer::set_caret_col(
+                    caret,
+                    scroll_offset,
+                    viewport_width,
+                    line_display_width,
+                    std::cmp::min(col_index, line_display_width),
+                );
+            },
+        );
+    }
+
     pub fn clear_selection(editor_buffer: &mut EditorBuffer) -> Option<()> {
         editor_buffer.clear_selection();
 
@@ -765,7 +1160,7 @@ mod caret_mut {
                     editor_engine,
                 );
 
-                let viewport_width = editor_engine.viewport_width();
+                let viewport_width = editor_engine.horizontal_scroll_width();
 
                 let maybe_char_to_right_of_caret =
                     content_get::string_to_right_of_caret(editor_buffer, editor_engine);
@@ -1399,7 +1794,7 @@ mod content_mut {
                 let new_line =
                     cur_line.delete_char_at_display_col(delete_at_this_display_col)?;
 
-                let viewport_width = engine.viewport_width();
+                let viewport_width = engine.horizontal_scroll_width();
                 validate_editor_buffer_change::apply_change(
                     buffer,
                     engine,
@@ -1432,7 +1827,7 @@ mod content_mut {
                 buffer: &mut EditorBuffer,
                 engine: &mut EditorEngine,
             ) -> Option<()> {
-                let viewport_width = engine.viewport_width();
+                let viewport_width = engine.horizontal_scroll_width();
 
                 let this_line = content_get::line_at_caret_to_string(buffer, engine)?;
                 let prev_line =
@@ -1491,8 +1886,10 @@ mod content_mut {
             if let Some(selection_range) = my_selection_map.get(selected_row_index) {
                 let line_width = buffer.get_line_display_width(selected_row_index);
 
-                // Remove entire line.
-                if selection_range.start_display_col_index == ch!(0)
+                // Remove entire line. Deleting a block selection never removes lines, it
+                // only removes the selected cols from each of them.
+                if !my_selection_map.is_block()
+                    && selection_range.start_display_col_index == ch!(0)
                     && selection_range.end_display_col_index == line_width
                 {
                     vec_row_indices_to_remove.push(selected_row_index);
@@ -1572,7 +1969,7 @@ mod content_mut {
         let (new_line, char_display_width) =
             line.insert_char_at_display_col(ch!(caret_adj.col_index), chunk)?;
 
-        let viewport_width = editor_engine.viewport_width();
+        let viewport_width = editor_engine.horizontal_scroll_width();
 
         validate_editor_buffer_change::apply_change(
             editor_buffer,
@@ -1631,7 +2028,7 @@ mod content_mut {
         // Make sure there's a line at caret_adj_row.
         let _ = editor_buffer.get_lines().get(caret_adj_row)?;
 
-        let viewport_width = editor_engine.viewport_width();
+        let viewport_width = editor_engine.horizontal_scroll_width();
 
         validate_editor_buffer_change::apply_change(
             editor_buffer,
@@ -1659,6 +2056,79 @@ mod content_mut {
 
         None
     }
+
+    /// Renumber the ordered lists in a Markdown buffer (see
+    /// [crate::ordered_list_renumbering]). If the number on the caret's line gets
+    /// wider or narrower, the caret is moved so that it stays on the same character.
+    pub fn renumber_ordered_lists(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        if editor_buffer.get_maybe_file_extension() != Some(DEFAULT_SYN_HI_FILE_EXT) {
+            return None;
+        }
+
+        let renumbered_lines = get_renumbered_lines(editor_buffer.get_lines());
+        if renumbered_lines.is_empty() {
+            return None;
+        }
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let viewport_width = editor_engine.horizontal_scroll_width();
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, caret, scroll_offset| {
+                for RenumberedLine {
+                    row_index,
+                    line,
+                    number_col_index,
+                    width_delta,
+                } in renumbered_lines
+                {
+                    lines[row_index] = UnicodeString::from(line);
+
+                    let is_caret_after_number = ch!(row_index) == caret_adj.row_index
+                        && caret_adj.col_index > ch!(number_col_index);
+                    if is_caret_after_number && width_delta != 0 {
+                        let desired_col = ch!(@to_usize caret_adj.col_index)
+                            .saturating_add_signed(width_delta);
+                        scroll_editor_buffer::set_caret_col(
+                            caret,
+                            scroll_offset,
+                            viewport_width,
+                            lines[row_index].display_width,
+                            ch!(desired_col),
+                        );
+                    }
+                }
+            },
+        );
+
+        None
+    }
+
+    /// Toggle the given checkbox (see [crate::checkbox_toggle]). Since `[ ]` and `[x]`
+    /// have the same width, the caret doesn't move.
+    pub fn toggle_checkbox(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        checkbox: TaskCheckbox,
+    ) -> Option<()> {
+        let line = editor_buffer.get_lines().get(checkbox.row_index)?;
+        let new_line = checkbox.get_toggled_line(&line.string);
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                lines[checkbox.row_index] = UnicodeString::from(new_line);
+            },
+        );
+
+        None
+    }
 }
 
 /// This is marked as `pub` because `apply_change` is needed by `cargo doc`.
@@ -1805,7 +2275,7 @@ pub mod validate_editor_buffer_change {
             editor_engine,
         } = args;
 
-        let viewport_width = editor_engine.viewport_width();
+        let viewport_width = editor_engine.horizontal_scroll_width();
 
         let (lines, caret, scroll_offset, _) = editor_buffer.get_mut();
         let row_idx = EditorBuffer::calc_scroll_adj_caret_row(caret, scroll_offset);
@@ -2126,6 +2596,134 @@ mod scroll_editor_buffer {
         EditorBuffer::calc_scroll_adj_caret_row(caret, scroll_offset)
     }
 
+    /// Scroll vertically so that the caret is in the middle row of the viewport (w/out
+    /// changing the caret's position in the buffer). Near the start or end of the
+    /// buffer, where this isn't possible, the scroll offset is clamped so that the
+    /// viewport does not go past either end. This is used when
+    /// [crate::TypewriterScrollingMode::Enable] is set.
+    pub fn center_caret_vertically(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let viewport_height = editor_engine.viewport_height();
+        if viewport_height == ch!(0) {
+            return;
+        }
+
+        let caret_row_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let max_scroll_offset_row = editor_buffer.len() - viewport_height;
+        let desired_scroll_offset_row =
+            std::cmp::min(caret_row_adj - viewport_height / 2, max_scroll_offset_row);
+
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+        scroll_offset.row_index = desired_scroll_offset_row;
+        caret.row_index = caret_row_adj - desired_scroll_offset_row;
+    }
+
+    /// Scroll vertically so that the viewport shows the position at `ratio` of the
+    /// content. See [get_scroll_offset_for_ratio] for how the `ratio` is clamped. The
+    /// caret stays on the same row of the viewport (like
+    /// [PageDown](crate::EditorEvent::PageDown)), clipped to the last line, and its column
+    /// is clipped to the width of its new line. The selection is left as is.
+    pub fn scroll_to_ratio(args: EditorArgsMut<'_>, ratio: f64) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        empty_check_early_return!(editor_buffer, @Nothing);
+
+        let viewport_height = editor_engine.viewport_height();
+        if viewport_height == ch!(0) {
+            return;
+        }
+
+        let desired_scroll_offset_row =
+            get_scroll_offset_for_ratio(ratio, editor_buffer.len(), viewport_height);
+        scroll_to_row(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            desired_scroll_offset_row,
+        );
+    }
+
+    /// Scroll the viewport up by `row_count` rows (eg: when the mouse wheel is turned),
+    /// but not past the start of the content. The caret stays on the same row of the
+    /// viewport.
+    pub fn scroll_up(args: EditorArgsMut<'_>, row_count: ChUnit) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        empty_check_early_return!(editor_buffer, @Nothing);
+
+        let desired_scroll_offset_row =
+            editor_buffer.get_scroll_offset().row_index - row_count;
+        scroll_to_row(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            desired_scroll_offset_row,
+        );
+    }
+
+    /// Scroll the viewport down by `row_count` rows (eg: when the mouse wheel is turned),
+    /// but the last page is never scrolled past the end of the content. The caret stays
+    /// on the same row of the viewport.
+    pub fn scroll_down(args: EditorArgsMut<'_>, row_count: ChUnit) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        empty_check_early_return!(editor_buffer, @Nothing);
+
+        let max_scroll_offset_row = editor_buffer.len() - editor_engine.viewport_height();
+        let desired_scroll_offset_row = std::cmp::min(
+            editor_buffer.get_scroll_offset().row_index + row_count,
+            max_scroll_offset_row,
+        );
+        scroll_to_row(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            desired_scroll_offset_row,
+        );
+    }
+
+    /// Make `desired_scroll_offset_row` the first row of the viewport, and keep the caret
+    /// on the same row of the viewport (clipped to the content).
+    fn scroll_to_row(args: EditorArgsMut<'_>, desired_scroll_offset_row: ChUnit) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_engine.viewport_height() == ch!(0) {
+            return;
+        }
+
+        let mut desired_caret_adj_row =
+            desired_scroll_offset_row + editor_buffer.get_caret(CaretKind::Raw).row_index;
+        clip_caret_row_to_content_height(editor_buffer, &mut desired_caret_adj_row);
+
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+        scroll_offset.row_index = desired_scroll_offset_row;
+        caret.row_index = desired_caret_adj_row - desired_scroll_offset_row;
+
+        clip_caret_to_content_width(EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        });
+    }
+
     /// Check whether caret is vertically within the viewport. This is meant to be used after resize
     /// events and for [inc_caret_col], [inc_caret_row] operations. Note that [dec_caret_col] and
     /// [dec_caret_row] are handled differently (and not by this function) since they can never be
@@ -2270,7 +2868,7 @@ mod scroll_editor_buffer {
                 editor_engine,
             } = args;
 
-            let viewport_width = editor_engine.viewport_width();
+            let viewport_width = editor_engine.horizontal_scroll_width();
 
             let caret_col_adj =
                 editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
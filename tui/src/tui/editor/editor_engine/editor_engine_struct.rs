@@ -15,13 +15,23 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
-use r3bl_core::ChUnit;
+use r3bl_core::{ch, ChUnit, ChUnitPrimitiveType, TuiStyle};
 use serde::{Deserialize, Serialize};
 use syntect::{highlighting::Theme, parsing::SyntaxSet};
 
-use crate::{load_default_theme, try_load_r3bl_theme, PartialFlexBox};
+use crate::{load_default_theme,
+            try_load_r3bl_theme,
+            EditorContent,
+            EditorGutter,
+            GuttersLayout,
+            IndentStyle,
+            MdIncrementalHighlighter,
+            MinimapLayout,
+            PartialFlexBox,
+            RegisteredEditorGutter,
+            SyntectLineCache};
 
 /// Do not create this struct directly. Please use [new()](EditorEngine::new) instead.
 ///
@@ -45,6 +55,35 @@ pub struct EditorEngine {
     pub syntax_set: SyntaxSet,
     /// Syntax highlighting support. This is a very heavy object to create, re-use it.
     pub theme: Theme,
+    /// Set by
+    /// [EditorEngineApi::begin_transaction](crate::EditorEngineApi::begin_transaction).
+    pub maybe_transaction: Option<EditorTransaction>,
+    /// Set by [EditorEngineApi::render_engine](crate::EditorEngineApi::render_engine) when
+    /// the minimap is enabled and there is enough room to show it.
+    pub maybe_minimap_layout: Option<MinimapLayout>,
+    /// Use [register_gutter](EditorEngine::register_gutter) to add to this. It is kept
+    /// sorted by [RegisteredEditorGutter::order].
+    #[serde(skip)]
+    pub gutters: Vec<RegisteredEditorGutter>,
+    /// Set by [EditorEngineApi::render_engine](crate::EditorEngineApi::render_engine) when
+    /// there are gutters, and there is enough room to show them.
+    pub maybe_gutters_layout: Option<GuttersLayout>,
+    /// Toggled by [crate::EditorEvent::ToggleTypingMode] when
+    /// [OverwriteMode::Enable] is set.
+    pub typing_mode: TypingMode,
+    /// Set by [set_syntax_language](EditorEngine::set_syntax_language) to highlight the
+    /// buffer as this language, instead of inferring it from the file extension.
+    pub maybe_syntax_language: Option<String>,
+    /// The lines that were highlighted w/ [syntect], so that they aren't highlighted
+    /// again on each render. If the `theme` is changed, call
+    /// [invalidate](SyntectLineCache::invalidate) on it.
+    #[serde(skip)]
+    pub syntect_line_cache: SyntectLineCache,
+    /// The last Markdown document that was highlighted, so that an edit only causes the
+    /// blocks that it changed to be parsed again. If the `theme` is changed, call
+    /// [invalidate](MdIncrementalHighlighter::invalidate) on it.
+    #[serde(skip)]
+    pub md_incremental_highlighter: MdIncrementalHighlighter,
 }
 
 impl Default for EditorEngine {
@@ -60,9 +99,46 @@ impl EditorEngine {
             config_options,
             syntax_set: SyntaxSet::load_defaults_newlines(),
             theme: try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme()),
+            maybe_transaction: None,
+            maybe_minimap_layout: None,
+            gutters: vec![],
+            maybe_gutters_layout: None,
+            typing_mode: TypingMode::Insert,
+            maybe_syntax_language: None,
+            syntect_line_cache: SyntectLineCache::default(),
+            md_incremental_highlighter: MdIncrementalHighlighter::default(),
         }
     }
 
+    /// Add a gutter that is painted to the left of the text. Gutters are painted from
+    /// left to right in ascending `order`, and gutters w/ the same `order` are painted in
+    /// the order in which they were registered.
+    pub fn register_gutter(&mut self, order: i32, gutter: impl EditorGutter + 'static) {
+        self.gutters.push(RegisteredEditorGutter {
+            order,
+            gutter: Arc::new(gutter),
+        });
+        // This is a stable sort, which preserves the registration order for ties.
+        self.gutters.sort_by_key(|it| it.order);
+    }
+
+    /// Remove all the gutters.
+    pub fn clear_gutters(&mut self) {
+        self.gutters.clear();
+        self.maybe_gutters_layout = None;
+    }
+
+    /// Highlight the buffer as the given `language` (eg: `"rust"`, `"Python"`, or an
+    /// extension like `"rs"`), regardless of its file extension. It is re-highlighted on
+    /// the next render. When the language isn't found, the buffer is shown as plain text.
+    /// See [crate::try_get_syntax_ref_by_language].
+    pub fn set_syntax_language(&mut self, language: &str) {
+        self.maybe_syntax_language = Some(language.to_string());
+    }
+
+    /// Go back to inferring the language from the buffer's file extension.
+    pub fn clear_syntax_language(&mut self) { self.maybe_syntax_language = None; }
+
     pub fn viewport_width(&self) -> ChUnit {
         self.current_box.style_adjusted_bounds_size.col_count
     }
@@ -70,6 +146,27 @@ impl EditorEngine {
     pub fn viewport_height(&self) -> ChUnit {
         self.current_box.style_adjusted_bounds_size.row_count
     }
+
+    /// How far the caret can move to the right in the viewport, before it is scrolled
+    /// horizontally. When the lines are wrapped (see [LineWrapMode]) they are never
+    /// scrolled horizontally, so there is no limit.
+    pub fn horizontal_scroll_width(&self) -> ChUnit {
+        match self.config_options.line_wrap {
+            LineWrapMode::None => self.viewport_width(),
+            LineWrapMode::Word | LineWrapMode::Char => ch!(ChUnitPrimitiveType::MAX),
+        }
+    }
+}
+
+/// An undo transaction that is in progress. Nested transactions are flattened into the
+/// outermost one, so only the `depth` is tracked for them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EditorTransaction {
+    /// The number of transactions that have begun, and have not been committed yet.
+    pub depth: usize,
+    /// Snapshot of the content (including the caret, scroll offset, and selection) when
+    /// the outermost transaction began. This is restored on rollback.
+    pub content_before: EditorContent,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -77,6 +174,20 @@ pub struct EditorEngineConfig {
     pub multiline_mode: LineMode,
     pub syntax_highlight: SyntaxHighlightMode,
     pub edit_mode: EditMode,
+    pub clipboard_export: ClipboardExportMode,
+    pub inline_diagnostics: InlineDiagnosticsMode,
+    pub minimap: MinimapMode,
+    pub list_renumbering: ListRenumberingMode,
+    pub typewriter_scrolling: TypewriterScrollingMode,
+    pub overwrite_mode: OverwriteMode,
+    pub current_line_highlight: CurrentLineHighlightMode,
+    pub multi_caret: MultiCaretMode,
+    pub horizontal_overflow_markers: HorizontalOverflowMarkersMode,
+    pub checkbox_toggle: CheckboxToggleMode,
+    pub line_wrap: LineWrapMode,
+    /// Used when the [crate::EditorBuffer] doesn't have its own, eg: when it wasn't
+    /// detected from its content.
+    pub indent_style: IndentStyle,
 }
 
 mod editor_engine_config_options_impl {
@@ -88,6 +199,18 @@ mod editor_engine_config_options_impl {
                 multiline_mode: LineMode::MultiLine,
                 syntax_highlight: SyntaxHighlightMode::Enable,
                 edit_mode: EditMode::ReadWrite,
+                clipboard_export: ClipboardExportMode::PlainText,
+                inline_diagnostics: InlineDiagnosticsMode::Disable,
+                minimap: MinimapMode::Disable,
+                list_renumbering: ListRenumberingMode::Disable,
+                typewriter_scrolling: TypewriterScrollingMode::Disable,
+                overwrite_mode: OverwriteMode::Disable,
+                current_line_highlight: CurrentLineHighlightMode::Disable,
+                multi_caret: MultiCaretMode::Disable,
+                horizontal_overflow_markers: HorizontalOverflowMarkersMode::Disable,
+                checkbox_toggle: CheckboxToggleMode::Disable,
+                line_wrap: LineWrapMode::None,
+                indent_style: IndentStyle::default(),
             }
         }
     }
@@ -110,3 +233,156 @@ pub enum SyntaxHighlightMode {
     Disable,
     Enable,
 }
+
+/// Controls which formats are placed on the clipboard when the selection is copied or
+/// cut.
+///
+/// - [ClipboardExportMode::PlainText] only copies the selected text as is.
+/// - [ClipboardExportMode::PlainTextAndHtml] also generates an HTML rendition of the
+///   selection when the buffer contains Markdown, and hands both to
+///   [ClipboardService::try_to_put_rich_content_into_clipboard](crate::ClipboardService::try_to_put_rich_content_into_clipboard).
+///   The [SystemClipboard](crate::SystemClipboard) can only hold the plain text, so the
+///   HTML only reaches other apps w/ a [ClipboardService](crate::ClipboardService) that
+///   can hold multiple formats.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClipboardExportMode {
+    PlainText,
+    PlainTextAndHtml,
+}
+
+/// Controls whether the [crate::Diagnostic]s in the [crate::EditorBuffer] are also
+/// painted as dim text after the end of the line that they apply to. This text is not
+/// part of the content, so it does not affect the caret, scrolling, or selection.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InlineDiagnosticsMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether a condensed overview of the document is painted in a narrow column
+/// on the right side of the editor. This column is taken away from the text, and it is
+/// not shown if the editor is too narrow. See [crate::editor_minimap] for details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MinimapMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether ordered lists in Markdown are renumbered after each edit, so that
+/// their numbers stay sequential when items are inserted or deleted. The renumbering is
+/// part of the same undo step as the edit. See [crate::ordered_list_renumbering] for
+/// details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListRenumberingMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether the viewport scrolls so that the caret stays in its middle row
+/// ("typewriter" scrolling), after each event that is applied, and each render. Near the
+/// start or end of the buffer, the caret moves away from the middle instead. Since this
+/// is checked every time, it can be toggled at runtime by changing
+/// [EditorEngine::config_options]: enabling it recenters once, and disabling it leaves
+/// the scroll offset as is.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypewriterScrollingMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether the Insert key toggles the [EditorEngine::typing_mode] between
+/// [TypingMode::Insert] and [TypingMode::Overwrite]. When this is disabled, the Insert key
+/// is ignored, and the editor always inserts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverwriteMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether the line that the caret is on gets a background color across the
+/// full width of the viewport, including the empty space after the end of its text. Only
+/// the `color_bg` of the given [TuiStyle] is used, and it is applied to the spans in the
+/// line that don't have a background of their own. The selection and the caret are
+/// painted on top of it. The highlight is only shown when the editor has focus.
+///
+/// Use [crate::get_current_line_style] for a subtle default that adapts to the terminal's
+/// color support, or pass a style from your app's stylesheet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CurrentLineHighlightMode {
+    Disable,
+    Enable(TuiStyle),
+}
+
+/// Controls whether the checkboxes of task list items in Markdown (eg: `- [ ] todo`) can
+/// be toggled between `[ ]` and `[x]`, w/ `Ctrl + Space` on the caret's line, or by
+/// clicking on the checkbox w/ the mouse. Each toggle is a single undo step, and the
+/// caret doesn't move. On a line that isn't a task list item, the event is not applied.
+/// See [crate::checkbox_toggle] for details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckboxToggleMode {
+    Disable,
+    Enable,
+}
+
+/// Whether more carets can be added to the editor, so that the same edit is made in
+/// multiple places at once. When this is enabled:
+/// - `Ctrl + Alt + Up` adds a caret above the topmost caret, and `Ctrl + Alt + Down`
+///   adds one below the bottommost caret.
+/// - Typing, deleting, and moving the caret (w/o selecting) applies to all the carets.
+///   Carets that end up in the same place are merged, and each edit is a single undo step.
+/// - Any other event (eg: selecting, or pasting), and `Esc`, removes the extra carets.
+///
+/// See [crate::multi_caret] for the details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultiCaretMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether the lines that are scrolled horizontally, or don't fit in the
+/// viewport, get `‹` and `›` markers (painted w/ the given [TuiStyle]) at the edges where
+/// some of their text is hidden. The markers are painted over the text at the edges, so
+/// they don't change where the caret is, or how the text scrolls. See
+/// [crate::HorizontalOverflowMarkers] for details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HorizontalOverflowMarkersMode {
+    Disable,
+    Enable(TuiStyle),
+}
+
+/// Controls whether the lines that are wider than the viewport are wrapped onto more
+/// rows of it, instead of being scrolled horizontally. The lines in the
+/// [crate::EditorBuffer] aren't changed, only how they are painted.
+///
+/// - [LineWrapMode::None] doesn't wrap.
+/// - [LineWrapMode::Word] wraps after the last whitespace that fits in the row, and only
+///   splits words that are wider than the viewport.
+/// - [LineWrapMode::Char] wraps before the first grapheme cluster that doesn't fit.
+///
+/// When the lines are wrapped, `Up`, `Down`, `Home`, and `End` move the caret on the rows
+/// of the viewport, instead of the lines of the buffer. The overflow markers (see
+/// [HorizontalOverflowMarkersMode]) aren't painted, since nothing is hidden to the left
+/// or right. See [crate::line_wrap] for details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineWrapMode {
+    None,
+    Word,
+    Char,
+}
+
+/// What happens when a character is typed.
+///
+/// - [TypingMode::Insert] inserts it at the caret. The terminal's cursor is a steady bar,
+///   and the caret is painted in reverse.
+/// - [TypingMode::Overwrite] replaces the grapheme cluster under the caret (a wide one is
+///   replaced as a whole, even by a narrow one). The terminal's cursor is a steady block,
+///   and the caret is painted as a bold, underlined block. At the end of a line there is
+///   nothing to replace, so it inserts.
+///
+/// See [crate::CursorShape] for the shape of the terminal's cursor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypingMode {
+    #[default]
+    Insert,
+    Overwrite,
+}
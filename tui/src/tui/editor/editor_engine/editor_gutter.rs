@@ -0,0 +1,292 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Gutters are columns that are painted to the left of the text in the editor, eg: line
+//! numbers, git diff markers, or fold indicators. Apps (and plugins) register them using
+//! [EditorEngine::register_gutter](crate::EditorEngine::register_gutter), and each one
+//! provides its width and the content for each line. See [LineNumbersGutter] for an
+//! example.
+//!
+//! The total width of all the gutters is taken away from the box that the text is painted
+//! in, so the caret, scrolling, and selection continue to work relative to the text.
+//! Gutters are asked for the content of each visible line every time the editor is
+//! rendered, so they stay in sync w/ scrolling and edits (they are not cached).
+
+use std::{fmt::Debug, sync::Arc};
+
+use r3bl_core::{ch, position, ChUnit, Position, Size, TuiStyle, UnicodeString};
+use serde::{Deserialize, Serialize};
+
+use crate::{CaretKind, EditorBuffer, PartialFlexBox};
+
+/// Implement this trait to provide a gutter column.
+pub trait EditorGutter: Debug + Send + Sync {
+    /// Width of the gutter in display cols. This is called once per render, so it can
+    /// depend on the content (eg: the number of digits in the line count).
+    fn get_width(&self, editor_buffer: &EditorBuffer) -> ChUnit;
+
+    /// Content of the gutter for the line at `line_index` in the buffer (this is not the
+    /// row in the viewport). Text that is wider than the gutter is truncated. Return
+    /// [None] to leave the gutter blank for this line.
+    fn get_line_content(
+        &self,
+        editor_buffer: &EditorBuffer,
+        line_index: usize,
+    ) -> Option<(String, Option<TuiStyle>)>;
+}
+
+/// A gutter that has been registered w/ the editor. Gutters are painted from left to
+/// right in ascending `order`, and gutters w/ the same `order` are painted in the order
+/// in which they were registered.
+#[derive(Clone, Debug)]
+pub struct RegisteredEditorGutter {
+    pub order: i32,
+    pub gutter: Arc<dyn EditorGutter>,
+}
+
+/// Where the gutters were last painted. This is set by
+/// [EditorEngineApi::render_engine](crate::EditorEngineApi::render_engine).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuttersLayout {
+    /// Absolute position of the top left corner of the left most gutter.
+    pub origin_pos: Position,
+    pub size: Size,
+    /// Width of each gutter, in the same order as the registered gutters.
+    pub widths: Vec<ChUnit>,
+}
+
+/// Returns the [GuttersLayout] for the given editor box & gutters, or [None] if there are
+/// no gutters, or if the box is too narrow to show them (and at least 1 col of text). The
+/// returned box is the one that is left for the text, which is narrower than the given
+/// one.
+pub fn compute_gutters_layout(
+    current_box: &PartialFlexBox,
+    gutters: &[RegisteredEditorGutter],
+    editor_buffer: &EditorBuffer,
+) -> Option<(PartialFlexBox, GuttersLayout)> {
+    let widths = gutters
+        .iter()
+        .map(|it| it.gutter.get_width(editor_buffer))
+        .collect::<Vec<_>>();
+    let total_width = widths.iter().fold(ch!(0), |acc, it| acc + *it);
+
+    let Size {
+        col_count,
+        row_count,
+    } = current_box.style_adjusted_bounds_size;
+    if total_width == ch!(0) || total_width >= col_count {
+        return None;
+    }
+
+    let mut text_box = *current_box;
+    text_box.style_adjusted_origin_pos = current_box.style_adjusted_origin_pos
+        + position!(col_index: total_width, row_index: 0);
+    text_box.style_adjusted_bounds_size.col_count = col_count - total_width;
+
+    let layout = GuttersLayout {
+        origin_pos: current_box.style_adjusted_origin_pos,
+        size: Size {
+            col_count: total_width,
+            row_count,
+        },
+        widths,
+    };
+
+    Some((text_box, layout))
+}
+
+/// Truncate or pad the `text` so that it is exactly `width` display cols wide.
+pub fn fit_gutter_text(text: &str, width: ChUnit) -> String {
+    let text =
+        UnicodeString::from(UnicodeString::from(text).truncate_end_to_fit_width(width));
+    text.pad_end_with_spaces_to_fit_width(" ", width)
+}
+
+/// Shows the line number, right aligned, w/ a space after it. See [LineNumbersMode] for
+/// the numbers that can be shown.
+#[derive(Clone, Debug, Default)]
+pub struct LineNumbersGutter {
+    pub mode: LineNumbersMode,
+    pub maybe_style: Option<TuiStyle>,
+}
+
+/// Controls the numbers shown by [LineNumbersGutter]. The relative modes count the
+/// distance from the line that the caret is on, by lines in the buffer. This is similar
+/// to Vim's `number` and `relativenumber` options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineNumbersMode {
+    /// The (1 based) line number.
+    #[default]
+    Absolute,
+    /// The distance from the caret's line, which shows `0`.
+    Relative,
+    /// The distance from the caret's line, which shows its absolute line number.
+    Hybrid,
+}
+
+impl EditorGutter for LineNumbersGutter {
+    fn get_width(&self, editor_buffer: &EditorBuffer) -> ChUnit {
+        let digit_count = ch!(@to_usize editor_buffer.len()).max(1).to_string().len();
+        ch!(digit_count + 1)
+    }
+
+    fn get_line_content(
+        &self,
+        editor_buffer: &EditorBuffer,
+        line_index: usize,
+    ) -> Option<(String, Option<TuiStyle>)> {
+        // The distance from the caret is never bigger than the line count, so the same
+        // width works for all the modes.
+        let digit_count = ch!(@to_usize self.get_width(editor_buffer)) - 1;
+        let caret_line_index =
+            ch!(@to_usize editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index);
+        let number = match self.mode {
+            LineNumbersMode::Absolute => line_index + 1,
+            LineNumbersMode::Hybrid if line_index == caret_line_index => line_index + 1,
+            LineNumbersMode::Relative | LineNumbersMode::Hybrid => {
+                line_index.abs_diff(caret_line_index)
+            }
+        };
+        Some((format!("{number:>digit_count$} "), self.maybe_style))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+    use crate::{FlexBoxId, DEFAULT_SYN_HI_FILE_EXT};
+
+    #[derive(Debug)]
+    struct FixedGutter(usize);
+
+    impl EditorGutter for FixedGutter {
+        fn get_width(&self, _: &EditorBuffer) -> ChUnit { ch!(self.0) }
+
+        fn get_line_content(
+            &self,
+            _: &EditorBuffer,
+            _: usize,
+        ) -> Option<(String, Option<TuiStyle>)> {
+            None
+        }
+    }
+
+    fn make_box(col_count: usize) -> PartialFlexBox {
+        PartialFlexBox {
+            id: FlexBoxId::default(),
+            style_adjusted_origin_pos: position!(col_index: 2, row_index: 1),
+            style_adjusted_bounds_size: size!(col_count: col_count, row_count: 10),
+            maybe_computed_style: None,
+        }
+    }
+
+    fn make_gutters(widths: &[usize]) -> Vec<RegisteredEditorGutter> {
+        widths
+            .iter()
+            .map(|it| RegisteredEditorGutter {
+                order: 0,
+                gutter: Arc::new(FixedGutter(*it)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_gutters_layout() {
+        let buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        // No gutters.
+        assert_eq2!(compute_gutters_layout(&make_box(20), &[], &buffer), None);
+
+        // Too narrow.
+        assert_eq2!(
+            compute_gutters_layout(&make_box(5), &make_gutters(&[3, 2]), &buffer),
+            None
+        );
+
+        let (text_box, layout) =
+            compute_gutters_layout(&make_box(20), &make_gutters(&[3, 2]), &buffer)
+                .unwrap();
+        assert_eq2!(
+            text_box.style_adjusted_origin_pos,
+            position!(col_index: 7, row_index: 1)
+        );
+        assert_eq2!(
+            text_box.style_adjusted_bounds_size,
+            size!(col_count: 15, row_count: 10)
+        );
+        assert_eq2!(layout.origin_pos, position!(col_index: 2, row_index: 1));
+        assert_eq2!(layout.size, size!(col_count: 5, row_count: 10));
+        assert_eq2!(layout.widths, vec![ch!(3), ch!(2)]);
+    }
+
+    #[test]
+    fn test_fit_gutter_text() {
+        assert_eq2!(fit_gutter_text("ab", ch!(4)), "ab  ");
+        assert_eq2!(fit_gutter_text("abcdef", ch!(4)), "abcd");
+        assert_eq2!(fit_gutter_text("😃😃", ch!(3)), "😃 ");
+    }
+
+    #[test]
+    fn test_line_numbers_gutter() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..12).map(|it| it.to_string()).collect());
+
+        let gutter = LineNumbersGutter::default();
+        assert_eq2!(gutter.get_width(&buffer), ch!(3));
+        assert_eq2!(
+            gutter.get_line_content(&buffer, 0),
+            Some((" 1 ".to_string(), None))
+        );
+        assert_eq2!(
+            gutter.get_line_content(&buffer, 11),
+            Some(("12 ".to_string(), None))
+        );
+    }
+
+    #[test]
+    fn test_relative_line_numbers_gutter() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..12).map(|it| it.to_string()).collect());
+        let (_, caret, _, _) = buffer.get_mut();
+        *caret = position!(col_index: 0, row_index: 3);
+
+        let get_numbers = |mode: LineNumbersMode| -> Vec<String> {
+            let gutter = LineNumbersGutter {
+                mode,
+                ..Default::default()
+            };
+            [0, 3, 4, 11]
+                .into_iter()
+                .map(|line_index| gutter.get_line_content(&buffer, line_index).unwrap().0)
+                .collect()
+        };
+
+        assert_eq2!(
+            get_numbers(LineNumbersMode::Relative),
+            vec![" 3 ", " 0 ", " 1 ", " 8 "]
+        );
+        assert_eq2!(
+            get_numbers(LineNumbersMode::Hybrid),
+            vec![" 3 ", " 4 ", " 1 ", " 8 "]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! An optional condensed overview of the document, that is painted in a narrow column on
+//! the right side of the editor (when [crate::MinimapMode::Enable] is set). The region of
+//! the document that is visible in the viewport is highlighted. Clicking (or dragging)
+//! on the minimap moves the caret to the corresponding row, and scrolling the mouse wheel
+//! over it pages up or down.
+//!
+//! Each row of the minimap represents a group of lines, and each cell in a row represents
+//! [MINIMAP_COLS_PER_CELL] columns of text. Its character is chosen based on how much of
+//! that area is not whitespace. In order to render large documents efficiently, at most
+//! [MINIMAP_MAX_SAMPLED_LINES_PER_ROW] lines are sampled for each row.
+
+use r3bl_core::{ch, position, ChUnit, Position, Size, UnicodeString};
+use serde::{Deserialize, Serialize};
+
+use crate::{Button,
+            EditorEvent,
+            InputEvent,
+            MouseInput,
+            MouseInputKind,
+            PartialFlexBox};
+
+/// Total width of the minimap, including the separator column.
+pub const MINIMAP_DISPLAY_COL_COUNT: u16 = 10;
+
+/// The minimap is not shown if the editor is narrower than this.
+pub const MINIMAP_MIN_VIEWPORT_COL_COUNT: u16 = 40;
+
+pub const MINIMAP_COLS_PER_CELL: usize = 4;
+
+pub const MINIMAP_MAX_SAMPLED_LINES_PER_ROW: usize = 8;
+
+pub const MINIMAP_SEPARATOR: &str = "│";
+
+/// Where the minimap was last painted, and how many lines each of its rows represents.
+/// This is set by [EditorEngineApi::render_engine](crate::EditorEngineApi::render_engine).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MinimapLayout {
+    /// Absolute position of the top left corner of the minimap (including the
+    /// separator column).
+    pub origin_pos: Position,
+    pub size: Size,
+    pub lines_per_row: ChUnit,
+}
+
+/// Returns the [MinimapLayout] for the given editor box & number of lines, or [None] if
+/// the box is too narrow (or short) to show it. The returned box is the one that is left
+/// for the text, which is narrower than the given one.
+pub fn compute_minimap_layout(
+    current_box: &PartialFlexBox,
+    line_count: usize,
+) -> Option<(PartialFlexBox, MinimapLayout)> {
+    let Size {
+        col_count,
+        row_count,
+    } = current_box.style_adjusted_bounds_size;
+
+    if col_count < ch!(MINIMAP_MIN_VIEWPORT_COL_COUNT) || row_count == ch!(0) {
+        return None;
+    }
+
+    let text_col_count = col_count - ch!(MINIMAP_DISPLAY_COL_COUNT);
+
+    let mut text_box = *current_box;
+    text_box.style_adjusted_bounds_size.col_count = text_col_count;
+
+    let row_count_usize = ch!(@to_usize row_count);
+    let lines_per_row = line_count.div_ceil(row_count_usize).max(1);
+
+    let layout = MinimapLayout {
+        origin_pos: current_box.style_adjusted_origin_pos
+            + position!(col_index: text_col_count, row_index: 0),
+        size: Size {
+            col_count: ch!(MINIMAP_DISPLAY_COL_COUNT),
+            row_count,
+        },
+        lines_per_row: ch!(lines_per_row),
+    };
+
+    Some((text_box, layout))
+}
+
+impl MinimapLayout {
+    /// Returns the (inclusive start, exclusive end) range of lines that the given minimap
+    /// row represents.
+    pub fn get_line_range(&self, minimap_row_index: usize) -> (usize, usize) {
+        let lines_per_row = ch!(@to_usize self.lines_per_row);
+        let start = minimap_row_index * lines_per_row;
+        (start, start + lines_per_row)
+    }
+
+    /// Returns the minimap row index for the given absolute position, if it is inside the
+    /// minimap.
+    pub fn get_row_index_at(&self, pos: Position) -> Option<usize> {
+        let is_inside = pos.col_index >= self.origin_pos.col_index
+            && pos.col_index < self.origin_pos.col_index + self.size.col_count
+            && pos.row_index >= self.origin_pos.row_index
+            && pos.row_index < self.origin_pos.row_index + self.size.row_count;
+        match is_inside {
+            true => Some(ch!(@to_usize pos.row_index - self.origin_pos.row_index)),
+            false => None,
+        }
+    }
+
+    /// Convert mouse input over the minimap into an [EditorEvent]:
+    /// - Left click or drag moves the caret to the first line represented by that row.
+    /// - Mouse wheel pages up or down.
+    pub fn try_convert_input_event(
+        &self,
+        input_event: &InputEvent,
+    ) -> Option<EditorEvent> {
+        let InputEvent::Mouse(MouseInput { pos, kind, .. }) = *input_event else {
+            return None;
+        };
+        let minimap_row_index = self.get_row_index_at(pos)?;
+        match kind {
+            MouseInputKind::MouseDown(Button::Left)
+            | MouseInputKind::MouseDrag(Button::Left) => {
+                let (start, _) = self.get_line_range(minimap_row_index);
+                Some(EditorEvent::MoveCaretToRow(ch!(start)))
+            }
+            MouseInputKind::ScrollUp => Some(EditorEvent::PageUp),
+            MouseInputKind::ScrollDown => Some(EditorEvent::PageDown),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the text for a single row of the minimap (w/out the separator), which is
+/// `cell_count` wide.
+pub fn get_minimap_row_text(
+    lines: &[UnicodeString],
+    line_range: (usize, usize),
+    cell_count: usize,
+) -> String {
+    let (start, end) = line_range;
+    let end = end.min(lines.len());
+    if start >= end {
+        return " ".repeat(cell_count);
+    }
+
+    // Sample the lines evenly, so that large documents render quickly.
+    let line_count = end - start;
+    let step = line_count
+        .div_ceil(MINIMAP_MAX_SAMPLED_LINES_PER_ROW)
+        .max(1);
+    let sampled_lines: Vec<&UnicodeString> =
+        lines[start..end].iter().step_by(step).collect();
+
+    let mut filled_counts = vec![0; cell_count];
+    for line in &sampled_lines {
+        for segment in line.iter() {
+            let cell_index =
+                ch!(@to_usize segment.display_col_offset) / MINIMAP_COLS_PER_CELL;
+            if cell_index >= cell_count {
+                break;
+            }
+            if !segment.string.trim().is_empty() {
+                filled_counts[cell_index] += ch!(@to_usize segment.unicode_width);
+            }
+        }
+    }
+
+    let cell_area = MINIMAP_COLS_PER_CELL * sampled_lines.len();
+    filled_counts
+        .iter()
+        .map(|filled_count| {
+            let density = *filled_count as f64 / cell_area as f64;
+            match density {
+                0.0 => ' ',
+                it if it < 0.34 => '░',
+                it if it < 0.67 => '▒',
+                _ => '▓',
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+    use crate::FlexBoxId;
+
+    fn make_box(col_count: usize, row_count: usize) -> PartialFlexBox {
+        PartialFlexBox {
+            id: FlexBoxId::default(),
+            style_adjusted_origin_pos: position!(col_index: 2, row_index: 1),
+            style_adjusted_bounds_size: size!(col_count: col_count, row_count: row_count),
+            maybe_computed_style: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_layout() {
+        // Too narrow.
+        assert_eq2!(compute_minimap_layout(&make_box(39, 10), 100), None);
+
+        let (text_box, layout) = compute_minimap_layout(&make_box(50, 10), 95).unwrap();
+        assert_eq2!(
+            text_box.style_adjusted_bounds_size,
+            size!(col_count: 40, row_count: 10)
+        );
+        assert_eq2!(layout.origin_pos, position!(col_index: 42, row_index: 1));
+        assert_eq2!(layout.lines_per_row, ch!(10));
+        assert_eq2!(layout.get_line_range(3), (30, 40));
+
+        // Short documents use one line per row.
+        let (_, layout) = compute_minimap_layout(&make_box(50, 10), 3).unwrap();
+        assert_eq2!(layout.lines_per_row, ch!(1));
+    }
+
+    #[test]
+    fn test_try_convert_input_event() {
+        let (_, layout) = compute_minimap_layout(&make_box(50, 10), 95).unwrap();
+        let mouse = |col: usize, row: usize, kind: MouseInputKind| {
+            InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: col, row_index: row),
+                kind,
+                maybe_modifier_keys: None,
+            })
+        };
+
+        // Row 3 of the minimap is at absolute row 4.
+        assert_eq2!(
+            layout.try_convert_input_event(&mouse(
+                45,
+                4,
+                MouseInputKind::MouseDown(Button::Left)
+            )),
+            Some(EditorEvent::MoveCaretToRow(ch!(30)))
+        );
+        assert_eq2!(
+            layout.try_convert_input_event(&mouse(45, 4, MouseInputKind::ScrollDown)),
+            Some(EditorEvent::PageDown)
+        );
+
+        // Outside of the minimap.
+        assert_eq2!(
+            layout.try_convert_input_event(&mouse(
+                10,
+                4,
+                MouseInputKind::MouseDown(Button::Left)
+            )),
+            None
+        );
+        assert_eq2!(
+            layout.try_convert_input_event(&mouse(
+                45,
+                11,
+                MouseInputKind::MouseDown(Button::Left)
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_row_text() {
+        let lines: Vec<UnicodeString> = ["abcd    ab", "abcd", "", "    xy"]
+            .iter()
+            .map(|it| UnicodeString::from(*it))
+            .collect();
+
+        // One line per row.
+        assert_eq2!(get_minimap_row_text(&lines, (0, 1), 4), "▓ ▒ ");
+        assert_eq2!(get_minimap_row_text(&lines, (2, 3), 4), "    ");
+
+        // Many lines per row.
+        assert_eq2!(get_minimap_row_text(&lines, (0, 4), 3), "▒░░");
+
+        // Past the end of the document.
+        assert_eq2!(get_minimap_row_text(&lines, (8, 12), 3), "   ");
+    }
+}
@@ -0,0 +1,491 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Lines that are wider than the viewport are painted on more than one row of it, when
+//! [crate::LineWrapMode::Word] or [crate::LineWrapMode::Char] is set. The lines in the
+//! [EditorBuffer] stay as they are, and so does the caret (which is in the buffer's
+//! coordinates). This module maps between the two.
+//!
+//! Each line is split into [WrapSegment]s, one for each row of the viewport that it is
+//! painted on. They are measured in display columns, using the width of each grapheme
+//! cluster, so a wide one (eg: `😃`) is never split across two rows. When the lines are
+//! wrapped, they are never scrolled horizontally, and the vertical scroll offset is still
+//! the index of the first line in the viewport (which is always painted from its start).
+//!
+//! ```text
+//! LineWrapMode::Word, viewport width 6:
+//! +------+
+//! |hello |  <- line 0, segment 0: [0, 6)
+//! |world |  <- line 0, segment 1: [6, 12)
+//! |ok    |  <- line 0, segment 2: [12, 14)
+//! |bye   |  <- line 1, segment 0: [0, 3)
+//! +------+
+//! ```
+
+use std::cmp::min;
+
+use r3bl_core::{ch, position, ChUnit, Position, UnicodeString};
+
+use crate::{CaretKind, EditorArgsMut, EditorBuffer, EditorEngine, LineWrapMode};
+
+/// The (inclusive start, exclusive end) display col indices of the part of a line that is
+/// painted on one row of the viewport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrapSegment {
+    pub start_col_index: ChUnit,
+    pub end_col_index: ChUnit,
+}
+
+/// A row of the viewport, and the part of a line that is painted on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VisualRow {
+    pub line_index: usize,
+    /// Index of the [WrapSegment] of the line that is painted on this row. This is always
+    /// 0 when the lines aren't wrapped.
+    pub segment_index: usize,
+    pub is_last_segment: bool,
+    pub start_col_index: ChUnit,
+    /// When the lines aren't wrapped, this is the scroll offset plus the viewport width.
+    pub end_col_index: ChUnit,
+}
+
+impl VisualRow {
+    /// The number of display columns that are painted on this row. Whitespace at the end
+    /// of a [WrapSegment] can go past the edge of the viewport, so it is clipped.
+    pub fn get_col_count(&self, viewport_width: ChUnit) -> ChUnit {
+        min(self.end_col_index - self.start_col_index, viewport_width)
+    }
+}
+
+/// Split the line into the [WrapSegment]s that are painted on each row of the viewport.
+/// There is always at least one, even for an empty line.
+///
+/// - [LineWrapMode::None] doesn't split the line.
+/// - [LineWrapMode::Char] splits it before the first grapheme cluster that doesn't fit.
+/// - [LineWrapMode::Word] splits it after the last whitespace that fits, so that words
+///   stay together. Whitespace that doesn't fit is kept at the end of the row (past the
+///   edge), instead of starting the next one. A word that is wider than the viewport is
+///   split like [LineWrapMode::Char] does.
+///
+/// When the last segment fills the whole row, an empty one is added after it, so that
+/// the caret has somewhere to go at the end of the line.
+pub fn get_wrap_segments(
+    line: &UnicodeString,
+    max_display_col_count: ChUnit,
+    line_wrap: &LineWrapMode,
+) -> Vec<WrapSegment> {
+    let display_width = line.display_width;
+
+    if *line_wrap == LineWrapMode::None || max_display_col_count == ch!(0) {
+        return vec![WrapSegment {
+            start_col_index: ch!(0),
+            end_col_index: display_width,
+        }];
+    }
+
+    let mut acc = vec![];
+    let mut start_col_index = ch!(0);
+    // Where the line can be split, after the last run of whitespace that is in the row.
+    let mut maybe_word_break_col_index: Option<ChUnit> = None;
+
+    for segment in line.iter() {
+        let col_index = segment.display_col_offset;
+        let end_col_index = col_index + segment.unicode_width;
+        let is_whitespace = segment.string.chars().all(char::is_whitespace);
+        let is_overflow = |start_col_index: ChUnit| {
+            col_index > start_col_index
+                && end_col_index - start_col_index > max_display_col_count
+        };
+
+        // Whitespace that doesn't fit is kept in this row, past the edge.
+        let is_hanging_whitespace = is_whitespace && *line_wrap == LineWrapMode::Word;
+
+        if is_overflow(start_col_index) && !is_hanging_whitespace {
+            if let (LineWrapMode::Word, Some(word_break_col_index)) =
+                (line_wrap, maybe_word_break_col_index)
+            {
+                if word_break_col_index > start_col_index {
+                    acc.push(WrapSegment {
+                        start_col_index,
+                        end_col_index: word_break_col_index,
+                    });
+                    start_col_index = word_break_col_index;
+                }
+            }
+            // The word (plus this grapheme cluster) might still not fit.
+            if is_overflow(start_col_index) {
+                acc.push(WrapSegment {
+                    start_col_index,
+                    end_col_index: col_index,
+                });
+                start_col_index = col_index;
+            }
+        }
+
+        if is_whitespace {
+            maybe_word_break_col_index = Some(end_col_index);
+        }
+    }
+
+    acc.push(WrapSegment {
+        start_col_index,
+        end_col_index: display_width,
+    });
+
+    if display_width > ch!(0) && display_width - start_col_index >= max_display_col_count
+    {
+        acc.push(WrapSegment {
+            start_col_index: display_width,
+            end_col_index: display_width,
+        });
+    }
+
+    acc
+}
+
+/// Returns the index of the [WrapSegment] that the given display col index is in. The
+/// col at the end of a segment is at the start of the next one, and the col at the end
+/// of the line is in the last one.
+pub fn get_segment_index(segments: &[WrapSegment], col_index: ChUnit) -> usize {
+    segments
+        .iter()
+        .position(|it| col_index < it.end_col_index)
+        .unwrap_or(segments.len() - 1)
+}
+
+/// Returns the display col index that is `col_offset` columns from the start of the
+/// given segment. It is clipped to the segment, and moved to the start of the grapheme
+/// cluster that it is in (if it is in the middle of a wide one).
+pub fn get_col_index_in_segment(
+    line: &UnicodeString,
+    segments: &[WrapSegment],
+    segment_index: usize,
+    col_offset: ChUnit,
+) -> ChUnit {
+    let segment = segments[segment_index];
+    let is_last_segment = segment_index + 1 == segments.len();
+    let desired_col_index = segment.start_col_index + col_offset;
+
+    if is_last_segment && desired_col_index >= line.display_width {
+        return line.display_width;
+    }
+
+    // The start of the grapheme cluster that the col is in, or the last one in the
+    // segment (since the end of the segment is in the next one).
+    line.iter()
+        .map(|it| it.display_col_offset)
+        .filter(|it| *it >= segment.start_col_index && *it < segment.end_col_index)
+        .take_while(|it| *it <= desired_col_index)
+        .last()
+        .unwrap_or(segment.start_col_index)
+}
+
+/// Returns the rows of the viewport (starting at the top), and the part of a line that
+/// is painted on each. This keeps going past the bottom of the viewport, until the end
+/// of the buffer.
+pub fn get_visual_rows<'a>(
+    editor_buffer: &'a EditorBuffer,
+    editor_engine: &EditorEngine,
+) -> impl Iterator<Item = VisualRow> + 'a {
+    let scroll_offset = editor_buffer.get_scroll_offset();
+    let viewport_width = editor_engine.viewport_width();
+    let line_wrap = editor_engine.config_options.line_wrap.clone();
+
+    editor_buffer
+        .get_lines()
+        .iter()
+        .enumerate()
+        .skip(ch!(@to_usize scroll_offset.row_index))
+        .flat_map(move |(line_index, line)| {
+            let segments = match line_wrap {
+                LineWrapMode::None => vec![WrapSegment {
+                    start_col_index: scroll_offset.col_index,
+                    end_col_index: scroll_offset.col_index + viewport_width,
+                }],
+                _ => get_wrap_segments(line, viewport_width, &line_wrap),
+            };
+            let segment_count = segments.len();
+            segments
+                .into_iter()
+                .enumerate()
+                .map(move |(segment_index, segment)| VisualRow {
+                    line_index,
+                    segment_index,
+                    is_last_segment: segment_index + 1 == segment_count,
+                    start_col_index: segment.start_col_index,
+                    end_col_index: segment.end_col_index,
+                })
+        })
+}
+
+/// Returns the position in the viewport where the given (scroll adjusted) position in
+/// the buffer is painted, or [None] if it isn't in the viewport.
+pub fn get_visual_position(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    position: Position,
+) -> Option<Position> {
+    let viewport_width = editor_engine.viewport_width();
+
+    if viewport_width == ch!(0) {
+        return None;
+    }
+
+    get_visual_rows(editor_buffer, editor_engine)
+        .take(ch!(@to_usize editor_engine.viewport_height()))
+        .enumerate()
+        .find(|(_, it)| {
+            it.line_index == ch!(@to_usize position.row_index)
+                && position.col_index >= it.start_col_index
+                && (position.col_index < it.end_col_index || it.is_last_segment)
+        })
+        .and_then(|(row_index, it)| {
+            let col_index = position.col_index - it.start_col_index;
+            match editor_engine.config_options.line_wrap {
+                LineWrapMode::None if col_index >= viewport_width => None,
+                LineWrapMode::None => Some(col_index),
+                // Whitespace past the edge of the viewport.
+                _ => Some(min(col_index, viewport_width - ch!(1))),
+            }
+            .map(|col_index| position!(col_index: col_index, row_index: row_index))
+        })
+}
+
+/// Returns the position in the viewport where the caret is painted. When the lines
+/// aren't wrapped, this is the same as [CaretKind::Raw].
+pub fn get_caret_visual_position(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+) -> Position {
+    match editor_engine.config_options.line_wrap {
+        LineWrapMode::None => editor_buffer.get_caret(CaretKind::Raw),
+        _ => get_visual_position(
+            editor_buffer,
+            editor_engine,
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+        )
+        .unwrap_or_else(|| editor_buffer.get_caret(CaretKind::Raw)),
+    }
+}
+
+/// Returns the (scroll adjusted) position in the buffer that is painted at the given
+/// position in the viewport, or [None] if there is no line there.
+pub fn get_buffer_position(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    viewport_pos: Position,
+) -> Option<Position> {
+    let visual_row = get_visual_rows(editor_buffer, editor_engine)
+        .nth(ch!(@to_usize viewport_pos.row_index))?;
+    Some(position!(
+        col_index: visual_row.start_col_index + viewport_pos.col_index,
+        row_index: visual_row.line_index
+    ))
+}
+
+/// When the lines are wrapped, the caret can be below the viewport even though its line
+/// is in it (or the lines above it take up more rows than there are). This scrolls down
+/// one line at a time, until the caret is in the viewport. The caret stays where it is in
+/// the buffer. A line that takes up more rows than the viewport has can't be scrolled
+/// into view any further than its start. Any horizontal scroll is also removed.
+pub fn scroll_caret_into_view(args: EditorArgsMut<'_>) {
+    let EditorArgsMut {
+        editor_buffer,
+        editor_engine,
+    } = args;
+
+    if editor_engine.config_options.line_wrap == LineWrapMode::None {
+        return;
+    }
+
+    {
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+        caret.col_index += scroll_offset.col_index;
+        scroll_offset.col_index = ch!(0);
+    }
+
+    loop {
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        if caret_adj.row_index <= editor_buffer.get_scroll_offset().row_index
+            || get_visual_position(editor_buffer, editor_engine, caret_adj).is_some()
+        {
+            break;
+        }
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+        scroll_offset.row_index += 1;
+        caret.row_index -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+    use crate::{EditorEngineConfig, PartialFlexBox};
+
+    fn segments(line: &str, width: usize, line_wrap: LineWrapMode) -> Vec<(u16, u16)> {
+        get_wrap_segments(&UnicodeString::from(line), ch!(width), &line_wrap)
+            .iter()
+            .map(|it| (*it.start_col_index, *it.end_col_index))
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_segments_char() {
+        assert_eq2!(segments("", 4, LineWrapMode::Char), vec![(0, 0)]);
+        assert_eq2!(segments("abc", 4, LineWrapMode::Char), vec![(0, 3)]);
+        assert_eq2!(
+            segments("abcdefghij", 4, LineWrapMode::Char),
+            vec![(0, 4), (4, 8), (8, 10)]
+        );
+        // The last row is full, so the end of the line gets a row of its own.
+        assert_eq2!(
+            segments("abcdefgh", 4, LineWrapMode::Char),
+            vec![(0, 4), (4, 8), (8, 8)]
+        );
+        assert_eq2!(segments("abcdefgh", 4, LineWrapMode::None), vec![(0, 8)]);
+    }
+
+    #[test]
+    fn test_wrap_segments_wide_graphemes() {
+        // Each 😃 is 2 cols wide, and is never split.
+        assert_eq2!(
+            segments("a😃😃b", 4, LineWrapMode::Char),
+            vec![(0, 3), (3, 6)]
+        );
+        assert_eq2!(
+            segments("a😃 😃b", 4, LineWrapMode::Word),
+            vec![(0, 4), (4, 7)]
+        );
+        // A grapheme cluster that is wider than the viewport gets a row of its own.
+        assert_eq2!(
+            segments("a😃b", 1, LineWrapMode::Char),
+            vec![(0, 1), (1, 3), (3, 4), (4, 4)]
+        );
+    }
+
+    #[test]
+    fn test_wrap_segments_word() {
+        assert_eq2!(
+            segments("hello world ok", 6, LineWrapMode::Word),
+            vec![(0, 6), (6, 12), (12, 14)]
+        );
+        // The whitespace that doesn't fit stays at the end of the row.
+        assert_eq2!(
+            segments("hello   world", 5, LineWrapMode::Word),
+            vec![(0, 8), (8, 13), (13, 13)]
+        );
+        // A word that doesn't fit is split.
+        assert_eq2!(
+            segments("a abcdefgh", 4, LineWrapMode::Word),
+            vec![(0, 2), (2, 6), (6, 10), (10, 10)]
+        );
+    }
+
+    #[test]
+    fn test_col_index_in_segment() {
+        let line = UnicodeString::from("ab😃de");
+        let segments = get_wrap_segments(&line, ch!(3), &LineWrapMode::Char);
+        assert_eq2!(
+            segments
+                .iter()
+                .map(|it| (*it.start_col_index, *it.end_col_index))
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (2, 5), (5, 6)]
+        );
+
+        assert_eq2!(get_segment_index(&segments, ch!(1)), 0);
+        assert_eq2!(get_segment_index(&segments, ch!(2)), 1);
+        assert_eq2!(get_segment_index(&segments, ch!(6)), 2);
+
+        // Moved to the start of 😃.
+        assert_eq2!(
+            get_col_index_in_segment(&line, &segments, 1, ch!(1)),
+            ch!(2)
+        );
+        // Clipped to the last grapheme cluster in the segment.
+        assert_eq2!(
+            get_col_index_in_segment(&line, &segments, 1, ch!(9)),
+            ch!(4)
+        );
+        assert_eq2!(
+            get_col_index_in_segment(&line, &segments, 0, ch!(9)),
+            ch!(1)
+        );
+        // The last segment ends at the end of the line.
+        assert_eq2!(
+            get_col_index_in_segment(&line, &segments, 2, ch!(9)),
+            ch!(6)
+        );
+    }
+
+    #[test]
+    fn test_visual_positions() {
+        let mut editor_buffer = EditorBuffer::new_empty(&None, &None);
+        editor_buffer.set_lines(vec!["abcdefgh".into(), "xy".into()]);
+        let mut editor_engine = EditorEngine::new(EditorEngineConfig {
+            line_wrap: LineWrapMode::Char,
+            ..Default::default()
+        });
+        editor_engine.current_box = PartialFlexBox {
+            style_adjusted_bounds_size: size!(col_count: 3, row_count: 10),
+            ..Default::default()
+        };
+
+        let visual_rows = get_visual_rows(&editor_buffer, &editor_engine)
+            .map(|it| (it.line_index, *it.start_col_index, *it.end_col_index))
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            visual_rows,
+            vec![(0, 0, 3), (0, 3, 6), (0, 6, 8), (1, 0, 2)]
+        );
+
+        assert_eq2!(
+            get_visual_position(
+                &editor_buffer,
+                &editor_engine,
+                position!(col_index: 7, row_index: 0)
+            ),
+            Some(position!(col_index: 1, row_index: 2))
+        );
+        assert_eq2!(
+            get_visual_position(
+                &editor_buffer,
+                &editor_engine,
+                position!(col_index: 2, row_index: 1)
+            ),
+            Some(position!(col_index: 2, row_index: 3))
+        );
+        assert_eq2!(
+            get_buffer_position(
+                &editor_buffer,
+                &editor_engine,
+                position!(col_index: 1, row_index: 1)
+            ),
+            Some(position!(col_index: 4, row_index: 0))
+        );
+        assert_eq2!(
+            get_buffer_position(
+                &editor_buffer,
+                &editor_engine,
+                position!(col_index: 0, row_index: 4)
+            ),
+            None
+        );
+    }
+}
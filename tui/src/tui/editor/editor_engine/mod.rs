@@ -19,8 +19,18 @@
 pub mod editor_engine_api;
 pub mod editor_engine_internal_api;
 pub mod editor_engine_struct;
+pub mod editor_gutter;
+pub mod editor_minimap;
+pub mod line_wrap;
+pub mod multi_caret;
+pub mod syntect_line_cache;
 
 // Re-export.
 pub use editor_engine_api::*;
 pub use editor_engine_internal_api::*;
 pub use editor_engine_struct::*;
+pub use editor_gutter::*;
+pub use editor_minimap::*;
+pub use line_wrap::*;
+pub use multi_caret::*;
+pub use syntect_line_cache::*;
@@ -0,0 +1,205 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Support for [crate::MultiCaretMode::Enable], where the editor has more than one caret.
+//! The primary caret is stored in the [EditorBuffer] as usual (it is the one that the
+//! viewport follows), and the others are stored in
+//! [EditorContent::extra_carets](crate::EditorContent::extra_carets).
+//!
+//! An event is applied at each caret by moving the primary caret there, and applying the
+//! event as usual. The carets are visited from the last one in the buffer to the first
+//! one, so an edit never changes the text in front of the carets that haven't been
+//! visited yet. The carets that have already been visited are after the edit, so they
+//! keep their distance from the end of the buffer (in rows), and from the end of their
+//! line (in cols), which is used to put them back in place.
+
+use r3bl_core::{ch, position, ChUnit, Position};
+
+use crate::{CaretDirection,
+            CaretKind,
+            EditorBuffer,
+            EditorEngine,
+            EditorEngineInternalApi,
+            EditorEvent,
+            LineMode};
+
+/// Returns true if this event is applied at each caret, instead of just the primary one.
+pub fn is_applied_at_each_caret(editor_event: &EditorEvent) -> bool {
+    matches!(
+        editor_event,
+        EditorEvent::InsertChar(_)
+            | EditorEvent::InsertString(_)
+            | EditorEvent::InsertNewLine
+            | EditorEvent::InsertIndent
+            | EditorEvent::Delete
+            | EditorEvent::Backspace
+            | EditorEvent::MoveCaret(_)
+            | EditorEvent::Home
+            | EditorEvent::End
+    )
+}
+
+/// Returns true if the extra carets are kept when this event is applied. All the other
+/// events (that aren't [applied at each caret](is_applied_at_each_caret)) remove them.
+pub fn keeps_extra_carets(editor_event: &EditorEvent) -> bool {
+    matches!(
+        editor_event,
+        EditorEvent::Undo
+            | EditorEvent::Redo
+            | EditorEvent::Copy
+            | EditorEvent::ToggleTypingMode
+            | EditorEvent::Resize(_)
+            | EditorEvent::AddCaretAbove
+            | EditorEvent::AddCaretBelow
+    )
+}
+
+/// Returns all the carets (including the primary one), sorted from the first one in the
+/// buffer to the last one.
+pub fn get_all_carets(editor_buffer: &EditorBuffer) -> Vec<Position> {
+    let mut acc = editor_buffer.get_extra_carets().to_vec();
+    acc.push(editor_buffer.get_caret(CaretKind::ScrollAdjusted));
+    sort_and_merge(&mut acc);
+    acc
+}
+
+/// Add a caret in the line above the first caret ([CaretDirection::Up]), or below the
+/// last one ([CaretDirection::Down]), in the same col as the primary caret (clipped to
+/// the width of that line). Nothing happens if there's no such line. The selection is
+/// removed.
+pub fn add_caret(
+    editor_buffer: &mut EditorBuffer,
+    editor_engine: &EditorEngine,
+    direction: CaretDirection,
+) -> Option<()> {
+    if editor_engine.config_options.multiline_mode == LineMode::SingleLine {
+        return None;
+    }
+
+    let carets = get_all_carets(editor_buffer);
+    let row_index = match direction {
+        CaretDirection::Up => {
+            let first_row_index = carets.first()?.row_index;
+            if first_row_index == ch!(0) {
+                return None;
+            }
+            first_row_index - 1
+        }
+        CaretDirection::Down => {
+            let last_row_index = carets.last()?.row_index;
+            if last_row_index + 1 >= editor_buffer.len() {
+                return None;
+            }
+            last_row_index + 1
+        }
+        CaretDirection::Left | CaretDirection::Right => return None,
+    };
+
+    let primary_caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let col_index = std::cmp::min(
+        primary_caret.col_index,
+        get_line_display_width(editor_buffer, row_index),
+    );
+
+    let mut extra_carets = editor_buffer.get_extra_carets().to_vec();
+    extra_carets.push(position!(col_index: col_index, row_index: row_index));
+    set_extra_carets(editor_buffer, extra_carets);
+    editor_buffer.clear_selection();
+
+    None
+}
+
+/// Apply an event at each caret (see the [module docs](self) for how this works). The
+/// carets that end up in the same place are merged. The primary caret stays the primary
+/// one.
+pub fn apply_at_each_caret(
+    editor_engine: &mut EditorEngine,
+    editor_buffer: &mut EditorBuffer,
+    mut apply: impl FnMut(&mut EditorEngine, &mut EditorBuffer),
+) {
+    let primary_caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let mut carets = get_all_carets(editor_buffer);
+    let primary_caret_index = carets
+        .iter()
+        .position(|it| *it == primary_caret)
+        .unwrap_or_default();
+
+    for index in (0..carets.len()).rev() {
+        let anchors: Vec<CaretAnchor> = carets[index + 1..]
+            .iter()
+            .map(|it| CaretAnchor::new(editor_buffer, *it))
+            .collect();
+
+        EditorEngineInternalApi::to_position(editor_buffer, editor_engine, carets[index]);
+        apply(editor_engine, editor_buffer);
+        carets[index] = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+
+        for (caret, anchor) in carets[index + 1..].iter_mut().zip(anchors) {
+            *caret = anchor.get_position(editor_buffer);
+        }
+    }
+
+    let primary_caret = carets[primary_caret_index];
+    EditorEngineInternalApi::to_position(editor_buffer, editor_engine, primary_caret);
+    set_extra_carets(editor_buffer, carets);
+}
+
+/// The extra carets are kept sorted, and the ones that are in the same place as another
+/// caret (including the primary one) are removed.
+fn set_extra_carets(editor_buffer: &mut EditorBuffer, mut extra_carets: Vec<Position>) {
+    let primary_caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    sort_and_merge(&mut extra_carets);
+    extra_carets.retain(|it| *it != primary_caret);
+    editor_buffer.set_extra_carets(extra_carets);
+}
+
+fn sort_and_merge(carets: &mut Vec<Position>) {
+    carets.sort_by_key(|it| (it.row_index, it.col_index));
+    carets.dedup();
+}
+
+fn get_line_display_width(editor_buffer: &EditorBuffer, row_index: ChUnit) -> ChUnit {
+    editor_buffer
+        .get_lines()
+        .get(ch!(@to_usize row_index))
+        .map(|line| line.display_width)
+        .unwrap_or_default()
+}
+
+/// The position of a caret, relative to the end of the buffer (rows) and the end of its
+/// line (cols). This doesn't change when the buffer is edited before the caret.
+struct CaretAnchor {
+    rows_from_end: ChUnit,
+    cols_from_line_end: ChUnit,
+}
+
+impl CaretAnchor {
+    fn new(editor_buffer: &EditorBuffer, caret: Position) -> Self {
+        Self {
+            rows_from_end: editor_buffer.len() - 1 - caret.row_index,
+            cols_from_line_end: get_line_display_width(editor_buffer, caret.row_index)
+                - caret.col_index,
+        }
+    }
+
+    fn get_position(&self, editor_buffer: &EditorBuffer) -> Position {
+        let row_index = editor_buffer.len() - 1 - self.rows_from_end;
+        let col_index =
+            get_line_display_width(editor_buffer, row_index) - self.cols_from_line_end;
+        position!(col_index: col_index, row_index: row_index)
+    }
+}
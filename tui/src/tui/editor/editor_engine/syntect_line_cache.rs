@@ -0,0 +1,145 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Cache of the lines that were highlighted w/ [syntect], so that they don't have to be
+//! highlighted again each time they are rendered, eg: when scrolling back and forth
+//! through a long file.
+//!
+//! Each line is highlighted on its own (see
+//! [EditorEngineApi](crate::EditorEngineApi)), so its highlighted spans only depend on:
+//! 1. Its content.
+//! 2. The syntax that it is highlighted w/.
+//! 3. The [Theme](syntect::highlighting::Theme) of the [EditorEngine](crate::EditorEngine).
+//!
+//! The first two are part of the key. Since the key has the content of the line, an edit
+//! only causes the lines that it changed to be highlighted again, and there's no need
+//! to track which lines those were. The theme is accounted for by the `generation`,
+//! which is bumped by [invalidate](SyntectLineCache::invalidate), eg: when the theme is
+//! changed.
+//!
+//! ```text
+//! key: (generation, syntax name, line content) ──▶ highlighted spans
+//! ```
+
+use std::collections::HashMap;
+
+use crate::StyleUSSpanLine;
+
+/// The max number of lines in the cache. When it is full, it is cleared, which is
+/// simple and good enough, since the lines that are visible are highlighted again on the
+/// next render.
+pub const SYNTECT_LINE_CACHE_MAX_SIZE: usize = 10_000;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SyntectLineCache {
+    pub generation: usize,
+    entries: HashMap<SyntectLineCacheKey, StyleUSSpanLine>,
+    /// The number of lookups that found a highlighted line in the cache.
+    pub hit_count: usize,
+    /// The number of lookups that had to highlight the line.
+    pub miss_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct SyntectLineCacheKey {
+    generation: usize,
+    syntax_name: String,
+    line: String,
+}
+
+impl SyntectLineCache {
+    /// Returns the highlighted spans of the `line` from the cache, or highlights it w/
+    /// `highlight_fn` (and caches the result) if it isn't there. Returns [None] (and
+    /// caches nothing) if it can't be highlighted.
+    pub fn get_or_highlight(
+        &mut self,
+        syntax_name: &str,
+        line: &str,
+        highlight_fn: impl FnOnce() -> Option<StyleUSSpanLine>,
+    ) -> Option<StyleUSSpanLine> {
+        let key = SyntectLineCacheKey {
+            generation: self.generation,
+            syntax_name: syntax_name.to_string(),
+            line: line.to_string(),
+        };
+
+        if let Some(it) = self.entries.get(&key) {
+            self.hit_count += 1;
+            return Some(it.clone());
+        }
+
+        self.miss_count += 1;
+        let it = highlight_fn()?;
+        if self.entries.len() >= SYNTECT_LINE_CACHE_MAX_SIZE {
+            self.entries.clear();
+        }
+        self.entries.insert(key, it.clone());
+        Some(it)
+    }
+
+    /// Drop all the cached lines, so that they are highlighted again, eg: after the
+    /// theme was changed.
+    pub fn invalidate(&mut self) {
+        self.generation += 1;
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{List, StyleUSSpan};
+
+    fn highlight(line: &str) -> Option<StyleUSSpanLine> {
+        Some(List {
+            inner: vec![StyleUSSpan::new(Default::default(), line.into())],
+        })
+    }
+
+    #[test]
+    fn test_get_or_highlight() {
+        let mut cache = SyntectLineCache::default();
+
+        // Only the first lookup highlights the line.
+        cache.get_or_highlight("Rust", "fn main() {}", || highlight("fn main() {}"));
+        let it = cache.get_or_highlight("Rust", "fn main() {}", || unreachable!());
+        assert_eq2!(it, highlight("fn main() {}"));
+        assert_eq2!((cache.hit_count, cache.miss_count), (1, 1));
+
+        // The same line w/ a different syntax is highlighted again.
+        cache
+            .get_or_highlight("Plain Text", "fn main() {}", || highlight("fn main() {}"));
+        assert_eq2!((cache.hit_count, cache.miss_count), (1, 2));
+        assert_eq2!(cache.len(), 2);
+
+        // Lines that can't be highlighted aren't cached.
+        assert_eq2!(cache.get_or_highlight("Rust", "let", || None), None);
+        assert_eq2!(cache.len(), 2);
+
+        // After invalidating, the lines are highlighted again.
+        cache.invalidate();
+        assert!(cache.is_empty());
+        cache.get_or_highlight("Rust", "fn main() {}", || highlight("fn main() {}"));
+        assert_eq2!((cache.hit_count, cache.miss_count), (1, 4));
+    }
+}
@@ -23,14 +23,195 @@ mod test_config_options {
                 test_fixtures::mock_real_objects_for_editor,
                 CaretDirection,
                 CaretKind,
+                EditMode,
                 EditorBuffer,
                 EditorEngine,
+                EditorEngineApi,
+                EditorEngineApplyEventResult,
                 EditorEngineConfig,
                 EditorEngineInternalApi,
                 EditorEvent,
+                IndentStyle,
+                InputEvent,
+                Key,
+                KeyPress,
                 LineMode,
+                ModifierKeysMask,
+                SpecialKey,
                 DEFAULT_SYN_HI_FILE_EXT};
 
+    #[test]
+    fn test_insert_indent() {
+        let tab_input_event = InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(SpecialKey::Tab),
+        });
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                indent_style: IndentStyle::Spaces(2),
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        assert_eq2!(
+            EditorEngineApi::would_apply_event(&engine, tab_input_event.clone()),
+            Some(EditorEvent::InsertIndent)
+        );
+
+        // The config is used when the buffer doesn't have its own indent style.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertIndent,
+                EditorEvent::InsertString("a".into()),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "  a");
+
+        // The buffer's own indent style (eg: detected from the file) takes precedence.
+        buffer.set_indent_style(IndentStyle::Tabs);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertIndent],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "  a\t");
+
+        // Single line editors don't indent, so `Tab` is left for the app (eg: to move the
+        // focus), and the event does nothing if it is applied anyway.
+        engine.config_options.multiline_mode = LineMode::SingleLine;
+        assert_eq2!(
+            EditorEngineApi::would_apply_event(&engine, tab_input_event.clone()),
+            None
+        );
+        assert_eq2!(
+            EditorEngineApi::apply_event(
+                &mut buffer,
+                &mut engine,
+                tab_input_event,
+                &mut TestClipboard::default(),
+            )
+            .unwrap(),
+            EditorEngineApplyEventResult::NotApplied
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertIndent],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "  a\t");
+    }
+
+    #[test]
+    fn test_would_apply_event_depends_on_edit_mode() {
+        let char_input_event = InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::Character('a'),
+        });
+        let up_input_event = InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(SpecialKey::Up),
+        });
+
+        let read_write_engine = mock_real_objects_for_editor::make_editor_engine();
+        assert_eq2!(
+            EditorEngineApi::would_apply_event(
+                &read_write_engine,
+                char_input_event.clone()
+            ),
+            Some(EditorEvent::InsertChar('a'))
+        );
+
+        let read_only_engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                edit_mode: EditMode::ReadOnly,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        assert_eq2!(
+            EditorEngineApi::would_apply_event(&read_only_engine, char_input_event),
+            None
+        );
+        assert_eq2!(
+            EditorEngineApi::would_apply_event(&read_only_engine, up_input_event),
+            Some(EditorEvent::MoveCaret(CaretDirection::Up))
+        );
+    }
+
+    #[test]
+    fn test_read_only_allows_selection_and_copy() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["abc".into()]);
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                edit_mode: EditMode::ReadOnly,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut clipboard = TestClipboard::default();
+
+        for input_event in [
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask: ModifierKeysMask::new().with_shift(),
+            }),
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask: ModifierKeysMask::new().with_shift(),
+            }),
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('c'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            }),
+            // These are all ignored.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('x'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            }),
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Backspace),
+            }),
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Delete),
+            }),
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character('z'),
+            }),
+        ] {
+            EditorEngineApi::apply_event(
+                &mut buffer,
+                &mut engine,
+                input_event,
+                &mut clipboard,
+            )
+            .unwrap();
+        }
+
+        assert_eq2!(clipboard.content, "ab");
+        assert_eq2!(buffer.get_lines(), &vec![UnicodeString::from("abc")]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert!(!buffer.get_selection_map().map.is_empty());
+
+        // Undo and redo don't apply either.
+        let undo_input_event = InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character('z'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        });
+        assert_eq2!(
+            EditorEngineApi::would_apply_event(&engine, undo_input_event),
+            None
+        );
+    }
+
     #[test]
     fn test_multiline_true() {
         // multiline true.
@@ -1886,11 +2067,18 @@ mod selection_tests {
 mod clipboard_tests {
     use r3bl_core::{assert_eq2, UnicodeString};
 
-    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+    use crate::{history,
+                system_clipboard_service_provider::test_fixtures::TestClipboard,
                 test_fixtures::mock_real_objects_for_editor,
                 CaretDirection,
+                ClipboardExportMode,
+                EditMode,
                 EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineConfig,
                 EditorEvent,
+                InputEvent,
                 SelectionAction,
                 DEFAULT_SYN_HI_FILE_EXT};
 
@@ -1950,6 +2138,54 @@ mod clipboard_tests {
         }
     }
 
+    #[test]
+    fn test_copy_with_html_export() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                clipboard_export: ClipboardExportMode::PlainTextAndHtml,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        buffer.set_lines(vec!["# r3bl".to_string(), "some *bold* text".to_string()]);
+        let mut test_clipboard = TestClipboard::default();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionAction::PageDown),
+                EditorEvent::Select(SelectionAction::End),
+                EditorEvent::Copy,
+            ],
+            &mut test_clipboard,
+        );
+
+        assert_eq2!(
+            test_clipboard.content,
+            "# r3bl\nsome *bold* text".to_string()
+        );
+        assert_eq2!(
+            test_clipboard.html_content,
+            Some("<h1>r3bl</h1><p>some <strong>bold</strong> text</p>".to_string())
+        );
+
+        // The HTML is only exported for Markdown buffers.
+        let mut buffer = EditorBuffer::new_empty(&Some("txt".to_owned()), &None);
+        buffer.set_lines(vec!["some *bold* text".to_string()]);
+        let mut test_clipboard = TestClipboard::default();
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Select(SelectionAction::End), EditorEvent::Copy],
+            &mut test_clipboard,
+        );
+        assert_eq2!(test_clipboard.content, "some *bold* text".to_string());
+        assert_eq2!(test_clipboard.html_content, None);
+    }
+
     #[test]
     fn test_paste() {
         let mut buffer =
@@ -1965,6 +2201,7 @@ mod clipboard_tests {
         {
             let mut test_clipboard = TestClipboard {
                 content: "copied text ".to_string(),
+                ..Default::default()
             };
 
             // Current Caret Position : [row : 0, col : 0]
@@ -1995,6 +2232,7 @@ mod clipboard_tests {
             // Current Caret Position : [row : 0, col : 4]
             let mut test_clipboard = TestClipboard {
                 content: "old line\nnew line ".to_string(),
+                ..Default::default()
             };
 
             EditorEvent::apply_editor_events::<(), ()>(
@@ -2013,6 +2251,53 @@ mod clipboard_tests {
         }
     }
 
+    #[test]
+    fn test_bracketed_paste() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["ab".to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+            &mut TestClipboard::default(),
+        );
+
+        // Terminals send a carriage return for each new line.
+        let paste_input_event = InputEvent::Paste("- x\r  - y\r\n".into());
+        assert_eq2!(
+            EditorEngineApi::would_apply_event(&engine, paste_input_event.clone()),
+            Some(EditorEvent::PasteText("- x\r  - y\r\n".into()))
+        );
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            paste_input_event.clone(),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+
+        // The text is inserted as is, split into lines at each new line.
+        let new_lines = vec![
+            UnicodeString::from("a- x"),
+            UnicodeString::from("  - y"),
+            UnicodeString::from("b"),
+        ];
+        assert_eq2!(buffer.get_lines(), &new_lines);
+
+        // A single undo removes the whole paste.
+        history::undo(&mut buffer);
+        assert_eq2!(buffer.get_lines(), &vec![UnicodeString::from("ab")]);
+
+        // Read only editors don't accept pasted text.
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        assert_eq2!(
+            EditorEngineApi::would_apply_event(&engine, paste_input_event),
+            None
+        );
+    }
+
     #[test]
     fn test_cut() {
         let mut buffer =
@@ -2098,3 +2383,1682 @@ mod clipboard_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod transaction_tests {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{history,
+                system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                InputEvent,
+                Key,
+                KeyPress,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn type_chars(buffer: &mut EditorBuffer, engine: &mut EditorEngine, text: &str) {
+        for character in text.chars() {
+            EditorEngineApi::apply_event(
+                buffer,
+                engine,
+                InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::Character(character),
+                }),
+                &mut TestClipboard::default(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_commit_adds_single_undo_step() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        type_chars(&mut buffer, &mut engine, "a");
+
+        EditorEngineApi::begin_transaction(&mut engine, &mut buffer);
+        type_chars(&mut buffer, &mut engine, "bc");
+        // Nested transactions are flattened into the outermost one.
+        EditorEngineApi::begin_transaction(&mut engine, &mut buffer);
+        type_chars(&mut buffer, &mut engine, "de");
+        assert_eq2!(
+            EditorEngineApi::commit_transaction(&mut engine, &mut buffer),
+            Some(())
+        );
+        assert!(engine.maybe_transaction.is_some());
+        assert_eq2!(
+            EditorEngineApi::commit_transaction(&mut engine, &mut buffer),
+            Some(())
+        );
+        assert!(engine.maybe_transaction.is_none());
+
+        assert_eq2!(buffer.get_as_string_with_newlines(), "abcde");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+
+        // A single undo reverts the whole transaction.
+        history::undo(&mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "a");
+        history::redo(&mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "abcde");
+
+        // No transaction in progress.
+        assert_eq2!(
+            EditorEngineApi::commit_transaction(&mut engine, &mut buffer),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rollback_restores_prior_state() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        type_chars(&mut buffer, &mut engine, "ab");
+        let content_before = buffer.editor_content.clone();
+
+        EditorEngineApi::begin_transaction(&mut engine, &mut buffer);
+        EditorEngineApi::begin_transaction(&mut engine, &mut buffer);
+        type_chars(&mut buffer, &mut engine, "xyz");
+
+        // Rolling back a nested transaction discards the outermost one.
+        assert_eq2!(
+            EditorEngineApi::rollback_transaction(&mut engine, &mut buffer),
+            Some(())
+        );
+        assert!(engine.maybe_transaction.is_none());
+        assert_eq2!(buffer.editor_content, content_before);
+
+        // Nothing was added to the undo history (the typed word is a single step).
+        history::undo(&mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "");
+
+        assert_eq2!(
+            EditorEngineApi::rollback_transaction(&mut engine, &mut buffer),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod undo_redo_tests {
+    use r3bl_core::assert_eq2;
+
+    use crate::{test_fixtures::mock_real_objects_for_editor::{apply_key_press,
+                                                              make_buffer_and_engine,
+                                                              press_key,
+                                                              type_str},
+                EditMode,
+                EditorEngineApi,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                SpecialKey};
+
+    #[test]
+    fn test_typed_word_is_a_single_undo_step() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&[]);
+        type_str(&mut buffer, &mut engine, "hello world");
+
+        assert_eq2!(EditorEngineApi::undo(&mut engine, &mut buffer), Some(()));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "hello ");
+        assert_eq2!(EditorEngineApi::undo(&mut engine, &mut buffer), Some(()));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "");
+        assert_eq2!(EditorEngineApi::undo(&mut engine, &mut buffer), None);
+
+        assert_eq2!(EditorEngineApi::redo(&mut engine, &mut buffer), Some(()));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "hello ");
+        assert_eq2!(EditorEngineApi::redo(&mut engine, &mut buffer), Some(()));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "hello world");
+        assert_eq2!(EditorEngineApi::redo(&mut engine, &mut buffer), None);
+    }
+
+    #[test]
+    fn test_moving_the_caret_ends_the_typed_word() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&[]);
+        type_str(&mut buffer, &mut engine, "ab");
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Left));
+        type_str(&mut buffer, &mut engine, "c");
+        assert_eq2!(buffer.get_as_string_with_newlines(), "acb");
+
+        EditorEngineApi::undo(&mut engine, &mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab");
+    }
+
+    #[test]
+    fn test_undo_delete() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&["abc"]);
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::End));
+        press_key(
+            &mut buffer,
+            &mut engine,
+            Key::SpecialKey(SpecialKey::Backspace),
+        );
+        press_key(
+            &mut buffer,
+            &mut engine,
+            Key::SpecialKey(SpecialKey::Backspace),
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "a");
+
+        // Each deletion is undone on its own.
+        EditorEngineApi::undo(&mut engine, &mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab");
+        EditorEngineApi::undo(&mut engine, &mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "abc");
+        EditorEngineApi::redo(&mut engine, &mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab");
+    }
+
+    #[test]
+    fn test_undo_multi_line_edit() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&[]);
+        type_str(&mut buffer, &mut engine, "ab");
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Enter));
+        type_str(&mut buffer, &mut engine, "cd");
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab\ncd");
+
+        EditorEngineApi::undo(&mut engine, &mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab\n");
+        EditorEngineApi::undo(&mut engine, &mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab");
+        EditorEngineApi::redo(&mut engine, &mut buffer);
+        EditorEngineApi::redo(&mut engine, &mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab\ncd");
+    }
+
+    #[test]
+    fn test_undo_redo_key_bindings() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&[]);
+        type_str(&mut buffer, &mut engine, "ab");
+
+        // Ctrl + z.
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('z'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            },
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "");
+
+        // Ctrl + Shift + z.
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('Z'),
+                mask: ModifierKeysMask::new().with_ctrl().with_shift(),
+            },
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab");
+    }
+
+    #[test]
+    fn test_undo_is_ignored_when_read_only() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&[]);
+        type_str(&mut buffer, &mut engine, "ab");
+
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        assert_eq2!(EditorEngineApi::undo(&mut engine, &mut buffer), None);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab");
+    }
+}
+
+#[cfg(test)]
+mod list_renumbering_tests {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{history,
+                test_fixtures::mock_real_objects_for_editor::{make_buffer_and_engine,
+                                                              press_key},
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                Key,
+                ListRenumberingMode,
+                SpecialKey};
+
+    fn make_list_buffer_and_engine(
+        lines: &[&str],
+        list_renumbering: ListRenumberingMode,
+    ) -> (EditorBuffer, EditorEngine) {
+        let (buffer, mut engine) = make_buffer_and_engine(lines);
+        engine.config_options.list_renumbering = list_renumbering;
+        (buffer, engine)
+    }
+
+    fn move_caret_to_end_of_row(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        row_index: usize,
+    ) {
+        for _ in 0..row_index {
+            press_key(buffer, engine, Key::SpecialKey(SpecialKey::Down));
+        }
+        press_key(buffer, engine, Key::SpecialKey(SpecialKey::End));
+    }
+
+    #[test]
+    fn test_renumbering_is_part_of_the_same_undo_step() {
+        let (mut buffer, mut engine) =
+            make_list_buffer_and_engine(&["1. a", "3. c"], ListRenumberingMode::Enable);
+
+        move_caret_to_end_of_row(&mut buffer, &mut engine, 1);
+        press_key(&mut buffer, &mut engine, Key::Character('x'));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "1. a\n2. cx");
+
+        history::undo(&mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "1. a\n3. c");
+    }
+
+    #[test]
+    fn test_renumbering_moves_caret_when_number_gets_wider() {
+        let mut lines: Vec<String> = (1..=9).map(|it| format!("{it}. item")).collect();
+        lines.push("9. x".to_string());
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let (mut buffer, mut engine) =
+            make_list_buffer_and_engine(&lines, ListRenumberingMode::Enable);
+
+        move_caret_to_end_of_row(&mut buffer, &mut engine, 9);
+        press_key(&mut buffer, &mut engine, Key::Character('y'));
+
+        assert_eq2!(buffer.get_lines()[9].string, "10. xy");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 6, row_index: 9)
+        );
+    }
+
+    #[test]
+    fn test_renumbering_disabled() {
+        let (mut buffer, mut engine) =
+            make_list_buffer_and_engine(&["1. a", "3. c"], ListRenumberingMode::Disable);
+
+        move_caret_to_end_of_row(&mut buffer, &mut engine, 1);
+        press_key(&mut buffer, &mut engine, Key::Character('x'));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "1. a\n3. cx");
+    }
+}
+
+#[cfg(test)]
+mod typewriter_scrolling_tests {
+    use r3bl_core::{assert_eq2, ch, position};
+
+    use crate::{test_fixtures::mock_real_objects_for_editor::{make_buffer_and_engine,
+                                                              press_special_key},
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                SpecialKey,
+                TypewriterScrollingMode};
+
+    /// The viewport is 10 rows high, so the middle row is 5.
+    fn make_typewriter_buffer_and_engine() -> (EditorBuffer, EditorEngine) {
+        let lines: Vec<String> = (0..30).map(|it| format!("line {it}")).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let (buffer, mut engine) = make_buffer_and_engine(&lines);
+        engine.config_options.typewriter_scrolling = TypewriterScrollingMode::Enable;
+        (buffer, engine)
+    }
+
+    #[test]
+    fn test_caret_stays_centered() {
+        let (mut buffer, mut engine) = make_typewriter_buffer_and_engine();
+
+        // Near the start, centering isn't possible.
+        for _ in 0..3 {
+            press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        }
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 3)
+        );
+
+        // In the middle, the content scrolls around the caret.
+        for _ in 0..9 {
+            press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        }
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(7));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 5)
+        );
+
+        // Page down keeps the caret centered as well.
+        press_special_key(&mut buffer, &mut engine, SpecialKey::PageDown);
+        let caret_row_adj = buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        assert_eq2!(caret_row_adj, ch!(22));
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).row_index, ch!(5));
+
+        // Near the end, the viewport does not go past the last line.
+        for _ in 0..10 {
+            press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        }
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(20));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted).row_index,
+            ch!(29)
+        );
+    }
+
+    #[test]
+    fn test_toggle_at_runtime() {
+        let (mut buffer, mut engine) = make_typewriter_buffer_and_engine();
+        engine.config_options.typewriter_scrolling = TypewriterScrollingMode::Disable;
+
+        for _ in 0..12 {
+            press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        }
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(2));
+
+        // Enabling it recenters on the next event.
+        engine.config_options.typewriter_scrolling = TypewriterScrollingMode::Enable;
+        press_special_key(&mut buffer, &mut engine, SpecialKey::End);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(7));
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).row_index, ch!(5));
+
+        // Disabling it leaves the scroll offset as is.
+        engine.config_options.typewriter_scrolling = TypewriterScrollingMode::Disable;
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Home);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(7));
+    }
+}
+
+#[cfg(test)]
+mod gutter_tests {
+    use r3bl_core::{assert_eq2, ch, position, size, ChUnit, TuiStyle};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorGutter,
+                FlexBox,
+                HasFocus,
+                InputEvent,
+                Key,
+                KeyPress,
+                LineNumbersGutter,
+                RenderOp,
+                SpecialKey,
+                ZOrder,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    /// Marks every even line w/ a `*`.
+    #[derive(Debug)]
+    struct EvenLinesGutter;
+
+    impl EditorGutter for EvenLinesGutter {
+        fn get_width(&self, _: &EditorBuffer) -> ChUnit { ch!(1) }
+
+        fn get_line_content(
+            &self,
+            _: &EditorBuffer,
+            line_index: usize,
+        ) -> Option<(String, Option<TuiStyle>)> {
+            line_index
+                .is_multiple_of(2)
+                .then(|| ("*".to_string(), None))
+        }
+    }
+
+    /// Returns the text painted in each gutter, for each row.
+    fn render(buffer: &mut EditorBuffer, engine: &mut EditorEngine) -> Vec<String> {
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 5),
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            ..Default::default()
+        };
+        let pipeline = EditorEngineApi::render_engine(
+            engine,
+            buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 20, row_count: 5),
+        )
+        .unwrap();
+
+        let layout = engine.maybe_gutters_layout.clone().unwrap();
+        let mut acc = vec![];
+        for render_ops in pipeline.get(&ZOrder::Normal).unwrap() {
+            let mut is_in_gutter = false;
+            for render_op in render_ops.iter() {
+                match render_op {
+                    RenderOp::MoveCursorPositionRelTo(origin_pos, _) => {
+                        is_in_gutter = *origin_pos == layout.origin_pos;
+                    }
+                    RenderOp::PaintTextWithAttributes(text, _) if is_in_gutter => {
+                        acc.push(text.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+        acc
+    }
+
+    #[test]
+    fn test_gutters_are_ordered_and_take_space_from_text() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..12).map(|it| format!("line {it}")).collect());
+        let mut engine = EditorEngine::default();
+        engine.register_gutter(1, LineNumbersGutter::default());
+        engine.register_gutter(0, EvenLinesGutter);
+
+        let painted = render(&mut buffer, &mut engine);
+        assert_eq2!(&painted[..4], &["*", " 1 ", " ", " 2 "]);
+        assert_eq2!(
+            engine.current_box.style_adjusted_origin_pos,
+            position!(col_index: 4, row_index: 0)
+        );
+        assert_eq2!(engine.viewport_width(), ch!(16));
+    }
+
+    #[test]
+    fn test_gutters_stay_in_sync_with_scroll_and_edits() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..9).map(|it| format!("line {it}")).collect());
+        let mut engine = EditorEngine::default();
+        engine.register_gutter(0, LineNumbersGutter::default());
+        render(&mut buffer, &mut engine);
+        assert_eq2!(engine.viewport_width(), ch!(18));
+
+        let mut press = |key: SpecialKey| {
+            EditorEngineApi::apply_event(
+                &mut buffer,
+                &mut engine,
+                InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::SpecialKey(key),
+                }),
+                &mut TestClipboard::default(),
+            )
+            .unwrap();
+        };
+
+        // Scroll down, and then insert a new line, which makes the line numbers wider.
+        for _ in 0..6 {
+            press(SpecialKey::Down);
+        }
+        press(SpecialKey::Up);
+        press(SpecialKey::Enter);
+
+        let painted = render(&mut buffer, &mut engine);
+        let scroll_offset_row = ch!(@to_usize buffer.get_scroll_offset().row_index);
+        assert!(scroll_offset_row > 0);
+        let expected = (scroll_offset_row..scroll_offset_row + 5)
+            .map(|it| format!("{:>2} ", it + 1))
+            .collect::<Vec<_>>();
+        assert_eq2!(painted, expected);
+        assert_eq2!(engine.viewport_width(), ch!(17));
+    }
+}
+
+#[cfg(test)]
+mod overwrite_mode_tests {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{test_fixtures::mock_real_objects_for_editor::{apply_key_press,
+                                                              make_buffer_and_engine,
+                                                              press_key,
+                                                              type_str},
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApplyEventResult,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                OverwriteMode,
+                SpecialKey,
+                TypingMode};
+
+    fn make_overwrite_buffer_and_engine(lines: &[&str]) -> (EditorBuffer, EditorEngine) {
+        let (buffer, mut engine) = make_buffer_and_engine(lines);
+        engine.config_options.overwrite_mode = OverwriteMode::Enable;
+        (buffer, engine)
+    }
+
+    #[test]
+    fn test_insert_key_is_ignored_when_disabled() {
+        let (mut buffer, mut engine) = make_overwrite_buffer_and_engine(&["abc"]);
+        engine.config_options.overwrite_mode = OverwriteMode::Disable;
+
+        let result = press_key(
+            &mut buffer,
+            &mut engine,
+            Key::SpecialKey(SpecialKey::Insert),
+        );
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+        assert_eq2!(engine.typing_mode, TypingMode::Insert);
+
+        type_str(&mut buffer, &mut engine, "xy");
+        assert_eq2!(buffer.get_lines()[0].string, "xyabc");
+    }
+
+    #[test]
+    fn test_toggle_and_overwrite() {
+        let (mut buffer, mut engine) = make_overwrite_buffer_and_engine(&["abcd", "efg"]);
+
+        press_key(
+            &mut buffer,
+            &mut engine,
+            Key::SpecialKey(SpecialKey::Insert),
+        );
+        assert_eq2!(engine.typing_mode, TypingMode::Overwrite);
+
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Right));
+        type_str(&mut buffer, &mut engine, "XY");
+        assert_eq2!(buffer.get_lines()[0].string, "aXYd");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // Undo restores the overwritten characters (the typed word is a single step).
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('z'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            },
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "abcd");
+
+        // Toggle back to insert.
+        press_key(
+            &mut buffer,
+            &mut engine,
+            Key::SpecialKey(SpecialKey::Insert),
+        );
+        assert_eq2!(engine.typing_mode, TypingMode::Insert);
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Home));
+        type_str(&mut buffer, &mut engine, "Z");
+        assert_eq2!(buffer.get_lines()[0].string, "Zabcd");
+    }
+
+    #[test]
+    fn test_overwrite_at_end_of_line_inserts() {
+        let (mut buffer, mut engine) = make_overwrite_buffer_and_engine(&["ab", "cd"]);
+        engine.typing_mode = TypingMode::Overwrite;
+
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Right));
+        type_str(&mut buffer, &mut engine, "XYZ");
+
+        // The next line is not joined or overwritten.
+        assert_eq2!(buffer.get_lines()[0].string, "aXYZ");
+        assert_eq2!(buffer.get_lines()[1].string, "cd");
+    }
+
+    #[test]
+    fn test_overwrite_wide_char_with_narrow_one() {
+        let (mut buffer, mut engine) = make_overwrite_buffer_and_engine(&["a😃b"]);
+        engine.typing_mode = TypingMode::Overwrite;
+
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Right));
+        type_str(&mut buffer, &mut engine, "x");
+        assert_eq2!(buffer.get_lines()[0].string, "axb");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 2, row_index: 0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod scroll_to_ratio_tests {
+    use r3bl_core::{assert_eq2, ch, position};
+
+    use crate::{test_fixtures::mock_real_objects_for_editor::{apply_event,
+                                                              make_buffer_and_engine,
+                                                              press_special_key},
+                CaretKind,
+                EditMode,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                InputEvent,
+                MouseInput,
+                MouseInputKind,
+                SpecialKey};
+
+    /// The viewport is 10 rows high, and there are 30 lines, so the max scroll offset is
+    /// 20.
+    fn make_scroll_buffer_and_engine() -> (EditorBuffer, EditorEngine) {
+        let mut lines = vec!["this is a long line".to_string()];
+        lines.extend((1..30).map(|it| format!("{it}")));
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        make_buffer_and_engine(&lines)
+    }
+
+    #[test]
+    fn test_scroll_to_ratio() {
+        let (mut buffer, mut engine) = make_scroll_buffer_and_engine();
+        for _ in 0..3 {
+            press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        }
+
+        // The caret stays on the same row of the viewport.
+        EditorEngineApi::scroll_to_ratio(&mut engine, &mut buffer, 0.5);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(10));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 13)
+        );
+
+        // The last page isn't scrolled past the end of the content.
+        EditorEngineApi::scroll_to_ratio(&mut engine, &mut buffer, 1.0);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(20));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 23)
+        );
+
+        // Out of range ratios are clamped.
+        EditorEngineApi::scroll_to_ratio(&mut engine, &mut buffer, 7.0);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(20));
+        EditorEngineApi::scroll_to_ratio(&mut engine, &mut buffer, -7.0);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 3)
+        );
+    }
+
+    fn turn_mouse_wheel(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        kind: MouseInputKind,
+    ) {
+        apply_event(
+            buffer,
+            engine,
+            InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: 0, row_index: 0),
+                kind,
+                maybe_modifier_keys: None,
+            }),
+        );
+    }
+
+    #[test]
+    fn test_mouse_wheel_scroll() {
+        let (mut buffer, mut engine) = make_scroll_buffer_and_engine();
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+
+        // The caret stays on the same row of the viewport.
+        turn_mouse_wheel(&mut buffer, &mut engine, MouseInputKind::ScrollDown);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(3));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 4)
+        );
+
+        // The last page isn't scrolled past the end of the content.
+        for _ in 0..10 {
+            turn_mouse_wheel(&mut buffer, &mut engine, MouseInputKind::ScrollDown);
+        }
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(20));
+
+        turn_mouse_wheel(&mut buffer, &mut engine, MouseInputKind::ScrollUp);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(17));
+
+        // The start of the content isn't scrolled past either.
+        for _ in 0..10 {
+            turn_mouse_wheel(&mut buffer, &mut engine, MouseInputKind::ScrollUp);
+        }
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_scroll_to_ratio_clips_caret() {
+        let (mut buffer, mut engine) = make_scroll_buffer_and_engine();
+
+        // The caret's column is clipped to the width of its new line.
+        press_special_key(&mut buffer, &mut engine, SpecialKey::End);
+        EditorEngineApi::scroll_to_ratio(&mut engine, &mut buffer, 1.0);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 20)
+        );
+    }
+}
+
+#[cfg(test)]
+mod multi_caret_tests {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{history,
+                test_fixtures::mock_real_objects_for_editor::{apply_key_press,
+                                                              make_buffer_and_engine,
+                                                              press_key},
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApplyEventResult,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                MultiCaretMode,
+                SpecialKey};
+
+    fn make_multi_caret_buffer_and_engine(
+        lines: &[&str],
+    ) -> (EditorBuffer, EditorEngine) {
+        let (buffer, mut engine) = make_buffer_and_engine(lines);
+        engine.config_options.multi_caret = MultiCaretMode::Enable;
+        (buffer, engine)
+    }
+
+    fn add_caret_below(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> EditorEngineApplyEventResult {
+        apply_key_press(
+            buffer,
+            engine,
+            KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Down),
+                mask: ModifierKeysMask::new().with_ctrl().with_alt(),
+            },
+        )
+    }
+
+    fn get_lines(buffer: &EditorBuffer) -> Vec<&str> {
+        buffer
+            .get_lines()
+            .iter()
+            .map(|line| line.string.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_add_caret_is_ignored_when_disabled() {
+        let (mut buffer, mut engine) =
+            make_multi_caret_buffer_and_engine(&["abc", "def"]);
+        engine.config_options.multi_caret = MultiCaretMode::Disable;
+
+        let result = add_caret_below(&mut buffer, &mut engine);
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+        assert!(!buffer.has_extra_carets());
+    }
+
+    #[test]
+    fn test_type_at_each_caret() {
+        let (mut buffer, mut engine) =
+            make_multi_caret_buffer_and_engine(&["abc", "def", "ghi"]);
+        add_caret_below(&mut buffer, &mut engine);
+        add_caret_below(&mut buffer, &mut engine);
+        // There's no line below the last caret.
+        add_caret_below(&mut buffer, &mut engine);
+
+        press_key(&mut buffer, &mut engine, Key::Character('x'));
+        assert_eq2!(get_lines(&buffer), vec!["xabc", "xdef", "xghi"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_extra_carets(),
+            &[
+                position!(col_index: 1, row_index: 1),
+                position!(col_index: 1, row_index: 2)
+            ]
+        );
+
+        // Simple motions also apply at each caret.
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::End));
+        press_key(&mut buffer, &mut engine, Key::Character('!'));
+        assert_eq2!(get_lines(&buffer), vec!["xabc!", "xdef!", "xghi!"]);
+    }
+
+    #[test]
+    fn test_carets_in_the_same_line_are_shifted() {
+        let (mut buffer, mut engine) = make_multi_caret_buffer_and_engine(&["ab cd"]);
+        buffer.set_extra_carets(vec![position!(col_index: 3, row_index: 0)]);
+
+        press_key(&mut buffer, &mut engine, Key::Character('x'));
+        press_key(&mut buffer, &mut engine, Key::Character('y'));
+        assert_eq2!(get_lines(&buffer), vec!["xyab xycd"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_extra_carets(),
+            &[position!(col_index: 7, row_index: 0)]
+        );
+    }
+
+    #[test]
+    fn test_backspace_joins_lines_at_each_caret() {
+        let (mut buffer, mut engine) =
+            make_multi_caret_buffer_and_engine(&["a", "b", "c"]);
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Down));
+        add_caret_below(&mut buffer, &mut engine);
+
+        press_key(
+            &mut buffer,
+            &mut engine,
+            Key::SpecialKey(SpecialKey::Backspace),
+        );
+        assert_eq2!(get_lines(&buffer), vec!["abc"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_extra_carets(),
+            &[position!(col_index: 2, row_index: 0)]
+        );
+    }
+
+    #[test]
+    fn test_colliding_carets_are_merged() {
+        let (mut buffer, mut engine) = make_multi_caret_buffer_and_engine(&["ab", "c"]);
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Right));
+        buffer.set_extra_carets(vec![position!(col_index: 2, row_index: 0)]);
+
+        // Both carets end up at the start of the line.
+        press_key(
+            &mut buffer,
+            &mut engine,
+            Key::SpecialKey(SpecialKey::Backspace),
+        );
+        assert_eq2!(get_lines(&buffer), vec!["", "c"]);
+        assert!(!buffer.has_extra_carets());
+
+        // Moving past the start of the buffer also merges carets.
+        add_caret_below(&mut buffer, &mut engine);
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Up));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert!(!buffer.has_extra_carets());
+    }
+
+    #[test]
+    fn test_edit_at_each_caret_is_a_single_undo_step() {
+        let (mut buffer, mut engine) =
+            make_multi_caret_buffer_and_engine(&["abc", "def"]);
+        add_caret_below(&mut buffer, &mut engine);
+        press_key(&mut buffer, &mut engine, Key::Character('x'));
+        assert_eq2!(get_lines(&buffer), vec!["xabc", "xdef"]);
+
+        history::undo(&mut buffer);
+        assert_eq2!(get_lines(&buffer), vec!["abc", "def"]);
+        history::redo(&mut buffer);
+        assert_eq2!(get_lines(&buffer), vec!["xabc", "xdef"]);
+    }
+
+    #[test]
+    fn test_esc_removes_extra_carets() {
+        let (mut buffer, mut engine) =
+            make_multi_caret_buffer_and_engine(&["abc", "def"]);
+        add_caret_below(&mut buffer, &mut engine);
+        assert!(buffer.has_extra_carets());
+
+        press_key(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Esc));
+        assert!(!buffer.has_extra_carets());
+
+        press_key(&mut buffer, &mut engine, Key::Character('x'));
+        assert_eq2!(get_lines(&buffer), vec!["xabc", "def"]);
+    }
+}
+
+#[cfg(test)]
+mod checkbox_toggle_tests {
+    use r3bl_core::{assert_eq2, position, size};
+
+    use crate::{history,
+                test_fixtures::mock_real_objects_for_editor::{apply_event,
+                                                              make_buffer_and_engine},
+                Button,
+                CaretKind,
+                CheckboxToggleMode,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApplyEventResult,
+                InputEvent,
+                Key,
+                KeyPress,
+                KeyState,
+                ModifierKeysMask,
+                MouseInput,
+                MouseInputKind,
+                SpecialKey};
+
+    fn make_checkbox_buffer_and_engine(
+        lines: &[&str],
+        checkbox_toggle: CheckboxToggleMode,
+    ) -> (EditorBuffer, EditorEngine) {
+        let (buffer, mut engine) = make_buffer_and_engine(lines);
+        engine.current_box.style_adjusted_bounds_size =
+            size!(col_count: 30, row_count: 10);
+        engine.current_box.style_adjusted_origin_pos =
+            position!(col_index: 2, row_index: 1);
+        engine.config_options.checkbox_toggle = checkbox_toggle;
+        (buffer, engine)
+    }
+
+    fn ctrl_space() -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character(' '),
+            mask: ModifierKeysMask {
+                ctrl_key_state: KeyState::Pressed,
+                shift_key_state: KeyState::NotPressed,
+                alt_key_state: KeyState::NotPressed,
+            },
+        })
+    }
+
+    fn click(col_index: usize, row_index: usize) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: None,
+        })
+    }
+
+    fn get_lines(buffer: &EditorBuffer) -> Vec<String> {
+        buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_toggle_at_caret_is_a_single_undo_step() {
+        let (mut buffer, mut engine) = make_checkbox_buffer_and_engine(
+            &["- [ ] a", "  - [x] nested"],
+            CheckboxToggleMode::Enable,
+        );
+
+        apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Down),
+            }),
+        );
+        apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::End),
+            }),
+        );
+
+        assert_eq2!(
+            apply_event(&mut buffer, &mut engine, ctrl_space()),
+            EditorEngineApplyEventResult::Applied
+        );
+        assert_eq2!(get_lines(&buffer), vec!["- [ ] a", "  - [ ] nested"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 14, row_index: 1)
+        );
+
+        history::undo(&mut buffer);
+        assert_eq2!(get_lines(&buffer), vec!["- [ ] a", "  - [x] nested"]);
+    }
+
+    #[test]
+    fn test_toggle_on_a_line_that_is_not_a_task() {
+        let (mut buffer, mut engine) = make_checkbox_buffer_and_engine(
+            &["- a", "- [ ] b"],
+            CheckboxToggleMode::Enable,
+        );
+
+        assert_eq2!(
+            apply_event(&mut buffer, &mut engine, ctrl_space()),
+            EditorEngineApplyEventResult::NotApplied
+        );
+        assert_eq2!(get_lines(&buffer), vec!["- a", "- [ ] b"]);
+    }
+
+    #[test]
+    fn test_click_on_checkbox() {
+        let (mut buffer, mut engine) = make_checkbox_buffer_and_engine(
+            &["- a", "- [ ] b"],
+            CheckboxToggleMode::Enable,
+        );
+
+        // The viewport starts at col 2 and row 1, so the checkbox is at cols 4 to 6.
+        assert_eq2!(
+            apply_event(&mut buffer, &mut engine, click(7, 2)),
+            EditorEngineApplyEventResult::NotApplied
+        );
+        assert_eq2!(
+            apply_event(&mut buffer, &mut engine, click(5, 2)),
+            EditorEngineApplyEventResult::Applied
+        );
+        assert_eq2!(get_lines(&buffer), vec!["- a", "- [x] b"]);
+
+        // The caret doesn't move.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_toggle_disabled() {
+        let (mut buffer, mut engine) =
+            make_checkbox_buffer_and_engine(&["- [ ] a"], CheckboxToggleMode::Disable);
+
+        assert_eq2!(
+            apply_event(&mut buffer, &mut engine, ctrl_space()),
+            EditorEngineApplyEventResult::NotApplied
+        );
+        assert_eq2!(
+            apply_event(&mut buffer, &mut engine, click(4, 1)),
+            EditorEngineApplyEventResult::NotApplied
+        );
+        assert_eq2!(get_lines(&buffer), vec!["- [ ] a"]);
+    }
+}
+
+#[cfg(test)]
+mod line_wrap_tests {
+    use r3bl_core::{assert_eq2, ch, position, size};
+
+    use crate::{get_caret_visual_position,
+                test_fixtures::mock_real_objects_for_editor::{make_buffer_and_engine,
+                                                              press_special_key},
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                FlexBox,
+                HasFocus,
+                LineWrapMode,
+                RenderOp,
+                SpecialKey,
+                SyntaxHighlightMode,
+                ZOrder};
+
+    fn make_wrap_buffer_and_engine(
+        lines: &[&str],
+        line_wrap: LineWrapMode,
+    ) -> (EditorBuffer, EditorEngine) {
+        let (buffer, mut engine) = make_buffer_and_engine(lines);
+        engine.config_options.syntax_highlight = SyntaxHighlightMode::Disable;
+        engine.config_options.line_wrap = line_wrap;
+        (buffer, engine)
+    }
+
+    /// Returns the text painted on each row of the 6 x 3 viewport.
+    fn render(buffer: &mut EditorBuffer, engine: &mut EditorEngine) -> Vec<String> {
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: size!(col_count: 6, row_count: 3),
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            ..Default::default()
+        };
+        let pipeline = EditorEngineApi::render_engine(
+            engine,
+            buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 6, row_count: 3),
+        )
+        .unwrap();
+
+        let mut acc = vec![String::new(); 3];
+        for render_ops in pipeline.get(&ZOrder::Normal).unwrap() {
+            let mut maybe_row_index = None;
+            for render_op in render_ops.iter() {
+                match render_op {
+                    RenderOp::MoveCursorPositionRelTo(_, pos) => {
+                        maybe_row_index = Some(ch!(@to_usize pos.row_index));
+                    }
+                    RenderOp::PaintTextWithAttributes(text, _) => {
+                        if let Some(it) = maybe_row_index.and_then(|it| acc.get_mut(it)) {
+                            it.push_str(text);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        acc
+    }
+
+    #[test]
+    fn test_render_wrapped_lines() {
+        let (mut buffer, mut engine) =
+            make_wrap_buffer_and_engine(&["hello world ok", "bye"], LineWrapMode::Word);
+        assert_eq2!(
+            render(&mut buffer, &mut engine),
+            vec!["hello ", "world ", "ok"]
+        );
+
+        let (mut buffer, mut engine) =
+            make_wrap_buffer_and_engine(&["hello world ok", "bye"], LineWrapMode::Char);
+        assert_eq2!(
+            render(&mut buffer, &mut engine),
+            vec!["hello ", "world ", "ok"]
+        );
+
+        let (mut buffer, mut engine) =
+            make_wrap_buffer_and_engine(&["ab😃cd😃", "bye"], LineWrapMode::Char);
+        assert_eq2!(
+            render(&mut buffer, &mut engine),
+            vec!["ab😃cd", "😃", "bye"]
+        );
+
+        // Toggling it at runtime doesn't use the cached content.
+        engine.config_options.line_wrap = LineWrapMode::None;
+        assert_eq2!(render(&mut buffer, &mut engine), vec!["ab😃cd", "bye", ""]);
+    }
+
+    #[test]
+    fn test_caret_moves_on_visual_rows() {
+        let (mut buffer, mut engine) =
+            make_wrap_buffer_and_engine(&["hello world ok", "bye"], LineWrapMode::Word);
+        render(&mut buffer, &mut engine);
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Right);
+
+        // Down keeps the distance from the start of the row.
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+        assert_eq2!(
+            get_caret_visual_position(&buffer, &engine),
+            position!(col_index: 1, row_index: 1)
+        );
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 13, row_index: 0)
+        );
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Up);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 13, row_index: 0)
+        );
+
+        // Home and End go to the edges of the row, not the line.
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Home);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 12, row_index: 0)
+        );
+        press_special_key(&mut buffer, &mut engine, SpecialKey::End);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 14, row_index: 0)
+        );
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Up);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 0)
+        );
+        // The col after the end of this row is at the start of the next one.
+        press_special_key(&mut buffer, &mut engine, SpecialKey::End);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 11, row_index: 0)
+        );
+
+        // Lines are never scrolled horizontally.
+        assert_eq2!(buffer.get_scroll_offset().col_index, ch!(0));
+    }
+
+    #[test]
+    fn test_caret_row_is_scrolled_into_view() {
+        let (mut buffer, mut engine) = make_wrap_buffer_and_engine(
+            &["abcdefgh", "ijklmnop", "qr"],
+            LineWrapMode::Char,
+        );
+        assert_eq2!(
+            render(&mut buffer, &mut engine),
+            vec!["abcdef", "gh", "ijklmn"]
+        );
+
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+
+        // The caret's line is in the viewport, but its row isn't.
+        press_special_key(&mut buffer, &mut engine, SpecialKey::Down);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 6, row_index: 1)
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(1));
+        assert_eq2!(
+            get_caret_visual_position(&buffer, &engine),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert_eq2!(render(&mut buffer, &mut engine), vec!["ijklmn", "op", "qr"]);
+    }
+}
+
+#[cfg(test)]
+mod block_selection_tests {
+    use r3bl_core::{assert_eq2, position, UnicodeString};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{apply_event_with_clipboard,
+                                                              make_buffer_and_engine},
+                Button,
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                MouseInput,
+                MouseInputKind,
+                SelectionKind,
+                SpecialKey};
+
+    fn make_block_buffer_and_engine() -> (EditorBuffer, EditorEngine) {
+        make_buffer_and_engine(&["abcdef", "gh", "mnopqrst"])
+    }
+
+    fn key(key: SpecialKey, mask: ModifierKeysMask) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::SpecialKey(key),
+            mask,
+        })
+    }
+
+    fn ctrl(ch: char) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character(ch),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        })
+    }
+
+    #[test]
+    fn test_copy_and_cut_block_selection() {
+        let (mut buffer, mut engine) = make_block_buffer_and_engine();
+        let mut clipboard = TestClipboard::default();
+        let alt_shift = ModifierKeysMask::new().with_alt().with_shift();
+
+        for input_event in [
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Right),
+            }),
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Right),
+            }),
+            key(SpecialKey::Down, alt_shift),
+            key(SpecialKey::Down, alt_shift),
+            key(SpecialKey::Right, alt_shift),
+            key(SpecialKey::Right, alt_shift),
+        ] {
+            apply_event_with_clipboard(
+                &mut buffer,
+                &mut engine,
+                &mut clipboard,
+                input_event,
+            );
+        }
+        assert_eq2!(
+            buffer.get_selection_map().kind,
+            SelectionKind::Block {
+                anchor: position!(col_index: 2, row_index: 0),
+                head: position!(col_index: 4, row_index: 2),
+            }
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 2)
+        );
+
+        // The short line is padded, so that the cols line up.
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('c'));
+        assert_eq2!(clipboard.content, "cd\n  \nop");
+
+        // Only the selected cols are removed, not the lines.
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('x'));
+        assert_eq2!(clipboard.content, "cd\n  \nop");
+        assert_eq2!(
+            buffer.get_lines(),
+            &vec![
+                UnicodeString::from("abef"),
+                UnicodeString::from("gh"),
+                UnicodeString::from("mnqrst"),
+            ]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert_eq2!(buffer.get_selection_map().kind, SelectionKind::Stream);
+    }
+
+    #[test]
+    fn test_stream_selection_replaces_block_selection() {
+        let (mut buffer, mut engine) = make_block_buffer_and_engine();
+        let mut clipboard = TestClipboard::default();
+
+        apply_event_with_clipboard(
+            &mut buffer,
+            &mut engine,
+            &mut clipboard,
+            key(
+                SpecialKey::Down,
+                ModifierKeysMask::new().with_alt().with_shift(),
+            ),
+        );
+        assert!(buffer.get_selection_map().is_block());
+
+        apply_event_with_clipboard(
+            &mut buffer,
+            &mut engine,
+            &mut clipboard,
+            key(SpecialKey::Right, ModifierKeysMask::new().with_shift()),
+        );
+        assert_eq2!(buffer.get_selection_map().kind, SelectionKind::Stream);
+        assert_eq2!(buffer.get_selection_map().get_ordered_indices().len(), 1);
+    }
+
+    #[test]
+    fn test_alt_drag_selects_block() {
+        let (mut buffer, mut engine) = make_block_buffer_and_engine();
+        let mut clipboard = TestClipboard::default();
+
+        for (kind, pos) in [
+            (
+                MouseInputKind::MouseDown(Button::Left),
+                position!(col_index: 1, row_index: 0),
+            ),
+            (
+                MouseInputKind::MouseDrag(Button::Left),
+                position!(col_index: 3, row_index: 1),
+            ),
+        ] {
+            apply_event_with_clipboard(
+                &mut buffer,
+                &mut engine,
+                &mut clipboard,
+                InputEvent::Mouse(MouseInput {
+                    pos,
+                    kind,
+                    maybe_modifier_keys: Some(ModifierKeysMask::new().with_alt()),
+                }),
+            );
+        }
+
+        assert_eq2!(
+            buffer.get_selection_map().kind,
+            SelectionKind::Block {
+                anchor: position!(col_index: 1, row_index: 0),
+                head: position!(col_index: 3, row_index: 1),
+            }
+        );
+        // The caret can't be past the end of the short line.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('c'));
+        assert_eq2!(clipboard.content, "bc\nh ");
+    }
+}
+
+#[cfg(test)]
+mod find_replace_tests {
+    use std::collections::HashMap;
+
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{apply_event_with_clipboard,
+                                                              make_buffer_and_engine,
+                                                              make_global_data,
+                                                              type_str},
+                CaretKind,
+                Component,
+                EditMode,
+                EditorBuffer,
+                EditorComponent,
+                EditorEngineApplyEventResult,
+                EventConsumption,
+                FindField,
+                FlexBoxId,
+                HasEditorBuffers,
+                HasFocus,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                SpecialKey};
+
+    fn plain(key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(key),
+        })
+    }
+
+    fn key(key: SpecialKey, mask: ModifierKeysMask) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::SpecialKey(key),
+            mask,
+        })
+    }
+
+    fn ctrl(ch: char) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character(ch),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        })
+    }
+
+    #[test]
+    fn test_find_w_wide_graphemes() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&["今日は 😃 hi", "hi 😃"]);
+        let mut clipboard = TestClipboard::default();
+
+        // Typing edits the query, instead of the content.
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('f'));
+        type_str(&mut buffer, &mut engine, "😃");
+        let find_state = buffer.get_find_state().unwrap();
+        assert_eq2!(find_state.query, "😃");
+        assert_eq2!(find_state.matches.len(), 2);
+        assert_eq2!(buffer.get_lines()[0].string, "今日は 😃 hi");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+
+        // Enter goes to the next match, and Shift+Enter to the previous one.
+        apply_event_with_clipboard(
+            &mut buffer,
+            &mut engine,
+            &mut clipboard,
+            plain(SpecialKey::Enter),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 1)
+        );
+        apply_event_with_clipboard(
+            &mut buffer,
+            &mut engine,
+            &mut clipboard,
+            key(SpecialKey::Enter, ModifierKeysMask::new().with_shift()),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+
+        // After Esc, typing edits the content again, at the match.
+        apply_event_with_clipboard(
+            &mut buffer,
+            &mut engine,
+            &mut clipboard,
+            plain(SpecialKey::Esc),
+        );
+        assert_eq2!(buffer.get_find_state(), None);
+        type_str(&mut buffer, &mut engine, "x");
+        assert_eq2!(buffer.get_lines()[0].string, "今日は x😃 hi");
+    }
+
+    #[test]
+    fn test_replace_is_undoable() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&["hi hi", "oh hi"]);
+        let mut clipboard = TestClipboard::default();
+
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('f'));
+        type_str(&mut buffer, &mut engine, "hi");
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('h'));
+        assert_eq2!(
+            buffer.get_find_state().unwrap().focus,
+            FindField::Replacement
+        );
+        type_str(&mut buffer, &mut engine, "yo");
+
+        // Enter replaces the current match, and goes to the next one.
+        apply_event_with_clipboard(
+            &mut buffer,
+            &mut engine,
+            &mut clipboard,
+            plain(SpecialKey::Enter),
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "yo hi\noh hi");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // Alt+Enter replaces all the matches.
+        apply_event_with_clipboard(
+            &mut buffer,
+            &mut engine,
+            &mut clipboard,
+            key(SpecialKey::Enter, ModifierKeysMask::new().with_alt()),
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "yo yo\noh yo");
+        assert_eq2!(buffer.get_find_state().unwrap().matches.len(), 0);
+
+        // Each replacement is an undo step, and the matches are found again after undo.
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('z'));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "yo hi\noh hi");
+        assert_eq2!(buffer.get_find_state().unwrap().matches.len(), 2);
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('z'));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "hi hi\noh hi");
+        assert_eq2!(buffer.get_find_state().unwrap().matches.len(), 3);
+    }
+
+    #[test]
+    fn test_find_but_not_replace_in_read_only_mode() {
+        let (mut buffer, mut engine) = make_buffer_and_engine(&["hi hi"]);
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        let mut clipboard = TestClipboard::default();
+
+        apply_event_with_clipboard(&mut buffer, &mut engine, &mut clipboard, ctrl('f'));
+        type_str(&mut buffer, &mut engine, "hi");
+        assert_eq2!(buffer.get_find_state().unwrap().matches.len(), 2);
+
+        assert_eq2!(
+            apply_event_with_clipboard(
+                &mut buffer,
+                &mut engine,
+                &mut clipboard,
+                ctrl('h')
+            ),
+            EditorEngineApplyEventResult::NotApplied
+        );
+        assert_eq2!(buffer.get_find_state().unwrap().maybe_replacement, None);
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct TestState {
+        editor_buffers: HashMap<FlexBoxId, EditorBuffer>,
+    }
+
+    impl HasEditorBuffers for TestState {
+        fn get_mut_editor_buffer(&mut self, id: FlexBoxId) -> Option<&mut EditorBuffer> {
+            self.editor_buffers.get_mut(&id)
+        }
+
+        fn insert_editor_buffer(&mut self, id: FlexBoxId, buffer: EditorBuffer) {
+            self.editor_buffers.insert(id, buffer);
+        }
+
+        fn contains_editor_buffer(&self, id: FlexBoxId) -> bool {
+            self.editor_buffers.contains_key(&id)
+        }
+
+        fn get_editor_buffer(&self, id: FlexBoxId) -> Option<&EditorBuffer> {
+            self.editor_buffers.get(&id)
+        }
+    }
+
+    #[test]
+    fn test_would_consume_event_while_finding_in_read_only_mode() {
+        let id = FlexBoxId::from(1);
+        let (mut buffer, mut engine) = make_buffer_and_engine(&["hi hi"]);
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+
+        apply_event_with_clipboard(
+            &mut buffer,
+            &mut engine,
+            &mut TestClipboard::default(),
+            ctrl('f'),
+        );
+        type_str(&mut buffer, &mut engine, "h");
+
+        let mut component =
+            EditorComponent::<TestState, ()>::new(id, Default::default(), |_, _| {});
+        component.data.editor_engine = engine;
+        let (mut global_data, _) = make_global_data::<TestState, ()>(None);
+        global_data.state.insert_editor_buffer(id, buffer);
+        let has_focus = HasFocus::default();
+
+        let would_consume = |input_event: InputEvent| {
+            matches!(
+                component.would_consume_event(&global_data, input_event, &has_focus),
+                EventConsumption::Consumed(_)
+            )
+        };
+
+        // Typing edits the query, which is allowed in read only mode.
+        assert_eq2!(
+            would_consume(InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character('i'),
+            })),
+            true
+        );
+        assert_eq2!(would_consume(plain(SpecialKey::Esc)), true);
+
+        // Replacing isn't allowed in read only mode.
+        assert_eq2!(would_consume(ctrl('h')), false);
+    }
+}
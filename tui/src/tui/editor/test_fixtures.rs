@@ -23,7 +23,20 @@ pub mod mock_real_objects_for_editor {
     use r3bl_test_fixtures::{output_device_ext::OutputDeviceExt as _, StdoutMock};
     use tokio::sync::mpsc;
 
-    use crate::{EditorEngine, FlexBox, GlobalData, PartialFlexBox, CHANNEL_WIDTH};
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineApplyEventResult,
+                FlexBox,
+                GlobalData,
+                InputEvent,
+                Key,
+                KeyPress,
+                PartialFlexBox,
+                SpecialKey,
+                CHANNEL_WIDTH,
+                DEFAULT_SYN_HI_FILE_EXT};
 
     pub fn make_global_data<S, AS>(
         window_size: Option<Size>,
@@ -41,6 +54,11 @@ pub mod mock_real_objects_for_editor {
             main_thread_channel_sender: sender,
             state: Default::default(),
             output_device,
+            maybe_render_profiler: None,
+            render_overlays: Default::default(),
+            input_event_repeat_count: 1,
+            hit_test_areas: Default::default(),
+            render_throttle_state: Default::default(),
         };
 
         (global_data, stdout_mock)
@@ -71,6 +89,74 @@ pub mod mock_real_objects_for_editor {
             ..Default::default()
         }
     }
+
+    /// An [EditorBuffer] w/ the given `lines`, and the engine from
+    /// [make_editor_engine]. Change the engine's config to turn on the feature that is
+    /// being tested.
+    pub fn make_buffer_and_engine(lines: &[&str]) -> (EditorBuffer, EditorEngine) {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+        (buffer, make_editor_engine())
+    }
+
+    /// Applies the `input_event` to the editor, w/ a [TestClipboard] that is thrown away.
+    pub fn apply_event(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        input_event: InputEvent,
+    ) -> EditorEngineApplyEventResult {
+        apply_event_with_clipboard(
+            buffer,
+            engine,
+            &mut TestClipboard::default(),
+            input_event,
+        )
+    }
+
+    /// Just like [apply_event], but w/ the given `clipboard`, so that what is copied can
+    /// be pasted by a later event.
+    pub fn apply_event_with_clipboard(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        clipboard: &mut TestClipboard,
+        input_event: InputEvent,
+    ) -> EditorEngineApplyEventResult {
+        EditorEngineApi::apply_event(buffer, engine, input_event, clipboard).unwrap()
+    }
+
+    pub fn apply_key_press(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        key_press: KeyPress,
+    ) -> EditorEngineApplyEventResult {
+        apply_event(buffer, engine, InputEvent::Keyboard(key_press))
+    }
+
+    /// Press the `key` w/out any modifiers.
+    pub fn press_key(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        key: Key,
+    ) -> EditorEngineApplyEventResult {
+        apply_key_press(buffer, engine, KeyPress::Plain { key })
+    }
+
+    /// Press the special `key` w/out any modifiers.
+    pub fn press_special_key(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        key: SpecialKey,
+    ) -> EditorEngineApplyEventResult {
+        press_key(buffer, engine, Key::SpecialKey(key))
+    }
+
+    /// Press the key for each character of the `text`, one at a time.
+    pub fn type_str(buffer: &mut EditorBuffer, engine: &mut EditorEngine, text: &str) {
+        for character in text.chars() {
+            press_key(buffer, engine, Key::Character(character));
+        }
+    }
 }
 
 #[cfg(test)]
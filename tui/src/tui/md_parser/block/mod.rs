@@ -17,12 +17,18 @@
 
 // Attach.
 pub mod parse_block_code;
+pub mod parse_block_footnote_def;
 pub mod parse_block_heading;
 pub mod parse_block_markdown_text_until_eol_or_eoi;
+pub mod parse_block_quote;
 pub mod parse_block_smart_list;
+pub mod parse_block_table;
 
 // Re-export.
 pub use parse_block_code::*;
+pub use parse_block_footnote_def::*;
 pub use parse_block_heading::*;
 pub use parse_block_markdown_text_until_eol_or_eoi::*;
+pub use parse_block_quote::*;
 pub use parse_block_smart_list::*;
+pub use parse_block_table::*;
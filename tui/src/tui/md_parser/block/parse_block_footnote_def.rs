@@ -0,0 +1,88 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Footnote definitions, eg: `[^1]: Some text.`. A definition takes a single line. The
+//! footnote references in the rest of the document (eg: `[^1]`) are parsed by
+//! [crate::parse_fragment_starts_with_footnote_ref_err_on_new_line()].
+
+use nom::{bytes::complete::tag, sequence::terminated, IResult};
+
+use crate::{constants::COLON,
+            parse_block_markdown_text_with_or_without_new_line,
+            parse_fragment_starts_with_footnote_ref_err_on_new_line,
+            List,
+            MdBlock};
+
+/// - Sample parse input: `[^1]: Some *text*.\n`.
+/// - The output is a [MdBlock::FootnoteDef]. The content is everything after the `:`
+///   (including any leading spaces), and the new line at the end is consumed.
+pub fn parse_block_footnote_def(input: &str) -> IResult<&str, MdBlock<'_>> {
+    // The `:` has to be right after the footnote reference, eg: `[^1]:`.
+    let (remainder, label) = terminated(
+        parse_fragment_starts_with_footnote_ref_err_on_new_line,
+        tag(COLON),
+    )(input)?;
+    // There may be nothing after the `:`, eg: `[^1]:` at the end of the input.
+    let (remainder, content) = match remainder.is_empty() {
+        true => (remainder, List::new()),
+        false => parse_block_markdown_text_with_or_without_new_line(remainder)?,
+    };
+    Ok((remainder, MdBlock::FootnoteDef { label, content }))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{list, MdLineFragment};
+
+    #[test]
+    fn test_parse_block_footnote_def() {
+        assert_eq2!(
+            parse_block_footnote_def("[^1]: Some *text*\nafter"),
+            Ok((
+                "after",
+                MdBlock::FootnoteDef {
+                    label: "1",
+                    content: list![
+                        MdLineFragment::Plain(" Some "),
+                        MdLineFragment::Bold("text")
+                    ],
+                }
+            ))
+        );
+
+        assert_eq2!(
+            parse_block_footnote_def("[^note]:"),
+            Ok((
+                "",
+                MdBlock::FootnoteDef {
+                    label: "note",
+                    content: list![],
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_not_a_footnote_def() {
+        for input in ["[^1] text", "[^1] : text", " [^1]: text", "[1]: text"] {
+            assert!(parse_block_footnote_def(input).is_err(), "input: {input:?}");
+        }
+    }
+}
@@ -0,0 +1,296 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Block quotes, which are lines that start w/ a `>` marker. They can be nested by
+//! repeating the marker, and a quote ends at the first blank line, or at the first line
+//! that doesn't have a marker and isn't a lazy continuation line:
+//!
+//! ```text
+//! > Outer quote.         <- BlockQuote: Text
+//! >> Nested quote,       <- BlockQuote: BlockQuote: Text
+//! and a lazy line.       <- BlockQuote: BlockQuote: Text (the marker is optional)
+//! > - A list item.       <- BlockQuote: SmartList
+//! ```
+//!
+//! Each line of a quote (w/out its marker) is parsed on its own, into a block that takes
+//! exactly one line. This keeps the blocks in the quote lined up w/ the lines in the
+//! document, so blocks that span multiple lines (like code blocks) aren't supported
+//! inside of a quote.
+
+use nom::IResult;
+
+use crate::{constants::{BLOCK_QUOTE_MARKER_CHAR,
+                        CODE_BLOCK_START_PARTIAL,
+                        HEADING_CHAR,
+                        NEW_LINE_CHAR,
+                        SPACE_CHAR},
+            parse_block_markdown_text_with_or_without_new_line,
+            parse_markdown_blocks,
+            List,
+            MdBlock};
+
+/// The max number of spaces that a `>` marker can be indented by. More than that is an
+/// indented code block in CommonMark, so it isn't a quote.
+const BLOCK_QUOTE_MAX_INDENT: usize = 3;
+
+/// - Sample parse input: `> Foo\n>> Bar\n`.
+/// - The output is a [MdBlock::BlockQuote]. It consumes the new line at the end of each
+///   of its lines.
+/// - If the first line doesn't start w/ a `>` marker, then this fails.
+pub fn parse_block_quote(input: &str) -> IResult<&str, MdBlock<'_>> {
+    let lines = input
+        .split_inclusive(NEW_LINE_CHAR)
+        .map(|line| line.strip_suffix(NEW_LINE_CHAR).unwrap_or(line))
+        .collect::<Vec<_>>();
+
+    let line_count = get_block_quote_line_count(&lines);
+    if line_count == 0 {
+        return Err(nom::Err::Error(nom::error::Error {
+            input,
+            code: nom::error::ErrorKind::Fail,
+        }));
+    }
+
+    let consumed_byte_size = input
+        .split_inclusive(NEW_LINE_CHAR)
+        .take(line_count)
+        .map(str::len)
+        .sum::<usize>();
+
+    Ok((
+        &input[consumed_byte_size..],
+        convert_lines_into_block_quote(&lines[..line_count]),
+    ))
+}
+
+/// Returns the content of the `line` after its `>` marker (and the optional space after
+/// it), or [None] if it doesn't start w/ a marker.
+pub fn strip_block_quote_marker(line: &str) -> Option<&str> {
+    let content = line.trim_start_matches(SPACE_CHAR);
+    if line.len() - content.len() > BLOCK_QUOTE_MAX_INDENT {
+        return None;
+    }
+    let content = content.strip_prefix(BLOCK_QUOTE_MARKER_CHAR)?;
+    Some(content.strip_prefix(SPACE_CHAR).unwrap_or(content))
+}
+
+/// Returns the number of lines at the start of `lines` that belong to a quote, which is
+/// 0 if the first line doesn't have a marker.
+fn get_block_quote_line_count(lines: &[&str]) -> usize {
+    let mut count = 0;
+    let mut maybe_prev_content: Option<&str> = None;
+    for line in lines {
+        let content = match (strip_block_quote_marker(line), maybe_prev_content) {
+            (Some(content), _) => content,
+            (None, Some(prev_content))
+                if is_lazy_continuation_line(prev_content, line) =>
+            {
+                line
+            }
+            _ => break,
+        };
+        count += 1;
+        maybe_prev_content = Some(content);
+    }
+    count
+}
+
+/// A line w/out a marker continues the quote if it follows a line of text, and doesn't
+/// start a block of its own.
+fn is_lazy_continuation_line(prev_content: &str, line: &str) -> bool {
+    // The previous line may itself be in a nested quote.
+    let prev_content = {
+        let mut it = prev_content;
+        while let Some(content) = strip_block_quote_marker(it) {
+            it = content;
+        }
+        it
+    };
+
+    let is_text = |it: &str| {
+        let it = it.trim();
+        !it.is_empty()
+            && !it.starts_with(HEADING_CHAR)
+            && !it.starts_with(CODE_BLOCK_START_PARTIAL)
+            && !matches!(
+                parse_markdown_blocks(it).map(|(_, it)| it.first().cloned()),
+                Ok(Some(MdBlock::SmartList(_)))
+            )
+    };
+
+    is_text(prev_content) && is_text(line)
+}
+
+/// Convert the `lines` of a quote (w/ their markers) into a [MdBlock::BlockQuote], w/ one
+/// block for each line, except for nested quotes, which have one block for all of their
+/// lines. The lines of a nested quote are the lines of its parent w/out their marker.
+fn convert_lines_into_block_quote<'a>(lines: &[&'a str]) -> MdBlock<'a> {
+    let contents = lines
+        .iter()
+        .map(|line| strip_block_quote_marker(line).unwrap_or(line))
+        .collect::<Vec<_>>();
+
+    let mut blocks = List::new();
+    let mut index = 0;
+    while index < contents.len() {
+        let nested_line_count = get_block_quote_line_count(&contents[index..]);
+        if nested_line_count > 0 {
+            blocks.push(convert_lines_into_block_quote(
+                &contents[index..index + nested_line_count],
+            ));
+            index += nested_line_count;
+        } else {
+            blocks.push(parse_block_quote_line(contents[index]));
+            index += 1;
+        }
+    }
+
+    MdBlock::BlockQuote {
+        blocks,
+        raw_lines: lines.to_vec(),
+    }
+}
+
+/// Parse the content of a single line of a quote into a block that takes exactly one
+/// line. If it can't be parsed into one, then it is parsed as text.
+fn parse_block_quote_line(content: &str) -> MdBlock<'_> {
+    if let Ok(("", blocks)) = parse_markdown_blocks(content) {
+        if let [block] = blocks.as_slice() {
+            if block.get_line_count() == Some(1) {
+                return block.clone();
+            }
+        }
+    }
+    MdBlock::Text(
+        parse_block_markdown_text_with_or_without_new_line(content)
+            .map(|(_, it)| it)
+            .unwrap_or_else(|_| List::new()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{list, BulletKind, HeadingData, HeadingLevel, MdLineFragment};
+
+    #[test]
+    fn test_strip_block_quote_marker() {
+        assert_eq2!(strip_block_quote_marker("> foo"), Some("foo"));
+        assert_eq2!(strip_block_quote_marker(">foo"), Some("foo"));
+        assert_eq2!(strip_block_quote_marker(">  foo"), Some(" foo"));
+        assert_eq2!(strip_block_quote_marker("   > foo"), Some("foo"));
+        assert_eq2!(strip_block_quote_marker(">> foo"), Some("> foo"));
+        assert_eq2!(strip_block_quote_marker(">"), Some(""));
+        assert_eq2!(strip_block_quote_marker("    > foo"), None);
+        assert_eq2!(strip_block_quote_marker("foo > bar"), None);
+    }
+
+    #[test]
+    fn test_parse_block_quote() {
+        let input = "> # Title\n> Foo\n> - bar\nafter\n";
+        let (remainder, block) = parse_block_quote(input).unwrap();
+        // A list item isn't text, so the line after it isn't a lazy continuation line.
+        assert_eq2!(remainder, "after\n");
+        assert_eq2!(
+            block,
+            MdBlock::BlockQuote {
+                blocks: list![
+                    MdBlock::Heading(HeadingData {
+                        heading_level: HeadingLevel { level: 1 },
+                        text: "Title",
+                    }),
+                    MdBlock::Text(list![MdLineFragment::Plain("Foo")]),
+                    MdBlock::SmartList((
+                        list![list![
+                            MdLineFragment::UnorderedListBullet {
+                                indent: 0,
+                                is_first_line: true
+                            },
+                            MdLineFragment::Plain("bar")
+                        ]],
+                        BulletKind::Unordered,
+                        0
+                    )),
+                ],
+                raw_lines: vec!["> # Title", "> Foo", "> - bar"],
+            }
+        );
+        assert_eq2!(block.get_line_count(), Some(3));
+    }
+
+    #[test]
+    fn test_parse_nested_block_quote() {
+        // The empty `>` line ends the nested quote, otherwise the last line would be a lazy
+        // continuation line of the nested quote.
+        let input = "> Outer\n>> Inner\nlazy\n>\n> Outer again\n\n> Next";
+        let (remainder, block) = parse_block_quote(input).unwrap();
+        assert_eq2!(remainder, "\n> Next");
+        assert_eq2!(
+            block,
+            MdBlock::BlockQuote {
+                blocks: list![
+                    MdBlock::Text(list![MdLineFragment::Plain("Outer")]),
+                    MdBlock::BlockQuote {
+                        blocks: list![
+                            MdBlock::Text(list![MdLineFragment::Plain("Inner")]),
+                            MdBlock::Text(list![MdLineFragment::Plain("lazy")]),
+                        ],
+                        raw_lines: vec!["> Inner", "lazy"],
+                    },
+                    MdBlock::Text(list![]),
+                    MdBlock::Text(list![MdLineFragment::Plain("Outer again")]),
+                ],
+                raw_lines: vec!["> Outer", ">> Inner", "lazy", ">", "> Outer again"],
+            }
+        );
+        assert_eq2!(block.get_line_count(), Some(5));
+    }
+
+    #[test]
+    fn test_block_quote_ends() {
+        // A blank line ends the quote.
+        let (remainder, _) = parse_block_quote("> a\n\nb\n").unwrap();
+        assert_eq2!(remainder, "\nb\n");
+
+        // A line that starts a block isn't a lazy continuation line.
+        let (remainder, _) = parse_block_quote("> a\n# b\n").unwrap();
+        assert_eq2!(remainder, "# b\n");
+        let (remainder, _) = parse_block_quote("> a\n- b\n").unwrap();
+        assert_eq2!(remainder, "- b\n");
+
+        // A lazy continuation line must follow text.
+        let (remainder, _) = parse_block_quote(">\nb\n").unwrap();
+        assert_eq2!(remainder, "b\n");
+    }
+
+    /// Conformance: malformed markers aren't quotes, so they are left for the other
+    /// parsers (eg: text, which is the catch all).
+    #[test]
+    fn test_invalid_block_quote_markers() {
+        for input in [
+            "    > too much indent",
+            "\\> escaped",
+            "a > b",
+            "",
+            "\t> tab",
+        ] {
+            assert!(parse_block_quote(input).is_err(), "input: {input:?}");
+        }
+    }
+}
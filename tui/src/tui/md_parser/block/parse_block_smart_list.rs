@@ -518,6 +518,7 @@ impl<'a> SmartListLine<'a> {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, size_of::SizeOf)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BulletKind {
     Ordered(usize),
     Unordered,
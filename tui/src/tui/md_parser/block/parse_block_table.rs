@@ -0,0 +1,296 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! GitHub style pipe tables. A table is a header row, followed by a separator row (which
+//! sets the alignment of each column), followed by any number of body rows:
+//!
+//! ```text
+//! | Name  | Qty | Price |   <- header row
+//! |:------|:---:|------:|   <- separator row: left, center, right
+//! | Apple |  3  |  1.50 |   <- body rows, until a line w/out a `|`, or a blank line
+//! ```
+//!
+//! The leading and trailing `|` of each row are optional. A `|` can be escaped w/ a `\`
+//! to use it inside of a cell. The number of columns is set by the header row, and the
+//! separator row must have the same number of them. Body rows that have fewer cells are
+//! padded w/ empty cells, and extra cells are dropped.
+
+use nom::IResult;
+
+use crate::{constants::{NEW_LINE_CHAR,
+                        TABLE_ALIGN_CHAR,
+                        TABLE_PIPE_CHAR,
+                        TABLE_SEPARATOR_CHAR},
+            parse_block_markdown_text_with_checkbox_policy_with_or_without_new_line,
+            CheckboxParsePolicy,
+            ColumnAlignment,
+            List,
+            MdBlock,
+            MdLineFragments};
+
+/// - Sample parse input: `| a | b |\n|---|:-:|\n| 1 | 2 |\n`.
+/// - The output is a [MdBlock::Table]. It consumes the new line at the end of each of its
+///   rows.
+/// - If the first two lines aren't a header row and a matching separator row, then this
+///   fails, so that the lines are parsed as regular Markdown instead.
+pub fn parse_block_table(input: &str) -> IResult<&str, MdBlock<'_>> {
+    let mut lines = input.split_inclusive(NEW_LINE_CHAR);
+    let (Some(header_line), Some(separator_line)) = (lines.next(), lines.next()) else {
+        // A table needs a header row, and a separator row.
+        return fail(input);
+    };
+
+    let header_row = strip_new_line(header_line);
+    let separator_row = strip_new_line(separator_line);
+    let (Some(header_cells), Some(separator_cells)) =
+        (split_table_row(header_row), split_table_row(separator_row))
+    else {
+        // Both rows must have a `|`.
+        return fail(input);
+    };
+    let Some(alignments) = separator_cells
+        .iter()
+        .map(|cell| parse_column_alignment(cell))
+        .collect::<Option<Vec<_>>>()
+    else {
+        // Each cell of the separator row must be like `:---:`.
+        return fail(input);
+    };
+    if alignments.len() != header_cells.len() {
+        // The separator row must have the same number of cells as the header row.
+        return fail(input);
+    }
+
+    let column_count = header_cells.len();
+    let mut consumed_byte_size = header_line.len() + separator_line.len();
+    let mut raw_lines = vec![header_row, separator_row];
+    let mut rows = vec![];
+    for line in lines {
+        let row = strip_new_line(line);
+        if row.trim().is_empty() {
+            break;
+        }
+        let Some(mut cells) = split_table_row(row) else {
+            break;
+        };
+        cells.resize(column_count, "");
+        rows.push(cells.into_iter().map(parse_cell).collect());
+        raw_lines.push(row);
+        consumed_byte_size += line.len();
+    }
+
+    Ok((
+        &input[consumed_byte_size..],
+        MdBlock::Table {
+            headers: header_cells.into_iter().map(parse_cell).collect(),
+            alignments,
+            rows,
+            raw_lines,
+        },
+    ))
+}
+
+fn fail<T>(input: &str) -> IResult<&str, T> {
+    Err(nom::Err::Error(nom::error::Error {
+        input,
+        code: nom::error::ErrorKind::Fail,
+    }))
+}
+
+fn strip_new_line(line: &str) -> &str { line.strip_suffix(NEW_LINE_CHAR).unwrap_or(line) }
+
+/// Returns the (trimmed) cells in the `row`, or [None] if it doesn't have a `|`. The
+/// leading and trailing `|` are optional, and an escaped `\|` doesn't split cells.
+pub fn split_table_row(row: &str) -> Option<Vec<&str>> {
+    let row = row.trim();
+    if !row.contains(TABLE_PIPE_CHAR) {
+        return None;
+    }
+    let row = row.strip_prefix(TABLE_PIPE_CHAR).unwrap_or(row);
+    let row = match row.strip_suffix(TABLE_PIPE_CHAR) {
+        Some(it) if !it.ends_with('\\') => it,
+        _ => row,
+    };
+
+    let mut acc = vec![];
+    let mut cell_start_index = 0;
+    let mut prev_char = None;
+    for (index, char) in row.char_indices() {
+        if char == TABLE_PIPE_CHAR && prev_char != Some('\\') {
+            acc.push(row[cell_start_index..index].trim());
+            cell_start_index = index + char.len_utf8();
+        }
+        prev_char = Some(char);
+    }
+    acc.push(row[cell_start_index..].trim());
+    Some(acc)
+}
+
+/// Parse a cell of the separator row, eg: `:---:`. It must have at least one `-`, w/ an
+/// optional `:` on either side.
+pub fn parse_column_alignment(cell: &str) -> Option<ColumnAlignment> {
+    let starts_with_colon = cell.starts_with(TABLE_ALIGN_CHAR);
+    let ends_with_colon = cell.len() > 1 && cell.ends_with(TABLE_ALIGN_CHAR);
+    let dashes = cell.trim_matches(TABLE_ALIGN_CHAR);
+    if dashes.is_empty() || !dashes.chars().all(|it| it == TABLE_SEPARATOR_CHAR) {
+        return None;
+    }
+    Some(match (starts_with_colon, ends_with_colon) {
+        (true, true) => ColumnAlignment::Center,
+        (true, false) => ColumnAlignment::Left,
+        (false, true) => ColumnAlignment::Right,
+        (false, false) => ColumnAlignment::None,
+    })
+}
+
+fn parse_cell(cell: &str) -> MdLineFragments<'_> {
+    parse_block_markdown_text_with_checkbox_policy_with_or_without_new_line(
+        cell,
+        CheckboxParsePolicy::IgnoreCheckbox,
+    )
+    .map(|(_, fragments)| fragments)
+    .unwrap_or_else(|_| List::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{list, MdLineFragment};
+
+    #[test]
+    fn test_split_table_row() {
+        assert_eq2!(split_table_row("| a | b |"), Some(vec!["a", "b"]));
+        assert_eq2!(split_table_row("a | b"), Some(vec!["a", "b"]));
+        assert_eq2!(split_table_row("| a |  |"), Some(vec!["a", ""]));
+        assert_eq2!(
+            split_table_row("| a \\| b | c |"),
+            Some(vec!["a \\| b", "c"])
+        );
+        assert_eq2!(split_table_row("| a \\|"), Some(vec!["a \\|"]));
+        assert_eq2!(split_table_row("no pipes"), None);
+    }
+
+    #[test]
+    fn test_parse_column_alignment() {
+        assert_eq2!(parse_column_alignment("---"), Some(ColumnAlignment::None));
+        assert_eq2!(parse_column_alignment(":---"), Some(ColumnAlignment::Left));
+        assert_eq2!(parse_column_alignment("-:"), Some(ColumnAlignment::Right));
+        assert_eq2!(parse_column_alignment(":-:"), Some(ColumnAlignment::Center));
+        assert_eq2!(parse_column_alignment(":"), None);
+        assert_eq2!(parse_column_alignment("::"), None);
+        assert_eq2!(parse_column_alignment("-x-"), None);
+        assert_eq2!(parse_column_alignment(""), None);
+    }
+
+    #[test]
+    fn test_parse_block_table_small() {
+        let input = "| a | b |\n|---|:-:|\n| 1 | 2 |\nafter\n";
+        let (remainder, table) = parse_block_table(input).unwrap();
+        assert_eq2!(remainder, "after\n");
+        assert_eq2!(
+            table,
+            MdBlock::Table {
+                headers: vec![
+                    list![MdLineFragment::Plain("a")],
+                    list![MdLineFragment::Plain("b")]
+                ],
+                alignments: vec![ColumnAlignment::None, ColumnAlignment::Center],
+                rows: vec![vec![
+                    list![MdLineFragment::Plain("1")],
+                    list![MdLineFragment::Plain("2")]
+                ]],
+                raw_lines: vec!["| a | b |", "|---|:-:|", "| 1 | 2 |"],
+            }
+        );
+    }
+
+    /// Conformance: a medium sized table, w/ inline formatting in the cells, ragged rows,
+    /// escaped pipes, and no new line at the end of the input.
+    #[test]
+    fn test_parse_block_table_medium() {
+        let input = [
+            "Name | *Qty* | Price",
+            ":--- | :---: | ---:",
+            "`apple` | 3 | 1.50",
+            "pear | 10",
+            "a \\| b | 1 | 2 | extra",
+        ]
+        .join("\n");
+        let (remainder, table) = parse_block_table(&input).unwrap();
+        assert_eq2!(remainder, "");
+
+        let MdBlock::Table {
+            headers,
+            alignments,
+            rows,
+            raw_lines,
+        } = table
+        else {
+            panic!("Expected a table.");
+        };
+        assert_eq2!(headers[1], list![MdLineFragment::Bold("Qty")]);
+        assert_eq2!(
+            alignments,
+            vec![
+                ColumnAlignment::Left,
+                ColumnAlignment::Center,
+                ColumnAlignment::Right
+            ]
+        );
+        assert_eq2!(rows.len(), 3);
+        assert_eq2!(rows[0][0], list![MdLineFragment::InlineCode("apple")]);
+        // Short rows are padded, and long rows are truncated.
+        assert_eq2!(rows[1][2], list![]);
+        assert_eq2!(rows[2].len(), 3);
+        assert_eq2!(raw_lines.len(), 5);
+    }
+
+    /// Conformance: a large table ends at the first blank line.
+    #[test]
+    fn test_parse_block_table_large() {
+        let mut lines = vec!["| id | value |".to_string(), "|----|-------|".to_string()];
+        for it in 0..500 {
+            lines.push(format!("| {it} | *{}* |", it * 2));
+        }
+        lines.push(String::new());
+        lines.push("| not | in the table |".to_string());
+        let input = lines.join("\n");
+
+        let (remainder, table) = parse_block_table(&input).unwrap();
+        assert_eq2!(remainder, "\n| not | in the table |");
+        let MdBlock::Table { rows, .. } = &table else {
+            panic!("Expected a table.");
+        };
+        assert_eq2!(rows.len(), 500);
+        assert_eq2!(rows[499][1], list![MdLineFragment::Bold("998")]);
+        assert_eq2!(table.get_line_count(), Some(502));
+    }
+
+    #[test]
+    fn test_not_a_table() {
+        // No separator row.
+        assert!(parse_block_table("| a | b |\n| 1 | 2 |\n").is_err());
+        // The separator row doesn't match the header row.
+        assert!(parse_block_table("| a | b |\n|---|\n").is_err());
+        // A horizontal rule isn't a separator row.
+        assert!(parse_block_table("a | b\n---\n").is_err());
+        // Only a header row.
+        assert!(parse_block_table("| a | b |").is_err());
+    }
+}
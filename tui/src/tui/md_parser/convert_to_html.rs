@@ -0,0 +1,386 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for converting a [MdDocument] into HTML. This is used to
+//! export Markdown content in a form that can be pasted into rich text editors.
+
+use crate::{BulletKind,
+            CodeBlockLine,
+            CodeBlockLineContent,
+            ColumnAlignment,
+            HyperlinkData,
+            MdBlock,
+            MdDocument,
+            MdLineFragment,
+            MdLineFragments};
+
+/// Convert the given [MdDocument] into an HTML fragment (there is no `<html>` or `<body>`
+/// wrapper, since this is meant to be pasted into other documents).
+///
+/// Consecutive smart list items are grouped into a single `<ul>` or `<ol>`, and items
+/// that are indented more than the previous item are put in a nested list, inside of the
+/// `<li>` of the previous item.
+pub fn convert_md_document_to_html(document: &MdDocument<'_>) -> String {
+    convert_blocks_to_html(document)
+}
+
+/// Block quotes contain blocks of their own, which are converted the same way as the
+/// blocks of the document.
+fn convert_blocks_to_html(blocks: &[MdBlock<'_>]) -> String {
+    let mut acc = String::new();
+
+    // Each open list is tracked by its indent and its tag (`ul` or `ol`). The `<li>` of
+    // the last item of each open list is left open, so that a nested list can be put in
+    // it.
+    let mut open_lists: Vec<(usize, &str)> = vec![];
+
+    for block in blocks.iter() {
+        let MdBlock::SmartList((lines, bullet_kind, indent)) = block else {
+            close_lists(&mut acc, &mut open_lists, None);
+            convert_block_to_html(&mut acc, block);
+            continue;
+        };
+
+        let tag = match bullet_kind {
+            BulletKind::Ordered(_) => "ol",
+            BulletKind::Unordered => "ul",
+        };
+
+        close_lists(&mut acc, &mut open_lists, Some((*indent, tag)));
+
+        let needs_new_list = match open_lists.last() {
+            Some((open_indent, _)) => *open_indent < *indent,
+            None => true,
+        };
+        if needs_new_list {
+            match bullet_kind {
+                BulletKind::Ordered(number) if *number != 1 => {
+                    acc.push_str(&format!("<ol start=\"{number}\">"))
+                }
+                _ => acc.push_str(&format!("<{tag}>")),
+            }
+            open_lists.push((*indent, tag));
+        } else {
+            acc.push_str("</li>");
+        }
+
+        let content = lines
+            .iter()
+            .map(convert_fragments_to_html)
+            .collect::<Vec<String>>()
+            .join(" ");
+        acc.push_str(&format!("<li>{content}"));
+    }
+
+    close_lists(&mut acc, &mut open_lists, None);
+
+    acc
+}
+
+/// Close the open lists (and the `<li>` of their last item) that can't contain the next
+/// list item. If there is no next list item, then all the open lists are closed.
+fn close_lists(
+    acc: &mut String,
+    open_lists: &mut Vec<(usize, &str)>,
+    maybe_next_item: Option<(usize, &str)>,
+) {
+    while let Some((open_indent, open_tag)) = open_lists.last() {
+        let should_close = match maybe_next_item {
+            Some((indent, tag)) => {
+                *open_indent > indent || (*open_indent == indent && *open_tag != tag)
+            }
+            None => true,
+        };
+        if !should_close {
+            break;
+        }
+        acc.push_str(&format!("</li></{open_tag}>"));
+        open_lists.pop();
+    }
+}
+
+fn convert_block_to_html(acc: &mut String, block: &MdBlock<'_>) {
+    match block {
+        MdBlock::Heading(heading_data) => {
+            // HTML only has 6 levels of headings.
+            let level = heading_data.heading_level.level.clamp(1, 6);
+            acc.push_str(&format!(
+                "<h{level}>{}</h{level}>",
+                escape_html(heading_data.text)
+            ));
+        }
+        MdBlock::Text(fragments) => {
+            // Empty lines separate paragraphs, they don't need to be exported.
+            if !fragments.is_empty() {
+                acc.push_str(&format!("<p>{}</p>", convert_fragments_to_html(fragments)));
+            }
+        }
+        MdBlock::CodeBlock(code_block_lines) => {
+            acc.push_str(&convert_code_block_to_html(code_block_lines))
+        }
+        MdBlock::Title(title) => {
+            acc.push_str(&format!("<h1>{}</h1>", escape_html(title)))
+        }
+        MdBlock::Date(date) => acc.push_str(&format!("<p>{}</p>", escape_html(date))),
+        MdBlock::Tags(tags) => {
+            acc.push_str(&format!("<p>Tags: {}</p>", escape_html(&tags.join(", "))))
+        }
+        MdBlock::Authors(authors) => acc.push_str(&format!(
+            "<p>Authors: {}</p>",
+            escape_html(&authors.join(", "))
+        )),
+        MdBlock::SmartList(_) => {
+            // Smart lists are handled by the caller since they span multiple blocks.
+        }
+        MdBlock::FrontMatter(_) => {
+            // Front matter is metadata for tools, it isn't part of the content.
+        }
+        MdBlock::Table {
+            headers,
+            alignments,
+            rows,
+            ..
+        } => acc.push_str(&convert_table_to_html(headers, alignments, rows)),
+        MdBlock::FootnoteDef { label, content } => {
+            let label = escape_html(label);
+            acc.push_str(&format!(
+                "<p id=\"fn-{label}\"><sup>{label}</sup>{}</p>",
+                convert_fragments_to_html(content)
+            ))
+        }
+        MdBlock::BlockQuote { blocks, .. } => acc.push_str(&format!(
+            "<blockquote>{}</blockquote>",
+            convert_blocks_to_html(blocks)
+        )),
+    }
+}
+
+fn convert_table_to_html(
+    headers: &[MdLineFragments<'_>],
+    alignments: &[ColumnAlignment],
+    rows: &[Vec<MdLineFragments<'_>>],
+) -> String {
+    let convert_row = |cells: &[MdLineFragments<'_>], tag: &str| -> String {
+        let cells = cells
+            .iter()
+            .zip(alignments)
+            .map(|(cell, alignment)| {
+                let style = match alignment {
+                    ColumnAlignment::None => "",
+                    ColumnAlignment::Left => " style=\"text-align: left\"",
+                    ColumnAlignment::Center => " style=\"text-align: center\"",
+                    ColumnAlignment::Right => " style=\"text-align: right\"",
+                };
+                format!("<{tag}{style}>{}</{tag}>", convert_fragments_to_html(cell))
+            })
+            .collect::<String>();
+        format!("<tr>{cells}</tr>")
+    };
+
+    let mut acc = format!("<table><thead>{}</thead>", convert_row(headers, "th"));
+    if !rows.is_empty() {
+        acc.push_str("<tbody>");
+        for row in rows {
+            acc.push_str(&convert_row(row, "td"));
+        }
+        acc.push_str("</tbody>");
+    }
+    acc.push_str("</table>");
+    acc
+}
+
+fn convert_code_block_to_html(code_block_lines: &[CodeBlockLine<'_>]) -> String {
+    let maybe_language = code_block_lines.first().and_then(|line| line.language);
+    let code = code_block_lines
+        .iter()
+        .filter_map(|line| match line.content {
+            CodeBlockLineContent::Text(text) => Some(escape_html(text)),
+            CodeBlockLineContent::StartTag | CodeBlockLineContent::EndTag => None,
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    match maybe_language {
+        Some(language) => format!(
+            "<pre><code class=\"language-{}\">{code}</code></pre>",
+            escape_html(language)
+        ),
+        None => format!("<pre><code>{code}</code></pre>"),
+    }
+}
+
+/// Convert a single line of fragments. List bullets are dropped, since they are replaced
+/// by the `<li>` tags.
+pub fn convert_fragments_to_html(fragments: &MdLineFragments<'_>) -> String {
+    fragments
+        .iter()
+        .map(|fragment| match fragment {
+            MdLineFragment::UnorderedListBullet { .. }
+            | MdLineFragment::OrderedListBullet { .. } => String::new(),
+            MdLineFragment::Plain(text) => escape_html(text),
+            MdLineFragment::Bold(text) => {
+                format!("<strong>{}</strong>", escape_html(text))
+            }
+            MdLineFragment::Italic(text) => format!("<em>{}</em>", escape_html(text)),
+            MdLineFragment::InlineCode(text) => {
+                format!("<code>{}</code>", escape_html(text))
+            }
+            MdLineFragment::Strikethrough(fragments) => {
+                format!("<del>{}</del>", convert_fragments_to_html(fragments))
+            }
+            MdLineFragment::Highlight(fragments) => {
+                format!("<mark>{}</mark>", convert_fragments_to_html(fragments))
+            }
+            MdLineFragment::Link(HyperlinkData { text, url }) => {
+                format!("<a href=\"{}\">{}</a>", escape_html(url), escape_html(text))
+            }
+            MdLineFragment::Image(HyperlinkData { text, url }) => format!(
+                "<img src=\"{}\" alt=\"{}\">",
+                escape_html(url),
+                escape_html(text)
+            ),
+            MdLineFragment::FootnoteRef(label) => {
+                let label = escape_html(label);
+                format!(
+                    "<sup id=\"fnref-{label}\"><a href=\"#fn-{label}\">{label}</a></sup>"
+                )
+            }
+            MdLineFragment::Checkbox(is_checked) => {
+                if *is_checked {
+                    "<input type=\"checkbox\" checked disabled>".to_string()
+                } else {
+                    "<input type=\"checkbox\" disabled>".to_string()
+                }
+            }
+        })
+        .collect()
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut it = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => it.push_str("&amp;"),
+            '<' => it.push_str("&lt;"),
+            '>' => it.push_str("&gt;"),
+            '"' => it.push_str("&quot;"),
+            '\'' => it.push_str("&#39;"),
+            _ => it.push(character),
+        }
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::parse_markdown;
+
+    fn to_html(input: &str) -> String {
+        let (_, document) = parse_markdown(input).unwrap();
+        convert_md_document_to_html(&document)
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq2!(
+            escape_html("<a href=\"x\">&'"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+    }
+
+    #[test]
+    fn test_heading_and_text() {
+        assert_eq2!(
+            to_html("# Hello\nSome *bold* and _italic_ `code`\n"),
+            "<h1>Hello</h1><p>Some <strong>bold</strong> and <em>italic</em> <code>code</code></p>"
+        );
+    }
+
+    #[test]
+    fn test_link_and_escaping() {
+        assert_eq2!(
+            to_html("a < b [r3bl](https://r3bl.com)\n"),
+            "<p>a &lt; b <a href=\"https://r3bl.com\">r3bl</a></p>"
+        );
+    }
+
+    #[test]
+    fn test_code_block() {
+        assert_eq2!(
+            to_html("```rust\nlet a = 1 < 2;\n```\n"),
+            "<pre><code class=\"language-rust\">let a = 1 &lt; 2;</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let html = to_html("| a | _b_ |\n|:-:|---|\n| 1 | 2 |\n");
+        assert_eq2!(
+            html,
+            "<table><thead><tr><th style=\"text-align: center\">a</th><th><em>b</em></th>\
+             </tr></thead><tbody><tr><td style=\"text-align: center\">1</td><td>2</td>\
+             </tr></tbody></table>"
+        );
+    }
+
+    #[test]
+    fn test_block_quote() {
+        assert_eq2!(
+            to_html("> Foo\n>> - bar\n"),
+            "<blockquote><p>Foo</p><blockquote><ul><li>bar</li></ul></blockquote></blockquote>"
+        );
+    }
+
+    #[test]
+    fn test_footnotes() {
+        assert_eq2!(
+            to_html("Foo[^1] bar[^2]\n[^1]: Note\n"),
+            "<p>Foo<sup id=\"fnref-1\"><a href=\"#fn-1\">1</a></sup> bar[^2]</p>\
+             <p id=\"fn-1\"><sup>1</sup> Note</p>"
+        );
+    }
+
+    #[test]
+    fn test_strikethrough_and_highlight() {
+        assert_eq2!(
+            to_html("~~*bold strike*~~ and ==<mark>==\n"),
+            "<p><del><strong>bold strike</strong></del> and <mark>&lt;mark&gt;</mark></p>"
+        );
+    }
+
+    #[test]
+    fn test_lists_are_grouped_and_nested() {
+        assert_eq2!(
+            to_html("- one\n  - nested\n- two\n1. first\n"),
+            "<ul><li>one<ul><li>nested</li></ul></li><li>two</li></ul><ol><li>first</li></ol>"
+        );
+    }
+
+    #[test]
+    fn test_nested_lists_are_in_the_parent_item() {
+        assert_eq2!(
+            to_html("- a\n  - b\n    - c\n  - d\n- e\n"),
+            "<ul><li>a<ul><li>b<ul><li>c</li></ul></li><li>d</li></ul></li><li>e</li></ul>"
+        );
+        assert_eq2!(
+            to_html("- a\n  1. b\n- c\n\ntext\n"),
+            "<ul><li>a<ol><li>b</li></ol></li><li>c</li></ul><p>text</p>"
+        );
+    }
+}
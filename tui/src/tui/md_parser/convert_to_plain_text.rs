@@ -22,6 +22,11 @@ use r3bl_core::PrettyPrintDebug;
 
 use crate::{constants::{BACK_TICK,
                         CHECKED,
+                        COLON,
+                        DOUBLE_EQUALS,
+                        DOUBLE_TILDE,
+                        FOOTNOTE_REF_END,
+                        FOOTNOTE_REF_START,
                         HEADING_CHAR,
                         LEFT_BRACKET,
                         LEFT_IMAGE,
@@ -89,6 +94,29 @@ impl PrettyPrintDebug for MdBlock<'_> {
             MdBlock::Tags(tags) => format!("tags: {}", tags.join(", ")),
             MdBlock::Date(date) => format!("title: {}", date),
             MdBlock::Authors(authors) => format!("tags: {}", authors.join(", ")),
+            MdBlock::FrontMatter(content) => {
+                format!(
+                    "front matter: {}",
+                    content.lines().collect::<Vec<_>>().join(" ↵ ")
+                )
+            }
+            MdBlock::Table { headers, rows, .. } => format!(
+                "table, column count: {}, row count: {}",
+                headers.len(),
+                rows.len()
+            ),
+            MdBlock::FootnoteDef { label, content } => format!(
+                "{FOOTNOTE_REF_START}{label}{FOOTNOTE_REF_END}{COLON}{}",
+                content.pretty_print_debug()
+            ),
+            MdBlock::BlockQuote { blocks, .. } => format!(
+                "> [  {}  ]",
+                blocks
+                    .iter()
+                    .map(|block| block.pretty_print_debug())
+                    .collect::<Vec<String>>()
+                    .join(" → ")
+            ),
             MdBlock::SmartList((list_lines, _bullet_kind, _indent)) => format!(
                 "[  {}  ]",
                 list_lines
@@ -136,6 +164,21 @@ impl PrettyPrintDebug for MdLineFragment<'_> {
             MdLineFragment::Bold(text) => format!("{STAR}{text}{STAR}"),
             MdLineFragment::Italic(text) => format!("{UNDERSCORE}{text}{UNDERSCORE}"),
             MdLineFragment::InlineCode(text) => format!("{BACK_TICK}{text}{BACK_TICK}"),
+            MdLineFragment::Strikethrough(fragments) => {
+                format!(
+                    "{DOUBLE_TILDE}{}{DOUBLE_TILDE}",
+                    fragments.pretty_print_debug()
+                )
+            }
+            MdLineFragment::Highlight(fragments) => {
+                format!(
+                    "{DOUBLE_EQUALS}{}{DOUBLE_EQUALS}",
+                    fragments.pretty_print_debug()
+                )
+            }
+            MdLineFragment::FootnoteRef(label) => {
+                format!("{FOOTNOTE_REF_START}{label}{FOOTNOTE_REF_END}")
+            }
             MdLineFragment::Checkbox(is_checked) => {
                 (if *is_checked { CHECKED } else { UNCHECKED }).to_string()
             }
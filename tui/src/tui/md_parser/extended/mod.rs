@@ -16,9 +16,11 @@
  */
 
 // Attach sources.
+pub mod parse_front_matter;
 pub mod parse_metadata_kcsv;
 pub mod parse_metadata_kv;
 
 // Re-exports.
+pub use parse_front_matter::*;
 pub use parse_metadata_kcsv::*;
 pub use parse_metadata_kv::*;
@@ -0,0 +1,114 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use nom::{bytes::complete::tag, sequence::terminated, IResult};
+
+use crate::constants::{FRONT_MATTER_DELIMITER, NEW_LINE, NEW_LINE_CHAR};
+
+/// - Sample parse input: `---\ntitle: Something\n---\n`.
+/// - The output is the raw content between the delimiters, including the new line at the
+///   end of each line (so `title: Something\n` in the example above). Parsing this (eg:
+///   as YAML) is left to the caller.
+/// - The closing delimiter is the first line that is exactly `---`. There may or may not
+///   be a new line after it. If there is, it is consumed.
+/// - If there is no closing delimiter, then this fails, so that the lines are parsed as
+///   regular Markdown instead.
+/// - Front matter is only valid at the very start of a document. This is enforced by
+///   [crate::parse_markdown()], which only uses this parser for the first block.
+pub fn parse_front_matter(input: &str) -> IResult<&str, &str> {
+    let (remainder, _) = terminated(tag(FRONT_MATTER_DELIMITER), tag(NEW_LINE))(input)?;
+
+    let mut content_byte_size = 0;
+    for line in remainder.split_inclusive(NEW_LINE_CHAR) {
+        let line_without_new_line = line.strip_suffix(NEW_LINE_CHAR).unwrap_or(line);
+        if line_without_new_line == FRONT_MATTER_DELIMITER {
+            return Ok((
+                &remainder[content_byte_size + line.len()..],
+                &remainder[..content_byte_size],
+            ));
+        }
+        content_byte_size += line.len();
+    }
+
+    Err(nom::Err::Error(nom::error::Error::new(
+        "Front matter does not have a closing delimiter.",
+        nom::error::ErrorKind::Fail,
+    )))
+}
+
+/// Returns the number of lines that the front matter takes up in the document, including
+/// both the delimiters. The `content` is the output of [parse_front_matter].
+pub fn get_front_matter_line_count(content: &str) -> usize {
+    content.matches(NEW_LINE_CHAR).count() + 2
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_front_matter() {
+        let input = "---\ntitle: Something\ntags: [a, b]\n---\n# Heading\n";
+        let (remainder, output) = parse_front_matter(input).unwrap();
+        assert_eq2!(output, "title: Something\ntags: [a, b]\n");
+        assert_eq2!(remainder, "# Heading\n");
+        assert_eq2!(get_front_matter_line_count(output), 4);
+    }
+
+    #[test]
+    fn test_front_matter_at_end_of_input() {
+        let (remainder, output) = parse_front_matter("---\na: 1\n---").unwrap();
+        assert_eq2!(output, "a: 1\n");
+        assert_eq2!(remainder, "");
+    }
+
+    #[test]
+    fn test_empty_front_matter() {
+        let (remainder, output) = parse_front_matter("---\n---\ntext").unwrap();
+        assert_eq2!(output, "");
+        assert_eq2!(remainder, "text");
+        assert_eq2!(get_front_matter_line_count(output), 2);
+
+        // Blank lines are part of the content.
+        let (_, output) = parse_front_matter("---\n\n---\n").unwrap();
+        assert_eq2!(output, "\n");
+        assert_eq2!(get_front_matter_line_count(output), 3);
+    }
+
+    #[test]
+    fn test_closing_delimiter_must_be_exact() {
+        // The first line that is exactly `---` closes the front matter.
+        let (remainder, output) =
+            parse_front_matter("---\na: ---\n--- \n---\n---\n").unwrap();
+        assert_eq2!(output, "a: ---\n--- \n");
+        assert_eq2!(remainder, "---\n");
+    }
+
+    #[test]
+    fn test_invalid_front_matter() {
+        // Unterminated.
+        assert!(parse_front_matter("---\ntitle: Something\n").is_err());
+        // The opening delimiter has to be on its own line.
+        assert!(parse_front_matter("--- \na: 1\n---\n").is_err());
+        assert!(parse_front_matter("----\na: 1\n---\n").is_err());
+        assert!(parse_front_matter("---").is_err());
+        // Not at the start of the input.
+        assert!(parse_front_matter("\n---\na: 1\n---\n").is_err());
+    }
+}
@@ -31,16 +31,22 @@ use crossterm::style::Stylize;
 use nom::{branch::alt, combinator::map, IResult};
 use r3bl_core::call_if_true;
 
-use crate::{parse_fragment_plain_text_no_new_line,
+use crate::{list,
+            parse_block_markdown_text_with_or_without_new_line,
+            parse_fragment_plain_text_no_new_line,
             parse_fragment_starts_with_backtick_err_on_new_line,
             parse_fragment_starts_with_checkbox_checkbox_into_bool,
             parse_fragment_starts_with_checkbox_into_str,
+            parse_fragment_starts_with_double_equals_err_on_new_line,
+            parse_fragment_starts_with_double_tilde_err_on_new_line,
+            parse_fragment_starts_with_footnote_ref_err_on_new_line,
             parse_fragment_starts_with_left_image_err_on_new_line,
             parse_fragment_starts_with_left_link_err_on_new_line,
             parse_fragment_starts_with_star_err_on_new_line,
             parse_fragment_starts_with_underscore_err_on_new_line,
             CheckboxParsePolicy,
             MdLineFragment,
+            MdLineFragments,
             DEBUG_MD_PARSER};
 
 // BOOKM: Parser for a single line of markdown
@@ -70,25 +76,32 @@ pub fn parse_inline_fragments_until_eol_or_eoi(
 ) -> IResult<&str, MdLineFragment<'_>> {
     // The order of the following parsers is important. The highest priority parser is at
     // the top. The lowest priority parser is at the bottom. This is because the first
-    // parser that matches will be the one that is used.
+    // parser that matches will be the one that is used. Strikethrough & highlight are
+    // above bold & italic, since they can wrap them, eg: `~~*bold strike*~~`.
     let it = match checkbox_policy {
         CheckboxParsePolicy::IgnoreCheckbox => alt((
-            map(parse_fragment_starts_with_underscore_err_on_new_line,  MdLineFragment::Italic),
-            map(parse_fragment_starts_with_star_err_on_new_line,        MdLineFragment::Bold),
-            map(parse_fragment_starts_with_backtick_err_on_new_line,    MdLineFragment::InlineCode),
-            map(parse_fragment_starts_with_left_image_err_on_new_line,  MdLineFragment::Image),
-            map(parse_fragment_starts_with_left_link_err_on_new_line,   MdLineFragment::Link),
-            map(parse_fragment_starts_with_checkbox_into_str,           MdLineFragment::Plain), // This line is different.
-            map(parse_fragment_plain_text_no_new_line,                  MdLineFragment::Plain),
+            map(parse_fragment_starts_with_double_tilde_err_on_new_line,  |it| MdLineFragment::Strikethrough(parse_nested_fragments(it))),
+            map(parse_fragment_starts_with_double_equals_err_on_new_line, |it| MdLineFragment::Highlight(parse_nested_fragments(it))),
+            map(parse_fragment_starts_with_underscore_err_on_new_line,    MdLineFragment::Italic),
+            map(parse_fragment_starts_with_star_err_on_new_line,          MdLineFragment::Bold),
+            map(parse_fragment_starts_with_backtick_err_on_new_line,      MdLineFragment::InlineCode),
+            map(parse_fragment_starts_with_footnote_ref_err_on_new_line,  MdLineFragment::FootnoteRef),
+            map(parse_fragment_starts_with_left_image_err_on_new_line,    MdLineFragment::Image),
+            map(parse_fragment_starts_with_left_link_err_on_new_line,     MdLineFragment::Link),
+            map(parse_fragment_starts_with_checkbox_into_str,             MdLineFragment::Plain), // This line is different.
+            map(parse_fragment_plain_text_no_new_line,                    MdLineFragment::Plain),
         ))(input),
         CheckboxParsePolicy::ParseCheckbox => alt((
-            map(parse_fragment_starts_with_underscore_err_on_new_line,  MdLineFragment::Italic),
-            map(parse_fragment_starts_with_star_err_on_new_line,        MdLineFragment::Bold),
-            map(parse_fragment_starts_with_backtick_err_on_new_line,    MdLineFragment::InlineCode),
-            map(parse_fragment_starts_with_left_image_err_on_new_line,  MdLineFragment::Image),
-            map(parse_fragment_starts_with_left_link_err_on_new_line,   MdLineFragment::Link),
-            map(parse_fragment_starts_with_checkbox_checkbox_into_bool, MdLineFragment::Checkbox), // This line is different.
-            map(parse_fragment_plain_text_no_new_line,                  MdLineFragment::Plain),
+            map(parse_fragment_starts_with_double_tilde_err_on_new_line,  |it| MdLineFragment::Strikethrough(parse_nested_fragments(it))),
+            map(parse_fragment_starts_with_double_equals_err_on_new_line, |it| MdLineFragment::Highlight(parse_nested_fragments(it))),
+            map(parse_fragment_starts_with_underscore_err_on_new_line,    MdLineFragment::Italic),
+            map(parse_fragment_starts_with_star_err_on_new_line,          MdLineFragment::Bold),
+            map(parse_fragment_starts_with_backtick_err_on_new_line,      MdLineFragment::InlineCode),
+            map(parse_fragment_starts_with_footnote_ref_err_on_new_line,  MdLineFragment::FootnoteRef),
+            map(parse_fragment_starts_with_left_image_err_on_new_line,    MdLineFragment::Image),
+            map(parse_fragment_starts_with_left_link_err_on_new_line,     MdLineFragment::Link),
+            map(parse_fragment_starts_with_checkbox_checkbox_into_bool,   MdLineFragment::Checkbox), // This line is different.
+            map(parse_fragment_plain_text_no_new_line,                    MdLineFragment::Plain),
         ))(input)
 
     };
@@ -108,6 +121,14 @@ pub fn parse_inline_fragments_until_eol_or_eoi(
     it
 }
 
+/// The text of a strikethrough or highlight fragment may have other fragments in it.
+fn parse_nested_fragments(text: &str) -> MdLineFragments<'_> {
+    match parse_block_markdown_text_with_or_without_new_line(text) {
+        Ok((_, fragments)) => fragments,
+        Err(_) => list![MdLineFragment::Plain(text)],
+    }
+}
+
 #[cfg(test)]
 mod tests_parse_fragment {
     use nom::{error::{Error, ErrorKind},
@@ -148,6 +169,81 @@ mod tests_parse_fragment {
         );
     }
 
+    #[test]
+    fn test_parse_fragment_strikethrough_and_highlight() {
+        let parse = |it| {
+            parse_inline_fragments_until_eol_or_eoi(
+                it,
+                CheckboxParsePolicy::IgnoreCheckbox,
+            )
+        };
+
+        assert_eq2!(
+            parse("~~strike~~ rest"),
+            Ok((" rest", MdLineFragment::Strikethrough(list![MdLineFragment::Plain("strike")])))
+        );
+        assert_eq2!(
+            parse("==highlight== rest"),
+            Ok((" rest", MdLineFragment::Highlight(list![MdLineFragment::Plain("highlight")])))
+        );
+
+        // The other fragments in the text are parsed, so they nest.
+        assert_eq2!(
+            parse("~~*bold strike*~~"),
+            Ok(("", MdLineFragment::Strikethrough(list![MdLineFragment::Bold("bold strike")])))
+        );
+        assert_eq2!(
+            parse("==_italic_ `code`=="),
+            Ok(("", MdLineFragment::Highlight(list![
+                    MdLineFragment::Italic("italic"),
+                    MdLineFragment::Plain(" "),
+                    MdLineFragment::InlineCode("code"),
+                ])))
+        );
+
+        // A single `~` or `=` isn't special.
+        assert_eq2!(parse("a~b=c"), Ok(("", MdLineFragment::Plain("a~b=c"))));
+
+        // No closing delim.
+        assert_eq2!(
+            parse("~~strike"),
+            Ok(("strike", MdLineFragment::Plain("~~")))
+        );
+
+        // Text padded w/ whitespace isn't strikethrough or highlight.
+        assert_eq2!(
+            parse("a == b == c"),
+            Ok(("== b == c", MdLineFragment::Plain("a ")))
+        );
+        assert_eq2!(
+            parse("== b == c"),
+            Ok(("", MdLineFragment::Plain("== b == c")))
+        );
+    }
+
+    #[test]
+    fn test_parse_fragment_footnote_ref() {
+        assert_eq2!(
+            parse_fragment_starts_with_footnote_ref_err_on_new_line("[^1] and more"),
+            Ok((/*rem*/ " and more", /*output*/ "1"))
+        );
+
+        assert_eq2!(
+            parse_inline_fragments_until_eol_or_eoi(
+                "[^note]: text",
+                CheckboxParsePolicy::IgnoreCheckbox
+            ),
+            Ok((": text", MdLineFragment::FootnoteRef("note")))
+        );
+
+        for input in ["[^]", "[^a b]", "[^1", "[1]", "[^\n1]"] {
+            assert!(
+                parse_fragment_starts_with_footnote_ref_err_on_new_line(input).is_err(),
+                "input: {input:?}"
+            );
+        }
+    }
+
     #[test]
     fn test_parse_fragment_checkbox_into_bool() {
         assert_eq2!(
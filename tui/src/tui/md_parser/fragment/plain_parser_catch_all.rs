@@ -43,6 +43,8 @@ use nom::{branch::alt,
 use r3bl_core::call_if_true;
 
 use crate::{constants::{BACK_TICK,
+                        DOUBLE_EQUALS,
+                        DOUBLE_TILDE,
                         LEFT_BRACKET,
                         LEFT_IMAGE,
                         NEW_LINE,
@@ -97,14 +99,14 @@ pub fn parse_fragment_plain_text_no_new_line(input: &str) -> IResult<&str, &str>
         // special case above will be triggered.
 
         // `tag_tuple` replaces the following:
-        // `( tag(UNDERSCORE), tag(STAR), tag(BACK_TICK), tag(LEFT_IMAGE), tag(LEFT_BRACKET), tag(NEW_LINE) )`
+        // `( tag(UNDERSCORE), tag(STAR), tag(BACK_TICK), tag(DOUBLE_TILDE), tag(DOUBLE_EQUALS), tag(LEFT_IMAGE), tag(LEFT_BRACKET), tag(NEW_LINE) )`
         let tag_vec = get_sp_char_set_3()
             .into_iter()
             .map(tag::<&str, &str, nom::error::Error<&str>>)
             .collect::<Vec<_>>();
         let tag_tuple = {
-            assert_eq!(tag_vec.len(), 6);
-            tuple6(&tag_vec)
+            assert_eq!(tag_vec.len(), 8);
+            tuple8(&tag_vec)
         };
 
         let it = recognize(
@@ -139,8 +141,8 @@ pub fn parse_fragment_plain_text_no_new_line(input: &str) -> IResult<&str, &str>
     // line. Since the specialized parsers did not match the input.
 
     // # Edge case -> Special case:
-    // Check for single UNDERSCORE, STAR, BACK_TICK. until the first new line. This is
-    // to handle the case with
+    // Check for single UNDERSCORE, STAR, BACK_TICK, DOUBLE_TILDE, DOUBLE_EQUALS. until
+    // the first new line. This is to handle the case with
     // [specialized_parser_delim_matchers::take_starts_with_delim_no_new_line()] where
     // there is no closing delim found.
     if let Some(special_str) = check_input_starts_with(input, &get_sp_char_set_1()) {
@@ -191,7 +193,9 @@ pub fn parse_fragment_plain_text_no_new_line(input: &str) -> IResult<&str, &str>
 /// only 1 occurrence is found, then this parser's `Edge case -> Special case` will take
 /// care of it by splitting the input, and returning the first part as plain text, and the
 /// remainder as the input to be parsed by the specialized parsers.
-pub fn get_sp_char_set_1<'a>() -> [&'a str; 3] { [UNDERSCORE, STAR, BACK_TICK] }
+pub fn get_sp_char_set_1<'a>() -> [&'a str; 5] {
+    [UNDERSCORE, STAR, BACK_TICK, DOUBLE_TILDE, DOUBLE_EQUALS]
+}
 
 /// This is a special set of chars called `set_2`.
 ///
@@ -200,7 +204,7 @@ pub fn get_sp_char_set_1<'a>() -> [&'a str; 3] { [UNDERSCORE, STAR, BACK_TICK] }
 /// return as plain text. Unless both of the following are true:
 /// 1. input is in [get_sp_char_set_1()] and,
 /// 2. count is 1.
-pub fn get_sp_char_set_2<'a>() -> [&'a str; 5] {
+pub fn get_sp_char_set_2<'a>() -> [&'a str; 7] {
     get_sp_char_set_1()
         .iter()
         .chain([LEFT_IMAGE, LEFT_BRACKET].iter())
@@ -217,7 +221,7 @@ pub fn get_sp_char_set_2<'a>() -> [&'a str; 5] {
 /// special character, and split there. This returns the chunk until the first special
 /// character as [crate::MdLineFragment::Plain], and the remainder of the input gets a
 /// chance to be parsed by the specialized parsers.
-pub fn get_sp_char_set_3<'a>() -> [&'a str; 6] {
+pub fn get_sp_char_set_3<'a>() -> [&'a str; 8] {
     get_sp_char_set_2()
         .iter()
         .chain([NEW_LINE].iter())
@@ -241,3 +245,6 @@ pub fn tuple5<T>(a: &[T]) -> (&T, &T, &T, &T, &T) { (&a[0], &a[1], &a[2], &a[3],
 pub fn tuple6<T>(a: &[T]) -> (&T, &T, &T, &T, &T, &T) {
     (&a[0], &a[1], &a[2], &a[3], &a[4], &a[5])
 }
+pub fn tuple8<T>(a: &[T]) -> (&T, &T, &T, &T, &T, &T, &T, &T) {
+    (&a[0], &a[1], &a[2], &a[3], &a[4], &a[5], &a[6], &a[7])
+}
@@ -16,7 +16,8 @@
  */
 
 //! For use with specialized parsers for: [crate::constants::UNDERSCORE],
-//! [crate::constants::STAR], and [crate::constants::BACK_TICK]. See:
+//! [crate::constants::STAR], [crate::constants::BACK_TICK],
+//! [crate::constants::DOUBLE_TILDE], and [crate::constants::DOUBLE_EQUALS]. See:
 //! [crate::parse_fragment_plain_text_no_new_line()].
 //!
 //! To see this in action, set the [DEBUG_MD_PARSER_STDOUT] to true, and run all the tests
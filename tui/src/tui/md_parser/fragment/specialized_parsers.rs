@@ -17,15 +17,20 @@
 
 use crossterm::style::Stylize;
 use nom::{branch::alt,
-          bytes::complete::tag,
-          combinator::{map, recognize},
+          bytes::complete::{tag, take_till1},
+          combinator::{map, recognize, verify},
           multi::many0,
+          sequence::delimited,
           IResult};
 use r3bl_core::call_if_true;
 
 use super::specialized_parser_delim_matchers;
 use crate::{constants::{BACK_TICK,
                         CHECKED,
+                        DOUBLE_EQUALS,
+                        DOUBLE_TILDE,
+                        FOOTNOTE_REF_END,
+                        FOOTNOTE_REF_START,
                         LEFT_BRACKET,
                         LEFT_IMAGE,
                         LEFT_PARENTHESIS,
@@ -53,6 +58,44 @@ pub fn parse_fragment_starts_with_star_err_on_new_line(
     specialized_parser_delim_matchers::take_starts_with_delim_no_new_line(input, STAR)
 }
 
+/// The text between the `~~` is parsed into other fragments by
+/// [crate::parse_inline_fragments_until_eol_or_eoi()], eg: `~~*bold strike*~~`. The text
+/// can't start or end w/ whitespace, so that something like `a ~~ b ~~ c` stays plain text.
+pub fn parse_fragment_starts_with_double_tilde_err_on_new_line(
+    input: &str,
+) -> IResult<&str, &str> {
+    verify(
+        |it| {
+            specialized_parser_delim_matchers::take_starts_with_delim_no_new_line(
+                it,
+                DOUBLE_TILDE,
+            )
+        },
+        is_not_padded_with_whitespace,
+    )(input)
+}
+
+/// The text between the `==` is parsed into other fragments by
+/// [crate::parse_inline_fragments_until_eol_or_eoi()], eg: `==*bold highlight*==`. The text
+/// can't start or end w/ whitespace, so that something like `a == b == c` stays plain text.
+pub fn parse_fragment_starts_with_double_equals_err_on_new_line(
+    input: &str,
+) -> IResult<&str, &str> {
+    verify(
+        |it| {
+            specialized_parser_delim_matchers::take_starts_with_delim_no_new_line(
+                it,
+                DOUBLE_EQUALS,
+            )
+        },
+        is_not_padded_with_whitespace,
+    )(input)
+}
+
+fn is_not_padded_with_whitespace(it: &str) -> bool {
+    !it.starts_with(char::is_whitespace) && !it.ends_with(char::is_whitespace)
+}
+
 pub fn parse_fragment_starts_with_backtick_err_on_new_line(
     input: &str,
 ) -> IResult<&str, &str> {
@@ -237,3 +280,28 @@ pub fn parse_fragment_starts_with_checkbox_checkbox_into_bool(
     });
     it
 }
+
+/// Footnote references also begin with "[", so this has to run before the parsers for
+/// hyperlinks and images. The label can't be empty, or have whitespace in it, eg: `[^1]`
+/// or `[^note]`. It returns the label.
+pub fn parse_fragment_starts_with_footnote_ref_err_on_new_line(
+    input: &str,
+) -> IResult<&str, &str> {
+    let it = delimited(
+        tag(FOOTNOTE_REF_START),
+        take_till1(|it: char| it.is_whitespace() || it == '[' || it == ']'),
+        tag(FOOTNOTE_REF_END),
+    )(input);
+    call_if_true!(DEBUG_MD_PARSER_STDOUT, {
+        println!(
+            "{} specialized parser for footnote reference: {:?}",
+            if it.is_err() {
+                "⬢⬢".red()
+            } else {
+                "▲▲".blue()
+            },
+            it
+        );
+    });
+    it
+}
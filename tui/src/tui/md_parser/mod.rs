@@ -162,6 +162,7 @@
 // External use.
 pub mod atomics;
 pub mod block;
+pub mod convert_to_html;
 pub mod convert_to_plain_text;
 pub mod extended;
 pub mod fragment;
@@ -170,6 +171,7 @@ pub mod types;
 
 pub use atomics::*;
 pub use block::*;
+pub use convert_to_html::*;
 pub use convert_to_plain_text::*;
 pub use extended::*;
 pub use fragment::*;
@@ -15,18 +15,34 @@
  *   limitations under the License.
  */
 
-use nom::{branch::alt, combinator::map, multi::many0, IResult};
+use std::collections::HashSet;
 
-use crate::{constants::{AUTHORS, DATE, TAGS, TITLE},
+use nom::{branch::alt,
+          combinator::{map, opt},
+          multi::many0,
+          IResult};
+
+use crate::{constants::{AUTHORS,
+                        DATE,
+                        FOOTNOTE_REF_END,
+                        FOOTNOTE_REF_START,
+                        TAGS,
+                        TITLE},
             parse_block_code,
+            parse_block_footnote_def,
             parse_block_heading_opt_eol,
             parse_block_markdown_text_with_or_without_new_line,
+            parse_block_quote,
             parse_block_smart_list,
+            parse_block_table,
             parse_csv_opt_eol,
+            parse_front_matter,
             parse_unique_kv_opt_eol,
             List,
             MdBlock,
-            MdDocument};
+            MdDocument,
+            MdLineFragment,
+            MdLineFragments};
 
 // BOOKM: Main Markdown parser entry point
 
@@ -48,9 +64,37 @@ use crate::{constants::{AUTHORS, DATE, TAGS, TITLE},
 ///    [mod@parse_block_code] file handle this.
 /// 6. line (which contains a [crate::MdLineFragments]). The parsers in
 ///    [mod@crate::fragment] handle this.
-#[rustfmt::skip]
+/// 7. Front matter (which contains the raw content between the `---` delimiters). This
+///    is only parsed at the very start of the document by [crate::parse_front_matter].
+/// 8. Table (which contains the header, alignments & rows of a pipe table). The parsers in
+///    [mod@parse_block_table] file handle this.
+/// 9. Block quote (which contains the blocks in the quote, including nested quotes). The
+///    parsers in [mod@parse_block_quote] file handle this.
+/// 10. Footnote definition (which contains the label & the text of a footnote). The
+///     parsers in [mod@parse_block_footnote_def] file handle this. Footnote references
+///     w/out a definition in the document are turned back into plain text.
 pub fn parse_markdown(input: &str) -> IResult<&str, MdDocument<'_>> {
-    let (input, output) = many0(
+    // Front matter is only valid at the very start of the document.
+    let (input, maybe_front_matter) = opt(parse_front_matter)(input)?;
+
+    let (input, output) = parse_markdown_blocks(input)?;
+
+    let mut it = maybe_front_matter
+        .map(MdBlock::FrontMatter)
+        .into_iter()
+        .chain(output)
+        .collect::<Vec<_>>();
+    convert_undefined_footnote_refs_into_plain_text(&mut it);
+    Ok((input, List::from(it)))
+}
+
+/// Parse the blocks of Markdown in the `input`, w/out the front matter. Unlike
+/// [parse_markdown()], footnote references aren't checked against the footnote
+/// definitions, since they may be in another part of the document, eg: when this is
+/// used to parse the lines of a block quote.
+#[rustfmt::skip]
+pub fn parse_markdown_blocks(input: &str) -> IResult<&str, Vec<MdBlock<'_>>> {
+    many0(
         // NOTE: The ordering of the parsers below matters.
         alt((
             map(parse_title_value,                                  MdBlock::Title),
@@ -60,12 +104,82 @@ pub fn parse_markdown(input: &str) -> IResult<&str, MdDocument<'_>> {
             map(parse_block_heading_opt_eol,                        MdBlock::Heading),
             map(parse_block_smart_list,                             MdBlock::SmartList),
             map(parse_block_code,                                   MdBlock::CodeBlock),
+            parse_block_table,
+            parse_block_quote,
+            parse_block_footnote_def,
             map(parse_block_markdown_text_with_or_without_new_line, MdBlock::Text),
         )),
-    )(input)?;
+    )(input)
+}
 
-    let it = List::from(output);
-    Ok((input, it))
+/// A footnote reference w/out a definition (eg: `[^1]` w/out `[^1]: text`) isn't an
+/// error, it is just turned back into the plain text that it was parsed from.
+fn convert_undefined_footnote_refs_into_plain_text(blocks: &mut [MdBlock<'_>]) {
+    let mut labels = HashSet::new();
+    for block in blocks.iter() {
+        collect_footnote_labels(block, &mut labels);
+    }
+
+    for block in blocks.iter_mut() {
+        for_each_fragments_mut(block, &mut |fragments| {
+            if !fragments.iter().any(|it| {
+                matches!(it, MdLineFragment::FootnoteRef(label) if !labels.contains(label))
+            }) {
+                return;
+            }
+            let mut acc = Vec::with_capacity(fragments.len() + 2);
+            for fragment in fragments.inner.drain(..) {
+                match fragment {
+                    MdLineFragment::FootnoteRef(label) if !labels.contains(label) => {
+                        acc.push(MdLineFragment::Plain(FOOTNOTE_REF_START));
+                        acc.push(MdLineFragment::Plain(label));
+                        acc.push(MdLineFragment::Plain(FOOTNOTE_REF_END));
+                    }
+                    _ => acc.push(fragment),
+                }
+            }
+            fragments.inner = acc;
+        });
+    }
+}
+
+fn collect_footnote_labels<'a>(block: &MdBlock<'a>, acc: &mut HashSet<&'a str>) {
+    match block {
+        MdBlock::FootnoteDef { label, .. } => {
+            acc.insert(label);
+        }
+        MdBlock::BlockQuote { blocks, .. } => {
+            for block in blocks.iter() {
+                collect_footnote_labels(block, acc);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Call `f` w/ each line of [MdLineFragments] in the `block`, including the ones in the
+/// blocks of a block quote.
+fn for_each_fragments_mut<'a>(
+    block: &mut MdBlock<'a>,
+    f: &mut impl FnMut(&mut MdLineFragments<'a>),
+) {
+    match block {
+        MdBlock::Text(fragments)
+        | MdBlock::FootnoteDef {
+            content: fragments, ..
+        } => f(fragments),
+        MdBlock::SmartList((lines, _, _)) => lines.iter_mut().for_each(f),
+        MdBlock::Table { headers, rows, .. } => {
+            headers.iter_mut().for_each(&mut *f);
+            rows.iter_mut().flatten().for_each(f);
+        }
+        MdBlock::BlockQuote { blocks, .. } => {
+            for block in blocks.iter_mut() {
+                for_each_fragments_mut(block, f);
+            }
+        }
+        _ => {}
+    }
 }
 
 // key: TAGS, value: CSV parser.
@@ -97,11 +211,173 @@ mod tests {
     use crate::{convert_into_code_block_lines,
                 list,
                 BulletKind,
+                ColumnAlignment,
                 HeadingData,
                 HeadingLevel,
                 HyperlinkData,
                 MdLineFragment};
 
+    #[test]
+    fn test_table() {
+        let input = "before\n| a | b |\n|:--|--:|\n| 1 | 2 |\nafter";
+        let (remainder, blocks) = parse_markdown(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 3);
+        assert_eq2!(
+            blocks[0],
+            MdBlock::Text(list![MdLineFragment::Plain("before")])
+        );
+        assert_eq2!(
+            blocks[1],
+            MdBlock::Table {
+                headers: vec![
+                    list![MdLineFragment::Plain("a")],
+                    list![MdLineFragment::Plain("b")]
+                ],
+                alignments: vec![ColumnAlignment::Left, ColumnAlignment::Right],
+                rows: vec![vec![
+                    list![MdLineFragment::Plain("1")],
+                    list![MdLineFragment::Plain("2")]
+                ]],
+                raw_lines: vec!["| a | b |", "|:--|--:|", "| 1 | 2 |"],
+            }
+        );
+        assert_eq2!(
+            blocks[2],
+            MdBlock::Text(list![MdLineFragment::Plain("after")])
+        );
+    }
+
+    #[test]
+    fn test_block_quote() {
+        let input = "> Foo\n>> *Bar*\nbaz\n";
+        let (remainder, blocks) = parse_markdown(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 1);
+        assert_eq2!(
+            blocks[0],
+            MdBlock::BlockQuote {
+                blocks: list![
+                    MdBlock::Text(list![MdLineFragment::Plain("Foo")]),
+                    MdBlock::BlockQuote {
+                        blocks: list![
+                            MdBlock::Text(list![MdLineFragment::Bold("Bar")]),
+                            MdBlock::Text(list![MdLineFragment::Plain("baz")]),
+                        ],
+                        raw_lines: vec!["> *Bar*", "baz"],
+                    },
+                ],
+                raw_lines: vec!["> Foo", ">> *Bar*", "baz"],
+            }
+        );
+    }
+
+    /// Conformance: malformed quote markers fall back to text.
+    #[test]
+    fn test_invalid_block_quote_markers() {
+        for input in ["    > indented", "\\> escaped", "a > b", "\t> tab"] {
+            let (remainder, blocks) = parse_markdown(input).unwrap();
+            assert_eq2!(remainder, "");
+            assert_eq2!(blocks.len(), 1);
+            assert!(matches!(blocks[0], MdBlock::Text(_)), "input: {input:?}");
+        }
+    }
+
+    /// Conformance: strikethrough & highlight in the different blocks that have text.
+    #[test]
+    fn test_strikethrough_and_highlight() {
+        let input = [
+            "# ~~Heading~~",
+            "Some ~~*bold strike*~~ and ==_italic highlight_== text",
+            "- ~~done~~ ==todo==",
+            "a ~~ b ~~ c",
+            "> ==quoted==",
+        ]
+        .join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 5);
+        assert_eq2!(
+            blocks[1],
+            MdBlock::Text(list![
+                MdLineFragment::Plain("Some "),
+                MdLineFragment::Strikethrough(list![MdLineFragment::Bold("bold strike")]),
+                MdLineFragment::Plain(" and "),
+                MdLineFragment::Highlight(list![MdLineFragment::Italic(
+                    "italic highlight"
+                )]),
+                MdLineFragment::Plain(" text"),
+            ])
+        );
+        let MdBlock::SmartList((lines, _, _)) = &blocks[2] else {
+            panic!("Expected a smart list.");
+        };
+        assert_eq2!(
+            lines[0][1],
+            MdLineFragment::Strikethrough(list![MdLineFragment::Plain("done")])
+        );
+        assert_eq2!(
+            lines[0][3],
+            MdLineFragment::Highlight(list![MdLineFragment::Plain("todo")])
+        );
+        // Text padded w/ whitespace is plain text.
+        assert!(matches!(&blocks[3], MdBlock::Text(fragments)
+            if fragments.iter().all(|it| matches!(it, MdLineFragment::Plain(_)))));
+        let MdBlock::BlockQuote { blocks: quote, .. } = &blocks[4] else {
+            panic!("Expected a block quote.");
+        };
+        assert_eq2!(
+            quote[0],
+            MdBlock::Text(list![MdLineFragment::Highlight(list![
+                MdLineFragment::Plain("quoted")
+            ])])
+        );
+    }
+
+    #[test]
+    fn test_footnotes() {
+        let input = "Foo[^1] bar[^2]\n\n[^1]: The *note*\n";
+        let (remainder, blocks) = parse_markdown(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            blocks[0],
+            MdBlock::Text(list![
+                MdLineFragment::Plain("Foo"),
+                MdLineFragment::FootnoteRef("1"),
+                MdLineFragment::Plain(" bar"),
+                // There's no definition for `[^2]`, so it is plain text.
+                MdLineFragment::Plain("[^"),
+                MdLineFragment::Plain("2"),
+                MdLineFragment::Plain("]"),
+            ])
+        );
+        assert_eq2!(
+            blocks[2],
+            MdBlock::FootnoteDef {
+                label: "1",
+                content: list![
+                    MdLineFragment::Plain(" The "),
+                    MdLineFragment::Bold("note")
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_footnote_ref_in_block_quote() {
+        let (_, blocks) = parse_markdown("> Foo[^a]\n\n[^a]: note").unwrap();
+        let MdBlock::BlockQuote { blocks, .. } = &blocks[0] else {
+            panic!("Expected a block quote.");
+        };
+        assert_eq2!(
+            blocks[0],
+            MdBlock::Text(list![
+                MdLineFragment::Plain("Foo"),
+                MdLineFragment::FootnoteRef("a"),
+            ])
+        );
+    }
+
     #[test]
     fn test_no_line() {
         let input = "Something";
@@ -367,4 +643,130 @@ mod tests {
         assert_eq2!(remainder, "");
         assert_eq2!(blocks.len(), 7);
     }
+
+    #[test]
+    fn test_parse_markdown_with_front_matter() {
+        let input = ["---", "title: Foo", "tags: [a, b]", "---", "# Foo", ""].join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 2);
+        assert_eq2!(
+            blocks[0],
+            MdBlock::FrontMatter("title: Foo\ntags: [a, b]\n")
+        );
+        assert_eq2!(
+            blocks[1],
+            MdBlock::Heading(HeadingData {
+                heading_level: HeadingLevel { level: 1 },
+                text: "Foo",
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_markdown_valid_with_front_matter() {
+        let input = [
+            "---",
+            "title: \"Front matter: ---\"",
+            "tags:",
+            "  - a",
+            "  - b",
+            "",
+            "---",
+            "# Foo",
+            "",
+            "```yaml",
+            "---",
+            "key: value",
+            "---",
+            "```",
+            "- item",
+            "---",
+            "end",
+            "",
+        ]
+        .join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        let expected_blocks = [
+            MdBlock::FrontMatter("title: \"Front matter: ---\"\ntags:\n  - a\n  - b\n\n"),
+            MdBlock::Heading(HeadingData {
+                heading_level: HeadingLevel { level: 1 },
+                text: "Foo",
+            }),
+            MdBlock::Text(list![]), // Empty line.
+            MdBlock::CodeBlock(convert_into_code_block_lines(
+                Some("yaml"),
+                vec!["---", "key: value", "---"],
+            )),
+            MdBlock::SmartList((
+                list![list![
+                    MdLineFragment::UnorderedListBullet {
+                        indent: 0,
+                        is_first_line: true
+                    },
+                    MdLineFragment::Plain("item"),
+                ],],
+                BulletKind::Unordered,
+                0,
+            )),
+            // Only the first block can be front matter.
+            MdBlock::Text(list![MdLineFragment::Plain("---")]),
+            MdBlock::Text(list![MdLineFragment::Plain("end")]),
+        ];
+
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), expected_blocks.len());
+        blocks
+            .iter()
+            .zip(expected_blocks.iter())
+            .for_each(|(lhs, rhs)| assert_eq2!(lhs, rhs));
+    }
+
+    #[test]
+    fn test_parse_markdown_front_matter_edge_cases() {
+        // Empty front matter, w/ nothing after it.
+        let (remainder, blocks) = parse_markdown("---\n---").unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 1);
+        assert_eq2!(blocks[0], MdBlock::FrontMatter(""));
+
+        // The closing delimiter has to be exactly `---`.
+        let input = ["---", "a: 1", "--- ", "----", "---", "text"].join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 2);
+        assert_eq2!(blocks[0], MdBlock::FrontMatter("a: 1\n--- \n----\n"));
+        assert_eq2!(
+            blocks[1],
+            MdBlock::Text(list![MdLineFragment::Plain("text")])
+        );
+
+        // Leading blank line, so it isn't front matter.
+        let input = ["", "---", "a: 1", "---", ""].join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert!(!blocks
+            .iter()
+            .any(|block| matches!(block, MdBlock::FrontMatter(_))));
+    }
+
+    #[test]
+    fn test_parse_markdown_front_matter_only_at_start() {
+        let input = ["# Foo", "---", "title: Foo", "---", ""].join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 4);
+        assert!(!blocks
+            .iter()
+            .any(|block| matches!(block, MdBlock::FrontMatter(_))));
+    }
+
+    #[test]
+    fn test_parse_markdown_unterminated_front_matter() {
+        let input = ["---", "title: Foo", "# Foo", ""].join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 3);
+        assert!(matches!(blocks[0], MdBlock::Text(_)));
+    }
 }
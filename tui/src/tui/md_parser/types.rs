@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use crate::{BulletKind, List};
+use crate::{get_front_matter_line_count, BulletKind, List};
 
 /// This corresponds to a single Markdown document, which is produced after a successful
 /// parse operation [crate::parse_markdown()].
@@ -35,6 +35,7 @@ pub type FragmentsInOneLine<'a> = MdLineFragments<'a>;
 pub type Lines<'a> = List<FragmentsInOneLine<'a>>;
 
 #[derive(Clone, Debug, PartialEq, size_of::SizeOf)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeadingData<'a> {
     pub heading_level: HeadingLevel,
     pub text: &'a str,
@@ -48,20 +49,88 @@ pub struct HeadingData<'a> {
 /// - There are some exceptions such as smart lists and code blocks which represent
 ///   multiple lines of text.
 #[derive(Clone, Debug, PartialEq, size_of::SizeOf)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MdBlock<'a> {
-    Heading(HeadingData<'a>),
-    SmartList((Lines<'a>, BulletKind, usize)),
-    Text(MdLineFragments<'a>),
-    CodeBlock(List<CodeBlockLine<'a>>),
+    Heading(#[cfg_attr(feature = "md-serde", serde(borrow))] HeadingData<'a>),
+    SmartList(
+        #[cfg_attr(feature = "md-serde", serde(borrow))] (Lines<'a>, BulletKind, usize),
+    ),
+    Text(#[cfg_attr(feature = "md-serde", serde(borrow))] MdLineFragments<'a>),
+    CodeBlock(#[cfg_attr(feature = "md-serde", serde(borrow))] List<CodeBlockLine<'a>>),
     Title(&'a str),
     Date(&'a str),
-    Tags(List<&'a str>),
-    Authors(List<&'a str>),
+    Tags(#[cfg_attr(feature = "md-serde", serde(borrow))] List<&'a str>),
+    Authors(#[cfg_attr(feature = "md-serde", serde(borrow))] List<&'a str>),
+    /// The raw content of the front matter (eg: YAML) at the start of the document. See
+    /// [crate::parse_front_matter()].
+    FrontMatter(&'a str),
+    /// Lines that start w/ a `>` marker, w/ one block for each line (w/out its marker),
+    /// except for nested quotes. See [crate::parse_block_quote()]. The `raw_lines` are the
+    /// lines as they are in the document (w/ their marker), which are used to render the
+    /// quote in the editor.
+    BlockQuote {
+        #[cfg_attr(feature = "md-serde", serde(borrow))]
+        #[size_of(skip_bounds)]
+        blocks: List<MdBlock<'a>>,
+        #[cfg_attr(feature = "md-serde", serde(borrow))]
+        raw_lines: Vec<&'a str>,
+    },
+    /// The definition of a footnote, eg: `[^1]: Some text.`. The `content` is everything
+    /// after the `:`, including any leading spaces. See [crate::parse_block_footnote_def()].
+    FootnoteDef {
+        label: &'a str,
+        #[cfg_attr(feature = "md-serde", serde(borrow))]
+        content: MdLineFragments<'a>,
+    },
+    /// A GitHub style pipe table. See [crate::parse_block_table()]. The `raw_lines` are
+    /// the rows as they are in the document (w/out the new line), including the separator
+    /// row, which are used to render the table in the editor.
+    Table {
+        #[cfg_attr(feature = "md-serde", serde(borrow))]
+        headers: Vec<MdLineFragments<'a>>,
+        alignments: Vec<ColumnAlignment>,
+        #[cfg_attr(feature = "md-serde", serde(borrow))]
+        rows: Vec<Vec<MdLineFragments<'a>>>,
+        #[cfg_attr(feature = "md-serde", serde(borrow))]
+        raw_lines: Vec<&'a str>,
+    },
+}
+
+/// The alignment of a column in a [MdBlock::Table], which is set by its separator row, eg:
+/// `:---` is [ColumnAlignment::Left], `:---:` is [ColumnAlignment::Center], `---:` is
+/// [ColumnAlignment::Right], and `---` is [ColumnAlignment::None].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, size_of::SizeOf)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnAlignment {
+    #[default]
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl MdBlock<'_> {
+    /// Returns the number of lines of the document that this block was parsed from, or
+    /// [None] for a list or code block w/out any lines (which can't be mapped back to the
+    /// document).
+    pub fn get_line_count(&self) -> Option<usize> {
+        match self {
+            MdBlock::SmartList((lines, _, _)) if lines.is_empty() => None,
+            MdBlock::SmartList((lines, _, _)) => Some(lines.len()),
+            MdBlock::CodeBlock(lines) if lines.is_empty() => None,
+            MdBlock::CodeBlock(lines) => Some(lines.len()),
+            MdBlock::FrontMatter(content) => Some(get_front_matter_line_count(content)),
+            MdBlock::Table { raw_lines, .. } => Some(raw_lines.len()),
+            MdBlock::BlockQuote { raw_lines, .. } => Some(raw_lines.len()),
+            _ => Some(1),
+        }
+    }
 }
 
 /// These are things that show up in a single line of Markdown text [MdLineFragments]. They do not
 /// include other Markdown blocks (like code blocks, lists, headings, etc).
-#[derive(Clone, Debug, PartialEq, size_of::SizeOf)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MdLineFragment<'a> {
     UnorderedListBullet {
         indent: usize,
@@ -76,12 +145,33 @@ pub enum MdLineFragment<'a> {
     Bold(&'a str),
     Italic(&'a str),
     InlineCode(&'a str),
-    Link(HyperlinkData<'a>),
-    Image(HyperlinkData<'a>),
+    /// The fragments parsed from the text between `~~`, eg: `*bold*`.
+    Strikethrough(#[cfg_attr(feature = "md-serde", serde(borrow))] MdLineFragments<'a>),
+    /// The fragments parsed from the text between `==`, eg: `*bold*`.
+    Highlight(#[cfg_attr(feature = "md-serde", serde(borrow))] MdLineFragments<'a>),
+    Link(#[cfg_attr(feature = "md-serde", serde(borrow))] HyperlinkData<'a>),
+    Image(#[cfg_attr(feature = "md-serde", serde(borrow))] HyperlinkData<'a>),
     Checkbox(bool),
+    /// The label of a footnote reference, eg: `1` for `[^1]`. See
+    /// [MdBlock::FootnoteDef].
+    FootnoteRef(&'a str),
+}
+
+/// This isn't derived, since the derived bounds of the nested [MdLineFragments] would
+/// require [MdLineFragment] to implement it, which never ends. The text is borrowed, so
+/// only the nested fragments are counted.
+impl size_of::SizeOf for MdLineFragment<'_> {
+    fn size_of_children(&self, context: &mut size_of::Context) {
+        match self {
+            MdLineFragment::Strikethrough(fragments)
+            | MdLineFragment::Highlight(fragments) => fragments.size_of_children(context),
+            _ => {}
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, size_of::SizeOf)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HyperlinkData<'a> {
     pub text: &'a str,
     pub url: &'a str,
@@ -100,6 +190,7 @@ mod hyperlink_data_impl {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, size_of::SizeOf)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeadingLevel {
     pub level: usize,
 }
@@ -118,6 +209,7 @@ pub mod constants {
     pub const DATE: &str = "@date";
     pub const TITLE: &str = "@title";
     pub const TAGS: &str = "@tags";
+    pub const FRONT_MATTER_DELIMITER: &str = "---";
     pub const COLON: &str = ":";
     pub const COMMA: &str = ",";
     pub const QUOTE: &str = "\"";
@@ -142,6 +234,8 @@ pub mod constants {
     pub const STAR: &str = "*";
     pub const UNDERSCORE: &str = "_";
     pub const BACK_TICK: &str = "`";
+    pub const DOUBLE_TILDE: &str = "~~";
+    pub const DOUBLE_EQUALS: &str = "==";
     pub const LEFT_BRACKET: &str = "[";
     pub const RIGHT_BRACKET: &str = "]";
     pub const LEFT_PARENTHESIS: &str = "(";
@@ -150,6 +244,12 @@ pub mod constants {
     pub const RIGHT_IMAGE: &str = "]";
     pub const NEW_LINE: &str = "\n";
     pub const NEW_LINE_CHAR: char = '\n';
+    pub const FOOTNOTE_REF_START: &str = "[^";
+    pub const FOOTNOTE_REF_END: &str = "]";
+    pub const BLOCK_QUOTE_MARKER_CHAR: char = '>';
+    pub const TABLE_PIPE_CHAR: char = '|';
+    pub const TABLE_ALIGN_CHAR: char = ':';
+    pub const TABLE_SEPARATOR_CHAR: char = '-';
     pub const CODE_BLOCK_START_PARTIAL: &str = "```";
     pub const CODE_BLOCK_END: &str = "```";
     pub const CHECKED: &str = "[x]";
@@ -160,8 +260,11 @@ pub mod constants {
 }
 
 #[derive(Debug, PartialEq, Clone, size_of::SizeOf)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CodeBlockLine<'a> {
+    #[cfg_attr(feature = "md-serde", serde(borrow))]
     pub language: Option<&'a str>,
+    #[cfg_attr(feature = "md-serde", serde(borrow))]
     pub content: CodeBlockLineContent<'a>,
 }
 
@@ -169,6 +272,7 @@ pub struct CodeBlockLine<'a> {
 pub type CodeBlockLines<'a> = List<CodeBlockLine<'a>>;
 
 #[derive(Debug, PartialEq, Clone, size_of::SizeOf)]
+#[cfg_attr(feature = "md-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CodeBlockLineContent<'a> {
     Text(&'a str),
     StartTag,
@@ -27,4 +27,10 @@ pub trait HasEditorBuffers {
     fn get_mut_editor_buffer(&mut self, id: FlexBoxId) -> Option<&mut EditorBuffer>;
     fn insert_editor_buffer(&mut self, id: FlexBoxId, buffer: EditorBuffer);
     fn contains_editor_buffer(&self, id: FlexBoxId) -> bool;
+
+    /// Used to find out whether an event would be consumed by the
+    /// [crate::EditorComponent] w/out changing the state. The default implementation
+    /// returns [None], in which case the state of the [EditorBuffer] (eg: whether it is
+    /// being searched) isn't taken into account.
+    fn get_editor_buffer(&self, _id: FlexBoxId) -> Option<&EditorBuffer> { None }
 }
@@ -56,10 +56,14 @@ pub mod global_constants;
 pub mod layout;
 pub mod md_parser;
 pub mod misc;
+pub mod panel;
+pub mod protocol;
 pub mod rsx;
+pub mod status_bar;
 pub mod syntax_highlighting;
 pub mod terminal_lib_backends;
 pub mod terminal_window;
+pub mod text_area;
 
 // Re-export.
 pub use animator::*;
@@ -69,10 +73,14 @@ pub use global_constants::*;
 pub use layout::*;
 pub use md_parser::*;
 pub use misc::*;
+pub use panel::*;
+pub use protocol::*;
 pub use rsx::*;
+pub use status_bar::*;
 pub use syntax_highlighting::*;
 pub use terminal_lib_backends::*;
 pub use terminal_window::*;
+pub use text_area::*;
 
 // Tests.
 mod test_make_style_macro;
@@ -0,0 +1,428 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Markers (eg: `‹` and `›`) that are painted at the left and right edges of a line that
+//! is wider than the viewport it is painted in, to show that there is more content off
+//! screen in that direction. A marker is only shown when there is content on its side,
+//! so neither is shown when the whole line fits.
+//!
+//! [HorizontalOverflowMarkers] can be used by any component that scrolls horizontally,
+//! given the scroll offset and the width of its content:
+//! - [HorizontalOverflowMarkers::apply] returns what to paint for a line of
+//!   [TuiStyledTexts] (eg: a row of a table, or a [crate::Panel]).
+//! - [HorizontalOverflowMarkers::get_edge_overlays] returns what to paint on top of a
+//!   line that has already been painted (eg: in the [crate::EditorEngine]).
+//!
+//! All widths are display widths. When a marker covers part of a wide grapheme (eg: an
+//! emoji), the rest of that grapheme is replaced w/ spaces, since half of it can't be
+//! painted.
+
+use r3bl_core::{ch, ChUnit, TuiStyle, TuiStyledText, TuiStyledTexts, UnicodeString};
+
+use crate::clip_styled_texts_to_width;
+
+/// Where the [HorizontalOverflowMarkers] are painted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowMarkerPlacement {
+    /// A marker takes up its own cell(s) at the edge, so less of the content is visible
+    /// while it is shown. The maximum scroll offset accounts for this, so the end of the
+    /// content can still be scrolled into view.
+    #[default]
+    ReserveCell,
+    /// A marker is painted over the content at the edge, so the content doesn't move
+    /// when it is shown or hidden.
+    OverlayEdge,
+}
+
+/// Whether there is content off screen on either side of the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HorizontalOverflow {
+    pub has_more_on_left: bool,
+    pub has_more_on_right: bool,
+}
+
+/// A marker that has to be painted on top of a line, at `col_index` (relative to the
+/// start of the viewport).
+#[derive(Debug, Clone)]
+pub struct OverflowMarkerOverlay {
+    pub col_index: ChUnit,
+    pub styled_text: TuiStyledText,
+}
+
+/// See the [module docs](self) for details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HorizontalOverflowMarkers {
+    pub left_marker: String,
+    pub right_marker: String,
+    pub maybe_style: Option<TuiStyle>,
+    pub placement: OverflowMarkerPlacement,
+}
+
+impl Default for HorizontalOverflowMarkers {
+    fn default() -> Self {
+        Self {
+            left_marker: "‹".to_string(),
+            right_marker: "›".to_string(),
+            maybe_style: None,
+            placement: OverflowMarkerPlacement::default(),
+        }
+    }
+}
+
+impl HorizontalOverflowMarkers {
+    pub fn with_markers(
+        mut self,
+        left_marker: impl Into<String>,
+        right_marker: impl Into<String>,
+    ) -> Self {
+        self.left_marker = left_marker.into();
+        self.right_marker = right_marker.into();
+        self
+    }
+
+    pub fn with_style(mut self, maybe_style: Option<TuiStyle>) -> Self {
+        self.maybe_style = maybe_style;
+        self
+    }
+
+    pub fn with_placement(mut self, placement: OverflowMarkerPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    fn get_left_marker_width(&self) -> ChUnit {
+        UnicodeString::from(&self.left_marker).display_width
+    }
+
+    fn get_right_marker_width(&self) -> ChUnit {
+        UnicodeString::from(&self.right_marker).display_width
+    }
+
+    /// Returns the number of columns of content that are visible when the left marker
+    /// is (or isn't) shown, before the right marker is accounted for.
+    fn get_visible_width(
+        &self,
+        has_more_on_left: bool,
+        viewport_width: ChUnit,
+    ) -> ChUnit {
+        match (self.placement, has_more_on_left) {
+            (OverflowMarkerPlacement::ReserveCell, true) => {
+                viewport_width - self.get_left_marker_width()
+            }
+            _ => viewport_width,
+        }
+    }
+
+    /// Returns which markers have to be shown for a line that is `content_width` columns
+    /// wide, and is scrolled by `scroll_offset` columns.
+    pub fn get_overflow(
+        &self,
+        content_width: ChUnit,
+        scroll_offset: ChUnit,
+        viewport_width: ChUnit,
+    ) -> HorizontalOverflow {
+        let has_more_on_left = scroll_offset > ch!(0) && content_width > ch!(0);
+        let visible_width = self.get_visible_width(has_more_on_left, viewport_width);
+        HorizontalOverflow {
+            has_more_on_left,
+            has_more_on_right: content_width > scroll_offset + visible_width,
+        }
+    }
+
+    /// Returns the largest scroll offset that still shows content, which is when the end
+    /// of the widest line is at the right edge of the viewport.
+    pub fn get_max_scroll_offset(
+        &self,
+        content_width: ChUnit,
+        viewport_width: ChUnit,
+    ) -> ChUnit {
+        if content_width <= viewport_width {
+            return ch!(0);
+        }
+        let visible_width = self.get_visible_width(true, viewport_width);
+        content_width - std::cmp::max(visible_width, ch!(1))
+    }
+
+    fn get_marker(&self, marker: &str) -> TuiStyledText {
+        TuiStyledText::new(self.maybe_style.unwrap_or_default(), marker.to_string())
+    }
+
+    /// Returns the part of `line` that is visible in the viewport when it is scrolled by
+    /// `scroll_offset` columns, w/ the markers added. The result is never wider than
+    /// `viewport_width`, and it is painted starting at the left edge of the viewport.
+    pub fn apply(
+        &self,
+        line: &TuiStyledTexts,
+        scroll_offset: ChUnit,
+        viewport_width: ChUnit,
+    ) -> TuiStyledTexts {
+        let HorizontalOverflow {
+            has_more_on_left,
+            has_more_on_right,
+        } = self.get_overflow(line.display_width(), scroll_offset, viewport_width);
+        let left_marker_width = match has_more_on_left {
+            true => self.get_left_marker_width(),
+            false => ch!(0),
+        };
+        let right_marker_width = match has_more_on_right {
+            true => self.get_right_marker_width(),
+            false => ch!(0),
+        };
+
+        // The content that is visible between the markers.
+        let content = skip_styled_texts_cols(
+            line,
+            scroll_offset,
+            StraddlingGraphemePolicy::PadWithSpaces,
+        );
+        let content = match self.placement {
+            OverflowMarkerPlacement::ReserveCell => content,
+            OverflowMarkerPlacement::OverlayEdge => skip_styled_texts_cols(
+                &content,
+                left_marker_width,
+                StraddlingGraphemePolicy::PadWithSpaces,
+            ),
+        };
+        let content_width = viewport_width - left_marker_width - right_marker_width;
+        let content = clip_styled_texts_to_width(content, content_width);
+
+        let mut it = TuiStyledTexts::default();
+        if has_more_on_left {
+            it += self.get_marker(&self.left_marker);
+        }
+        it += content;
+        if has_more_on_right {
+            // Fill the gap left by a wide grapheme that didn't fit.
+            let padding = viewport_width - right_marker_width - it.display_width();
+            if padding > ch!(0) {
+                it += get_spaces(padding);
+            }
+            it += self.get_marker(&self.right_marker);
+        }
+        clip_styled_texts_to_width(it, viewport_width)
+    }
+
+    /// Returns the markers to paint on top of a `line` that has already been painted
+    /// (scrolled by `scroll_offset` columns). This always overlays the edges, regardless
+    /// of the [placement](HorizontalOverflowMarkers::placement), since the line has
+    /// already been laid out. The markers are padded w/ spaces to cover the whole of any
+    /// wide grapheme that they overlap.
+    pub fn get_edge_overlays(
+        &self,
+        line: &UnicodeString,
+        scroll_offset: ChUnit,
+        viewport_width: ChUnit,
+    ) -> Vec<OverflowMarkerOverlay> {
+        let overlay_edge = self
+            .clone()
+            .with_placement(OverflowMarkerPlacement::OverlayEdge);
+        let HorizontalOverflow {
+            has_more_on_left,
+            has_more_on_right,
+        } = overlay_edge.get_overflow(line.display_width, scroll_offset, viewport_width);
+        let left_marker_width = self.get_left_marker_width();
+        let right_marker_width = self.get_right_marker_width();
+
+        let mut it = vec![];
+
+        if has_more_on_left && left_marker_width <= viewport_width {
+            // Cover the rest of a wide grapheme that starts under the marker.
+            let end_col_index = scroll_offset + left_marker_width;
+            let padding = match line
+                .is_display_col_index_in_middle_of_grapheme_cluster(end_col_index)
+            {
+                Some(segment) => {
+                    segment.display_col_offset + segment.unicode_width - end_col_index
+                }
+                None => ch!(0),
+            };
+            it.push(OverflowMarkerOverlay {
+                col_index: ch!(0),
+                styled_text: self.get_marker(&format!(
+                    "{}{}",
+                    self.left_marker,
+                    " ".repeat(ch!(@to_usize padding))
+                )),
+            });
+        }
+
+        if has_more_on_right && left_marker_width + right_marker_width <= viewport_width {
+            // Cover the start of a wide grapheme that ends under the marker.
+            let start_col_index = scroll_offset + viewport_width - right_marker_width;
+            let padding = match line
+                .is_display_col_index_in_middle_of_grapheme_cluster(start_col_index)
+            {
+                Some(segment) => start_col_index - segment.display_col_offset,
+                None => ch!(0),
+            };
+            it.push(OverflowMarkerOverlay {
+                col_index: viewport_width - right_marker_width - padding,
+                styled_text: self.get_marker(&format!(
+                    "{}{}",
+                    " ".repeat(ch!(@to_usize padding)),
+                    self.right_marker
+                )),
+            });
+        }
+
+        it
+    }
+}
+
+fn get_spaces(col_count: ChUnit) -> TuiStyledText {
+    TuiStyledText::new(TuiStyle::default(), " ".repeat(ch!(@to_usize col_count)))
+}
+
+/// What [skip_styled_texts_cols] does w/ a wide grapheme (eg: an emoji) that straddles
+/// the cut, since half of it can't be painted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StraddlingGraphemePolicy {
+    /// Remove it entirely, so the rest of the content moves left.
+    Remove,
+    /// Replace the part of it that remains w/ spaces, so the rest of the content stays in
+    /// the same columns.
+    PadWithSpaces,
+}
+
+/// Remove `col_count` columns from the start of the given styled texts. A wide grapheme
+/// that straddles the cut is handled according to the `policy`.
+pub fn skip_styled_texts_cols(
+    styled_texts: &TuiStyledTexts,
+    col_count: ChUnit,
+    policy: StraddlingGraphemePolicy,
+) -> TuiStyledTexts {
+    let mut it = TuiStyledTexts::default();
+    let mut skip_col_count = col_count;
+    for styled_text in styled_texts.inner.iter() {
+        let text = styled_text.get_text();
+        if skip_col_count >= text.display_width {
+            skip_col_count -= text.display_width;
+            continue;
+        }
+        let remaining = text.truncate_start_by_n_col(skip_col_count);
+        let padding = text.display_width
+            - skip_col_count
+            - UnicodeString::from(remaining).display_width;
+        skip_col_count = ch!(0);
+        if policy == StraddlingGraphemePolicy::PadWithSpaces && padding > ch!(0) {
+            it += TuiStyledText::new(
+                *styled_text.get_style(),
+                " ".repeat(ch!(@to_usize padding)),
+            );
+        }
+        if !remaining.is_empty() {
+            it += TuiStyledText::new(*styled_text.get_style(), remaining.into());
+        }
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, tui_styled_text, tui_styled_texts, ConvertToPlainText};
+
+    use super::*;
+
+    fn apply(
+        markers: &HorizontalOverflowMarkers,
+        line: &'static str,
+        scroll_offset: u16,
+        viewport_width: u16,
+    ) -> String {
+        let line = tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: line } };
+        markers
+            .apply(&line, ch!(scroll_offset), ch!(viewport_width))
+            .to_plain_text_us()
+            .string
+    }
+
+    #[test]
+    fn test_reserve_cell_markers_hide_at_edges() {
+        let markers = HorizontalOverflowMarkers::default();
+        assert_eq2!(apply(&markers, "abcdefghij", 0, 5), "abcd›");
+        assert_eq2!(apply(&markers, "abcdefghij", 3, 5), "‹def›");
+
+        // At the max scroll offset, the last column is visible.
+        let max_scroll_offset = markers.get_max_scroll_offset(ch!(10), ch!(5));
+        assert_eq2!(max_scroll_offset, ch!(6));
+        assert_eq2!(apply(&markers, "abcdefghij", 6, 5), "‹ghij");
+
+        // No markers when the whole line fits.
+        assert_eq2!(apply(&markers, "ab", 0, 5), "ab");
+        assert_eq2!(
+            markers.get_overflow(ch!(5), ch!(0), ch!(5)),
+            HorizontalOverflow::default()
+        );
+    }
+
+    #[test]
+    fn test_overlay_edge_markers_keep_content_in_place() {
+        let markers = HorizontalOverflowMarkers::default()
+            .with_placement(OverflowMarkerPlacement::OverlayEdge);
+        assert_eq2!(apply(&markers, "abcdefghij", 0, 5), "abcd›");
+        assert_eq2!(apply(&markers, "abcdefghij", 3, 5), "‹efg›");
+        assert_eq2!(markers.get_max_scroll_offset(ch!(10), ch!(5)), ch!(5));
+        assert_eq2!(apply(&markers, "abcdefghij", 5, 5), "‹ghij");
+    }
+
+    #[test]
+    fn test_markers_w_wide_graphemes() {
+        // The emoji are at columns 1-2 and 4-5.
+        let line = "a😀b😀c";
+
+        let markers = HorizontalOverflowMarkers::default();
+        assert_eq2!(apply(&markers, line, 0, 4), "a😀›");
+        assert_eq2!(apply(&markers, line, 2, 4), "‹ b›");
+
+        let markers = markers.with_placement(OverflowMarkerPlacement::OverlayEdge);
+        assert_eq2!(apply(&markers, line, 1, 4), "‹ b›");
+
+        // Overlays are padded to cover the whole emoji that they overlap.
+        let line = UnicodeString::from(line);
+        let overlays = markers.get_edge_overlays(&line, ch!(1), ch!(4));
+        let overlays = overlays
+            .iter()
+            .map(|it| (it.col_index, it.styled_text.get_text().string.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq2!(overlays, vec![(ch!(0), "‹ "), (ch!(3), "›")]);
+
+        let overlays = markers.get_edge_overlays(&line, ch!(0), ch!(3));
+        let overlays = overlays
+            .iter()
+            .map(|it| (it.col_index, it.styled_text.get_text().string.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq2!(overlays, vec![(ch!(1), " ›")]);
+    }
+
+    #[test]
+    fn test_skip_styled_texts_cols_w_straddling_grapheme() {
+        let line = tui_styled_texts! {
+            tui_styled_text! { @style: TuiStyle::default(), @text: "a😀" },
+            tui_styled_text! { @style: TuiStyle::default(), @text: "b" },
+        };
+        let skip = |col_count: u16, policy| {
+            skip_styled_texts_cols(&line, ch!(col_count), policy)
+                .to_plain_text_us()
+                .string
+        };
+
+        assert_eq2!(skip(2, StraddlingGraphemePolicy::Remove), "b");
+        assert_eq2!(skip(2, StraddlingGraphemePolicy::PadWithSpaces), " b");
+        assert_eq2!(skip(3, StraddlingGraphemePolicy::PadWithSpaces), "b");
+        assert_eq2!(skip(5, StraddlingGraphemePolicy::PadWithSpaces), "");
+    }
+}
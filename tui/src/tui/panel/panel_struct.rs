@@ -0,0 +1,566 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch,
+                position,
+                size,
+                ChUnit,
+                Position,
+                Size,
+                TuiStyle,
+                TuiStyledTexts};
+
+use crate::{clip_styled_texts_to_width,
+            render_ops,
+            render_tui_styled_texts_into,
+            skip_styled_texts_cols,
+            BorderGlyphCharacter,
+            HorizontalOverflowMarkers,
+            RenderOp,
+            RenderOps,
+            RenderPipeline,
+            StraddlingGraphemePolicy,
+            ZOrder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanelHorizontalAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanelVerticalAlignment {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// What to do w/ content that is larger than the inner area of the [Panel].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanelOverflow {
+    /// Only show the part of the content that fits. Rows are dropped from the end(s)
+    /// opposite to the vertical alignment, eg: a bottom aligned panel shows the last
+    /// rows. Columns are always dropped from the end of each line.
+    #[default]
+    Clip,
+    /// Show the content starting at this row and column (of the content). The offsets
+    /// are clamped, so the content can't be scrolled past its end.
+    Scroll {
+        row_offset: ChUnit,
+        col_offset: ChUnit,
+    },
+}
+
+/// The number of blank rows or columns on each side, between the border and the content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PanelPadding {
+    pub top: ChUnit,
+    pub right: ChUnit,
+    pub bottom: ChUnit,
+    pub left: ChUnit,
+}
+
+impl PanelPadding {
+    pub fn uniform(it: impl Into<ChUnit>) -> Self {
+        let it = it.into();
+        Self {
+            top: it,
+            right: it,
+            bottom: it,
+            left: it,
+        }
+    }
+
+    pub fn symmetric(vertical: impl Into<ChUnit>, horizontal: impl Into<ChUnit>) -> Self {
+        let (vertical, horizontal) = (vertical.into(), horizontal.into());
+        Self {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+}
+
+/// A box w/ an (optional) border, that positions lines of content inside it. Here's an
+/// example.
+///
+/// ```rust
+/// use r3bl_core::{ch, position, size, tui_styled_text, tui_styled_texts, TuiStyle};
+/// use r3bl_tui::{Panel, PanelHorizontalAlignment, PanelPadding, PanelVerticalAlignment};
+///
+/// let panel = Panel::default()
+///     .with_content(vec![
+///         tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: "hi" } },
+///     ])
+///     .with_padding(PanelPadding::symmetric(0, 1))
+///     .with_alignment(PanelHorizontalAlignment::Center, PanelVerticalAlignment::Middle);
+///
+/// // The border takes up 1 column / row on each side, and the padding 1 more column.
+/// let layout = panel.layout(size!(col_count: 10, row_count: 5));
+/// assert_eq!(layout[0].pos, position!(col_index: 4, row_index: 2));
+/// ```
+///
+/// The layout rules are:
+/// 1. The inner area is what remains after removing the border and the padding. If it
+///    is empty, then the content isn't rendered at all.
+/// 2. The content (as a whole) is aligned in the inner area. When it can't be centered
+///    exactly, the extra row or column goes after the content (ie: it is nudged up, or
+///    to the left).
+/// 3. Content that doesn't fit is clipped or scrolled (see [PanelOverflow]). Each line
+///    is aligned on its own, so lines of different widths line up on the aligned side.
+///
+/// All widths are display widths, so wide graphemes (eg: emoji) are accounted for, and
+/// are never split when clipping.
+#[derive(Debug, Clone)]
+pub struct Panel {
+    /// The lines of content.
+    pub content: Vec<TuiStyledTexts>,
+    pub has_border: bool,
+    pub maybe_border_style: Option<TuiStyle>,
+    pub padding: PanelPadding,
+    pub horizontal_alignment: PanelHorizontalAlignment,
+    pub vertical_alignment: PanelVerticalAlignment,
+    pub overflow: PanelOverflow,
+    /// When this is set, lines that are wider than the inner area get markers at the
+    /// edge(s) where content is hidden. See [HorizontalOverflowMarkers].
+    pub maybe_overflow_markers: Option<HorizontalOverflowMarkers>,
+}
+
+impl Default for Panel {
+    fn default() -> Self {
+        Self {
+            content: vec![],
+            has_border: true,
+            maybe_border_style: None,
+            padding: PanelPadding::default(),
+            horizontal_alignment: PanelHorizontalAlignment::default(),
+            vertical_alignment: PanelVerticalAlignment::default(),
+            overflow: PanelOverflow::default(),
+            maybe_overflow_markers: None,
+        }
+    }
+}
+
+/// A laid out line of the panel's content, which is ready to be painted.
+#[derive(Debug, Clone, Default)]
+pub struct PanelLayoutItem {
+    /// This is relative to the top left corner of the panel.
+    pub pos: Position,
+    pub styled_texts: TuiStyledTexts,
+}
+
+impl Panel {
+    pub fn with_content(mut self, content: Vec<TuiStyledTexts>) -> Self {
+        self.content = content;
+        self
+    }
+
+    pub fn with_border(
+        mut self,
+        has_border: bool,
+        maybe_style: Option<TuiStyle>,
+    ) -> Self {
+        self.has_border = has_border;
+        self.maybe_border_style = maybe_style;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: PanelPadding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_alignment(
+        mut self,
+        horizontal: PanelHorizontalAlignment,
+        vertical: PanelVerticalAlignment,
+    ) -> Self {
+        self.horizontal_alignment = horizontal;
+        self.vertical_alignment = vertical;
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: PanelOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_overflow_markers(
+        mut self,
+        maybe_overflow_markers: Option<HorizontalOverflowMarkers>,
+    ) -> Self {
+        self.maybe_overflow_markers = maybe_overflow_markers;
+        self
+    }
+
+    /// Returns the position (relative to the top left corner of the panel) and size of
+    /// the area that the content is laid out in, or [None] if the border and padding
+    /// don't leave any room for it.
+    pub fn get_inner_area(&self, size: Size) -> Option<(Position, Size)> {
+        let border = ch!(if self.has_border { 1 } else { 0 });
+        let PanelPadding {
+            top,
+            right,
+            bottom,
+            left,
+        } = self.padding;
+
+        let horizontal_used = *border * 2 + *left + *right;
+        let vertical_used = *border * 2 + *top + *bottom;
+        if horizontal_used >= *size.col_count || vertical_used >= *size.row_count {
+            return None;
+        }
+
+        Some((
+            position!(col_index: border + left, row_index: border + top),
+            size!(
+                col_count: size.col_count - ch!(horizontal_used),
+                row_count: size.row_count - ch!(vertical_used)
+            ),
+        ))
+    }
+
+    /// Lay out the content to fit in a panel of the given size. See [Panel] for the
+    /// layout rules.
+    pub fn layout(&self, size: Size) -> Vec<PanelLayoutItem> {
+        let Some((inner_pos, inner_size)) = self.get_inner_area(size) else {
+            return vec![];
+        };
+
+        // Pick the rows to show.
+        let row_count = ch!(self.content.len());
+        let visible_row_count = std::cmp::min(row_count, inner_size.row_count);
+        let (first_row_index, col_offset) = match self.overflow {
+            PanelOverflow::Clip => {
+                let first_row_index = get_aligned_offset(
+                    row_count,
+                    visible_row_count,
+                    self.vertical_alignment.into(),
+                );
+                (first_row_index, ch!(0))
+            }
+            PanelOverflow::Scroll {
+                row_offset,
+                col_offset,
+            } => {
+                let max_row_offset = row_count - visible_row_count;
+                let max_width = self
+                    .content
+                    .iter()
+                    .map(|line| line.display_width())
+                    .max()
+                    .unwrap_or_default();
+                let max_col_offset = match self.maybe_overflow_markers {
+                    Some(ref markers) => {
+                        markers.get_max_scroll_offset(max_width, inner_size.col_count)
+                    }
+                    None => max_width - std::cmp::min(max_width, inner_size.col_count),
+                };
+                (
+                    std::cmp::min(row_offset, max_row_offset),
+                    std::cmp::min(col_offset, max_col_offset),
+                )
+            }
+        };
+
+        let row_index_in_inner_area = get_aligned_offset(
+            inner_size.row_count,
+            visible_row_count,
+            self.vertical_alignment.into(),
+        );
+
+        self.content
+            .iter()
+            .skip(ch!(@to_usize first_row_index))
+            .take(ch!(@to_usize visible_row_count))
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let line = match self.maybe_overflow_markers {
+                    Some(ref markers) => {
+                        markers.apply(line, col_offset, inner_size.col_count)
+                    }
+                    None => {
+                        let line = skip_styled_texts_cols(
+                            line,
+                            col_offset,
+                            StraddlingGraphemePolicy::Remove,
+                        );
+                        clip_styled_texts_to_width(line, inner_size.col_count)
+                    }
+                };
+                if line.is_empty() {
+                    return None;
+                }
+                let col_index_in_inner_area = get_aligned_offset(
+                    inner_size.col_count,
+                    line.display_width(),
+                    self.horizontal_alignment.into(),
+                );
+                Some(PanelLayoutItem {
+                    pos: position!(
+                        col_index: inner_pos.col_index + col_index_in_inner_area,
+                        row_index: inner_pos.row_index + row_index_in_inner_area + ch!(index)
+                    ),
+                    styled_texts: line,
+                })
+            })
+            .collect()
+    }
+
+    /// Paint the border (if any), and the laid out content (see [Panel::layout]) in the
+    /// box at `origin` w/ the given `size`.
+    pub fn render_into(
+        &self,
+        pipeline: &mut RenderPipeline,
+        origin: Position,
+        size: Size,
+    ) {
+        let mut render_ops = render_ops!();
+
+        if self.has_border && size.col_count >= ch!(2) && size.row_count >= ch!(2) {
+            self.render_border(&mut render_ops, origin, size);
+        }
+
+        for PanelLayoutItem { pos, styled_texts } in self.layout(size) {
+            render_ops.push(RenderOp::ResetColor);
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(origin, pos));
+            render_tui_styled_texts_into(&styled_texts, &mut render_ops);
+        }
+
+        pipeline.push(ZOrder::Normal, render_ops);
+    }
+
+    fn render_border(&self, render_ops: &mut RenderOps, origin: Position, size: Size) {
+        let inner_width = ch!(@to_usize size.col_count - 2);
+        let horizontal = BorderGlyphCharacter::Horizontal
+            .as_ref()
+            .repeat(inner_width);
+        let vertical = BorderGlyphCharacter::Vertical.as_ref();
+        let last_row_index = size.row_count - 1;
+
+        for row_index in 0..*size.row_count {
+            let row_index = ch!(row_index);
+            render_ops.push(RenderOp::ResetColor);
+            render_ops.push(RenderOp::ApplyColors(self.maybe_border_style));
+            if row_index == ch!(0) || row_index == last_row_index {
+                let (left, right) = if row_index == ch!(0) {
+                    (
+                        BorderGlyphCharacter::TopLeft,
+                        BorderGlyphCharacter::TopRight,
+                    )
+                } else {
+                    (
+                        BorderGlyphCharacter::BottomLeft,
+                        BorderGlyphCharacter::BottomRight,
+                    )
+                };
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin,
+                    position!(col_index: 0, row_index: row_index),
+                ));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    format!("{}{horizontal}{}", left.as_ref(), right.as_ref()),
+                    self.maybe_border_style,
+                ));
+            } else {
+                for col_index in [ch!(0), size.col_count - 1] {
+                    render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                        origin,
+                        position!(col_index: col_index, row_index: row_index),
+                    ));
+                    render_ops.push(RenderOp::PaintTextWithAttributes(
+                        vertical.into(),
+                        self.maybe_border_style,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// The start, center, or end of a span, which both alignments map to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpanAlignment {
+    Start,
+    Center,
+    End,
+}
+
+impl From<PanelHorizontalAlignment> for SpanAlignment {
+    fn from(it: PanelHorizontalAlignment) -> Self {
+        match it {
+            PanelHorizontalAlignment::Left => SpanAlignment::Start,
+            PanelHorizontalAlignment::Center => SpanAlignment::Center,
+            PanelHorizontalAlignment::Right => SpanAlignment::End,
+        }
+    }
+}
+
+impl From<PanelVerticalAlignment> for SpanAlignment {
+    fn from(it: PanelVerticalAlignment) -> Self {
+        match it {
+            PanelVerticalAlignment::Top => SpanAlignment::Start,
+            PanelVerticalAlignment::Middle => SpanAlignment::Center,
+            PanelVerticalAlignment::Bottom => SpanAlignment::End,
+        }
+    }
+}
+
+/// Returns the offset of a span of length `used` that is aligned in a span of length
+/// `available`. When centering leaves an odd remainder, the extra one goes after it.
+fn get_aligned_offset(
+    available: ChUnit,
+    used: ChUnit,
+    alignment: SpanAlignment,
+) -> ChUnit {
+    let remainder = available - std::cmp::min(used, available);
+    match alignment {
+        SpanAlignment::Start => ch!(0),
+        SpanAlignment::Center => remainder / 2,
+        SpanAlignment::End => remainder,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, tui_styled_text, tui_styled_texts, ConvertToPlainText};
+
+    use super::*;
+
+    fn lines(it: &[&'static str]) -> Vec<TuiStyledTexts> {
+        it.iter()
+            .map(|line| tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: *line } })
+            .collect()
+    }
+
+    fn plain_text(item: &PanelLayoutItem) -> String {
+        item.styled_texts.to_plain_text_us().string
+    }
+
+    #[test]
+    fn test_inner_area_with_asymmetric_padding() {
+        let panel = Panel::default().with_padding(PanelPadding {
+            top: ch!(1),
+            right: ch!(3),
+            bottom: ch!(0),
+            left: ch!(2),
+        });
+        assert_eq2!(
+            panel.get_inner_area(size!(col_count: 10, row_count: 5)),
+            Some((
+                position!(col_index: 3, row_index: 2),
+                size!(col_count: 3, row_count: 2)
+            ))
+        );
+
+        // No room left for the content.
+        let layout = panel
+            .with_content(lines(&["abc"]))
+            .layout(size!(col_count: 7, row_count: 5));
+        assert!(layout.is_empty());
+    }
+
+    #[test]
+    fn test_center_with_odd_remainder() {
+        let panel = Panel::default()
+            .with_content(lines(&["ab", "abcd"]))
+            .with_alignment(
+                PanelHorizontalAlignment::Center,
+                PanelVerticalAlignment::Middle,
+            );
+
+        // Inner area is 7x3, so there is 1 row left over, and 5 and 3 columns.
+        let layout = panel.layout(size!(col_count: 9, row_count: 5));
+        assert_eq2!(layout.len(), 2);
+        assert_eq2!(layout[0].pos, position!(col_index: 3, row_index: 1));
+        assert_eq2!(layout[1].pos, position!(col_index: 2, row_index: 2));
+    }
+
+    #[test]
+    fn test_bottom_right() {
+        let panel = Panel::default()
+            .with_border(false, None)
+            .with_content(lines(&["ab"]))
+            .with_alignment(
+                PanelHorizontalAlignment::Right,
+                PanelVerticalAlignment::Bottom,
+            );
+
+        let layout = panel.layout(size!(col_count: 5, row_count: 3));
+        assert_eq2!(layout[0].pos, position!(col_index: 3, row_index: 2));
+    }
+
+    #[test]
+    fn test_clip_keeps_aligned_rows() {
+        let panel = Panel::default()
+            .with_border(false, None)
+            .with_content(lines(&["1", "2", "3", "4 is too long"]))
+            .with_alignment(
+                PanelHorizontalAlignment::Left,
+                PanelVerticalAlignment::Bottom,
+            );
+
+        let layout = panel.layout(size!(col_count: 4, row_count: 2));
+        assert_eq2!(layout.len(), 2);
+        assert_eq2!(plain_text(&layout[0]), "3");
+        assert_eq2!(plain_text(&layout[1]), "4 is");
+        assert_eq2!(layout[0].pos, position!(col_index: 0, row_index: 0));
+    }
+
+    #[test]
+    fn test_scroll_is_clamped() {
+        let panel = Panel::default()
+            .with_border(false, None)
+            .with_content(lines(&["abcdef", "ghijkl", "mnopqr"]))
+            .with_overflow(PanelOverflow::Scroll {
+                row_offset: ch!(10),
+                col_offset: ch!(2),
+            });
+
+        let layout = panel.layout(size!(col_count: 3, row_count: 2));
+        assert_eq2!(layout.len(), 2);
+        assert_eq2!(plain_text(&layout[0]), "ijk");
+        assert_eq2!(plain_text(&layout[1]), "opq");
+    }
+
+    #[test]
+    fn test_scroll_w_overflow_markers() {
+        let panel = Panel::default()
+            .with_border(false, None)
+            .with_content(lines(&["abcdef", "gh"]))
+            .with_overflow_markers(Some(HorizontalOverflowMarkers::default()));
+
+        let layout = panel.layout(size!(col_count: 4, row_count: 2));
+        assert_eq2!(plain_text(&layout[0]), "abc›");
+        assert_eq2!(plain_text(&layout[1]), "gh");
+
+        // The scroll offset is clamped so the end of the widest line is visible, even
+        // though the left marker takes up a column.
+        let layout = panel
+            .with_overflow(PanelOverflow::Scroll {
+                row_offset: ch!(0),
+                col_offset: ch!(10),
+            })
+            .layout(size!(col_count: 4, row_count: 2));
+        assert_eq2!(plain_text(&layout[0]), "‹def");
+        assert_eq2!(plain_text(&layout[1]), "‹");
+    }
+}
@@ -0,0 +1,235 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Inactivity based keepalive for a connection between a process running the engine and
+//! a remote renderer or consumer (eg: over TCP or IPC). It lets either side detect a dead
+//! connection, so that the server can prune it.
+//!
+//! [ConnectionHeartbeat] doesn't do any IO itself. The transport owns the socket, and
+//! drives the heartbeat from its write loop:
+//! 1. Call [ConnectionHeartbeat::on_received] whenever anything arrives from the
+//!    consumer (a pong, an input event, an ack, etc).
+//! 2. Call [ConnectionHeartbeat::on_sent] whenever a whole frame (or ping) has been
+//!    written.
+//! 3. Between frames, call [ConnectionHeartbeat::poll] (eg: when the
+//!    [ConnectionHeartbeat::get_next_deadline] elapses) and act on the
+//!    [HeartbeatAction] that it returns.
+//!
+//! Since pings are only ever sent between frames by the same loop that writes the frames,
+//! they can't be interleaved w/ (or reorder) the frames themselves.
+//!
+//! A ping is only sent when nothing else has been sent for a while, and the connection
+//! is only considered dead when *nothing* has been received for the whole timeout. So a
+//! consumer that is slow but still sending anything back is not pruned.
+//!
+//! When a connection is established (or re-established) the consumer has no idea what is
+//! on screen, so [ConnectionHeartbeat::take_needs_full_resync] returns `true` once after
+//! [ConnectionHeartbeat::new] and [ConnectionHeartbeat::on_reconnect], so that the next
+//! frame that is sent is a full one, rather than a diff.
+
+use std::time::{Duration, Instant};
+
+/// Default time after which a ping is sent, if nothing else has been sent.
+pub const DEFAULT_HEARTBEAT_PING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default time after which a connection is considered dead, if nothing has been
+/// received.
+pub const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Whether [ConnectionHeartbeat] sends pings and prunes dead connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatMode {
+    /// Never send pings, and never disconnect due to inactivity.
+    Disable,
+    Enable(HeartbeatConfig),
+}
+
+impl Default for HeartbeatMode {
+    fn default() -> Self { HeartbeatMode::Enable(HeartbeatConfig::default()) }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeartbeatConfig {
+    pub ping_interval: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: DEFAULT_HEARTBEAT_PING_INTERVAL,
+            timeout: DEFAULT_HEARTBEAT_TIMEOUT,
+        }
+    }
+}
+
+impl HeartbeatConfig {
+    pub fn with_ping_interval(mut self, ping_interval: Duration) -> Self {
+        self.ping_interval = ping_interval;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The timeout is never shorter than the ping interval, otherwise a consumer that
+    /// only replies to pings would always be pruned before it is pinged.
+    pub fn get_timeout(&self) -> Duration { self.timeout.max(self.ping_interval) }
+}
+
+/// What the transport should do after calling [ConnectionHeartbeat::poll].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatAction {
+    /// Nothing to do until [ConnectionHeartbeat::get_next_deadline].
+    Wait,
+    /// Send a ping (between frames), and then call [ConnectionHeartbeat::on_sent].
+    SendPing,
+    /// Nothing has been received for the whole timeout, so close the connection.
+    Disconnect,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionHeartbeat {
+    pub mode: HeartbeatMode,
+    pub last_received_at: Instant,
+    pub last_sent_at: Instant,
+    pub needs_full_resync: bool,
+}
+
+impl ConnectionHeartbeat {
+    pub fn new(mode: HeartbeatMode, now: Instant) -> Self {
+        Self {
+            mode,
+            last_received_at: now,
+            last_sent_at: now,
+            needs_full_resync: true,
+        }
+    }
+
+    /// Anything that is received counts as proof that the consumer is alive, not just
+    /// pongs.
+    pub fn on_received(&mut self, now: Instant) { self.last_received_at = now; }
+
+    /// Anything that is sent (a frame or a ping) resets the ping interval. This does not
+    /// count as proof that the consumer is alive.
+    pub fn on_sent(&mut self, now: Instant) { self.last_sent_at = now; }
+
+    /// The consumer has reconnected. The timers start over, and the next frame must be a
+    /// full one.
+    pub fn on_reconnect(&mut self, now: Instant) {
+        self.last_received_at = now;
+        self.last_sent_at = now;
+        self.needs_full_resync = true;
+    }
+
+    /// Returns `true` only once per (re)connection.
+    pub fn take_needs_full_resync(&mut self) -> bool {
+        std::mem::take(&mut self.needs_full_resync)
+    }
+
+    pub fn poll(&self, now: Instant) -> HeartbeatAction {
+        let HeartbeatMode::Enable(config) = self.mode else {
+            return HeartbeatAction::Wait;
+        };
+
+        if now.saturating_duration_since(self.last_received_at) >= config.get_timeout() {
+            HeartbeatAction::Disconnect
+        } else if now.saturating_duration_since(self.last_sent_at) >= config.ping_interval
+        {
+            HeartbeatAction::SendPing
+        } else {
+            HeartbeatAction::Wait
+        }
+    }
+
+    /// When [Self::poll] should be called next, or [None] if the heartbeat is disabled.
+    pub fn get_next_deadline(&self) -> Option<Instant> {
+        let HeartbeatMode::Enable(config) = self.mode else {
+            return None;
+        };
+        let ping_at = self.last_sent_at + config.ping_interval;
+        let disconnect_at = self.last_received_at + config.get_timeout();
+        Some(ping_at.min(disconnect_at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEC: Duration = Duration::from_secs(1);
+
+    fn make_heartbeat(start: Instant) -> ConnectionHeartbeat {
+        let config = HeartbeatConfig::default()
+            .with_ping_interval(2 * SEC)
+            .with_timeout(5 * SEC);
+        ConnectionHeartbeat::new(HeartbeatMode::Enable(config), start)
+    }
+
+    #[test]
+    fn test_ping_then_disconnect_when_nothing_is_received() {
+        let start = Instant::now();
+        let mut heartbeat = make_heartbeat(start);
+
+        assert_eq!(heartbeat.poll(start + SEC), HeartbeatAction::Wait);
+        assert_eq!(heartbeat.get_next_deadline(), Some(start + 2 * SEC));
+        assert_eq!(heartbeat.poll(start + 2 * SEC), HeartbeatAction::SendPing);
+        heartbeat.on_sent(start + 2 * SEC);
+        assert_eq!(heartbeat.poll(start + 3 * SEC), HeartbeatAction::Wait);
+        assert_eq!(heartbeat.poll(start + 5 * SEC), HeartbeatAction::Disconnect);
+    }
+
+    #[test]
+    fn test_slow_consumer_that_sends_anything_is_not_pruned() {
+        let start = Instant::now();
+        let mut heartbeat = make_heartbeat(start);
+
+        // Frames keep being sent, so no pings are needed.
+        for sec in 1..=10 {
+            heartbeat.on_sent(start + sec * SEC);
+            // The consumer only sends something back every 4 seconds.
+            if sec % 4 == 0 {
+                heartbeat.on_received(start + sec * SEC);
+            }
+            assert_eq!(heartbeat.poll(start + sec * SEC), HeartbeatAction::Wait);
+        }
+    }
+
+    #[test]
+    fn test_full_resync_on_connect_and_reconnect() {
+        let start = Instant::now();
+        let mut heartbeat = make_heartbeat(start);
+
+        assert!(heartbeat.take_needs_full_resync());
+        assert!(!heartbeat.take_needs_full_resync());
+
+        assert_eq!(heartbeat.poll(start + 6 * SEC), HeartbeatAction::Disconnect);
+        heartbeat.on_reconnect(start + 6 * SEC);
+        assert_eq!(heartbeat.poll(start + 7 * SEC), HeartbeatAction::Wait);
+        assert!(heartbeat.take_needs_full_resync());
+    }
+
+    #[test]
+    fn test_disabled() {
+        let start = Instant::now();
+        let heartbeat = ConnectionHeartbeat::new(HeartbeatMode::Disable, start);
+        assert_eq!(heartbeat.poll(start + 1000 * SEC), HeartbeatAction::Wait);
+        assert_eq!(heartbeat.get_next_deadline(), None);
+    }
+}
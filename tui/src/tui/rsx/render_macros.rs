@@ -34,12 +34,28 @@ macro_rules! render_component_in_current_box {
         if let Some(component_ref) = maybe_component_ref {
             let surface_bounds = $crate::SurfaceBounds::from(&*($arg_surface));
             let current_box = $arg_surface.current_box()?;
+            let maybe_profiling_start = $arg_global_data.get_render_profiling_start();
             let queue = component_ref.render(
                 $arg_global_data,
                 *current_box,
                 surface_bounds,
                 $arg_has_focus,
             )?;
+            if let (Some(start), Some(render_profiler)) = (
+                maybe_profiling_start,
+                $arg_global_data.maybe_render_profiler.as_mut(),
+            ) {
+                render_profiler.record_component_cost($crate::ComponentRenderCost::new(
+                    $arg_component_id,
+                    start.elapsed(),
+                    &queue,
+                ));
+            }
+            $arg_global_data.hit_test_areas.record(
+                $arg_component_id,
+                current_box,
+                &queue,
+            );
             $arg_surface.render_pipeline += queue;
         }
     };
@@ -66,12 +82,26 @@ macro_rules! render_component_in_given_box {
 
         if let Some(component_ref) = maybe_component_ref {
             let surface_bounds = $crate::SurfaceBounds::from(&*($arg_surface));
+            let maybe_profiling_start = $arg_global_data.get_render_profiling_start();
             let queue: $crate::RenderPipeline = component_ref.render(
                 $arg_global_data,
                 $arg_box,
                 surface_bounds,
                 $arg_has_focus,
             )?;
+            if let (Some(start), Some(render_profiler)) = (
+                maybe_profiling_start,
+                $arg_global_data.maybe_render_profiler.as_mut(),
+            ) {
+                render_profiler.record_component_cost($crate::ComponentRenderCost::new(
+                    $arg_component_id,
+                    start.elapsed(),
+                    &queue,
+                ));
+            }
+            $arg_global_data
+                .hit_test_areas
+                .record($arg_component_id, &$arg_box, &queue);
             $arg_surface.render_pipeline += queue;
         }
     }};
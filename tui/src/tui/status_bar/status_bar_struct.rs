@@ -0,0 +1,522 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{ch,
+                position,
+                ChUnit,
+                Position,
+                TruncationIndicator,
+                TuiStyledText,
+                TuiStyledTexts};
+
+use crate::{render_ops, render_tui_styled_texts_into, RenderOp, RenderPipeline, ZOrder};
+
+/// The group that a [StatusBarSegment] is laid out in. Segments in the same group are
+/// painted next to each other (in the order in which they were added), with the
+/// [StatusBar::separator] between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusBarAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// A single piece of the status bar. The content is generated by a closure each time
+/// that the status bar is laid out, so dynamic content (eg: a clock, or the caret
+/// position) is always up to date. Only the cells that actually change are repainted,
+/// since the offscreen buffer is diffed before painting.
+pub struct StatusBarSegment {
+    pub alignment: StatusBarAlignment,
+    /// When there isn't enough room to show every segment, the ones with the lowest
+    /// priority are dropped first.
+    pub priority: u8,
+    pub content: Box<dyn Fn() -> TuiStyledTexts>,
+}
+
+impl Debug for StatusBarSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatusBarSegment")
+            .field("alignment", &self.alignment)
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+/// A status bar made up of segments, which are added to the left, center, or right
+/// groups. Here's an example.
+///
+/// ```rust
+/// use r3bl_core::{ch, tui_styled_text, tui_styled_texts, TuiStyle};
+/// use r3bl_tui::{StatusBar, StatusBarAlignment};
+///
+/// let status_bar = StatusBar::default()
+///     .add_segment(StatusBarAlignment::Left, 2, || {
+///         tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: "edi" } }
+///     })
+///     .add_segment(StatusBarAlignment::Right, 1, || {
+///         tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: "1:1" } }
+///     });
+///
+/// let layout = status_bar.layout(ch!(20));
+/// assert_eq!(layout.len(), 2);
+/// ```
+///
+/// The layout rules are:
+/// 1. The left group starts at the first column, the right group ends at the last column,
+///    and the center group is centered in the available width (it is nudged so that it
+///    doesn't overlap the left or right groups).
+/// 2. If all the groups don't fit, then segments are dropped in order of lowest
+///    [StatusBarSegment::priority] first. When priorities are the same, center segments
+///    are dropped before the others, and later segments before earlier ones.
+/// 3. If the one remaining segment still doesn't fit, then it is clipped. If a
+///    [StatusBar::maybe_truncation_indicator] is set, then it is painted at the end of
+///    the clipped segment.
+///
+/// All widths are display widths, so wide graphemes (eg: emoji) are accounted for, and
+/// are never split when clipping.
+#[derive(Debug, Default)]
+pub struct StatusBar {
+    pub segments: Vec<StatusBarSegment>,
+    /// This is painted between adjacent segments in the same group.
+    pub separator: TuiStyledTexts,
+    /// If this is `None`, then clipped segments are simply cut off.
+    pub maybe_truncation_indicator: Option<TruncationIndicator>,
+}
+
+/// A laid out piece of the status bar, which is ready to be painted.
+#[derive(Debug, Clone, Default)]
+pub struct StatusBarLayoutItem {
+    /// This is relative to the start of the status bar.
+    pub col_index: ChUnit,
+    pub styled_texts: TuiStyledTexts,
+}
+
+/// The number of blank columns between adjacent (non empty) groups.
+const GAP_BETWEEN_GROUPS: u16 = 1;
+
+impl StatusBar {
+    pub fn with_separator(mut self, separator: TuiStyledTexts) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    pub fn with_truncation_indicator(
+        mut self,
+        truncation_indicator: TruncationIndicator,
+    ) -> Self {
+        self.maybe_truncation_indicator = Some(truncation_indicator);
+        self
+    }
+
+    pub fn add_segment(
+        mut self,
+        alignment: StatusBarAlignment,
+        priority: u8,
+        content: impl Fn() -> TuiStyledTexts + 'static,
+    ) -> Self {
+        self.segments.push(StatusBarSegment {
+            alignment,
+            priority,
+            content: Box::new(content),
+        });
+        self
+    }
+
+    /// Generate the content for all the segments, and lay them out to fit in the given
+    /// width. See [StatusBar] for the layout rules.
+    pub fn layout(&self, width: ChUnit) -> Vec<StatusBarLayoutItem> {
+        // Generate the content for each segment.
+        let mut visible: Vec<(usize, &StatusBarSegment, TuiStyledTexts)> = self
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| (index, segment, (segment.content)()))
+            .collect();
+
+        // Drop the lowest priority segments until everything fits.
+        while visible.len() > 1 && self.get_total_width(&visible) > width {
+            let maybe_index_to_drop = visible
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (index, segment, _))| {
+                    (
+                        segment.priority,
+                        segment.alignment != StatusBarAlignment::Center,
+                        usize::MAX - index,
+                    )
+                })
+                .map(|(it, _)| it);
+            if let Some(index_to_drop) = maybe_index_to_drop {
+                visible.remove(index_to_drop);
+            }
+        }
+
+        let left = self.join_group(&visible, StatusBarAlignment::Left);
+        let center = self.join_group(&visible, StatusBarAlignment::Center);
+        let right = self.join_group(&visible, StatusBarAlignment::Right);
+
+        // Clip the one remaining segment if it still doesn't fit.
+        let clip = |styled_texts: TuiStyledTexts| match &self.maybe_truncation_indicator {
+            Some(truncation_indicator) => clip_styled_texts_to_width_with_indicator(
+                styled_texts,
+                width,
+                truncation_indicator,
+            ),
+            None => clip_styled_texts_to_width(styled_texts, width),
+        };
+        let left = clip(left);
+        let center = clip(center);
+        let right = clip(right);
+
+        let left_width = left.display_width();
+        let center_width = center.display_width();
+        let right_width = right.display_width();
+
+        let mut it = vec![];
+
+        let left_end = if left.is_empty() {
+            ch!(0)
+        } else {
+            left_width + GAP_BETWEEN_GROUPS
+        };
+        let right_start = if right.is_empty() {
+            width
+        } else {
+            (width - right_width) - GAP_BETWEEN_GROUPS
+        };
+
+        if !left.is_empty() {
+            it.push(StatusBarLayoutItem {
+                col_index: ch!(0),
+                styled_texts: left,
+            });
+        }
+
+        if !center.is_empty() {
+            let ideal_col_index = (width - center_width) / 2;
+            let max_col_index = right_start - center_width;
+            let col_index =
+                std::cmp::max(left_end, std::cmp::min(ideal_col_index, max_col_index));
+            it.push(StatusBarLayoutItem {
+                col_index,
+                styled_texts: center,
+            });
+        }
+
+        if !right.is_empty() {
+            it.push(StatusBarLayoutItem {
+                col_index: width - right_width,
+                styled_texts: right,
+            });
+        }
+
+        it
+    }
+
+    /// Lay out the status bar (see [StatusBar::layout]), and paint it in the row at
+    /// `origin`, taking up `width` columns.
+    pub fn render_into(
+        &self,
+        pipeline: &mut RenderPipeline,
+        origin: Position,
+        width: ChUnit,
+    ) {
+        let mut render_ops = render_ops!();
+        for StatusBarLayoutItem {
+            col_index,
+            styled_texts,
+        } in self.layout(width)
+        {
+            render_ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                col_index: origin.col_index + col_index,
+                row_index: origin.row_index
+            )));
+            render_tui_styled_texts_into(&styled_texts, &mut render_ops);
+        }
+        pipeline.push(ZOrder::Normal, render_ops);
+    }
+
+    fn join_group(
+        &self,
+        visible: &[(usize, &StatusBarSegment, TuiStyledTexts)],
+        alignment: StatusBarAlignment,
+    ) -> TuiStyledTexts {
+        let mut it = TuiStyledTexts::default();
+        for (_, segment, styled_texts) in visible {
+            if segment.alignment != alignment {
+                continue;
+            }
+            if !it.is_empty() {
+                it += self.separator.clone();
+            }
+            it += styled_texts.clone();
+        }
+        it
+    }
+
+    fn get_total_width(
+        &self,
+        visible: &[(usize, &StatusBarSegment, TuiStyledTexts)],
+    ) -> ChUnit {
+        let mut total = ch!(0);
+        let mut non_empty_group_count = 0;
+        for alignment in [
+            StatusBarAlignment::Left,
+            StatusBarAlignment::Center,
+            StatusBarAlignment::Right,
+        ] {
+            let group = self.join_group(visible, alignment);
+            if !group.is_empty() {
+                total += group.display_width();
+                non_empty_group_count += 1;
+            }
+        }
+        if non_empty_group_count > 1 {
+            total += ch!(non_empty_group_count - 1) * ch!(GAP_BETWEEN_GROUPS);
+        }
+        total
+    }
+}
+
+/// Clip the given styled texts so they fit in `max_display_col_count` columns. Graphemes
+/// are never split, so the result may be narrower than the given width.
+pub fn clip_styled_texts_to_width(
+    styled_texts: TuiStyledTexts,
+    max_display_col_count: ChUnit,
+) -> TuiStyledTexts {
+    if styled_texts.display_width() <= max_display_col_count {
+        return styled_texts;
+    }
+
+    let mut it = TuiStyledTexts::default();
+    let mut avail_col_count = max_display_col_count;
+    for styled_text in styled_texts.inner.iter() {
+        if avail_col_count == ch!(0) {
+            break;
+        }
+        let text = styled_text.get_text();
+        let clipped_text = text.truncate_end_to_fit_width(avail_col_count);
+        if !clipped_text.is_empty() {
+            let clipped =
+                TuiStyledText::new(*styled_text.get_style(), clipped_text.into());
+            avail_col_count -= clipped.get_text().display_width;
+            it += clipped;
+        }
+        if clipped_text.len() < text.string.len() {
+            break;
+        }
+    }
+    it
+}
+
+/// Just like [clip_styled_texts_to_width], but if the styled texts are clipped, then the
+/// `truncation_indicator` is painted at the end (and its width is taken into account).
+/// If the indicator doesn't have a style, then the style of the last clipped text is
+/// used. If the indicator doesn't fit, then the styled texts are clipped w/out it.
+pub fn clip_styled_texts_to_width_with_indicator(
+    styled_texts: TuiStyledTexts,
+    max_display_col_count: ChUnit,
+    truncation_indicator: &TruncationIndicator,
+) -> TuiStyledTexts {
+    let indicator_display_width = truncation_indicator.get_display_width();
+    if styled_texts.display_width() <= max_display_col_count
+        || indicator_display_width > max_display_col_count
+    {
+        return clip_styled_texts_to_width(styled_texts, max_display_col_count);
+    }
+
+    let maybe_last_style = styled_texts.inner.last().map(|it| *it.get_style());
+    let mut it = clip_styled_texts_to_width(
+        styled_texts,
+        max_display_col_count - indicator_display_width,
+    );
+    let indicator_style = truncation_indicator
+        .maybe_style
+        .or(it.inner.last().map(|it| *it.get_style()))
+        .or(maybe_last_style)
+        .unwrap_or_default();
+    it += TuiStyledText::new(indicator_style, truncation_indicator.text.to_string());
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    tui_styled_text,
+                    tui_styled_texts,
+                    ConvertToPlainText,
+                    TuiStyle};
+
+    use super::*;
+
+    fn text(it: &'static str) -> impl Fn() -> TuiStyledTexts {
+        move || tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: it } }
+    }
+
+    fn plain_text(item: &StatusBarLayoutItem) -> String {
+        item.styled_texts.to_plain_text_us().string
+    }
+
+    #[test]
+    fn test_layout_left_center_right() {
+        let status_bar = StatusBar::default()
+            .add_segment(StatusBarAlignment::Left, 1, text("left"))
+            .add_segment(StatusBarAlignment::Center, 1, text("mid"))
+            .add_segment(StatusBarAlignment::Right, 1, text("right"));
+
+        let layout = status_bar.layout(ch!(21));
+        assert_eq2!(layout.len(), 3);
+
+        assert_eq2!(layout[0].col_index, ch!(0));
+        assert_eq2!(plain_text(&layout[0]), "left");
+
+        assert_eq2!(layout[1].col_index, ch!(9));
+        assert_eq2!(plain_text(&layout[1]), "mid");
+
+        assert_eq2!(layout[2].col_index, ch!(16));
+        assert_eq2!(plain_text(&layout[2]), "right");
+    }
+
+    #[test]
+    fn test_layout_center_is_nudged_to_not_overlap() {
+        let status_bar = StatusBar::default()
+            .add_segment(StatusBarAlignment::Left, 1, text("a long left side"))
+            .add_segment(StatusBarAlignment::Center, 1, text("mid"));
+
+        let layout = status_bar.layout(ch!(24));
+        assert_eq2!(layout[1].col_index, ch!(17));
+    }
+
+    #[test]
+    fn test_layout_separator_between_segments_in_same_group() {
+        let status_bar = StatusBar::default()
+            .with_separator(
+                tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: " | " } },
+            )
+            .add_segment(StatusBarAlignment::Left, 1, text("a"))
+            .add_segment(StatusBarAlignment::Left, 1, text("b"));
+
+        let layout = status_bar.layout(ch!(20));
+        assert_eq2!(layout.len(), 1);
+        assert_eq2!(plain_text(&layout[0]), "a | b");
+    }
+
+    #[test]
+    fn test_layout_drops_lowest_priority_segments_first() {
+        let status_bar = StatusBar::default()
+            .add_segment(StatusBarAlignment::Left, 3, text("important"))
+            .add_segment(StatusBarAlignment::Center, 2, text("center"))
+            .add_segment(StatusBarAlignment::Right, 1, text("extra"));
+
+        // Everything fits: 9 + 1 + 6 + 1 + 5 = 22.
+        assert_eq2!(status_bar.layout(ch!(22)).len(), 3);
+
+        // The right segment has the lowest priority.
+        let layout = status_bar.layout(ch!(21));
+        assert_eq2!(layout.len(), 2);
+        assert_eq2!(plain_text(&layout[0]), "important");
+        assert_eq2!(plain_text(&layout[1]), "center");
+
+        // Then the center segment.
+        let layout = status_bar.layout(ch!(12));
+        assert_eq2!(layout.len(), 1);
+        assert_eq2!(plain_text(&layout[0]), "important");
+    }
+
+    #[test]
+    fn test_layout_drops_center_first_when_priorities_are_equal() {
+        let status_bar = StatusBar::default()
+            .add_segment(StatusBarAlignment::Left, 1, text("left"))
+            .add_segment(StatusBarAlignment::Center, 1, text("center"))
+            .add_segment(StatusBarAlignment::Right, 1, text("right"));
+
+        let layout = status_bar.layout(ch!(12));
+        assert_eq2!(layout.len(), 2);
+        assert_eq2!(plain_text(&layout[0]), "left");
+        assert_eq2!(plain_text(&layout[1]), "right");
+    }
+
+    #[test]
+    fn test_layout_clips_last_segment_without_splitting_graphemes() {
+        let status_bar =
+            StatusBar::default().add_segment(StatusBarAlignment::Left, 1, text("ab😃cd"));
+
+        // "😃" is 2 columns wide, so it can't fit in the 3rd column.
+        let layout = status_bar.layout(ch!(3));
+        assert_eq2!(layout.len(), 1);
+        assert_eq2!(plain_text(&layout[0]), "ab");
+    }
+
+    #[test]
+    fn test_layout_clips_last_segment_with_truncation_indicator() {
+        let indicator_style = TuiStyle {
+            dim: true,
+            ..Default::default()
+        };
+        let status_bar = StatusBar::default()
+            .with_truncation_indicator(
+                TruncationIndicator::new("⏩").with_style(indicator_style),
+            )
+            .add_segment(StatusBarAlignment::Left, 1, text("abcdef"));
+
+        // The indicator is 2 columns wide, and it is styled separately.
+        let layout = status_bar.layout(ch!(5));
+        assert_eq2!(plain_text(&layout[0]), "abc⏩");
+        assert_eq2!(
+            layout[0].styled_texts.inner[0].get_style(),
+            &TuiStyle::default()
+        );
+        assert_eq2!(
+            layout[0].styled_texts.inner[1].get_style(),
+            &indicator_style
+        );
+
+        // Nothing is clipped, so there's no indicator.
+        let layout = status_bar.layout(ch!(6));
+        assert_eq2!(plain_text(&layout[0]), "abcdef");
+
+        // The indicator doesn't fit.
+        let layout = status_bar.layout(ch!(1));
+        assert_eq2!(plain_text(&layout[0]), "a");
+    }
+
+    #[test]
+    fn test_render_into_pipeline() {
+        let status_bar = StatusBar::default()
+            .add_segment(StatusBarAlignment::Left, 1, text("left"))
+            .add_segment(StatusBarAlignment::Right, 1, text("right"));
+
+        let mut pipeline = RenderPipeline::default();
+        status_bar.render_into(
+            &mut pipeline,
+            position!(col_index: 0, row_index: 9),
+            ch!(20),
+        );
+
+        let render_ops = pipeline.get_all_render_op_in(ZOrder::Normal).unwrap();
+        assert_eq2!(
+            render_ops[0],
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 9))
+        );
+        assert_eq2!(
+            render_ops[4],
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 15, row_index: 9))
+        );
+    }
+}
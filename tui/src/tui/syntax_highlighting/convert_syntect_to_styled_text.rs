@@ -34,7 +34,7 @@ use r3bl_core::{tui_styled_text, RgbValue, TuiColor, TuiStyle, TuiStyledTexts};
 use syntect::parsing::SyntaxSet;
 
 use super::{StyleUSSpan, StyleUSSpanLine};
-use crate::aliases::US;
+use crate::{aliases::US, DEFAULT_SYN_HI_FILE_EXT};
 
 // Type aliases for syntect types.
 
@@ -56,6 +56,25 @@ pub fn try_get_syntax_ref<'a>(
     syntax_set.find_syntax_by_extension(file_extension)
 }
 
+/// Returns the syntax for the given `language`, which can be its name (eg: `"Rust"`,
+/// case insensitive) or one of its file extensions (eg: `"rs"`). Falls back to plain text
+/// when the language isn't found.
+pub fn try_get_syntax_ref_by_language<'a>(
+    syntax_set: &'a SyntaxSet,
+    language: &str,
+) -> &'a syntect::parsing::SyntaxReference {
+    syntax_set
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
+}
+
+/// Returns true if the given `language` (see [try_get_syntax_ref_by_language]) is
+/// Markdown, which is highlighted by the custom MD parser instead of [syntect].
+pub fn is_markdown_language(language: &str) -> bool {
+    language.eq_ignore_ascii_case(DEFAULT_SYN_HI_FILE_EXT)
+        || language.eq_ignore_ascii_case("markdown")
+}
+
 pub fn convert_style_from_syntect_to_tui(st_style: SyntectStyle) -> TuiStyle {
     TuiStyle {
         color_fg: Some(convert_color_from_syntect_to_tui(st_style.foreground)),
@@ -540,3 +559,43 @@ mod tests_convert_style_and_color {
         }
     }
 }
+
+#[cfg(test)]
+mod tests_syntax_ref_by_language {
+    use r3bl_core::assert_eq2;
+    use syntect::parsing::SyntaxSet;
+
+    use crate::{is_markdown_language, try_get_syntax_ref_by_language};
+
+    #[test]
+    fn test_try_get_syntax_ref_by_language() {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+
+        // By name (case insensitive), or by extension.
+        assert_eq2!(
+            try_get_syntax_ref_by_language(&syntax_set, "rust").name,
+            "Rust"
+        );
+        assert_eq2!(
+            try_get_syntax_ref_by_language(&syntax_set, "Python").name,
+            "Python"
+        );
+        assert_eq2!(
+            try_get_syntax_ref_by_language(&syntax_set, "rs").name,
+            "Rust"
+        );
+
+        // Unknown languages fall back to plain text.
+        assert_eq2!(
+            try_get_syntax_ref_by_language(&syntax_set, "not-a-language").name,
+            "Plain Text"
+        );
+    }
+
+    #[test]
+    fn test_is_markdown_language() {
+        assert!(is_markdown_language("md"));
+        assert!(is_markdown_language("Markdown"));
+        assert!(!is_markdown_language("rust"));
+    }
+}
@@ -43,6 +43,85 @@ pub fn get_selection_style() -> TuiStyle {
     }
 }
 
+/// This style is for the background of the line that the caret is on. It is meant to be
+/// subtle, and there is no background when the terminal has no color support.
+pub fn get_current_line_style() -> TuiStyle {
+    match global_color_support::detect() {
+        ColorSupport::Truecolor => tui_style! {
+            color_bg: TuiColor::Rgb(RgbValue::from_hex("#2b2735"))
+        },
+        ColorSupport::Ansi256 => tui_style! {
+            color_bg: TuiColor::Ansi(AnsiValue::new(235)) // Grey15.
+        },
+        ColorSupport::Grayscale => tui_style! {
+            color_bg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
+        },
+        ColorSupport::NoColor => TuiStyle::default(),
+    }
+}
+
+/// This style is for the matches of the query while finding text in the editor (see
+/// [crate::find_replace]), except for the current one.
+pub fn get_find_match_style() -> TuiStyle {
+    match global_color_support::detect() {
+        ColorSupport::Truecolor => tui_style! {
+            color_fg: TuiColor::Rgb(RgbValue::from_hex("#1e1e1e"))
+            color_bg: TuiColor::Rgb(RgbValue::from_hex("#b3a36b"))
+        },
+        ColorSupport::Ansi256 => tui_style! {
+            color_fg: TuiColor::Ansi(AnsiValue::new(234)) // Grey11.
+            color_bg: TuiColor::Ansi(AnsiValue::new(143)) // DarkKhaki.
+        },
+        ColorSupport::Grayscale => tui_style! {
+            color_fg: TuiColor::Basic(ANSIBasicColor::Black)
+            color_bg: TuiColor::Basic(ANSIBasicColor::DarkYellow)
+        },
+        ColorSupport::NoColor => tui_style! {
+            attrib: [underline]
+        },
+    }
+}
+
+/// This style is for the current match while finding text in the editor, ie: the one
+/// that the caret is on. It stands out from the other matches.
+pub fn get_current_find_match_style() -> TuiStyle {
+    match global_color_support::detect() {
+        ColorSupport::Truecolor => tui_style! {
+            color_fg: TuiColor::Rgb(RgbValue::from_hex("#1e1e1e"))
+            color_bg: TuiColor::Rgb(RgbValue::from_hex("#ffd700"))
+        },
+        ColorSupport::Ansi256 => tui_style! {
+            color_fg: TuiColor::Ansi(AnsiValue::new(234)) // Grey11.
+            color_bg: TuiColor::Ansi(AnsiValue::new(220)) // Gold1.
+        },
+        ColorSupport::Grayscale => tui_style! {
+            color_fg: TuiColor::Basic(ANSIBasicColor::Black)
+            color_bg: TuiColor::Basic(ANSIBasicColor::Yellow)
+        },
+        ColorSupport::NoColor => tui_style! {
+            attrib: [reverse]
+        },
+    }
+}
+
+/// This style is for the find bar, which is painted in the last row of the editor
+/// while finding text in it.
+pub fn get_find_bar_style() -> TuiStyle {
+    match global_color_support::detect() {
+        ColorSupport::Truecolor => tui_style! {
+            color_fg: TuiColor::Rgb(RgbValue::from_hex("#dddddd"))
+            color_bg: TuiColor::Rgb(RgbValue::from_hex("#4e3f6b"))
+        },
+        ColorSupport::Ansi256 => tui_style! {
+            color_fg: TuiColor::Ansi(AnsiValue::new(253)) // Grey85.
+            color_bg: TuiColor::Ansi(AnsiValue::new(60)) // MediumPurple4.
+        },
+        _ => tui_style! {
+            attrib: [reverse]
+        },
+    }
+}
+
 /// This style is for the foreground text of the entire document. This is the default
 /// style. It is overridden by other styles like bold, italic, etc. below.
 pub fn get_foreground_style() -> TuiStyle {
@@ -106,6 +185,20 @@ pub fn get_bold_italic_style() -> TuiStyle {
     }
 }
 
+/// This is added to the style of the strikethrough content, not the enclosing `~~`.
+pub fn get_strikethrough_style() -> TuiStyle {
+    tui_style! {
+        attrib: [strikethrough]
+    }
+}
+
+/// This is added to the style of the highlighted content, not the enclosing `==`.
+pub fn get_highlight_style() -> TuiStyle {
+    tui_style! {
+        attrib: [reverse]
+    }
+}
+
 /// This is just for the bold content, not the enclosing "`".
 pub fn get_inline_code_style() -> TuiStyle {
     tui_style! {
@@ -175,6 +268,23 @@ pub fn get_list_bullet_style() -> TuiStyle {
     }
 }
 
+/// This is just for the label of a footnote reference or definition, not the enclosing
+/// `[^` and `]`.
+pub fn get_footnote_label_style() -> TuiStyle {
+    get_link_text_style()
+        + tui_style! {
+            attrib: [italic]
+        }
+}
+
+/// This is for the `>` markers of a block quote, not the content.
+pub fn get_block_quote_marker_style() -> TuiStyle {
+    get_list_bullet_style()
+        + tui_style! {
+            attrib: [bold]
+        }
+}
+
 pub fn get_code_block_lang_style() -> TuiStyle {
     get_inline_code_style()
         + tui_style! {
@@ -34,7 +34,13 @@ use crate::{constants::{AUTHORS,
                         BACK_TICK,
                         CHECKED_OUTPUT,
                         CODE_BLOCK_START_PARTIAL,
+                        COLON,
                         DATE,
+                        DOUBLE_EQUALS,
+                        DOUBLE_TILDE,
+                        FOOTNOTE_REF_END,
+                        FOOTNOTE_REF_START,
+                        FRONT_MATTER_DELIMITER,
                         LEFT_BRACKET,
                         LEFT_IMAGE,
                         LEFT_PARENTHESIS,
@@ -42,6 +48,7 @@ use crate::{constants::{AUTHORS,
                         RIGHT_IMAGE,
                         RIGHT_PARENTHESIS,
                         STAR,
+                        TABLE_PIPE_CHAR,
                         TAGS,
                         TITLE,
                         UNCHECKED_OUTPUT,
@@ -49,19 +56,24 @@ use crate::{constants::{AUTHORS,
             convert_syntect_to_styled_text,
             generate_ordered_list_item_bullet,
             generate_unordered_list_item_bullet,
+            get_block_quote_marker_style,
             get_bold_style,
             get_checkbox_checked_style,
             get_checkbox_unchecked_style,
             get_code_block_content_style,
             get_code_block_lang_style,
+            get_footnote_label_style,
             get_foreground_dim_style,
             get_foreground_style,
+            get_highlight_style,
             get_inline_code_style,
             get_italic_style,
             get_link_text_style,
             get_link_url_style,
             get_list_bullet_style,
+            get_strikethrough_style,
             parse_markdown,
+            strip_block_quote_marker,
             try_get_syntax_ref,
             CodeBlockLineContent,
             CodeBlockLines,
@@ -73,6 +85,7 @@ use crate::{constants::{AUTHORS,
             MdBlock,
             MdDocument,
             MdLineFragment,
+            MdLineFragments,
             StyleUSSpan,
             StyleUSSpanLine,
             StyleUSSpanLines,
@@ -371,6 +384,141 @@ impl StyleUSSpanLines {
         acc_lines_output
     }
 
+    /// - 1st line        : "---": `get_foreground_dim_style()`
+    /// - 2nd line .. end : `get_code_block_content_style()`
+    /// - last line       : "---": `get_foreground_dim_style()`
+    pub fn from_block_front_matter(
+        content: &str,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+    ) -> Self {
+        let mut acc_lines_output = StyleUSSpanLines::default();
+
+        let delimiter_line = || {
+            let mut it = StyleUSSpanLine::default();
+            it += StyleUSSpan::new(
+                maybe_current_box_computed_style.unwrap_or_default()
+                    + get_foreground_dim_style(),
+                US::from(FRONT_MATTER_DELIMITER),
+            );
+            it
+        };
+
+        acc_lines_output += delimiter_line();
+        for line in content.lines() {
+            let mut acc_line_output = StyleUSSpanLine::default();
+            acc_line_output += StyleUSSpan::new(
+                maybe_current_box_computed_style.unwrap_or_default()
+                    + get_code_block_content_style(),
+                US::from(line),
+            );
+            acc_lines_output += acc_line_output;
+        }
+        acc_lines_output += delimiter_line();
+
+        acc_lines_output
+    }
+
+    /// The text of each row is kept as is, so that it lines up w/ the editor buffer.
+    /// - header row    : `|`: `get_foreground_dim_style()`, cells: `get_bold_style()`
+    /// - separator row : `get_foreground_dim_style()`
+    /// - body rows     : `|`: `get_foreground_dim_style()`, cells: `get_foreground_style()`
+    pub fn from_block_table(
+        raw_lines: &[&str],
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+    ) -> Self {
+        let mut acc_lines_output = StyleUSSpanLines::default();
+
+        let base_style = maybe_current_box_computed_style.unwrap_or_default();
+        let pipe_style = base_style + get_foreground_dim_style();
+
+        for (row_index, raw_line) in raw_lines.iter().enumerate() {
+            let mut acc_line_output = StyleUSSpanLine::default();
+            let cell_style = match row_index {
+                0 => base_style + get_bold_style(),
+                1 => pipe_style,
+                _ => base_style + get_foreground_style(),
+            };
+
+            // Split the row at each `|` that isn't escaped.
+            let mut cell_start_index = 0;
+            let mut prev_char = None;
+            for (index, char) in raw_line.char_indices() {
+                if char == TABLE_PIPE_CHAR && prev_char != Some('\\') {
+                    if cell_start_index < index {
+                        acc_line_output += StyleUSSpan::new(
+                            cell_style,
+                            US::from(&raw_line[cell_start_index..index]),
+                        );
+                    }
+                    acc_line_output += StyleUSSpan::new(
+                        pipe_style,
+                        US::from(TABLE_PIPE_CHAR.to_string()),
+                    );
+                    cell_start_index = index + char.len_utf8();
+                }
+                prev_char = Some(char);
+            }
+            if cell_start_index < raw_line.len() {
+                acc_line_output +=
+                    StyleUSSpan::new(cell_style, US::from(&raw_line[cell_start_index..]));
+            }
+
+            acc_lines_output += acc_line_output;
+        }
+
+        acc_lines_output
+    }
+
+    /// Each line of the quote is its `>` marker(s) followed by the line of the block (in
+    /// the quote) that it has. Nested quotes are highlighted the same way, since their
+    /// lines are the lines of the parent quote w/out its marker.
+    /// - marker  : `get_block_quote_marker_style()`
+    /// - content : the style of the block, w/ `italic` added
+    pub fn from_block_quote(
+        blocks: &[MdBlock<'_>],
+        raw_lines: &[&str],
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    ) -> Self {
+        let mut acc_lines_output = StyleUSSpanLines::default();
+
+        let mut content_lines = blocks
+            .iter()
+            .flat_map(|block| {
+                StyleUSSpanLines::from_block(
+                    block,
+                    maybe_current_box_computed_style,
+                    maybe_syntect_tuple,
+                )
+                .inner
+            })
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        for raw_line in raw_lines {
+            let content = strip_block_quote_marker(raw_line).unwrap_or(raw_line);
+            let marker = &raw_line[..raw_line.len() - content.len()];
+
+            let mut acc_line_output = StyleUSSpanLine::default();
+            if !marker.is_empty() {
+                acc_line_output += StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_block_quote_marker_style(),
+                    US::from(marker),
+                );
+            }
+            for mut span in content_lines.next().unwrap_or_default().inner {
+                span.style += tui_style! {
+                    attrib: [italic]
+                };
+                acc_line_output += span;
+            }
+            acc_lines_output += acc_line_output;
+        }
+
+        acc_lines_output
+    }
+
     /// Each [MdBlock] needs to be translated into a line. The [MdBlock::CodeBlock] is
     /// the only block that needs to be translated into multiple lines. This is why the return type
     /// is a [StyleUSSpanLines] (and not a single line).
@@ -428,6 +576,48 @@ impl StyleUSSpanLines {
                     maybe_current_box_computed_style,
                 );
             }
+            MdBlock::FrontMatter(content) => {
+                lines += StyleUSSpanLines::from_block_front_matter(
+                    content,
+                    maybe_current_box_computed_style,
+                );
+            }
+            MdBlock::FootnoteDef { label, content } => {
+                let mut line = StyleUSSpanLine::default();
+                for span in StyleUSSpan::format_footnote_label(
+                    label,
+                    maybe_current_box_computed_style,
+                ) {
+                    line += span;
+                }
+                line += StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_foreground_dim_style(),
+                    US::from(COLON),
+                );
+                line.inner.extend(
+                    StyleUSSpanLine::from_fragments(
+                        content,
+                        maybe_current_box_computed_style,
+                    )
+                    .inner,
+                );
+                lines.push(line);
+            }
+            MdBlock::BlockQuote { blocks, raw_lines } => {
+                lines += StyleUSSpanLines::from_block_quote(
+                    blocks,
+                    raw_lines,
+                    maybe_current_box_computed_style,
+                    maybe_syntect_tuple,
+                );
+            }
+            MdBlock::Table { raw_lines, .. } => {
+                lines += StyleUSSpanLines::from_block_table(
+                    raw_lines,
+                    maybe_current_box_computed_style,
+                );
+            }
             MdBlock::CodeBlock(code_block_lines) => {
                 lines += StyleUSSpanLines::from_block_codeblock(
                     code_block_lines,
@@ -441,6 +631,57 @@ impl StyleUSSpanLines {
     }
 }
 
+impl StyleUSSpan {
+    /// The `fragments` (between the `delim`s) are highlighted as usual, eg:
+    /// `~~*bold strike*~~`, and the `nested_style` is added to them.
+    /// - `delim`     : `get_foreground_dim_style()`
+    /// - `fragments` : the style of each fragment + `nested_style`
+    fn format_nested_fragments(
+        fragments: &MdLineFragments<'_>,
+        delim: &str,
+        nested_style: TuiStyle,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+    ) -> Vec<Self> {
+        let delim_span = StyleUSSpan::new(
+            maybe_current_box_computed_style.unwrap_or_default()
+                + get_foreground_dim_style(),
+            US::from(delim),
+        );
+
+        let mut acc = vec![delim_span.clone()];
+        for fragment in fragments.iter() {
+            for mut span in
+                StyleUSSpan::from_fragment(fragment, maybe_current_box_computed_style)
+            {
+                span.style += nested_style;
+                acc.push(span);
+            }
+        }
+        acc.push(delim_span);
+        acc
+    }
+
+    /// - `[^`  : `get_foreground_dim_style()`
+    /// - label : `get_footnote_label_style()`
+    /// - `]`   : `get_foreground_dim_style()`
+    fn format_footnote_label(
+        label: &str,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+    ) -> Vec<Self> {
+        let base_style = maybe_current_box_computed_style.unwrap_or_default()
+            + get_foreground_dim_style();
+        vec![
+            StyleUSSpan::new(base_style, US::from(FOOTNOTE_REF_START)),
+            StyleUSSpan::new(
+                maybe_current_box_computed_style.unwrap_or_default()
+                    + get_footnote_label_style(),
+                US::from(label),
+            ),
+            StyleUSSpan::new(base_style, US::from(FOOTNOTE_REF_END)),
+        ]
+    }
+}
+
 enum HyperlinkType {
     Image,
     Link,
@@ -588,6 +829,20 @@ impl StyleUSSpan {
                 ),
             ],
 
+            MdLineFragment::Strikethrough(fragments) => Self::format_nested_fragments(
+                fragments,
+                DOUBLE_TILDE,
+                get_strikethrough_style(),
+                maybe_current_box_computed_style,
+            ),
+
+            MdLineFragment::Highlight(fragments) => Self::format_nested_fragments(
+                fragments,
+                DOUBLE_EQUALS,
+                get_highlight_style(),
+                maybe_current_box_computed_style,
+            ),
+
             MdLineFragment::Link(link_data) => Self::format_hyperlink_data(
                 link_data,
                 maybe_current_box_computed_style,
@@ -600,6 +855,10 @@ impl StyleUSSpan {
                 HyperlinkType::Image,
             ),
 
+            MdLineFragment::FootnoteRef(label) => {
+                Self::format_footnote_label(label, maybe_current_box_computed_style)
+            }
+
             MdLineFragment::Checkbox(done) => {
                 vec![if *done {
                     StyleUSSpan::new(
@@ -948,6 +1207,51 @@ mod tests_style_us_span_lines_from {
             // println!("{}", List::from(actual)..pretty_print_debug());
         }
 
+        #[test]
+        fn test_strikethrough_and_highlight() {
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let fragment = MdLineFragment::Strikethrough(list![
+                MdLineFragment::Bold("bold"),
+                MdLineFragment::Plain(" strike"),
+            ]);
+            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let strike = style + get_strikethrough_style();
+            assert_eq2!(
+                actual,
+                vec![
+                    StyleUSSpan::new(style + get_foreground_dim_style(), US::from("~~")),
+                    StyleUSSpan::new(
+                        style + get_foreground_dim_style() + strike,
+                        US::from("*")
+                    ),
+                    StyleUSSpan::new(style + get_bold_style() + strike, US::from("bold")),
+                    StyleUSSpan::new(
+                        style + get_foreground_dim_style() + strike,
+                        US::from("*")
+                    ),
+                    StyleUSSpan::new(
+                        style + get_foreground_style() + strike,
+                        US::from(" strike")
+                    ),
+                    StyleUSSpan::new(style + get_foreground_dim_style(), US::from("~~")),
+                ]
+            );
+
+            let fragment =
+                MdLineFragment::Highlight(list![MdLineFragment::Plain("note")]);
+            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            assert_eq2!(
+                actual[1],
+                StyleUSSpan::new(
+                    style + get_foreground_style() + get_highlight_style(),
+                    US::from("note")
+                )
+            );
+        }
+
         #[test]
         fn test_inline_code() {
             let fragment = MdLineFragment::InlineCode("Foobar");
@@ -1284,6 +1588,94 @@ mod tests_style_us_span_lines_from {
             });
         }
 
+        #[test]
+        fn test_block_footnotes() {
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+            let input = "Foo[^1] bar[^2]\n[^1]: Note\n";
+            let (_, doc) = parse_markdown(input).unwrap();
+            let lines = StyleUSSpanLines::from_document(&doc, &Some(style), None);
+
+            // The text of each line is the same as in the document.
+            assert_eq2!(lines.len(), 2);
+            for (line, raw_line) in lines.iter().zip(input.lines()) {
+                let text = line
+                    .iter()
+                    .map(|span| span.text.string.as_str())
+                    .collect::<String>();
+                assert_eq2!(text, raw_line);
+            }
+
+            let label_style = style + get_footnote_label_style();
+            assert_eq2!(lines[0][2], StyleUSSpan::new(label_style, US::from("1")));
+            assert_eq2!(lines[1][1], StyleUSSpan::new(label_style, US::from("1")));
+            // The undefined reference is plain text.
+            assert!(lines[0].iter().all(|span| span.text.string != "2"
+                || span.style == style + get_foreground_style()));
+        }
+
+        #[test]
+        fn test_block_quote() {
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+            let input = "> Foo\n >> **Bar**\nbaz\n";
+            let (_, doc) = parse_markdown(input).unwrap();
+            let lines = StyleUSSpanLines::from_block(&doc[0], &Some(style), None);
+
+            // The text of each line is the same as in the document.
+            assert_eq2!(lines.len(), 3);
+            for (line, raw_line) in lines.iter().zip(input.lines()) {
+                let text = line
+                    .iter()
+                    .map(|span| span.text.string.as_str())
+                    .collect::<String>();
+                assert_eq2!(text, raw_line);
+            }
+
+            let marker_style = style + get_block_quote_marker_style();
+            assert_eq2!(lines[0][0], StyleUSSpan::new(marker_style, US::from("> ")));
+            assert_eq2!(lines[1][0], StyleUSSpan::new(marker_style, US::from(" >")));
+            // The marker of the nested quote is in the content of the outer quote.
+            assert_eq2!(lines[1][1].text, US::from("> "));
+            // The lazy continuation line has no marker.
+            assert_eq2!(lines[2].len(), 1);
+            assert!(lines[2][0].style.italic);
+        }
+
+        #[test]
+        fn test_block_table() {
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+            let input = "| a | b |\n|---|:-:|\n| 1 \\| 2 | 3 |\n";
+            let (_, doc) = parse_markdown(input).unwrap();
+            let lines = StyleUSSpanLines::from_block(&doc[0], &Some(style), None);
+
+            // The text of each row is the same as in the document.
+            assert_eq2!(lines.len(), 3);
+            for (line, raw_line) in lines.iter().zip(input.lines()) {
+                let text = line
+                    .iter()
+                    .map(|span| span.text.string.as_str())
+                    .collect::<String>();
+                assert_eq2!(text, raw_line);
+            }
+
+            let pipe_style = style + get_foreground_dim_style();
+            assert_eq2!(lines[0][0], StyleUSSpan::new(pipe_style, US::from("|")));
+            assert_eq2!(
+                lines[0][1],
+                StyleUSSpan::new(style + get_bold_style(), US::from(" a "))
+            );
+            assert_eq2!(
+                lines[2][1],
+                StyleUSSpan::new(style + get_foreground_style(), US::from(" 1 \\| 2 "))
+            );
+            assert!(lines[1].iter().all(|span| span.style == pipe_style));
+        }
+
         #[test]
         fn test_block_text() {
             let text_block = MdBlock::Text(list![MdLineFragment::Plain("Foobar")]);
@@ -0,0 +1,567 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Incremental version of [try_parse_and_highlight()](super::try_parse_and_highlight),
+//! so that an edit to a long document doesn't cause the whole document to be parsed and
+//! highlighted again on each render.
+//!
+//! The [MdIncrementalHighlighter] remembers the lines that it highlighted last time, and
+//! the number of lines in each block of the document. The lines that were changed are
+//! found by comparing the new lines w/ the old ones, and only the blocks around them are
+//! parsed again, and spliced back into the highlighted lines:
+//!
+//! ```text
+//! old blocks:  [ 0 ][ 1 ][  2  ][ 3 ][ 4 ][ 5 ]
+//! edit:                   ^^^
+//! parsed:           [ 1 ][ 2' ][ 3 ]            <- until a block starts at an old one
+//! new blocks:  [ 0 ][ 1 ][ 2' ][ 3 ][ 4 ][ 5 ]
+//! ```
+//!
+//! 1. The block before the edit is parsed again too, since an edit can join its lines to
+//!    it (eg: a table row, or a lazy continuation line of a quote).
+//! 2. Parsing continues past the edit until a block starts at the same place as a block
+//!    did before the edit. The blocks after that are parsed from the same text, so they
+//!    don't change.
+//!
+//! The whole document is parsed again instead when:
+//! - A code block fence is added, changed, or removed. An unclosed fence can turn the
+//!   rest of the document into a code block, so the edit can't be contained.
+//! - A footnote reference or definition is near the edit, since whether a reference is
+//!   defined depends on the whole document.
+//! - The edit is in the first block, which may be front matter.
+//! - The style of the box that the editor is in was changed.
+
+use r3bl_core::{CommonError, CommonErrorType, CommonResult, TuiStyle};
+use syntect::{highlighting::Theme, parsing::SyntaxSet};
+
+use crate::{constants::{CODE_BLOCK_START_PARTIAL, FOOTNOTE_REF_START},
+            parse_markdown,
+            parse_markdown_blocks,
+            StyleUSSpanLines,
+            US};
+
+/// Holds the result of the last call to
+/// [try_parse_and_highlight](MdIncrementalHighlighter::try_parse_and_highlight), so that
+/// the next call only has to parse the blocks that were changed. If the `theme` that is
+/// used to highlight code blocks is changed, call
+/// [invalidate](MdIncrementalHighlighter::invalidate) on it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MdIncrementalHighlighter {
+    maybe_prev: Option<PrevHighlight>,
+    /// The number of times that the whole document was parsed.
+    pub full_parse_count: usize,
+    /// The number of times that only the changed blocks were parsed.
+    pub incremental_parse_count: usize,
+    /// The number of lines that were parsed by the last call.
+    pub last_parsed_line_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct PrevHighlight {
+    editor_text_lines: Vec<US>,
+    maybe_current_box_computed_style: Option<TuiStyle>,
+    /// The number of lines in each block of the document, in order. They add up to the
+    /// number of lines in `editor_text_lines`.
+    block_line_counts: Vec<usize>,
+    highlighted_lines: StyleUSSpanLines,
+}
+
+impl MdIncrementalHighlighter {
+    /// Same as [try_parse_and_highlight()](super::try_parse_and_highlight), except that
+    /// only the blocks that were changed since the last call are parsed & highlighted.
+    pub fn try_parse_and_highlight(
+        &mut self,
+        editor_text_lines: &[US],
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    ) -> CommonResult<StyleUSSpanLines> {
+        if let Some(mut prev) = self.maybe_prev.take() {
+            if prev.maybe_current_box_computed_style == *maybe_current_box_computed_style
+            {
+                if let Some(parsed_line_count) = try_parse_changed_blocks(
+                    &mut prev,
+                    editor_text_lines,
+                    maybe_syntect_tuple,
+                ) {
+                    self.incremental_parse_count += 1;
+                    self.last_parsed_line_count = parsed_line_count;
+                    let it = prev.highlighted_lines.clone();
+                    self.maybe_prev = Some(prev);
+                    return Ok(it);
+                }
+            }
+        }
+
+        self.parse_all_blocks(
+            editor_text_lines,
+            maybe_current_box_computed_style,
+            maybe_syntect_tuple,
+        )
+    }
+
+    /// Forget the last result, so that the next call parses the whole document, eg:
+    /// after the theme was changed.
+    pub fn invalidate(&mut self) { self.maybe_prev = None; }
+
+    fn parse_all_blocks(
+        &mut self,
+        editor_text_lines: &[US],
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    ) -> CommonResult<StyleUSSpanLines> {
+        let editor_text_to_string = join_editor_text_lines(editor_text_lines);
+        let Ok((_remainder, document)) = parse_markdown(&editor_text_to_string) else {
+            return CommonError::new_error_result_with_only_type(
+                CommonErrorType::ParsingError,
+            );
+        };
+
+        self.full_parse_count += 1;
+        self.last_parsed_line_count = editor_text_lines.len();
+
+        let mut highlighted_lines = StyleUSSpanLines::default();
+        let mut maybe_block_line_counts = Some(vec![]);
+        for block in document.iter() {
+            let block_lines = StyleUSSpanLines::from_block(
+                block,
+                maybe_current_box_computed_style,
+                maybe_syntect_tuple,
+            );
+            // If a block can't be mapped back to the lines that it was parsed from, then
+            // the next edit can't be spliced in, and the whole document is parsed again.
+            if block.get_line_count() != Some(block_lines.len()) {
+                maybe_block_line_counts = None;
+            }
+            if let Some(it) = maybe_block_line_counts.as_mut() {
+                it.push(block_lines.len());
+            }
+            highlighted_lines.inner.extend(block_lines.inner);
+        }
+
+        self.maybe_prev = maybe_block_line_counts
+            .filter(|_| highlighted_lines.len() == editor_text_lines.len())
+            .map(|block_line_counts| PrevHighlight {
+                editor_text_lines: editor_text_lines.to_vec(),
+                maybe_current_box_computed_style: *maybe_current_box_computed_style,
+                block_line_counts,
+                highlighted_lines: highlighted_lines.clone(),
+            });
+
+        Ok(highlighted_lines)
+    }
+}
+
+/// Parse & highlight the blocks around the lines that are different in
+/// `editor_text_lines` and `prev`, and splice them into `prev`. Returns the number of
+/// lines that were parsed, or [None] (w/out changing `prev`) if the whole document has to
+/// be parsed again.
+fn try_parse_changed_blocks(
+    prev: &mut PrevHighlight,
+    editor_text_lines: &[US],
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+) -> Option<usize> {
+    let old_lines = &prev.editor_text_lines;
+
+    // The changed lines are `edit_start..old_edit_end` in the old lines, and
+    // `edit_start..new_edit_end` in the new ones.
+    let prefix_len = old_lines
+        .iter()
+        .zip(editor_text_lines)
+        .take_while(|(old, new)| old == new)
+        .count();
+    if prefix_len == old_lines.len() && prefix_len == editor_text_lines.len() {
+        return Some(0);
+    }
+    let suffix_len = old_lines[prefix_len..]
+        .iter()
+        .rev()
+        .zip(editor_text_lines[prefix_len..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let edit_start = prefix_len;
+    let old_edit_end = old_lines.len() - suffix_len;
+    let new_edit_end = editor_text_lines.len() - suffix_len;
+
+    let is_fence_or_footnote = |line: &US| {
+        line.string
+            .trim_start()
+            .starts_with(CODE_BLOCK_START_PARTIAL)
+            || line.string.contains(FOOTNOTE_REF_START)
+    };
+    if old_lines[edit_start..old_edit_end]
+        .iter()
+        .chain(&editor_text_lines[edit_start..new_edit_end])
+        .any(is_fence_or_footnote)
+    {
+        return None;
+    }
+
+    // The row that each block starts at, followed by the number of lines.
+    let block_count = prev.block_line_counts.len();
+    let block_starts = std::iter::once(0)
+        .chain(prev.block_line_counts.iter().scan(0, |acc, it| {
+            *acc += it;
+            Some(*acc)
+        }))
+        .collect::<Vec<_>>();
+
+    // The block that has the first changed line, or the last block, if lines were added
+    // to the end.
+    let first_changed_block = block_starts[1..]
+        .iter()
+        .position(|block_end| edit_start < *block_end)
+        .unwrap_or(block_count.checked_sub(1)?);
+    // Also parse the block before it. The first block isn't re-used, since it may be
+    // front matter.
+    let region_start_block = first_changed_block.checked_sub(1).filter(|it| *it > 0)?;
+    let region_start_row = block_starts[region_start_block];
+    // The first block that starts after the changed lines.
+    let first_unchanged_block = block_starts[..block_count]
+        .iter()
+        .position(|block_start| *block_start >= old_edit_end)
+        .unwrap_or(block_count);
+    let convert_old_row_to_new_row = |row: usize| row - old_edit_end + new_edit_end;
+
+    // Parse up to the end of a window of the blocks after the edit, which is grown until a
+    // block in it starts at the same place as before the edit.
+    let maybe_current_box_computed_style = &prev.maybe_current_box_computed_style;
+    let mut window_end_block = (first_unchanged_block + 1).min(block_count);
+    let (new_highlighted_lines, new_block_line_counts, region_end_block, window_end_row) = loop {
+        let window_end_row = convert_old_row_to_new_row(block_starts[window_end_block]);
+        let window_text =
+            join_editor_text_lines(&editor_text_lines[region_start_row..window_end_row]);
+        if window_text.contains(FOOTNOTE_REF_START) {
+            return None;
+        }
+
+        let (remainder, blocks) = parse_markdown_blocks(&window_text).ok()?;
+        if !remainder.is_empty() {
+            return None;
+        }
+
+        let mut row = region_start_row;
+        let mut new_block_line_counts = vec![];
+        let mut maybe_region_end_block = None;
+        for block in blocks.iter() {
+            let line_count = block.get_line_count()?;
+            row += line_count;
+            new_block_line_counts.push(line_count);
+            if row < new_edit_end || row >= window_end_row {
+                continue;
+            }
+            let old_row = row - new_edit_end + old_edit_end;
+            if let Ok(old_block) = block_starts.binary_search(&old_row) {
+                if old_block >= first_unchanged_block {
+                    maybe_region_end_block = Some(old_block);
+                    break;
+                }
+            }
+        }
+
+        let region_end_block = match maybe_region_end_block {
+            Some(it) => it,
+            // Parsed to the end of the document.
+            None if window_end_block == block_count && row == window_end_row => {
+                block_count
+            }
+            None if window_end_block == block_count => return None,
+            None => {
+                let window_block_count = window_end_block - first_unchanged_block;
+                window_end_block =
+                    (window_end_block + window_block_count).min(block_count);
+                continue;
+            }
+        };
+
+        let mut new_highlighted_lines = StyleUSSpanLines::default();
+        for (block, line_count) in blocks.iter().zip(&new_block_line_counts) {
+            let block_lines = StyleUSSpanLines::from_block(
+                block,
+                maybe_current_box_computed_style,
+                maybe_syntect_tuple,
+            );
+            if block_lines.len() != *line_count {
+                return None;
+            }
+            new_highlighted_lines.inner.extend(block_lines.inner);
+        }
+
+        break (
+            new_highlighted_lines,
+            new_block_line_counts,
+            region_end_block,
+            window_end_row,
+        );
+    };
+
+    let region_end_row = block_starts[region_end_block];
+    prev.highlighted_lines.inner.splice(
+        region_start_row..region_end_row,
+        new_highlighted_lines.inner,
+    );
+    prev.block_line_counts
+        .splice(region_start_block..region_end_block, new_block_line_counts);
+    prev.editor_text_lines = editor_text_lines.to_vec();
+
+    Some(window_end_row - region_start_row)
+}
+
+fn join_editor_text_lines(editor_text_lines: &[US]) -> String {
+    let mut acc = String::new();
+    for line in editor_text_lines {
+        acc.push_str(&line.string);
+        acc.push('\n');
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::try_parse_and_highlight;
+
+    const DOCUMENT: [&str; 17] = [
+        "# Title",
+        "",
+        "Some text.",
+        "- item one",
+        "- item two",
+        "",
+        "| a | b |",
+        "|---|---|",
+        "| 1 | 2 |",
+        "",
+        "> quote",
+        "lazy",
+        "",
+        "```rs",
+        "let x = 1;",
+        "```",
+        "last line",
+    ];
+
+    /// Highlight the `lines` w/ the `highlighter`, and check that they are the same as
+    /// when the whole document is parsed.
+    fn assert_same_as_full_parse(
+        highlighter: &mut MdIncrementalHighlighter,
+        lines: &[String],
+    ) {
+        let lines = lines
+            .iter()
+            .map(|it| US::from(it.as_str()))
+            .collect::<Vec<_>>();
+        let expected = try_parse_and_highlight(&lines, &None, None).unwrap();
+        let actual = highlighter
+            .try_parse_and_highlight(&lines, &None, None)
+            .unwrap();
+        assert_eq2!(actual, expected);
+    }
+
+    fn to_lines(it: &[&str]) -> Vec<String> {
+        it.iter().map(|it| it.to_string()).collect()
+    }
+
+    #[test]
+    fn test_edits_match_full_parse() {
+        let mut highlighter = MdIncrementalHighlighter::default();
+        let mut lines = to_lines(&DOCUMENT);
+        assert_same_as_full_parse(&mut highlighter, &lines);
+        assert_eq2!(highlighter.full_parse_count, 1);
+
+        let edits: Vec<fn(&mut Vec<String>)> = vec![
+            // Edit a line of text.
+            |it: &mut Vec<String>| it[2] = "Some **bold** text.".to_string(),
+            // Add a row to the table.
+            |it: &mut Vec<String>| it.insert(9, "| 3 | 4 |".to_string()),
+            // Break the separator row, which turns the table into text.
+            |it: &mut Vec<String>| it[7] = "not a separator".to_string(),
+            // And back into a table.
+            |it: &mut Vec<String>| it[7] = "|:--|--:|".to_string(),
+            // Add a lazy continuation line to the quote.
+            |it: &mut Vec<String>| it.insert(13, "more lazy".to_string()),
+            // Add an item to the list.
+            |it: &mut Vec<String>| it.insert(5, "- item three".to_string()),
+            // Remove some lines.
+            |it: &mut Vec<String>| {
+                it.drain(3..6);
+            },
+            // Edit a line in the code block.
+            |it: &mut Vec<String>| {
+                let index = it.iter().position(|it| it == "let x = 1;").unwrap();
+                it[index] = "let y = 2;".to_string();
+            },
+            // Add lines to the end, and remove them.
+            |it: &mut Vec<String>| it.extend(["".to_string(), "## End".to_string()]),
+            |it: &mut Vec<String>| it.truncate(it.len() - 2),
+            // Nothing is changed.
+            |_: &mut Vec<String>| {},
+        ];
+
+        for edit in edits {
+            edit(&mut lines);
+            assert_same_as_full_parse(&mut highlighter, &lines);
+        }
+        assert_eq2!(highlighter.full_parse_count, 1);
+    }
+
+    #[test]
+    fn test_code_block_fence_parses_whole_document() {
+        let mut highlighter = MdIncrementalHighlighter::default();
+        let mut lines = to_lines(&DOCUMENT);
+        assert_same_as_full_parse(&mut highlighter, &lines);
+
+        // Removing the closing fence turns the code block into text.
+        lines.remove(15);
+        assert_same_as_full_parse(&mut highlighter, &lines);
+        assert_eq2!(highlighter.full_parse_count, 2);
+
+        // Opening a fence before the end of the document turns it into a code block.
+        lines.insert(3, "```".to_string());
+        assert_same_as_full_parse(&mut highlighter, &lines);
+        assert_eq2!(highlighter.full_parse_count, 3);
+        assert_eq2!(highlighter.incremental_parse_count, 0);
+    }
+
+    #[test]
+    fn test_footnote_parses_whole_document() {
+        let mut highlighter = MdIncrementalHighlighter::default();
+        let mut lines = to_lines(&DOCUMENT);
+        lines.insert(3, "Ref[^1].".to_string());
+        assert_same_as_full_parse(&mut highlighter, &lines);
+
+        // Defining the footnote changes how the reference is highlighted.
+        lines.push("[^1]: The footnote.".to_string());
+        assert_same_as_full_parse(&mut highlighter, &lines);
+        assert_eq2!(highlighter.full_parse_count, 2);
+
+        // An edit near the reference can't be contained either.
+        lines[4] = "- item 1".to_string();
+        assert_same_as_full_parse(&mut highlighter, &lines);
+        assert_eq2!(highlighter.full_parse_count, 3);
+    }
+
+    #[test]
+    fn test_changed_style_parses_whole_document() {
+        let mut highlighter = MdIncrementalHighlighter::default();
+        let lines = DOCUMENT.iter().map(|it| US::from(*it)).collect::<Vec<_>>();
+        let style = Some(TuiStyle {
+            bold: true,
+            ..Default::default()
+        });
+        highlighter
+            .try_parse_and_highlight(&lines, &None, None)
+            .unwrap();
+        let it = highlighter
+            .try_parse_and_highlight(&lines, &style, None)
+            .unwrap();
+        assert_eq2!(it, try_parse_and_highlight(&lines, &style, None).unwrap());
+        assert_eq2!(highlighter.full_parse_count, 2);
+
+        highlighter.invalidate();
+        highlighter
+            .try_parse_and_highlight(&lines, &style, None)
+            .unwrap();
+        assert_eq2!(highlighter.full_parse_count, 3);
+    }
+
+    /// On a jumbo document, an edit only parses a few lines, instead of all of them.
+    #[test]
+    fn test_jumbo_document_only_parses_changed_blocks() {
+        let mut highlighter = MdIncrementalHighlighter::default();
+        let mut lines = (0..1_000)
+            .flat_map(|it| {
+                DOCUMENT.iter().map(move |line| match *line {
+                    "Some text." => format!("Some text {it}."),
+                    _ => line.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_same_as_full_parse(&mut highlighter, &lines);
+        assert_eq2!(highlighter.last_parsed_line_count, 17_000);
+
+        let index = lines.iter().position(|it| it == "Some text 500.").unwrap();
+        lines[index] = "Some *edited* text 500.".to_string();
+        assert_same_as_full_parse(&mut highlighter, &lines);
+        assert_eq2!(highlighter.full_parse_count, 1);
+        assert!(highlighter.last_parsed_line_count < 10);
+
+        lines.insert(index + 1, "New text.".to_string());
+        assert_same_as_full_parse(&mut highlighter, &lines);
+        assert_eq2!(highlighter.full_parse_count, 1);
+        assert!(highlighter.last_parsed_line_count < 10);
+    }
+}
+
+/// Run w/ `cargo bench -p r3bl_tui bench_md_incremental_highlighter`, to compare an edit
+/// to a jumbo document when only the changed blocks are parsed, and when the whole
+/// document is parsed.
+#[cfg(test)]
+mod bench_md_incremental_highlighter {
+    extern crate test;
+    use test::Bencher;
+
+    use super::*;
+    use crate::try_parse_and_highlight;
+
+    const BLOCK: [&str; 9] = [
+        "# Title",
+        "",
+        "Some *text* w/ `code`.",
+        "- item one",
+        "- [ ] item two",
+        "",
+        "```rs",
+        "let x = 1;",
+        "```",
+    ];
+
+    fn make_jumbo_document() -> Vec<US> {
+        (0..200)
+            .flat_map(|_| BLOCK.iter().chain(&[""]).map(|it| US::from(*it)))
+            .collect()
+    }
+
+    /// Each iteration changes a line in the middle of the document back and forth.
+    fn bench_edit(bencher: &mut Bencher, is_incremental: bool) {
+        let mut lines = make_jumbo_document();
+        let index = lines.len() / 2 + 2;
+        let edits = [US::from("Some **edited** text."), lines[index].clone()];
+        let mut highlighter = MdIncrementalHighlighter::default();
+        highlighter
+            .try_parse_and_highlight(&lines, &None, None)
+            .unwrap();
+
+        let mut count = 0;
+        bencher.iter(|| {
+            count += 1;
+            lines[index] = edits[count % 2].clone();
+            if is_incremental {
+                highlighter.try_parse_and_highlight(&lines, &None, None)
+            } else {
+                try_parse_and_highlight(&lines, &None, None)
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_edit_incremental(bencher: &mut Bencher) { bench_edit(bencher, true); }
+
+    #[bench]
+    fn bench_edit_full(bencher: &mut Bencher) { bench_edit(bencher, false); }
+}
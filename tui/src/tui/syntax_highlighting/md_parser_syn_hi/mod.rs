@@ -25,7 +25,9 @@
 // Attach.
 pub mod md_parser_stylesheet;
 pub mod md_parser_syn_hi_impl;
+pub mod md_parser_syn_hi_incremental;
 
 // Re-export.
 pub use md_parser_stylesheet::*;
 pub use md_parser_syn_hi_impl::*;
+pub use md_parser_syn_hi_incremental::*;
@@ -0,0 +1,364 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Compose several [RenderPipeline]s into one, each moved by its own offset, so that
+//! content that has already been rendered can be positioned freely (outside of the
+//! [crate::FlexBox] layout). Here's an example.
+//!
+//! ```rust
+//! use r3bl_core::{position, size};
+//! use r3bl_tui::*;
+//!
+//! let sidebar = render_pipeline!(@new ZOrder::Normal =>
+//!     RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+//!     RenderOp::PaintTextWithAttributes("menu".into(), None)
+//! );
+//! let content = sidebar.clone();
+//!
+//! let pipeline = compose_render_pipelines(
+//!     vec![
+//!         (sidebar, RenderPipelineOffset::default()),
+//!         (content, RenderPipelineOffset::new(/* row */ 1, /* col */ -2)),
+//!     ],
+//!     size!(col_count: 10, row_count: 5),
+//! );
+//! assert_eq!(
+//!     pipeline.get_all_render_op_in(ZOrder::Normal).unwrap()[2..],
+//!     [
+//!         RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 1)),
+//!         RenderOp::PaintTextWithAttributes("nu".into(), None),
+//!     ]
+//! );
+//! ```
+//!
+//! The rules are:
+//! 1. Each [RenderOps] is kept in the same [ZOrder]. Within a [ZOrder], the pipelines are
+//!    painted in the order they are given, so a later one is painted on top.
+//! 2. Text that ends up (partially) outside of the `clip_size` is clipped. The cursor is
+//!    moved (w/ [RenderOp::MoveCursorPositionAbs]) before each text that is painted, so
+//!    the result doesn't depend on where the cursor was left by other pipelines.
+//! 3. A wide grapheme (eg: an emoji) that is cut in half by the edge is replaced w/ a
+//!    space for the half that is visible.
+//! 4. The other [RenderOp]s (eg: colors) are kept as is. Note that
+//!    [RenderOp::ClearScreen] clears the whole screen, regardless of the offset.
+
+use r3bl_core::{ch, position, ChUnit, Position, Size, UnicodeString};
+
+use super::{RenderOp, RenderOps, RenderPipeline};
+use crate::ZOrder;
+
+/// How far to move the content of a [RenderPipeline], in rows and columns. Negative
+/// values move it up or to the left.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderPipelineOffset {
+    pub row_offset: isize,
+    pub col_offset: isize,
+}
+
+impl RenderPipelineOffset {
+    pub fn new(row_offset: isize, col_offset: isize) -> Self {
+        Self {
+            row_offset,
+            col_offset,
+        }
+    }
+}
+
+impl From<Position> for RenderPipelineOffset {
+    fn from(it: Position) -> Self {
+        Self::new(
+            ch!(@to_usize it.row_index) as isize,
+            ch!(@to_usize it.col_index) as isize,
+        )
+    }
+}
+
+/// Merge the given pipelines into one. See the [module docs](self) for details.
+pub fn compose_render_pipelines(
+    pipelines: impl IntoIterator<Item = (RenderPipeline, RenderPipelineOffset)>,
+    clip_size: Size,
+) -> RenderPipeline {
+    let mut it = RenderPipeline::default();
+    for (pipeline, offset) in pipelines {
+        it.join_into(pipeline.translate_and_clip(offset, clip_size));
+    }
+    it
+}
+
+impl RenderPipeline {
+    /// Returns a copy of this pipeline w/ its content moved by the `offset`, and clipped
+    /// to `clip_size`. See [compose_render_pipelines] for details.
+    pub fn translate_and_clip(
+        &self,
+        offset: RenderPipelineOffset,
+        clip_size: Size,
+    ) -> RenderPipeline {
+        let mut it = RenderPipeline::default();
+
+        // The cursor is tracked in the same order that the pipeline is painted in.
+        let mut cursor = (0_isize, 0_isize);
+        for z_order in ZOrder::get_render_order().iter() {
+            let Some(render_ops_vec) = self.get(z_order) else {
+                continue;
+            };
+            for render_ops in render_ops_vec.iter() {
+                let mut translated = RenderOps::default();
+                for render_op in render_ops.iter() {
+                    translate_render_op(
+                        render_op,
+                        &mut cursor,
+                        offset,
+                        clip_size,
+                        &mut translated,
+                    );
+                }
+                if !translated.list.is_empty() {
+                    it.push(*z_order, translated);
+                }
+            }
+        }
+
+        it
+    }
+}
+
+fn get_row_col(pos: Position) -> (isize, isize) {
+    (
+        ch!(@to_usize pos.row_index) as isize,
+        ch!(@to_usize pos.col_index) as isize,
+    )
+}
+
+fn translate_render_op(
+    render_op: &RenderOp,
+    cursor: &mut (isize, isize),
+    offset: RenderPipelineOffset,
+    clip_size: Size,
+    acc: &mut RenderOps,
+) {
+    match render_op {
+        RenderOp::MoveCursorPositionAbs(pos) => *cursor = get_row_col(*pos),
+        RenderOp::MoveCursorPositionRelTo(origin, relative) => {
+            *cursor = get_row_col(*origin + *relative);
+        }
+        RenderOp::PaintTextWithAttributes(text, maybe_style)
+        | RenderOp::CompositorNoClipTruncPaintTextWithAttributes(text, maybe_style) => {
+            let text = UnicodeString::from(text);
+            let (row_index, col_index) =
+                (cursor.0 + offset.row_offset, cursor.1 + offset.col_offset);
+            cursor.1 += ch!(@to_usize text.display_width) as isize;
+
+            let Some((clipped_col_index, clipped_text)) =
+                clip_text_to_row(&text, col_index, ch!(@to_usize clip_size.col_count))
+            else {
+                return;
+            };
+            let row_count = ch!(@to_usize clip_size.row_count) as isize;
+            if row_index < 0 || row_index >= row_count {
+                return;
+            }
+
+            acc.push(RenderOp::MoveCursorPositionAbs(position!(
+                col_index: ch!(clipped_col_index),
+                row_index: ch!(row_index as usize)
+            )));
+            acc.push(match render_op {
+                RenderOp::PaintTextWithAttributes(..) => {
+                    RenderOp::PaintTextWithAttributes(clipped_text, *maybe_style)
+                }
+                _ => RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                    clipped_text,
+                    *maybe_style,
+                ),
+            });
+        }
+        RenderOp::Noop => {}
+        _ => acc.push(render_op.clone()),
+    }
+}
+
+/// Clip the `text` that starts at `col_index` (which can be negative) so that it fits
+/// in `[0, col_count)`. Returns the column where the clipped text starts, and the text,
+/// or [None] if none of it is visible. Wide graphemes that are cut by an edge are
+/// replaced w/ spaces.
+fn clip_text_to_row(
+    text: &UnicodeString,
+    col_index: isize,
+    col_count: usize,
+) -> Option<(usize, String)> {
+    let width = ch!(@to_usize text.display_width) as isize;
+    let col_count = col_count as isize;
+    let start = col_index.max(0);
+    let end = (col_index + width).min(col_count);
+    if start >= end {
+        return None;
+    }
+
+    // Cut from the start.
+    let skip_col_count = ch!((start - col_index) as usize);
+    let remaining = UnicodeString::from(text.truncate_start_by_n_col(skip_col_count));
+    let padding = text.display_width - skip_col_count - remaining.display_width;
+    let mut it = " ".repeat(ch!(@to_usize padding));
+
+    // Cut from the end.
+    let avail_col_count: ChUnit = ch!((end - start) as usize) - padding;
+    let kept = remaining.truncate_end_to_fit_width(avail_col_count);
+    it.push_str(kept);
+    let padding = avail_col_count - UnicodeString::from(kept).display_width;
+    it.push_str(&" ".repeat(ch!(@to_usize padding)));
+
+    Some((start as usize, it))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+    use crate::{render_pipeline, PixelChar};
+
+    fn paint_at(
+        z_order: ZOrder,
+        col_index: u16,
+        row_index: u16,
+        text: &str,
+    ) -> RenderPipeline {
+        render_pipeline!(@new z_order =>
+            RenderOp::MoveCursorPositionAbs(position!(col_index: col_index, row_index: row_index)),
+            RenderOp::PaintTextWithAttributes(text.into(), None)
+        )
+    }
+
+    fn get_row_text(
+        pipeline: &RenderPipeline,
+        clip_size: Size,
+        row_index: usize,
+    ) -> String {
+        let offscreen_buffer = pipeline.convert(clip_size);
+        offscreen_buffer.buffer[row_index]
+            .iter()
+            .map(|pixel_char| match pixel_char {
+                PixelChar::PlainText { content, .. } => content.string.clone(),
+                PixelChar::Void => "".into(),
+                PixelChar::Spacer => ".".into(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clip_text_to_row() {
+        let text = UnicodeString::from("a😀b");
+        // Fully visible.
+        assert_eq2!(clip_text_to_row(&text, 1, 10), Some((1, "a😀b".into())));
+        // Cut at the start, and in the middle of the emoji.
+        assert_eq2!(clip_text_to_row(&text, -1, 10), Some((0, "😀b".into())));
+        assert_eq2!(clip_text_to_row(&text, -2, 10), Some((0, " b".into())));
+        // Cut at the end, and in the middle of the emoji.
+        assert_eq2!(clip_text_to_row(&text, 7, 10), Some((7, "a😀".into())));
+        assert_eq2!(clip_text_to_row(&text, 8, 10), Some((8, "a ".into())));
+        // Fully clipped.
+        assert_eq2!(clip_text_to_row(&text, -4, 10), None);
+        assert_eq2!(clip_text_to_row(&text, 10, 10), None);
+    }
+
+    #[test]
+    fn test_compose_w_offsets_and_clipping() {
+        let clip_size = size!(col_count: 6, row_count: 2);
+        let pipeline = compose_render_pipelines(
+            vec![
+                (
+                    paint_at(ZOrder::Normal, 0, 0, "hello"),
+                    RenderPipelineOffset::new(1, -2),
+                ),
+                (
+                    paint_at(ZOrder::Normal, 0, 0, "😀ab"),
+                    RenderPipelineOffset::new(0, 5),
+                ),
+                // Off screen.
+                (
+                    paint_at(ZOrder::Normal, 0, 0, "gone"),
+                    RenderPipelineOffset::new(2, 0),
+                ),
+                (
+                    paint_at(ZOrder::Normal, 0, 0, "gone"),
+                    RenderPipelineOffset::new(0, -4),
+                ),
+            ],
+            clip_size,
+        );
+
+        assert_eq2!(get_row_text(&pipeline, clip_size, 1), "llo...");
+        assert_eq2!(pipeline.get(&ZOrder::Normal).unwrap().len(), 2);
+
+        // Only the left half of the emoji is visible, so it is replaced w/ a space.
+        assert_eq2!(
+            pipeline.get(&ZOrder::Normal).unwrap()[1].list,
+            vec![
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 5, row_index: 0)),
+                RenderOp::PaintTextWithAttributes(" ".into(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compose_preserves_z_order() {
+        let clip_size = size!(col_count: 4, row_count: 1);
+
+        // The 1st pipeline is on top (in the glass layer), even though the 2nd one is
+        // painted after it.
+        let top = paint_at(ZOrder::Glass, 0, 0, "top");
+        let bottom = paint_at(ZOrder::Normal, 0, 0, "bottom");
+        let pipeline = compose_render_pipelines(
+            vec![
+                (top, RenderPipelineOffset::new(0, 1)),
+                (bottom, RenderPipelineOffset::default()),
+            ],
+            clip_size,
+        );
+        assert_eq2!(get_row_text(&pipeline, clip_size, 0), "btop");
+
+        // In the same layer, the one that is painted last is on top.
+        let pipeline = compose_render_pipelines(
+            vec![
+                (
+                    paint_at(ZOrder::Normal, 0, 0, "abcd"),
+                    RenderPipelineOffset::default(),
+                ),
+                (
+                    paint_at(ZOrder::Normal, 0, 0, "xy"),
+                    RenderPipelineOffset::new(0, 1),
+                ),
+            ],
+            clip_size,
+        );
+        assert_eq2!(get_row_text(&pipeline, clip_size, 0), "axyd");
+
+        // The cursor is tracked across the render ops, and relative positions are
+        // translated too.
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionRelTo(
+                position!(col_index: 1, row_index: 0),
+                position!(col_index: 0, row_index: 0)
+            ),
+            RenderOp::PaintTextWithAttributes("a".into(), None),
+            RenderOp::PaintTextWithAttributes("b".into(), None)
+        );
+        let pipeline =
+            pipeline.translate_and_clip(RenderPipelineOffset::new(0, 1), clip_size);
+        assert_eq2!(get_row_text(&pipeline, clip_size, 0), "..ab");
+    }
+}
@@ -68,6 +68,13 @@ impl DebugFormatRenderOp for CrosstermDebugFormatRenderOp {
                 RenderOp::PaintTextWithAttributes(text, maybe_style) => {
                     format_print_text("PrintTextWithAttributes", text, maybe_style)
                 }
+                RenderOp::SetHyperlink(hyperlink) =>
+                    format!("SetHyperlink({hyperlink:?})"),
+                RenderOp::ResetHyperlink => "ResetHyperlink".into(),
+                RenderOp::SetTitle(title) => format!("SetTitle({title:?})"),
+                RenderOp::ShowCursor(cursor_shape) =>
+                    format!("ShowCursor({cursor_shape:?})"),
+                RenderOp::HideCursor => "HideCursor".into(),
             }
         )
     }
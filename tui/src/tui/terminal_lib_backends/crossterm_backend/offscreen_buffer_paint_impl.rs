@@ -20,6 +20,7 @@ use r3bl_core::{call_if_true,
                 position,
                 ChUnit,
                 LockedOutputDevice,
+                Position,
                 Size,
                 TuiStyle,
                 UnicodeString,
@@ -28,6 +29,7 @@ use r3bl_core::{call_if_true,
 use crate::{render_ops,
             Flush as _,
             FlushKind,
+            Hyperlink,
             OffscreenBuffer,
             OffscreenBufferPaint,
             PixelChar,
@@ -124,6 +126,7 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
     ///   - make sure to flush at the
     ///     - end of line
     ///     - when style changes
+    ///     - when the hyperlink changes (which also paints its start or end)
     ///     - when switchover from ANSI <-> PLAIN happens
     fn render(&mut self, offscreen_buffer: &OffscreenBuffer) -> RenderOps {
         use render_helpers::*;
@@ -136,28 +139,43 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
 
             // For each pixel char in the line.
             for (pixel_char_index, pixel_char) in line.iter().enumerate() {
-                let (pixel_char_str, pixel_char_style): (&str, Option<TuiStyle>) =
-                    match pixel_char {
-                        PixelChar::Void => continue,
-                        PixelChar::Spacer => (SPACER, None),
-                        PixelChar::PlainText {
-                            content,
-                            maybe_style,
-                        } => (&content.string, *maybe_style),
-                    };
+                let (pixel_char_str, pixel_char_style, pixel_char_hyperlink): (
+                    &str,
+                    Option<TuiStyle>,
+                    Option<&Hyperlink>,
+                ) = match pixel_char {
+                    PixelChar::Void => continue,
+                    PixelChar::Spacer => (SPACER, None, None),
+                    PixelChar::PlainText {
+                        content,
+                        maybe_style,
+                        maybe_hyperlink,
+                    } => (&content.string, *maybe_style, maybe_hyperlink.as_ref()),
+                };
 
                 let is_style_same_as_prev =
                     render_helpers::style_eq(&pixel_char_style, &context.prev_style);
+                let is_hyperlink_same_as_prev =
+                    pixel_char_hyperlink == context.prev_hyperlink.as_ref();
                 let is_at_end_of_line = ch!(pixel_char_index) == (ch!(line.len() - 1));
                 let is_first_loop_iteration = row_index == 0 && pixel_char_index == 0;
 
                 // Deal w/: fg and bg colors | text attrib style | ANSI <-> PLAIN switchover.
-                if !is_style_same_as_prev {
+                if !is_style_same_as_prev || !is_hyperlink_same_as_prev {
                     // The style changed / render path has changed and something is already in the
                     // buffer, so flush it!
                     render_helpers::flush_all_buffers(&mut context);
                 }
 
+                // Deal w/: hyperlink start | end.
+                if !is_hyperlink_same_as_prev {
+                    context.render_ops.push(match pixel_char_hyperlink {
+                        Some(hyperlink) => RenderOp::SetHyperlink(hyperlink.clone()),
+                        None => RenderOp::ResetHyperlink,
+                    });
+                    context.prev_hyperlink = pixel_char_hyperlink.cloned();
+                }
+
                 // Deal w/: fg and bg colors | text attrib style
                 if is_first_loop_iteration || !is_style_same_as_prev {
                     context.render_ops.push(RenderOp::ResetColor);
@@ -191,9 +209,18 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
             render_helpers::flush_all_buffers(&mut context);
         }
 
+        // Don't leave a link open, since it would apply to whatever is painted next.
+        if context.prev_hyperlink.is_some() {
+            context.render_ops.push(RenderOp::ResetHyperlink);
+        }
+
         context.render_ops
     }
 
+    /// Changed pixel chars that are next to each other on the same row, and look the
+    /// same, are painted together (see [render_helpers::DiffRun]). The cursor is only
+    /// moved to the start of a run if it isn't already there, ie: if the run doesn't
+    /// start right after the previous one.
     fn render_diff(&mut self, diff_chunks: &PixelCharDiffChunks) -> RenderOps {
         call_if_true!(DEBUG_TUI_COMPOSITOR, {
             tracing::info!(
@@ -202,30 +229,51 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
             );
         });
 
+        use render_helpers::*;
+
         let mut it = render_ops!();
+        let mut maybe_run: Option<DiffRun> = None;
+        let mut maybe_cursor_pos: Option<Position> = None;
 
         for (position, pixel_char) in diff_chunks.iter() {
-            it.push(RenderOp::MoveCursorPositionAbs(*position));
-            it.push(RenderOp::ResetColor);
-            match pixel_char {
-                PixelChar::Void => continue,
-                PixelChar::Spacer => {
-                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
-                        SPACER.into(),
-                        None,
-                    ))
-                }
-                PixelChar::PlainText {
-                    content,
-                    maybe_style,
-                } => {
-                    it.push(RenderOp::ApplyColors(*maybe_style));
-                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
-                        content.string.clone(),
+            let (pixel_char_str, pixel_char_style, pixel_char_hyperlink, display_width) =
+                match pixel_char {
+                    PixelChar::Void => continue,
+                    PixelChar::Spacer => (SPACER, None, None, ch!(1)),
+                    PixelChar::PlainText {
+                        content,
+                        maybe_style,
+                        maybe_hyperlink,
+                    } => (
+                        content.string.as_str(),
                         *maybe_style,
-                    ))
+                        maybe_hyperlink.as_ref(),
+                        content.unicode_width,
+                    ),
+                };
+
+            // Extend the run if this pixel char is painted right after it, and looks the
+            // same. Otherwise paint the run, and start a new one.
+            if let Some(ref mut run) = maybe_run {
+                if run.can_extend(*position, &pixel_char_style, pixel_char_hyperlink) {
+                    run.extend(pixel_char_str, display_width);
+                    continue;
                 }
             }
+            if let Some(run) = maybe_run.take() {
+                run.push_render_ops(&mut it, &mut maybe_cursor_pos);
+            }
+            maybe_run = Some(DiffRun::new(
+                *position,
+                pixel_char_str,
+                pixel_char_style,
+                pixel_char_hyperlink,
+                display_width,
+            ));
+        }
+
+        if let Some(run) = maybe_run.take() {
+            run.push_render_ops(&mut it, &mut maybe_cursor_pos);
         }
 
         it
@@ -241,6 +289,7 @@ mod render_helpers {
         pub display_row_index: ChUnit,
         pub buffer_plain_text: String,
         pub prev_style: Option<TuiStyle>,
+        pub prev_hyperlink: Option<Hyperlink>,
         pub render_ops: RenderOps,
     }
 
@@ -252,6 +301,7 @@ mod render_helpers {
                 render_ops: render_ops!(),
                 display_row_index: ch!(0),
                 prev_style: None,
+                prev_hyperlink: None,
             }
         }
 
@@ -262,6 +312,80 @@ mod render_helpers {
         }
     }
 
+    /// Consecutive changed pixel chars on the same row that look the same (style and
+    /// hyperlink), which are painted together by [OffscreenBufferPaint::render_diff].
+    #[derive(Debug, Clone)]
+    pub struct DiffRun {
+        pub start_pos: Position,
+        pub end_col_index: ChUnit,
+        pub text: String,
+        pub maybe_style: Option<TuiStyle>,
+        pub maybe_hyperlink: Option<Hyperlink>,
+    }
+
+    impl DiffRun {
+        pub fn new(
+            start_pos: Position,
+            text: &str,
+            maybe_style: Option<TuiStyle>,
+            maybe_hyperlink: Option<&Hyperlink>,
+            display_width: ChUnit,
+        ) -> Self {
+            Self {
+                start_pos,
+                end_col_index: start_pos.col_index + display_width,
+                text: text.to_string(),
+                maybe_style,
+                maybe_hyperlink: maybe_hyperlink.cloned(),
+            }
+        }
+
+        pub fn can_extend(
+            &self,
+            pos: Position,
+            maybe_style: &Option<TuiStyle>,
+            maybe_hyperlink: Option<&Hyperlink>,
+        ) -> bool {
+            pos.row_index == self.start_pos.row_index
+                && pos.col_index == self.end_col_index
+                && style_eq(maybe_style, &self.maybe_style)
+                && maybe_hyperlink == self.maybe_hyperlink.as_ref()
+        }
+
+        pub fn extend(&mut self, text: &str, display_width: ChUnit) {
+            self.text.push_str(text);
+            self.end_col_index += display_width;
+        }
+
+        /// The cursor is only moved if it isn't already at the start of the run, ie: if
+        /// this run doesn't start right where the previous one ended.
+        pub fn push_render_ops(
+            self,
+            render_ops: &mut RenderOps,
+            maybe_cursor_pos: &mut Option<Position>,
+        ) {
+            if *maybe_cursor_pos != Some(self.start_pos) {
+                render_ops.push(RenderOp::MoveCursorPositionAbs(self.start_pos));
+            }
+            render_ops.push(RenderOp::ResetColor);
+            if let Some(ref hyperlink) = self.maybe_hyperlink {
+                render_ops.push(RenderOp::SetHyperlink(hyperlink.clone()));
+            }
+            render_ops.push(RenderOp::ApplyColors(self.maybe_style));
+            render_ops.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                self.text,
+                self.maybe_style,
+            ));
+            if self.maybe_hyperlink.is_some() {
+                render_ops.push(RenderOp::ResetHyperlink);
+            }
+            *maybe_cursor_pos = Some(position! {
+                col_index: self.end_col_index,
+                row_index: self.start_pos.row_index
+            });
+        }
+    }
+
     /// `this` is eq to `other` if they are both `Some` and their following fields are eq:
     /// - `color_fg`
     /// - `color_bg`
@@ -325,11 +449,20 @@ mod render_helpers {
 
 #[cfg(test)]
 mod tests {
-    use r3bl_core::{assert_eq2, color, size, ANSIBasicColor};
+    use r3bl_core::{assert_eq2,
+                    color,
+                    output_device_as_mut,
+                    size,
+                    ANSIBasicColor,
+                    OutputDevice};
     use r3bl_macro::tui_style;
+    use r3bl_test_fixtures::output_device_ext::OutputDeviceExt as _;
 
     use super::*;
-    use crate::render_pipeline_to_offscreen_buffer::print_text_with_attributes;
+    use crate::{render_pipeline,
+                render_pipeline_to_offscreen_buffer::print_text_with_attributes,
+                OffscreenBufferDiffResult,
+                ZOrder};
 
     /// Helper function to make an `OffscreenBuffer`.
     fn make_offscreen_buffer_plain_text() -> OffscreenBuffer {
@@ -441,4 +574,194 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_render_hyperlink() {
+        let hyperlink = Hyperlink::new("https://r3bl.com");
+        let window_size = size! { col_count: 6, row_count: 1};
+        let make_pipeline = |maybe_hyperlink: Option<Hyperlink>| {
+            let mut pipeline = render_pipeline!();
+            let mut render_ops = render_ops!(
+                @new
+                RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+                RenderOp::PaintTextWithAttributes("ab".into(), None),
+            );
+            if let Some(hyperlink) = maybe_hyperlink {
+                render_ops.push(RenderOp::SetHyperlink(hyperlink));
+            }
+            render_ops.push(RenderOp::PaintTextWithAttributes("cd".into(), None));
+            render_ops.push(RenderOp::ResetHyperlink);
+            pipeline.push(ZOrder::Normal, render_ops);
+            pipeline
+        };
+
+        // The link is carried by the pixel chars that were painted while it was set.
+        let my_offscreen_buffer =
+            make_pipeline(Some(hyperlink.clone())).convert(window_size);
+        assert_eq2!(
+            my_offscreen_buffer.buffer[0][2],
+            PixelChar::PlainText {
+                content: "c".into(),
+                maybe_style: None,
+                maybe_hyperlink: Some(hyperlink.clone()),
+            }
+        );
+        assert_eq2!(
+            my_offscreen_buffer.buffer[0][1],
+            PixelChar::PlainText {
+                content: "b".into(),
+                maybe_style: None,
+                maybe_hyperlink: None,
+            }
+        );
+
+        // The link starts and ends around the text that it covers.
+        let mut paint = OffscreenBufferPaintImplCrossterm {};
+        let render_ops = paint.render(&my_offscreen_buffer);
+        let render_ops = render_ops
+            .iter()
+            .filter(|it| {
+                matches!(
+                    it,
+                    RenderOp::SetHyperlink(_)
+                        | RenderOp::ResetHyperlink
+                        | RenderOp::CompositorNoClipTruncPaintTextWithAttributes(..)
+                )
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            render_ops,
+            vec![
+                RenderOp::CompositorNoClipTruncPaintTextWithAttributes("ab".into(), None),
+                RenderOp::SetHyperlink(hyperlink.clone()),
+                RenderOp::CompositorNoClipTruncPaintTextWithAttributes("cd".into(), None),
+                RenderOp::ResetHyperlink,
+                RenderOp::CompositorNoClipTruncPaintTextWithAttributes("  ".into(), None),
+            ]
+        );
+
+        // Adding a link to text that is otherwise unchanged is a diff.
+        let prev_offscreen_buffer = make_pipeline(None).convert(window_size);
+        let OffscreenBufferDiffResult::Comparable(diff_chunks) =
+            prev_offscreen_buffer.diff(&my_offscreen_buffer)
+        else {
+            panic!("Expected the offscreen buffers to be comparable");
+        };
+        assert_eq2!(diff_chunks.len(), 2);
+        let render_ops = paint.render_diff(&diff_chunks);
+        assert_eq2!(render_ops[2], RenderOp::SetHyperlink(hyperlink.clone()));
+        assert_eq2!(render_ops[5], RenderOp::ResetHyperlink);
+    }
+
+    /// What [OffscreenBufferPaint::render_diff] used to do, ie: move the cursor before
+    /// each changed pixel char, and paint it on its own.
+    fn render_diff_w_a_move_per_pixel_char(
+        diff_chunks: &PixelCharDiffChunks,
+    ) -> RenderOps {
+        let mut it = render_ops!();
+        for (position, pixel_char) in diff_chunks.iter() {
+            it.push(RenderOp::MoveCursorPositionAbs(*position));
+            it.push(RenderOp::ResetColor);
+            match pixel_char {
+                PixelChar::Void => continue,
+                PixelChar::Spacer => {
+                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                        SPACER.into(),
+                        None,
+                    ))
+                }
+                PixelChar::PlainText {
+                    content,
+                    maybe_style,
+                    ..
+                } => {
+                    it.push(RenderOp::ApplyColors(*maybe_style));
+                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                        content.string.clone(),
+                        *maybe_style,
+                    ));
+                }
+            }
+        }
+        it
+    }
+
+    fn get_painted_byte_count(render_ops: RenderOps, window_size: Size) -> usize {
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+        OffscreenBufferPaintImplCrossterm {}.paint_diff(
+            render_ops,
+            window_size,
+            output_device_as_mut!(output_device),
+            true,
+        );
+        stdout_mock.get_copy_of_buffer_as_string().len()
+    }
+
+    /// Compare the number of bytes that are painted when an editor scrolls down by one
+    /// line, w/ and w/out coalescing the changed pixel chars into runs.
+    #[test]
+    fn test_render_diff_of_editor_scroll_paints_fewer_bytes() {
+        let lines = [
+            "use std::collections::HashMap;",
+            "",
+            "/// Count the words in the text.",
+            "pub fn count_words(text: &str) -> usize {",
+            "    let mut counts = HashMap::new();",
+            "    for word in text.split_whitespace() {",
+            "        *counts.entry(word).or_insert(0) += 1;",
+            "    }",
+            "    counts.len()",
+            "}",
+            "",
+            "#[test]",
+        ];
+        let window_size = size! { col_count: 40, row_count: 10 };
+        let make_offscreen_buffer = |first_line_index: usize| {
+            let mut pipeline = render_pipeline!();
+            let mut render_ops = render_ops!();
+            for (row_index, line) in lines
+                .iter()
+                .skip(first_line_index)
+                .take(ch!(@to_usize window_size.row_count))
+                .enumerate()
+            {
+                render_ops.push(RenderOp::MoveCursorPositionAbs(
+                    position! { col_index: 0, row_index: row_index },
+                ));
+                render_ops
+                    .push(RenderOp::PaintTextWithAttributes(line.to_string(), None));
+            }
+            pipeline.push(ZOrder::Normal, render_ops);
+            pipeline.convert(window_size)
+        };
+
+        let OffscreenBufferDiffResult::Comparable(diff_chunks) =
+            make_offscreen_buffer(0).diff(&make_offscreen_buffer(1))
+        else {
+            panic!("Expected the offscreen buffers to be comparable");
+        };
+
+        let mut paint = OffscreenBufferPaintImplCrossterm {};
+        let render_ops = paint.render_diff(&diff_chunks);
+        let render_ops_before = render_diff_w_a_move_per_pixel_char(&diff_chunks);
+
+        // There is at most one cursor move per changed run, instead of one per changed
+        // pixel char.
+        let count_moves = |render_ops: &RenderOps| {
+            render_ops
+                .iter()
+                .filter(|it| matches!(it, RenderOp::MoveCursorPositionAbs(_)))
+                .count()
+        };
+        assert_eq2!(count_moves(&render_ops_before), diff_chunks.len());
+        assert!(count_moves(&render_ops) * 4 < diff_chunks.len());
+
+        let byte_count_before = get_painted_byte_count(render_ops_before, window_size);
+        let byte_count = get_painted_byte_count(render_ops, window_size);
+        assert!(
+            byte_count * 2 < byte_count_before,
+            "Painted {byte_count} bytes, instead of {byte_count_before} bytes"
+        );
+    }
 }
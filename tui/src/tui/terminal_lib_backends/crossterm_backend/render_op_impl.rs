@@ -18,8 +18,11 @@
 use std::borrow::Cow;
 
 use crossterm::{self,
-                cursor::{Hide, MoveTo, Show},
-                event::{DisableMouseCapture, EnableMouseCapture},
+                cursor::{Hide, MoveTo, SetCursorStyle, Show},
+                event::{DisableBracketedPaste,
+                        DisableMouseCapture,
+                        EnableBracketedPaste,
+                        EnableMouseCapture},
                 style::{Attribute,
                         Print,
                         ResetColor,
@@ -41,12 +44,23 @@ use r3bl_core::{call_if_true,
 use crate::{crossterm_color_converter::convert_from_tui_color_to_crossterm_color,
             disable_raw_mode_now,
             flush_now,
+            get_set_title_sequence,
+            hyperlink_support_global_static::{get_hyperlink_support, HyperlinkSupport},
             queue_render_op,
             sanitize_and_save_abs_position,
+            title_support_global_static::{get_title_support,
+                                          mark_title_as_saved,
+                                          take_is_title_saved,
+                                          TitleSupport},
+            CursorShape,
             Flush,
+            Hyperlink,
             PaintRenderOp,
             RenderOp,
-            RenderOpsLocalData};
+            RenderOpsLocalData,
+            HYPERLINK_RESET_SEQUENCE,
+            TITLE_RESTORE_SEQUENCE,
+            TITLE_SAVE_SEQUENCE};
 
 /// Struct representing the implementation of [RenderOp] for crossterm terminal backend.
 /// This empty struct is needed since the [Flush] trait needs to be implemented.
@@ -134,6 +148,24 @@ mod impl_trait_paint_render_op {
                     // buffer first, then that is diff'd and then painted via calls to
                     // CompositorNoClipTruncPaintTextWithAttributes.
                 }
+                RenderOp::SetHyperlink(hyperlink) => {
+                    RenderOpImplCrossterm::set_hyperlink(hyperlink, locked_output_device);
+                }
+                RenderOp::ResetHyperlink => {
+                    RenderOpImplCrossterm::reset_hyperlink(locked_output_device);
+                }
+                RenderOp::SetTitle(title) => {
+                    RenderOpImplCrossterm::set_title(title, locked_output_device);
+                }
+                RenderOp::ShowCursor(cursor_shape) => {
+                    RenderOpImplCrossterm::show_cursor(
+                        *cursor_shape,
+                        locked_output_device,
+                    );
+                }
+                RenderOp::HideCursor => {
+                    queue_render_op!(locked_output_device, "HideCursor", Hide);
+                }
             }
         }
     }
@@ -204,12 +236,22 @@ mod impl_self {
         ) {
             queue_render_op!(
                 locked_output_device,
-                "ExitRawMode -> Show, LeaveAlternateScreen, DisableMouseCapture",
+                "ExitRawMode -> SetCursorStyle(DefaultUserShape), Show, LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste",
+                SetCursorStyle::DefaultUserShape,
                 Show,
                 LeaveAlternateScreen,
-                DisableMouseCapture
+                DisableMouseCapture,
+                DisableBracketedPaste
             );
 
+            if take_is_title_saved() {
+                queue_render_op!(
+                    locked_output_device,
+                    "ExitRawMode -> RestoreTitle",
+                    Print(TITLE_RESTORE_SEQUENCE),
+                );
+            }
+
             flush_now!(locked_output_device, "ExitRawMode -> flush()");
 
             disable_raw_mode_now!(is_mock, "ExitRawMode -> disable_raw_mode()");
@@ -226,8 +268,9 @@ mod impl_self {
 
             queue_render_op!(
                 locked_output_device,
-                "EnterRawMode -> EnableMouseCapture, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide",
+                "EnterRawMode -> EnableMouseCapture, EnableBracketedPaste, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide",
                 EnableMouseCapture,
+                EnableBracketedPaste,
                 EnterAlternateScreen,
                 MoveTo(0,0),
                 Clear(ClearType::All),
@@ -268,6 +311,74 @@ mod impl_self {
             )
         }
 
+        /// Nothing is painted if the terminal doesn't support hyperlinks, so the text
+        /// that follows is just plain text.
+        pub fn set_hyperlink(
+            hyperlink: &Hyperlink,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            if let HyperlinkSupport::No = get_hyperlink_support() {
+                return;
+            }
+
+            queue_render_op!(
+                locked_output_device,
+                format!("SetHyperlink({})", hyperlink.uri),
+                Print(hyperlink.get_start_sequence()),
+            );
+        }
+
+        pub fn reset_hyperlink(locked_output_device: LockedOutputDevice<'_>) {
+            if let HyperlinkSupport::No = get_hyperlink_support() {
+                return;
+            }
+
+            queue_render_op!(
+                locked_output_device,
+                "ResetHyperlink",
+                Print(HYPERLINK_RESET_SEQUENCE),
+            );
+        }
+
+        pub fn show_cursor(
+            cursor_shape: CursorShape,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            let cursor_style = match cursor_shape {
+                CursorShape::SteadyBlock => SetCursorStyle::SteadyBlock,
+                CursorShape::SteadyBar => SetCursorStyle::SteadyBar,
+            };
+            queue_render_op!(
+                locked_output_device,
+                format!("ShowCursor({cursor_shape:?})"),
+                cursor_style,
+                Show,
+            );
+        }
+
+        /// The title that the terminal had before the first call to this is saved, so
+        /// that it can be restored by [RenderOp::ExitRawMode]. Nothing is painted if the
+        /// terminal doesn't support setting the title.
+        pub fn set_title(title: &str, locked_output_device: LockedOutputDevice<'_>) {
+            if let TitleSupport::No = get_title_support() {
+                return;
+            }
+
+            if mark_title_as_saved() {
+                queue_render_op!(
+                    locked_output_device,
+                    "SetTitle -> SaveTitle",
+                    Print(TITLE_SAVE_SEQUENCE),
+                );
+            }
+
+            queue_render_op!(
+                locked_output_device,
+                format!("SetTitle({title})"),
+                Print(get_set_title_sequence(title)),
+            );
+        }
+
         pub fn paint_text_with_attributes(
             text_arg: &String,
             maybe_style: &Option<TuiStyle>,
@@ -564,3 +675,112 @@ macro_rules! enable_raw_mode_now {
         }
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{output_device_as_mut, size, OutputDevice};
+    use r3bl_test_fixtures::output_device_ext::OutputDeviceExt as _;
+    use serial_test::serial;
+
+    use super::*;
+    use crate::{hyperlink_support_global_static::{clear_hyperlink_support,
+                                                  set_hyperlink_support},
+                render_ops,
+                title_support_global_static::{clear_title_support, set_title_support}};
+
+    fn paint_link(hyperlink: Hyperlink) -> String {
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+        let render_ops = render_ops!(
+            @new
+            RenderOp::SetHyperlink(hyperlink),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("r3bl".into(), None),
+            RenderOp::ResetHyperlink,
+        );
+        let mut skip_flush = false;
+        render_ops.execute_all(
+            &mut skip_flush,
+            size!(col_count: 10, row_count: 1),
+            output_device_as_mut!(output_device),
+            true,
+        );
+        stdout_mock.get_copy_of_buffer_as_string()
+    }
+
+    #[serial]
+    #[test]
+    fn test_hyperlink_is_painted_when_supported() {
+        set_hyperlink_support(HyperlinkSupport::Yes);
+
+        let output = paint_link(Hyperlink::new("https://r3bl.com"));
+        assert_eq!(
+            output,
+            "\x1b]8;;https://r3bl.com\x1b\\r3bl\x1b]8;;\x1b\\".to_string()
+        );
+
+        // Control characters can't end the sequence early, and the id can't add params.
+        let output =
+            paint_link(Hyperlink::new("https://r3bl.com\x1b\\").with_id("a;b:c\x07"));
+        assert!(output.starts_with("\x1b]8;id=abc;https://r3bl.com\\\x1b\\r3bl"));
+
+        clear_hyperlink_support();
+    }
+
+    #[serial]
+    #[test]
+    fn test_hyperlink_is_plain_text_when_not_supported() {
+        set_hyperlink_support(HyperlinkSupport::No);
+
+        let output = paint_link(Hyperlink::new("https://r3bl.com"));
+        assert_eq!(output, "r3bl".to_string());
+
+        clear_hyperlink_support();
+    }
+
+    fn paint_title_then_exit_raw_mode(titles: &[&str]) -> String {
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+        let mut render_ops = render_ops!();
+        for title in titles {
+            render_ops.list.push(RenderOp::SetTitle(title.to_string()));
+        }
+        render_ops.list.push(RenderOp::ExitRawMode);
+        let mut skip_flush = false;
+        render_ops.execute_all(
+            &mut skip_flush,
+            size!(col_count: 10, row_count: 1),
+            output_device_as_mut!(output_device),
+            true,
+        );
+        stdout_mock.get_copy_of_buffer_as_string()
+    }
+
+    #[serial]
+    #[test]
+    fn test_title_is_set_and_restored_when_supported() {
+        set_title_support(TitleSupport::Yes);
+
+        let output = paint_title_then_exit_raw_mode(&["r3bl", "edi\x07\x1b"]);
+        let expected_prefix =
+            format!("{TITLE_SAVE_SEQUENCE}\x1b]0;r3bl\x07\x1b]0;edi\x07");
+        assert!(output.starts_with(&expected_prefix));
+        assert!(output.ends_with(TITLE_RESTORE_SEQUENCE));
+
+        // The title is only restored once.
+        let output = paint_title_then_exit_raw_mode(&[]);
+        assert!(!output.contains(TITLE_RESTORE_SEQUENCE));
+
+        clear_title_support();
+    }
+
+    #[serial]
+    #[test]
+    fn test_title_is_not_set_when_not_supported() {
+        set_title_support(TitleSupport::No);
+
+        let output = paint_title_then_exit_raw_mode(&["r3bl"]);
+        assert!(!output.contains("\x1b]0;"));
+        assert!(!output.contains(TITLE_SAVE_SEQUENCE));
+        assert!(!output.contains(TITLE_RESTORE_SEQUENCE));
+
+        clear_title_support();
+    }
+}
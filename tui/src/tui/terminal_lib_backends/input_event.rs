@@ -17,7 +17,13 @@
 
 use std::fmt::{Display, Formatter};
 
-use crossterm::event::{Event::{self, FocusGained, FocusLost, Key, Mouse, Resize},
+use crossterm::event::{Event::{self,
+                               FocusGained,
+                               FocusLost,
+                               Key,
+                               Mouse,
+                               Paste,
+                               Resize},
                        KeyEvent,
                        MouseEvent};
 use r3bl_core::{size, Size};
@@ -27,12 +33,16 @@ use super::{KeyPress, MouseInput};
 
 /// Please see [KeyPress] for more information about handling keyboard input.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputEvent {
     Keyboard(KeyPress),
     Resize(Size),
     Mouse(MouseInput),
     Focus(FocusEvent),
+    /// Text that was pasted into the terminal, all at once. This is only reported while
+    /// bracketed paste mode is on (which [crate::RenderOp::EnterRawMode] turns on),
+    /// otherwise the terminal types out the text one key at a time.
+    Paste(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -96,7 +106,7 @@ pub(crate) mod converters {
                 Resize(cols, rows) => Ok((rows, cols).into()),
                 FocusGained => Ok(InputEvent::Focus(FocusEvent::Gained)),
                 FocusLost => Ok(InputEvent::Focus(FocusEvent::Lost)),
-                _ => Err(()),
+                Paste(text) => Ok(InputEvent::Paste(text)),
             }
         }
     }
@@ -55,6 +55,7 @@ pub enum TerminalLibBackend {
 pub const TERMINAL_LIB_BACKEND: TerminalLibBackend = TerminalLibBackend::Crossterm;
 
 // Attach source files.
+pub mod compose_render_pipelines;
 pub mod crossterm_backend;
 pub mod crossterm_color_converter;
 pub mod enhanced_keys;
@@ -75,6 +76,7 @@ pub mod termion_backend;
 pub mod z_order;
 
 // Re-export.
+pub use compose_render_pipelines::*;
 pub use crossterm_backend::*;
 pub use crossterm_color_converter::*;
 pub use enhanced_keys::*;
@@ -16,6 +16,7 @@
  */
 
 use std::{fmt::{self, Debug},
+          hash::{Hash, Hasher},
           ops::{Deref, DerefMut}};
 
 use r3bl_core::{ch,
@@ -28,10 +29,11 @@ use r3bl_core::{ch,
                 Position,
                 Size,
                 TuiColor,
-                TuiStyle};
+                TuiStyle,
+                SPACER};
 use serde::{Deserialize, Serialize};
 
-use super::{FlushKind, RenderOps};
+use super::{CursorShape, FlushKind, Hyperlink, RenderOps};
 use crate::List;
 
 /// Represents a grid of cells where the row/column index maps to the terminal screen.
@@ -54,8 +56,22 @@ pub struct OffscreenBuffer {
     pub my_pos: Position,
     pub my_fg_color: Option<TuiColor>,
     pub my_bg_color: Option<TuiColor>,
+    /// Set by [crate::RenderOp::SetHyperlink], and applied to the text that is painted
+    /// after it.
+    pub my_hyperlink: Option<Hyperlink>,
+    /// Set by [crate::RenderOp::SetTitle]. It is not part of the grid, so it is painted
+    /// separately (and only when it changes) by [crate::paint].
+    pub my_title: Option<String>,
+    /// Set by [crate::RenderOp::ShowCursor] to the position of the cursor when it was
+    /// used. It is not part of the grid, so it is painted separately by [crate::paint].
+    pub my_cursor: Option<(Position, CursorShape)>,
 }
 
+/// A deterministic hash of the content and styling of an [OffscreenBuffer]. Use
+/// [OffscreenBuffer::get_fingerprint] to compute it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OffscreenBufferFingerprint(pub u64);
+
 pub enum OffscreenBufferDiffResult {
     NotComparable,
     Comparable(PixelCharDiffChunks),
@@ -132,7 +148,142 @@ mod offscreen_buffer_impl {
                 my_pos: Default::default(),
                 my_fg_color: None,
                 my_bg_color: None,
+                my_hyperlink: None,
+                my_title: None,
+                my_cursor: None,
+            }
+        }
+
+        /// Compute a [OffscreenBufferFingerprint] of the window size, and the content and
+        /// styling of each pixel char. This is cheap enough to do for every frame, eg: to
+        /// check that a frame is unchanged in a test, or to skip sending an identical
+        /// frame to a remote terminal.
+        ///
+        /// Unlike the [Hash] impl, this only includes what is visible in the terminal. So
+        /// it ignores the state that is left over from painting (eg:
+        /// [my_pos](OffscreenBuffer::my_pos)), where each grapheme cluster came from
+        /// (eg: its byte offset), and style fields that don't change how a pixel char
+        /// looks (eg: its `id`). The value is the same across runs and platforms.
+        pub fn get_fingerprint(&self) -> OffscreenBufferFingerprint {
+            let mut hasher = FingerprintHasher::default();
+
+            self.window_size.col_count.value.hash(&mut hasher);
+            self.window_size.row_count.value.hash(&mut hasher);
+
+            for line in self.buffer.iter() {
+                // Lines may not have the same length.
+                line.len().hash(&mut hasher);
+                for pixel_char in line.iter() {
+                    match pixel_char {
+                        PixelChar::Void => 0_u8.hash(&mut hasher),
+                        PixelChar::Spacer => 1_u8.hash(&mut hasher),
+                        PixelChar::PlainText {
+                            content,
+                            maybe_style,
+                            maybe_hyperlink,
+                        } => {
+                            2_u8.hash(&mut hasher);
+                            maybe_hyperlink.hash(&mut hasher);
+                            content.string.hash(&mut hasher);
+                            maybe_style
+                                .map(|style| {
+                                    (
+                                        style.bold,
+                                        style.italic,
+                                        style.dim,
+                                        style.underline,
+                                        style.reverse,
+                                        style.hidden,
+                                        style.strikethrough,
+                                        style.color_fg,
+                                        style.color_bg,
+                                    )
+                                })
+                                .hash(&mut hasher);
+                        }
+                    }
+                }
+            }
+
+            OffscreenBufferFingerprint(hasher.finish())
+        }
+
+        /// The text in the grid, one line per row, which is handy for asserting what an
+        /// app paints in a test (w/out a terminal). Empty cells are spaces, and the
+        /// [PixelChar::Void] after a wide grapheme cluster (eg: "😃") is skipped, so each
+        /// line has the same display width as the window.
+        pub fn to_plain_text_snapshot(&self) -> String {
+            self.buffer
+                .iter()
+                .map(|line| {
+                    line.iter()
+                        .map(|pixel_char| match pixel_char {
+                            PixelChar::Void => "",
+                            PixelChar::Spacer => SPACER,
+                            PixelChar::PlainText { content, .. } => {
+                                content.string.as_str()
+                            }
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        /// Like [OffscreenBuffer::to_plain_text_snapshot], but the cells in each row are
+        /// split into runs that have the same fg and bg colors, eg:
+        /// `0: "hello" [fg: green, bg: blue] "  " [bg: blue] "   "`. The colors are
+        /// omitted if they aren't set.
+        pub fn to_styled_snapshot(&self) -> String {
+            let mut lines = vec![];
+
+            for (row_index, line) in self.buffer.iter().enumerate() {
+                // Each run is its text, and its fg and bg colors.
+                let mut runs: Vec<(String, Option<TuiColor>, Option<TuiColor>)> = vec![];
+                for pixel_char in line.iter() {
+                    let (text, maybe_fg, maybe_bg) = match pixel_char {
+                        PixelChar::Void => continue,
+                        PixelChar::Spacer => (SPACER, None, None),
+                        PixelChar::PlainText {
+                            content,
+                            maybe_style,
+                            ..
+                        } => (
+                            content.string.as_str(),
+                            maybe_style.and_then(|it| it.color_fg),
+                            maybe_style.and_then(|it| it.color_bg),
+                        ),
+                    };
+                    match runs.last_mut() {
+                        Some((run_text, run_fg, run_bg))
+                            if *run_fg == maybe_fg && *run_bg == maybe_bg =>
+                        {
+                            run_text.push_str(text);
+                        }
+                        _ => runs.push((text.to_string(), maybe_fg, maybe_bg)),
+                    }
+                }
+
+                let runs = runs
+                    .iter()
+                    .map(|(text, maybe_fg, maybe_bg)| {
+                        let colors = [("fg", maybe_fg), ("bg", maybe_bg)]
+                            .iter()
+                            .filter_map(|(name, maybe_color)| {
+                                maybe_color.map(|color| format!("{name}: {color:?}"))
+                            })
+                            .collect::<Vec<_>>();
+                        match colors.is_empty() {
+                            true => format!("{text:?}"),
+                            false => format!("{text:?} [{}]", colors.join(", ")),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                lines.push(format!("{row_index}: {}", runs.join(" ")));
             }
+
+            lines.join("\n")
         }
 
         // Make sure each line is full of empty chars.
@@ -158,6 +309,45 @@ mod offscreen_buffer_impl {
     }
 }
 
+/// 64 bit [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+/// hash, which is used by [OffscreenBuffer::get_fingerprint]. Unlike
+/// [std::collections::hash_map::DefaultHasher], its output is specified, so it does not
+/// change between Rust releases. Integers are written as little endian 64 bit values, so
+/// it does not change between platforms either.
+struct FingerprintHasher(u64);
+
+mod fingerprint_hasher_impl {
+    use super::*;
+
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    impl Default for FingerprintHasher {
+        fn default() -> Self { Self(FNV_OFFSET_BASIS) }
+    }
+
+    impl Hasher for FingerprintHasher {
+        fn finish(&self) -> u64 { self.0 }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 ^= *byte as u64;
+                self.0 = self.0.wrapping_mul(FNV_PRIME);
+            }
+        }
+
+        fn write_u16(&mut self, it: u16) { self.write_u64(it as u64) }
+
+        fn write_u32(&mut self, it: u32) { self.write_u64(it as u64) }
+
+        fn write_u64(&mut self, it: u64) { self.write(&it.to_le_bytes()) }
+
+        fn write_usize(&mut self, it: usize) { self.write_u64(it as u64) }
+
+        fn write_isize(&mut self, it: isize) { self.write_u64(it as u64) }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, size_of::SizeOf)]
 pub struct PixelCharLines {
     pub lines: Vec<PixelCharLine>,
@@ -381,6 +571,9 @@ pub enum PixelChar {
     PlainText {
         content: GraphemeClusterSegment,
         maybe_style: Option<TuiStyle>,
+        /// The link that this is part of (if any). It is included when diffing, so
+        /// changing only the link of some text causes it to be repainted.
+        maybe_hyperlink: Option<Hyperlink>,
     },
 }
 
@@ -415,6 +608,7 @@ mod pixel_char_impl {
                 PixelChar::PlainText {
                     content: character,
                     maybe_style,
+                    ..
                 } => {
                     let output = match maybe_style {
                         // Content + style.
@@ -488,10 +682,12 @@ mod tests {
         my_offscreen_buffer.buffer[0][0] = PixelChar::PlainText {
             content: GraphemeClusterSegment::from("a"),
             maybe_style: Some(tui_style! {color_bg: color!(@green) }),
+            maybe_hyperlink: None,
         };
         my_offscreen_buffer.buffer[1][9] = PixelChar::PlainText {
             content: GraphemeClusterSegment::from("z"),
             maybe_style: Some(tui_style! {color_bg: color!(@red) }),
+            maybe_hyperlink: None,
         };
         // println!("my_offscreen_buffer: \n{:#?}", my_offscreen_buffer);
         my_offscreen_buffer.clear();
@@ -502,4 +698,100 @@ mod tests {
         }
         // println!("my_offscreen_buffer: \n{:#?}", my_offscreen_buffer);
     }
+
+    #[test]
+    fn test_offscreen_buffer_fingerprint() {
+        let window_size = size! { col_count: 10, row_count: 2};
+        let make_buffer = |text: &str, style: TuiStyle| {
+            let mut it = OffscreenBuffer::new_with_capacity_initialized(window_size);
+            it.buffer[0][0] = PixelChar::PlainText {
+                content: GraphemeClusterSegment::from(text),
+                maybe_style: Some(style),
+                maybe_hyperlink: None,
+            };
+            it
+        };
+        let green = tui_style! {color_bg: color!(@green) };
+        let red = tui_style! {color_bg: color!(@red) };
+
+        // Stable across runs.
+        assert_eq2!(
+            OffscreenBuffer::new_with_capacity_initialized(window_size).get_fingerprint(),
+            OffscreenBufferFingerprint(14_408_609_831_467_224_101)
+        );
+
+        let buffer = make_buffer("a", green);
+        assert_eq2!(buffer.get_fingerprint(), buffer.clone().get_fingerprint());
+
+        // Same glyphs, different styling.
+        assert_ne!(
+            buffer.get_fingerprint(),
+            make_buffer("a", red).get_fingerprint()
+        );
+
+        // Different glyphs, same styling.
+        assert_ne!(
+            buffer.get_fingerprint(),
+            make_buffer("b", green).get_fingerprint()
+        );
+
+        // Different size, same content.
+        assert_ne!(
+            OffscreenBuffer::new_with_capacity_initialized(window_size).get_fingerprint(),
+            OffscreenBuffer::new_with_capacity_initialized(
+                size! { col_count: 2, row_count: 10}
+            )
+            .get_fingerprint()
+        );
+
+        // Incidental state is ignored.
+        let mut other_buffer =
+            make_buffer("a", tui_style! {id: 7 color_bg: color!(@green) });
+        other_buffer.my_pos = position!(col_index: 3, row_index: 1);
+        other_buffer.my_fg_color = Some(color!(@red));
+        if let PixelChar::PlainText { content, .. } = &mut other_buffer.buffer[0][0] {
+            content.byte_offset = 42;
+            content.logical_index = 7;
+        }
+        assert_eq2!(buffer.get_fingerprint(), other_buffer.get_fingerprint());
+    }
+
+    #[test]
+    fn test_offscreen_buffer_snapshots() {
+        let window_size = size! { col_count: 6, row_count: 2};
+        let mut my_offscreen_buffer =
+            OffscreenBuffer::new_with_capacity_initialized(window_size);
+        let style = tui_style! { color_fg: color!(@green) color_bg: color!(@blue) };
+        for (col_index, text) in ["h", "i"].iter().enumerate() {
+            my_offscreen_buffer.buffer[0][col_index] = PixelChar::PlainText {
+                content: GraphemeClusterSegment::from(*text),
+                maybe_style: Some(style),
+                maybe_hyperlink: None,
+            };
+        }
+        my_offscreen_buffer.buffer[1][1] = PixelChar::PlainText {
+            content: GraphemeClusterSegment::from("😃"),
+            maybe_style: Some(tui_style! { color_bg: color!(@red) }),
+            maybe_hyperlink: None,
+        };
+        my_offscreen_buffer.buffer[1][2] = PixelChar::Void;
+        my_offscreen_buffer.buffer[1][3] = PixelChar::PlainText {
+            content: GraphemeClusterSegment::from("!"),
+            maybe_style: None,
+            maybe_hyperlink: None,
+        };
+
+        assert_eq2!(
+            my_offscreen_buffer.to_plain_text_snapshot(),
+            "hi    \n 😃!  ".to_string()
+        );
+        assert_eq2!(
+            my_offscreen_buffer.to_styled_snapshot(),
+            [
+                r#"0: "hi" [fg: green, bg: blue] "    ""#,
+                r#"1: " " "😃" [bg: red] "!  ""#,
+            ]
+            .join("\n")
+        );
+    }
 }
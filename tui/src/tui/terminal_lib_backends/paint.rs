@@ -19,8 +19,9 @@ use std::fmt::Debug;
 
 use r3bl_core::{call_if_true, LockedOutputDevice, Position, Size};
 
-use super::{FlushKind, RenderOp, RenderOpsLocalData, RenderPipeline};
-use crate::{GlobalData,
+use super::{FlushKind, RenderOp, RenderOps, RenderOpsLocalData, RenderPipeline};
+use crate::{CursorShape,
+            GlobalData,
             OffscreenBuffer,
             OffscreenBufferDiffResult,
             OffscreenBufferPaint,
@@ -64,12 +65,34 @@ pub fn paint<S, AS>(
 
     let window_size = global_data.window_size;
 
-    let offscreen_buffer = pipeline.convert(window_size);
+    let maybe_profiling_start = global_data.get_render_profiling_start();
+    let mut offscreen_buffer = pipeline.convert(window_size);
+    // The overlays are part of the frame, so the buffer that is saved includes them.
+    global_data.render_overlays.paint(&mut offscreen_buffer);
+    if let (Some(start), Some(render_profiler)) = (
+        maybe_profiling_start,
+        global_data.maybe_render_profiler.as_mut(),
+    ) {
+        render_profiler.record_composite_duration(start.elapsed());
+    }
+
+    paint_title_if_changed(
+        &mut offscreen_buffer,
+        &maybe_saved_offscreen_buffer,
+        window_size,
+        locked_output_device,
+        is_mock,
+    );
+
+    let is_cursor_shown = maybe_saved_offscreen_buffer
+        .as_ref()
+        .is_some_and(|it| it.my_cursor.is_some());
 
     match maybe_saved_offscreen_buffer {
         None => {
             perform_full_paint(
                 &offscreen_buffer,
+                is_cursor_shown,
                 flush_kind,
                 window_size,
                 locked_output_device,
@@ -82,6 +105,7 @@ pub fn paint<S, AS>(
                 OffscreenBufferDiffResult::NotComparable => {
                     perform_full_paint(
                         &offscreen_buffer,
+                        is_cursor_shown,
                         flush_kind,
                         window_size,
                         locked_output_device,
@@ -91,6 +115,8 @@ pub fn paint<S, AS>(
                 OffscreenBufferDiffResult::Comparable(ref diff_chunks) => {
                     perform_diff_paint(
                         diff_chunks,
+                        offscreen_buffer.my_cursor,
+                        is_cursor_shown,
                         window_size,
                         locked_output_device,
                         is_mock,
@@ -102,8 +128,57 @@ pub fn paint<S, AS>(
 
     global_data.maybe_saved_offscreen_buffer = Some(offscreen_buffer);
 
+    /// The title is only painted when it changes. A frame that doesn't set the title
+    /// keeps the one from the previous frame.
+    fn paint_title_if_changed(
+        offscreen_buffer: &mut OffscreenBuffer,
+        maybe_saved_offscreen_buffer: &Option<OffscreenBuffer>,
+        window_size: Size,
+        locked_output_device: LockedOutputDevice<'_>,
+        is_mock: bool,
+    ) {
+        let maybe_saved_title = maybe_saved_offscreen_buffer
+            .as_ref()
+            .and_then(|it| it.my_title.clone());
+
+        match offscreen_buffer.my_title {
+            None => offscreen_buffer.my_title = maybe_saved_title,
+            Some(ref title) if maybe_saved_title.as_ref() != Some(title) => {
+                let mut skip_flush = false;
+                RenderOps::route_paint_render_op_to_backend(
+                    &mut RenderOpsLocalData::default(),
+                    &mut skip_flush,
+                    &RenderOp::SetTitle(title.clone()),
+                    window_size,
+                    locked_output_device,
+                    is_mock,
+                );
+            }
+            Some(_) => {}
+        }
+    }
+
+    /// The terminal's cursor is hidden while the frame is painted (if it was shown by the
+    /// previous frame), and then shown where the frame placed it (see
+    /// [RenderOp::ShowCursor]).
+    fn add_cursor_render_ops(
+        render_ops: &mut RenderOps,
+        maybe_cursor: Option<(Position, CursorShape)>,
+        is_cursor_shown: bool,
+    ) {
+        if is_cursor_shown {
+            render_ops.insert(0, RenderOp::HideCursor);
+        }
+        if let Some((cursor_pos, cursor_shape)) = maybe_cursor {
+            render_ops.push(RenderOp::MoveCursorPositionAbs(cursor_pos));
+            render_ops.push(RenderOp::ShowCursor(cursor_shape));
+        }
+    }
+
     fn perform_diff_paint(
         diff_chunks: &PixelCharDiffChunks,
+        maybe_cursor: Option<(Position, CursorShape)>,
+        is_cursor_shown: bool,
         window_size: Size,
         locked_output_device: LockedOutputDevice<'_>,
         is_mock: bool,
@@ -111,7 +186,8 @@ pub fn paint<S, AS>(
         match TERMINAL_LIB_BACKEND {
             TerminalLibBackend::Crossterm => {
                 let mut crossterm_impl = OffscreenBufferPaintImplCrossterm {};
-                let render_ops = crossterm_impl.render_diff(diff_chunks);
+                let mut render_ops = crossterm_impl.render_diff(diff_chunks);
+                add_cursor_render_ops(&mut render_ops, maybe_cursor, is_cursor_shown);
                 crossterm_impl.paint_diff(
                     render_ops,
                     window_size,
@@ -125,6 +201,7 @@ pub fn paint<S, AS>(
 
     fn perform_full_paint(
         offscreen_buffer: &OffscreenBuffer,
+        is_cursor_shown: bool,
         flush_kind: FlushKind,
         window_size: Size,
         locked_output_device: LockedOutputDevice<'_>,
@@ -133,7 +210,12 @@ pub fn paint<S, AS>(
         match TERMINAL_LIB_BACKEND {
             TerminalLibBackend::Crossterm => {
                 let mut crossterm_impl = OffscreenBufferPaintImplCrossterm {};
-                let render_ops = crossterm_impl.render(offscreen_buffer);
+                let mut render_ops = crossterm_impl.render(offscreen_buffer);
+                add_cursor_render_ops(
+                    &mut render_ops,
+                    offscreen_buffer.my_cursor,
+                    is_cursor_shown,
+                );
                 crossterm_impl.paint(
                     render_ops,
                     flush_kind,
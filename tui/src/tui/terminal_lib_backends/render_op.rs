@@ -298,10 +298,109 @@ pub enum RenderOp {
     /// padding.
     CompositorNoClipTruncPaintTextWithAttributes(String, Option<TuiStyle>),
 
+    /// Text that is painted after this (until [RenderOp::ResetHyperlink]) is a clickable
+    /// link. In terminals that don't support hyperlinks (see
+    /// [crate::hyperlink_support_global_static]), the text is painted w/out the link.
+    SetHyperlink(Hyperlink),
+
+    /// Text that is painted after this is no longer part of a link.
+    ResetHyperlink,
+
+    /// Set the title of the terminal window (or tab). The title that the terminal had
+    /// before the first one of these is restored by [RenderOp::ExitRawMode]. In
+    /// terminals that don't support it (see [crate::title_support_global_static]), this
+    /// does nothing.
+    SetTitle(String),
+
+    /// The terminal's cursor is hidden while a frame is painted. This shows it w/ the
+    /// given shape, at the position that the cursor was last moved to, once the frame has
+    /// been painted. A frame that doesn't have this keeps the cursor hidden. The shape
+    /// that the terminal had is restored by [RenderOp::ExitRawMode].
+    ShowCursor(CursorShape),
+
+    /// Hide the terminal's cursor. This is only used by [crate::paint], since a frame
+    /// that doesn't have a [RenderOp::ShowCursor] hides it.
+    HideCursor,
+
     /// For [Default] impl.
     Noop,
 }
 
+/// The shape of the terminal's cursor, see [RenderOp::ShowCursor]. Terminals that don't
+/// support changing it show their default shape.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, size_of::SizeOf,
+)]
+pub enum CursorShape {
+    SteadyBlock,
+    SteadyBar,
+}
+
+/// The escape sequence that ends a [Hyperlink]. It is an OSC 8 w/ an empty URI.
+pub const HYPERLINK_RESET_SEQUENCE: &str = "\x1b]8;;\x1b\\";
+
+/// A clickable link that is painted using an
+/// [OSC 8](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda) escape
+/// sequence. Cells that are painted w/ the same `maybe_id` (and `uri`) are highlighted
+/// together by the terminal when the mouse hovers over any of them, eg: when a link is
+/// split over multiple lines.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, size_of::SizeOf)]
+pub struct Hyperlink {
+    pub uri: String,
+    pub maybe_id: Option<String>,
+}
+
+mod hyperlink_impl {
+    use super::*;
+
+    impl Hyperlink {
+        pub fn new(uri: impl Into<String>) -> Self {
+            Self {
+                uri: uri.into(),
+                maybe_id: None,
+            }
+        }
+
+        pub fn with_id(mut self, id: impl Into<String>) -> Self {
+            self.maybe_id = Some(id.into());
+            self
+        }
+
+        /// The escape sequence that starts this link. Control characters are removed
+        /// from the `uri` and `maybe_id` (so they can't end the sequence early), and so
+        /// are `:` and `;` from the `maybe_id` (since they separate the parameters).
+        pub fn get_start_sequence(&self) -> String {
+            let uri: String = self.uri.chars().filter(|it| !it.is_control()).collect();
+            let params = match self.maybe_id {
+                Some(ref id) => {
+                    let id: String = id
+                        .chars()
+                        .filter(|it| !it.is_control() && *it != ':' && *it != ';')
+                        .collect();
+                    format!("id={id}")
+                }
+                None => "".into(),
+            };
+            format!("\x1b]8;{params};{uri}\x1b\\")
+        }
+    }
+}
+
+/// Save the current title of the terminal window on the terminal's title stack
+/// (XTWINOPS), so that it can be restored w/ [TITLE_RESTORE_SEQUENCE].
+pub const TITLE_SAVE_SEQUENCE: &str = "\x1b[22;0t";
+
+/// Restore the title of the terminal window that was saved w/ [TITLE_SAVE_SEQUENCE].
+pub const TITLE_RESTORE_SEQUENCE: &str = "\x1b[23;0t";
+
+/// The escape sequence (OSC 0) that sets the title of the terminal window to `title`.
+/// Control characters are removed from the `title` (so they can't end the sequence
+/// early).
+pub fn get_set_title_sequence(title: &str) -> String {
+    let title: String = title.chars().filter(|it| !it.is_control()).collect();
+    format!("\x1b]0;{title}\x07")
+}
+
 mod render_op_impl {
     use super::*;
 
@@ -105,6 +105,22 @@ fn process_render_op(
                 my_offscreen_buffer.my_bg_color = style_ref.color_bg;
             }
         }
+        RenderOp::SetHyperlink(hyperlink) => {
+            my_offscreen_buffer.my_hyperlink = Some(hyperlink.clone());
+        }
+        RenderOp::ResetHyperlink => {
+            my_offscreen_buffer.my_hyperlink = None;
+        }
+        RenderOp::SetTitle(title) => {
+            my_offscreen_buffer.my_title = Some(title.clone());
+        }
+        RenderOp::ShowCursor(cursor_shape) => {
+            my_offscreen_buffer.my_cursor =
+                Some((my_offscreen_buffer.my_pos, *cursor_shape));
+        }
+        RenderOp::HideCursor => {
+            my_offscreen_buffer.my_cursor = None;
+        }
         RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
             _arg_text_ref,
             _maybe_style_ref,
@@ -255,11 +271,16 @@ pub fn print_plain_text(
             let pixel_char = {
                 let new_gc_segment =
                     GraphemeClusterSegment::from(gc_segment.string.as_ref());
-                match (&maybe_style, new_gc_segment.string.as_str()) {
-                    (None, SPACER) => PixelChar::Spacer,
+                match (
+                    &maybe_style,
+                    &my_offscreen_buffer.my_hyperlink,
+                    new_gc_segment.string.as_str(),
+                ) {
+                    (None, None, SPACER) => PixelChar::Spacer,
                     _ => PixelChar::PlainText {
                         content: new_gc_segment,
                         maybe_style,
+                        maybe_hyperlink: my_offscreen_buffer.my_hyperlink.clone(),
                     },
                 }
             };
@@ -362,7 +383,7 @@ mod tests {
     use r3bl_macro::tui_style;
 
     use super::*;
-    use crate::render_pipeline;
+    use crate::{render_pipeline, CursorShape};
 
     #[test]
     fn test_print_plain_text_render_path_reuse_buffer() {
@@ -402,6 +423,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold, italic] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -411,6 +433,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold, italic] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -420,6 +443,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold, italic] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -429,6 +453,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold, italic] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
         }
@@ -465,6 +490,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -474,6 +500,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -483,6 +510,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][9], PixelChar::Spacer);
@@ -527,6 +555,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -536,6 +565,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -545,6 +575,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -554,6 +585,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
         }
@@ -592,6 +624,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -601,6 +634,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -610,6 +644,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][9], PixelChar::Spacer);
@@ -647,6 +682,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -656,6 +692,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -665,6 +702,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -674,6 +712,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][9], PixelChar::Void);
@@ -728,6 +767,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -737,6 +777,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -746,6 +787,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -755,6 +797,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][8], PixelChar::Void);
@@ -821,6 +864,7 @@ mod tests {
                 maybe_style: Some(
                     tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                 ),
+                maybe_hyperlink: None,
             }
         );
         assert_eq2!(
@@ -830,12 +874,38 @@ mod tests {
                 maybe_style: Some(
                     tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                 ),
+                maybe_hyperlink: None,
             }
         );
         assert_eq2!(my_offscreen_buffer.buffer[0][8], PixelChar::Void);
         assert_eq2!(my_offscreen_buffer.buffer[0][9], PixelChar::Spacer);
     }
 
+    #[test]
+    fn test_convert_show_cursor() {
+        let window_size = size! { col_count: 10, row_count: 2 };
+
+        // The cursor is hidden, unless it is shown by the frame.
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 2, row_index: 1 }),
+        );
+        assert_eq2!(pipeline.convert(window_size).my_cursor, None);
+
+        // The cursor is shown where it was moved to last.
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 2, row_index: 1 }),
+            RenderOp::ShowCursor(CursorShape::SteadyBar),
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+        );
+        assert_eq2!(
+            pipeline.convert(window_size).my_cursor,
+            Some((
+                position! { col_index: 2, row_index: 1 },
+                CursorShape::SteadyBar
+            ))
+        );
+    }
+
     #[test]
     fn test_convert_non_zero_position() {
         let window_size = size! { col_count: 10, row_count: 2 };
@@ -917,6 +987,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -926,6 +997,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][8], PixelChar::Void);
@@ -945,6 +1017,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -954,6 +1027,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[1][9], PixelChar::Spacer);
@@ -17,7 +17,7 @@
 
 #[cfg(test)]
 mod tests {
-    use crossterm::event::{KeyCode, KeyModifiers};
+    use crossterm::event::{Event, KeyCode, KeyModifiers};
     use r3bl_core::{assert_eq2, throws};
 
     use crate::{convert_key_event,
@@ -57,6 +57,15 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_convert_paste_event_into_input_event() {
+        let text = "fn main() {\n    println!(\"hi\");\n}";
+        assert_eq2!(
+            InputEvent::try_from(Event::Paste(text.to_string())),
+            Ok(InputEvent::Paste(text.to_string()))
+        );
+    }
+
     #[test]
     fn test_input_event_matches_correctly() -> Result<(), ()> {
         throws!({
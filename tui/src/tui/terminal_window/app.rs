@@ -17,10 +17,20 @@
 
 use std::fmt::Debug;
 
-use r3bl_core::CommonResult;
+use r3bl_core::{size, CommonResult, Size};
 
-use super::{ComponentRegistryMap, EventPropagation, GlobalData, HasFocus};
-use crate::{InputEvent, RenderPipeline};
+use super::{AppInitContext,
+            AppInitTask,
+            ComponentRegistry,
+            ComponentRegistryMap,
+            EscTimeout,
+            EventConsumption,
+            EventPropagation,
+            GlobalData,
+            HasFocus,
+            KeyRepeatCoalescing,
+            RenderThrottle};
+use crate::{InputEvent, MinSize, RenderPipeline};
 
 /// An app is typically a holder for [crate::ComponentRegistry].
 ///
@@ -49,6 +59,21 @@ pub trait App {
         has_focus: &mut HasFocus,
     );
 
+    /// Optionally return an [AppInitTask] to perform async setup (eg: loading data, or
+    /// connecting to a server). This is called once, right after [App::app_init], and the
+    /// task is awaited before the first render, while a splash screen shows the progress
+    /// that is reported w/ [AppInitContext::progress_reporter]. Use
+    /// [AppInitContext::main_thread_channel_sender] to send the results to the app as
+    /// signals, which are handled after the first render.
+    ///
+    /// The default implementation returns `None`, so the app is rendered right away.
+    fn app_init_async(
+        &mut self,
+        _init_context: AppInitContext<Self::AS>,
+    ) -> Option<AppInitTask> {
+        None
+    }
+
     /// At a high level:
     /// - Use the `input_event` to dispatch an action to the store if needed.
     /// - It returns an [EventPropagation].
@@ -63,6 +88,29 @@ pub trait App {
         has_focus: &mut HasFocus,
     ) -> CommonResult<EventPropagation>;
 
+    /// Answers whether [App::app_handle_input_event] would consume the `input_event`,
+    /// w/out actually handling it, and w/out any side effects. Use
+    /// [crate::DefaultInputEventHandler::would_consume] to also take the exit keys into
+    /// account.
+    ///
+    /// The default implementation asks the focused component. Apps that handle global
+    /// hotkeys before routing the event to the focused component should override this,
+    /// so that the hotkeys take precedence in the answer too.
+    fn app_would_consume_input_event(
+        &self,
+        input_event: InputEvent,
+        global_data: &GlobalData<Self::S, Self::AS>,
+        component_registry_map: &ComponentRegistryMap<Self::S, Self::AS>,
+        has_focus: &HasFocus,
+    ) -> EventConsumption {
+        ComponentRegistry::would_focused_component_consume_event(
+            global_data,
+            input_event,
+            component_registry_map,
+            has_focus,
+        )
+    }
+
     /// At a high level:
     /// - Use the `action` to dispatch an action to the store if needed.
     /// - It returns an [EventPropagation].
@@ -77,6 +125,47 @@ pub trait App {
         has_focus: &mut HasFocus,
     ) -> CommonResult<EventPropagation>;
 
+    /// The smallest terminal window that the app can be used in. When the window is
+    /// smaller than this, a message asking the user to resize it is rendered instead of
+    /// the app, and input events (other than resizes and the exit keys) are not passed to
+    /// the app, so that its state is exactly as it was when the window is big enough
+    /// again.
+    ///
+    /// The default implementation returns [MinSize].
+    fn app_min_size(&self) -> Size {
+        size!(col_count: MinSize::Col as u8, row_count: MinSize::Row as u8)
+    }
+
+    /// Return [Some] to coalesce the key events that pile up while a key is held down.
+    /// The app then gets a single event, and [GlobalData::input_event_repeat_count] is
+    /// set to how many times it was repeated. See [KeyRepeatCoalescing] for the keys
+    /// that are coalesced.
+    ///
+    /// [App::app_handle_input_event] gets the coalesced event once, and must read the
+    /// repeat count itself. When it routes the event to the focused component (w/
+    /// [crate::ComponentRegistry::route_event_to_focused_component]), the event is
+    /// replayed once for each repeat, unless the component opts in w/
+    /// [crate::Component::supports_input_event_repeat_count] (eg: the editor component).
+    ///
+    /// The default implementation returns [None], so every key event is handled on its
+    /// own.
+    fn app_key_repeat_coalescing(&self) -> Option<KeyRepeatCoalescing> { None }
+
+    /// Return [Some] to assemble the escape sequences that arrive split up (eg: over a
+    /// slow connection) into the keys that they stand for. A bare `Esc` is then reported
+    /// after [EscTimeout::timeout]. See [EscTimeout] for more details.
+    ///
+    /// The default implementation returns [None], so `Esc` is reported right away.
+    fn app_esc_timeout(&self) -> Option<EscTimeout> { None }
+
+    /// Return [Some] to render at most once per [RenderThrottle::min_interval]. Render
+    /// requests that arrive sooner are coalesced into one render (of the latest state)
+    /// when the interval has elapsed. See [RenderThrottle] for more details.
+    ///
+    /// The default implementation returns [None], so every request is rendered right
+    /// away.
+    fn app_render_throttle(&self) -> Option<RenderThrottle> { None }
+
     /// Use the state to render the output (via crossterm). The state is immutable. If you
     /// want to change it then it should be done in the [App::app_handle_input_event]
     /// method.
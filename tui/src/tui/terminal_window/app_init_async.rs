@@ -0,0 +1,237 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Support for [App::app_init_async](crate::App::app_init_async), which lets an app perform async setup (eg: loading
+//! data, or connecting to a server) before its first render. While this runs, the main
+//! event loop paints a splash screen w/ the progress that is reported by the app.
+
+use std::{fmt::Debug, future::Future, pin::Pin};
+
+use r3bl_core::{ch, position, ChUnit, CommonResult, Percent, Size, UnicodeString};
+use r3bl_macro::tui_style;
+use tokio::sync::{mpsc, watch};
+
+use crate::{render_pipeline,
+            RenderOp,
+            RenderPipeline,
+            TerminalWindowMainThreadSignal,
+            ZOrder};
+
+/// The future that performs the async setup of an app. It can't borrow the app, so it
+/// has to send its results back using [AppInitContext::main_thread_channel_sender].
+pub type AppInitFuture = Pin<Box<dyn Future<Output = CommonResult<()>> + Send>>;
+
+/// Returned by [App::app_init_async](crate::App::app_init_async) to run some async setup
+/// before the first render of the app.
+pub struct AppInitTask {
+    pub future: AppInitFuture,
+    pub input_during_init: InputDuringInit,
+    pub on_failure: InitFailurePolicy,
+}
+
+impl AppInitTask {
+    pub fn new(future: impl Future<Output = CommonResult<()>> + Send + 'static) -> Self {
+        Self {
+            future: Box::pin(future),
+            input_during_init: InputDuringInit::default(),
+            on_failure: InitFailurePolicy::default(),
+        }
+    }
+
+    pub fn with_input_during_init(mut self, input_during_init: InputDuringInit) -> Self {
+        self.input_during_init = input_during_init;
+        self
+    }
+
+    pub fn with_on_failure(mut self, on_failure: InitFailurePolicy) -> Self {
+        self.on_failure = on_failure;
+        self
+    }
+}
+
+/// Controls what happens to the input events that arrive while the [AppInitTask] is
+/// running. Resize events are always applied, so that the splash screen fits the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputDuringInit {
+    /// Drop the input events.
+    #[default]
+    Ignore,
+    /// Hand the input events to the app (in order) after the first render.
+    Buffer,
+}
+
+/// Controls what happens when the [AppInitTask] returns an error. In both cases the
+/// error is logged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InitFailurePolicy {
+    /// Restore the terminal, and return the error from the main event loop.
+    #[default]
+    AbortStartup,
+    /// Render the app anyway, eg: if it can show the error itself.
+    Continue,
+}
+
+/// Passed to [App::app_init_async](crate::App::app_init_async), so that the
+/// [AppInitTask] can report its progress, and send signals to the app.
+pub struct AppInitContext<AS>
+where
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    /// Signals that are sent during init are handled by the main event loop after the
+    /// first render of the app.
+    pub main_thread_channel_sender: mpsc::Sender<TerminalWindowMainThreadSignal<AS>>,
+    pub progress_reporter: AppInitProgressReporter,
+}
+
+/// What is painted on the splash screen while the [AppInitTask] is running.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AppInitProgress {
+    pub message: String,
+    pub maybe_percent: Option<Percent>,
+}
+
+/// Use this to update the splash screen. It can be cloned and moved into the
+/// [AppInitTask]. Reports that are made in quick succession are coalesced, so only the
+/// latest one is painted.
+#[derive(Clone, Debug)]
+pub struct AppInitProgressReporter {
+    sender: watch::Sender<AppInitProgress>,
+}
+
+impl AppInitProgressReporter {
+    pub fn new() -> (Self, watch::Receiver<AppInitProgress>) {
+        let (sender, receiver) = watch::channel(AppInitProgress::default());
+        (Self { sender }, receiver)
+    }
+
+    pub fn report(&self, message: impl Into<String>, maybe_percent: Option<Percent>) {
+        // The receiver is dropped once init is done, so there's no one to report to.
+        let _ = self.sender.send(AppInitProgress {
+            message: message.into(),
+            maybe_percent,
+        });
+    }
+}
+
+pub const APP_INIT_DEFAULT_MESSAGE: &str = "Starting…";
+
+/// The splash screen that is painted while the [AppInitTask] is running. The message is
+/// centered in the window, w/ a progress bar below it (if there's a percentage).
+pub fn render_app_init_splash(
+    window_size: Size,
+    progress: &AppInitProgress,
+) -> RenderPipeline {
+    let message = match progress.message.is_empty() {
+        true => APP_INIT_DEFAULT_MESSAGE,
+        false => progress.message.as_str(),
+    };
+    let message = UnicodeString::from(
+        UnicodeString::from(message).truncate_to_fit_size(window_size),
+    );
+
+    let mut pipeline = render_pipeline!();
+    let row_index = window_size.row_count / 2;
+
+    render_pipeline! {
+        @push_into pipeline
+        at ZOrder::Normal
+        =>
+            RenderOp::ResetColor,
+            RenderOp::MoveCursorPositionAbs(position! {
+                col_index: (window_size.col_count - message.display_width) / 2,
+                row_index: row_index
+            }),
+            RenderOp::PaintTextWithAttributes(
+                message.string.clone(),
+                Some(tui_style!(attrib: [bold])),
+            )
+    }
+
+    if let Some(percent) = progress.maybe_percent {
+        let progress_bar =
+            UnicodeString::from(get_progress_bar_text(percent, window_size.col_count));
+        render_pipeline! {
+            @push_into pipeline
+            at ZOrder::Normal
+            =>
+                RenderOp::ResetColor,
+                RenderOp::MoveCursorPositionAbs(position! {
+                    col_index: (window_size.col_count - progress_bar.display_width) / 2,
+                    row_index: row_index + 1
+                }),
+                RenderOp::PaintTextWithAttributes(
+                    progress_bar.string,
+                    Some(tui_style!(attrib: [dim])),
+                )
+        }
+    }
+
+    pipeline
+}
+
+/// Eg: `━━━━━━──────────  40%`. The bar is at most 40 columns wide, and it shrinks to fit
+/// the window.
+fn get_progress_bar_text(percent: Percent, window_width: ChUnit) -> String {
+    let percent_text = format!(" {:>3}%", percent.value);
+    let bar_width =
+        std::cmp::min(ch!(40), window_width - ch!(percent_text.len()) - ch!(2));
+    let bar_width = ch!(@to_usize bar_width);
+    let filled_width = bar_width * percent.value as usize / 100;
+    format!(
+        "{}{}{percent_text}",
+        "━".repeat(filled_width),
+        "─".repeat(bar_width - filled_width)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, percent};
+
+    use super::*;
+
+    #[test]
+    fn test_get_progress_bar_text() {
+        let percent = percent!(50).unwrap();
+        assert_eq2!(
+            get_progress_bar_text(percent, ch!(80)),
+            format!("{}{}  50%", "━".repeat(20), "─".repeat(20))
+        );
+
+        // The bar shrinks to fit a narrow window.
+        assert_eq2!(get_progress_bar_text(percent, ch!(17)), "━━━━━─────  50%");
+        assert_eq2!(get_progress_bar_text(percent, ch!(3)), "  50%");
+    }
+
+    #[test]
+    fn test_progress_reporter_keeps_latest() {
+        let (progress_reporter, receiver) = AppInitProgressReporter::new();
+        progress_reporter.report("Loading", None);
+        progress_reporter.report("Connecting", percent!(75).ok());
+        assert_eq2!(
+            *receiver.borrow(),
+            AppInitProgress {
+                message: "Connecting".to_string(),
+                maybe_percent: percent!(75).ok(),
+            }
+        );
+
+        // Reporting after init is done (the receiver is dropped) is not an error.
+        drop(receiver);
+        progress_reporter.report("Done", None);
+    }
+}
@@ -19,7 +19,11 @@ use std::fmt::Debug;
 
 use r3bl_core::CommonResult;
 
-use super::{ComponentRegistryMap, EventPropagation, GlobalData, HasFocus};
+use super::{ComponentRegistryMap,
+            EventConsumption,
+            EventPropagation,
+            GlobalData,
+            HasFocus};
 use crate::{FlexBox, FlexBoxId, InputEvent, RenderPipeline, Surface, SurfaceBounds};
 
 /// See [crate::App].
@@ -89,6 +93,29 @@ where
         input_event: InputEvent,
         has_focus: &mut HasFocus,
     ) -> CommonResult<EventPropagation>;
+
+    /// Answers whether [Component::handle_event] would consume the `input_event`, given
+    /// the current state of this component (eg: its mode), w/out actually handling it.
+    /// This is useful for building help systems and macro recorders.
+    ///
+    /// Implementations must not have any side effects, which is why they only get shared
+    /// references. The default implementation returns [EventConsumption::Unknown].
+    fn would_consume_event(
+        &self,
+        _global_data: &GlobalData<S, AS>,
+        _input_event: InputEvent,
+        _has_focus: &HasFocus,
+    ) -> EventConsumption {
+        EventConsumption::Unknown
+    }
+
+    /// Return `true` if [Component::handle_event] applies a coalesced key repeat on its
+    /// own, by reading [GlobalData::input_event_repeat_count]. Otherwise the event is
+    /// replayed to this component once for each repeat (w/ the count set to `1`), by
+    /// [crate::ComponentRegistry::route_event_to_focused_component].
+    ///
+    /// The default implementation returns `false`.
+    fn supports_input_event_repeat_count(&self) -> bool { false }
 }
 
 pub trait SurfaceRender<S, AS>
@@ -15,16 +15,15 @@
  *   limitations under the License.
  */
 
-use super::Continuation;
+use super::{Continuation, EventConsumption};
 use crate::InputEvent;
 
 pub struct DefaultInputEventHandler;
 
 impl DefaultInputEventHandler {
-    /// This function does **not** consume the `input_event` argument. [InputEvent] implements [Copy]
-    /// (no need to pass references into this function).
+    /// This function does **not** consume the `input_event` argument.
     pub fn no_consume(
-        input_event: InputEvent,
+        input_event: &InputEvent,
         exit_keys: &[InputEvent],
     ) -> Continuation<String> {
         // Early return if any exit key sequence is pressed.
@@ -33,4 +32,70 @@ impl DefaultInputEventHandler {
         }
         Continuation::Continue
     }
+
+    /// Applies the same precedence as the main event loop to the answer from
+    /// [crate::App::app_would_consume_input_event]: the exit keys are only checked if the
+    /// app would not consume the `input_event`.
+    pub fn would_consume(
+        input_event: &InputEvent,
+        app_answer: EventConsumption,
+        exit_keys: &[InputEvent],
+    ) -> EventConsumption {
+        match app_answer {
+            EventConsumption::Propagate => match Self::no_consume(input_event, exit_keys)
+            {
+                Continuation::Exit => EventConsumption::ExitMainEventLoop,
+                _ => EventConsumption::Propagate,
+            },
+            _ => app_answer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{Key, KeyPress, ModifierKeysMask};
+
+    #[test]
+    fn test_would_consume_applies_exit_keys_last() {
+        let exit_key = InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character('q'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        });
+        let exit_keys = [exit_key.clone()];
+
+        // The app consumes the exit key first.
+        assert_eq2!(
+            DefaultInputEventHandler::would_consume(
+                &exit_key,
+                EventConsumption::Consumed(None),
+                &exit_keys
+            ),
+            EventConsumption::Consumed(None)
+        );
+
+        assert_eq2!(
+            DefaultInputEventHandler::would_consume(
+                &exit_key,
+                EventConsumption::Propagate,
+                &exit_keys
+            ),
+            EventConsumption::ExitMainEventLoop
+        );
+
+        let other_key = InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::Character('q'),
+        });
+        assert_eq2!(
+            DefaultInputEventHandler::would_consume(
+                &other_key,
+                EventConsumption::Propagate,
+                &exit_keys
+            ),
+            EventConsumption::Propagate
+        );
+    }
 }
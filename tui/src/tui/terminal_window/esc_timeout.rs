@@ -0,0 +1,420 @@
+/*
+ *   Copyright (c) 2025 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! A bare `Esc` key press and the start of an escape sequence (that the terminal sends
+//! for arrow keys, function keys, etc) both begin w/ the `ESC` byte. When all the bytes
+//! of a sequence arrive together, they are parsed as a single key. But over a slow
+//! connection (eg: SSH) they can arrive in separate reads, and then they are reported as
+//! `Esc` followed by the other characters, eg: `Esc`, `[`, `A` instead of `Up`.
+//!
+//! The `Esc` timeout fixes this by holding on to a bare `Esc` event for a short time. If
+//! the events that follow it (within the timeout) complete an escape sequence, they are
+//! assembled into the key that the sequence stands for. Otherwise the `Esc` is reported
+//! once the timeout elapses, followed by the events that arrived in the meantime. Opt in
+//! by returning an [EscTimeout] from [App::app_esc_timeout].
+//!
+//! A timeout that is too short misfires on slow connections (the sequence is split into
+//! `Esc` and other keys), and one that is too long makes `Esc` feel laggy, since it is
+//! only reported after the timeout. See [DEFAULT_ESC_TIMEOUT].
+//!
+//! [App::app_esc_timeout]: crate::App::app_esc_timeout
+
+use std::time::Duration;
+
+use r3bl_core::InputDevice;
+
+use crate::{FunctionKey,
+            InputDeviceExt,
+            InputEvent,
+            Key,
+            KeyPress,
+            ModifierKeysMask,
+            SpecialKey};
+
+/// Long enough for the bytes of a sequence that are split across reads on most remote
+/// connections, and short enough that the delay before a bare `Esc` is reported isn't
+/// noticeable.
+pub const DEFAULT_ESC_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// The longest escape sequence (after the `ESC`) that is assembled, eg: `[24~` for
+/// `F12`.
+const MAX_SEQUENCE_LEN: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscTimeout {
+    /// How long to wait (after the `Esc`) for the rest of an escape sequence.
+    pub timeout: Duration,
+}
+
+impl Default for EscTimeout {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_ESC_TIMEOUT,
+        }
+    }
+}
+
+impl EscTimeout {
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// The result of [parse_esc_sequence].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscSequenceParse {
+    /// The characters so far are the start of an escape sequence.
+    Incomplete,
+    /// The characters are a complete escape sequence for this key.
+    Complete(KeyPress),
+    /// The characters aren't an escape sequence, so the `Esc` is a bare one.
+    Invalid,
+}
+
+/// Parse the characters that follow an `ESC` byte. The CSI (`ESC [`) and SS3 (`ESC O`)
+/// sequences for the arrow, navigation and function keys are supported, as is `ESC`
+/// followed by a single character (which terminals send for `Alt` + character).
+pub fn parse_esc_sequence(chars: &[char]) -> EscSequenceParse {
+    use EscSequenceParse::*;
+
+    let special = |special_key| {
+        Complete(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        })
+    };
+    let function = |function_key| {
+        Complete(KeyPress::Plain {
+            key: Key::FunctionKey(function_key),
+        })
+    };
+
+    match chars {
+        [] | ['['] | ['O'] => Incomplete,
+
+        // CSI or SS3 arrow keys, Home and End.
+        ['[' | 'O', 'A'] => special(SpecialKey::Up),
+        ['[' | 'O', 'B'] => special(SpecialKey::Down),
+        ['[' | 'O', 'C'] => special(SpecialKey::Right),
+        ['[' | 'O', 'D'] => special(SpecialKey::Left),
+        ['[' | 'O', 'H'] => special(SpecialKey::Home),
+        ['[' | 'O', 'F'] => special(SpecialKey::End),
+        ['[', 'Z'] => special(SpecialKey::BackTab),
+
+        // SS3 F1 to F4.
+        ['O', 'P'] => function(FunctionKey::F1),
+        ['O', 'Q'] => function(FunctionKey::F2),
+        ['O', 'R'] => function(FunctionKey::F3),
+        ['O', 'S'] => function(FunctionKey::F4),
+
+        // CSI sequences that end w/ `~`, eg: `[3~` for Delete.
+        ['[', rest @ ..] if rest.len() < MAX_SEQUENCE_LEN => {
+            let Some((last, digits)) = rest.split_last() else {
+                return Incomplete;
+            };
+            if !digits.iter().all(char::is_ascii_digit) {
+                return Invalid;
+            }
+            if last.is_ascii_digit() {
+                return if rest.len() < 3 { Incomplete } else { Invalid };
+            }
+            if *last != '~' {
+                return Invalid;
+            }
+            match digits.iter().collect::<String>().as_str() {
+                "1" | "7" => special(SpecialKey::Home),
+                "2" => special(SpecialKey::Insert),
+                "3" => special(SpecialKey::Delete),
+                "4" | "8" => special(SpecialKey::End),
+                "5" => special(SpecialKey::PageUp),
+                "6" => special(SpecialKey::PageDown),
+                "15" => function(FunctionKey::F5),
+                "17" => function(FunctionKey::F6),
+                "18" => function(FunctionKey::F7),
+                "19" => function(FunctionKey::F8),
+                "20" => function(FunctionKey::F9),
+                "21" => function(FunctionKey::F10),
+                "23" => function(FunctionKey::F11),
+                "24" => function(FunctionKey::F12),
+                _ => Invalid,
+            }
+        }
+
+        // Alt + character.
+        [character] => Complete(KeyPress::WithModifiers {
+            key: Key::Character(*character),
+            mask: ModifierKeysMask::new().with_alt(),
+        }),
+
+        _ => Invalid,
+    }
+}
+
+fn is_bare_esc(input_event: &InputEvent) -> bool {
+    *input_event
+        == InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(SpecialKey::Esc),
+        })
+}
+
+/// Characters are reported as [KeyPress::Plain], even when `Shift` is pressed.
+fn get_plain_char(input_event: &InputEvent) -> Option<char> {
+    match input_event {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::Character(character),
+        }) => Some(*character),
+        _ => None,
+    }
+}
+
+/// The result of [assemble_esc_sequence].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssembledInputEvents {
+    /// The events to handle, in order.
+    pub input_events: Vec<InputEvent>,
+    /// The input device has no more events.
+    pub is_end_of_stream: bool,
+}
+
+impl From<InputEvent> for AssembledInputEvents {
+    fn from(input_event: InputEvent) -> Self {
+        Self {
+            input_events: vec![input_event],
+            is_end_of_stream: false,
+        }
+    }
+}
+
+/// If `input_event` is a bare `Esc`, read the events that follow it from the
+/// `input_device` (for up to [EscTimeout::timeout]) and try to assemble them into the key
+/// of an escape sequence. Any other event is returned as is, w/out reading more events.
+pub async fn assemble_esc_sequence(
+    input_device: &mut InputDevice,
+    input_event: InputEvent,
+    config: &EscTimeout,
+) -> AssembledInputEvents {
+    if !is_bare_esc(&input_event) {
+        return AssembledInputEvents::from(input_event);
+    }
+    let mut it = AssembledInputEvents {
+        input_events: vec![],
+        is_end_of_stream: false,
+    };
+
+    let mut chars = vec![];
+    let mut deadline = tokio::time::Instant::now() + config.timeout;
+
+    loop {
+        let maybe_next_event = match tokio::time::timeout_at(
+            deadline,
+            input_device.next_input_event(),
+        )
+        .await
+        {
+            // Timed out, so this is a bare `Esc`.
+            Err(_elapsed) => None,
+            Ok(None) => {
+                it.is_end_of_stream = true;
+                None
+            }
+            Ok(Some(next_event)) => Some(next_event),
+        };
+
+        let Some(next_event) = maybe_next_event else {
+            it.input_events.push(input_event.clone());
+            it.input_events.extend(chars.iter().map(|&character| {
+                InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::Character(character),
+                })
+            }));
+            return it;
+        };
+
+        if let Some(character) = get_plain_char(&next_event) {
+            chars.push(character);
+            match parse_esc_sequence(&chars) {
+                EscSequenceParse::Incomplete => continue,
+                EscSequenceParse::Complete(key_press) => {
+                    it.input_events.push(InputEvent::Keyboard(key_press));
+                    return it;
+                }
+                EscSequenceParse::Invalid => {
+                    chars.pop();
+                }
+            }
+        }
+
+        // The `Esc` is a bare one, and so are the characters that were read so far.
+        it.input_events.push(input_event.clone());
+        it.input_events.extend(chars.drain(..).map(|character| {
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character(character),
+            })
+        }));
+
+        // Another `Esc` might be the start of a sequence.
+        if is_bare_esc(&next_event) {
+            deadline = tokio::time::Instant::now() + config.timeout;
+            continue;
+        }
+        it.input_events.push(next_event);
+        return it;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+    use r3bl_core::CrosstermEventResult;
+    use r3bl_test_fixtures::InputDeviceExt as _;
+
+    use super::*;
+    use crate::keypress;
+
+    fn key(key_press: KeyPress) -> InputEvent { InputEvent::Keyboard(key_press) }
+
+    fn crossterm_key(key_code: KeyCode) -> CrosstermEventResult {
+        Ok(Event::Key(KeyEvent::new(key_code, KeyModifiers::empty())))
+    }
+
+    fn crossterm_chars(text: &str) -> Vec<CrosstermEventResult> {
+        text.chars()
+            .map(|character| crossterm_key(KeyCode::Char(character)))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_esc_sequence() {
+        use EscSequenceParse::*;
+
+        assert_eq!(parse_esc_sequence(&['[']), Incomplete);
+        assert_eq!(
+            parse_esc_sequence(&['[', 'A']),
+            Complete(keypress!(@special SpecialKey::Up))
+        );
+        assert_eq!(
+            parse_esc_sequence(&['O', 'D']),
+            Complete(keypress!(@special SpecialKey::Left))
+        );
+        assert_eq!(
+            parse_esc_sequence(&['O', 'P']),
+            Complete(keypress!(@fn FunctionKey::F1))
+        );
+        assert_eq!(parse_esc_sequence(&['[', '3']), Incomplete);
+        assert_eq!(
+            parse_esc_sequence(&['[', '3', '~']),
+            Complete(keypress!(@special SpecialKey::Delete))
+        );
+        assert_eq!(parse_esc_sequence(&['[', '2', '4']), Incomplete);
+        assert_eq!(
+            parse_esc_sequence(&['[', '2', '4', '~']),
+            Complete(keypress!(@fn FunctionKey::F12))
+        );
+        assert_eq!(parse_esc_sequence(&['[', '9', '~']), Invalid);
+        assert_eq!(parse_esc_sequence(&['[', '1', '2', '3']), Invalid);
+        assert_eq!(parse_esc_sequence(&['[', 'x']), Invalid);
+        assert_eq!(
+            parse_esc_sequence(&['x']),
+            Complete(keypress!(@char ModifierKeysMask::new().with_alt(), 'x'))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_assemble_split_sequence() {
+        let mut events = vec![crossterm_key(KeyCode::Esc)];
+        events.extend(crossterm_chars("[A"));
+        events.push(crossterm_key(KeyCode::Esc));
+        events.extend(crossterm_chars("[5~"));
+        let mut input_device = InputDevice::new_mock(events);
+        let config = EscTimeout::default();
+
+        let esc = input_device.next_input_event().await.unwrap();
+        let assembled =
+            assemble_esc_sequence(&mut input_device, esc.clone(), &config).await;
+        assert_eq!(
+            assembled.input_events,
+            vec![key(keypress!(@special SpecialKey::Up))]
+        );
+
+        let esc = input_device.next_input_event().await.unwrap();
+        let assembled =
+            assemble_esc_sequence(&mut input_device, esc.clone(), &config).await;
+        assert_eq!(
+            assembled.input_events,
+            vec![key(keypress!(@special SpecialKey::PageUp))]
+        );
+        assert!(!assembled.is_end_of_stream);
+    }
+
+    #[tokio::test]
+    async fn test_bare_esc() {
+        let mut events = vec![crossterm_key(KeyCode::Esc)];
+        events.extend(crossterm_chars("[x"));
+        events.push(crossterm_key(KeyCode::Esc));
+        events.push(crossterm_key(KeyCode::Down));
+        events.push(crossterm_key(KeyCode::Esc));
+        let mut input_device = InputDevice::new_mock(events);
+        let config = EscTimeout::default();
+        let esc = key(keypress!(@special SpecialKey::Esc));
+
+        // Not a sequence, so the characters are reported after the `Esc`.
+        input_device.next_input_event().await.unwrap();
+        let assembled =
+            assemble_esc_sequence(&mut input_device, esc.clone(), &config).await;
+        assert_eq!(
+            assembled.input_events,
+            vec![
+                esc.clone(),
+                key(keypress!(@char '[')),
+                key(keypress!(@char 'x'))
+            ]
+        );
+
+        // A key that the terminal already parsed.
+        input_device.next_input_event().await.unwrap();
+        let assembled =
+            assemble_esc_sequence(&mut input_device, esc.clone(), &config).await;
+        assert_eq!(
+            assembled.input_events,
+            vec![esc.clone(), key(keypress!(@special SpecialKey::Down))]
+        );
+
+        // Nothing follows the last `Esc`.
+        input_device.next_input_event().await.unwrap();
+        let assembled =
+            assemble_esc_sequence(&mut input_device, esc.clone(), &config).await;
+        assert_eq!(assembled.input_events, vec![esc]);
+        assert!(assembled.is_end_of_stream);
+    }
+
+    #[tokio::test]
+    async fn test_esc_timeout_elapses() {
+        let mut events = vec![crossterm_key(KeyCode::Esc)];
+        events.extend(crossterm_chars("[A"));
+        let mut input_device =
+            InputDevice::new_mock_with_delay(events, Duration::from_millis(30));
+        let config = EscTimeout::default().with_timeout(Duration::from_millis(5));
+        let esc = key(keypress!(@special SpecialKey::Esc));
+
+        // The rest of the sequence arrives too late.
+        input_device.next_input_event().await.unwrap();
+        let assembled =
+            assemble_esc_sequence(&mut input_device, esc.clone(), &config).await;
+        assert_eq!(assembled.input_events, vec![esc]);
+        assert!(!assembled.is_end_of_stream);
+    }
+}
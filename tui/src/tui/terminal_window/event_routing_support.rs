@@ -40,3 +40,18 @@ pub enum EventPropagation {
     Propagate,
     ExitMainEventLoop,
 }
+
+/// The answer to whether an input event would be consumed, which is determined w/out
+/// actually handling it. More info in [crate::Component::would_consume_event].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventConsumption {
+    /// The event would be consumed. Optionally contains a description of the action
+    /// that the event maps to.
+    Consumed(Option<String>),
+    /// The event would not be consumed.
+    Propagate,
+    /// The event would exit the main event loop, eg: it is one of the exit keys.
+    ExitMainEventLoop,
+    /// The component (or app) does not answer this query.
+    Unknown,
+}
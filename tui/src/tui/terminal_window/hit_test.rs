@@ -0,0 +1,311 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Hit testing maps an absolute position in the terminal window (eg: from a mouse event)
+//! to the component that is under it, and the position relative to that component's box.
+//! The boxes that components are rendered in are recorded in
+//! [GlobalData::hit_test_areas] each time the app is rendered (by
+//! [crate::render_component_in_current_box!] and
+//! [crate::render_component_in_given_box!]), so hit testing always uses the layout that
+//! is on the screen.
+//!
+//! The style adjusted box is used, so the padding of a box (and the gaps between boxes)
+//! doesn't belong to any component, and any centering or margins (eg: a surface that
+//! doesn't start at the top left corner of the window) are already accounted for.
+
+use r3bl_core::{position, Position, Size};
+
+use crate::{FlexBox,
+            FlexBoxId,
+            GlobalData,
+            InputEvent,
+            MouseInput,
+            RenderPipeline,
+            ZOrder};
+
+/// The boxes that components were rendered in, in the order that they were rendered.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HitTestAreas {
+    pub areas: Vec<HitTestArea>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HitTestArea {
+    pub id: FlexBoxId,
+    /// Absolute position of the top left corner of the (style adjusted) box.
+    pub origin_pos: Position,
+    pub size: Size,
+    /// The highest [ZOrder] that the component painted anything in.
+    pub z_order: ZOrder,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HitTestResult {
+    pub id: FlexBoxId,
+    /// Relative to the top left corner of the component's (style adjusted) box.
+    pub local_pos: Position,
+}
+
+impl HitTestArea {
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.col_index >= self.origin_pos.col_index
+            && pos.col_index < self.origin_pos.col_index + self.size.col_count
+            && pos.row_index >= self.origin_pos.row_index
+            && pos.row_index < self.origin_pos.row_index + self.size.row_count
+    }
+
+    fn get_z_index(&self) -> usize {
+        ZOrder::get_render_order()
+            .iter()
+            .position(|it| *it == self.z_order)
+            .unwrap_or_default()
+    }
+}
+
+impl HitTestAreas {
+    pub fn clear(&mut self) { self.areas.clear(); }
+
+    /// Record that the component w/ the given `id` was rendered in `flex_box`, and
+    /// painted the given `pipeline`.
+    pub fn record(
+        &mut self,
+        id: FlexBoxId,
+        flex_box: &FlexBox,
+        pipeline: &RenderPipeline,
+    ) {
+        let z_order = ZOrder::get_render_order()
+            .into_iter()
+            .rev()
+            .find(|z_order| {
+                pipeline
+                    .pipeline_map
+                    .get(z_order)
+                    .is_some_and(|render_ops_vec| {
+                        render_ops_vec
+                            .iter()
+                            .any(|render_ops| !render_ops.is_empty())
+                    })
+            })
+            .unwrap_or_default();
+
+        self.areas.push(HitTestArea {
+            id,
+            origin_pos: flex_box.style_adjusted_origin_pos,
+            size: flex_box.style_adjusted_bounds_size,
+            z_order,
+        });
+    }
+
+    /// Returns the component at the given absolute position. When boxes overlap, the one
+    /// w/ the highest [ZOrder] wins, and if they are the same, the one that was rendered
+    /// last (ie: the one that is painted on top). Returns [None] if the position isn't in
+    /// any component's box.
+    pub fn hit_test(&self, pos: Position) -> Option<HitTestResult> {
+        self.areas
+            .iter()
+            .enumerate()
+            .filter(|(_, area)| area.contains(pos))
+            .max_by_key(|(index, area)| (area.get_z_index(), *index))
+            .map(|(_, area)| HitTestResult {
+                id: area.id,
+                local_pos: position!(
+                    col_index: pos.col_index - area.origin_pos.col_index,
+                    row_index: pos.row_index - area.origin_pos.row_index
+                ),
+            })
+    }
+}
+
+impl<S, AS> GlobalData<S, AS>
+where
+    S: std::fmt::Debug + Default + Clone + Sync + Send,
+    AS: std::fmt::Debug + Default + Clone + Sync + Send,
+{
+    /// See [HitTestAreas::hit_test].
+    pub fn hit_test(&self, pos: Position) -> Option<HitTestResult> {
+        self.hit_test_areas.hit_test(pos)
+    }
+
+    /// Hit test the position of a mouse event. Returns [None] for other events.
+    pub fn hit_test_input_event(
+        &self,
+        input_event: &InputEvent,
+    ) -> Option<HitTestResult> {
+        match input_event {
+            InputEvent::Mouse(MouseInput { pos, .. }) => self.hit_test(*pos),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    ch,
+                    get_tui_styles,
+                    requested_size_percent,
+                    size,
+                    throws,
+                    tui_stylesheet,
+                    CommonResult};
+    use r3bl_macro::tui_style;
+
+    use super::*;
+    use crate::{box_end,
+                box_props,
+                box_start,
+                render_pipeline,
+                LayoutDirection,
+                LayoutManagement,
+                PerformPositioningAndSizing as _,
+                RenderOp,
+                Surface,
+                SurfaceProps};
+
+    fn make_flex_box(origin_pos: Position, size: Size) -> FlexBox {
+        FlexBox {
+            style_adjusted_origin_pos: origin_pos,
+            style_adjusted_bounds_size: size,
+            ..Default::default()
+        }
+    }
+
+    fn make_pipeline(z_order: ZOrder) -> RenderPipeline {
+        render_pipeline!(@new z_order => RenderOp::ClearScreen)
+    }
+
+    #[test]
+    fn test_hit_test_local_pos_and_gaps() {
+        let mut areas = HitTestAreas::default();
+        areas.record(
+            FlexBoxId::from(1),
+            &make_flex_box(
+                position!(col_index: 1, row_index: 1),
+                size!(col_count: 4, row_count: 2),
+            ),
+            &make_pipeline(ZOrder::Normal),
+        );
+        areas.record(
+            FlexBoxId::from(2),
+            &make_flex_box(
+                position!(col_index: 6, row_index: 1),
+                size!(col_count: 4, row_count: 2),
+            ),
+            &make_pipeline(ZOrder::Normal),
+        );
+
+        assert_eq2!(
+            areas.hit_test(position!(col_index: 7, row_index: 2)),
+            Some(HitTestResult {
+                id: FlexBoxId::from(2),
+                local_pos: position!(col_index: 1, row_index: 1),
+            })
+        );
+
+        // The gap between the boxes, and the padding around them.
+        assert_eq2!(areas.hit_test(position!(col_index: 5, row_index: 1)), None);
+        assert_eq2!(areas.hit_test(position!(col_index: 0, row_index: 0)), None);
+    }
+
+    #[test]
+    fn test_hit_test_returns_topmost() {
+        let mut areas = HitTestAreas::default();
+        let full_box = make_flex_box(
+            position!(col_index: 0, row_index: 0),
+            size!(col_count: 10, row_count: 10),
+        );
+        let dialog_box = make_flex_box(
+            position!(col_index: 2, row_index: 2),
+            size!(col_count: 4, row_count: 4),
+        );
+
+        // The dialog is painted in the glass layer, so it is on top, even though it is
+        // rendered first.
+        areas.record(
+            FlexBoxId::from(2),
+            &dialog_box,
+            &make_pipeline(ZOrder::Glass),
+        );
+        areas.record(
+            FlexBoxId::from(1),
+            &full_box,
+            &make_pipeline(ZOrder::Normal),
+        );
+        let pos = position!(col_index: 3, row_index: 3);
+        assert_eq2!(
+            areas.hit_test(pos).map(|it| it.id),
+            Some(FlexBoxId::from(2))
+        );
+
+        // In the same layer, the one that was rendered last is on top.
+        areas.clear();
+        areas.record(
+            FlexBoxId::from(2),
+            &dialog_box,
+            &make_pipeline(ZOrder::Normal),
+        );
+        areas.record(
+            FlexBoxId::from(1),
+            &full_box,
+            &make_pipeline(ZOrder::Normal),
+        );
+        assert_eq2!(
+            areas.hit_test(pos).map(|it| it.id),
+            Some(FlexBoxId::from(1))
+        );
+    }
+
+    #[test]
+    fn test_hit_test_box_in_offset_surface_with_padding() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface {
+                stylesheet: tui_stylesheet! { tui_style! { id: 1 padding: 1 } },
+                ..Default::default()
+            };
+            // Eg: a centered text column.
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 10, row_index: 1),
+                size: size!(col_count: 20, row_count: 5),
+            })?;
+            box_start! {
+                in:                     surface,
+                id:                     FlexBoxId::from(1),
+                dir:                    LayoutDirection::Vertical,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                styles:                 [1]
+            }
+            let mut areas = HitTestAreas::default();
+            areas.record(
+                FlexBoxId::from(1),
+                surface.current_box()?,
+                &make_pipeline(ZOrder::Normal),
+            );
+            box_end!(in: surface);
+            surface.surface_end()?;
+
+            assert_eq2!(
+                areas.hit_test(position!(col_index: 11, row_index: 2)),
+                Some(HitTestResult {
+                    id: FlexBoxId::from(1),
+                    local_pos: position!(col_index: 0, row_index: 0),
+                })
+            );
+            // The padding.
+            assert_eq2!(areas.hit_test(position!(col_index: 10, row_index: 1)), None);
+        });
+    }
+}
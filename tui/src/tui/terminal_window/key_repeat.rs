@@ -0,0 +1,288 @@
+/*
+ *   Copyright (c) 2025 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! When a key is held down, the terminal sends a stream of identical key events. If the
+//! app is slower to handle (and render) each one than the key repeat rate, these events
+//! pile up, and the app keeps moving (eg: scrolling) long after the key is released.
+//!
+//! Key repeat coalescing fixes this by collapsing a run of identical key events that
+//! are already waiting to be handled into a single event, and its repeat count, which
+//! is available to the app in [GlobalData::input_event_repeat_count]. Opt in by
+//! returning a [KeyRepeatCoalescing] from [App::app_key_repeat_coalescing].
+//!
+//! Only the app and the components that opt in w/
+//! [Component::supports_input_event_repeat_count] read the repeat count. Any other
+//! focused component has the event replayed to it once for each repeat, so it behaves
+//! just as if nothing was coalesced.
+//!
+//! Whether a key is coalesced depends on its [KeyRepeatCategory]. Keys that insert text
+//! are never coalesced by default, since every character that is typed matters to the
+//! app, and it is easy to get them wrong if the repeat count is ignored.
+//!
+//! [GlobalData::input_event_repeat_count]: crate::GlobalData::input_event_repeat_count
+//! [App::app_key_repeat_coalescing]: crate::App::app_key_repeat_coalescing
+//! [Component::supports_input_event_repeat_count]: crate::Component::supports_input_event_repeat_count
+
+use std::time::Duration;
+
+use r3bl_core::InputDevice;
+
+use crate::{InputDeviceExt, InputEvent, Key, KeyPress, KeyState, SpecialKey};
+
+/// The kinds of keys that [KeyRepeatCoalescing] can be turned on and off for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyRepeatCategory {
+    /// Arrow keys, `Home`, `End`, `PageUp` and `PageDown`, w/ or w/out modifiers.
+    Navigation,
+    /// Displayable characters (w/out `Ctrl` or `Alt`), `Enter` and `Tab`.
+    TextInsertion,
+    /// `Backspace` and `Delete`.
+    Editing,
+    /// All other keys, eg: function keys, and characters w/ `Ctrl` or `Alt`.
+    Other,
+}
+
+impl KeyRepeatCategory {
+    pub fn from_key_press(key_press: KeyPress) -> Self {
+        let (key, has_ctrl_or_alt) = match key_press {
+            KeyPress::Plain { key } => (key, false),
+            KeyPress::WithModifiers { key, mask } => {
+                let has_ctrl_or_alt = mask.ctrl_key_state == KeyState::Pressed
+                    || mask.alt_key_state == KeyState::Pressed;
+                (key, has_ctrl_or_alt)
+            }
+        };
+        match key {
+            Key::SpecialKey(
+                SpecialKey::Left
+                | SpecialKey::Right
+                | SpecialKey::Up
+                | SpecialKey::Down
+                | SpecialKey::Home
+                | SpecialKey::End
+                | SpecialKey::PageUp
+                | SpecialKey::PageDown,
+            ) => Self::Navigation,
+            Key::SpecialKey(SpecialKey::Backspace | SpecialKey::Delete) => Self::Editing,
+            Key::Character(_) | Key::SpecialKey(SpecialKey::Enter | SpecialKey::Tab)
+                if !has_ctrl_or_alt =>
+            {
+                Self::TextInsertion
+            }
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Configures which keys are coalesced when they are held down. The defaults only
+/// coalesce [KeyRepeatCategory::Navigation] keys, and don't wait for more events to
+/// arrive, so only the events that are already queued up are coalesced, and there is
+/// no added latency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyRepeatCoalescing {
+    /// How long to wait for the next identical event, before handling the ones that
+    /// have been collected so far. [Duration::ZERO] only coalesces the events that are
+    /// ready right now.
+    pub max_wait: Duration,
+    /// The most events that are coalesced into one.
+    pub max_count: usize,
+    pub navigation: bool,
+    pub text_insertion: bool,
+    pub editing: bool,
+    pub other: bool,
+}
+
+impl Default for KeyRepeatCoalescing {
+    fn default() -> Self {
+        Self {
+            max_wait: Duration::ZERO,
+            max_count: 32,
+            navigation: true,
+            text_insertion: false,
+            editing: false,
+            other: false,
+        }
+    }
+}
+
+impl KeyRepeatCoalescing {
+    pub fn is_enabled_for(&self, input_event: &InputEvent) -> bool {
+        let InputEvent::Keyboard(key_press) = *input_event else {
+            return false;
+        };
+        match KeyRepeatCategory::from_key_press(key_press) {
+            KeyRepeatCategory::Navigation => self.navigation,
+            KeyRepeatCategory::TextInsertion => self.text_insertion,
+            KeyRepeatCategory::Editing => self.editing,
+            KeyRepeatCategory::Other => self.other,
+        }
+    }
+}
+
+/// The result of [coalesce_key_repeats].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoalescedInputEvent {
+    pub input_event: InputEvent,
+    pub repeat_count: usize,
+    /// What ended the run of identical events.
+    pub next: CoalesceEnd,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CoalesceEnd {
+    /// No more events arrived in time, or [KeyRepeatCoalescing::max_count] was
+    /// reached.
+    NoMoreEvents,
+    /// A different event arrived. It has to be handled right after the coalesced one.
+    DifferentEvent(InputEvent),
+    /// The input device has no more events.
+    EndOfStream,
+}
+
+/// Read the identical events that follow `input_event` from the `input_device` (if it
+/// is coalesced, based on `config`) and count them.
+pub async fn coalesce_key_repeats(
+    input_device: &mut InputDevice,
+    input_event: InputEvent,
+    config: &KeyRepeatCoalescing,
+) -> CoalescedInputEvent {
+    let mut it = CoalescedInputEvent {
+        input_event,
+        repeat_count: 1,
+        next: CoalesceEnd::NoMoreEvents,
+    };
+
+    if !config.is_enabled_for(&it.input_event) {
+        return it;
+    }
+
+    while it.repeat_count < config.max_count {
+        // The future is polled once before the timeout is checked, so an event that is
+        // ready is always read, even w/ a zero wait.
+        match tokio::time::timeout(config.max_wait, input_device.next_input_event()).await
+        {
+            Err(_elapsed) => break,
+            Ok(None) => {
+                it.next = CoalesceEnd::EndOfStream;
+                break;
+            }
+            Ok(Some(next_event)) if next_event == it.input_event => it.repeat_count += 1,
+            Ok(Some(next_event)) => {
+                it.next = CoalesceEnd::DifferentEvent(next_event);
+                break;
+            }
+        }
+    }
+
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+    use r3bl_core::{CrosstermEventResult, InputDevice};
+    use r3bl_test_fixtures::InputDeviceExt as _;
+
+    use super::*;
+    use crate::{keypress, FunctionKey};
+
+    fn key(key_press: KeyPress) -> InputEvent { InputEvent::Keyboard(key_press) }
+
+    #[test]
+    fn test_key_repeat_category() {
+        assert_eq!(
+            KeyRepeatCategory::from_key_press(keypress!(@special SpecialKey::Down)),
+            KeyRepeatCategory::Navigation
+        );
+        assert_eq!(
+            KeyRepeatCategory::from_key_press(keypress!(@char 'a')),
+            KeyRepeatCategory::TextInsertion
+        );
+        assert_eq!(
+            KeyRepeatCategory::from_key_press(keypress!(@special SpecialKey::Backspace)),
+            KeyRepeatCategory::Editing
+        );
+        assert_eq!(
+            KeyRepeatCategory::from_key_press(keypress!(@fn FunctionKey::F1)),
+            KeyRepeatCategory::Other
+        );
+    }
+
+    #[test]
+    fn test_default_only_coalesces_navigation() {
+        let config = KeyRepeatCoalescing::default();
+        assert!(config.is_enabled_for(&key(keypress!(@special SpecialKey::PageDown))));
+        assert!(!config.is_enabled_for(&key(keypress!(@char 'a'))));
+        assert!(!config.is_enabled_for(&key(keypress!(@special SpecialKey::Delete))));
+    }
+
+    fn crossterm_key(key_code: KeyCode) -> CrosstermEventResult {
+        Ok(Event::Key(KeyEvent::new(key_code, KeyModifiers::empty())))
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_key_repeats() {
+        let mut input_device = InputDevice::new_mock(vec![
+            crossterm_key(KeyCode::Down),
+            crossterm_key(KeyCode::Down),
+            crossterm_key(KeyCode::Down),
+            crossterm_key(KeyCode::Char('a')),
+            crossterm_key(KeyCode::Char('a')),
+        ]);
+        let config = KeyRepeatCoalescing::default();
+        let down = key(keypress!(@special SpecialKey::Down));
+        let char_a = key(keypress!(@char 'a'));
+
+        // The first down has already been read.
+        let first_event = input_device.next_input_event().await.unwrap();
+        assert_eq!(first_event, down);
+        let coalesced =
+            coalesce_key_repeats(&mut input_device, down.clone(), &config).await;
+        assert_eq!(coalesced.repeat_count, 3);
+        assert_eq!(coalesced.next, CoalesceEnd::DifferentEvent(char_a.clone()));
+
+        // Text insertion isn't coalesced.
+        let coalesced = coalesce_key_repeats(&mut input_device, char_a, &config).await;
+        assert_eq!(coalesced.repeat_count, 1);
+        assert_eq!(coalesced.next, CoalesceEnd::NoMoreEvents);
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_key_repeats_max_count_and_end_of_stream() {
+        let mut input_device =
+            InputDevice::new_mock((0..4).map(|_| crossterm_key(KeyCode::Up)).collect());
+        let config = KeyRepeatCoalescing {
+            max_count: 2,
+            ..Default::default()
+        };
+        let up = key(keypress!(@special SpecialKey::Up));
+
+        input_device.next_input_event().await.unwrap();
+        let coalesced =
+            coalesce_key_repeats(&mut input_device, up.clone(), &config).await;
+        assert_eq!(coalesced.repeat_count, 2);
+        assert_eq!(coalesced.next, CoalesceEnd::NoMoreEvents);
+
+        input_device.next_input_event().await.unwrap();
+        let coalesced =
+            coalesce_key_repeats(&mut input_device, up.clone(), &config).await;
+        assert_eq!(coalesced.repeat_count, 2);
+        let coalesced =
+            coalesce_key_repeats(&mut input_device, up.clone(), &config).await;
+        assert_eq!(coalesced.next, CoalesceEnd::EndOfStream);
+    }
+}
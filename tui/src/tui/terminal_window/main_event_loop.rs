@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use std::{fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, marker::PhantomData, time::Instant};
 
 use r3bl_core::{call_if_true,
                 ch,
@@ -38,22 +38,34 @@ use r3bl_core::{call_if_true,
                 UnicodeString};
 use r3bl_macro::tui_style;
 use size_of::SizeOf as _;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 use super::{BoxedSafeApp, Continuation, DefaultInputEventHandler, EventPropagation};
-use crate::{render_pipeline,
+use crate::{assemble_esc_sequence,
+            coalesce_key_repeats,
+            render_app_init_splash,
+            render_pipeline,
             telemetry_global_static,
+            AppInitContext,
+            AppInitProgress,
+            AppInitProgressReporter,
+            AppInitTask,
+            AssembledInputEvents,
+            CoalesceEnd,
+            CoalescedInputEvent,
             ComponentRegistryMap,
             Flush as _,
             FlushKind,
             GlobalData,
             HasFocus,
+            InitFailurePolicy,
             InputDeviceExt,
+            InputDuringInit,
             InputEvent,
-            MinSize,
             RawMode,
             RenderOp,
             RenderPipeline,
+            RenderRequest,
             TerminalWindowMainThreadSignal,
             ZOrder,
             DEBUG_TUI_MOD};
@@ -106,8 +118,41 @@ where
     let component_registry_map = &mut ComponentRegistryMap::default();
     let has_focus = &mut HasFocus::default();
 
-    // Init the app, and perform first render.
+    // Init the app.
     app.app_init(component_registry_map, has_focus);
+
+    // Run the app's async init (if any), while painting a splash screen.
+    let (progress_reporter, progress_receiver) = AppInitProgressReporter::new();
+    let maybe_app_init_task = app.app_init_async(AppInitContext {
+        main_thread_channel_sender: main_thread_channel_sender.clone(),
+        progress_reporter,
+    });
+    let mut buffered_input_events = vec![];
+    if let Some(app_init_task) = maybe_app_init_task {
+        let on_failure = app_init_task.on_failure;
+        let result = run_app_init_task(
+            app_init_task,
+            progress_receiver,
+            global_data_ref,
+            &mut input_device,
+            &output_device,
+            &mut buffered_input_events,
+        )
+        .await;
+        if let Err(error) = result {
+            tracing::error!("main_event_loop -> app_init_async. Error: {error}");
+            if let InitFailurePolicy::AbortStartup = on_failure {
+                RawMode::end(
+                    global_data_ref.window_size,
+                    output_device_as_mut!(output_device),
+                    output_device.is_mock,
+                );
+                return Err(error);
+            }
+        }
+    }
+
+    // Perform first render.
     AppManager::render_app(
         app,
         global_data_ref,
@@ -117,11 +162,45 @@ where
         output_device.is_mock,
     )?;
 
+    // Handle the input events that were buffered during the async init.
+    for input_event in buffered_input_events {
+        actually_process_input_event(
+            global_data_ref,
+            app,
+            input_event,
+            &exit_keys,
+            component_registry_map,
+            has_focus,
+            output_device_as_mut!(output_device),
+            output_device.is_mock,
+        );
+    }
+
     global_data_ref.dump_to_log("main_event_loop -> Startup 🚀");
 
     // Main event loop.
     loop {
+        let maybe_pending_render_deadline =
+            app.app_render_throttle().and_then(|throttle| {
+                global_data_ref
+                    .render_throttle_state
+                    .get_pending_render_deadline(&throttle)
+            });
+
         tokio::select! {
+            // Perform the render that was deferred by the render throttle (if any).
+            // This branch is cancel safe since sleep is cancel safe.
+            _ = sleep_until_deadline(maybe_pending_render_deadline) => {
+                AppManager::render_app(
+                    app,
+                    global_data_ref,
+                    component_registry_map,
+                    has_focus,
+                    output_device_as_mut!(output_device),
+                    output_device.is_mock,
+                )?;
+            }
+
             // Handle signals on the channel.
             // This branch is cancel safe since recv is cancel safe.
             maybe_signal = main_thread_channel_receiver.recv() => {
@@ -137,7 +216,7 @@ where
                             break;
                         },
                         TerminalWindowMainThreadSignal::Render(_) => {
-                            AppManager::render_app(
+                            AppManager::request_render_app(
                                 app,
                                 global_data_ref,
                                 component_registry_map,
@@ -172,32 +251,83 @@ where
             //   pinned_input_stream isn't used and the state isn't modified.
             maybe_input_event = input_device.next_input_event() => {
                 if let Some(input_event) = maybe_input_event {
-                    telemetry_global_static::set_start_ts();
-
-                    call_if_true!(DEBUG_TUI_MOD, {
-                        if let InputEvent::Keyboard(_)= input_event {
-                            tracing::info!("main_event_loop -> Tick: ⏰ {input_event}");
+                    // Assemble an escape sequence that arrived split up, if this is a
+                    // bare Esc (and the app opts in).
+                    let assembled = match app.app_esc_timeout() {
+                        Some(config) => {
+                            assemble_esc_sequence(&mut input_device, input_event, &config)
+                                .await
                         }
-                    });
+                        None => AssembledInputEvents::from(input_event),
+                    };
+                    let mut is_end_of_stream = assembled.is_end_of_stream;
+
+                    // Coalesce the identical key events that are queued up behind this
+                    // one (if the app opts in). A different event that ends the run is
+                    // handled right after it.
+                    let mut input_events = vec![];
+                    if let ([input_event], false) =
+                        (assembled.input_events.as_slice(), is_end_of_stream)
+                    {
+                        let coalesced = match app.app_key_repeat_coalescing() {
+                            Some(config) => {
+                                coalesce_key_repeats(&mut input_device, input_event.clone(), &config)
+                                    .await
+                            }
+                            None => CoalescedInputEvent {
+                                input_event: input_event.clone(),
+                                repeat_count: 1,
+                                next: CoalesceEnd::NoMoreEvents,
+                            },
+                        };
+                        input_events.push((coalesced.input_event, coalesced.repeat_count));
+                        match coalesced.next {
+                            CoalesceEnd::DifferentEvent(next_event) => {
+                                input_events.push((next_event, 1));
+                            }
+                            CoalesceEnd::EndOfStream => is_end_of_stream = true,
+                            CoalesceEnd::NoMoreEvents => {}
+                        }
+                    } else {
+                        input_events.extend(
+                            assembled.input_events.into_iter().map(|it| (it, 1)),
+                        );
+                    }
 
-                    handle_resize_if_applicable(input_event,
-                        global_data_ref, app,
-                        component_registry_map,
-                        has_focus,
-                        output_device_as_mut!(output_device),
-                        output_device.is_mock,
-                    );
+                    for (input_event, repeat_count) in input_events {
+                        telemetry_global_static::set_start_ts();
 
-                    actually_process_input_event(
-                        global_data_ref,
-                        app,
-                        input_event,
-                        &exit_keys,
-                        component_registry_map,
-                        has_focus,
-                        output_device_as_mut!(output_device),
-                        output_device.is_mock,
-                    );
+                        call_if_true!(DEBUG_TUI_MOD, {
+                            if let InputEvent::Keyboard(_)= input_event {
+                                tracing::info!("main_event_loop -> Tick: ⏰ {input_event} x {repeat_count}");
+                            }
+                        });
+
+                        handle_resize_if_applicable(&input_event,
+                            global_data_ref, app,
+                            component_registry_map,
+                            has_focus,
+                            output_device_as_mut!(output_device),
+                            output_device.is_mock,
+                        );
+
+                        global_data_ref.input_event_repeat_count = repeat_count;
+                        actually_process_input_event(
+                            global_data_ref,
+                            app,
+                            input_event,
+                            &exit_keys,
+                            component_registry_map,
+                            has_focus,
+                            output_device_as_mut!(output_device),
+                            output_device.is_mock,
+                        );
+                        global_data_ref.input_event_repeat_count = 1;
+                    }
+
+                    if is_end_of_stream {
+                        break;
+                    }
                 } else {
                     // There are no events in the stream, so exit. This happens in test
                     // environments with InputDevice::new_mock_with_delay() or
@@ -215,6 +345,83 @@ where
     ok!((global_data, input_device, output_device))
 }
 
+/// Await the [AppInitTask], and paint the splash screen whenever progress is reported, or
+/// the window is resized. Other input events are added to `buffered_input_events` or
+/// dropped, depending on [AppInitTask::input_during_init].
+async fn run_app_init_task<S, AS>(
+    app_init_task: AppInitTask,
+    mut progress_receiver: watch::Receiver<AppInitProgress>,
+    global_data: &mut GlobalData<S, AS>,
+    input_device: &mut InputDevice,
+    output_device: &OutputDevice,
+    buffered_input_events: &mut Vec<InputEvent>,
+) -> CommonResult<()>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    let AppInitTask {
+        mut future,
+        input_during_init,
+        ..
+    } = app_init_task;
+
+    let progress = progress_receiver.borrow_and_update().clone();
+    paint_app_init_splash(&progress, global_data, output_device);
+
+    // Once the input device runs out of events, stop polling it.
+    let mut is_input_device_done = false;
+
+    loop {
+        tokio::select! {
+            result = &mut future => {
+                return result;
+            }
+
+            // This branch is disabled when the progress reporter is dropped.
+            Ok(()) = progress_receiver.changed() => {
+                let progress = progress_receiver.borrow_and_update().clone();
+                paint_app_init_splash(&progress, global_data, output_device);
+            }
+
+            maybe_input_event = input_device.next_input_event(), if !is_input_device_done => {
+                match maybe_input_event {
+                    Some(InputEvent::Resize(new_size)) => {
+                        global_data.set_size(new_size);
+                        global_data.maybe_saved_offscreen_buffer = None;
+                        let progress = progress_receiver.borrow().clone();
+                        paint_app_init_splash(&progress, global_data, output_device);
+                    }
+                    Some(input_event) => {
+                        if let InputDuringInit::Buffer = input_during_init {
+                            buffered_input_events.push(input_event);
+                        }
+                    }
+                    None => {
+                        is_input_device_done = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn paint_app_init_splash<S, AS>(
+    progress: &AppInitProgress,
+    global_data: &mut GlobalData<S, AS>,
+    output_device: &OutputDevice,
+) where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    render_app_init_splash(global_data.window_size, progress).paint(
+        FlushKind::ClearBeforeFlush,
+        global_data,
+        output_device_as_mut!(output_device),
+        output_device.is_mock,
+    );
+}
+
 #[allow(clippy::too_many_arguments)]
 fn actually_process_input_event<S, AS>(
     global_data: &mut GlobalData<S, AS>,
@@ -229,12 +436,21 @@ fn actually_process_input_event<S, AS>(
     S: Debug + Default + Clone + Sync + Send,
     AS: Debug + Default + Clone + Sync + Send + 'static,
 {
-    let result = app.app_handle_input_event(
-        input_event,
-        global_data,
-        component_registry_map,
-        has_focus,
+    // While the app isn't shown, it doesn't get any input events that could change its
+    // state, except for resizes. The exit keys still work.
+    let is_window_too_small = matches!(
+        fits_min_size(global_data.window_size, app.app_min_size()),
+        TooSmallToDisplayResult::IsTooSmall
     );
+    let result = match (is_window_too_small, &input_event) {
+        (true, InputEvent::Resize(_)) | (false, _) => app.app_handle_input_event(
+            input_event.clone(),
+            global_data,
+            component_registry_map,
+            has_focus,
+        ),
+        (true, _) => Ok(EventPropagation::Propagate),
+    };
 
     handle_result_generated_by_app_after_handling_action_or_input_event(
         result,
@@ -252,7 +468,7 @@ fn actually_process_input_event<S, AS>(
 /// Before any app gets to process the `input_event`, perform special handling in case
 /// it is a resize event.
 pub fn handle_resize_if_applicable<S, AS>(
-    input_event: InputEvent,
+    input_event: &InputEvent,
     global_data: &mut GlobalData<S, AS>,
     app: &mut BoxedSafeApp<S, AS>,
     component_registry_map: &mut ComponentRegistryMap<S, AS>,
@@ -263,7 +479,7 @@ pub fn handle_resize_if_applicable<S, AS>(
     S: Debug + Default + Clone + Sync + Send,
     AS: Debug + Default + Clone + Sync + Send,
 {
-    if let InputEvent::Resize(new_size) = input_event {
+    if let InputEvent::Resize(new_size) = *input_event {
         global_data.set_size(new_size);
         global_data.maybe_saved_offscreen_buffer = None;
         let _ = AppManager::render_app(
@@ -299,7 +515,7 @@ fn handle_result_generated_by_app_after_handling_action_or_input_event<S, AS>(
             EventPropagation::Propagate => {
                 if let Some(input_event) = maybe_input_event {
                     let check_if_exit_keys_pressed =
-                        DefaultInputEventHandler::no_consume(input_event, exit_keys);
+                        DefaultInputEventHandler::no_consume(&input_event, exit_keys);
                     if let Continuation::Exit = check_if_exit_keys_pressed {
                         request_exit_by_sending_signal(main_thread_channel_sender);
                     };
@@ -307,7 +523,7 @@ fn handle_result_generated_by_app_after_handling_action_or_input_event<S, AS>(
             }
 
             EventPropagation::ConsumedRender => {
-                let _ = AppManager::render_app(
+                let _ = AppManager::request_render_app(
                     app,
                     global_data,
                     component_registry_map,
@@ -329,6 +545,14 @@ fn handle_result_generated_by_app_after_handling_action_or_input_event<S, AS>(
     }
 }
 
+/// Resolves at the `maybe_deadline`, or never if it is [None].
+async fn sleep_until_deadline(maybe_deadline: Option<Instant>) {
+    match maybe_deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
 fn request_exit_by_sending_signal<AS>(
     channel_sender: mpsc::Sender<TerminalWindowMainThreadSignal<AS>>,
 ) where
@@ -357,6 +581,36 @@ where
     S: Debug + Default + Clone + Sync + Send,
     AS: Debug + Default + Clone + Sync + Send,
 {
+    /// Render the app, unless it opts into [crate::App::app_render_throttle] and was
+    /// rendered too recently. Then the render is deferred, and performed by the main
+    /// event loop once the interval has elapsed.
+    pub fn request_render_app(
+        app: &mut BoxedSafeApp<S, AS>,
+        global_data: &mut GlobalData<S, AS>,
+        component_registry_map: &mut ComponentRegistryMap<S, AS>,
+        has_focus: &mut HasFocus,
+        locked_output_device: LockedOutputDevice<'_>,
+        is_mock: bool,
+    ) -> CommonResult<()> {
+        if let Some(throttle) = app.app_render_throttle() {
+            let render_request = global_data
+                .render_throttle_state
+                .request_render(Instant::now(), &throttle);
+            if let RenderRequest::Deferred = render_request {
+                return Ok(());
+            }
+        }
+
+        Self::render_app(
+            app,
+            global_data,
+            component_registry_map,
+            has_focus,
+            locked_output_device,
+            is_mock,
+        )
+    }
+
     pub fn render_app(
         app: &mut BoxedSafeApp<S, AS>,
         global_data: &mut GlobalData<S, AS>,
@@ -368,17 +622,28 @@ where
         throws!({
             let window_size = global_data.window_size;
 
+            global_data
+                .render_throttle_state
+                .on_render_start(Instant::now());
+
+            if let Some(render_profiler) = global_data.maybe_render_profiler.as_mut() {
+                render_profiler.begin_frame();
+            }
+
+            // The components record their boxes as they are rendered.
+            global_data.hit_test_areas.clear();
+
             // Check to see if the window_size is large enough to render.
-            let render_result =
-                match window_size.fits_min_size(MinSize::Col as u8, MinSize::Row as u8) {
-                    TooSmallToDisplayResult::IsLargeEnough => {
-                        app.app_render(global_data, component_registry_map, has_focus)
-                    }
-                    TooSmallToDisplayResult::IsTooSmall => {
-                        global_data.maybe_saved_offscreen_buffer = None;
-                        Ok(render_window_too_small_error(window_size))
-                    }
-                };
+            let min_size = app.app_min_size();
+            let render_result = match fits_min_size(window_size, min_size) {
+                TooSmallToDisplayResult::IsLargeEnough => {
+                    app.app_render(global_data, component_registry_map, has_focus)
+                }
+                TooSmallToDisplayResult::IsTooSmall => {
+                    global_data.maybe_saved_offscreen_buffer = None;
+                    Ok(render_window_too_small_error(window_size, min_size))
+                }
+            };
 
             match render_result {
                 Err(error) => {
@@ -423,19 +688,53 @@ where
                     });
                 }
             }
+
+            if let Some(render_profiler) = global_data.maybe_render_profiler.as_mut() {
+                render_profiler.end_frame();
+            }
         });
     }
 }
 
-fn render_window_too_small_error(window_size: Size) -> RenderPipeline {
+fn fits_min_size(window_size: Size, min_size: Size) -> TooSmallToDisplayResult {
+    match window_size.col_count < min_size.col_count
+        || window_size.row_count < min_size.row_count
+    {
+        false => TooSmallToDisplayResult::IsLargeEnough,
+        true => TooSmallToDisplayResult::IsTooSmall,
+    }
+}
+
+/// Returns the longest message that fits in the width of the window. If even the
+/// shortest one doesn't fit, then it is truncated.
+fn get_window_too_small_message(window_size: Size, min_size: Size) -> UnicodeString {
+    let Size {
+        col_count: min_col_count,
+        row_count: min_row_count,
+    } = min_size;
+    let messages = [
+        format!(
+            "Terminal is too small, please resize it (need at least {min_col_count}x{min_row_count})"
+        ),
+        format!("Too small, need at least {min_col_count}x{min_row_count}"),
+        format!("Need {min_col_count}x{min_row_count}"),
+    ];
+    let maybe_fitting_message = messages.iter().find(|message| {
+        UnicodeString::from(message.as_str()).display_width <= window_size.col_count
+    });
+    match maybe_fitting_message {
+        Some(message) => UnicodeString::from(message.as_str()),
+        None => {
+            let shortest_message =
+                UnicodeString::from(messages[messages.len() - 1].as_str());
+            UnicodeString::from(shortest_message.truncate_to_fit_size(window_size))
+        }
+    }
+}
+
+fn render_window_too_small_error(window_size: Size, min_size: Size) -> RenderPipeline {
     // Show warning message that window_size is too small.
-    let display_msg = UnicodeString::from(format!(
-        "Window size is too small. Minimum size is {} cols x {} rows",
-        MinSize::Col as u8,
-        MinSize::Row as u8
-    ));
-    let trunc_display_msg =
-        UnicodeString::from(display_msg.truncate_to_fit_size(window_size));
+    let trunc_display_msg = get_window_too_small_message(window_size, min_size);
     let trunc_display_msg_len = ch!(trunc_display_msg.len());
 
     let row_pos = window_size.row_count / 2;
@@ -482,6 +781,7 @@ mod tests {
                     ch,
                     color,
                     ok,
+                    percent,
                     position,
                     send_signal,
                     size,
@@ -492,6 +792,8 @@ mod tests {
                     ColorWheel,
                     ColorWheelConfig,
                     ColorWheelSpeed,
+                    CommonError,
+                    CommonErrorType,
                     CommonResult,
                     CrosstermEventResult,
                     GradientGenerationPolicy,
@@ -508,16 +810,20 @@ mod tests {
     use size::Size;
     use state::{AppSignal, State};
 
+    use super::get_window_too_small_message;
     use crate::{keypress,
                 main_event_loop_impl,
                 render_ops,
                 render_pipeline,
                 render_tui_styled_texts_into,
                 App,
+                AppInitContext,
+                AppInitTask,
                 ComponentRegistryMap,
                 EventPropagation,
                 GlobalData,
                 HasFocus,
+                InputDuringInit,
                 InputEvent,
                 Key,
                 KeyPress,
@@ -528,6 +834,74 @@ mod tests {
                 TerminalWindowMainThreadSignal,
                 ZOrder};
 
+    #[test]
+    fn test_get_window_too_small_message() {
+        let min_size = size!(col_count: 65, row_count: 11);
+        let get_message = |col_count: u8| {
+            let window_size = size!(col_count: col_count, row_count: 5);
+            get_window_too_small_message(window_size, min_size).string
+        };
+
+        assert_eq2!(
+            get_message(64),
+            "Terminal is too small, please resize it (need at least 65x11)"
+        );
+        assert_eq2!(get_message(40), "Too small, need at least 65x11");
+        assert_eq2!(get_message(10), "Need 65x11");
+        assert_eq2!(get_message(6), "Need 6");
+    }
+
+    #[tokio::test]
+    async fn test_main_event_loop_impl_with_window_too_small() {
+        let app = Box::<AppMain>::default();
+        let exit_keys: Vec<InputEvent> =
+            vec![InputEvent::Keyboard(keypress! { @char 'x' })];
+
+        // The first up is not passed to the app, since it isn't shown.
+        let generator_vec: Vec<CrosstermEventResult> = vec![
+            Ok(crossterm::event::Event::Key(
+                crossterm::event::KeyEvent::new(
+                    crossterm::event::KeyCode::Up,
+                    crossterm::event::KeyModifiers::empty(),
+                ),
+            )),
+            Ok(crossterm::event::Event::Resize(65, 11)),
+            Ok(crossterm::event::Event::Key(
+                crossterm::event::KeyEvent::new(
+                    crossterm::event::KeyCode::Up,
+                    crossterm::event::KeyModifiers::empty(),
+                ),
+            )),
+            Ok(crossterm::event::Event::Key(
+                crossterm::event::KeyEvent::new(
+                    crossterm::event::KeyCode::Char('x'),
+                    crossterm::event::KeyModifiers::empty(),
+                ),
+            )),
+        ];
+
+        let initial_size = size!(col_count: 40, row_count: 11);
+        let input_device =
+            InputDevice::new_mock_with_delay(generator_vec, Duration::from_millis(10));
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+
+        let (global_data, _, _) = main_event_loop_impl(
+            app,
+            exit_keys,
+            State::default(),
+            initial_size,
+            input_device,
+            output_device,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(global_data.state.counter, 1);
+        let output = stdout_mock.get_copy_of_buffer_as_string_strip_ansi();
+        assert!(output.contains("Too small, need at least 65x11"));
+        assert!(output.contains("State{counter:1}"));
+    }
+
     #[tokio::test]
     #[allow(clippy::needless_return)]
     async fn test_main_event_loop_impl() -> CommonResult<()> {
@@ -604,10 +978,8 @@ mod tests {
         if let TTYResult::IsNotInteractive = is_fully_uninteractive_terminal() {
             // Check pixel char at 4 x 7.
             {
-                let PixelChar::PlainText {
-                    content,
-                    maybe_style: _,
-                } = my_offscreen_buffer.buffer[4][7].clone()
+                let PixelChar::PlainText { content, .. } =
+                    my_offscreen_buffer.buffer[4][7].clone()
                 else {
                     panic!(
                         "Expected PixelChar::PlainText, got: {:?}",
@@ -619,10 +991,8 @@ mod tests {
 
             // Check pixel char at 10 x 7.
             {
-                let PixelChar::PlainText {
-                    content,
-                    maybe_style: _,
-                } = my_offscreen_buffer.buffer[10][7].clone()
+                let PixelChar::PlainText { content, .. } =
+                    my_offscreen_buffer.buffer[10][7].clone()
                 else {
                     panic!(
                         "Expected PixelChar::PlainText, got: {:?}",
@@ -643,6 +1013,7 @@ mod tests {
                             color_fg: Some(color!(102, 0, 255)),
                             ..Default::default()
                         }),
+                        maybe_hyperlink: None,
                     },
                     my_offscreen_buffer.buffer[4][7].clone()
                 );
@@ -658,6 +1029,7 @@ mod tests {
                             dim: true,
                             ..Default::default()
                         }),
+                        maybe_hyperlink: None,
                     },
                     my_offscreen_buffer.buffer[10][7].clone()
                 );
@@ -667,6 +1039,82 @@ mod tests {
         ok!()
     }
 
+    /// Simulated key inputs (↑, ↑, x). The async init takes 50ms, so the ↑ keys arrive
+    /// while it is running, and x arrives after it is done.
+    fn create_up_up_x_input_device() -> InputDevice {
+        let generator_vec: Vec<CrosstermEventResult> = [
+            crossterm::event::KeyCode::Up,
+            crossterm::event::KeyCode::Up,
+            crossterm::event::KeyCode::Char('x'),
+        ]
+        .into_iter()
+        .map(|key_code| {
+            Ok(crossterm::event::Event::Key(
+                crossterm::event::KeyEvent::new(
+                    key_code,
+                    crossterm::event::KeyModifiers::empty(),
+                ),
+            ))
+        })
+        .collect();
+        InputDevice::new_mock_with_delay(generator_vec, Duration::from_millis(20))
+    }
+
+    #[tokio::test]
+    async fn test_main_event_loop_impl_with_async_init() {
+        let app = Box::new(AppMain {
+            data: AppData {
+                maybe_async_init_kind: Some(AsyncInitKind::Succeed),
+                ..Default::default()
+            },
+        });
+        let exit_keys: Vec<InputEvent> =
+            vec![InputEvent::Keyboard(keypress! { @char 'x' })];
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+
+        let (global_data, _, _) = main_event_loop_impl(
+            app,
+            exit_keys,
+            State::default(),
+            size!(col_count: 65, row_count: 11),
+            create_up_up_x_input_device(),
+            output_device,
+        )
+        .await
+        .unwrap();
+
+        // The splash screen showed the progress.
+        let output = stdout_mock.get_copy_of_buffer_as_string_strip_ansi();
+        assert!(output.contains("Loading data"));
+        assert!(output.contains(" 50%"));
+
+        // The signal sent by the init, and the buffered input events, were all handled.
+        assert_eq!(global_data.state.counter, 3);
+    }
+
+    #[tokio::test]
+    async fn test_main_event_loop_impl_with_failed_async_init() {
+        let app = Box::new(AppMain {
+            data: AppData {
+                maybe_async_init_kind: Some(AsyncInitKind::Fail),
+                ..Default::default()
+            },
+        });
+        let (output_device, _) = OutputDevice::new_mock();
+
+        let result = main_event_loop_impl(
+            app,
+            vec![],
+            State::default(),
+            size!(col_count: 65, row_count: 11),
+            create_up_up_x_input_device(),
+            output_device,
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+
     mod state {
         use super::*;
 
@@ -709,6 +1157,13 @@ mod tests {
     #[derive(Default)]
     pub struct AppData {
         pub color_wheel_rgb: ColorWheel,
+        pub maybe_async_init_kind: Option<AsyncInitKind>,
+    }
+
+    #[derive(Clone, Copy)]
+    pub enum AsyncInitKind {
+        Succeed,
+        Fail,
     }
 
     mod app_main_impl_trait_app {
@@ -882,6 +1337,38 @@ mod tests {
                 });
             }
 
+            fn app_init_async(
+                &mut self,
+                init_context: AppInitContext<AppSignal>,
+            ) -> Option<AppInitTask> {
+                let async_init_kind = self.data.maybe_async_init_kind?;
+                let AppInitContext {
+                    main_thread_channel_sender,
+                    progress_reporter,
+                } = init_context;
+
+                let app_init_task = AppInitTask::new(async move {
+                    progress_reporter.report("Loading data", percent!(50).ok());
+                    // Let the ↑ keys arrive during init.
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    match async_init_kind {
+                        AsyncInitKind::Succeed => {
+                            let _ = main_thread_channel_sender
+                                .send(TerminalWindowMainThreadSignal::ApplyAction(
+                                    AppSignal::Add,
+                                ))
+                                .await;
+                            Ok(())
+                        }
+                        AsyncInitKind::Fail => CommonError::new_error_result(
+                            CommonErrorType::NotFound,
+                            "Failed to load data",
+                        ),
+                    }
+                });
+                Some(app_init_task.with_input_during_init(InputDuringInit::Buffer))
+            }
+
             fn app_init(
                 &mut self,
                 _component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
@@ -20,7 +20,12 @@ use std::{collections::HashMap, fmt::Debug, marker::PhantomData};
 use r3bl_core::{CommonResult, ContainsResult};
 
 use super::HasFocus;
-use crate::{BoxedSafeComponent, EventPropagation, FlexBoxId, GlobalData, InputEvent};
+use crate::{BoxedSafeComponent,
+            EventConsumption,
+            EventPropagation,
+            FlexBoxId,
+            GlobalData,
+            InputEvent};
 
 #[derive(Debug)]
 pub struct ComponentRegistry<S, AS>
@@ -119,12 +124,161 @@ where
             component_registry_map,
             has_focus,
         ) {
-            let result_event_propagation =
-                component.handle_event(global_data, input_event, has_focus)?;
+            if component.supports_input_event_repeat_count() {
+                return component.handle_event(global_data, input_event, has_focus);
+            }
+
+            // Replay a coalesced key repeat to a component that doesn't apply the repeat
+            // count on its own.
+            let repeat_count = global_data.input_event_repeat_count;
+            global_data.input_event_repeat_count = 1;
+            let mut result_event_propagation = EventPropagation::Propagate;
+            let mut needs_render = false;
+            for _ in 0..repeat_count.max(1) {
+                result_event_propagation = match component.handle_event(
+                    global_data,
+                    input_event.clone(),
+                    has_focus,
+                ) {
+                    Ok(it) => it,
+                    Err(error) => {
+                        global_data.input_event_repeat_count = repeat_count;
+                        return Err(error);
+                    }
+                };
+                match result_event_propagation {
+                    EventPropagation::ConsumedRender => needs_render = true,
+                    EventPropagation::Consumed => {}
+                    _ => break,
+                }
+            }
+            global_data.input_event_repeat_count = repeat_count;
+
+            if needs_render && result_event_propagation == EventPropagation::Consumed {
+                result_event_propagation = EventPropagation::ConsumedRender;
+            }
             Ok(result_event_propagation)
         } else {
             // input_event not handled, propagate it.
             Ok(EventPropagation::Propagate)
         }
     }
+
+    /// The side effect free counterpart to
+    /// [route_event_to_focused_component](ComponentRegistry::route_event_to_focused_component).
+    pub fn would_focused_component_consume_event(
+        global_data: &GlobalData<S, AS>,
+        input_event: InputEvent,
+        component_registry_map: &ComponentRegistryMap<S, AS>,
+        has_focus: &HasFocus,
+    ) -> EventConsumption {
+        let maybe_component = has_focus
+            .get_id()
+            .and_then(|id| component_registry_map.get(&id));
+        match maybe_component {
+            Some(component) => {
+                component.would_consume_event(global_data, input_event, has_focus)
+            }
+            None => EventConsumption::Propagate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use r3bl_core::{assert_eq2, CommonResult};
+
+    use super::*;
+    use crate::{keypress,
+                test_dialog::mock_real_objects_for_dialog,
+                Component,
+                FlexBox,
+                RenderPipeline,
+                SpecialKey,
+                SurfaceBounds};
+
+    type State = mock_real_objects_for_dialog::State;
+
+    /// Records the repeat count that it sees each time it handles an event.
+    #[derive(Default)]
+    struct MockComponent {
+        supports_repeat_count: bool,
+        seen_repeat_counts: Arc<Mutex<Vec<usize>>>,
+    }
+
+    impl Component<State, ()> for MockComponent {
+        fn reset(&mut self) {}
+
+        fn get_id(&self) -> FlexBoxId { FlexBoxId::from(1) }
+
+        fn render(
+            &mut self,
+            _global_data: &mut GlobalData<State, ()>,
+            _current_box: FlexBox,
+            _surface_bounds: SurfaceBounds,
+            _has_focus: &mut HasFocus,
+        ) -> CommonResult<RenderPipeline> {
+            Ok(RenderPipeline::default())
+        }
+
+        fn handle_event(
+            &mut self,
+            global_data: &mut GlobalData<State, ()>,
+            _input_event: InputEvent,
+            _has_focus: &mut HasFocus,
+        ) -> CommonResult<EventPropagation> {
+            if let Ok(mut it) = self.seen_repeat_counts.lock() {
+                it.push(global_data.input_event_repeat_count);
+            }
+            Ok(EventPropagation::Consumed)
+        }
+
+        fn supports_input_event_repeat_count(&self) -> bool { self.supports_repeat_count }
+    }
+
+    fn route_repeated_event(supports_repeat_count: bool) -> (Vec<usize>, usize) {
+        let (mut global_data, _) = mock_real_objects_for_dialog::make_global_data(None);
+        global_data.input_event_repeat_count = 3;
+
+        let seen_repeat_counts = Arc::new(Mutex::new(vec![]));
+        let mut map = ComponentRegistryMap::default();
+        ComponentRegistry::put(
+            &mut map,
+            FlexBoxId::from(1),
+            Box::new(MockComponent {
+                supports_repeat_count,
+                seen_repeat_counts: seen_repeat_counts.clone(),
+            }),
+        );
+        let mut has_focus = HasFocus::default();
+        has_focus.set_id(FlexBoxId::from(1));
+
+        let result = ComponentRegistry::route_event_to_focused_component(
+            &mut global_data,
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Down)),
+            &mut map,
+            &mut has_focus,
+        )
+        .unwrap();
+        assert_eq2!(result, EventPropagation::Consumed);
+
+        let it = seen_repeat_counts.lock().unwrap().clone();
+        (it, global_data.input_event_repeat_count)
+    }
+
+    #[test]
+    fn test_repeat_is_replayed_to_component_that_does_not_support_it() {
+        let (seen_repeat_counts, restored_repeat_count) = route_repeated_event(false);
+        assert_eq2!(seen_repeat_counts, vec![1, 1, 1]);
+        assert_eq2!(restored_repeat_count, 3);
+    }
+
+    #[test]
+    fn test_repeat_count_is_passed_to_component_that_supports_it() {
+        let (seen_repeat_counts, restored_repeat_count) = route_repeated_event(true);
+        assert_eq2!(seen_repeat_counts, vec![3]);
+        assert_eq2!(restored_repeat_count, 3);
+    }
 }
@@ -17,24 +17,38 @@
 
 // Attach files.
 pub mod app;
+pub mod app_init_async;
 pub mod component;
 pub mod default_input_handler;
+pub mod esc_timeout;
 pub mod event_routing_support;
+pub mod hit_test;
+pub mod key_repeat;
 pub mod main_event_loop;
 pub mod manage_focus;
 pub mod public_api;
+pub mod render_overlay;
+pub mod render_profiler;
+pub mod render_throttle;
 pub mod shared_global_data;
 pub mod static_global_data;
 pub mod type_aliases;
 
 // Re-export.
 pub use app::*;
+pub use app_init_async::*;
 pub use component::*;
 pub use default_input_handler::*;
+pub use esc_timeout::*;
 pub use event_routing_support::*;
+pub use hit_test::*;
+pub use key_repeat::*;
 pub use main_event_loop::*;
 pub use manage_focus::*;
 pub use public_api::*;
+pub use render_overlay::*;
+pub use render_profiler::*;
+pub use render_throttle::*;
 pub use shared_global_data::*;
 pub use static_global_data::*;
 pub use type_aliases::*;
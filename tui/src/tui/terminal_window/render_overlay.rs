@@ -0,0 +1,524 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Overlays are painted on top of the composited [OffscreenBuffer] of each frame, after
+//! all the components have rendered, and before it is painted to the terminal. They are
+//! useful during development (eg: a grid to line things up), or for things that aren't
+//! part of any component (eg: a watermark). Register them w/
+//! [GlobalData::add_render_overlay], and turn them on and off w/
+//! [GlobalData::toggle_render_overlay].
+//!
+//! Overlays are part of the frame, so the offscreen buffer that is saved (and diffed
+//! against the next frame) includes them. This means that the cells that an overlay
+//! covered are painted again when it is turned off.
+
+use std::{fmt::{self, Debug},
+          sync::Arc};
+
+use r3bl_core::{ch,
+                position,
+                ChUnit,
+                GraphemeClusterSegment,
+                Position,
+                Size,
+                TuiStyle,
+                UnicodeString};
+
+use crate::{GlobalData, OffscreenBuffer, PixelChar};
+
+/// Paints an overlay. It is called once for each frame (while the overlay is enabled).
+pub type RenderOverlayFn = dyn Fn(&mut OverlayPainter<'_>) + Send + Sync;
+
+/// The overlays that are registered w/ [GlobalData::add_render_overlay], in the order
+/// that they are painted.
+#[derive(Clone, Default)]
+pub struct RenderOverlays {
+    pub overlays: Vec<RenderOverlay>,
+}
+
+#[derive(Clone)]
+pub struct RenderOverlay {
+    pub name: String,
+    pub is_enabled: bool,
+    pub paint_fn: Arc<RenderOverlayFn>,
+}
+
+impl Debug for RenderOverlays {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.overlays
+                    .iter()
+                    .map(|overlay| (&overlay.name, overlay.is_enabled)),
+            )
+            .finish()
+    }
+}
+
+impl RenderOverlays {
+    /// Paint the enabled overlays on top of the given buffer.
+    pub fn paint(&self, offscreen_buffer: &mut OffscreenBuffer) {
+        for overlay in self.overlays.iter().filter(|it| it.is_enabled) {
+            (overlay.paint_fn)(&mut OverlayPainter { offscreen_buffer });
+        }
+    }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut RenderOverlay> {
+        self.overlays.iter_mut().find(|it| it.name == name)
+    }
+}
+
+impl<S, AS> GlobalData<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    /// Register an overlay, which is enabled. An overlay w/ the same `name` is replaced.
+    /// It is painted from the next frame on.
+    pub fn add_render_overlay(
+        &mut self,
+        name: impl Into<String>,
+        paint_fn: impl Fn(&mut OverlayPainter<'_>) + Send + Sync + 'static,
+    ) {
+        let overlay = RenderOverlay {
+            name: name.into(),
+            is_enabled: true,
+            paint_fn: Arc::new(paint_fn),
+        };
+        match self.render_overlays.get_mut(&overlay.name) {
+            Some(it) => *it = overlay,
+            None => self.render_overlays.overlays.push(overlay),
+        }
+    }
+
+    pub fn remove_render_overlay(&mut self, name: &str) {
+        self.render_overlays.overlays.retain(|it| it.name != name);
+    }
+
+    /// Does nothing if there's no overlay w/ this `name`.
+    pub fn set_render_overlay_enabled(&mut self, name: &str, is_enabled: bool) {
+        if let Some(it) = self.render_overlays.get_mut(name) {
+            it.is_enabled = is_enabled;
+        }
+    }
+
+    /// Returns whether the overlay is enabled now, or [None] if there's no overlay w/
+    /// this `name`.
+    pub fn toggle_render_overlay(&mut self, name: &str) -> Option<bool> {
+        let it = self.render_overlays.get_mut(name)?;
+        it.is_enabled = !it.is_enabled;
+        Some(it.is_enabled)
+    }
+}
+
+/// Passed to each overlay, to paint on the composited [OffscreenBuffer]. Painting is
+/// clipped to the window. A wide grapheme cluster (eg: an emoji) takes up more than one
+/// cell, so when any of its cells is painted over, the rest of its cells are replaced w/
+/// spacers, instead of leaving half a glyph behind.
+pub struct OverlayPainter<'a> {
+    offscreen_buffer: &'a mut OffscreenBuffer,
+}
+
+impl OverlayPainter<'_> {
+    pub fn get_window_size(&self) -> Size { self.offscreen_buffer.window_size }
+
+    pub fn get_pixel_char(&self, position: Position) -> Option<&PixelChar> {
+        self.offscreen_buffer
+            .get(ch!(@to_usize position.row_index))?
+            .get(ch!(@to_usize position.col_index))
+    }
+
+    /// Returns true if nothing is painted in this cell (or it is outside the window).
+    pub fn is_empty(&self, position: Position) -> bool {
+        matches!(
+            self.get_pixel_char(position),
+            None | Some(PixelChar::Spacer)
+        )
+    }
+
+    /// Paint the text starting at the given position. A grapheme cluster that doesn't fit
+    /// in the rest of the row isn't painted.
+    pub fn paint_text(
+        &mut self,
+        position: Position,
+        text: &str,
+        maybe_style: Option<TuiStyle>,
+    ) {
+        let window_size = self.get_window_size();
+        if position.row_index >= window_size.row_count {
+            return;
+        }
+
+        let mut col_index = position.col_index;
+        for segment in UnicodeString::from(text).vec_segment {
+            let width = segment.unicode_width;
+            if width == ch!(0) {
+                continue;
+            }
+            if col_index + width > window_size.col_count {
+                break;
+            }
+
+            for offset in 0..ch!(@to_usize width) {
+                self.clear_cell(position.row_index, col_index + ch!(offset));
+            }
+            self.set_pixel_char(
+                position.row_index,
+                col_index,
+                PixelChar::PlainText {
+                    content: GraphemeClusterSegment::from(segment.string.as_str()),
+                    maybe_style,
+                    maybe_hyperlink: None,
+                },
+            );
+            for offset in 1..ch!(@to_usize width) {
+                self.set_pixel_char(
+                    position.row_index,
+                    col_index + ch!(offset),
+                    PixelChar::Void,
+                );
+            }
+
+            col_index += width;
+        }
+    }
+
+    /// Change the style of the cell, w/out changing its content, eg: to tint the
+    /// background. Empty cells get a space, so that the style is visible.
+    pub fn update_style(
+        &mut self,
+        position: Position,
+        update: impl FnOnce(&mut TuiStyle),
+    ) {
+        // The style of a wide grapheme cluster is stored in its first cell.
+        let Some(col_index) = self.get_start_col_index(position) else {
+            return;
+        };
+        let Some(pixel_char) = self.get_pixel_char_mut(position.row_index, col_index)
+        else {
+            return;
+        };
+
+        match pixel_char {
+            PixelChar::PlainText { maybe_style, .. } => {
+                let mut style = maybe_style.unwrap_or_default();
+                update(&mut style);
+                *maybe_style = Some(style);
+            }
+            PixelChar::Spacer => {
+                let mut style = TuiStyle::default();
+                update(&mut style);
+                *pixel_char = PixelChar::PlainText {
+                    content: GraphemeClusterSegment::from(" "),
+                    maybe_style: Some(style),
+                    maybe_hyperlink: None,
+                };
+            }
+            PixelChar::Void => {}
+        }
+    }
+
+    /// Replace the cell w/ a spacer, along w/ the rest of the cells of the wide grapheme
+    /// cluster that it is part of.
+    fn clear_cell(&mut self, row_index: ChUnit, col_index: ChUnit) {
+        let Some(start_col_index) = self
+            .get_start_col_index(position!(col_index: col_index, row_index: row_index))
+        else {
+            return;
+        };
+
+        let mut current_col_index = start_col_index;
+        loop {
+            self.set_pixel_char(row_index, current_col_index, PixelChar::Spacer);
+            current_col_index += 1;
+            if !matches!(
+                self.get_pixel_char_mut(row_index, current_col_index),
+                Some(PixelChar::Void)
+            ) {
+                break;
+            }
+        }
+    }
+
+    /// Returns the col index of the first cell of the grapheme cluster that is painted in
+    /// the given cell.
+    fn get_start_col_index(&self, position: Position) -> Option<ChUnit> {
+        let mut col_index = position.col_index;
+        loop {
+            let pixel_char = self.get_pixel_char(
+                position!(col_index: col_index, row_index: position.row_index),
+            )?;
+            if !matches!(pixel_char, PixelChar::Void) || col_index == ch!(0) {
+                return Some(col_index);
+            }
+            col_index -= 1;
+        }
+    }
+
+    fn get_pixel_char_mut(
+        &mut self,
+        row_index: ChUnit,
+        col_index: ChUnit,
+    ) -> Option<&mut PixelChar> {
+        self.offscreen_buffer
+            .get_mut(ch!(@to_usize row_index))?
+            .get_mut(ch!(@to_usize col_index))
+    }
+
+    fn set_pixel_char(&mut self, row_index: ChUnit, col_index: ChUnit, new: PixelChar) {
+        if let Some(pixel_char) = self.get_pixel_char_mut(row_index, col_index) {
+            *pixel_char = new;
+        }
+    }
+}
+
+/// An overlay that paints `│` every `col_spacing` cols, and `─` every `row_spacing` rows
+/// (`┼` where they cross), in the cells that are empty. This is useful to line up the
+/// layout of an app.
+pub fn get_grid_overlay(
+    col_spacing: ChUnit,
+    row_spacing: ChUnit,
+    maybe_style: Option<TuiStyle>,
+) -> impl Fn(&mut OverlayPainter<'_>) + Send + Sync + 'static {
+    move |painter: &mut OverlayPainter<'_>| {
+        let window_size = painter.get_window_size();
+        let is_on_grid = |index: ChUnit, spacing: ChUnit| {
+            spacing > ch!(0) && index.value.is_multiple_of(spacing.value)
+        };
+
+        for row_index in 0..window_size.row_count.value {
+            for col_index in 0..window_size.col_count.value {
+                let position = position!(col_index: col_index, row_index: row_index);
+                let text = match (
+                    is_on_grid(ch!(col_index), col_spacing),
+                    is_on_grid(ch!(row_index), row_spacing),
+                ) {
+                    (true, true) => "┼",
+                    (true, false) => "│",
+                    (false, true) => "─",
+                    (false, false) => continue,
+                };
+                if painter.is_empty(position) {
+                    painter.paint_text(position, text, maybe_style);
+                }
+            }
+        }
+    }
+}
+
+/// An overlay that paints the text in the bottom right corner of the window (over
+/// whatever is painted there).
+pub fn get_watermark_overlay(
+    text: impl Into<String>,
+    maybe_style: Option<TuiStyle>,
+) -> impl Fn(&mut OverlayPainter<'_>) + Send + Sync + 'static {
+    let text = UnicodeString::from(text.into());
+    move |painter: &mut OverlayPainter<'_>| {
+        let window_size = painter.get_window_size();
+        if window_size.row_count == ch!(0) {
+            return;
+        }
+        let text = text.truncate_to_fit_size(window_size);
+        let width = UnicodeString::from(text).display_width;
+        painter.paint_text(
+            position! {
+                col_index: window_size.col_count - width,
+                row_index: window_size.row_count - 1
+            },
+            text,
+            maybe_style,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, output_device_as_mut, size};
+
+    use super::*;
+    use crate::{render_pipeline,
+                test_fixtures::mock_real_objects_for_editor,
+                FlushKind,
+                RenderPipeline};
+
+    fn plain_text(text: &str) -> PixelChar {
+        PixelChar::PlainText {
+            content: GraphemeClusterSegment::from(text),
+            maybe_style: None,
+            maybe_hyperlink: None,
+        }
+    }
+
+    /// A buffer w/ `😃a` in the first row.
+    fn make_offscreen_buffer() -> OffscreenBuffer {
+        let mut offscreen_buffer = OffscreenBuffer::new_with_capacity_initialized(
+            size!(col_count: 4, row_count: 2),
+        );
+        offscreen_buffer[0][0] = plain_text("😃");
+        offscreen_buffer[0][1] = PixelChar::Void;
+        offscreen_buffer[0][2] = plain_text("a");
+        offscreen_buffer
+    }
+
+    fn paint_text(offscreen_buffer: &mut OffscreenBuffer, col_index: usize, text: &str) {
+        OverlayPainter { offscreen_buffer }.paint_text(
+            position!(col_index: col_index, row_index: 0),
+            text,
+            None,
+        );
+    }
+
+    #[test]
+    fn test_paint_over_half_of_a_wide_char() {
+        let mut offscreen_buffer = make_offscreen_buffer();
+        paint_text(&mut offscreen_buffer, 1, "x");
+        assert_eq2!(
+            offscreen_buffer[0][..3],
+            [PixelChar::Spacer, plain_text("x"), plain_text("a")]
+        );
+
+        let mut offscreen_buffer = make_offscreen_buffer();
+        paint_text(&mut offscreen_buffer, 0, "x");
+        assert_eq2!(
+            offscreen_buffer[0][..3],
+            [plain_text("x"), PixelChar::Spacer, plain_text("a")]
+        );
+    }
+
+    #[test]
+    fn test_paint_wide_char() {
+        let mut offscreen_buffer = make_offscreen_buffer();
+        paint_text(&mut offscreen_buffer, 1, "🙂");
+        assert_eq2!(
+            offscreen_buffer[0][..4],
+            [
+                PixelChar::Spacer,
+                plain_text("🙂"),
+                PixelChar::Void,
+                PixelChar::Spacer
+            ]
+        );
+
+        // A wide char that doesn't fit in the rest of the row isn't painted.
+        let mut offscreen_buffer = make_offscreen_buffer();
+        paint_text(&mut offscreen_buffer, 2, "b🙂");
+        assert_eq2!(
+            offscreen_buffer[0][2..4],
+            [plain_text("b"), PixelChar::Spacer]
+        );
+    }
+
+    #[test]
+    fn test_update_style_of_wide_char() {
+        let mut offscreen_buffer = make_offscreen_buffer();
+        let mut painter = OverlayPainter {
+            offscreen_buffer: &mut offscreen_buffer,
+        };
+        painter.update_style(position!(col_index: 1, row_index: 0), |style| {
+            style.bold = true
+        });
+        painter.update_style(position!(col_index: 3, row_index: 0), |style| {
+            style.bold = true
+        });
+
+        let bold = Some(TuiStyle {
+            bold: true,
+            ..Default::default()
+        });
+        assert_eq2!(
+            offscreen_buffer[0][..4],
+            [
+                PixelChar::PlainText {
+                    content: GraphemeClusterSegment::from("😃"),
+                    maybe_style: bold,
+                    maybe_hyperlink: None,
+                },
+                PixelChar::Void,
+                plain_text("a"),
+                PixelChar::PlainText {
+                    content: GraphemeClusterSegment::from(" "),
+                    maybe_style: bold,
+                    maybe_hyperlink: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grid_overlay_only_paints_empty_cells() {
+        let mut offscreen_buffer = make_offscreen_buffer();
+        get_grid_overlay(ch!(2), ch!(1), None)(&mut OverlayPainter {
+            offscreen_buffer: &mut offscreen_buffer,
+        });
+        assert_eq2!(
+            offscreen_buffer[0][..4],
+            [
+                plain_text("😃"),
+                PixelChar::Void,
+                plain_text("a"),
+                plain_text("─")
+            ]
+        );
+        assert_eq2!(
+            offscreen_buffer[1][..4],
+            [
+                plain_text("┼"),
+                plain_text("─"),
+                plain_text("┼"),
+                plain_text("─")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlay_is_part_of_the_saved_frame() {
+        let (mut global_data, _) = mock_real_objects_for_editor::make_global_data::<(), ()>(
+            Some(size!(col_count: 4, row_count: 2)),
+        );
+        global_data.add_render_overlay("watermark", get_watermark_overlay("ab", None));
+
+        let paint = |global_data: &mut GlobalData<(), ()>| {
+            let pipeline: RenderPipeline = render_pipeline!();
+            let output_device = global_data.output_device.clone();
+            pipeline.paint(
+                FlushKind::JustFlush,
+                global_data,
+                output_device_as_mut!(output_device),
+                true,
+            );
+            global_data.maybe_saved_offscreen_buffer.clone().unwrap()
+        };
+
+        let offscreen_buffer = paint(&mut global_data);
+        assert_eq2!(
+            offscreen_buffer[1][2..4],
+            [plain_text("a"), plain_text("b")]
+        );
+
+        // Once it is turned off, the cells that it covered are painted again.
+        assert_eq2!(global_data.toggle_render_overlay("watermark"), Some(false));
+        let offscreen_buffer = paint(&mut global_data);
+        assert_eq2!(
+            offscreen_buffer[1][2..4],
+            [PixelChar::Spacer, PixelChar::Spacer]
+        );
+
+        assert_eq2!(global_data.toggle_render_overlay("watermark"), Some(true));
+        global_data.remove_render_overlay("watermark");
+        assert_eq2!(global_data.toggle_render_overlay("watermark"), None);
+    }
+}
@@ -0,0 +1,247 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Opt-in measurement of how much it costs to render each [crate::Component], so that
+//! apps can decide which components to show, eg: to stay within a frame budget. Turn it
+//! on w/ [GlobalData::enable_render_profiling], and read the results of the last frame
+//! w/ [GlobalData::get_last_render_profile]. When it is off (the default) nothing is
+//! measured.
+//!
+//! Generating a component's [RenderPipeline] is measured separately for each
+//! [FlexBoxId]. Compositing happens once for the whole frame, after the pipelines of all
+//! the components have been joined, so its cost is only available for the whole frame.
+
+use std::{fmt::Debug,
+          time::{Duration, Instant}};
+
+use r3bl_core::UnicodeString;
+
+use crate::{FlexBoxId, GlobalData, RenderOp, RenderPipeline};
+
+/// The cost of generating the [RenderPipeline] of a single component.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentRenderCost {
+    pub id: FlexBoxId,
+    /// How long [crate::Component::render] took.
+    pub pipeline_generation_duration: Duration,
+    /// How many [RenderOp]s are in the pipeline, across all the [crate::ZOrder]s.
+    pub render_op_count: usize,
+    /// How many pixels (display columns) the [RenderOp::PaintTextWithAttributes] ops in
+    /// the pipeline paint. Pixels that are painted more than once are counted each time.
+    pub painted_pixel_count: usize,
+}
+
+impl ComponentRenderCost {
+    pub fn new(
+        id: FlexBoxId,
+        pipeline_generation_duration: Duration,
+        pipeline: &RenderPipeline,
+    ) -> Self {
+        let mut render_op_count = 0;
+        let mut painted_pixel_count = 0;
+        for render_ops in pipeline.values().flatten() {
+            render_op_count += render_ops.len();
+            for render_op in render_ops.iter() {
+                if let RenderOp::PaintTextWithAttributes(text, _) = render_op {
+                    painted_pixel_count +=
+                        usize::from(UnicodeString::from(text.as_str()).display_width);
+                }
+            }
+        }
+        Self {
+            id,
+            pipeline_generation_duration,
+            render_op_count,
+            painted_pixel_count,
+        }
+    }
+}
+
+/// The cost of rendering a single frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderProfile {
+    /// In the order that the components were rendered. A component that is rendered
+    /// more than once in a frame has an entry for each time.
+    pub component_costs: Vec<ComponentRenderCost>,
+    /// How long it took to composite the pipeline of the whole frame into an
+    /// [crate::OffscreenBuffer]. This is [None] if the frame was not painted, eg: if
+    /// rendering failed.
+    pub maybe_composite_duration: Option<Duration>,
+}
+
+impl RenderProfile {
+    /// Add up the costs of all the times that the component w/ the given `id` was
+    /// rendered in this frame. Returns [None] if it wasn't rendered.
+    pub fn get_component_cost(&self, id: FlexBoxId) -> Option<ComponentRenderCost> {
+        self.component_costs
+            .iter()
+            .filter(|it| it.id == id)
+            .cloned()
+            .reduce(|acc, it| ComponentRenderCost {
+                pipeline_generation_duration: acc.pipeline_generation_duration
+                    + it.pipeline_generation_duration,
+                render_op_count: acc.render_op_count + it.render_op_count,
+                painted_pixel_count: acc.painted_pixel_count + it.painted_pixel_count,
+                ..acc
+            })
+    }
+
+    pub fn get_total_pipeline_generation_duration(&self) -> Duration {
+        self.component_costs
+            .iter()
+            .map(|it| it.pipeline_generation_duration)
+            .sum()
+    }
+}
+
+/// Collects the [RenderProfile] of the frame that is being rendered, and holds on to the
+/// one from the last frame that was rendered. This lives in
+/// [GlobalData::maybe_render_profiler].
+#[derive(Clone, Debug, Default)]
+pub struct RenderProfiler {
+    current_profile: RenderProfile,
+    maybe_last_profile: Option<RenderProfile>,
+}
+
+impl RenderProfiler {
+    pub fn begin_frame(&mut self) { self.current_profile = RenderProfile::default(); }
+
+    pub fn record_component_cost(&mut self, cost: ComponentRenderCost) {
+        self.current_profile.component_costs.push(cost);
+    }
+
+    pub fn record_composite_duration(&mut self, duration: Duration) {
+        self.current_profile.maybe_composite_duration = Some(duration);
+    }
+
+    pub fn end_frame(&mut self) {
+        self.maybe_last_profile = Some(std::mem::take(&mut self.current_profile));
+    }
+
+    pub fn get_last_profile(&self) -> Option<&RenderProfile> {
+        self.maybe_last_profile.as_ref()
+    }
+}
+
+impl<S, AS> GlobalData<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    /// Start measuring the cost of rendering each component, from the next frame on.
+    pub fn enable_render_profiling(&mut self) {
+        if self.maybe_render_profiler.is_none() {
+            self.maybe_render_profiler = Some(RenderProfiler::default());
+        }
+    }
+
+    /// Stop measuring, and discard the results.
+    pub fn disable_render_profiling(&mut self) { self.maybe_render_profiler = None; }
+
+    /// Returns [None] if profiling is off, or if no frame has been rendered since it was
+    /// turned on.
+    pub fn get_last_render_profile(&self) -> Option<&RenderProfile> {
+        self.maybe_render_profiler.as_ref()?.get_last_profile()
+    }
+
+    /// Returns [Some] only when profiling is on, so that callers can measure the time
+    /// that something takes w/out any overhead when it is off.
+    pub fn get_render_profiling_start(&self) -> Option<Instant> {
+        self.maybe_render_profiler.as_ref().map(|_| Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position};
+
+    use super::*;
+    use crate::{render_ops, render_pipeline, ZOrder};
+
+    fn make_pipeline() -> RenderPipeline {
+        render_pipeline!(
+            @new ZOrder::Normal
+            =>
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+                RenderOp::PaintTextWithAttributes("abc".into(), None),
+                RenderOp::ResetColor
+        )
+    }
+
+    #[test]
+    fn test_component_render_cost() {
+        let mut pipeline = make_pipeline();
+        pipeline.push(
+            ZOrder::Glass,
+            render_ops!(
+                @new
+                RenderOp::PaintTextWithAttributes("😃".into(), None)
+            ),
+        );
+
+        let cost = ComponentRenderCost::new(
+            FlexBoxId::from(1),
+            Duration::from_micros(10),
+            &pipeline,
+        );
+
+        assert_eq2!(cost.render_op_count, 4);
+        assert_eq2!(cost.painted_pixel_count, 5);
+    }
+
+    #[test]
+    fn test_render_profiler_frames() {
+        let mut profiler = RenderProfiler::default();
+        assert_eq2!(profiler.get_last_profile(), None);
+
+        let pipeline = make_pipeline();
+        profiler.begin_frame();
+        for (id, micros) in [(1, 10), (2, 20), (1, 5)] {
+            profiler.record_component_cost(ComponentRenderCost::new(
+                FlexBoxId::from(id),
+                Duration::from_micros(micros),
+                &pipeline,
+            ));
+        }
+        profiler.record_composite_duration(Duration::from_micros(7));
+        profiler.end_frame();
+
+        let profile = profiler.get_last_profile().unwrap();
+        assert_eq2!(
+            profile.get_total_pipeline_generation_duration(),
+            Duration::from_micros(35)
+        );
+        assert_eq2!(
+            profile.maybe_composite_duration,
+            Some(Duration::from_micros(7))
+        );
+        let cost = profile.get_component_cost(FlexBoxId::from(1)).unwrap();
+        assert_eq2!(cost.pipeline_generation_duration, Duration::from_micros(15));
+        assert_eq2!(cost.render_op_count, 6);
+        assert_eq2!(cost.painted_pixel_count, 6);
+        assert_eq2!(profile.get_component_cost(FlexBoxId::from(3)), None);
+
+        // A new frame doesn't replace the last one until it ends.
+        profiler.begin_frame();
+        assert_eq2!(
+            profiler.get_last_profile().unwrap().component_costs.len(),
+            3
+        );
+        profiler.end_frame();
+        assert_eq2!(profiler.get_last_profile(), Some(&RenderProfile::default()));
+    }
+}
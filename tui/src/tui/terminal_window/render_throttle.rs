@@ -0,0 +1,152 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Signals (eg: from an animation) and input events can ask for many renders in a short
+//! time, more than the terminal can show. The render throttle caps the frame rate by
+//! coalescing these requests, so that the app is rendered at most once per
+//! [RenderThrottle::min_interval]. A request that arrives too soon after the last render
+//! isn't dropped, it is deferred until the interval has elapsed. So the latest state is
+//! always rendered, and the screen isn't left stale after the last change. Opt in by
+//! returning a [RenderThrottle] from [App::app_render_throttle].
+//!
+//! Resizing the window, and the first render, aren't throttled.
+//!
+//! [App::app_render_throttle]: crate::App::app_render_throttle
+
+use std::time::{Duration, Instant};
+
+/// About 60 frames per second.
+pub const DEFAULT_RENDER_MIN_INTERVAL: Duration = Duration::from_millis(16);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderThrottle {
+    /// The least amount of time between the start of two renders.
+    pub min_interval: Duration,
+}
+
+impl Default for RenderThrottle {
+    fn default() -> Self {
+        Self {
+            min_interval: DEFAULT_RENDER_MIN_INTERVAL,
+        }
+    }
+}
+
+impl RenderThrottle {
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+}
+
+/// What to do w/ a render request, see [RenderThrottleState::request_render].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderRequest {
+    RenderNow,
+    /// The render is deferred until [RenderThrottleState::get_pending_render_deadline].
+    Deferred,
+}
+
+/// Keeps track of when the app was last rendered, and whether a render was deferred.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RenderThrottleState {
+    pub maybe_last_render_start: Option<Instant>,
+    pub is_render_pending: bool,
+}
+
+impl RenderThrottleState {
+    pub fn request_render(
+        &mut self,
+        now: Instant,
+        throttle: &RenderThrottle,
+    ) -> RenderRequest {
+        match self.maybe_last_render_start {
+            Some(last_render_start)
+                if now.duration_since(last_render_start) < throttle.min_interval =>
+            {
+                self.is_render_pending = true;
+                RenderRequest::Deferred
+            }
+            _ => RenderRequest::RenderNow,
+        }
+    }
+
+    /// Call this when a render starts (throttled or not), since it also renders the
+    /// latest state for any deferred request.
+    pub fn on_render_start(&mut self, now: Instant) {
+        self.maybe_last_render_start = Some(now);
+        self.is_render_pending = false;
+    }
+
+    /// When the deferred render (if any) is due.
+    pub fn get_pending_render_deadline(
+        &self,
+        throttle: &RenderThrottle,
+    ) -> Option<Instant> {
+        match (self.is_render_pending, self.maybe_last_render_start) {
+            (true, Some(last_render_start)) => {
+                Some(last_render_start + throttle.min_interval)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_render_throttle_coalesces_requests() {
+        let throttle =
+            RenderThrottle::default().with_min_interval(Duration::from_millis(10));
+        let start = Instant::now();
+        let mut state = RenderThrottleState::default();
+
+        // The first request is rendered right away.
+        assert_eq2!(
+            state.request_render(start, &throttle),
+            RenderRequest::RenderNow
+        );
+        state.on_render_start(start);
+        assert_eq2!(state.get_pending_render_deadline(&throttle), None);
+
+        // Requests within the interval are deferred to the end of it.
+        for millis in [1, 5, 9] {
+            assert_eq2!(
+                state.request_render(start + Duration::from_millis(millis), &throttle),
+                RenderRequest::Deferred
+            );
+        }
+        assert_eq2!(
+            state.get_pending_render_deadline(&throttle),
+            Some(start + Duration::from_millis(10))
+        );
+
+        // The deferred render happens once.
+        state.on_render_start(start + Duration::from_millis(10));
+        assert_eq2!(state.get_pending_render_deadline(&throttle), None);
+
+        // A request after the interval is rendered right away.
+        assert_eq2!(
+            state.request_render(start + Duration::from_millis(25), &throttle),
+            RenderRequest::RenderNow
+        );
+    }
+}
@@ -21,7 +21,13 @@ use r3bl_core::{call_if_true, CommonResult, OutputDevice, Size};
 use tokio::sync::mpsc::Sender;
 
 use super::TerminalWindowMainThreadSignal;
-use crate::{OffscreenBuffer, DEBUG_TUI_COMPOSITOR, DEBUG_TUI_MOD};
+use crate::{HitTestAreas,
+            OffscreenBuffer,
+            RenderOverlays,
+            RenderProfiler,
+            RenderThrottleState,
+            DEBUG_TUI_COMPOSITOR,
+            DEBUG_TUI_MOD};
 
 /// This is a global data structure that holds state for the entire application
 /// [crate::App] and the terminal window [crate::TerminalWindow] itself.
@@ -34,6 +40,8 @@ use crate::{OffscreenBuffer, DEBUG_TUI_COMPOSITOR, DEBUG_TUI_MOD};
 /// - The `output_device` is the terminal's output device (anything that implements
 ///   [r3bl_core::SafeRawTerminal] which can be [std::io::stdout] or
 ///   [r3bl_core::SharedWriter], etc.`).
+/// - The `maybe_render_profiler` is only [Some] when render profiling is turned on w/
+///   [GlobalData::enable_render_profiling]. See [crate::render_profiler] for details.
 pub struct GlobalData<S, AS>
 where
     S: Debug + Default + Clone + Sync + Send,
@@ -44,6 +52,18 @@ where
     pub main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
     pub state: S,
     pub output_device: OutputDevice,
+    pub maybe_render_profiler: Option<RenderProfiler>,
+    pub render_overlays: RenderOverlays,
+    /// How many identical key events the event that is being handled stands for. This
+    /// is `1` unless the app opts into [crate::App::app_key_repeat_coalescing]. Focused
+    /// components only see it if they opt into
+    /// [crate::Component::supports_input_event_repeat_count].
+    pub input_event_repeat_count: usize,
+    /// The boxes that components were rendered in (in the last render), for
+    /// [GlobalData::hit_test].
+    pub hit_test_areas: HitTestAreas,
+    /// When the app was last rendered, for [crate::App::app_render_throttle].
+    pub render_throttle_state: RenderThrottleState,
 }
 
 impl<S, AS> Debug for GlobalData<S, AS>
@@ -88,6 +108,11 @@ where
             state,
             main_thread_channel_sender,
             output_device,
+            maybe_render_profiler: None,
+            render_overlays: Default::default(),
+            input_event_repeat_count: 1,
+            hit_test_areas: Default::default(),
+            render_throttle_state: Default::default(),
         };
 
         it.set_size(initial_size);
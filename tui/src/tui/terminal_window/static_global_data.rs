@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 
 use chrono::Utc;
 
@@ -171,3 +171,196 @@ pub mod is_vscode_term_global_static {
         }
     }
 }
+
+/// Whether the terminal supports [OSC 8](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda)
+/// hyperlinks (see [crate::RenderOp::SetHyperlink]). Terminals that don't support them
+/// may print the escape sequences as garbage, so they are only painted when the terminal
+/// is known to support them. The app can override the detection w/
+/// [set_hyperlink_support](hyperlink_support_global_static::set_hyperlink_support).
+pub mod hyperlink_support_global_static {
+    use super::*;
+
+    pub static mut HYPERLINK_SUPPORT: AtomicI64 = AtomicI64::new(NOT_SET_VALUE);
+
+    /// The values of `TERM_PROGRAM` for terminals that support hyperlinks.
+    const TERM_PROGRAMS_W_HYPERLINKS: [&str; 5] =
+        ["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper"];
+
+    /// Env vars that are only set by terminals that support hyperlinks.
+    const ENV_KEYS_OF_TERMS_W_HYPERLINKS: [&str; 3] =
+        ["KITTY_WINDOW_ID", "WT_SESSION", "KONSOLE_VERSION"];
+
+    /// VTE based terminals (eg: GNOME Terminal) support hyperlinks since 0.50.
+    const MIN_VTE_VERSION_W_HYPERLINKS: u32 = 5000;
+
+    fn detect_hyperlink_support_from_env() -> HyperlinkSupport {
+        let is_dumb_term = matches!(std::env::var("TERM"), Ok(term) if term == "dumb");
+        let is_known_term_program = match std::env::var("TERM_PROGRAM") {
+            Ok(value) => TERM_PROGRAMS_W_HYPERLINKS.contains(&value.as_str()),
+            _ => false,
+        };
+        let has_known_env_key = ENV_KEYS_OF_TERMS_W_HYPERLINKS
+            .iter()
+            .any(|env_key| std::env::var(env_key).is_ok());
+        let is_new_vte = match std::env::var("VTE_VERSION") {
+            Ok(value) => value
+                .parse::<u32>()
+                .is_ok_and(|version| version >= MIN_VTE_VERSION_W_HYPERLINKS),
+            _ => false,
+        };
+        match !is_dumb_term && (is_known_term_program || has_known_env_key || is_new_vte)
+        {
+            true => HyperlinkSupport::Yes,
+            false => HyperlinkSupport::No,
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum HyperlinkSupport {
+        Yes,
+        No,
+    }
+
+    impl From<i64> for HyperlinkSupport {
+        fn from(value: i64) -> Self {
+            match value {
+                1 => HyperlinkSupport::Yes,
+                _ => HyperlinkSupport::No,
+            }
+        }
+    }
+
+    impl From<HyperlinkSupport> for i64 {
+        fn from(value: HyperlinkSupport) -> Self {
+            match value {
+                HyperlinkSupport::No => 0,
+                HyperlinkSupport::Yes => 1,
+            }
+        }
+    }
+
+    /// Override the detection, eg: if the user knows their terminal supports hyperlinks.
+    #[allow(static_mut_refs)]
+    pub fn set_hyperlink_support(hyperlink_support: HyperlinkSupport) {
+        unsafe {
+            HYPERLINK_SUPPORT.store(i64::from(hyperlink_support), Ordering::Release);
+        }
+    }
+
+    /// Go back to detecting the hyperlink support from the environment.
+    #[allow(static_mut_refs)]
+    pub fn clear_hyperlink_support() {
+        unsafe {
+            HYPERLINK_SUPPORT.store(NOT_SET_VALUE, Ordering::Release);
+        }
+    }
+
+    #[allow(static_mut_refs)]
+    pub fn get_hyperlink_support() -> HyperlinkSupport {
+        let existing_value = unsafe { HYPERLINK_SUPPORT.load(Ordering::Acquire) };
+
+        match existing_value == NOT_SET_VALUE {
+            // If not set, then calculate new value, save it, return it.
+            true => {
+                let hyperlink_support = detect_hyperlink_support_from_env();
+                set_hyperlink_support(hyperlink_support);
+                hyperlink_support
+            }
+
+            // Return saved value.
+            false => HyperlinkSupport::from(existing_value),
+        }
+    }
+}
+
+/// Whether the title of the terminal window can be set (see [crate::RenderOp::SetTitle]).
+/// Most terminals support this, except for dumb ones (where the escape sequences would
+/// be printed as garbage). The app can override the detection w/
+/// [set_title_support](title_support_global_static::set_title_support).
+pub mod title_support_global_static {
+    use super::*;
+
+    pub static mut TITLE_SUPPORT: AtomicI64 = AtomicI64::new(NOT_SET_VALUE);
+
+    /// Whether the title that the terminal had before the first
+    /// [crate::RenderOp::SetTitle] was saved, and must be restored on exit.
+    pub static mut IS_TITLE_SAVED: AtomicBool = AtomicBool::new(false);
+
+    fn detect_title_support_from_env() -> TitleSupport {
+        match std::env::var("TERM") {
+            Ok(term) if term == "dumb" => TitleSupport::No,
+            _ => TitleSupport::Yes,
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum TitleSupport {
+        Yes,
+        No,
+    }
+
+    impl From<i64> for TitleSupport {
+        fn from(value: i64) -> Self {
+            match value {
+                1 => TitleSupport::Yes,
+                _ => TitleSupport::No,
+            }
+        }
+    }
+
+    impl From<TitleSupport> for i64 {
+        fn from(value: TitleSupport) -> Self {
+            match value {
+                TitleSupport::No => 0,
+                TitleSupport::Yes => 1,
+            }
+        }
+    }
+
+    /// Override the detection, eg: if the user doesn't want the title to be changed.
+    #[allow(static_mut_refs)]
+    pub fn set_title_support(title_support: TitleSupport) {
+        unsafe {
+            TITLE_SUPPORT.store(i64::from(title_support), Ordering::Release);
+        }
+    }
+
+    /// Go back to detecting the title support from the environment.
+    #[allow(static_mut_refs)]
+    pub fn clear_title_support() {
+        unsafe {
+            TITLE_SUPPORT.store(NOT_SET_VALUE, Ordering::Release);
+        }
+    }
+
+    #[allow(static_mut_refs)]
+    pub fn get_title_support() -> TitleSupport {
+        let existing_value = unsafe { TITLE_SUPPORT.load(Ordering::Acquire) };
+
+        match existing_value == NOT_SET_VALUE {
+            // If not set, then calculate new value, save it, return it.
+            true => {
+                let title_support = detect_title_support_from_env();
+                set_title_support(title_support);
+                title_support
+            }
+
+            // Return saved value.
+            false => TitleSupport::from(existing_value),
+        }
+    }
+
+    /// Returns `true` only the first time it is called (until [take_is_title_saved] is
+    /// called), ie: when the title has to be saved before it is set.
+    #[allow(static_mut_refs)]
+    pub fn mark_title_as_saved() -> bool {
+        let was_saved = unsafe { IS_TITLE_SAVED.swap(true, Ordering::AcqRel) };
+        !was_saved
+    }
+
+    /// Returns whether the title was saved (and must be restored), and resets it.
+    #[allow(static_mut_refs)]
+    pub fn take_is_title_saved() -> bool {
+        unsafe { IS_TITLE_SAVED.swap(false, Ordering::AcqRel) }
+    }
+}
@@ -0,0 +1,490 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch,
+                position,
+                ChUnit,
+                Position,
+                Size,
+                TuiStyle,
+                TuiStyledText,
+                TuiStyledTexts};
+
+use crate::{clip_styled_texts_to_width,
+            render_ops,
+            render_tui_styled_texts_into,
+            BorderGlyphCharacter,
+            RenderOp,
+            RenderPipeline,
+            ZOrder};
+
+pub const SCROLLBAR_THUMB_CHAR: &str = "█";
+
+/// What to do w/ content that has more rows than the height of the [TextArea].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAreaOverflow {
+    /// Only show the rows that fit, starting w/ the first one.
+    #[default]
+    Clip,
+    /// Show the content starting at this row (after wrapping). The offset is clamped, so
+    /// the content can't be scrolled past its end.
+    Scroll { row_offset: ChUnit },
+}
+
+/// The part of the scrollbar track that is painted w/ [SCROLLBAR_THUMB_CHAR], which shows
+/// where the visible rows are in the content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScrollbarThumb {
+    pub row_index: ChUnit,
+    pub row_count: ChUnit,
+}
+
+/// A read only region of rich text (eg: a help screen, a description, or a preview),
+/// which is lighter than an [crate::EditorComponent]. Here's an example.
+///
+/// ```rust
+/// use r3bl_core::{ch, size, tui_styled_text, tui_styled_texts, TuiStyle};
+/// use r3bl_tui::{TextArea, TextAreaOverflow};
+///
+/// let text_area = TextArea::default()
+///     .with_content(vec![
+///         tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: "abcdefgh" } },
+///     ])
+///     .with_overflow(TextAreaOverflow::Scroll { row_offset: ch!(1) });
+///
+/// // The line is wrapped into 3 rows, and the first one is scrolled out of view.
+/// let layout = text_area.layout(size!(col_count: 3, row_count: 2));
+/// assert_eq!(layout.row_count, ch!(3));
+/// assert_eq!(layout.visible_rows.len(), 2);
+/// ```
+///
+/// The layout rules are:
+/// 1. When wrapping is enabled, each line of content is wrapped into as many rows as it
+///    needs. Otherwise each line is one row, that is clipped to the width.
+/// 2. Wrapping happens at grapheme cluster boundaries, and a wide grapheme (eg: an emoji)
+///    that doesn't fit at the end of a row is moved to the next one. A grapheme that is
+///    wider than the whole row can't be painted, so it is dropped.
+/// 3. Styles carry across wrap points, ie: a styled text that is split over 2 rows has
+///    the same style on both.
+/// 4. Rows that don't fit are clipped or scrolled (see [TextAreaOverflow]).
+/// 5. When the scrollbar is enabled and there are more rows than fit, the last column is
+///    used for the scrollbar (and the content is wrapped to fit in the remaining ones).
+///
+/// All widths are display widths.
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    /// The lines of content.
+    pub content: Vec<TuiStyledTexts>,
+    pub is_wrapped: bool,
+    pub overflow: TextAreaOverflow,
+    pub has_scrollbar: bool,
+    pub maybe_scrollbar_style: Option<TuiStyle>,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self {
+            content: vec![],
+            is_wrapped: true,
+            overflow: TextAreaOverflow::default(),
+            has_scrollbar: false,
+            maybe_scrollbar_style: None,
+        }
+    }
+}
+
+/// The laid out content of a [TextArea], which is ready to be painted.
+#[derive(Debug, Clone, Default)]
+pub struct TextAreaLayout {
+    /// The rows that fit in the region, starting at its top.
+    pub visible_rows: Vec<TuiStyledTexts>,
+    /// The number of rows of content (after wrapping).
+    pub row_count: ChUnit,
+    /// The index of the first visible row (after the scroll offset is clamped).
+    pub first_row_index: ChUnit,
+    /// This is [None] if the scrollbar is disabled, or if all the rows fit.
+    pub maybe_scrollbar_thumb: Option<ScrollbarThumb>,
+}
+
+impl TextArea {
+    pub fn with_content(mut self, content: Vec<TuiStyledTexts>) -> Self {
+        self.content = content;
+        self
+    }
+
+    pub fn with_wrap(mut self, is_wrapped: bool) -> Self {
+        self.is_wrapped = is_wrapped;
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: TextAreaOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_scrollbar(
+        mut self,
+        has_scrollbar: bool,
+        maybe_style: Option<TuiStyle>,
+    ) -> Self {
+        self.has_scrollbar = has_scrollbar;
+        self.maybe_scrollbar_style = maybe_style;
+        self
+    }
+
+    /// Returns the largest row offset that [TextAreaOverflow::Scroll] can have for a
+    /// region of the given size, eg: so that the scroll offset can be clamped when it is
+    /// changed in response to a keypress.
+    pub fn get_max_row_offset(&self, size: Size) -> ChUnit {
+        let layout = self.layout(size);
+        layout.row_count - size.row_count
+    }
+
+    /// Lay out the content to fit in a region of the given size. See [TextArea] for the
+    /// layout rules.
+    pub fn layout(&self, size: Size) -> TextAreaLayout {
+        if size.col_count == ch!(0) || size.row_count == ch!(0) {
+            return TextAreaLayout::default();
+        }
+
+        let mut rows = self.get_rows(size.col_count);
+        let mut has_scrollbar = false;
+        if self.has_scrollbar
+            && ch!(rows.len()) > size.row_count
+            && size.col_count > ch!(1)
+        {
+            has_scrollbar = true;
+            rows = self.get_rows(size.col_count - 1);
+        }
+
+        let row_count = ch!(rows.len());
+        let visible_row_count = std::cmp::min(row_count, size.row_count);
+        let first_row_index = match self.overflow {
+            TextAreaOverflow::Clip => ch!(0),
+            TextAreaOverflow::Scroll { row_offset } => {
+                std::cmp::min(row_offset, row_count - visible_row_count)
+            }
+        };
+
+        let maybe_scrollbar_thumb = if has_scrollbar {
+            Some(get_scrollbar_thumb(
+                row_count,
+                size.row_count,
+                first_row_index,
+            ))
+        } else {
+            None
+        };
+
+        TextAreaLayout {
+            visible_rows: rows
+                .into_iter()
+                .skip(ch!(@to_usize first_row_index))
+                .take(ch!(@to_usize visible_row_count))
+                .collect(),
+            row_count,
+            first_row_index,
+            maybe_scrollbar_thumb,
+        }
+    }
+
+    /// Paint the laid out content (see [TextArea::layout]) and the scrollbar (if any) in
+    /// the region at `origin` w/ the given `size`.
+    pub fn render_into(
+        &self,
+        pipeline: &mut RenderPipeline,
+        origin: Position,
+        size: Size,
+    ) {
+        let mut render_ops = render_ops!();
+        let layout = self.layout(size);
+
+        for (row_index, row) in layout.visible_rows.iter().enumerate() {
+            render_ops.push(RenderOp::ResetColor);
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                origin,
+                position!(col_index: 0, row_index: ch!(row_index)),
+            ));
+            render_tui_styled_texts_into(row, &mut render_ops);
+        }
+
+        if let Some(thumb) = layout.maybe_scrollbar_thumb {
+            let col_index = size.col_count - 1;
+            let thumb_end_row_index = thumb.row_index + thumb.row_count;
+            for row_index in 0..*size.row_count {
+                let row_index = ch!(row_index);
+                let glyph =
+                    if row_index >= thumb.row_index && row_index < thumb_end_row_index {
+                        SCROLLBAR_THUMB_CHAR
+                    } else {
+                        BorderGlyphCharacter::Vertical.as_ref()
+                    };
+                render_ops.push(RenderOp::ResetColor);
+                render_ops.push(RenderOp::ApplyColors(self.maybe_scrollbar_style));
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin,
+                    position!(col_index: col_index, row_index: row_index),
+                ));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    glyph.into(),
+                    self.maybe_scrollbar_style,
+                ));
+            }
+        }
+
+        pipeline.push(ZOrder::Normal, render_ops);
+    }
+
+    /// Returns all the rows of the content, which are wrapped or clipped (depending on
+    /// [TextArea::is_wrapped]) to fit in `col_count` columns.
+    fn get_rows(&self, col_count: ChUnit) -> Vec<TuiStyledTexts> {
+        if self.is_wrapped {
+            self.content
+                .iter()
+                .flat_map(|line| wrap_styled_texts(line, col_count))
+                .collect()
+        } else {
+            self.content
+                .iter()
+                .map(|line| clip_styled_texts_to_width(line.clone(), col_count))
+                .collect()
+        }
+    }
+}
+
+/// Split the given styled texts into rows that are at most `col_count` columns wide. An
+/// empty line is a single empty row. See [TextArea] for how wide graphemes and styles are
+/// handled at the wrap points.
+pub fn wrap_styled_texts(
+    styled_texts: &TuiStyledTexts,
+    col_count: ChUnit,
+) -> Vec<TuiStyledTexts> {
+    let mut rows = vec![];
+    let mut row = TuiStyledTexts::default();
+    let mut row_width = ch!(0);
+
+    for styled_text in styled_texts.inner.iter() {
+        let style = *styled_text.get_style();
+        let mut acc_text = String::new();
+        for segment in styled_text.get_text().vec_segment.iter() {
+            if segment.unicode_width > col_count {
+                continue;
+            }
+            if row_width + segment.unicode_width > col_count {
+                if !acc_text.is_empty() {
+                    row += TuiStyledText::new(style, std::mem::take(&mut acc_text));
+                }
+                rows.push(std::mem::take(&mut row));
+                row_width = ch!(0);
+            }
+            acc_text.push_str(&segment.string);
+            row_width += segment.unicode_width;
+        }
+        if !acc_text.is_empty() {
+            row += TuiStyledText::new(style, acc_text);
+        }
+    }
+
+    rows.push(row);
+    rows
+}
+
+/// The thumb is proportional to the fraction of the rows that are visible (and is at
+/// least 1 row tall). It is at the very top (or bottom) of the track only when the
+/// content is scrolled all the way to the top (or bottom).
+fn get_scrollbar_thumb(
+    row_count: ChUnit,
+    track_row_count: ChUnit,
+    first_row_index: ChUnit,
+) -> ScrollbarThumb {
+    let row_count = ch!(@to_usize row_count);
+    let track_row_count = ch!(@to_usize track_row_count);
+    let first_row_index = ch!(@to_usize first_row_index);
+
+    let thumb_row_count =
+        (track_row_count * track_row_count / row_count).clamp(1, track_row_count);
+    let max_first_row_index = row_count - track_row_count;
+    let max_thumb_row_index = track_row_count - thumb_row_count;
+    let thumb_row_index = if first_row_index == 0 {
+        0
+    } else if first_row_index >= max_first_row_index {
+        max_thumb_row_index
+    } else {
+        // Keep it off both ends (if there is room), since the content isn't scrolled to
+        // either end.
+        let it = (first_row_index * max_thumb_row_index).div_ceil(max_first_row_index);
+        if max_thumb_row_index >= 2 {
+            it.clamp(1, max_thumb_row_index - 1)
+        } else {
+            std::cmp::min(it, max_thumb_row_index)
+        }
+    };
+
+    ScrollbarThumb {
+        row_index: ch!(thumb_row_index),
+        row_count: ch!(thumb_row_count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    size,
+                    tui_styled_text,
+                    tui_styled_texts,
+                    ANSIBasicColor,
+                    ConvertToPlainText,
+                    TuiColor};
+    use r3bl_macro::tui_style;
+
+    use super::*;
+
+    fn lines(it: &[&'static str]) -> Vec<TuiStyledTexts> {
+        it.iter()
+            .map(|line| tui_styled_texts! { tui_styled_text! { @style: TuiStyle::default(), @text: *line } })
+            .collect()
+    }
+
+    fn plain_text(rows: &[TuiStyledTexts]) -> Vec<String> {
+        rows.iter()
+            .map(|row| row.to_plain_text_us().string)
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_carries_styles_across_rows() {
+        let red = tui_style! { color_fg: TuiColor::Basic(ANSIBasicColor::Red) };
+        let blue = tui_style! { color_fg: TuiColor::Basic(ANSIBasicColor::Blue) };
+        let line = tui_styled_texts! {
+            tui_styled_text! { @style: red, @text: "abcd" },
+            tui_styled_text! { @style: blue, @text: "efg" },
+        };
+
+        let rows = wrap_styled_texts(&line, ch!(3));
+        assert_eq2!(plain_text(&rows), vec!["abc", "def", "g"]);
+
+        // The 2nd row is made up of the end of the red text, and the start of the blue.
+        assert_eq2!(rows[1].inner.len(), 2);
+        assert_eq2!(*rows[1].inner[0].get_style(), red);
+        assert_eq2!(*rows[1].inner[1].get_style(), blue);
+        assert_eq2!(*rows[2].inner[0].get_style(), blue);
+    }
+
+    #[test]
+    fn test_wrap_wide_graphemes() {
+        // The emoji doesn't fit at the end of the first row, so it moves to the next one.
+        let rows = wrap_styled_texts(&lines(&["ab😃c"])[0], ch!(3));
+        assert_eq2!(plain_text(&rows), vec!["ab", "😃c"]);
+
+        // The emoji is wider than the row, so it is dropped.
+        let rows = wrap_styled_texts(&lines(&["a😃b"])[0], ch!(1));
+        assert_eq2!(plain_text(&rows), vec!["a", "b"]);
+
+        // Empty lines are kept.
+        let rows = wrap_styled_texts(&lines(&[""])[0], ch!(3));
+        assert_eq2!(plain_text(&rows), vec![""]);
+    }
+
+    #[test]
+    fn test_clip_without_scrolling() {
+        let text_area = TextArea::default()
+            .with_wrap(false)
+            .with_content(lines(&["abcdef", "gh", "ij"]));
+
+        let layout = text_area.layout(size!(col_count: 4, row_count: 2));
+        assert_eq2!(plain_text(&layout.visible_rows), vec!["abcd", "gh"]);
+        assert_eq2!(layout.row_count, ch!(3));
+        assert_eq2!(layout.maybe_scrollbar_thumb, None);
+    }
+
+    #[test]
+    fn test_scroll_is_clamped() {
+        let text_area = TextArea::default()
+            .with_content(lines(&["abcdef", "ghi"]))
+            .with_overflow(TextAreaOverflow::Scroll {
+                row_offset: ch!(10),
+            });
+
+        let size = size!(col_count: 3, row_count: 2);
+        let layout = text_area.layout(size);
+        assert_eq2!(plain_text(&layout.visible_rows), vec!["def", "ghi"]);
+        assert_eq2!(layout.first_row_index, ch!(1));
+        assert_eq2!(text_area.get_max_row_offset(size), ch!(1));
+    }
+
+    #[test]
+    fn test_scrollbar() {
+        let text_area = TextArea::default()
+            .with_content(lines(&["a", "b", "c", "d", "e", "f", "g", "h"]))
+            .with_scrollbar(true, None);
+
+        // All the rows fit, so there is no scrollbar.
+        let layout = text_area.layout(size!(col_count: 2, row_count: 8));
+        assert_eq2!(layout.maybe_scrollbar_thumb, None);
+
+        // Half of the rows fit, so the thumb is half of the track.
+        let layout = text_area.layout(size!(col_count: 2, row_count: 4));
+        assert_eq2!(
+            layout.maybe_scrollbar_thumb,
+            Some(ScrollbarThumb {
+                row_index: ch!(0),
+                row_count: ch!(2),
+            })
+        );
+
+        let layout = text_area
+            .clone()
+            .with_overflow(TextAreaOverflow::Scroll { row_offset: ch!(2) })
+            .layout(size!(col_count: 2, row_count: 4));
+        assert_eq2!(layout.maybe_scrollbar_thumb.unwrap().row_index, ch!(1));
+
+        let layout = text_area
+            .clone()
+            .with_overflow(TextAreaOverflow::Scroll { row_offset: ch!(4) })
+            .layout(size!(col_count: 2, row_count: 4));
+        assert_eq2!(layout.maybe_scrollbar_thumb.unwrap().row_index, ch!(2));
+    }
+
+    #[test]
+    fn test_scrollbar_reserves_last_col() {
+        let text_area = TextArea::default()
+            .with_content(lines(&["abcd", "efgh"]))
+            .with_scrollbar(true, None);
+
+        // Wrapped at 4 cols, the content is 2 rows (which fits). At 3 cols (w/ the
+        // scrollbar) it is 4 rows (which doesn't).
+        let layout = text_area.layout(size!(col_count: 4, row_count: 2));
+        assert_eq2!(plain_text(&layout.visible_rows), vec!["abcd", "efgh"]);
+
+        let layout = text_area.layout(size!(col_count: 4, row_count: 1));
+        assert_eq2!(plain_text(&layout.visible_rows), vec!["abc"]);
+        assert_eq2!(layout.row_count, ch!(4));
+        assert!(layout.maybe_scrollbar_thumb.is_some());
+
+        let mut pipeline = RenderPipeline::default();
+        text_area.render_into(
+            &mut pipeline,
+            position!(col_index: 0, row_index: 0),
+            size!(col_count: 4, row_count: 1),
+        );
+        let render_ops = pipeline.get_all_render_op_in(ZOrder::Normal).unwrap();
+        assert!(render_ops.iter().any(|op| matches!(
+            op,
+            RenderOp::PaintTextWithAttributes(text, _) if text == SCROLLBAR_THUMB_CHAR
+        )));
+    }
+}
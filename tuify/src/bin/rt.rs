@@ -18,7 +18,8 @@
 //! For more information on how to use CLAP and Tuify, please read this tutorial:
 //! <https://developerlife.com/2023/09/17/tuify-clap/>
 
-use std::{io::{stdin, BufRead, Result},
+use std::{io::{stderr, stdin, BufRead, Error, ErrorKind, Result},
+          path::{Path, PathBuf},
           process::Command};
 
 use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
@@ -32,8 +33,13 @@ use r3bl_core::{call_if_true,
                 get_terminal_width,
                 throws,
                 try_initialize_global_logging};
-use r3bl_tuify::{select_from_list, SelectionMode, StyleSheet, DEVELOPMENT_MODE};
+use r3bl_tuify::{select_from_list,
+                 SelectFromListBuilder,
+                 SelectionMode,
+                 StyleSheet,
+                 DEVELOPMENT_MODE};
 use reedline::{DefaultPrompt, DefaultPromptSegment, Reedline, Signal};
+use serde::Serialize;
 use StdinIsPipedResult::{StdinIsNotPiped, StdinIsPiped};
 use StdoutIsPipedResult::{StdoutIsNotPiped, StdoutIsPiped};
 
@@ -72,7 +78,7 @@ struct GlobalOpts {
 
 #[derive(Debug, Subcommand)]
 enum CLICommand {
-    /// Show TUI to allow you to select one or more options from a list, piped in via stdin 👉
+    /// Show TUI to allow you to select one or more options from a list, piped in via stdin (or read from a file) 👉
     SelectFromList {
         /// Would you like to select one or more items?
         #[arg(value_name = "mode", long, short = 's')]
@@ -82,9 +88,75 @@ enum CLICommand {
         /// For eg: "echo %". Please wrap the command in quotes 💡
         #[arg(value_name = "command", long, short = 'c')]
         command_to_run_with_each_selection: Option<String>,
+
+        /// Read the list of items from this file (one item per line) instead of stdin,
+        /// which is ignored (so it may be redirected, eg: in a script) 📄
+        #[arg(value_name = "file", long, short = 'f')]
+        items_file: Option<PathBuf>,
+
+        /// What to do w/ the selected items. `json` prints them (and their indices) to
+        /// stdout, which can be piped to another command. The TUI is shown on stderr 🧾
+        #[arg(value_name = "format", long, short = 'o', visible_alias = "output-format", value_enum, default_value_t = OutputFormat::Command)]
+        output: OutputFormat,
+
+        /// Split the items on NUL bytes instead of newlines, eg: for the output of
+        /// `find -print0` 🔪
+        #[arg(long = "null", short = '0', conflicts_with = "delimiter")]
+        null_delimited: bool,
+
+        /// Split the items on this (ASCII) character instead of newlines, eg: `,` 🔪
+        #[arg(value_name = "char", long, short = 'd', value_parser = parse_delimiter)]
+        delimiter: Option<u8>,
     },
 }
 
+/// The items are split on newlines, unless another delimiter is passed.
+const DEFAULT_DELIMITER: u8 = b'\n';
+
+/// Returns the byte that the items are split on. The delimiter must be a single ASCII
+/// character, so that it is never part of a multi-byte UTF-8 character.
+fn parse_delimiter(arg: &str) -> std::result::Result<u8, String> {
+    let mut chars = arg.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii() => Ok(ch as u8),
+        _ => Err(format!("`{arg}` is not a single ASCII character")),
+    }
+}
+
+fn get_delimiter(null_delimited: bool, maybe_delimiter: Option<u8>) -> u8 {
+    match (null_delimited, maybe_delimiter) {
+        (true, _) => b'\0',
+        (false, Some(delimiter)) => delimiter,
+        (false, None) => DEFAULT_DELIMITER,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Run the command w/ each selected item.
+    Command,
+    /// Print a JSON object w/ the `selected` items (an array of `{"index": ..,
+    /// "item": ..}` objects), and the selection `mode` (`single` or `multiple`). The
+    /// array is empty if there are no items to select from, and `selected` is `null` if
+    /// the user cancels. The selection mode defaults to single select.
+    Json,
+}
+
+/// The JSON output, eg: `{"selected":[{"index":0,"item":"foo"}],"mode":"single"}`.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct JsonOutput {
+    /// [None] if the user cancelled.
+    selected: Option<Vec<JsonSelectedItem>>,
+    mode: String,
+}
+
+/// A selected item, in the JSON output.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct JsonSelectedItem {
+    index: usize,
+    item: String,
+}
+
 fn get_bin_name() -> String {
     let cmd = AppArgs::command();
     cmd.get_bin_name().unwrap_or("this command").to_string()
@@ -108,44 +180,95 @@ fn main() -> Result<()> {
             CLICommand::SelectFromList {
                 selection_mode,
                 command_to_run_with_each_selection: command_to_run_with_selection,
+                items_file: Some(items_file),
+                output,
+                null_delimited,
+                delimiter,
+            } => {
+                let delimiter = get_delimiter(null_delimited, delimiter);
+                match get_items_file_action(
+                    is_stdin_piped(),
+                    is_stdout_piped(),
+                    output,
+                    cfg!(target_os = "macos"),
+                ) {
+                    ItemsFileAction::StdinPipeDoesNotWorkOnMacos => {
+                        show_error_stdin_pipe_does_not_work_on_macos();
+                    }
+                    ItemsFileAction::DoNotPipeStdout => {
+                        show_error_do_not_pipe_stdout(get_bin_name().as_ref());
+                    }
+                    ItemsFileAction::ShowTui => {
+                        match read_items_from_file(&items_file, delimiter) {
+                            Ok(lines) => {
+                                let tui_height = cli_args.global_opts.tui_height;
+                                let tui_width = cli_args.global_opts.tui_width;
+                                show_tui(
+                                    lines,
+                                    selection_mode,
+                                    command_to_run_with_selection,
+                                    output,
+                                    tui_height,
+                                    tui_width,
+                                    enable_logging,
+                                );
+                            }
+                            Err(error) => {
+                                show_error_could_not_read_items_file(&items_file, error);
+                            }
+                        }
+                    }
+                }
+            }
+            CLICommand::SelectFromList {
+                selection_mode,
+                command_to_run_with_each_selection: command_to_run_with_selection,
+                items_file: None,
+                output,
+                null_delimited,
+                delimiter,
             } => {
+                let delimiter = get_delimiter(null_delimited, delimiter);
                 // macos has issues w/ stdin piped in.
                 // https://github.com/crossterm-rs/crossterm/issues/396
                 if cfg!(target_os = "macos") {
-                    match (is_stdin_piped(), is_stdout_piped()) {
-                        (StdinIsPiped, _) => {
+                    match (is_stdin_piped(), is_stdout_piped(), output) {
+                        (StdinIsPiped, _, _) => {
                             show_error_stdin_pipe_does_not_work_on_macos();
                         }
-                        (_, StdoutIsPiped) => {
+                        (_, StdoutIsPiped, OutputFormat::Command) => {
                             show_error_do_not_pipe_stdout(get_bin_name().as_ref());
                         }
-                        (StdinIsNotPiped, StdoutIsNotPiped) => {
+                        (StdinIsNotPiped, _, _) => {
                             print_help()?;
                         }
                     }
                 }
-                // Linux works fine.
+                // Linux works fine. Piping stdout is only allowed for the JSON output.
                 else {
-                    match (is_stdin_piped(), is_stdout_piped()) {
-                        (StdinIsPiped, StdoutIsNotPiped) => {
+                    match (is_stdin_piped(), is_stdout_piped(), output) {
+                        (StdinIsPiped, StdoutIsNotPiped, _)
+                        | (StdinIsPiped, StdoutIsPiped, OutputFormat::Json) => {
                             let tui_height = cli_args.global_opts.tui_height;
                             let tui_width = cli_args.global_opts.tui_width;
                             show_tui(
+                                read_items_from_stdin(delimiter),
                                 selection_mode,
                                 command_to_run_with_selection,
+                                output,
                                 tui_height,
                                 tui_width,
                                 enable_logging,
                             );
                         }
-                        (StdinIsPiped, StdoutIsPiped) => {
+                        (StdinIsPiped, StdoutIsPiped, OutputFormat::Command) => {
                             show_error_do_not_pipe_stdout(get_bin_name().as_ref());
                         }
-                        (StdinIsNotPiped, StdoutIsPiped) => {
+                        (StdinIsNotPiped, StdoutIsPiped, OutputFormat::Command) => {
                             show_error_need_to_pipe_stdin(get_bin_name().as_ref());
                             show_error_do_not_pipe_stdout(get_bin_name().as_ref());
                         }
-                        (StdinIsNotPiped, StdoutIsNotPiped) => {
+                        (StdinIsNotPiped, _, _) => {
                             show_error_need_to_pipe_stdin(get_bin_name().as_ref());
                         }
                     }
@@ -158,6 +281,32 @@ fn main() -> Result<()> {
     });
 }
 
+/// What to do when the items are read from a file (`--items-file`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ItemsFileAction {
+    ShowTui,
+    DoNotPipeStdout,
+    StdinPipeDoesNotWorkOnMacos,
+}
+
+/// The items aren't read from stdin, so it is ignored, and may be redirected (eg: when
+/// `rt` is run from a script). Key presses are read from the controlling terminal
+/// instead, which doesn't work on macOS (see [show_error_stdin_pipe_does_not_work_on_macos]).
+/// Piping stdout is only allowed for the JSON output, since the TUI is painted to stdout
+/// otherwise.
+fn get_items_file_action(
+    stdin: StdinIsPipedResult,
+    stdout: StdoutIsPipedResult,
+    output: OutputFormat,
+    is_macos: bool,
+) -> ItemsFileAction {
+    match (stdin, stdout, output) {
+        (StdinIsPiped, _, _) if is_macos => ItemsFileAction::StdinPipeDoesNotWorkOnMacos,
+        (_, StdoutIsPiped, OutputFormat::Command) => ItemsFileAction::DoNotPipeStdout,
+        _ => ItemsFileAction::ShowTui,
+    }
+}
+
 fn show_error_stdin_pipe_does_not_work_on_macos() {
     let msg = "Unfortunately at this time macOS `stdin` pipe does not work on macOS.\
                      \nhttps://github.com/crossterm-rs/crossterm/issues/396"
@@ -176,6 +325,16 @@ fn show_error_need_to_pipe_stdin(bin_name: &str) {
     println!("{msg}");
 }
 
+fn show_error_could_not_read_items_file(items_file: &Path, error: Error) {
+    let msg = format!(
+        "Could not read items from file `{}`: {error}",
+        items_file.display()
+    )
+    .red()
+    .to_string();
+    println!("{msg}");
+}
+
 fn show_error_do_not_pipe_stdout(bin_name: &str) {
     let msg = format!(
         "Please do *not* pipe the output of {bin_name} to another command. \
@@ -186,32 +345,97 @@ fn show_error_do_not_pipe_stdout(bin_name: &str) {
     println!("{msg}");
 }
 
+/// Read the items from stdin, split on the `delimiter`. Reading stops at the first item
+/// that isn't valid UTF-8.
+fn read_items_from_stdin(delimiter: u8) -> Vec<String> {
+    if delimiter == DEFAULT_DELIMITER {
+        return stdin()
+            .lock()
+            .lines()
+            .map_while(Result::ok)
+            .collect::<Vec<String>>();
+    }
+
+    stdin()
+        .lock()
+        .split(delimiter)
+        .map_while(Result::ok)
+        .map_while(|bytes| String::from_utf8(bytes).ok())
+        .collect::<Vec<String>>()
+}
+
+/// Split the `content` into items on the `delimiter`. A trailing delimiter doesn't
+/// produce an empty item. Just like [str::lines], a `\r` before each newline is removed
+/// when splitting on newlines.
+fn split_items(content: &str, delimiter: u8) -> Vec<String> {
+    if delimiter == DEFAULT_DELIMITER {
+        return content.lines().map(String::from).collect();
+    }
+    content
+        .split_terminator(delimiter as char)
+        .map(String::from)
+        .collect()
+}
+
+/// Read the items from the given file, split on the `delimiter`. The file must be valid
+/// UTF-8.
+fn read_items_from_file(items_file: &Path, delimiter: u8) -> Result<Vec<String>> {
+    let bytes = std::fs::read(items_file)?;
+    let content = String::from_utf8(bytes).map_err(|error| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "file is not valid UTF-8 (invalid byte at offset {})",
+                error.utf8_error().valid_up_to()
+            ),
+        )
+    })?;
+    Ok(split_items(&content, delimiter))
+}
+
 fn show_tui(
+    lines: Vec<String>,
     maybe_selection_mode: Option<SelectionMode>,
     maybe_command_to_run_with_each_selection: Option<String>,
+    output: OutputFormat,
     tui_height: Option<usize>,
     tui_width: Option<usize>,
     enable_logging: bool,
 ) {
-    let lines: Vec<String> = stdin()
-        .lock()
-        .lines()
-        .map_while(Result::ok)
-        .collect::<Vec<String>>();
-
     call_if_true!(enable_logging, {
         tracing::debug!("lines: {lines:?}");
     });
 
-    // Early return, nothing to do. No content found in stdin.
-    if lines.is_empty() {
-        return;
-    }
-
     // Get display size.
     let max_width_col_count: usize = tui_width.unwrap_or(get_terminal_width());
     let max_height_row_count: usize = tui_height.unwrap_or(5);
 
+    // Nothing is prompted for, and only the JSON is printed to stdout.
+    if output == OutputFormat::Json {
+        let selection_mode = maybe_selection_mode.unwrap_or(SelectionMode::Single);
+        let maybe_selected_items = if lines.is_empty() {
+            Some(vec![])
+        } else {
+            SelectFromListBuilder::new()
+                .header("Select one line")
+                .items(lines.clone())
+                .max_height(max_height_row_count)
+                .max_width(max_width_col_count)
+                .selection_mode(selection_mode)
+                .build_and_run_with_writer(stderr())
+        };
+        println!(
+            "{}",
+            convert_selection_into_json(&lines, maybe_selected_items, selection_mode)
+        );
+        return;
+    }
+
+    // Early return, nothing to do. No content found in stdin or the items file.
+    if lines.is_empty() {
+        return;
+    }
+
     // Handle `selection-mode` is not passed in.
     let selection_mode = if let Some(selection_mode) = maybe_selection_mode {
         selection_mode
@@ -308,6 +532,40 @@ fn show_tui(
     }
 }
 
+/// The `selected` items are `null` if the user cancelled. Each selected item is paired
+/// w/ its index in `items` (duplicate items get the indices of successive occurrences).
+fn convert_selection_into_json(
+    items: &[String],
+    maybe_selected_items: Option<Vec<String>>,
+    selection_mode: SelectionMode,
+) -> String {
+    let maybe_json_selected_items = maybe_selected_items.map(|selected_items| {
+        let mut used_indices = vec![];
+        selected_items
+            .into_iter()
+            .filter_map(|selected_item| {
+                let index = items.iter().enumerate().position(|(index, item)| {
+                    *item == selected_item && !used_indices.contains(&index)
+                })?;
+                used_indices.push(index);
+                Some(JsonSelectedItem {
+                    index,
+                    item: selected_item,
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+    let mode = selection_mode
+        .to_possible_value()
+        .map(|it| it.get_name().to_string())
+        .unwrap_or_default();
+    let json_output = JsonOutput {
+        selected: maybe_json_selected_items,
+        mode,
+    };
+    serde_json::to_string(&json_output).unwrap_or_else(|_| "null".into())
+}
+
 fn convert_user_input_into_vec_of_strings(
     user_input: Option<Vec<String>>,
 ) -> Vec<String> {
@@ -406,3 +664,170 @@ fn get_possible_values_for_subcommand_and_option(
 
     vec![]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("r3bl_tuify_rt_{}_{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_items_from_file() {
+        let path = make_temp_file("items.txt", "one\ntwo 🦀\r\nthree\n".as_bytes());
+        let items = read_items_from_file(&path, DEFAULT_DELIMITER).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(items, vec!["one", "two 🦀", "three"]);
+    }
+
+    #[test]
+    fn test_read_items_from_file_w_delimiter() {
+        let path = make_temp_file("null.txt", b"./a b\n\0./c\0");
+        let items = read_items_from_file(&path, b'\0').unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(items, vec!["./a b\n", "./c"]);
+    }
+
+    #[test]
+    fn test_split_items() {
+        assert_eq!(split_items("a,b,,c", b','), vec!["a", "b", "", "c"]);
+        assert_eq!(split_items("a\r\nb", DEFAULT_DELIMITER), vec!["a", "b"]);
+        assert_eq!(split_items("", b','), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_delimiter_args() {
+        let get_delimiter_from_args = |args: &[&str]| {
+            let app_args = AppArgs::try_parse_from(
+                ["rt", "select-from-list"].iter().chain(args.iter()),
+            )?;
+            let CLICommand::SelectFromList {
+                null_delimited,
+                delimiter,
+                ..
+            } = app_args.command;
+            Ok::<_, clap::Error>(get_delimiter(null_delimited, delimiter))
+        };
+
+        assert_eq!(get_delimiter_from_args(&[]).unwrap(), b'\n');
+        assert_eq!(get_delimiter_from_args(&["-0"]).unwrap(), b'\0');
+        assert_eq!(get_delimiter_from_args(&["--null"]).unwrap(), b'\0');
+        assert_eq!(get_delimiter_from_args(&["-d", ","]).unwrap(), b',');
+        assert_eq!(
+            get_delimiter_from_args(&["--delimiter", ":"]).unwrap(),
+            b':'
+        );
+
+        // Only a single ASCII character is allowed, and not both flags.
+        assert!(get_delimiter_from_args(&["-d", "ab"]).is_err());
+        assert!(get_delimiter_from_args(&["-d", "🦀"]).is_err());
+        assert!(get_delimiter_from_args(&["-0", "-d", ","]).is_err());
+    }
+
+    #[test]
+    fn test_read_items_from_file_invalid_utf8() {
+        let path = make_temp_file("invalid.txt", b"one\n\xff\xfe");
+        let error = read_items_from_file(&path, DEFAULT_DELIMITER).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().contains("offset 4"));
+    }
+
+    #[test]
+    fn test_read_items_from_missing_file() {
+        let path = std::env::temp_dir().join("r3bl_tuify_rt_does_not_exist.txt");
+        let error = read_items_from_file(&path, DEFAULT_DELIMITER).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_items_file_action_w_redirected_stdin() {
+        // Eg: `rt select-from-list -f items.txt < /dev/null`.
+        assert_eq!(
+            get_items_file_action(
+                StdinIsPiped,
+                StdoutIsNotPiped,
+                OutputFormat::Command,
+                false
+            ),
+            ItemsFileAction::ShowTui
+        );
+        assert_eq!(
+            get_items_file_action(StdinIsPiped, StdoutIsPiped, OutputFormat::Json, false),
+            ItemsFileAction::ShowTui
+        );
+        assert_eq!(
+            get_items_file_action(
+                StdinIsPiped,
+                StdoutIsNotPiped,
+                OutputFormat::Command,
+                true
+            ),
+            ItemsFileAction::StdinPipeDoesNotWorkOnMacos
+        );
+
+        // Stdout can only be piped for the JSON output.
+        assert_eq!(
+            get_items_file_action(
+                StdinIsNotPiped,
+                StdoutIsPiped,
+                OutputFormat::Command,
+                false
+            ),
+            ItemsFileAction::DoNotPipeStdout
+        );
+        assert_eq!(
+            get_items_file_action(
+                StdinIsNotPiped,
+                StdoutIsNotPiped,
+                OutputFormat::Command,
+                true
+            ),
+            ItemsFileAction::ShowTui
+        );
+    }
+
+    #[test]
+    fn test_convert_selection_into_json() {
+        let items: Vec<String> = vec!["a \"quoted\" 🦀".into(), "b".into(), "b".into()];
+
+        // The user cancelled.
+        assert_eq!(
+            convert_selection_into_json(&items, None, SelectionMode::Single),
+            r#"{"selected":null,"mode":"single"}"#
+        );
+
+        // No items to select from (or nothing selected).
+        assert_eq!(
+            convert_selection_into_json(&[], Some(vec![]), SelectionMode::Multiple),
+            r#"{"selected":[],"mode":"multiple"}"#
+        );
+
+        // The text is escaped, and duplicates get successive indices.
+        assert_eq!(
+            convert_selection_into_json(
+                &items,
+                Some(vec!["b".into(), "a \"quoted\" 🦀".into(), "b".into()]),
+                SelectionMode::Multiple,
+            ),
+            r#"{"selected":[{"index":1,"item":"b"},{"index":0,"item":"a \"quoted\" 🦀"},{"index":2,"item":"b"}],"mode":"multiple"}"#
+        );
+    }
+
+    #[test]
+    fn test_output_format_alias() {
+        let app_args = AppArgs::try_parse_from([
+            "rt",
+            "select-from-list",
+            "--output-format",
+            "json",
+        ])
+        .unwrap();
+        let CLICommand::SelectFromList { output, .. } = app_args.command;
+        assert_eq!(output, OutputFormat::Json);
+    }
+}
@@ -18,6 +18,9 @@
 use crossterm::style::{Attribute, Color, SetAttribute};
 use r3bl_ansi_color::{global_color_support, ColorSupport, TransformColor};
 
+/// Converts the `color` to one that the terminal can display. If the terminal doesn't
+/// support colors (eg: `NO_COLOR` is set), then [Color::Reset] is returned, so that the
+/// output is plain, and uses the terminal's default colors.
 pub fn get_crossterm_color_based_on_terminal_capabilities(
     color: r3bl_ansi_color::Color,
 ) -> Color {
@@ -31,7 +34,9 @@ pub fn get_crossterm_color_based_on_terminal_capabilities(
                 b: rgb_color.blue,
             }
         }
-        _ => Color::AnsiValue(color.as_ansi256().index),
+        ColorSupport::Ansi256 => Color::AnsiValue(color.as_ansi256().index),
+        ColorSupport::Grayscale => Color::AnsiValue(color.as_grayscale().index),
+        ColorSupport::NoColor => Color::Reset,
     }
 }
 
@@ -27,21 +27,31 @@ use crossterm::{cursor::{MoveToColumn, MoveToNextLine, MoveToPreviousLine},
                         Stylize},
                 terminal::{Clear, ClearType}};
 use r3bl_ansi_color::AnsiStyledText;
-use r3bl_core::{call_if_true, ch, get_terminal_width, throws, ChUnit, UnicodeString};
+use r3bl_core::{call_if_true, ch, throws, ChUnit, TruncationIndicator, UnicodeString};
 
 use crate::{apply_style,
+            clamp_horizontal_scroll,
+            fuzzy_match,
             get_crossterm_color_based_on_terminal_capabilities,
+            highlight_filter_matches,
             set_attribute,
+            DefaultItemRenderer,
             FunctionComponent,
             Header,
+            ItemRenderer,
+            ItemSpan,
+            RowRenderContext,
             SelectionMode,
             State,
+            Style,
             StyleSheet,
             DEVELOPMENT_MODE};
 
 pub struct SelectComponent<W: Write> {
     pub write: W,
     pub style: StyleSheet,
+    /// If this is `None`, then the [DefaultItemRenderer] is used.
+    pub maybe_item_renderer: Option<Box<dyn ItemRenderer>>,
 }
 
 const IS_FOCUSED: &str = " › ";
@@ -50,27 +60,27 @@ const MULTI_SELECT_IS_SELECTED: &str = "✔";
 const MULTI_SELECT_IS_NOT_SELECTED: &str = "☐";
 const SINGLE_SELECT_IS_SELECTED: &str = "◉";
 const SINGLE_SELECT_IS_NOT_SELECTED: &str = "◌";
+const START_DISPLAY_COL_OFFSET: usize = 1;
+const FILTER_TEXT_PREFIX: &str = " / ";
 
 impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
     fn get_write(&mut self) -> &mut W { &mut self.write }
 
     // Header can be either a single line or a multi line.
     fn calculate_header_viewport_height(&self, state: &mut State<'_>) -> ChUnit {
-        match state.get_header() {
-            Header::Single => ch!(1),
-            Header::Multiple => ch!(state.multi_line_header.len()),
-        }
+        state.get_header_height()
     }
 
     /// If there are more items than the max display height, then we only use max display
     /// height. Otherwise we can shrink the display height to the number of items.
     /// This does NOT include the header.
     fn calculate_items_viewport_height(&self, state: &mut State<'_>) -> ChUnit {
-        if state.items.len() > ch!(@to_usize state.max_display_height) {
-            state.max_display_height
-        } else {
-            ch!(state.items.len())
-        }
+        state.get_items_viewport_height()
+    }
+
+    /// The preview pane (if any) is painted below the items.
+    fn calculate_preview_viewport_height(&self, state: &mut State<'_>) -> ChUnit {
+        state.max_preview_height
     }
 
     /// Allocate space and print the lines. The bring the cursor back to the start of the
@@ -83,7 +93,14 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
             let unselected_style = self.style.unselected_style;
             let selected_style = self.style.selected_style;
             let single_line_header_style = self.style.header_style;
-            let start_display_col_offset = 1;
+            let preview_style = self.style.preview_style;
+            let section_header_style = self.style.section_header_style;
+            // Only the foreground color is used, so the row's highlight is preserved.
+            let filter_match_fg_color = self.style.match_highlight_style.fg_color;
+            let truncation_indicator = self.style.truncation_indicator;
+            let truncation_indicator_display_width =
+                truncation_indicator.get_display_width();
+            let start_display_col_offset = START_DISPLAY_COL_OFFSET;
             let header_viewport_height: ChUnit =
                 self.calculate_header_viewport_height(state);
 
@@ -93,24 +110,18 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
             let items_viewport_height: ChUnit =
                 self.calculate_items_viewport_height(state);
 
-            let viewport_width: ChUnit = {
-                // Try to get the terminal width from state first (since it should be set
-                // when resize events occur). If that is not set, then get the terminal
-                // width directly.
-                let terminal_width = match state.window_size {
-                    Some(size) => size.col_count,
-                    None => ch!(get_terminal_width()),
-                };
+            let preview_viewport_height: ChUnit =
+                self.calculate_preview_viewport_height(state);
 
-                // Do not exceed the max display width (if it is set).
-                if state.max_display_width == ch!(0)
-                    || state.max_display_width > ch!(terminal_width)
-                {
-                    ch!(terminal_width)
-                } else {
-                    state.max_display_width
-                }
-            };
+            let viewport_width: ChUnit = state.get_viewport_width();
+
+            // The viewport may have been resized since the last render, so make sure that
+            // it isn't scrolled past the end of the longest item.
+            state.horizontal_scroll_offset = clamp_horizontal_scroll(
+                state.horizontal_scroll_offset,
+                state.get_max_item_display_width(),
+                state.get_item_text_viewport_width(),
+            );
 
             call_if_true!(DEVELOPMENT_MODE, {
                 tracing::debug!(
@@ -127,7 +138,9 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
 
             let data_row_index_start = *state.scroll_offset_row_index;
 
-            let writer = self.get_write();
+            // Borrow the fields separately, so that the renderer can be used while writing.
+            let mut maybe_item_renderer = self.maybe_item_renderer.as_deref_mut();
+            let writer = &mut self.write;
 
             match state.get_header() {
                 Header::Single => {
@@ -136,9 +149,18 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
                         " ".repeat(start_display_col_offset),
                         state.header
                     );
+                    if state.is_filtered() {
+                        header_text = format!(
+                            "{header_text}{FILTER_TEXT_PREFIX}{}",
+                            state.filter_text
+                        );
+                    }
 
-                    header_text =
-                        clip_string_to_width_with_ellipsis(header_text, viewport_width);
+                    header_text = clip_string_to_width_with_ellipsis(
+                        header_text,
+                        viewport_width,
+                        &truncation_indicator,
+                    );
 
                     queue! {
                         writer,
@@ -168,9 +190,10 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
                     }?;
                 }
                 Header::Multiple => {
-                    // Subtract 3 from viewport width because we need to add "..." to the
-                    // end of the line.
-                    let mut available_space_col_count: ChUnit = viewport_width - 3;
+                    // Subtract the width of the truncation indicator from viewport width
+                    // because we need to add it to the end of the line.
+                    let mut available_space_col_count: ChUnit =
+                        viewport_width - truncation_indicator_display_width;
                     // This is the vector of vectors of AnsiStyledText we want to print to
                     // the screen.
                     let mut multi_line_header_clipped_vec: Vec<Vec<AnsiStyledText<'_>>> =
@@ -190,7 +213,10 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
                                 // Clip the text to available space.
                                 let clipped_text = span_as_unicode_string
                                     .clip_to_width(ch!(0), available_space_col_count);
-                                let clipped_text = format!("{clipped_text}...");
+                                let clipped_text = format!(
+                                    "{clipped_text}{}",
+                                    truncation_indicator.text
+                                );
                                 header_line_modified.push(clipped_text.to_owned());
                                 break 'inner;
                             } else {
@@ -206,10 +232,11 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
                                     maybe_header_line_last_span
                                 {
                                     if last_span == header_line_last_span {
-                                        // Because text is not clipped, we add back the 3 we subtracted
-                                        // earlier for the "...".
+                                        // Because text is not clipped, we add back the width we
+                                        // subtracted earlier for the truncation indicator.
                                         let num_of_spaces: ChUnit =
-                                            available_space_col_count + ch!(3);
+                                            available_space_col_count
+                                                + truncation_indicator_display_width;
                                         let span_with_spaces = span_text.to_owned()
                                             + &" ".repeat(num_of_spaces.into());
                                         header_line_modified.push(span_with_spaces);
@@ -221,7 +248,8 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
                         }
 
                         // Reset the available space.
-                        available_space_col_count = viewport_width - 3;
+                        available_space_col_count =
+                            viewport_width - truncation_indicator_display_width;
                         maybe_clipped_text_vec.push(header_line_modified);
                     }
 
@@ -275,12 +303,76 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
 
             // Print each line in viewport.
             for viewport_row_index in 0..*items_viewport_height {
-                let data_row_index: usize =
+                let list_row_index: usize =
                     (data_row_index_start + viewport_row_index).into();
                 let caret_row_scroll_adj =
                     ch!(viewport_row_index) + state.scroll_offset_row_index;
+
+                // When the list is filtered, there may be fewer rows than the viewport
+                // height, so the rest of the viewport is painted blank.
+                let Some(data_row_index) = state.get_item_index(list_row_index) else {
+                    queue! {
+                        writer,
+                        // Bring the caret back to the start of line.
+                        MoveToColumn(0),
+                        // Reset the colors that may have been set by the previous command.
+                        ResetColor,
+                        // Clear the current line.
+                        Clear(ClearType::CurrentLine),
+                        // Move to next line.
+                        MoveToNextLine(1),
+                    }?;
+                    continue;
+                };
                 let data_item = &state.items[data_row_index];
 
+                // Section headers are not items, so they don't have a row prefix, and
+                // can't be focused or selected.
+                if state.is_section_header(data_row_index) {
+                    let section_header_text = clip_string_to_width_with_ellipsis(
+                        format!("{}{data_item}", " ".repeat(start_display_col_offset)),
+                        viewport_width,
+                        &truncation_indicator,
+                    );
+                    let section_header_display_width: ChUnit =
+                        UnicodeString::from(&section_header_text).display_width;
+                    let padding_right = if section_header_display_width < viewport_width {
+                        " ".repeat(ch!(@to_usize (viewport_width - section_header_display_width)))
+                    } else {
+                        "".to_string()
+                    };
+
+                    queue! {
+                        writer,
+                        // Bring the caret back to the start of line.
+                        MoveToColumn(0),
+                        // Reset the colors that may have been set by the previous command.
+                        ResetColor,
+                        // Clear the current line.
+                        Clear(ClearType::CurrentLine),
+                        // Set the colors for the text.
+                        apply_style!(section_header_style => fg_color),
+                        apply_style!(section_header_style => bg_color),
+                        // Style the text.
+                        apply_style!(section_header_style => bold),
+                        apply_style!(section_header_style => italic),
+                        apply_style!(section_header_style => dim),
+                        apply_style!(section_header_style => underline),
+                        apply_style!(section_header_style => reverse),
+                        apply_style!(section_header_style => hidden),
+                        apply_style!(section_header_style => strikethrough),
+                        // Print the text.
+                        Print(section_header_text),
+                        // Print the padding text.
+                        Print(padding_right),
+                        // Move to next line.
+                        MoveToNextLine(1),
+                        // Reset the colors.
+                        ResetColor,
+                    }?;
+                    continue;
+                }
+
                 // Invert colors for selected items.
                 enum SelectionStateStyle {
                     FocusedAndSelected,
@@ -289,8 +381,13 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
                     Unselected,
                 }
 
-                let is_selected = state.selected_items.contains(data_item);
+                let is_selected = state.is_item_selected(data_row_index);
                 let is_focused = ch!(caret_row_scroll_adj) == state.get_focused_index();
+                let row_render_context = RowRenderContext {
+                    is_focused,
+                    is_selected,
+                    max_display_width: state.get_item_text_viewport_width(),
+                };
 
                 let selection_state = match (is_focused, is_selected) {
                     (true, true) => SelectionStateStyle::FocusedAndSelected,
@@ -306,39 +403,70 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
                     SelectionStateStyle::Unselected => unselected_style,
                 };
 
-                let row_prefix = match state.selection_mode {
-                    SelectionMode::Single => {
-                        let padding_left = " ".repeat(start_display_col_offset);
-                        if is_focused {
-                            format!("{padding_left} {SINGLE_SELECT_IS_SELECTED} ")
-                        } else {
-                            format!("{padding_left} {SINGLE_SELECT_IS_NOT_SELECTED} ")
-                        }
-                    }
-                    SelectionMode::Multiple => {
-                        let padding_left = " ".repeat(start_display_col_offset);
-                        match (is_focused, is_selected) {
-                            (true, true) => {
-                                format!("{padding_left} {IS_FOCUSED} {MULTI_SELECT_IS_SELECTED} ")
-                            }
-                            (true, false) => format!(
-                                "{padding_left} {IS_FOCUSED} {MULTI_SELECT_IS_NOT_SELECTED} "
-                            ),
-                            (false, true) => format!(
-                                "{padding_left} {IS_NOT_FOCUSED} {MULTI_SELECT_IS_SELECTED} "
-                            ),
-                            (false, false) => format!(
-                                "{padding_left} {IS_NOT_FOCUSED} {MULTI_SELECT_IS_NOT_SELECTED} "
-                            ),
-                        }
+                let row_prefix =
+                    get_row_prefix(state.selection_mode, is_focused, is_selected);
+
+                // The icon is painted between the row prefix and the text. Its width is
+                // taken into account when clipping the text.
+                let icon_text = state.get_item_icon_text(data_row_index);
+                let maybe_icon_fg_color = state
+                    .get_item_icon(data_row_index)
+                    .and_then(|it| it.maybe_fg_color);
+                let item_spans = match maybe_item_renderer.as_mut() {
+                    Some(item_renderer) => item_renderer.render_item(
+                        data_row_index,
+                        data_item,
+                        row_render_context,
+                    ),
+                    None => DefaultItemRenderer.render_item(
+                        data_row_index,
+                        data_item,
+                        row_render_context,
+                    ),
+                };
+                let item_spans = match fuzzy_match(&state.filter_text, data_item) {
+                    Some(matched_indices) if state.is_filtered() => {
+                        highlight_filter_matches(
+                            data_item,
+                            item_spans,
+                            &matched_indices,
+                            filter_match_fg_color,
+                        )
                     }
+                    _ => item_spans,
                 };
-
-                let data_item = format!("{row_prefix}{data_item}");
-                let data_item: String =
-                    clip_string_to_width_with_ellipsis(data_item, viewport_width);
+                let item_spans = scroll_item_spans_horizontally(
+                    item_spans,
+                    state.horizontal_scroll_offset,
+                );
+                let row_prefix_display_width: ChUnit =
+                    UnicodeString::from(format!("{row_prefix}{icon_text}")).display_width;
+                let (row_prefix, icon_text, item_spans) =
+                    if row_prefix_display_width < viewport_width {
+                        let item_spans = clip_item_spans_to_width_with_ellipsis(
+                            item_spans,
+                            viewport_width - row_prefix_display_width,
+                            &truncation_indicator,
+                        );
+                        (row_prefix, icon_text, item_spans)
+                    } else {
+                        // Not enough room to paint the icon (or the spans) separately.
+                        let item_text: String =
+                            item_spans.iter().map(|it| it.text.as_str()).collect();
+                        let row_text = clip_string_to_width_with_ellipsis(
+                            format!("{row_prefix}{icon_text}{item_text}"),
+                            viewport_width,
+                            &truncation_indicator,
+                        );
+                        (row_text, "".to_string(), vec![])
+                    };
+                let item_spans_display_width =
+                    item_spans.iter().fold(ch!(0), |acc, it| {
+                        acc + UnicodeString::from(&it.text).display_width
+                    });
                 let data_item_display_width: ChUnit =
-                    UnicodeString::from(&data_item).display_width;
+                    UnicodeString::from(format!("{row_prefix}{icon_text}")).display_width
+                        + item_spans_display_width;
                 let padding_right = if data_item_display_width < viewport_width {
                     " ".repeat(ch!(@to_usize (viewport_width - data_item_display_width)))
                 } else {
@@ -364,8 +492,106 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
                     apply_style!(data_style => reverse),
                     apply_style!(data_style => hidden),
                     apply_style!(data_style => strikethrough),
+                    // Print the row prefix.
+                    Print(row_prefix),
+                }?;
+
+                if !icon_text.is_empty() {
+                    match maybe_icon_fg_color {
+                        Some(icon_fg_color) => queue! {
+                            writer,
+                            // Use the icon's color, then restore the row's color.
+                            SetForegroundColor(
+                                get_crossterm_color_based_on_terminal_capabilities(
+                                    icon_fg_color
+                                )
+                            ),
+                            Print(icon_text),
+                            apply_style!(data_style => fg_color),
+                        }?,
+                        None => queue! {
+                            writer,
+                            Print(icon_text),
+                        }?,
+                    }
+                }
+
+                for item_span in item_spans {
+                    match item_span.maybe_fg_color {
+                        Some(span_fg_color) => queue! {
+                            writer,
+                            // Use the span's color, then restore the row's color.
+                            SetForegroundColor(
+                                get_crossterm_color_based_on_terminal_capabilities(
+                                    span_fg_color
+                                )
+                            ),
+                            Print(item_span.text),
+                            apply_style!(data_style => fg_color),
+                        }?,
+                        None => queue! {
+                            writer,
+                            Print(item_span.text),
+                        }?,
+                    }
+                }
+
+                queue! {
+                    writer,
+                    // Print the padding text.
+                    Print(padding_right),
+                    // Move to next line.
+                    MoveToNextLine(1),
+                    // Reset the colors.
+                    ResetColor,
+                }?;
+            }
+
+            // Print each line in the preview pane. Lines that don't fit are clipped, and
+            // rows that don't have a line are painted blank so stale content is cleared.
+            for preview_row_index in 0..ch!(@to_usize preview_viewport_height) {
+                let preview_line = match state.preview_lines.get(preview_row_index) {
+                    Some(line) => {
+                        format!("{}{line}", " ".repeat(start_display_col_offset))
+                    }
+                    None => "".to_string(),
+                };
+                let preview_line: String = clip_string_to_width_with_ellipsis(
+                    preview_line,
+                    viewport_width,
+                    &truncation_indicator,
+                );
+                let preview_line_display_width: ChUnit =
+                    UnicodeString::from(&preview_line).display_width;
+                let padding_right = if preview_line_display_width < viewport_width {
+                    " ".repeat(
+                        ch!(@to_usize (viewport_width - preview_line_display_width)),
+                    )
+                } else {
+                    "".to_string()
+                };
+
+                queue! {
+                    writer,
+                    // Bring the caret back to the start of line.
+                    MoveToColumn(0),
+                    // Reset the colors that may have been set by the previous command.
+                    ResetColor,
+                    // Clear the current line.
+                    Clear(ClearType::CurrentLine),
+                    // Set the colors for the text.
+                    apply_style!(preview_style => fg_color),
+                    apply_style!(preview_style => bg_color),
+                    // Style the text.
+                    apply_style!(preview_style => bold),
+                    apply_style!(preview_style => italic),
+                    apply_style!(preview_style => dim),
+                    apply_style!(preview_style => underline),
+                    apply_style!(preview_style => reverse),
+                    apply_style!(preview_style => hidden),
+                    apply_style!(preview_style => strikethrough),
                     // Print the text.
-                    Print(data_item),
+                    Print(preview_line),
                     // Print the padding text.
                     Print(padding_right),
                     // Move to next line.
@@ -378,7 +604,11 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
             // Move the cursor back up.
             queue! {
                 writer,
-                MoveToPreviousLine(*items_viewport_height + *header_viewport_height),
+                MoveToPreviousLine(
+                    *items_viewport_height
+                        + *header_viewport_height
+                        + *preview_viewport_height
+                ),
             }?;
 
             writer.flush()?;
@@ -386,45 +616,278 @@ impl<W: Write> FunctionComponent<W, State<'_>> for SelectComponent<W> {
     }
 }
 
+/// Returns the text that is painted before the icon and text of an item, which shows
+/// whether the item is focused (and selected). It has the same display width for all the
+/// items in a given [SelectionMode].
+pub fn get_row_prefix(
+    selection_mode: SelectionMode,
+    is_focused: bool,
+    is_selected: bool,
+) -> String {
+    let padding_left = " ".repeat(START_DISPLAY_COL_OFFSET);
+    match selection_mode {
+        SelectionMode::Single => {
+            if is_focused {
+                format!("{padding_left} {SINGLE_SELECT_IS_SELECTED} ")
+            } else {
+                format!("{padding_left} {SINGLE_SELECT_IS_NOT_SELECTED} ")
+            }
+        }
+        SelectionMode::Multiple => match (is_focused, is_selected) {
+            (true, true) => {
+                format!("{padding_left} {IS_FOCUSED} {MULTI_SELECT_IS_SELECTED} ")
+            }
+            (true, false) => {
+                format!("{padding_left} {IS_FOCUSED} {MULTI_SELECT_IS_NOT_SELECTED} ")
+            }
+            (false, true) => {
+                format!("{padding_left} {IS_NOT_FOCUSED} {MULTI_SELECT_IS_SELECTED} ")
+            }
+            (false, false) => {
+                format!("{padding_left} {IS_NOT_FOCUSED} {MULTI_SELECT_IS_NOT_SELECTED} ")
+            }
+        },
+    }
+}
+
+/// Drops the first `horizontal_scroll_offset` display columns of the text that is spread
+/// across the `item_spans`. Grapheme clusters are never split, so a wide grapheme that
+/// straddles the left edge of the viewport is replaced w/ spaces, which keeps the rest of
+/// the text in place.
+pub fn scroll_item_spans_horizontally(
+    item_spans: Vec<ItemSpan>,
+    horizontal_scroll_offset: ChUnit,
+) -> Vec<ItemSpan> {
+    let mut skip_col_count = horizontal_scroll_offset;
+    let mut scrolled_item_spans = vec![];
+    for item_span in item_spans {
+        if skip_col_count == ch!(0) {
+            scrolled_item_spans.push(item_span);
+            continue;
+        }
+
+        let mut text = String::new();
+        for segment in UnicodeString::from(&item_span.text).iter() {
+            if skip_col_count == ch!(0) {
+                text.push_str(&segment.string);
+            } else if segment.unicode_width <= skip_col_count {
+                skip_col_count -= segment.unicode_width;
+            } else {
+                let visible_col_count = segment.unicode_width - skip_col_count;
+                text.push_str(&" ".repeat(ch!(@to_usize visible_col_count)));
+                skip_col_count = ch!(0);
+            }
+        }
+
+        if !text.is_empty() {
+            scrolled_item_spans.push(ItemSpan {
+                text,
+                maybe_fg_color: item_span.maybe_fg_color,
+            });
+        }
+    }
+    scrolled_item_spans
+}
+
+/// Clip the text to fit in the viewport, and paint the `truncation_indicator` at the end
+/// if it had to be clipped (in the style of the text).
 pub fn clip_string_to_width_with_ellipsis(
-    mut header_text: String,
+    text: String,
     viewport_width: ChUnit,
+    truncation_indicator: &TruncationIndicator<Style>,
 ) -> String {
-    let unicode_string = UnicodeString::from(header_text);
-    let unicode_string_width = unicode_string.display_width;
-    let available_space_col_count: ChUnit = viewport_width;
-    if unicode_string_width > available_space_col_count {
-        // Clip the text to available space.
-        let clipped_text =
-            unicode_string.clip_to_width(ch!(0), available_space_col_count - 3);
-        let clipped_text = format!("{clipped_text}...");
-        header_text = clipped_text;
+    truncation_indicator.truncate_end(&text, viewport_width)
+}
+
+/// Just like [clip_string_to_width_with_ellipsis], but the text is spread across the
+/// `item_spans`. The display width of each span is measured (so wide graphemes are
+/// accounted for), and spans that don't fit are dropped. If the `truncation_indicator`
+/// has a style, then it is painted in its own span (w/ the foreground color of that
+/// style). Otherwise it is added to the end of the clipped span.
+pub fn clip_item_spans_to_width_with_ellipsis(
+    item_spans: Vec<ItemSpan>,
+    viewport_width: ChUnit,
+    truncation_indicator: &TruncationIndicator<Style>,
+) -> Vec<ItemSpan> {
+    let total_display_width = item_spans.iter().fold(ch!(0), |acc, it| {
+        acc + UnicodeString::from(&it.text).display_width
+    });
+    if total_display_width <= viewport_width {
+        return item_spans;
+    }
+
+    // If the indicator doesn't fit, then the spans are clipped w/out it.
+    let indicator_display_width = truncation_indicator.get_display_width();
+    let maybe_indicator_text = if indicator_display_width <= viewport_width {
+        Some(truncation_indicator.text)
     } else {
-        header_text = unicode_string.string;
+        None
+    };
+
+    let mut available_space_col_count: ChUnit = match maybe_indicator_text {
+        Some(_) => viewport_width - indicator_display_width,
+        None => viewport_width,
+    };
+    let mut clipped_item_spans = vec![];
+    for item_span in item_spans {
+        let unicode_string = UnicodeString::from(&item_span.text);
+        if unicode_string.display_width <= available_space_col_count {
+            available_space_col_count -= unicode_string.display_width;
+            clipped_item_spans.push(item_span);
+            continue;
+        }
+
+        let clipped_text =
+            unicode_string.truncate_end_to_fit_width(available_space_col_count);
+        match (maybe_indicator_text, truncation_indicator.maybe_style) {
+            (Some(indicator_text), Some(indicator_style)) => {
+                if !clipped_text.is_empty() {
+                    clipped_item_spans.push(ItemSpan {
+                        text: clipped_text.to_string(),
+                        maybe_fg_color: item_span.maybe_fg_color,
+                    });
+                }
+                clipped_item_spans.push(
+                    ItemSpan::new(indicator_text).with_fg_color(indicator_style.fg_color),
+                );
+            }
+            (maybe_indicator_text, _) => clipped_item_spans.push(ItemSpan {
+                text: format!("{clipped_text}{}", maybe_indicator_text.unwrap_or("")),
+                maybe_fg_color: item_span.maybe_fg_color,
+            }),
+        }
+        break;
     }
-    header_text
+    clipped_item_spans
 }
 
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
-    use r3bl_ansi_color::global_color_support::{clear_override, set_override};
+    use r3bl_ansi_color::{global_color_support::{clear_override, set_override},
+                          Color};
     use serial_test::serial;
 
     use super::*;
-    use crate::TestStringWriter;
+    use crate::{ItemIcon, TestStringWriter};
 
     #[test]
     fn test_clip_string_to_width_with_ellipsis() {
         let line = "This is a long line that needs to be clipped".to_string();
-        let clipped_line =
-            clip_string_to_width_with_ellipsis(line.clone(), ChUnit::new(20));
+        let three_dots = TruncationIndicator::three_dots();
+        let clipped_line = clip_string_to_width_with_ellipsis(
+            line.clone(),
+            ChUnit::new(20),
+            &three_dots,
+        );
         assert_eq!(clipped_line, "This is a long li...");
 
         let short_line = "This is a short line".to_string();
-        let clipped_short_line =
-            clip_string_to_width_with_ellipsis(short_line.clone(), ChUnit::new(20));
+        let clipped_short_line = clip_string_to_width_with_ellipsis(
+            short_line.clone(),
+            ChUnit::new(20),
+            &three_dots,
+        );
         assert_eq!(clipped_short_line, "This is a short line");
+
+        // The indicator is measured, so wide graphemes are accounted for.
+        let clipped_line = clip_string_to_width_with_ellipsis(
+            line.clone(),
+            ChUnit::new(20),
+            &TruncationIndicator::new("⏩"),
+        );
+        assert_eq!(clipped_line, "This is a long lin⏩");
+    }
+
+    #[test]
+    fn test_scroll_item_spans_horizontally() {
+        let item_spans = vec![
+            ItemSpan::new("ab"),
+            ItemSpan::new("c🦀d").with_fg_color(Color::Rgb(1, 2, 3)),
+        ];
+
+        assert_eq!(
+            scroll_item_spans_horizontally(item_spans.clone(), ch!(0)),
+            item_spans
+        );
+        // The first span is dropped once it is scrolled out of view.
+        assert_eq!(
+            scroll_item_spans_horizontally(item_spans.clone(), ch!(3)),
+            vec![ItemSpan::new("🦀d").with_fg_color(Color::Rgb(1, 2, 3))]
+        );
+        // The emoji is 2 columns wide, and it is not split in half.
+        assert_eq!(
+            scroll_item_spans_horizontally(item_spans.clone(), ch!(4)),
+            vec![ItemSpan::new(" d").with_fg_color(Color::Rgb(1, 2, 3))]
+        );
+        assert_eq!(
+            scroll_item_spans_horizontally(item_spans.clone(), ch!(10)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_clip_item_spans_to_width_with_ellipsis() {
+        let red = Color::Rgb(255, 0, 0);
+        let item_spans = vec![
+            ItemSpan::new("🦀🦀 "),
+            ItemSpan::new("crab").with_fg_color(red),
+            ItemSpan::new(" rust"),
+        ];
+
+        let three_dots = TruncationIndicator::three_dots();
+
+        // Everything fits.
+        assert_eq!(
+            clip_item_spans_to_width_with_ellipsis(
+                item_spans.clone(),
+                ch!(14),
+                &three_dots
+            ),
+            item_spans
+        );
+
+        // The wide graphemes are 2 columns each.
+        assert_eq!(
+            clip_item_spans_to_width_with_ellipsis(
+                item_spans.clone(),
+                ch!(10),
+                &three_dots
+            ),
+            vec![
+                ItemSpan::new("🦀🦀 "),
+                ItemSpan::new("cr...").with_fg_color(red),
+            ]
+        );
+
+        // A styled indicator is painted in its own span.
+        let blue = Color::Rgb(0, 0, 255);
+        let styled_ellipsis = TruncationIndicator::ellipsis().with_style(Style {
+            fg_color: blue,
+            ..Style::default()
+        });
+        assert_eq!(
+            clip_item_spans_to_width_with_ellipsis(
+                item_spans.clone(),
+                ch!(10),
+                &styled_ellipsis
+            ),
+            vec![
+                ItemSpan::new("🦀🦀 "),
+                ItemSpan::new("crab").with_fg_color(red),
+                ItemSpan::new("…").with_fg_color(blue),
+            ]
+        );
+
+        // The indicator doesn't fit.
+        assert_eq!(
+            clip_item_spans_to_width_with_ellipsis(
+                item_spans.clone(),
+                ch!(2),
+                &three_dots
+            ),
+            vec![ItemSpan::new("🦀")]
+        );
     }
 
     #[serial]
@@ -453,6 +916,7 @@ mod tests {
         let mut component = SelectComponent {
             write: &mut writer,
             style: StyleSheet::default(),
+            maybe_item_renderer: None,
         };
 
         set_override(r3bl_ansi_color::ColorSupport::Ansi256);
@@ -465,9 +929,302 @@ mod tests {
             generated_output
         );
 
-        let expected_output = "\u{1b}[4F\u{1b}[1G\u{1b}[0m\u{1b}[38;5;153m\u{1b}[48;5;235m\u{1b}[21m\u{1b}[23m\u{1b}[22m\u{1b}[24m\u{1b}[27m\u{1b}[28m\u{1b}[29m\u{1b}[2K Header\u{1b}[1E\u{1b}[0m\u{1b}[1G\u{1b}[0m\u{1b}[2K\u{1b}[38;5;46m\u{1b}[48;5;233m\u{1b}[21m\u{1b}[23m\u{1b}[22m\u{1b}[24m\u{1b}[27m\u{1b}[28m\u{1b}[29m  ◉ Item 1                              \u{1b}[1E\u{1b}[0m\u{1b}[1G\u{1b}[0m\u{1b}[2K\u{1b}[38;5;250m\u{1b}[48;5;233m\u{1b}[21m\u{1b}[23m\u{1b}[22m\u{1b}[24m\u{1b}[27m\u{1b}[28m\u{1b}[29m  ◌ Item 2                              \u{1b}[1E\u{1b}[0m\u{1b}[1G\u{1b}[0m\u{1b}[2K\u{1b}[38;5;250m\u{1b}[48;5;233m\u{1b}[21m\u{1b}[23m\u{1b}[22m\u{1b}[24m\u{1b}[27m\u{1b}[28m\u{1b}[29m  ◌ Item 3                              \u{1b}[1E\u{1b}[0m\u{1b}[4F";
+        let expected_output = "\n\n\n\n\u{1b}[4F\u{1b}[1G\u{1b}[0m\u{1b}[38;5;153m\u{1b}[48;5;235m\u{1b}[21m\u{1b}[23m\u{1b}[22m\u{1b}[24m\u{1b}[27m\u{1b}[28m\u{1b}[29m\u{1b}[2K Header\u{1b}[1E\u{1b}[0m\u{1b}[1G\u{1b}[0m\u{1b}[2K\u{1b}[38;5;46m\u{1b}[48;5;233m\u{1b}[21m\u{1b}[23m\u{1b}[22m\u{1b}[24m\u{1b}[27m\u{1b}[28m\u{1b}[29m  ◉ Item 1                              \u{1b}[1E\u{1b}[0m\u{1b}[1G\u{1b}[0m\u{1b}[2K\u{1b}[38;5;250m\u{1b}[48;5;233m\u{1b}[21m\u{1b}[23m\u{1b}[22m\u{1b}[24m\u{1b}[27m\u{1b}[28m\u{1b}[29m  ◌ Item 2                              \u{1b}[1E\u{1b}[0m\u{1b}[1G\u{1b}[0m\u{1b}[2K\u{1b}[38;5;250m\u{1b}[48;5;233m\u{1b}[21m\u{1b}[23m\u{1b}[22m\u{1b}[24m\u{1b}[27m\u{1b}[28m\u{1b}[29m  ◌ Item 3                              \u{1b}[1E\u{1b}[0m\u{1b}[4F";
         assert_eq!(generated_output, expected_output);
 
         clear_override();
     }
+
+    #[serial]
+    #[test]
+    fn test_select_component_grayscale() {
+        let mut state = State {
+            header: "Header".to_string(),
+            items: vec!["Item 1".to_string(), "Item 2".to_string()],
+            max_display_height: ch!(5),
+            max_display_width: ch!(40),
+            selection_mode: SelectionMode::Single,
+            ..Default::default()
+        };
+
+        let mut writer = TestStringWriter::new();
+
+        let mut component = SelectComponent {
+            write: &mut writer,
+            style: StyleSheet::default(),
+            maybe_item_renderer: None,
+        };
+
+        set_override(r3bl_ansi_color::ColorSupport::Grayscale);
+        component.render(&mut state).unwrap();
+
+        let generated_output = writer.get_buffer().to_string();
+
+        // Colors are degraded to the grayscale ramp, and never emitted as truecolor.
+        assert!(!generated_output.contains("[38;2;"));
+        assert!(!generated_output.contains("[48;2;"));
+        assert!(generated_output.contains("[38;5;"));
+        assert!(generated_output.contains("[48;5;"));
+
+        clear_override();
+    }
+
+    #[serial]
+    #[test]
+    fn test_select_component_no_color() {
+        let mut state = State {
+            header: "Header".to_string(),
+            items: vec!["Item 1".to_string(), "Item 2".to_string()],
+            max_display_height: ch!(5),
+            max_display_width: ch!(40),
+            selection_mode: SelectionMode::Single,
+            ..Default::default()
+        };
+
+        let mut writer = TestStringWriter::new();
+
+        let mut component = SelectComponent {
+            write: &mut writer,
+            style: StyleSheet::default(),
+            maybe_item_renderer: None,
+        };
+
+        set_override(r3bl_ansi_color::ColorSupport::NoColor);
+        component.render(&mut state).unwrap();
+
+        let generated_output = writer.get_buffer().to_string();
+
+        // No colors are emitted, only the terminal's default colors.
+        assert!(!generated_output.contains("[38;2;"));
+        assert!(!generated_output.contains("[48;2;"));
+        assert!(!generated_output.contains("[38;5;"));
+        assert!(!generated_output.contains("[48;5;"));
+        assert!(generated_output.contains("[39m"));
+        assert!(generated_output.contains("[49m"));
+
+        clear_override();
+    }
+
+    #[serial]
+    #[test]
+    fn test_select_component_with_preview() {
+        let mut state = State {
+            header: "Header".to_string(),
+            items: vec!["Item 1".to_string(), "Item 2".to_string()],
+            max_display_height: ch!(5),
+            max_display_width: ch!(24),
+            selection_mode: SelectionMode::Single,
+            max_preview_height: ch!(3),
+            preview_lines: vec![
+                "abc1234 Short commit".to_string(),
+                "def5678 A much longer commit message".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let mut writer = TestStringWriter::new();
+
+        let mut component = SelectComponent {
+            write: &mut writer,
+            style: StyleSheet::default(),
+            maybe_item_renderer: None,
+        };
+
+        assert_eq!(
+            component.calculate_preview_viewport_height(&mut state),
+            ch!(3)
+        );
+
+        set_override(r3bl_ansi_color::ColorSupport::Ansi256);
+        component.render(&mut state).unwrap();
+
+        let generated_output = writer.get_buffer().to_string();
+
+        // Preview lines are painted after the items, and clipped to the viewport width.
+        assert!(generated_output.contains(" abc1234 Short commit"));
+        assert!(generated_output.contains(" def5678 A much longe..."));
+        assert!(!generated_output.contains("longer commit"));
+        assert!(
+            generated_output.find("Item 2").unwrap()
+                < generated_output.find("abc1234").unwrap()
+        );
+
+        // The caret is moved back up past the header, items, and preview rows.
+        assert!(generated_output.ends_with("\u{1b}[6F"));
+
+        clear_override();
+    }
+
+    #[serial]
+    #[test]
+    fn test_select_component_with_icons() {
+        let mut state = State {
+            header: "Header".to_string(),
+            items: vec![
+                "Item 1".to_string(),
+                "Item 2".to_string(),
+                "A very long item".to_string(),
+            ],
+            item_icons: vec![
+                Some(ItemIcon::new("🦀").with_fg_color(Color::Rgb(255, 0, 0))),
+                None,
+                Some(ItemIcon::new("*")),
+            ],
+            max_display_height: ch!(5),
+            max_display_width: ch!(16),
+            selection_mode: SelectionMode::Single,
+            ..Default::default()
+        };
+
+        let mut writer = TestStringWriter::new();
+
+        let mut component = SelectComponent {
+            write: &mut writer,
+            style: StyleSheet::default(),
+            maybe_item_renderer: None,
+        };
+
+        set_override(r3bl_ansi_color::ColorSupport::Ansi256);
+        component.render(&mut state).unwrap();
+
+        let generated_output = writer.get_buffer().to_string();
+
+        // The icon is painted in its own color, then the row's color is restored.
+        assert!(generated_output.contains("  ◉ \u{1b}[38;5;196m🦀 \u{1b}[38;5;46mItem 1"));
+        // Space is reserved for the (2 column wide) icon in items w/out one.
+        assert!(generated_output.contains("  ◌    Item 2"));
+        // The icon's width is accounted for when clipping the text.
+        assert!(generated_output.contains("  ◌ *  A very...\u{1b}[1E"));
+
+        clear_override();
+    }
+
+    #[derive(Debug)]
+    struct TypeColoredItemRenderer;
+
+    impl ItemRenderer for TypeColoredItemRenderer {
+        fn render_item(
+            &mut self,
+            _item_index: usize,
+            item: &str,
+            context: RowRenderContext,
+        ) -> Vec<ItemSpan> {
+            let (name, kind) = item.split_once(':').unwrap();
+            let kind_span = ItemSpan::new(format!(" [{kind}]"));
+            vec![
+                ItemSpan::new(name),
+                if context.is_focused {
+                    kind_span.with_fg_color(Color::Rgb(255, 0, 0))
+                } else {
+                    kind_span
+                },
+            ]
+        }
+    }
+
+    #[serial]
+    #[test]
+    fn test_select_component_with_item_renderer() {
+        let mut state = State {
+            header: "Header".to_string(),
+            items: vec![
+                "src:dir".to_string(),
+                "a_long_file_name.rs:file".to_string(),
+            ],
+            max_display_height: ch!(5),
+            max_display_width: ch!(20),
+            selection_mode: SelectionMode::Single,
+            ..Default::default()
+        };
+
+        let mut writer = TestStringWriter::new();
+
+        let mut component = SelectComponent {
+            write: &mut writer,
+            style: StyleSheet::default(),
+            maybe_item_renderer: Some(Box::new(TypeColoredItemRenderer)),
+        };
+
+        set_override(r3bl_ansi_color::ColorSupport::Ansi256);
+        component.render(&mut state).unwrap();
+
+        let generated_output = writer.get_buffer().to_string();
+
+        // The span is painted in its own color, then the row's color is restored, and
+        // the row is padded to the viewport width.
+        assert!(generated_output
+            .contains("  ◉ src\u{1b}[38;5;196m [dir]\u{1b}[38;5;46m       \u{1b}[1E"));
+        // The spans are clipped to fit the viewport.
+        assert!(generated_output.contains("  ◌ a_long_file_n...\u{1b}[1E"));
+
+        clear_override();
+    }
+
+    #[serial]
+    #[test]
+    fn test_select_component_with_closure_item_renderer() {
+        let mut state = State {
+            header: "Header".to_string(),
+            items: vec!["same".to_string(), "same".to_string()],
+            max_display_height: ch!(5),
+            max_display_width: ch!(20),
+            window_size: Some(r3bl_core::Size {
+                col_count: ch!(80),
+                row_count: ch!(24),
+            }),
+            selection_mode: SelectionMode::Single,
+            ..Default::default()
+        };
+
+        let mut writer = TestStringWriter::new();
+
+        // The closure can tell duplicate items apart by their index, and keep state.
+        let mut render_count = 0;
+        let item_renderer =
+            move |item_index: usize, item: &str, context: RowRenderContext| {
+                render_count += 1;
+                // " " + " ◉ " is 4 columns wide.
+                assert_eq!(context.max_display_width, ch!(16));
+                vec![ItemSpan::new(format!(
+                    "{item_index}:{item} #{render_count}"
+                ))]
+            };
+
+        let mut component = SelectComponent {
+            write: &mut writer,
+            style: StyleSheet::default(),
+            maybe_item_renderer: Some(Box::new(item_renderer)),
+        };
+
+        set_override(r3bl_ansi_color::ColorSupport::Ansi256);
+        component.render(&mut state).unwrap();
+
+        let generated_output = writer.get_buffer().to_string();
+
+        assert!(generated_output.contains("  ◉ 0:same #1"));
+        assert!(generated_output.contains("  ◌ 1:same #2"));
+
+        clear_override();
+    }
+
+    #[test]
+    fn test_item_span_from_ansi_styled_text() {
+        let item_span = ItemSpan::from(AnsiStyledText {
+            text: "text",
+            style: &[
+                r3bl_ansi_color::Style::Bold,
+                r3bl_ansi_color::Style::Foreground(Color::Rgb(1, 2, 3)),
+            ],
+        });
+        assert_eq!(
+            item_span,
+            ItemSpan::new("text").with_fg_color(Color::Rgb(1, 2, 3))
+        );
+
+        let item_span = ItemSpan::from(AnsiStyledText {
+            text: "text",
+            style: &[r3bl_ansi_color::Style::Bold],
+        });
+        assert_eq!(item_span, ItemSpan::new("text"));
+    }
 }
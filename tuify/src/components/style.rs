@@ -16,6 +16,7 @@
  */
 
 use r3bl_ansi_color::Color;
+use r3bl_core::TruncationIndicator;
 
 #[derive(Copy, Clone, Debug)]
 pub struct StyleSheet {
@@ -24,6 +25,18 @@ pub struct StyleSheet {
     pub unselected_style: Style,
     pub selected_style: Style,
     pub header_style: Style,
+    pub preview_style: Style,
+    /// Used to paint the non-selectable section header rows in a list w/ sections.
+    pub section_header_style: Style,
+    /// Used to paint the characters of the items that match the filter text, as the user
+    /// types. Only the foreground color is used, so that the rest of the row's style
+    /// (eg: the focus and selection highlight) is preserved.
+    pub match_highlight_style: Style,
+    /// Painted at the end of the header, items, and preview lines that are too wide to
+    /// fit. For items, only the foreground color of its style is used (just like
+    /// [ItemSpan](crate::ItemSpan)), and elsewhere it is painted in the style of the
+    /// clipped text.
+    pub truncation_indicator: TruncationIndicator<Style>,
 }
 
 impl Default for StyleSheet {
@@ -48,12 +61,30 @@ impl Default for StyleSheet {
             bg_color: Color::Rgb(31, 36, 46),
             ..Style::default()
         };
+        let preview_style = Style {
+            fg_color: Color::Rgb(94, 103, 111),
+            bg_color: Color::Rgb(14, 17, 23),
+            ..Style::default()
+        };
+        let section_header_style = Style {
+            fg_color: Color::Rgb(171, 204, 242),
+            bold: true,
+            ..Style::default()
+        };
+        let match_highlight_style = Style {
+            fg_color: Color::Rgb(255, 216, 9),
+            ..Style::default()
+        };
         StyleSheet {
             focused_and_selected_style,
             focused_style,
             unselected_style,
             selected_style,
             header_style,
+            preview_style,
+            section_header_style,
+            match_highlight_style,
+            truncation_indicator: TruncationIndicator::three_dots(),
         }
     }
 }
@@ -85,12 +116,30 @@ impl StyleSheet {
             bg_color: Color::Rgb(31, 36, 46),
             ..Style::default()
         };
+        let preview_style = Style {
+            fg_color: Color::Rgb(159, 183, 190),
+            bg_color: Color::Rgb(14, 17, 23),
+            ..Style::default()
+        };
+        let section_header_style = Style {
+            fg_color: Color::Rgb(229, 239, 123),
+            bold: true,
+            ..Style::default()
+        };
+        let match_highlight_style = Style {
+            fg_color: Color::Rgb(255, 132, 18),
+            ..Style::default()
+        };
         StyleSheet {
             focused_and_selected_style,
             focused_style,
             unselected_style,
             selected_style,
             header_style,
+            preview_style,
+            section_header_style,
+            match_highlight_style,
+            truncation_indicator: TruncationIndicator::three_dots(),
         }
     }
 
@@ -120,12 +169,30 @@ impl StyleSheet {
             bg_color: Color::Rgb(31, 36, 46),
             ..Style::default()
         };
+        let preview_style = Style {
+            fg_color: Color::Rgb(188, 170, 200),
+            bg_color: Color::Rgb(14, 17, 23),
+            ..Style::default()
+        };
+        let section_header_style = Style {
+            fg_color: Color::Rgb(190, 253, 249),
+            bold: true,
+            ..Style::default()
+        };
+        let match_highlight_style = Style {
+            fg_color: Color::Rgb(255, 234, 0),
+            ..Style::default()
+        };
         StyleSheet {
             focused_and_selected_style,
             focused_style,
             unselected_style,
             selected_style,
             header_style,
+            preview_style,
+            section_header_style,
+            match_highlight_style,
+            truncation_indicator: TruncationIndicator::three_dots(),
         }
     }
 }
@@ -192,6 +259,14 @@ mod tests {
 
         assert_eq!(stylesheet.header_style.fg_color, Color::Rgb(171, 204, 242));
         assert_eq!(stylesheet.header_style.bg_color, Color::Rgb(31, 36, 46));
+
+        assert_eq!(
+            stylesheet.match_highlight_style.fg_color,
+            Color::Rgb(255, 216, 9)
+        );
+
+        assert_eq!(stylesheet.truncation_indicator.text, "...");
+        assert!(stylesheet.truncation_indicator.maybe_style.is_none());
     }
 
     #[test]
@@ -224,6 +299,11 @@ mod tests {
 
         assert_eq!(stylesheet.header_style.fg_color, Color::Rgb(229, 239, 123));
         assert_eq!(stylesheet.header_style.bg_color, Color::Rgb(31, 36, 46));
+
+        assert_eq!(
+            stylesheet.match_highlight_style.fg_color,
+            Color::Rgb(255, 132, 18)
+        );
     }
 
     #[test]
@@ -255,5 +335,9 @@ mod tests {
         assert_eq!(style_sheet.selected_style.bg_color, Color::Rgb(62, 14, 74));
         assert_eq!(style_sheet.header_style.fg_color, Color::Rgb(190, 253, 249));
         assert_eq!(style_sheet.header_style.bg_color, Color::Rgb(31, 36, 46));
+        assert_eq!(
+            style_sheet.match_highlight_style.fg_color,
+            Color::Rgb(255, 234, 0)
+        );
     }
 }
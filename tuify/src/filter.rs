@@ -0,0 +1,257 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! ### Fuzzy filtering
+//!
+//! Typing printable characters while a list is shown builds up a filter, which narrows
+//! the rows that are painted to the items that match it. An item matches if all the
+//! characters of the filter appear in it, in order, but not necessarily next to each
+//! other (a subsequence match). Eg: `"gco"` matches `"git checkout"`.
+//!
+//! The rows of a filtered list are mapped back to the items in [State::items], so the
+//! selection that is returned is always made up of the original items. See
+//! [State::get_item_index].
+
+use r3bl_ansi_color::Color;
+use r3bl_core::{ch, UnicodeString};
+
+use crate::{ItemSpan, State};
+
+/// Returns the indices of the grapheme clusters in `text` that match the grapheme
+/// clusters of `filter_text` in order, or `None` if `text` doesn't match. The first
+/// occurrence of each grapheme cluster is used, and the match is case insensitive. An
+/// empty `filter_text` matches any `text`.
+pub fn fuzzy_match(filter_text: &str, text: &str) -> Option<Vec<usize>> {
+    let filter_graphemes: Vec<String> = UnicodeString::from(filter_text)
+        .iter()
+        .map(|it| it.string.to_lowercase())
+        .collect();
+
+    let mut matched_indices = vec![];
+    for (index, segment) in UnicodeString::from(text).iter().enumerate() {
+        let Some(filter_grapheme) = filter_graphemes.get(matched_indices.len()) else {
+            break;
+        };
+        if segment.string.to_lowercase() == *filter_grapheme {
+            matched_indices.push(index);
+        }
+    }
+
+    match matched_indices.len() == filter_graphemes.len() {
+        true => Some(matched_indices),
+        false => None,
+    }
+}
+
+/// Paints the grapheme clusters of the item at the `matched_indices` (see [fuzzy_match])
+/// w/ the `match_fg_color`, by splitting the `item_spans` into matched and unmatched
+/// spans. If the text of the spans isn't the text of the item (eg: an
+/// [ItemRenderer](crate::ItemRenderer) added a column), then the matched indices don't
+/// line up w/ the spans, and they are returned as is.
+pub fn highlight_filter_matches(
+    item: &str,
+    item_spans: Vec<ItemSpan>,
+    matched_indices: &[usize],
+    match_fg_color: Color,
+) -> Vec<ItemSpan> {
+    let item_spans_text: String = item_spans.iter().map(|it| it.text.as_str()).collect();
+    if matched_indices.is_empty() || item_spans_text != item {
+        return item_spans;
+    }
+
+    let mut highlighted_item_spans: Vec<ItemSpan> = vec![];
+    let mut grapheme_index = 0;
+    for item_span in item_spans {
+        for segment in UnicodeString::from(&item_span.text).iter() {
+            let maybe_fg_color = match matched_indices.contains(&grapheme_index) {
+                true => Some(match_fg_color),
+                false => item_span.maybe_fg_color,
+            };
+            grapheme_index += 1;
+
+            // Merge consecutive grapheme clusters that have the same color.
+            match highlighted_item_spans.last_mut() {
+                Some(last) if last.maybe_fg_color == maybe_fg_color => {
+                    last.text.push_str(&segment.string);
+                }
+                _ => highlighted_item_spans.push(ItemSpan {
+                    text: segment.string.clone(),
+                    maybe_fg_color,
+                }),
+            }
+        }
+    }
+    highlighted_item_spans
+}
+
+impl State<'_> {
+    /// Returns true if there is a filter, in which case only the items that match it are
+    /// painted. See [State::set_filter_text].
+    pub fn is_filtered(&self) -> bool { !self.filter_text.is_empty() }
+
+    /// Set the filter, and narrow the rows down to the items that match it. A section
+    /// header is kept if any of the items in its section match (the header itself is
+    /// never matched). The focus moves to the first item, since the focused item may not
+    /// match.
+    pub fn set_filter_text(&mut self, filter_text: impl Into<String>) {
+        self.filter_text = filter_text.into();
+        self.filtered_indices = match self.is_filtered() {
+            true => self.get_matching_indices(),
+            false => vec![],
+        };
+        self.raw_caret_row_index = ch!(0);
+        self.scroll_offset_row_index = ch!(0);
+        self.focus_first_item();
+    }
+
+    /// Returns the indices of the items that match the filter, each preceded by the index
+    /// of its section header (if it has one), the first time that its section matches.
+    fn get_matching_indices(&self) -> Vec<usize> {
+        let mut acc = vec![];
+        let mut maybe_section_header_index = None;
+        for (index, item) in self.items.iter().enumerate() {
+            if self.is_section_header(index) {
+                maybe_section_header_index = Some(index);
+                continue;
+            }
+            if fuzzy_match(&self.filter_text, item).is_none() {
+                continue;
+            }
+            if let Some(section_header_index) = maybe_section_header_index.take() {
+                acc.push(section_header_index);
+            }
+            acc.push(index);
+        }
+        acc
+    }
+
+    /// The number of rows in the list, which is the number of items that match the
+    /// filter (if there is one), or the number of items.
+    pub fn get_row_count(&self) -> usize {
+        match self.is_filtered() {
+            true => self.filtered_indices.len(),
+            false => self.items.len(),
+        }
+    }
+
+    /// Maps the index of a row in the (possibly filtered) list to the index of its item
+    /// in [State::items]. Returns `None` if the row is out of bounds.
+    pub fn get_item_index(&self, row_index: usize) -> Option<usize> {
+        match self.is_filtered() {
+            true => self.filtered_indices.get(row_index).copied(),
+            false => (row_index < self.items.len()).then_some(row_index),
+        }
+    }
+
+    /// Returns true if the row at the given index is a section header.
+    pub fn is_section_header_row(&self, row_index: usize) -> bool {
+        match self.get_item_index(row_index) {
+            Some(index) => self.is_section_header(index),
+            None => false,
+        }
+    }
+
+    /// Returns the index in [State::items] of the item that has focus, or `None` if the
+    /// filter doesn't match any items.
+    pub fn get_focused_item_index(&self) -> Option<usize> {
+        self.get_item_index(ch!(@to_usize self.get_focused_index()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::ListSection;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq2!(fuzzy_match("gco", "git checkout"), Some(vec![0, 4, 9]));
+        assert_eq2!(fuzzy_match("GCO", "git checkout"), Some(vec![0, 4, 9]));
+        assert_eq2!(fuzzy_match("", "git checkout"), Some(vec![]));
+        assert_eq2!(fuzzy_match("ocg", "git checkout"), None);
+        assert_eq2!(fuzzy_match("gitx", "git"), None);
+        // Grapheme clusters are matched as a whole.
+        assert_eq2!(fuzzy_match("🦀r", "a 🦀 rust"), Some(vec![2, 4]));
+    }
+
+    #[test]
+    fn test_highlight_filter_matches() {
+        let color = Color::Rgb(1, 2, 3);
+        let item_spans = vec![ItemSpan::new("ab"), ItemSpan::new("cd")];
+        assert_eq2!(
+            highlight_filter_matches("abcd", item_spans.clone(), &[1, 2], color),
+            vec![
+                ItemSpan::new("a"),
+                ItemSpan::new("bc").with_fg_color(color),
+                ItemSpan::new("d"),
+            ]
+        );
+
+        // The spans don't spell out the item.
+        let item_spans = vec![ItemSpan::new("abcd"), ItemSpan::new(" (4)")];
+        assert_eq2!(
+            highlight_filter_matches("abcd", item_spans.clone(), &[1, 2], color),
+            item_spans
+        );
+    }
+
+    #[test]
+    fn test_set_filter_text() {
+        let (items, section_header_indices) = ListSection::flatten(vec![
+            ListSection::new("Fruits", vec!["apple".to_string(), "pear".to_string()]),
+            ListSection::new("Veg", vec!["kale".to_string(), "pea".to_string()]),
+        ]);
+        let mut state = State {
+            max_display_height: ch!(3),
+            items,
+            section_header_indices,
+            ..Default::default()
+        };
+        state.raw_caret_row_index = ch!(2);
+
+        // The headers of the sections w/ matching items are kept, and the focus moves to
+        // the first item.
+        state.set_filter_text("pea");
+        assert_eq2!(state.filtered_indices, vec![0, 2, 3, 5]);
+        assert_eq2!(state.get_row_count(), 4);
+        assert_eq2!(state.get_focused_item_index(), Some(2));
+        assert_eq2!(state.get_item_index(3), Some(5));
+        assert_eq2!(state.get_item_index(4), None);
+        assert_eq2!(state.is_section_header_row(0), true);
+        assert_eq2!(state.is_section_header_row(2), true);
+
+        // The header of a section w/out matching items is dropped, and a header never
+        // matches by itself.
+        state.set_filter_text("kal");
+        assert_eq2!(state.filtered_indices, vec![3, 4]);
+        state.set_filter_text("Veg");
+        assert_eq2!(state.get_row_count(), 0);
+
+        // Nothing matches.
+        state.set_filter_text("xyz");
+        assert_eq2!(state.get_row_count(), 0);
+        assert_eq2!(state.get_focused_item_index(), None);
+
+        // Clearing the filter brings back all the rows, and the first item is focused.
+        state.set_filter_text("");
+        assert_eq2!(state.get_row_count(), 6);
+        assert_eq2!(state.get_focused_item_index(), Some(1));
+        assert_eq2!(state.is_section_header_row(0), true);
+    }
+}
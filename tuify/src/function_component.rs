@@ -20,7 +20,8 @@ use std::io::{Result, Write};
 use crossterm::{cursor::{MoveToNextLine, MoveToPreviousLine},
                 queue,
                 terminal::{Clear, ClearType}};
-use r3bl_core::{call_if_true, throws, ChUnit, Size};
+use r3bl_ansi_color::{AnsiStyledText, Color, Style as AnsiStyle};
+use r3bl_core::{call_if_true, ch, throws, ChUnit, Size};
 
 use crate::{ResizeHint, DEVELOPMENT_MODE};
 
@@ -31,6 +32,103 @@ pub trait CalculateResizeHint {
     fn clear_resize_hint(&mut self);
 }
 
+/// The state of the row that is passed to an [ItemRenderer].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct RowRenderContext {
+    pub is_focused: bool,
+    pub is_selected: bool,
+    /// The number of display columns that are available for the spans (after the row
+    /// prefix and icon). Spans that are wider than this are clipped w/ an ellipsis.
+    pub max_display_width: ChUnit,
+}
+
+/// A span of text in a row that is produced by an [ItemRenderer].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ItemSpan {
+    pub text: String,
+    /// If this is `None`, then the style of the row is used. The rest of the row's style
+    /// (eg: its background color) is always applied, so that the focus and selection
+    /// highlight stay consistent.
+    pub maybe_fg_color: Option<Color>,
+}
+
+impl ItemSpan {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            maybe_fg_color: None,
+        }
+    }
+
+    pub fn with_fg_color(mut self, color: Color) -> Self {
+        self.maybe_fg_color = Some(color);
+        self
+    }
+}
+
+/// Only the foreground color of the [AnsiStyledText] is used, since the rest of the
+/// row's style is always applied.
+impl From<AnsiStyledText<'_>> for ItemSpan {
+    fn from(styled_text: AnsiStyledText<'_>) -> Self {
+        let maybe_fg_color = styled_text.style.iter().rev().find_map(|it| match it {
+            AnsiStyle::Foreground(color) => Some(*color),
+            _ => None,
+        });
+        Self {
+            text: styled_text.text.to_string(),
+            maybe_fg_color,
+        }
+    }
+}
+
+/// Produces the spans that are painted for an item (after the row prefix and icon). This
+/// allows rich items (eg: multiple columns, or colored by type) to be painted. The spans
+/// are clipped to the width of the viewport, so they don't need to be measured.
+///
+/// The item itself (not the spans) is what gets returned in the selection.
+///
+/// Closures w/ the signature `FnMut(usize, &str, RowRenderContext) -> Vec<ItemSpan>` are
+/// renderers too, so there is no need to declare a type for simple ones.
+pub trait ItemRenderer {
+    /// The `item_index` is the index of the `item` in [State::items](crate::State::items),
+    /// even when the list is filtered.
+    fn render_item(
+        &mut self,
+        item_index: usize,
+        item: &str,
+        context: RowRenderContext,
+    ) -> Vec<ItemSpan>;
+}
+
+impl<F> ItemRenderer for F
+where
+    F: FnMut(usize, &str, RowRenderContext) -> Vec<ItemSpan>,
+{
+    fn render_item(
+        &mut self,
+        item_index: usize,
+        item: &str,
+        context: RowRenderContext,
+    ) -> Vec<ItemSpan> {
+        self(item_index, item, context)
+    }
+}
+
+/// Paints the item as is, using the style of the row.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct DefaultItemRenderer;
+
+impl ItemRenderer for DefaultItemRenderer {
+    fn render_item(
+        &mut self,
+        _item_index: usize,
+        item: &str,
+        _context: RowRenderContext,
+    ) -> Vec<ItemSpan> {
+        vec![ItemSpan::new(item)]
+    }
+}
+
 pub trait FunctionComponent<W: Write, S: CalculateResizeHint> {
     fn get_write(&mut self) -> &mut W;
 
@@ -38,22 +136,28 @@ pub trait FunctionComponent<W: Write, S: CalculateResizeHint> {
 
     fn calculate_items_viewport_height(&self, state: &mut S) -> ChUnit;
 
+    /// The number of rows that are painted below the items, eg: for a preview pane. By
+    /// default nothing is painted below the items.
+    fn calculate_preview_viewport_height(&self, _state: &mut S) -> ChUnit { ch!(0) }
+
     fn render(&mut self, state: &mut S) -> Result<()>;
 
     fn allocate_viewport_height_space(&mut self, state: &mut S) -> Result<()> {
         throws!({
             let viewport_height =
                 /* not including the header */ self.calculate_items_viewport_height(state) +
-                /* for header row(s) */ self.calculate_header_viewport_height(state);
+                /* for header row(s) */ self.calculate_header_viewport_height(state) +
+                /* for preview row(s) */ self.calculate_preview_viewport_height(state);
 
             // Allocate space. This is required so that the commands to move the cursor up and
-            // down shown below will work.
+            // down shown below will work. This uses the component's writer (and not
+            // `stdout`), so that it can be used to render to `stderr`.
+            let writer = self.get_write();
             for _ in 0..*viewport_height {
-                println!();
+                writeln!(writer)?;
             }
 
             // Move the cursor back up.
-            let writer = self.get_write();
             queue! {
                 writer,
                 MoveToPreviousLine(*viewport_height),
@@ -75,7 +179,9 @@ pub trait FunctionComponent<W: Write, S: CalculateResizeHint> {
                     /* not including the header */
                     self.calculate_items_viewport_height(state) +
                     /* for header row(s) */
-                    self.calculate_header_viewport_height(state)
+                    self.calculate_header_viewport_height(state) +
+                    /* for preview row(s) */
+                    self.calculate_preview_viewport_height(state)
                 }
                 // Nothing to do, since resize didn't happen.
                 None => return Ok(()),
@@ -107,7 +213,8 @@ pub trait FunctionComponent<W: Write, S: CalculateResizeHint> {
         throws!({
             let viewport_height =
                 /* not including the header */ self.calculate_items_viewport_height(state) +
-                /* for header row(s) */ self.calculate_header_viewport_height(state);
+                /* for header row(s) */ self.calculate_header_viewport_height(state) +
+                /* for preview row(s) */ self.calculate_preview_viewport_height(state);
 
             let writer = self.get_write();
 
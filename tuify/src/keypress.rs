@@ -34,12 +34,21 @@ pub trait KeyPressReader {
 pub enum KeyPress {
     Up,
     Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
     Enter,
     Esc,
     #[default]
     Noop,
     Error,
     Space,
+    /// A printable character, which is added to the filter.
+    Char(char),
+    Backspace,
     Resize(Size),
     CtrlC,
 }
@@ -79,14 +88,26 @@ fn read_key_press_unix() -> KeyPress {
                     code: KeyCode::Char('c'),
                     ..
                 }) => KeyPress::CtrlC,
+                crossterm::event::Event::Key(KeyEvent {
+                    modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                    code: KeyCode::Char(ch),
+                    ..
+                }) if ch != ' ' => KeyPress::Char(ch),
                 crossterm::event::Event::Key(KeyEvent { code, .. }) => {
                     // Only trap the right code.
                     match code {
                         crossterm::event::KeyCode::Up => KeyPress::Up,
                         crossterm::event::KeyCode::Down => KeyPress::Down,
+                        crossterm::event::KeyCode::Left => KeyPress::Left,
+                        crossterm::event::KeyCode::Right => KeyPress::Right,
+                        crossterm::event::KeyCode::PageUp => KeyPress::PageUp,
+                        crossterm::event::KeyCode::PageDown => KeyPress::PageDown,
+                        crossterm::event::KeyCode::Home => KeyPress::Home,
+                        crossterm::event::KeyCode::End => KeyPress::End,
                         crossterm::event::KeyCode::Enter => KeyPress::Enter,
                         crossterm::event::KeyCode::Esc => KeyPress::Esc,
                         crossterm::event::KeyCode::Char(' ') => KeyPress::Space,
+                        crossterm::event::KeyCode::Backspace => KeyPress::Backspace,
                         _ => KeyPress::Noop,
                     }
                 }
@@ -137,6 +158,54 @@ fn read_key_press_windows() -> KeyPress {
                     state: KeyEventState::NONE,
                 }) => KeyPress::Up,
 
+                // Left.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Left,
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press, // This is for Windows.
+                    state: KeyEventState::NONE,
+                }) => KeyPress::Left,
+
+                // Right.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Right,
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press, // This is for Windows.
+                    state: KeyEventState::NONE,
+                }) => KeyPress::Right,
+
+                // Page up.
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageUp,
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press, // This is for Windows.
+                    state: KeyEventState::NONE,
+                }) => KeyPress::PageUp,
+
+                // Page down.
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageDown,
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press, // This is for Windows.
+                    state: KeyEventState::NONE,
+                }) => KeyPress::PageDown,
+
+                // Home.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Home,
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press, // This is for Windows.
+                    state: KeyEventState::NONE,
+                }) => KeyPress::Home,
+
+                // End.
+                Event::Key(KeyEvent {
+                    code: KeyCode::End,
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press, // This is for Windows.
+                    state: KeyEventState::NONE,
+                }) => KeyPress::End,
+
                 // Esc.
                 Event::Key(KeyEvent {
                     code: KeyCode::Esc,
@@ -161,6 +230,22 @@ fn read_key_press_windows() -> KeyPress {
                     state: KeyEventState::NONE,
                 }) => KeyPress::CtrlC,
 
+                // Printable character (w/ or w/out Shift).
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(ch),
+                    modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                    kind: KeyEventKind::Press, // This is for Windows.
+                    state: KeyEventState::NONE,
+                }) if ch != ' ' => KeyPress::Char(ch),
+
+                // Backspace.
+                Event::Key(KeyEvent {
+                    code: KeyCode::Backspace,
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press, // This is for Windows.
+                    state: KeyEventState::NONE,
+                }) => KeyPress::Backspace,
+
                 // Resize.
                 Event::Resize(width, height) => KeyPress::Resize(Size {
                     col_count: ch!(width),
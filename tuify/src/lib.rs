@@ -191,11 +191,26 @@
 //!
 //! # APIs
 //!
-//! We provide 2 APIs:
+//! We provide 3 APIs:
 //!
 //! - [`select_from_list`]: Use this API if you want to display a list of items with a single line header.
 //! - [`select_from_list_with_multi_line_header`]: Use this API if you want to display a list of items
 //!   with a multi line header.
+//! - [`SelectFromListBuilder`]: This is the single entry point that the 2 APIs above are
+//!   shorthands for. Use it if you want to pass the arguments by name, render to something other
+//!   than `stdout`, get the indices of the selected items, or turn on some of the optional
+//!   features of the list w/ its methods:
+//!   - A preview pane below the items which shows information about the focused item. The
+//!     preview lines are generated by a function that you pass in, and they are cached for each
+//!     item.
+//!   - A short icon (eg: an emoji) before the text of each item. The icons are not part of the
+//!     selection that is returned.
+//!   - Paint each item using the styled spans produced by an [`ItemRenderer`] that you pass in
+//!     (eg: multiple columns, or colored by type).
+//!   - Organize the items into [`ListSection`]s. Each section has a header row that is skipped
+//!     when navigating, and is never part of the selection.
+//!   - Remember the scroll position and selection of the list between invocations, w/ a
+//!     [`SelectionPersistence`].
 //!
 //! ## select_from_list
 //!
@@ -395,6 +410,19 @@
 //!    selected item.
 //! 1. `-t` or `--tui-height` - Optionally allows you to set the height of the TUI. The
 //!    default is 5.
+//! 1. `-f` or `--items-file` - Optionally read the list of items from a file (one item
+//!    per line) instead of `stdin`. The file must be valid UTF-8. This can't be combined
+//!    with piping into `stdin`, and since it doesn't use `stdin` it also works on macOS.
+//! 1. `-o` or `--output` (or `--output-format`) - Optionally set to `json` to print the
+//!    selected items (and their indices), and the selection mode, as JSON to `stdout`
+//!    instead of running a command, eg:
+//!    `{"selected":[{"index":0,"item":"foo"}],"mode":"single"}`. `selected` is `null`
+//!    if the user cancels, and `[]` if there are no items. In this mode `stdout` can be
+//!    piped (eg: into `jq`), since the TUI is shown on `stderr`.
+//! 1. `-0` or `--null` - Optionally split the items (from `stdin` or the items file) on
+//!    NUL bytes instead of newlines, eg: `find . -print0 | rt select-from-list -0`.
+//! 1. `-d` or `--delimiter` - Optionally split the items on this (single ASCII)
+//!    character instead of newlines, eg: `-d ,`.
 //!
 //! ## Interactive user experience
 //!
@@ -506,6 +534,8 @@
 //!      in, and it prints the user selected option to `stdout`.
 //!   1. `cat Cargo.toml | rt -s multiple -c "echo foo \'%\'"` - `stdin` is piped
 //!      in, and it prints the user selected option to `stdout`.
+//!   1. `rt -s single -c "echo foo \'%\'" -f Cargo.toml` - the items are read from
+//!      the file, and it prints the user selected option to `stdout`.
 //!
 //! - Unhappy paths (`stdin` is _not_ piped in and, or `stdout` _is_ piped out):
 //!   1. `rt -s single` - expects `stdin` to be piped in, and prints help.
@@ -586,6 +616,7 @@
 //! ```rust
 //! use std::io::Result;
 //! use r3bl_ansi_color::{AnsiStyledText, Color};
+//! use r3bl_core::TruncationIndicator;
 //! use r3bl_tuify::{components::style::{Style, StyleSheet},
 //!                 select_from_list,
 //!                 SelectionMode};
@@ -615,6 +646,20 @@
 //!             bg_color: Color::Rgb(31, 36, 46),
 //!             ..Style::default()
 //!       },
+//!       preview_style: Style {
+//!             fg_color: Color::Rgb(94, 103, 111),
+//!             ..Style::default()
+//!       },
+//!       section_header_style: Style {
+//!             fg_color: Color::Rgb(171, 204, 242),
+//!             bold: true,
+//!             ..Style::default()
+//!       },
+//!       match_highlight_style: Style {
+//!             fg_color: Color::Rgb(255, 216, 9),
+//!             ..Style::default()
+//!       },
+//!       truncation_indicator: TruncationIndicator::three_dots(),
 //!    };
 //!
 //!    // Then pass `my_custom_style` as the last argument to the `select_from_list` function.
@@ -725,20 +770,26 @@
 pub mod components;
 pub mod constants;
 pub mod event_loop;
+pub mod filter;
 pub mod function_component;
 pub mod keypress;
 pub mod public_api;
 pub mod scroll;
+pub mod select_from_list_builder;
+pub mod selection_persistence;
 pub mod state;
 pub mod test_utils;
 
 pub use components::*;
 pub use constants::*;
 pub use event_loop::*;
+pub use filter::*;
 pub use function_component::*;
 pub use keypress::*;
 pub use public_api::*;
 pub use scroll::*;
+pub use select_from_list_builder::*;
+pub use selection_persistence::*;
 pub use state::*;
 pub use test_utils::*;
 
@@ -15,26 +15,34 @@
  *   limitations under the License.
  */
 
-use std::io::stdout;
+use std::{cell::RefCell, collections::HashMap, io::Write};
 
 use clap::ValueEnum;
 use crossterm::style::Stylize;
 use r3bl_ansi_color::AnsiStyledText;
 use r3bl_core::{call_if_true, ch, get_size, Size};
 
-use crate::{enter_event_loop,
+use crate::{clamp_horizontal_scroll,
+            enter_event_loop,
             CalculateResizeHint,
             CaretVerticalViewportLocation,
             CrosstermKeyPressReader,
             EventLoopResult,
             KeyPress,
+            ListSection,
+            SavedSelection,
             SelectComponent,
+            SelectFromListBuilder,
             State,
             StyleSheet,
             DEVELOPMENT_MODE};
 
 pub const DEFAULT_HEIGHT: usize = 5;
 
+/// The number of columns that the text of the items is scrolled by when Left or Right is
+/// pressed.
+pub const HORIZONTAL_SCROLL_COL_COUNT: u16 = 4;
+
 /// This function does the work of rendering the TUI.
 ///
 /// It takes a list of items, and returns the selected item or items (depending on the
@@ -44,6 +52,9 @@ pub const DEFAULT_HEIGHT: usize = 5;
 ///
 /// If the terminal is *fully* uninteractive, it returns `None`. This is useful so that it
 /// won't block `cargo test` or when run in non-interactive CI/CD environments.
+///
+/// This is a shorthand for [SelectFromListBuilder], which passes these arguments by name,
+/// and turns on the optional features of the list.
 pub fn select_from_list(
     header: String,
     items: Vec<String>,
@@ -53,44 +64,18 @@ pub fn select_from_list(
     selection_mode: SelectionMode,
     style: StyleSheet,
 ) -> Option<Vec<String>> {
-    // There are fewer items than viewport height. So make viewport shorter.
-    let max_height_row_count = if items.len() <= max_height_row_count {
-        items.len()
-    } else {
-        max_height_row_count
-    };
-
-    let mut state = State {
-        max_display_height: ch!(max_height_row_count),
-        max_display_width: ch!(max_width_col_count),
-        items,
-        header,
-        selection_mode,
-        ..Default::default()
-    };
-
-    let mut function_component = SelectComponent {
-        write: stdout(),
-        style,
-    };
-
-    if let Ok(size) = get_size() {
-        state.set_size(size);
-    }
-
-    let result_user_input = enter_event_loop(
-        &mut state,
-        &mut function_component,
-        |state, key_press| keypress_handler(state, key_press),
-        &mut CrosstermKeyPressReader {},
-    );
-
-    match result_user_input {
-        Ok(EventLoopResult::ExitWithResult(it)) => Some(it),
-        _ => None,
-    }
+    SelectFromListBuilder::new()
+        .header(header)
+        .items(items)
+        .max_height(max_height_row_count)
+        .max_width(max_width_col_count)
+        .selection_mode(selection_mode)
+        .style(style)
+        .build_and_run()
 }
 
+/// Just like [select_from_list], but w/ a multi line header. This is a shorthand for
+/// [SelectFromListBuilder::multi_line_header].
 pub fn select_from_list_with_multi_line_header(
     multi_line_header: Vec<Vec<AnsiStyledText<'_>>>,
     items: Vec<String>,
@@ -100,45 +85,160 @@ pub fn select_from_list_with_multi_line_header(
     selection_mode: SelectionMode,
     style: StyleSheet,
 ) -> Option<Vec<String>> {
-    // There are fewer items than viewport height. So make viewport shorter.
-    let max_height_row_count = match maybe_max_height_row_count {
-        Some(requested_height) => sanitize_height(&items, requested_height),
-        None => sanitize_height(&items, DEFAULT_HEIGHT),
-    };
+    SelectFromListBuilder::new()
+        .multi_line_header(multi_line_header)
+        .items(items)
+        .max_height(maybe_max_height_row_count.unwrap_or(DEFAULT_HEIGHT))
+        .max_width(maybe_max_width_col_count.unwrap_or(0))
+        .selection_mode(selection_mode)
+        .style(style)
+        .build_and_run()
+}
 
-    let max_width_col_count = maybe_max_width_col_count.unwrap_or(0);
+/// Generates the lines of the preview pane for the given item. See [PreviewCache].
+pub type PreviewProvider<'a> = Box<dyn FnMut(&str) -> Vec<String> + 'a>;
 
-    let mut state = State {
-        max_display_height: ch!(max_height_row_count),
-        max_display_width: ch!(max_width_col_count),
-        items,
-        multi_line_header,
-        selection_mode,
-        ..Default::default()
-    };
+/// Sets up the [State] from the `builder`, and runs the event loop. Returns the selected
+/// items, and their indices (in the items), or `None` if the user does not select
+/// anything.
+pub(crate) fn run_select_from_list(
+    mut builder: SelectFromListBuilder<'_>,
+    write: impl Write,
+) -> Option<(Vec<String>, Vec<usize>)> {
+    let mut state = make_state(&mut builder);
+
+    let SelectFromListBuilder {
+        style,
+        maybe_item_renderer,
+        maybe_persistence,
+        maybe_preview_provider,
+        ..
+    } = builder;
 
     let mut function_component = SelectComponent {
-        write: stdout(),
+        write,
         style,
+        maybe_item_renderer,
     };
 
     if let Ok(size) = get_size() {
         state.set_size(size);
     }
 
+    // The keypress handler is a `Fn`, so the cache has to be mutated via a `RefCell`.
+    let maybe_preview_cache =
+        maybe_preview_provider.map(|it| RefCell::new(PreviewCache::new(it)));
+
+    // Paint the preview for the initially focused item.
+    if let Some(preview_cache) = &maybe_preview_cache {
+        preview_cache.borrow_mut().update_state(&mut state);
+    }
+
     let result_user_input = enter_event_loop(
         &mut state,
         &mut function_component,
-        |state, key_press| keypress_handler(state, key_press),
+        |state, key_press| {
+            let result = keypress_handler(state, key_press);
+            if let Some(preview_cache) = &maybe_preview_cache {
+                preview_cache.borrow_mut().update_state(state);
+            }
+            result
+        },
         &mut CrosstermKeyPressReader {},
     );
 
+    // Failing to save the state should not prevent the selection from being returned.
+    if let Some(persistence) = maybe_persistence {
+        if let Err(error) = persistence.save(SavedSelection::from_state(&state)) {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::error!("Failed to save the selection state: {error}");
+            });
+        }
+    }
+
     match result_user_input {
-        Ok(EventLoopResult::ExitWithResult(it)) => Some(it),
+        Ok(EventLoopResult::ExitWithResult(it)) => {
+            Some((it, state.get_selected_indices()))
+        }
         _ => None,
     }
 }
 
+/// Creates the [State] for the list from the `builder`, and takes the parts that it uses
+/// out of the `builder`. The first item is focused (which may not be the first row if
+/// the list has sections), unless a saved selection is restored.
+fn make_state<'a>(builder: &mut SelectFromListBuilder<'a>) -> State<'a> {
+    let mut state = State {
+        max_display_width: ch!(builder.max_width_col_count),
+        items: std::mem::take(&mut builder.items),
+        header: std::mem::take(&mut builder.header),
+        multi_line_header: std::mem::take(&mut builder.multi_line_header),
+        selection_mode: builder.selection_mode,
+        item_icons: std::mem::take(&mut builder.item_icons),
+        icon_alignment: builder.icon_alignment,
+        max_preview_height: ch!(builder.preview_height),
+        ..Default::default()
+    };
+
+    if let Some(sections) = builder.maybe_sections.take() {
+        let (items, section_header_indices) = ListSection::flatten(sections);
+        state.items = items;
+        state.section_header_indices = section_header_indices;
+    }
+
+    // There are fewer items than viewport height. So make viewport shorter.
+    state.max_display_height =
+        ch!(sanitize_height(&state.items, builder.max_height_row_count));
+
+    // The first row may be a section header, so focus the first item. This has to happen
+    // after the height is set, so that the item is scrolled into view.
+    state.focus_first_item();
+
+    if let Some(saved_selection) = builder.maybe_persistence.and_then(|it| it.load()) {
+        saved_selection.restore_into(&mut state);
+    }
+
+    state
+}
+
+/// Memoizes the lines that are generated by a preview provider function for each item.
+pub struct PreviewCache<F: FnMut(&str) -> Vec<String>> {
+    pub preview_provider: F,
+    pub cache: HashMap<String, Vec<String>>,
+}
+
+impl<F: FnMut(&str) -> Vec<String>> PreviewCache<F> {
+    pub fn new(preview_provider: F) -> Self {
+        Self {
+            preview_provider,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Get the preview lines for the given item, only calling the provider if this item
+    /// has not been seen before.
+    pub fn get(&mut self, item: &str) -> &Vec<String> {
+        let Self {
+            preview_provider,
+            cache,
+        } = self;
+        cache
+            .entry(item.to_string())
+            .or_insert_with(|| preview_provider(item))
+    }
+
+    /// Set the [State::preview_lines] for the item that currently has focus.
+    pub fn update_state(&mut self, state: &mut State<'_>) {
+        let maybe_focused_item = state
+            .get_focused_item_index()
+            .and_then(|index| state.items.get(index));
+        state.preview_lines = match maybe_focused_item {
+            Some(item) => self.get(&item.clone()).clone(),
+            None => vec![],
+        };
+    }
+}
+
 fn sanitize_height(items: &[String], requested_height: usize) -> usize {
     let num_items = items.len();
     if num_items > requested_height {
@@ -148,6 +248,223 @@ fn sanitize_height(items: &[String], requested_height: usize) -> usize {
     }
 }
 
+/// Move the caret down by one row, scrolling if it is at the bottom of the viewport.
+fn move_caret_down(state: &mut State<'_>) {
+    match state.locate_cursor_in_viewport() {
+        CaretVerticalViewportLocation::AtAbsoluteTop
+        | CaretVerticalViewportLocation::AboveTopOfViewport
+        | CaretVerticalViewportLocation::AtTopOfViewport
+        | CaretVerticalViewportLocation::InMiddleOfViewport => {
+            state.raw_caret_row_index += 1;
+        }
+
+        CaretVerticalViewportLocation::AtBottomOfViewport
+        | CaretVerticalViewportLocation::BelowBottomOfViewport => {
+            state.scroll_offset_row_index += 1;
+        }
+
+        CaretVerticalViewportLocation::AtAbsoluteBottom
+        | CaretVerticalViewportLocation::NotFound => {
+            // Do nothing.
+        }
+    }
+}
+
+/// Move the caret up by one row, scrolling if it is at the top of the viewport.
+fn move_caret_up(state: &mut State<'_>) {
+    match state.locate_cursor_in_viewport() {
+        CaretVerticalViewportLocation::NotFound
+        | CaretVerticalViewportLocation::AtAbsoluteTop => {
+            // Do nothing.
+        }
+
+        CaretVerticalViewportLocation::AboveTopOfViewport
+        | CaretVerticalViewportLocation::AtTopOfViewport => {
+            state.scroll_offset_row_index -= 1;
+        }
+
+        CaretVerticalViewportLocation::InMiddleOfViewport => {
+            state.raw_caret_row_index -= 1;
+        }
+
+        CaretVerticalViewportLocation::AtBottomOfViewport
+        | CaretVerticalViewportLocation::BelowBottomOfViewport
+        | CaretVerticalViewportLocation::AtAbsoluteBottom => {
+            state.raw_caret_row_index -= 1;
+        }
+    }
+}
+
+/// Move the focus to the next item below it, jumping over any section header rows. If
+/// there are only section headers below, then the focus does not move.
+fn move_focus_down(state: &mut State<'_>) {
+    let focused_index = ch!(@to_usize state.get_focused_index());
+    let maybe_target_index = (focused_index + 1..state.get_row_count())
+        .find(|&index| !state.is_section_header_row(index));
+    if let Some(target_index) = maybe_target_index {
+        for _ in focused_index..target_index {
+            move_caret_down(state);
+        }
+    }
+}
+
+/// Move the focus to the previous item above it, jumping over any section header rows.
+/// If the section header of the newly focused item is above the viewport, then the list
+/// is scrolled to show it (as long as the focused item stays in the viewport).
+fn move_focus_up(state: &mut State<'_>) {
+    let focused_index = ch!(@to_usize state.get_focused_index());
+    let maybe_target_index = (0..focused_index)
+        .rev()
+        .find(|&index| !state.is_section_header_row(index));
+    if let Some(target_index) = maybe_target_index {
+        for _ in target_index..focused_index {
+            move_caret_up(state);
+        }
+    }
+
+    let focused_index = ch!(@to_usize state.get_focused_index());
+    let is_header_above_viewport = focused_index > 0
+        && state.is_section_header_row(focused_index - 1)
+        && ch!(focused_index - 1) < state.scroll_offset_row_index;
+    let is_room_below_caret = state.raw_caret_row_index + 1 < state.max_display_height;
+    if is_header_above_viewport && is_room_below_caret {
+        state.scroll_offset_row_index -= 1;
+        state.raw_caret_row_index += 1;
+    }
+}
+
+/// Returns the number of rows that can be focused in the viewport, which is
+/// [State::max_display_height] unless there are fewer rows (eg: when the list is
+/// filtered) than that.
+fn get_viewport_height(state: &State<'_>) -> usize {
+    let max_display_height = ch!(@to_usize state.max_display_height);
+    max_display_height.min(state.get_row_count()).max(1)
+}
+
+/// Move the focus to the item at `target_index`, using `scroll_offset` as the new scroll
+/// offset as long as the item is visible w/ it. Otherwise the scroll offset is adjusted
+/// (by as little as possible) to bring the item into the viewport. The scroll offset is
+/// also clamped so that the viewport never goes past the last item.
+fn set_focus(state: &mut State<'_>, target_index: usize, scroll_offset: usize) {
+    let item_count = state.get_row_count();
+    if item_count == 0 {
+        return;
+    }
+    let target_index = target_index.min(item_count - 1);
+    let viewport_height = get_viewport_height(state);
+    let max_scroll_offset = item_count - viewport_height;
+    let scroll_offset = scroll_offset
+        .clamp(
+            target_index.saturating_sub(viewport_height - 1),
+            target_index,
+        )
+        .min(max_scroll_offset);
+    state.scroll_offset_row_index = ch!(scroll_offset);
+    state.raw_caret_row_index = ch!(target_index - scroll_offset);
+}
+
+/// Returns the index of the item at or below `index` that isn't a section header. If
+/// there is none, then the closest one above it is returned, but only if it is below
+/// `focused_index` (so that the focus never moves in the wrong direction).
+fn find_focusable_index_below(
+    state: &State<'_>,
+    index: usize,
+    focused_index: usize,
+) -> Option<usize> {
+    (index..state.get_row_count())
+        .find(|&it| !state.is_section_header_row(it))
+        .or_else(|| {
+            (focused_index + 1..index)
+                .rev()
+                .find(|&it| !state.is_section_header_row(it))
+        })
+}
+
+/// Returns the index of the item at or above `index` that isn't a section header. If
+/// there is none, then the closest one below it is returned, but only if it is above
+/// `focused_index` (so that the focus never moves in the wrong direction).
+fn find_focusable_index_above(
+    state: &State<'_>,
+    index: usize,
+    focused_index: usize,
+) -> Option<usize> {
+    (0..=index)
+        .rev()
+        .find(|&it| !state.is_section_header_row(it))
+        .or_else(|| {
+            (index + 1..focused_index).find(|&it| !state.is_section_header_row(it))
+        })
+}
+
+/// Move the focus (and the scroll offset) down by a page, which is the height of the
+/// viewport. The focus stops at the last item.
+fn move_focus_page_down(state: &mut State<'_>) {
+    if state.get_row_count() == 0 {
+        return;
+    }
+    let focused_index = ch!(@to_usize state.get_focused_index());
+    let page = get_viewport_height(state);
+    let target_index = (focused_index + page).min(state.get_row_count() - 1);
+    if let Some(target_index) =
+        find_focusable_index_below(state, target_index, focused_index)
+    {
+        let scroll_offset = ch!(@to_usize state.scroll_offset_row_index) + page;
+        set_focus(state, target_index, scroll_offset);
+    }
+}
+
+/// Move the focus (and the scroll offset) up by a page, which is the height of the
+/// viewport. The focus stops at the first item.
+fn move_focus_page_up(state: &mut State<'_>) {
+    if state.get_row_count() == 0 {
+        return;
+    }
+    let focused_index = ch!(@to_usize state.get_focused_index());
+    let page = get_viewport_height(state);
+    let target_index = focused_index.saturating_sub(page);
+    if let Some(target_index) =
+        find_focusable_index_above(state, target_index, focused_index)
+    {
+        let scroll_offset =
+            ch!(@to_usize state.scroll_offset_row_index).saturating_sub(page);
+        set_focus(state, target_index, scroll_offset);
+    }
+}
+
+/// Move the focus to the first item, and scroll to the top of the list (so that its
+/// section header, if any, is visible too).
+fn move_focus_to_first(state: &mut State<'_>) {
+    if let Some(target_index) = find_focusable_index_below(state, 0, 0) {
+        set_focus(state, target_index, 0);
+    }
+}
+
+/// Move the focus to the last item, and scroll to the bottom of the list.
+fn move_focus_to_last(state: &mut State<'_>) {
+    let Some(last_index) = state.get_row_count().checked_sub(1) else {
+        return;
+    };
+    if let Some(target_index) = find_focusable_index_above(state, last_index, last_index)
+    {
+        set_focus(state, target_index, last_index);
+    }
+}
+
+/// Scroll the text of all the items to the left, which reveals the start of the items.
+fn scroll_left(state: &mut State<'_>) {
+    state.horizontal_scroll_offset -= HORIZONTAL_SCROLL_COL_COUNT;
+}
+
+/// Scroll the text of all the items to the right, which reveals the end of the items
+/// that are too wide to fit. This stops when the end of the longest item is visible.
+fn scroll_right(state: &mut State<'_>) {
+    state.horizontal_scroll_offset = clamp_horizontal_scroll(
+        state.horizontal_scroll_offset + HORIZONTAL_SCROLL_COL_COUNT,
+        state.get_max_item_display_width(),
+        state.get_item_text_viewport_width(),
+    );
+}
+
 fn keypress_handler(state: &mut State<'_>, key_press: KeyPress) -> EventLoopResult {
     call_if_true!(DEVELOPMENT_MODE, {
         tracing::debug!(
@@ -183,25 +500,7 @@ fn keypress_handler(state: &mut State<'_>, key_press: KeyPress) -> EventLoopResu
             call_if_true!(DEVELOPMENT_MODE, {
                 tracing::debug!("Down");
             });
-            let caret_location = state.locate_cursor_in_viewport();
-            match caret_location {
-                CaretVerticalViewportLocation::AtAbsoluteTop
-                | CaretVerticalViewportLocation::AboveTopOfViewport
-                | CaretVerticalViewportLocation::AtTopOfViewport
-                | CaretVerticalViewportLocation::InMiddleOfViewport => {
-                    state.raw_caret_row_index += 1;
-                }
-
-                CaretVerticalViewportLocation::AtBottomOfViewport
-                | CaretVerticalViewportLocation::BelowBottomOfViewport => {
-                    state.scroll_offset_row_index += 1;
-                }
-
-                CaretVerticalViewportLocation::AtAbsoluteBottom
-                | CaretVerticalViewportLocation::NotFound => {
-                    // Do nothing.
-                }
-            }
+            move_focus_down(state);
             call_if_true!(DEVELOPMENT_MODE, {
                 tracing::debug!(
                     "enter_event_loop()::state: {}",
@@ -217,28 +516,67 @@ fn keypress_handler(state: &mut State<'_>, key_press: KeyPress) -> EventLoopResu
             call_if_true!(DEVELOPMENT_MODE, {
                 tracing::debug!("Up");
             });
+            move_focus_up(state);
 
-            match state.locate_cursor_in_viewport() {
-                CaretVerticalViewportLocation::NotFound
-                | CaretVerticalViewportLocation::AtAbsoluteTop => {
-                    // Do nothing.
-                }
-
-                CaretVerticalViewportLocation::AboveTopOfViewport
-                | CaretVerticalViewportLocation::AtTopOfViewport => {
-                    state.scroll_offset_row_index -= 1;
-                }
-
-                CaretVerticalViewportLocation::InMiddleOfViewport => {
-                    state.raw_caret_row_index -= 1;
-                }
-
-                CaretVerticalViewportLocation::AtBottomOfViewport
-                | CaretVerticalViewportLocation::BelowBottomOfViewport
-                | CaretVerticalViewportLocation::AtAbsoluteBottom => {
-                    state.raw_caret_row_index -= 1;
-                }
-            }
+            EventLoopResult::ContinueAndRerender
+        }
+
+        // Left.
+        KeyPress::Left => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("Left");
+            });
+            scroll_left(state);
+
+            EventLoopResult::ContinueAndRerender
+        }
+
+        // Right.
+        KeyPress::Right => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("Right");
+            });
+            scroll_right(state);
+
+            EventLoopResult::ContinueAndRerender
+        }
+
+        // Page down.
+        KeyPress::PageDown => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("PageDown");
+            });
+            move_focus_page_down(state);
+
+            EventLoopResult::ContinueAndRerender
+        }
+
+        // Page up.
+        KeyPress::PageUp => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("PageUp");
+            });
+            move_focus_page_up(state);
+
+            EventLoopResult::ContinueAndRerender
+        }
+
+        // Home.
+        KeyPress::Home => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("Home");
+            });
+            move_focus_to_first(state);
+
+            EventLoopResult::ContinueAndRerender
+        }
+
+        // End.
+        KeyPress::End => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("End");
+            });
+            move_focus_to_last(state);
 
             EventLoopResult::ContinueAndRerender
         }
@@ -266,14 +604,25 @@ fn keypress_handler(state: &mut State<'_>, key_press: KeyPress) -> EventLoopResu
                     format!("{:?}", state.get_focused_index()).green()
                 );
             });
-            let selection_index: usize = ch!(@to_usize state.get_focused_index());
-            let maybe_item: Option<&String> = state.items.get(selection_index);
+            let maybe_item: Option<&String> = state
+                .get_focused_item_index()
+                .and_then(|index| state.get_item(index));
             match maybe_item {
                 Some(it) => EventLoopResult::ExitWithResult(vec![it.to_string()]),
                 None => EventLoopResult::ExitWithoutResult,
             }
         }
 
+        // Escape w/ a filter clears the filter, instead of exiting.
+        KeyPress::Esc if state.is_filtered() => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("Esc: clear filter");
+            });
+            state.set_filter_text("");
+
+            EventLoopResult::ContinueAndRerender
+        }
+
         // Escape or Ctrl + c.
         KeyPress::Esc | KeyPress::CtrlC => {
             call_if_true!(DEVELOPMENT_MODE, {
@@ -290,28 +639,39 @@ fn keypress_handler(state: &mut State<'_>, key_press: KeyPress) -> EventLoopResu
                     format!("{:?}", state.get_focused_index()).magenta()
                 );
             });
-            let selection_index: usize = ch!(@to_usize state.get_focused_index());
-            let maybe_item: Option<&String> = state.items.get(selection_index);
-            let maybe_index: Option<usize> = state
-                .selected_items
-                .iter()
-                .position(|x| Some(x) == maybe_item);
-            match (maybe_item, maybe_index) {
-                // No selected_item.
-                (None, _) => (),
-                // Item already in selected_items so remove it.
-                (Some(_), Some(it)) => {
-                    state.selected_items.remove(it);
-                }
-                // Item not found in selected_items so add it.
-                (Some(it), None) => state.selected_items.push(it.to_string()),
-            };
+            // Items don't have to be unique, so toggle the item by its index.
+            if let Some(index) = state.get_focused_item_index() {
+                state.toggle_item_selection(index);
+            }
+
+            EventLoopResult::ContinueAndRerender
+        }
+
+        // Printable character.
+        KeyPress::Char(ch) => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("Char: {}", format!("{ch:?}").magenta());
+            });
+            let filter_text = format!("{}{ch}", state.filter_text);
+            state.set_filter_text(filter_text);
 
             EventLoopResult::ContinueAndRerender
         }
 
-        // Noop, default behavior on Space
-        KeyPress::Noop | KeyPress::Space => {
+        // Backspace w/ a filter.
+        KeyPress::Backspace if state.is_filtered() => {
+            call_if_true!(DEVELOPMENT_MODE, {
+                tracing::debug!("Backspace");
+            });
+            let mut filter_text = state.filter_text.clone();
+            filter_text.pop();
+            state.set_filter_text(filter_text);
+
+            EventLoopResult::ContinueAndRerender
+        }
+
+        // Noop, default behavior on Space (and Backspace w/out a filter).
+        KeyPress::Noop | KeyPress::Space | KeyPress::Backspace => {
             call_if_true!(DEVELOPMENT_MODE, {
                 tracing::debug!("Noop");
             });
@@ -373,6 +733,7 @@ mod test_select_from_list {
         let mut function_component = SelectComponent {
             write: string_writer,
             style: style_sheet,
+            maybe_item_renderer: None,
         };
 
         let mut reader = TestVecKeyPressReader {
@@ -406,6 +767,7 @@ mod test_select_from_list {
         let mut function_component = SelectComponent {
             write: string_writer,
             style: style_sheet,
+            maybe_item_renderer: None,
         };
 
         let mut reader = TestVecKeyPressReader {
@@ -429,4 +791,359 @@ mod test_select_from_list {
             }
         );
     }
+
+    #[test]
+    fn navigation_jumps_over_section_headers() {
+        let sections = vec![
+            ListSection::new("A", vec!["a1".to_string(), "a2".to_string()]),
+            ListSection::new("Empty", vec![]),
+            ListSection::new("B", vec!["b1".to_string()]),
+            ListSection::new("C", vec!["c1".to_string(), "c2".to_string()]),
+        ];
+        let (items, section_header_indices) = ListSection::flatten(sections);
+        let mut state = State {
+            max_display_height: ch!(3),
+            items,
+            section_header_indices,
+            selection_mode: SelectionMode::Multiple,
+            ..Default::default()
+        };
+        state.focus_first_item();
+        assert_eq2!(state.get_focused_index(), ch!(1));
+
+        // Down: a2, b1, c1, c2, and then it stays on the last item.
+        let mut focused_indices = vec![];
+        for _ in 0..5 {
+            keypress_handler(&mut state, KeyPress::Down);
+            focused_indices.push(ch!(@to_usize state.get_focused_index()));
+        }
+        assert_eq2!(focused_indices, vec![2, 4, 6, 7, 7]);
+        assert_eq2!(state.scroll_offset_row_index, ch!(5));
+
+        // Up: c1, b1 (w/ its header scrolled into view), a2, a1 (w/ its header scrolled
+        // into view), and then it stays on the first item.
+        let mut focused_indices_and_scroll_offsets = vec![];
+        for _ in 0..5 {
+            keypress_handler(&mut state, KeyPress::Up);
+            focused_indices_and_scroll_offsets.push((
+                ch!(@to_usize state.get_focused_index()),
+                ch!(@to_usize state.scroll_offset_row_index),
+            ));
+        }
+        assert_eq2!(
+            focused_indices_and_scroll_offsets,
+            vec![(6, 5), (4, 3), (2, 2), (1, 0), (1, 0)]
+        );
+
+        // Section headers are never selected.
+        keypress_handler(&mut state, KeyPress::Space);
+        assert_eq2!(state.selected_items, vec!["a1".to_string()]);
+        assert_eq2!(state.get_item(0), None);
+        assert_eq2!(
+            keypress_handler(&mut state, KeyPress::Enter),
+            EventLoopResult::ExitWithResult(vec!["a1".to_string()])
+        );
+    }
+
+    #[test]
+    fn make_state_w_sections_focuses_first_item() {
+        let mut builder = SelectFromListBuilder::new().max_height(3).sections(vec![
+            ListSection::new("A", vec!["a1".to_string(), "a2".to_string()]),
+            ListSection::new("B", vec!["b1".to_string()]),
+        ]);
+        let state = make_state(&mut builder);
+
+        // The first section header stays in view, above the focused item.
+        assert_eq2!(state.max_display_height, ch!(3));
+        assert_eq2!(state.scroll_offset_row_index, ch!(0));
+        assert_eq2!(state.raw_caret_row_index, ch!(1));
+        assert_eq2!(state.get_focused_index(), ch!(1));
+        assert_eq2!(
+            keypress_handler(&mut state.clone(), KeyPress::Enter),
+            EventLoopResult::ExitWithResult(vec!["a1".to_string()])
+        );
+    }
+
+    #[test]
+    fn selected_indices_of_duplicate_items() {
+        let mut state = State {
+            max_display_height: ch!(10),
+            items: ["a", "b", "a", "a"]
+                .iter()
+                .map(|it| it.to_string())
+                .collect(),
+            selection_mode: SelectionMode::Multiple,
+            ..Default::default()
+        };
+
+        // Select the last "a", then the "b", and then the first "a".
+        keypress_handler(&mut state, KeyPress::End);
+        keypress_handler(&mut state, KeyPress::Space);
+        keypress_handler(&mut state, KeyPress::Up);
+        keypress_handler(&mut state, KeyPress::Up);
+        keypress_handler(&mut state, KeyPress::Space);
+        keypress_handler(&mut state, KeyPress::Up);
+        keypress_handler(&mut state, KeyPress::Space);
+        assert_eq2!(state.get_selected_indices(), vec![3, 1, 0]);
+        assert_eq2!(
+            state.selected_items,
+            vec!["a".to_string(), "b".to_string(), "a".to_string()]
+        );
+        assert!(!state.is_item_selected(2));
+
+        // Unselecting the last "a" leaves the first one selected.
+        keypress_handler(&mut state, KeyPress::End);
+        keypress_handler(&mut state, KeyPress::Space);
+        assert_eq2!(state.get_selected_indices(), vec![1, 0]);
+        assert_eq2!(state.selected_items, vec!["b".to_string(), "a".to_string()]);
+
+        // In single selection mode, it is the focused item.
+        state.selection_mode = SelectionMode::Single;
+        keypress_handler(&mut state, KeyPress::Up);
+        assert_eq2!(state.get_selected_indices(), vec![2]);
+    }
+
+    fn get_focused_index_and_scroll_offset(state: &State<'_>) -> (usize, usize) {
+        (
+            ch!(@to_usize state.get_focused_index()),
+            ch!(@to_usize state.scroll_offset_row_index),
+        )
+    }
+
+    #[test]
+    fn page_down_and_page_up_move_by_viewport_height() {
+        for selection_mode in [SelectionMode::Single, SelectionMode::Multiple] {
+            let mut state = State {
+                max_display_height: ch!(4),
+                items: (0..10).map(|it| it.to_string()).collect(),
+                selection_mode,
+                ..Default::default()
+            };
+            state.raw_caret_row_index = ch!(1);
+
+            // PageDown: the scroll offset is clamped so the viewport doesn't go past the
+            // last item, and then the focus stays on the last item.
+            let mut results = vec![];
+            for _ in 0..4 {
+                keypress_handler(&mut state, KeyPress::PageDown);
+                results.push(get_focused_index_and_scroll_offset(&state));
+            }
+            assert_eq2!(results, vec![(5, 4), (9, 6), (9, 6), (9, 6)]);
+
+            // PageUp: the focus stops at the first item.
+            let mut results = vec![];
+            for _ in 0..4 {
+                keypress_handler(&mut state, KeyPress::PageUp);
+                results.push(get_focused_index_and_scroll_offset(&state));
+            }
+            assert_eq2!(results, vec![(5, 2), (1, 0), (0, 0), (0, 0)]);
+
+            // The caret always stays inside the viewport.
+            assert!(state.raw_caret_row_index < state.max_display_height);
+        }
+    }
+
+    #[test]
+    fn home_and_end_jump_to_first_and_last_item() {
+        let mut state = State {
+            max_display_height: ch!(3),
+            items: (0..10).map(|it| it.to_string()).collect(),
+            ..Default::default()
+        };
+        state.raw_caret_row_index = ch!(1);
+        state.scroll_offset_row_index = ch!(3);
+
+        keypress_handler(&mut state, KeyPress::End);
+        assert_eq2!(get_focused_index_and_scroll_offset(&state), (9, 7));
+        assert_eq2!(state.raw_caret_row_index, ch!(2));
+
+        keypress_handler(&mut state, KeyPress::Home);
+        assert_eq2!(get_focused_index_and_scroll_offset(&state), (0, 0));
+        assert_eq2!(state.raw_caret_row_index, ch!(0));
+    }
+
+    #[test]
+    fn paging_with_fewer_items_than_max_display_height() {
+        let mut state = create_state();
+
+        keypress_handler(&mut state, KeyPress::PageDown);
+        assert_eq2!(get_focused_index_and_scroll_offset(&state), (2, 0));
+
+        keypress_handler(&mut state, KeyPress::PageUp);
+        assert_eq2!(get_focused_index_and_scroll_offset(&state), (0, 0));
+
+        let mut state = State {
+            max_display_height: ch!(3),
+            ..Default::default()
+        };
+        for key_press in [
+            KeyPress::PageDown,
+            KeyPress::PageUp,
+            KeyPress::Home,
+            KeyPress::End,
+        ] {
+            keypress_handler(&mut state, key_press);
+            assert_eq2!(get_focused_index_and_scroll_offset(&state), (0, 0));
+        }
+    }
+
+    #[test]
+    fn paging_skips_section_headers() {
+        let sections = vec![
+            ListSection::new("A", vec!["a1".to_string(), "a2".to_string()]),
+            ListSection::new("B", vec!["b1".to_string(), "b2".to_string()]),
+            ListSection::new("C", vec!["c1".to_string()]),
+        ];
+        let (items, section_header_indices) = ListSection::flatten(sections);
+        let mut state = State {
+            max_display_height: ch!(3),
+            items,
+            section_header_indices,
+            ..Default::default()
+        };
+        state.focus_first_item();
+
+        // a1 (1) + 3 = B (3), which is a header, so b1 (4) gets focus.
+        keypress_handler(&mut state, KeyPress::PageDown);
+        assert_eq2!(get_focused_index_and_scroll_offset(&state), (4, 3));
+
+        // b1 (4) - 3 = a1 (1).
+        keypress_handler(&mut state, KeyPress::PageUp);
+        assert_eq2!(get_focused_index_and_scroll_offset(&state), (1, 0));
+
+        keypress_handler(&mut state, KeyPress::End);
+        assert_eq2!(get_focused_index_and_scroll_offset(&state), (7, 5));
+
+        // Home focuses a1, but scrolls to show its section header too.
+        keypress_handler(&mut state, KeyPress::Home);
+        assert_eq2!(get_focused_index_and_scroll_offset(&state), (1, 0));
+        assert_eq2!(state.raw_caret_row_index, ch!(1));
+    }
+
+    #[test]
+    fn typing_filters_the_items() {
+        let mut state = State {
+            max_display_height: ch!(3),
+            items: ["git status", "git checkout", "cargo check", "git checkout"]
+                .iter()
+                .map(|it| it.to_string())
+                .collect(),
+            selection_mode: SelectionMode::Multiple,
+            ..Default::default()
+        };
+
+        for ch in "chk".chars() {
+            keypress_handler(&mut state, KeyPress::Char(ch));
+        }
+        assert_eq2!(state.filter_text, "chk");
+        assert_eq2!(state.filtered_indices, vec![1, 2, 3]);
+
+        // Navigation and selection use the rows of the filtered list.
+        keypress_handler(&mut state, KeyPress::Down);
+        assert_eq2!(state.get_focused_item_index(), Some(2));
+        keypress_handler(&mut state, KeyPress::Space);
+        assert_eq2!(state.selected_items, vec!["cargo check".to_string()]);
+
+        // Backspace removes the last character, which matches more items.
+        keypress_handler(&mut state, KeyPress::Backspace);
+        keypress_handler(&mut state, KeyPress::Backspace);
+        assert_eq2!(state.filter_text, "c");
+        assert_eq2!(state.filtered_indices, vec![1, 2, 3]);
+
+        // Esc clears the filter first, and then exits.
+        assert_eq2!(
+            keypress_handler(&mut state, KeyPress::Esc),
+            EventLoopResult::ContinueAndRerender
+        );
+        assert_eq2!(state.is_filtered(), false);
+        assert_eq2!(state.get_row_count(), 4);
+        assert_eq2!(
+            keypress_handler(&mut state, KeyPress::Esc),
+            EventLoopResult::ExitWithoutResult
+        );
+    }
+
+    #[test]
+    fn enter_on_filtered_list_returns_the_original_item() {
+        let mut state = create_state();
+        keypress_handler(&mut state, KeyPress::Char('c'));
+        assert_eq2!(state.get_row_count(), 1);
+        assert_eq2!(
+            keypress_handler(&mut state, KeyPress::Enter),
+            EventLoopResult::ExitWithResult(vec!["c".to_string()])
+        );
+
+        // Nothing matches, so there is nothing to select.
+        keypress_handler(&mut state, KeyPress::Char('x'));
+        keypress_handler(&mut state, KeyPress::Down);
+        assert_eq2!(state.get_row_count(), 0);
+        assert_eq2!(
+            keypress_handler(&mut state, KeyPress::Enter),
+            EventLoopResult::ExitWithoutResult
+        );
+    }
+
+    #[test]
+    fn left_and_right_scroll_items_horizontally() {
+        let mut state = State {
+            max_display_height: ch!(3),
+            // " " + " ◌ " leaves 6 columns for the text.
+            max_display_width: ch!(10),
+            window_size: Some(Size {
+                col_count: ch!(80),
+                row_count: ch!(24),
+            }),
+            items: vec!["short".to_string(), "0123456789abcdef".to_string()],
+            ..Default::default()
+        };
+
+        // Right stops when the end of the longest item (16 - 6 = 10) is visible.
+        let mut offsets = vec![];
+        for _ in 0..4 {
+            keypress_handler(&mut state, KeyPress::Right);
+            offsets.push(ch!(@to_usize state.horizontal_scroll_offset));
+        }
+        assert_eq2!(offsets, vec![4, 8, 10, 10]);
+
+        // Left stops at the start of the items.
+        let mut offsets = vec![];
+        for _ in 0..4 {
+            keypress_handler(&mut state, KeyPress::Left);
+            offsets.push(ch!(@to_usize state.horizontal_scroll_offset));
+        }
+        assert_eq2!(offsets, vec![6, 2, 0, 0]);
+
+        // Scrolling doesn't change the focus.
+        assert_eq2!(state.get_focused_index(), ch!(0));
+
+        // All the items fit, so there is nothing to scroll.
+        state.items = vec!["short".to_string()];
+        keypress_handler(&mut state, KeyPress::Right);
+        assert_eq2!(state.horizontal_scroll_offset, ch!(0));
+    }
+
+    #[test]
+    fn preview_cache_calls_provider_once_per_item() {
+        let mut call_count = 0;
+        let mut preview_cache = PreviewCache::new(|item: &str| {
+            call_count += 1;
+            vec![format!("preview of {item}")]
+        });
+
+        let mut state = create_state();
+
+        preview_cache.update_state(&mut state);
+        assert_eq2!(state.preview_lines, vec!["preview of a".to_string()]);
+
+        state.raw_caret_row_index = ch!(1);
+        preview_cache.update_state(&mut state);
+        assert_eq2!(state.preview_lines, vec!["preview of b".to_string()]);
+
+        state.raw_caret_row_index = ch!(0);
+        preview_cache.update_state(&mut state);
+        assert_eq2!(state.preview_lines, vec!["preview of a".to_string()]);
+
+        drop(preview_cache);
+        assert_eq2!(call_count, 2);
+    }
 }
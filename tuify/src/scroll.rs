@@ -59,6 +59,20 @@
 //!    |                     |
 //!    +---------------------+ <- AtAbsoluteBottom
 //! ```
+//!
+//! ### Horizontal scrolling
+//!
+//! Items that are wider than the viewport can be scrolled horizontally. The
+//! `horizontal_scroll_offset` is the number of display columns of each item's text that
+//! are scrolled out of view on the left. Use [clamp_horizontal_scroll] so that the
+//! viewport never scrolls past the end of the longest item.
+//!
+//! ```text
+//!                  +--- viewport width ---+
+//!    +-- offset -->|                      |
+//!    this is a very|long item that doesn't| fit
+//!                  +----------------------+
+//! ```
 
 use crossterm::style::Stylize;
 use r3bl_core::{call_if_true, ch, ChUnit};
@@ -129,6 +143,19 @@ pub fn locate_cursor_in_viewport(
     }
 }
 
+/// Returns the `horizontal_scroll_offset` clamped so that the end of the longest item
+/// (`max_item_display_width`) is at the right edge of the viewport (`viewport_width`)
+/// when it is scrolled all the way. If all the items fit, then this is always `0`.
+pub fn clamp_horizontal_scroll(
+    horizontal_scroll_offset: ChUnit,
+    max_item_display_width: ChUnit,
+    viewport_width: ChUnit,
+) -> ChUnit {
+    // This saturates at 0 when the longest item fits in the viewport.
+    let max_horizontal_scroll_offset = max_item_display_width - viewport_width;
+    std::cmp::min(horizontal_scroll_offset, max_horizontal_scroll_offset)
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -178,4 +205,14 @@ mod tests {
             CaretVerticalViewportLocation::BelowBottomOfViewport
         );
     }
+
+    #[test]
+    fn test_clamp_horizontal_scroll() {
+        assert_eq!(clamp_horizontal_scroll(ch!(5), ch!(30), ch!(10)), ch!(5));
+        assert_eq!(clamp_horizontal_scroll(ch!(20), ch!(30), ch!(10)), ch!(20));
+        assert_eq!(clamp_horizontal_scroll(ch!(25), ch!(30), ch!(10)), ch!(20));
+        // All the items fit.
+        assert_eq!(clamp_horizontal_scroll(ch!(5), ch!(10), ch!(10)), ch!(0));
+        assert_eq!(clamp_horizontal_scroll(ch!(5), ch!(8), ch!(10)), ch!(0));
+    }
 }
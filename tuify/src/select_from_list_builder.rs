@@ -0,0 +1,304 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::io::{stdout, Write};
+
+use r3bl_ansi_color::AnsiStyledText;
+
+use crate::{run_select_from_list,
+            IconAlignment,
+            ItemIcon,
+            ItemRenderer,
+            ListSection,
+            PreviewProvider,
+            SelectionMode,
+            SelectionPersistence,
+            StyleSheet,
+            DEFAULT_HEIGHT};
+
+/// The single entry point to show a list, and get the item or items that the user
+/// selects. Its arguments are named (eg: the height and width can't be swapped by
+/// accident), and the optional features of the list are turned on w/ its methods.
+/// [select_from_list](crate::select_from_list) and
+/// [select_from_list_with_multi_line_header](crate::select_from_list_with_multi_line_header)
+/// are shorthands for it. Example usage:
+///
+/// ```no_run
+/// use r3bl_tuify::{SelectFromListBuilder, SelectionMode};
+///
+/// let maybe_selected_items = SelectFromListBuilder::new()
+///     .header("Pick a fruit")
+///     .items(vec!["apple".to_string(), "banana".to_string()])
+///     .max_height(10)
+///     .selection_mode(SelectionMode::Multiple)
+///     .build_and_run();
+/// ```
+///
+/// Anything that isn't set uses its default:
+/// - `max_height`: [DEFAULT_HEIGHT] rows.
+/// - `max_width`: the width of the terminal.
+/// - `selection_mode`: [SelectionMode::Single].
+/// - `style`: [StyleSheet::default].
+/// - The optional features (icons, item renderer, sections, persistence, and preview) are
+///   turned off.
+pub struct SelectFromListBuilder<'a> {
+    pub header: String,
+    /// If this isn't empty, then it is painted instead of the
+    /// [header](SelectFromListBuilder::header).
+    pub multi_line_header: Vec<Vec<AnsiStyledText<'a>>>,
+    pub items: Vec<String>,
+    pub max_height_row_count: usize,
+    /// If this is `0`, then the width of the terminal is used.
+    pub max_width_col_count: usize,
+    pub selection_mode: SelectionMode,
+    pub style: StyleSheet,
+    /// The icon for the item at the same index in the items. Use `None` for items that
+    /// don't have an icon. Missing entries are treated as `None`.
+    pub item_icons: Vec<Option<ItemIcon>>,
+    /// Whether space is reserved for the icon in items that don't have one, so that the
+    /// text of all the items lines up.
+    pub icon_alignment: IconAlignment,
+    /// If this is `None`, then the [DefaultItemRenderer](crate::DefaultItemRenderer) is
+    /// used.
+    pub maybe_item_renderer: Option<Box<dyn ItemRenderer>>,
+    /// If this is set, then these sections are shown instead of the items.
+    pub maybe_sections: Option<Vec<ListSection>>,
+    /// If this is set, then the scroll position and selection are restored from, and
+    /// saved to it.
+    pub maybe_persistence: Option<&'a SelectionPersistence>,
+    /// Number of rows that are reserved for the preview pane. If this is `0` then no
+    /// preview pane is shown.
+    pub preview_height: usize,
+    /// Generates the lines of the preview pane for the focused item.
+    pub maybe_preview_provider: Option<PreviewProvider<'a>>,
+}
+
+impl Default for SelectFromListBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            header: String::new(),
+            multi_line_header: vec![],
+            items: vec![],
+            max_height_row_count: DEFAULT_HEIGHT,
+            max_width_col_count: 0, /* use the width of the terminal */
+            selection_mode: SelectionMode::Single,
+            style: StyleSheet::default(),
+            item_icons: vec![],
+            icon_alignment: IconAlignment::default(),
+            maybe_item_renderer: None,
+            maybe_sections: None,
+            maybe_persistence: None,
+            preview_height: 0,
+            maybe_preview_provider: None,
+        }
+    }
+}
+
+impl<'a> SelectFromListBuilder<'a> {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.header = header.into();
+        self
+    }
+
+    /// Paint these lines instead of the single line [header](Self::header).
+    pub fn multi_line_header(
+        mut self,
+        multi_line_header: Vec<Vec<AnsiStyledText<'a>>>,
+    ) -> Self {
+        self.multi_line_header = multi_line_header;
+        self
+    }
+
+    pub fn items(mut self, items: Vec<String>) -> Self {
+        self.items = items;
+        self
+    }
+
+    pub fn max_height(mut self, max_height_row_count: usize) -> Self {
+        self.max_height_row_count = max_height_row_count;
+        self
+    }
+
+    /// If you pass `0`, then the width of the terminal is used.
+    pub fn max_width(mut self, max_width_col_count: usize) -> Self {
+        self.max_width_col_count = max_width_col_count;
+        self
+    }
+
+    pub fn selection_mode(mut self, selection_mode: SelectionMode) -> Self {
+        self.selection_mode = selection_mode;
+        self
+    }
+
+    pub fn style(mut self, style: StyleSheet) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Paint an [ItemIcon] before the text of each item. The icons are not part of the
+    /// items, so the selection that is returned does not contain them.
+    pub fn item_icons(
+        mut self,
+        item_icons: Vec<Option<ItemIcon>>,
+        icon_alignment: IconAlignment,
+    ) -> Self {
+        self.item_icons = item_icons;
+        self.icon_alignment = icon_alignment;
+        self
+    }
+
+    /// Paint each row using the spans produced by the [ItemRenderer] (eg: to show
+    /// multiple columns, or to color items by their type). The focus and selection
+    /// highlight of the row is still applied, and the spans are clipped to fit the
+    /// viewport. It can also be a closure, eg:
+    /// `Box::new(|index: usize, item: &str, context: RowRenderContext| vec![...])`.
+    pub fn item_renderer(mut self, item_renderer: Box<dyn ItemRenderer>) -> Self {
+        self.maybe_item_renderer = Some(item_renderer);
+        self
+    }
+
+    /// Organize the items into [ListSection]s, which are shown instead of the
+    /// [items](Self::items). Each section's header is painted (w/
+    /// [StyleSheet::section_header_style]) on its own row above its items. Section
+    /// headers can't be focused or selected, so navigating up and down jumps over them,
+    /// and they are never returned in the selection. Sections that don't have any items
+    /// are not shown. The height of the viewport includes the section header rows.
+    pub fn sections(mut self, sections: Vec<ListSection>) -> Self {
+        self.maybe_sections = Some(sections);
+        self
+    }
+
+    /// Restore the scroll position and selection of the list from the last time it was
+    /// used (w/ the same [key](SelectionPersistence::key)), and save them when the user
+    /// exits it. Since the items may have changed in the meantime, the restored selection
+    /// is validated against them. Use [SelectionPersistence::reset] to forget the saved
+    /// state.
+    pub fn persistence(mut self, persistence: &'a SelectionPersistence) -> Self {
+        self.maybe_persistence = Some(persistence);
+        self
+    }
+
+    /// Paint a preview pane (w/ `preview_height` rows) below the items, which shows some
+    /// lines of information about the currently focused item. Lines that don't fit are
+    /// dropped, and lines that are too wide are clipped. The `preview_provider` is called
+    /// w/ the focused item, and its result is cached for each item (see
+    /// [PreviewCache](crate::PreviewCache)).
+    pub fn preview(
+        mut self,
+        preview_height: usize,
+        preview_provider: impl FnMut(&str) -> Vec<String> + 'a,
+    ) -> Self {
+        self.preview_height = preview_height;
+        self.maybe_preview_provider = Some(Box::new(preview_provider));
+        self
+    }
+
+    /// Displays the list, and returns the selected item or items (depending on the
+    /// selection mode). If the user does not select anything, or the terminal is not
+    /// interactive, it returns `None`.
+    pub fn build_and_run(self) -> Option<Vec<String>> {
+        self.build_and_run_with_writer(stdout())
+    }
+
+    /// Just like [build_and_run](Self::build_and_run), but the TUI is rendered to the
+    /// given `write` instead of `stdout`. Eg: render to `stderr`, so that `stdout` can be
+    /// piped to another command.
+    pub fn build_and_run_with_writer(self, write: impl Write) -> Option<Vec<String>> {
+        run_select_from_list(self, write).map(|(selected_items, _)| selected_items)
+    }
+
+    /// Just like [build_and_run](Self::build_and_run), but the indices (in the items) of
+    /// the chosen items are returned, instead of their text. This is useful when the
+    /// items aren't unique, since the text alone can't tell which ones were chosen.
+    /// - [SelectionMode::Single]: the index of the item that had focus when Enter was
+    ///   pressed.
+    /// - [SelectionMode::Multiple]: the indices of the selected items, in the order that
+    ///   they were selected.
+    pub fn build_and_run_for_indices(self) -> Option<Vec<usize>> {
+        run_select_from_list(self, stdout()).map(|(_, selected_indices)| selected_indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let builder = SelectFromListBuilder::new();
+        assert_eq2!(builder.header, "");
+        assert_eq2!(builder.items, Vec::<String>::new());
+        assert_eq2!(builder.max_height_row_count, DEFAULT_HEIGHT);
+        assert_eq2!(builder.max_width_col_count, 0);
+        assert_eq2!(builder.selection_mode, SelectionMode::Single);
+        assert_eq2!(builder.multi_line_header.is_empty(), true);
+        assert_eq2!(builder.maybe_item_renderer.is_none(), true);
+        assert_eq2!(builder.maybe_sections.is_none(), true);
+        assert_eq2!(builder.maybe_persistence.is_none(), true);
+        assert_eq2!(builder.preview_height, 0);
+        assert_eq2!(builder.maybe_preview_provider.is_none(), true);
+    }
+
+    #[test]
+    fn test_fluent_methods() {
+        let builder = SelectFromListBuilder::new()
+            .header("Header")
+            .items(vec!["a".to_string(), "b".to_string()])
+            .max_height(10)
+            .max_width(40)
+            .selection_mode(SelectionMode::Multiple)
+            .style(StyleSheet::hot_pink_style());
+        assert_eq2!(builder.header, "Header");
+        assert_eq2!(builder.items, vec!["a".to_string(), "b".to_string()]);
+        assert_eq2!(builder.max_height_row_count, 10);
+        assert_eq2!(builder.max_width_col_count, 40);
+        assert_eq2!(builder.selection_mode, SelectionMode::Multiple);
+        assert_eq2!(
+            builder.style.focused_style.fg_color,
+            StyleSheet::hot_pink_style().focused_style.fg_color
+        );
+    }
+
+    #[test]
+    fn test_fluent_methods_for_optional_features() {
+        let persistence =
+            SelectionPersistence::with_file_path("list", "/tmp/unused.json");
+        let builder = SelectFromListBuilder::new()
+            .multi_line_header(vec![vec![AnsiStyledText {
+                text: "Header",
+                style: &[],
+            }]])
+            .item_icons(vec![None], IconAlignment::Compact)
+            .sections(vec![ListSection::new("Section", vec!["a".to_string()])])
+            .persistence(&persistence)
+            .preview(3, |item: &str| vec![item.to_string()]);
+        assert_eq2!(builder.multi_line_header.len(), 1);
+        assert_eq2!(builder.item_icons, vec![None]);
+        assert_eq2!(builder.icon_alignment, IconAlignment::Compact);
+        assert_eq2!(builder.maybe_sections.map(|it| it.len()), Some(1));
+        assert_eq2!(builder.maybe_persistence.is_some(), true);
+        assert_eq2!(builder.preview_height, 3);
+        assert_eq2!(
+            builder.maybe_preview_provider.map(|mut it| it("a")),
+            Some(vec!["a".to_string()])
+        );
+    }
+}
@@ -0,0 +1,335 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Optionally remember the scroll position and selection of a named list between
+//! invocations (eg: for repeated interactive filtering workflows). This is opt-in: pass
+//! a [SelectionPersistence] to
+//! [SelectFromListBuilder::persistence](crate::SelectFromListBuilder::persistence) to
+//! enable it.
+//!
+//! The saved state for all the lists is stored in a small JSON file, keyed by the name of
+//! each list. Since the items may have changed between invocations, the focused item is
+//! restored by its text (falling back to its index, which is clamped), and selected items
+//! that no longer exist are dropped.
+
+use std::{collections::HashMap,
+          fs,
+          io,
+          path::{Path, PathBuf}};
+
+use r3bl_core::ch;
+use serde::{Deserialize, Serialize};
+
+use crate::State;
+
+pub const SELECTION_STATE_FOLDER_NAME: &str = "r3bl-tuify";
+pub const SELECTION_STATE_FILE_NAME: &str = "selection_state.json";
+
+/// Where (and under which key) the state of a list is saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectionPersistence {
+    pub key: String,
+    pub file_path: PathBuf,
+}
+
+/// The state of a list that is saved when the user exits it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSelection {
+    pub focused_index: usize,
+    pub scroll_offset_row_index: usize,
+    pub maybe_focused_item: Option<String>,
+    pub selected_items: Vec<String>,
+}
+
+type SavedSelections = HashMap<String, SavedSelection>;
+
+impl SelectionPersistence {
+    /// Use the default file in the user's config folder (eg: `~/.config/r3bl-tuify/` on
+    /// Linux). Returns [None] if the config folder can't be determined.
+    pub fn new(key: impl Into<String>) -> Option<Self> {
+        let file_path = dirs::config_dir()?
+            .join(SELECTION_STATE_FOLDER_NAME)
+            .join(SELECTION_STATE_FILE_NAME);
+        Some(Self::with_file_path(key, file_path))
+    }
+
+    pub fn with_file_path(key: impl Into<String>, file_path: impl Into<PathBuf>) -> Self {
+        Self {
+            key: key.into(),
+            file_path: file_path.into(),
+        }
+    }
+
+    /// Returns [None] if nothing has been saved for this key, or if the file can't be
+    /// read or parsed.
+    pub fn load(&self) -> Option<SavedSelection> {
+        read_saved_selections(&self.file_path).remove(&self.key)
+    }
+
+    /// Save the state for this key, keeping the state of the other keys as is.
+    pub fn save(&self, saved_selection: SavedSelection) -> io::Result<()> {
+        let mut saved_selections = read_saved_selections(&self.file_path);
+        saved_selections.insert(self.key.clone(), saved_selection);
+        write_saved_selections(&self.file_path, &saved_selections)
+    }
+
+    /// Forget the saved state for this key.
+    pub fn reset(&self) -> io::Result<()> {
+        let mut saved_selections = read_saved_selections(&self.file_path);
+        if saved_selections.remove(&self.key).is_some() {
+            write_saved_selections(&self.file_path, &saved_selections)?;
+        }
+        Ok(())
+    }
+
+    /// Forget the saved state for all the keys, by deleting the file.
+    pub fn reset_all(&self) -> io::Result<()> {
+        match fs::remove_file(&self.file_path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn read_saved_selections(file_path: &Path) -> SavedSelections {
+    fs::read_to_string(file_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_saved_selections(
+    file_path: &Path,
+    saved_selections: &SavedSelections,
+) -> io::Result<()> {
+    if let Some(parent_folder) = file_path.parent() {
+        fs::create_dir_all(parent_folder)?;
+    }
+    let content = serde_json::to_string_pretty(saved_selections)?;
+    fs::write(file_path, content)
+}
+
+impl SavedSelection {
+    pub fn from_state(state: &State<'_>) -> Self {
+        // The list may be filtered, so save the index of the item (not the row).
+        let focused_index = state.get_focused_item_index().unwrap_or_default();
+        Self {
+            focused_index,
+            scroll_offset_row_index: ch!(@to_usize state.scroll_offset_row_index),
+            maybe_focused_item: state.items.get(focused_index).cloned(),
+            selected_items: state.selected_items.clone(),
+        }
+    }
+
+    /// Restore this into the `state`, whose items (and display height) must already be
+    /// set. The restored caret and scroll offset are always valid for the current items,
+    /// and the caret is never on a section header.
+    pub fn restore_into(&self, state: &mut State<'_>) {
+        let item_count = state.items.len();
+        if item_count == 0 {
+            return;
+        }
+
+        // Prefer the item that had focus, even if it has moved. Section headers can't be
+        // focused, so they are never matched.
+        let focused_index = self
+            .maybe_focused_item
+            .as_ref()
+            .and_then(|focused_item| {
+                (0..item_count).find(|&index| state.get_item(index) == Some(focused_item))
+            })
+            .unwrap_or(self.focused_index)
+            .min(item_count - 1);
+
+        // If the saved index is now a section header, then focus the item after it (or
+        // the one before it, at the end of the list).
+        let maybe_focused_index = (focused_index..item_count)
+            .chain((0..focused_index).rev())
+            .find(|&index| !state.is_section_header(index));
+        let Some(focused_index) = maybe_focused_index else {
+            return;
+        };
+
+        // Keep the scroll offset as long as the focused item is still visible.
+        let viewport_height = ch!(@to_usize state.max_display_height).max(1);
+        let max_scroll_offset = item_count.saturating_sub(viewport_height);
+        let scroll_offset = self
+            .scroll_offset_row_index
+            .clamp(
+                focused_index.saturating_sub(viewport_height - 1),
+                focused_index,
+            )
+            .min(max_scroll_offset);
+
+        state.scroll_offset_row_index = ch!(scroll_offset);
+        state.raw_caret_row_index = ch!(focused_index - scroll_offset);
+
+        // Items don't have to be unique, so each saved item is matched to the first item
+        // w/ the same text that hasn't been matched yet.
+        state.selected_indices.clear();
+        state.selected_items.clear();
+        for saved_item in &self.selected_items {
+            let maybe_index = (0..item_count).find(|&index| {
+                state.get_item(index) == Some(saved_item)
+                    && !state.is_item_selected(index)
+            });
+            if let Some(index) = maybe_index {
+                state.toggle_item_selection(index);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::ListSection;
+
+    fn make_state(items: &[&str], max_display_height: usize) -> State<'static> {
+        State {
+            items: items.iter().map(|it| it.to_string()).collect(),
+            max_display_height: ch!(max_display_height),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_restore_into() {
+        let saved_selection = SavedSelection {
+            focused_index: 4,
+            scroll_offset_row_index: 2,
+            maybe_focused_item: Some("e".to_string()),
+            selected_items: vec!["b".to_string(), "z".to_string()],
+        };
+
+        // Same items.
+        let mut state = make_state(&["a", "b", "c", "d", "e", "f"], 3);
+        saved_selection.restore_into(&mut state);
+        assert_eq2!(state.get_focused_index(), ch!(4));
+        assert_eq2!(state.scroll_offset_row_index, ch!(2));
+        assert_eq2!(state.selected_items, vec!["b".to_string()]);
+        assert_eq2!(state.selected_indices, vec![1]);
+
+        // The focused item moved.
+        let mut state = make_state(&["e", "a", "b", "c", "d", "f"], 3);
+        saved_selection.restore_into(&mut state);
+        assert_eq2!(state.get_focused_index(), ch!(0));
+        assert_eq2!(state.scroll_offset_row_index, ch!(0));
+
+        // The focused item is gone, and there are fewer items.
+        let mut state = make_state(&["a", "b", "c"], 3);
+        saved_selection.restore_into(&mut state);
+        assert_eq2!(state.get_focused_index(), ch!(2));
+        assert_eq2!(state.scroll_offset_row_index, ch!(0));
+        assert_eq2!(state.raw_caret_row_index, ch!(2));
+    }
+
+    #[test]
+    fn test_restore_into_list_that_shrank() {
+        let saved_selection = SavedSelection {
+            focused_index: 9,
+            scroll_offset_row_index: 7,
+            maybe_focused_item: Some("j".to_string()),
+            selected_items: vec!["b".to_string(), "i".to_string()],
+        };
+
+        let mut state = make_state(&["a", "b"], 3);
+        saved_selection.restore_into(&mut state);
+        assert_eq2!(state.get_focused_index(), ch!(1));
+        assert_eq2!(state.scroll_offset_row_index, ch!(0));
+        assert_eq2!(state.raw_caret_row_index, ch!(1));
+        assert_eq2!(state.selected_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_restore_into_list_w_sections() {
+        let (items, section_header_indices) = ListSection::flatten(vec![
+            ListSection::new("A", vec!["a1".to_string(), "a2".to_string()]),
+            ListSection::new("B", vec!["b1".to_string(), "A".to_string()]),
+        ]);
+        let mut state = State {
+            items,
+            section_header_indices,
+            max_display_height: ch!(3),
+            ..Default::default()
+        };
+
+        // The saved index is a section header now.
+        let saved_selection = SavedSelection {
+            focused_index: 3,
+            scroll_offset_row_index: 3,
+            ..Default::default()
+        };
+        saved_selection.restore_into(&mut state);
+        assert_eq2!(state.get_focused_index(), ch!(4));
+        assert_eq2!(state.scroll_offset_row_index, ch!(3));
+        assert_eq2!(state.raw_caret_row_index, ch!(1));
+
+        // The focused item has the same text as a section header, which is skipped.
+        let saved_selection = SavedSelection {
+            focused_index: 0,
+            scroll_offset_row_index: 0,
+            maybe_focused_item: Some("A".to_string()),
+            selected_items: vec!["A".to_string()],
+        };
+        saved_selection.restore_into(&mut state);
+        assert_eq2!(state.get_focused_index(), ch!(5));
+        assert_eq2!(state.selected_indices, vec![5]);
+
+        // The saved index is past the end, and the last row is a section header.
+        state.items.push("C".to_string());
+        state.section_header_indices.push(6);
+        let saved_selection = SavedSelection {
+            focused_index: 10,
+            scroll_offset_row_index: 8,
+            ..Default::default()
+        };
+        saved_selection.restore_into(&mut state);
+        assert_eq2!(state.get_focused_index(), ch!(5));
+        assert_eq2!(state.scroll_offset_row_index, ch!(4));
+        assert_eq2!(state.raw_caret_row_index, ch!(1));
+    }
+
+    #[test]
+    fn test_save_load_reset() {
+        let file_path = std::env::temp_dir()
+            .join(format!("r3bl_tuify_{}", std::process::id()))
+            .join(SELECTION_STATE_FILE_NAME);
+        let persistence_1 = SelectionPersistence::with_file_path("list 1", &file_path);
+        let persistence_2 = SelectionPersistence::with_file_path("list 2", &file_path);
+
+        assert_eq2!(persistence_1.load(), None);
+
+        let saved_selection = SavedSelection {
+            focused_index: 1,
+            ..Default::default()
+        };
+        persistence_1.save(saved_selection.clone()).unwrap();
+        persistence_2.save(SavedSelection::default()).unwrap();
+        assert_eq2!(persistence_1.load(), Some(saved_selection));
+
+        persistence_1.reset().unwrap();
+        assert_eq2!(persistence_1.load(), None);
+        assert_eq2!(persistence_2.load(), Some(SavedSelection::default()));
+
+        persistence_2.reset_all().unwrap();
+        assert_eq2!(persistence_2.load(), None);
+        fs::remove_dir_all(file_path.parent().unwrap()).unwrap();
+    }
+}
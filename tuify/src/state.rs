@@ -15,10 +15,18 @@
  *   limitations under the License.
  */
 
-use r3bl_ansi_color::AnsiStyledText;
-use r3bl_core::{ChUnit, Size};
+use std::ops::Range;
 
-use crate::{get_scroll_adjusted_row_index,
+use r3bl_ansi_color::{AnsiStyledText, Color};
+use r3bl_core::{ch,
+                get_scroll_offset_for_ratio,
+                get_terminal_width,
+                ChUnit,
+                Size,
+                UnicodeString};
+
+use crate::{get_row_prefix,
+            get_scroll_adjusted_row_index,
             locate_cursor_in_viewport,
             CalculateResizeHint,
             CaretVerticalViewportLocation,
@@ -32,8 +40,19 @@ pub struct State<'a> {
     /// This is not adjusted for [scroll_offset_row_index](State::scroll_offset_row_index).
     pub raw_caret_row_index: ChUnit,
     pub scroll_offset_row_index: ChUnit,
+    /// The number of display columns of the text of each item that are scrolled out of
+    /// view on the left. The row prefix, icons, and section headers are not scrolled.
+    /// See [clamp_horizontal_scroll](crate::clamp_horizontal_scroll).
+    pub horizontal_scroll_offset: ChUnit,
     pub items: Vec<String>,
+    /// The text of the selected items (in multiple selection mode), in the order that
+    /// they were selected. Use [toggle_item_selection](State::toggle_item_selection) to
+    /// change it, so that [selected_indices](State::selected_indices) is kept in sync.
     pub selected_items: Vec<String>,
+    /// The indices (in [items](State::items)) of the
+    /// [selected_items](State::selected_items), at the same position. Since items don't
+    /// have to be unique, these (and not the text) identify what was selected.
+    pub selected_indices: Vec<usize>,
     pub header: String,
     pub multi_line_header: Vec<Vec<AnsiStyledText<'a>>>,
     pub selection_mode: SelectionMode,
@@ -41,6 +60,124 @@ pub struct State<'a> {
     pub resize_hint: Option<ResizeHint>,
     /// This is used to determine if the terminal has been resized.
     pub window_size: Option<Size>,
+    /// Number of rows reserved for the preview pane that is painted below the items. If
+    /// this is `0` then no preview pane is shown.
+    pub max_preview_height: ChUnit,
+    /// The lines that are painted in the preview pane for the currently focused item.
+    pub preview_lines: Vec<String>,
+    /// The icon for each item in [items](State::items), at the same index. Items that
+    /// don't have an entry (or have `None`) are painted w/out an icon.
+    pub item_icons: Vec<Option<ItemIcon>>,
+    pub icon_alignment: IconAlignment,
+    /// The indices of the rows in [items](State::items) that are section headers. These
+    /// rows are painted w/ [StyleSheet::section_header_style](crate::StyleSheet), and
+    /// they can't be focused or selected. Use [ListSection::flatten] to create them.
+    pub section_header_indices: Vec<usize>,
+    /// The text that the user typed to narrow down the items. Use
+    /// [set_filter_text](State::set_filter_text) to change it, so that
+    /// [filtered_indices](State::filtered_indices) is kept in sync.
+    pub filter_text: String,
+    /// The indices (in [items](State::items)) of the items that match the
+    /// [filter_text](State::filter_text) (and the headers of their sections), which are
+    /// the rows of the list when it is filtered. This is empty when there is no filter.
+    pub filtered_indices: Vec<usize>,
+}
+
+/// A group of items that are painted under a non-selectable header row. See
+/// [SelectFromListBuilder::sections](crate::SelectFromListBuilder::sections).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ListSection {
+    pub header: String,
+    pub items: Vec<String>,
+}
+
+impl ListSection {
+    pub fn new(header: impl Into<String>, items: Vec<String>) -> Self {
+        Self {
+            header: header.into(),
+            items,
+        }
+    }
+
+    /// Returns the rows for [State::items] (each section's header followed by its
+    /// items), and the indices of the header rows for [State::section_header_indices].
+    /// Sections that don't have any items are dropped, so their header isn't shown.
+    pub fn flatten(sections: Vec<ListSection>) -> (Vec<String>, Vec<usize>) {
+        let mut rows = vec![];
+        let mut section_header_indices = vec![];
+        for section in sections {
+            if section.items.is_empty() {
+                continue;
+            }
+            section_header_indices.push(rows.len());
+            rows.push(section.header);
+            rows.extend(section.items);
+        }
+        (rows, section_header_indices)
+    }
+}
+
+/// A short glyph (eg: an emoji, or a nerd font symbol) that is painted before the text of
+/// an item. It is not part of the item, so it is never returned in the selection.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ItemIcon {
+    pub glyph: String,
+    /// If this is `None`, then the style of the row is used.
+    pub maybe_fg_color: Option<Color>,
+}
+
+impl ItemIcon {
+    pub fn new(glyph: impl Into<String>) -> Self {
+        Self {
+            glyph: glyph.into(),
+            maybe_fg_color: None,
+        }
+    }
+
+    pub fn with_fg_color(mut self, color: Color) -> Self {
+        self.maybe_fg_color = Some(color);
+        self
+    }
+}
+
+/// Controls how the text of items w/out an icon lines up w/ those that have one.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum IconAlignment {
+    /// Items w/out an icon are padded w/ spaces, so that the text of all the items lines
+    /// up. Icons w/ different display widths are padded to the widest one.
+    #[default]
+    ReserveSpace,
+    /// Items w/out an icon start their text right after the row prefix.
+    Compact,
+}
+
+/// An item (or section header) that is currently painted in the viewport. See
+/// [State::get_visible_items].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VisibleItem {
+    /// Index of the item in [State::items].
+    pub index: usize,
+    /// The rows that the item is painted on, relative to the first row that is painted
+    /// by the component (the header rows come first). Each item is currently painted on
+    /// a single row (long items are clipped w/ an ellipsis), but this may change, so
+    /// don't assume that the range only has one row.
+    pub row_range: Range<usize>,
+    /// False for section headers, which can't be focused or selected.
+    pub is_selectable: bool,
+    pub is_focused: bool,
+}
+
+/// The items that are painted in the viewport, and whether there are more items that
+/// are scrolled out of view above or below it.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct VisibleItems {
+    pub items: Vec<VisibleItem>,
+    /// The first visible item is at the top edge of the viewport, and there are more
+    /// items above it.
+    pub has_more_above: bool,
+    /// The last visible item is at the bottom edge of the viewport, and there are more
+    /// items below it.
+    pub has_more_below: bool,
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -56,6 +193,55 @@ impl State<'_> {
             false => Header::Multiple,
         }
     }
+
+    /// The number of rows that the header is painted on.
+    pub fn get_header_height(&self) -> ChUnit {
+        match self.get_header() {
+            Header::Single => ch!(1),
+            Header::Multiple => ch!(self.multi_line_header.len()),
+        }
+    }
+
+    /// The number of rows that the items are painted on, which is
+    /// [max_display_height](State::max_display_height) unless there are fewer items. This
+    /// doesn't shrink while the list is filtered (the rows that don't have an item are
+    /// painted blank), so that the component doesn't jump around while the user types.
+    pub fn get_items_viewport_height(&self) -> ChUnit {
+        std::cmp::min(ch!(self.items.len()), self.max_display_height)
+    }
+
+    /// Returns the items that are currently painted in the viewport, and the rows that
+    /// they are painted on (eg: to anchor an overlay to an item). This is derived from
+    /// the scroll offset (and the filter), so it has to be called again after the list is
+    /// scrolled.
+    pub fn get_visible_items(&self) -> VisibleItems {
+        let header_height = ch!(@to_usize self.get_header_height());
+        let scroll_offset = ch!(@to_usize self.scroll_offset_row_index);
+        let viewport_height = ch!(@to_usize self.get_items_viewport_height());
+        let focused_index = ch!(@to_usize self.get_focused_index());
+
+        let row_count = self.get_row_count();
+        let end = std::cmp::min(scroll_offset + viewport_height, row_count);
+        let items = (scroll_offset..end)
+            .filter_map(|list_row_index| {
+                let index = self.get_item_index(list_row_index)?;
+                let row_index = header_height + list_row_index - scroll_offset;
+                let is_selectable = !self.is_section_header(index);
+                Some(VisibleItem {
+                    index,
+                    row_range: row_index..row_index + 1,
+                    is_selectable,
+                    is_focused: is_selectable && list_row_index == focused_index,
+                })
+            })
+            .collect();
+
+        VisibleItems {
+            items,
+            has_more_above: scroll_offset > 0,
+            has_more_below: end < row_count,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -80,6 +266,177 @@ mod tests {
         state.multi_line_header = vec![];
         assert_eq2!(state.get_header(), Header::Single);
     }
+
+    #[test]
+    fn test_scroll_to_ratio() {
+        let mut state = State {
+            max_display_height: ch!(5),
+            raw_caret_row_index: ch!(2),
+            items: (0..25).map(|it| it.to_string()).collect(),
+            ..Default::default()
+        };
+
+        state.scroll_to_ratio(0.5);
+        assert_eq2!(state.scroll_offset_row_index, ch!(10));
+        assert_eq2!(state.get_focused_index(), ch!(12));
+
+        // The last page isn't scrolled past the last item.
+        state.scroll_to_ratio(1.5);
+        assert_eq2!(state.scroll_offset_row_index, ch!(20));
+        assert_eq2!(state.get_focused_index(), ch!(22));
+
+        state.scroll_to_ratio(-1.0);
+        assert_eq2!(state.scroll_offset_row_index, ch!(0));
+        assert_eq2!(state.get_focused_index(), ch!(2));
+
+        // All the items fit, and the caret is clipped to the last item.
+        state.items.truncate(2);
+        state.scroll_to_ratio(1.0);
+        assert_eq2!(state.scroll_offset_row_index, ch!(0));
+        assert_eq2!(state.get_focused_index(), ch!(1));
+    }
+
+    #[test]
+    fn test_get_visible_items() {
+        let (items, section_header_indices) = ListSection::flatten(vec![
+            ListSection::new("A", vec!["a1".to_string(), "a2".to_string()]),
+            ListSection::new("B", vec!["b1".to_string(), "b2".to_string()]),
+        ]);
+        let mut state = State {
+            max_display_height: ch!(3),
+            raw_caret_row_index: ch!(1),
+            items,
+            section_header_indices,
+            ..Default::default()
+        };
+
+        let visible_items = state.get_visible_items();
+        assert_eq2!(visible_items.has_more_above, false);
+        assert_eq2!(visible_items.has_more_below, true);
+        assert_eq2!(
+            visible_items.items,
+            vec![
+                VisibleItem {
+                    index: 0,
+                    row_range: 1..2,
+                    is_selectable: false,
+                    is_focused: false,
+                },
+                VisibleItem {
+                    index: 1,
+                    row_range: 2..3,
+                    is_selectable: true,
+                    is_focused: true,
+                },
+                VisibleItem {
+                    index: 2,
+                    row_range: 3..4,
+                    is_selectable: true,
+                    is_focused: false,
+                },
+            ]
+        );
+
+        // Scroll to the end, w/ a 2 row header.
+        state.scroll_offset_row_index = ch!(3);
+        state.multi_line_header = vec![vec![], vec![]];
+        let visible_items = state.get_visible_items();
+        assert_eq2!(visible_items.has_more_above, true);
+        assert_eq2!(visible_items.has_more_below, false);
+        assert_eq2!(
+            visible_items
+                .items
+                .iter()
+                .map(|it| (it.index, it.row_range.clone(), it.is_focused))
+                .collect::<Vec<_>>(),
+            vec![(3, 2..3, false), (4, 3..4, true), (5, 4..5, false)]
+        );
+
+        // All the items fit.
+        state.items.truncate(2);
+        state.scroll_offset_row_index = ch!(0);
+        let visible_items = state.get_visible_items();
+        assert_eq2!(visible_items.items.len(), 2);
+        assert_eq2!(visible_items.has_more_below, false);
+    }
+
+    #[test]
+    fn test_flatten_list_sections() {
+        let sections = vec![
+            ListSection::new("Fruits", vec!["apple".to_string(), "pear".to_string()]),
+            ListSection::new("Empty", vec![]),
+            ListSection::new("Vegetables", vec!["kale".to_string()]),
+        ];
+        let (items, section_header_indices) = ListSection::flatten(sections);
+        assert_eq2!(items, vec!["Fruits", "apple", "pear", "Vegetables", "kale"]);
+        assert_eq2!(section_header_indices, vec![0, 3]);
+
+        let state = State {
+            items,
+            section_header_indices,
+            ..Default::default()
+        };
+        assert_eq2!(state.get_item(0), None);
+        assert_eq2!(state.get_item(1), Some(&"apple".to_string()));
+        assert_eq2!(state.get_item(5), None);
+    }
+
+    #[test]
+    fn test_get_item_text_viewport_width() {
+        let mut state = State {
+            max_display_width: ch!(30),
+            window_size: Some(Size {
+                col_count: ch!(80),
+                row_count: ch!(24),
+            }),
+            items: vec!["short".to_string(), "a much longer item".to_string()],
+            ..Default::default()
+        };
+
+        // " " + " ◉ " is 4 columns wide.
+        assert_eq2!(state.get_viewport_width(), ch!(30));
+        assert_eq2!(state.get_item_text_viewport_width(), ch!(26));
+        assert_eq2!(state.get_max_item_display_width(), ch!(18));
+
+        // The icon (2 columns) is followed by a space.
+        state.item_icons = vec![Some(ItemIcon::new("🦀"))];
+        assert_eq2!(state.get_item_text_viewport_width(), ch!(23));
+
+        // The terminal is narrower than the max display width.
+        state.window_size = Some(Size {
+            col_count: ch!(20),
+            row_count: ch!(24),
+        });
+        assert_eq2!(state.get_viewport_width(), ch!(20));
+
+        // Section headers are not scrolled, so they are not measured.
+        state.section_header_indices = vec![1];
+        assert_eq2!(state.get_max_item_display_width(), ch!(5));
+    }
+
+    #[test]
+    fn test_get_item_icon_text() {
+        let mut state = State {
+            items: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            item_icons: vec![Some(ItemIcon::new("🦀")), None, Some(ItemIcon::new("*"))],
+            ..Default::default()
+        };
+
+        // The emoji is 2 columns wide, so the other items are padded to match.
+        assert_eq2!(state.get_item_icon_text(0), "🦀 ");
+        assert_eq2!(state.get_item_icon_text(1), "   ");
+        assert_eq2!(state.get_item_icon_text(2), "*  ");
+
+        state.icon_alignment = IconAlignment::Compact;
+        assert_eq2!(state.get_item_icon_text(0), "🦀 ");
+        assert_eq2!(state.get_item_icon_text(1), "");
+        assert_eq2!(state.get_item_icon_text(2), "* ");
+
+        // No icons at all.
+        state.item_icons = vec![None];
+        state.icon_alignment = IconAlignment::ReserveSpace;
+        assert_eq2!(state.get_item_icon_text(0), "");
+    }
 }
 
 impl CalculateResizeHint for State<'_> {
@@ -128,7 +485,9 @@ pub enum ResizeHint {
 }
 
 impl State<'_> {
-    /// This the row index that currently has keyboard focus.
+    /// This the row index that currently has keyboard focus. If the list is filtered,
+    /// then use [get_focused_item_index](State::get_focused_item_index) to get the index
+    /// of its item.
     pub fn get_focused_index(&self) -> ChUnit {
         get_scroll_adjusted_row_index(
             self.raw_caret_row_index,
@@ -136,12 +495,177 @@ impl State<'_> {
         )
     }
 
+    pub fn is_section_header(&self, index: usize) -> bool {
+        self.section_header_indices.contains(&index)
+    }
+
+    /// Returns the item at the given index, or `None` if it is a section header (or
+    /// out of bounds), since those can't be selected.
+    pub fn get_item(&self, index: usize) -> Option<&String> {
+        match self.is_section_header(index) {
+            true => None,
+            false => self.items.get(index),
+        }
+    }
+
+    pub fn is_item_selected(&self, index: usize) -> bool {
+        self.selected_indices.contains(&index)
+    }
+
+    /// Select the item at the given index (in [items](State::items)), or unselect it if
+    /// it is already selected. Section headers can't be selected.
+    pub fn toggle_item_selection(&mut self, index: usize) {
+        let Some(item) = self.get_item(index).cloned() else {
+            return;
+        };
+        match self.selected_indices.iter().position(|&it| it == index) {
+            Some(position) => {
+                self.selected_indices.remove(position);
+                self.selected_items.remove(position);
+            }
+            None => {
+                self.selected_indices.push(index);
+                self.selected_items.push(item);
+            }
+        }
+    }
+
+    /// Returns the indices (in [items](State::items)) of the items that the user chose,
+    /// which depends on the [selection_mode](State::selection_mode):
+    /// - [SelectionMode::Single]: the focused item.
+    /// - [SelectionMode::Multiple]: the [selected_indices](State::selected_indices), in
+    ///   the order that they were selected.
+    pub fn get_selected_indices(&self) -> Vec<usize> {
+        match self.selection_mode {
+            SelectionMode::Single => self.get_focused_item_index().into_iter().collect(),
+            SelectionMode::Multiple => self.selected_indices.clone(),
+        }
+    }
+
+    /// If the focused row is a section header (eg: the first row of a list w/
+    /// sections), then move the focus down to the first item after it.
+    pub fn focus_first_item(&mut self) {
+        let focused_index = ch!(@to_usize self.get_focused_index());
+        if !self.is_section_header_row(focused_index) {
+            return;
+        }
+        let maybe_target_index = (focused_index + 1..self.get_row_count())
+            .find(|&index| !self.is_section_header_row(index));
+        if let Some(target_index) = maybe_target_index {
+            // Scroll if the item is below the viewport, so that it is in the last row.
+            let target_index = ch!(target_index);
+            if target_index >= self.scroll_offset_row_index + self.max_display_height {
+                self.scroll_offset_row_index =
+                    target_index + ch!(1) - self.max_display_height;
+            }
+            self.raw_caret_row_index = target_index - self.scroll_offset_row_index;
+        }
+    }
+
+    pub fn get_item_icon(&self, index: usize) -> Option<&ItemIcon> {
+        self.item_icons.get(index).and_then(|it| it.as_ref())
+    }
+
+    /// Returns the display width of the widest icon, or `None` if there are no icons.
+    fn get_icon_col_width(&self) -> Option<usize> {
+        self.item_icons
+            .iter()
+            .flatten()
+            .map(|it| ch!(@to_usize UnicodeString::from(&it.glyph).display_width))
+            .max()
+    }
+
+    /// Returns the text that is painted in the icon column for the item at the given
+    /// index (including the trailing space), which is empty if there are no icons at all.
+    pub fn get_item_icon_text(&self, index: usize) -> String {
+        let Some(icon_col_width) = self.get_icon_col_width() else {
+            return "".to_string();
+        };
+
+        match (self.get_item_icon(index), self.icon_alignment) {
+            (Some(icon), _) => {
+                let icon_width =
+                    ch!(@to_usize UnicodeString::from(&icon.glyph).display_width);
+                let padding = match self.icon_alignment {
+                    IconAlignment::ReserveSpace => icon_col_width - icon_width,
+                    IconAlignment::Compact => 0,
+                };
+                format!("{}{} ", icon.glyph, " ".repeat(padding))
+            }
+            (None, IconAlignment::ReserveSpace) => " ".repeat(icon_col_width + 1),
+            (None, IconAlignment::Compact) => "".to_string(),
+        }
+    }
+
+    /// The number of columns that the component is painted on, which is the width of the
+    /// terminal, unless [max_display_width](State::max_display_width) is set and smaller.
+    pub fn get_viewport_width(&self) -> ChUnit {
+        // Try to get the terminal width from state first (since it should be set when
+        // resize events occur). If that is not set, then get the terminal width directly.
+        let terminal_width = match self.window_size {
+            Some(size) => size.col_count,
+            None => ch!(get_terminal_width()),
+        };
+
+        // Do not exceed the max display width (if it is set).
+        if self.max_display_width == ch!(0) || self.max_display_width > terminal_width {
+            terminal_width
+        } else {
+            self.max_display_width
+        }
+    }
+
+    /// The number of columns that are left to paint the text of an item, after the row
+    /// prefix and the (widest) icon.
+    pub fn get_item_text_viewport_width(&self) -> ChUnit {
+        let row_prefix_display_width =
+            UnicodeString::from(get_row_prefix(self.selection_mode, false, false))
+                .display_width;
+        let icon_text_display_width = match self.get_icon_col_width() {
+            // The icon is followed by a space.
+            Some(icon_col_width) => ch!(icon_col_width + 1),
+            None => ch!(0),
+        };
+        self.get_viewport_width() - row_prefix_display_width - icon_text_display_width
+    }
+
+    /// The display width of the longest item (section headers are not included). This is
+    /// measured from the text of the items, not the spans of an
+    /// [ItemRenderer](crate::ItemRenderer).
+    pub fn get_max_item_display_width(&self) -> ChUnit {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.is_section_header(*index))
+            .map(|(_, item)| UnicodeString::from(item).display_width)
+            .max()
+            .unwrap_or(ch!(0))
+    }
+
+    /// Scroll the list so that the viewport shows the item at `ratio` (from `0.0` to
+    /// `1.0`) of the items. See [get_scroll_offset_for_ratio] for how the `ratio` is
+    /// clamped. The caret stays on the same row of the viewport, so the focused item
+    /// changes, but the selected items don't.
+    pub fn scroll_to_ratio(&mut self, ratio: f64) {
+        let items_len = ch!(self.get_row_count());
+        if items_len == ch!(0) {
+            return;
+        }
+
+        self.scroll_offset_row_index =
+            get_scroll_offset_for_ratio(ratio, items_len, self.max_display_height);
+
+        let max_raw_caret_row_index = items_len - ch!(1) - self.scroll_offset_row_index;
+        self.raw_caret_row_index =
+            std::cmp::min(self.raw_caret_row_index, max_raw_caret_row_index);
+    }
+
     pub fn locate_cursor_in_viewport(&self) -> CaretVerticalViewportLocation {
         locate_cursor_in_viewport(
             self.raw_caret_row_index,
             self.scroll_offset_row_index,
             self.max_display_height,
-            self.items.len().into(),
+            self.get_row_count().into(),
         )
     }
 }